@@ -2,7 +2,9 @@
 pub mod ticks;
 pub mod time;
 
+use exchange::Trade;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
 /// ساختار نگهدارنده تعداد تیک‌ها برای تجمیع داده‌ها
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -31,3 +33,54 @@ impl std::fmt::Display for TickCount {
         write!(f, "{}T", self.0)
     }
 }
+
+/// ادغام معاملات پیاپی هم‌قیمت و هم‌جهت در یک بازه زمانی کوتاه برای نمایش؛
+/// معاملات خام بدون تغییر باقی می‌مانند و فقط خروجی نمایشی تجمیع می‌شود
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrintAggregation {
+    pub enabled: bool,  // فعال بودن تجمیع
+    pub window_ms: u64, // بیشترین فاصله زمانی از اولین معامله گروه (میلی‌ثانیه)
+}
+
+impl PrintAggregation {
+    pub const WINDOW_RANGE: std::ops::RangeInclusive<f32> = 1.0..=1000.0;
+
+    /// تجمیع معاملات بافر؛ مقدار معاملات گروه جمع می‌شود و زمان آخرین معامله حفظ می‌شود
+    pub fn apply<'a>(&self, trades: &'a [Trade]) -> Cow<'a, [Trade]> {
+        if !self.enabled || trades.len() < 2 {
+            return Cow::Borrowed(trades);
+        }
+
+        let mut prints: Vec<Trade> = Vec::with_capacity(trades.len());
+        let mut group_start = 0;
+
+        for trade in trades {
+            match prints.last_mut() {
+                Some(last)
+                    if last.price == trade.price
+                        && last.is_sell == trade.is_sell
+                        && trade.time.saturating_sub(group_start) <= self.window_ms =>
+                {
+                    last.qty += trade.qty;
+                    last.time = last.time.max(trade.time);
+                }
+                _ => {
+                    group_start = trade.time;
+                    prints.push(*trade);
+                }
+            }
+        }
+
+        Cow::Owned(prints)
+    }
+}
+
+impl Default for PrintAggregation {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window_ms: 50,
+        }
+    }
+}
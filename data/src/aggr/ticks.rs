@@ -29,6 +29,7 @@ impl TickAccumulation {
                 if trade.is_sell { 0.0 } else { trade.qty },
                 if trade.is_sell { trade.qty } else { 0.0 },
             ),
+            trade_count: None,
         };
 
         Self {
@@ -133,6 +134,15 @@ impl TickAggr {
         self.into()
     }
 
+    /// تعداد معاملات هر بازه تیکی (کلید: ایندکس بازه)
+    pub fn trade_count_data(&self) -> BTreeMap<u64, u64> {
+        self.datapoints
+            .iter()
+            .enumerate()
+            .map(|(idx, dp)| (idx as u64, dp.tick_count as u64))
+            .collect()
+    }
+
     /// وارد کردن معاملات جدید به تجمیع‌کننده
     pub fn insert_trades(&mut self, buffer: &[Trade]) {
         let mut updated_indices = Vec::new();
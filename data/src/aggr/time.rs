@@ -1,7 +1,7 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, VecDeque};
 
 use crate::chart::Basis;
-use crate::chart::heatmap::HeatmapDataPoint;
+use crate::chart::heatmap::{GroupedTrade, HeatmapDataPoint};
 use crate::chart::kline::{ClusterKind, KlineDataPoint, KlineTrades, NPoc};
 
 use exchange::util::{Price, PriceStep};
@@ -171,6 +171,41 @@ impl<D: DataPoint> TimeSeries<D> {
 
         None
     }
+
+    /// شکاف‌های زمانی بین کندل‌های متوالی که بیش از یک بازه فاصله دارند (توقف معاملات، قطعی صرافی)
+    ///
+    /// هر شکاف به صورت (زمان اولین کندل مفقود، زمان آخرین کندل مفقود) و فقط در صورت همپوشانی با محدوده برگردانده می‌شود
+    pub fn kline_gaps(&self, earliest: u64, latest: u64) -> Vec<(u64, u64)> {
+        let interval = self.interval.to_milliseconds();
+
+        // آخرین کندل پیش از محدوده هم لازم است تا شکافی که از لبه محدوده عبور می‌کند دیده شود
+        let start = self
+            .datapoints
+            .range(..earliest)
+            .next_back()
+            .map_or(earliest, |(&time, _)| time);
+
+        let mut gaps = vec![];
+        let mut prev: Option<u64> = None;
+
+        for &time in self.datapoints.range(start..).map(|(time, _)| time) {
+            if let Some(prev) = prev
+                && time - prev > interval
+            {
+                let (first_missing, last_missing) = (prev + interval, time - interval);
+                if first_missing <= latest && last_missing >= earliest {
+                    gaps.push((first_missing, last_missing));
+                }
+            }
+
+            if time > latest {
+                break;
+            }
+            prev = Some(time);
+        }
+
+        gaps
+    }
 }
 
 impl TimeSeries<KlineDataPoint> {
@@ -186,6 +221,108 @@ impl TimeSeries<KlineDataPoint> {
         timeseries
     }
 
+    /// ساخت کندل‌های فوت‌پرینت با بازه `interval` تنها از روی معاملات؛
+    /// OHLCV هر کندل از معاملات همان بازه به دست می‌آید
+    pub fn from_trades(interval: Timeframe, tick_size: PriceStep, trades: &[Trade]) -> Self {
+        let mut timeseries = Self::new(interval, tick_size, &[]);
+        let aggr_time = interval.to_milliseconds();
+
+        for trade in trades {
+            let time = (trade.time / aggr_time) * aggr_time;
+
+            let entry = timeseries
+                .datapoints
+                .entry(time)
+                .or_insert_with(|| KlineDataPoint {
+                    kline: Kline {
+                        time,
+                        open: trade.price,
+                        high: trade.price,
+                        low: trade.price,
+                        close: trade.price,
+                        volume: (0.0, 0.0),
+                        trade_count: None,
+                    },
+                    footprint: KlineTrades::new(),
+                });
+
+            let kline = &mut entry.kline;
+            kline.high = kline.high.max(trade.price);
+            kline.low = kline.low.min(trade.price);
+            kline.close = trade.price;
+            if trade.is_sell {
+                kline.volume.1 += trade.qty;
+            } else {
+                kline.volume.0 += trade.qty;
+            }
+
+            entry.add_trade(trade, tick_size);
+        }
+
+        timeseries
+            .datapoints
+            .values_mut()
+            .for_each(KlineDataPoint::calculate_poc);
+        timeseries
+    }
+
+    /// ادغام کندل‌های فوت‌پرینت یک بازه ریزتر در کندل‌های این سری؛
+    /// کندل‌هایی که در سری نیستند از OHLCV همان کندل‌ها ساخته و برگردانده می‌شوند
+    pub fn merge_footprint_candles(&mut self, candles: TimeSeries<KlineDataPoint>) -> Vec<Kline> {
+        let aggr_time = self.interval.to_milliseconds();
+        let mut updated_times: Vec<u64> = Vec::new();
+        let mut created_times: Vec<u64> = Vec::new();
+
+        for (time, candle) in candles.datapoints {
+            let rounded_time = (time / aggr_time) * aggr_time;
+
+            if !updated_times.contains(&rounded_time) {
+                updated_times.push(rounded_time);
+            }
+
+            let Some(entry) = self.datapoints.get_mut(&rounded_time) else {
+                created_times.push(rounded_time);
+                self.datapoints.insert(
+                    rounded_time,
+                    KlineDataPoint {
+                        kline: Kline {
+                            time: rounded_time,
+                            ..candle.kline
+                        },
+                        footprint: candle.footprint,
+                    },
+                );
+                continue;
+            };
+
+            // کندل‌های دریافتی از صرافی دست نمی‌خورند و تنها فوت‌پرینتشان پر می‌شود
+            if created_times.contains(&rounded_time) {
+                let kline = &mut entry.kline;
+                kline.high = kline.high.max(candle.kline.high);
+                kline.low = kline.low.min(candle.kline.low);
+                kline.close = candle.kline.close;
+                kline.volume.0 += candle.kline.volume.0;
+                kline.volume.1 += candle.kline.volume.1;
+            }
+            entry.footprint.merge(&candle.footprint);
+        }
+
+        for time in updated_times {
+            if let Some(data_point) = self.datapoints.get_mut(&time) {
+                data_point.calculate_poc();
+            }
+        }
+
+        if !created_times.is_empty() {
+            self.update_poc_status();
+        }
+
+        created_times
+            .iter()
+            .filter_map(|time| self.datapoints.get(time).map(|dp| dp.kline))
+            .collect()
+    }
+
     pub fn with_trades(&self, trades: &[Trade]) -> TimeSeries<KlineDataPoint> {
         let mut new_series = Self {
             datapoints: self.datapoints.clone(),
@@ -197,6 +334,14 @@ impl TimeSeries<KlineDataPoint> {
         new_series
     }
 
+    /// تعداد معاملات هر کندل از زمان `earliest` به بعد؛ کندل‌های بدون اطلاعات حذف می‌شوند
+    pub fn trade_count_data(&self, earliest: u64) -> BTreeMap<u64, u64> {
+        self.datapoints
+            .range(earliest..)
+            .filter_map(|(time, dp)| dp.trade_count().map(|count| (*time, count)))
+            .collect()
+    }
+
     /// وارد کردن کندل‌های جدید به سری زمانی
     pub fn insert_klines(&mut self, klines: &[Kline]) {
         for kline in klines {
@@ -240,6 +385,7 @@ impl TimeSeries<KlineDataPoint> {
                         low: trade.price,
                         close: trade.price,
                         volume: (0.0, 0.0),
+                        trade_count: None,
                     },
                     footprint: KlineTrades::new(),
                 });
@@ -414,7 +560,14 @@ impl TimeSeries<HeatmapDataPoint> {
     }
 
     /// محاسبه حداکثر حجم معامله و حجم تجمیعی در یک بازه زمانی
-    pub fn max_trade_qty_and_aggr_volume(&self, earliest: u64, latest: u64) -> (f32, f32) {
+    ///
+    /// حداکثر حجم معامله فقط از میان معاملاتی که `is_visible` آن‌ها را می‌پذیرد محاسبه می‌شود.
+    pub fn max_trade_qty_and_aggr_volume(
+        &self,
+        earliest: u64,
+        latest: u64,
+        is_visible: impl Fn(&GroupedTrade) -> bool,
+    ) -> (f32, f32) {
         let mut max_trade_qty = 0.0f32;
         let mut max_aggr_volume = 0.0f32;
 
@@ -424,7 +577,9 @@ impl TimeSeries<HeatmapDataPoint> {
                 let (mut buy_volume, mut sell_volume) = (0.0, 0.0);
 
                 dp.grouped_trades.iter().for_each(|trade| {
-                    max_trade_qty = max_trade_qty.max(trade.qty);
+                    if is_visible(trade) {
+                        max_trade_qty = max_trade_qty.max(trade.qty);
+                    }
 
                     if trade.is_sell {
                         sell_volume += trade.qty;
@@ -438,6 +593,56 @@ impl TimeSeries<HeatmapDataPoint> {
 
         (max_trade_qty, max_aggr_volume)
     }
+
+    /// بیشینه متحرک حجم یک خانه قیمتی برای هر ستون بین `earliest` و `latest`؛ پنجره شامل
+    /// همان ستون و `window - 1` ستون پیش از آن است، حتی اگر خارج از محدوده دید باشند،
+    /// تا مقیاس نوارها با جابه‌جایی نمودار تغییر نکند و پس از یک جهش حجم دوباره بازگردد
+    pub fn rolling_max_cell_volume(
+        &self,
+        earliest: u64,
+        latest: u64,
+        window: usize,
+    ) -> BTreeMap<u64, f32> {
+        let window = window.max(1);
+
+        let mut leading = self
+            .datapoints
+            .range(..earliest)
+            .rev()
+            .take(window - 1)
+            .collect::<Vec<_>>();
+        leading.reverse();
+
+        let mut recent = VecDeque::<(usize, f32)>::with_capacity(window);
+        let mut maxima = BTreeMap::new();
+
+        for (i, (time, dp)) in leading
+            .into_iter()
+            .chain(self.datapoints.range(earliest..=latest))
+            .enumerate()
+        {
+            let column_max = dp
+                .cell_volumes()
+                .values()
+                .map(|(buy, sell)| buy + sell)
+                .fold(0.0f32, f32::max);
+
+            // صف نزولی از بیشینه‌ها؛ مقادیر کوچک‌تر پیش از مقدار جدید دیگر بیشینه نمی‌شوند
+            while recent.back().is_some_and(|(_, qty)| *qty <= column_max) {
+                recent.pop_back();
+            }
+            recent.push_back((i, column_max));
+            while recent.front().is_some_and(|(start, _)| start + window <= i) {
+                recent.pop_front();
+            }
+
+            if *time >= earliest {
+                maxima.insert(*time, recent.front().map_or(0.0, |(_, qty)| *qty));
+            }
+        }
+
+        maxima
+    }
 }
 
 impl From<&TimeSeries<KlineDataPoint>> for BTreeMap<u64, (f32, f32)> {
@@ -450,3 +655,120 @@ impl From<&TimeSeries<KlineDataPoint>> for BTreeMap<u64, (f32, f32)> {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kline_at(time: u64) -> Kline {
+        Kline {
+            time,
+            open: Price::from_f32(1.0),
+            high: Price::from_f32(1.0),
+            low: Price::from_f32(1.0),
+            close: Price::from_f32(1.0),
+            volume: (0.0, 0.0),
+            trade_count: None,
+        }
+    }
+
+    #[test]
+    fn detects_gaps_overlapping_range() {
+        let minute = Timeframe::M1.to_milliseconds();
+        let klines: Vec<Kline> = [0, 1, 4, 5, 6, 9]
+            .into_iter()
+            .map(|i| kline_at(i * minute))
+            .collect();
+        let series =
+            TimeSeries::<KlineDataPoint>::new(Timeframe::M1, PriceStep::from_f32(0.1), &klines);
+
+        assert_eq!(
+            series.kline_gaps(0, 9 * minute),
+            vec![(2 * minute, 3 * minute), (7 * minute, 8 * minute)]
+        );
+        // شکافی که از لبه‌های محدوده عبور می‌کند
+        assert_eq!(
+            series.kline_gaps(3 * minute, 7 * minute),
+            vec![(2 * minute, 3 * minute), (7 * minute, 8 * minute)]
+        );
+        assert!(series.kline_gaps(4 * minute, 6 * minute).is_empty());
+    }
+
+    #[test]
+    fn merges_finer_footprint_candles_into_chart_candles() {
+        let minute = Timeframe::M1.to_milliseconds();
+        let trade = |time: u64, price: f32, is_sell: bool| Trade {
+            time,
+            is_sell,
+            price: Price::from_f32(price),
+            qty: 1.0,
+        };
+        let step = PriceStep::from_f32(1.0);
+        let mut series = TimeSeries::<KlineDataPoint>::new(Timeframe::M5, step, &[kline_at(0)]);
+
+        let trades = [
+            trade(0, 1.0, false),
+            trade(3 * minute, 1.0, true),
+            trade(5 * minute, 4.0, false),
+            trade(7 * minute, 2.0, true),
+            trade(9 * minute, 3.0, false),
+        ];
+        let candles = TimeSeries::from_trades(Timeframe::M1, step, &trades);
+        assert_eq!(candles.datapoints.len(), 5);
+
+        let created = series.merge_footprint_candles(candles);
+
+        // کندل موجود فقط فوت‌پرینت می‌گیرد
+        let existing = &series.datapoints[&0];
+        assert_eq!(existing.kline.volume, (0.0, 0.0));
+        assert_eq!(existing.footprint.trade_count(), 2);
+
+        // کندل گمشده از روی کندل‌های یک دقیقه‌ای ساخته می‌شود
+        assert_eq!(created.len(), 1);
+        let rebuilt = created[0];
+        assert_eq!(rebuilt.time, 5 * minute);
+        assert_eq!(rebuilt.open, Price::from_f32(4.0));
+        assert_eq!(rebuilt.high, Price::from_f32(4.0));
+        assert_eq!(rebuilt.low, Price::from_f32(2.0));
+        assert_eq!(rebuilt.close, Price::from_f32(3.0));
+        assert_eq!(rebuilt.volume, (2.0, 1.0));
+        assert_eq!(series.datapoints[&(5 * minute)].footprint.trades.len(), 3);
+    }
+
+    #[test]
+    fn rolling_cell_volume_max_looks_back_past_the_visible_range() {
+        let trade = |price: f32, is_sell: bool, qty: f32| GroupedTrade {
+            is_sell,
+            price: Price::from_f32(price),
+            qty,
+        };
+        let mut series = TimeSeries::<HeatmapDataPoint>::new(
+            Basis::Time(Timeframe::MS100),
+            PriceStep::from_f32(1.0),
+        );
+
+        // حجم هر ستون: ۸ (دو سمت در یک قیمت)، ۳، ۱، ۲، ۱
+        let columns = [
+            vec![trade(10.0, false, 5.0), trade(10.0, true, 3.0)],
+            vec![trade(11.0, false, 3.0)],
+            vec![trade(12.0, true, 1.0)],
+            vec![trade(11.0, false, 2.0)],
+            vec![trade(10.0, true, 1.0)],
+        ];
+        for (i, trades) in columns.into_iter().enumerate() {
+            series.datapoints.insert(
+                i as u64 * 100,
+                HeatmapDataPoint {
+                    grouped_trades: trades.into_boxed_slice(),
+                    buy_sell: (0.0, 0.0),
+                },
+            );
+        }
+
+        let maxima = series.rolling_max_cell_volume(200, 400, 3);
+        assert_eq!(
+            maxima.into_iter().collect::<Vec<_>>(),
+            vec![(200, 8.0), (300, 3.0), (400, 2.0)]
+        );
+    }
+}
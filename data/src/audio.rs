@@ -1,9 +1,11 @@
 use crate::util::ok_or_default;
-use exchange::SerTicker;
+use exchange::{SerTicker, Trade};
 
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
 
+use std::collections::VecDeque;
+
 /// آستانه (Threshold) برای پخش صدا
 #[derive(Debug, Clone, Copy, Deserialize, Serialize)]
 pub enum Threshold {
@@ -36,6 +38,235 @@ impl Default for StreamCfg {
     }
 }
 
+/// نمونه‌های صوتی داخلی برنامه
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Sample {
+    TypewriterClick, // کلیک ماشین تحریر
+    DryPopUp,        // پاپ خشک
+    TypewriterHit,   // ضربه ماشین تحریر
+    FoamSplash,      // برخورد با فوم
+}
+
+impl Sample {
+    pub const ALL: [Sample; 4] = [
+        Sample::TypewriterClick,
+        Sample::DryPopUp,
+        Sample::TypewriterHit,
+        Sample::FoamSplash,
+    ];
+}
+
+impl std::fmt::Display for Sample {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Sample::TypewriterClick => "Typewriter click",
+            Sample::DryPopUp => "Dry pop",
+            Sample::TypewriterHit => "Typewriter hit",
+            Sample::FoamSplash => "Foam splash",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// یک نمونه صوتی به همراه میزان صدای اختصاصی آن (درصد)
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct SampleCfg {
+    pub sample: Sample, // نمونه صوتی
+    pub volume: f32,    // میزان صدا نسبت به صدای کلی (۰ تا ۱۰۰)
+}
+
+impl SampleCfg {
+    const fn new(sample: Sample) -> Self {
+        Self {
+            sample,
+            volume: 100.0,
+        }
+    }
+}
+
+/// دسته‌بندی اندازه معامله برای انتخاب صدا
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeBucket {
+    Small,  // کوچک
+    Medium, // متوسط
+    Large,  // بزرگ
+}
+
+impl SizeBucket {
+    pub const ALL: [SizeBucket; 3] = [SizeBucket::Small, SizeBucket::Medium, SizeBucket::Large];
+}
+
+impl std::fmt::Display for SizeBucket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            SizeBucket::Small => "Small",
+            SizeBucket::Medium => "Medium",
+            SizeBucket::Large => "Large",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// تم صوتی: نگاشت سمت معامله و دسته اندازه به نمونه‌های صوتی
+///
+/// آستانه‌ها بر حسب واحد نمایش حجم فعال (پایه یا کوت) هستند.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SoundTheme {
+    pub enabled: bool,        // استفاده از تم به جای صداهای پیش‌فرض
+    pub medium_size: f32,     // حداقل اندازه برای دسته متوسط
+    pub large_size: f32,      // حداقل اندازه برای دسته بزرگ
+    pub min_interval_ms: u64, // حداقل فاصله زمانی بین دو صدا برای هر نماد
+    pub buy: [SampleCfg; 3],  // صداهای خرید به ترتیب کوچک، متوسط، بزرگ
+    pub sell: [SampleCfg; 3], // صداهای فروش به ترتیب کوچک، متوسط، بزرگ
+}
+
+impl Default for SoundTheme {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            medium_size: 10_000.0,
+            large_size: 100_000.0,
+            min_interval_ms: 100,
+            buy: [
+                SampleCfg::new(Sample::TypewriterClick),
+                SampleCfg::new(Sample::TypewriterClick),
+                SampleCfg::new(Sample::DryPopUp),
+            ],
+            sell: [
+                SampleCfg::new(Sample::TypewriterHit),
+                SampleCfg::new(Sample::TypewriterHit),
+                SampleCfg::new(Sample::FoamSplash),
+            ],
+        }
+    }
+}
+
+impl SoundTheme {
+    pub fn bucket(&self, size: f32) -> SizeBucket {
+        if size >= self.large_size {
+            SizeBucket::Large
+        } else if size >= self.medium_size {
+            SizeBucket::Medium
+        } else {
+            SizeBucket::Small
+        }
+    }
+
+    pub fn sample(&self, is_sell: bool, bucket: SizeBucket) -> SampleCfg {
+        let samples = if is_sell { &self.sell } else { &self.buy };
+        samples[bucket as usize]
+    }
+
+    pub fn with_sample(mut self, is_sell: bool, bucket: SizeBucket, cfg: SampleCfg) -> Self {
+        let samples = if is_sell {
+            &mut self.sell
+        } else {
+            &mut self.buy
+        };
+        samples[bucket as usize] = cfg;
+        self
+    }
+}
+
+/// تنظیمات هشدار صوتی فشار خرید یا فروش (عدم تعادل حجم در N معامله آخر)
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ImbalanceCfg {
+    pub enabled: bool,   // فعال بودن هشدار عدم تعادل
+    pub window: usize,   // تعداد معاملات پنجره غلتان
+    pub threshold: f32,  // درصد حجم یک سمت برای پخش صدا (۵۰ تا ۱۰۰)
+    pub buy: SampleCfg,  // صدای فشار خرید
+    pub sell: SampleCfg, // صدای فشار فروش
+}
+
+impl Default for ImbalanceCfg {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window: 50,
+            threshold: 80.0,
+            buy: SampleCfg::new(Sample::DryPopUp),
+            sell: SampleCfg::new(Sample::FoamSplash),
+        }
+    }
+}
+
+impl ImbalanceCfg {
+    pub const WINDOW_RANGE: std::ops::RangeInclusive<f32> = 10.0..=500.0;
+    pub const THRESHOLD_RANGE: std::ops::RangeInclusive<f32> = 55.0..=100.0;
+
+    pub fn sample(&self, pressure: Pressure) -> SampleCfg {
+        match pressure {
+            Pressure::Buy => self.buy,
+            Pressure::Sell => self.sell,
+        }
+    }
+}
+
+/// سمت غالب جریان سفارش
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pressure {
+    Buy,  // خریداران غالب هستند
+    Sell, // فروشندگان غالب هستند
+}
+
+/// پنجره غلتان آخرین معاملات یک استریم برای سنجش عدم تعادل خرید و فروش
+///
+/// هر سمت تنها یک بار هنگام عبور از آستانه اعلام می‌شود تا نسبت دوباره زیر آستانه برگردد.
+#[derive(Debug, Default, Clone)]
+pub struct TradeImbalance {
+    trades: VecDeque<(bool, f32)>, // (فروش است؟، حجم)
+    active: Option<Pressure>,
+}
+
+impl TradeImbalance {
+    pub fn push(&mut self, cfg: &ImbalanceCfg, trades: &[Trade]) -> Option<Pressure> {
+        let window = cfg.window.max(1);
+
+        self.trades
+            .extend(trades.iter().map(|trade| (trade.is_sell, trade.qty)));
+        while self.trades.len() > window {
+            self.trades.pop_front();
+        }
+
+        // تا پر شدن پنجره، چند معامله اول نسبت گمراه‌کننده‌ای می‌دهند
+        if self.trades.len() < window {
+            return None;
+        }
+
+        let buy_ratio = self.buy_ratio()? * 100.0;
+        let pressure = if buy_ratio >= cfg.threshold {
+            Some(Pressure::Buy)
+        } else if 100.0 - buy_ratio >= cfg.threshold {
+            Some(Pressure::Sell)
+        } else {
+            None
+        };
+
+        let event = pressure.filter(|side| self.active != Some(*side));
+        self.active = pressure;
+        event
+    }
+
+    /// سهم حجم خرید از کل حجم پنجره (۰ تا ۱)
+    pub fn buy_ratio(&self) -> Option<f32> {
+        let (buy, sell) = self
+            .trades
+            .iter()
+            .fold((0.0, 0.0), |(buy, sell), &(is_sell, qty)| {
+                if is_sell {
+                    (buy, sell + qty)
+                } else {
+                    (buy + qty, sell)
+                }
+            });
+
+        let total = buy + sell;
+        (total > 0.0).then(|| buy / total)
+    }
+}
+
 /// ساختار کلی تنظیمات صوتی برنامه
 #[derive(Default, Clone, Deserialize, Serialize)]
 #[serde(default)]
@@ -46,4 +277,72 @@ pub struct AudioStream {
     // میزان صدای کلی برنامه
     #[serde(deserialize_with = "ok_or_default")]
     pub volume: Option<f32>,
+    // تم صوتی برای انتخاب صدا بر اساس سمت و اندازه معامله
+    #[serde(deserialize_with = "ok_or_default")]
+    pub theme: SoundTheme,
+    // حداقل فاصله زمانی بین دو صدا برای هر استریم (میلی‌ثانیه، صفر یعنی بدون محدودیت)
+    pub cooldown_ms: u64,
+    // بی‌صدا شدن هنگام خارج شدن پنجره برنامه از فوکوس
+    pub mute_on_focus_loss: bool,
+    // بی‌صدا کردن کلی هشدارها بدون از دست دادن سطح صدا
+    pub muted: bool,
+    // هشدار صوتی فشار خرید یا فروش در معاملات اخیر
+    #[serde(deserialize_with = "ok_or_default")]
+    pub imbalance: ImbalanceCfg,
+}
+
+impl AudioStream {
+    pub const MAX_COOLDOWN_MS: u64 = 5_000;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exchange::util::Price;
+
+    fn trade(is_sell: bool, qty: f32) -> Trade {
+        Trade {
+            time: 0,
+            is_sell,
+            price: Price::from_f32(100.0),
+            qty,
+        }
+    }
+
+    #[test]
+    fn imbalance_fires_once_per_crossing() {
+        let cfg = ImbalanceCfg {
+            enabled: true,
+            window: 4,
+            threshold: 80.0,
+            ..ImbalanceCfg::default()
+        };
+        let mut imbalance = TradeImbalance::default();
+
+        // پنجره هنوز پر نشده است
+        assert_eq!(imbalance.push(&cfg, &[trade(false, 5.0)]), None);
+        assert_eq!(
+            imbalance.push(
+                &cfg,
+                &[trade(false, 3.0), trade(true, 1.0), trade(false, 1.0)]
+            ),
+            Some(Pressure::Buy)
+        );
+        assert_eq!(imbalance.push(&cfg, &[trade(false, 2.0)]), None);
+
+        // غلبه فروش بعد از خروج خریدها از پنجره
+        assert_eq!(
+            imbalance.push(&cfg, &[trade(true, 10.0), trade(true, 10.0)]),
+            Some(Pressure::Sell)
+        );
+        assert_eq!(imbalance.push(&cfg, &[trade(true, 10.0)]), None);
+
+        // بازگشت به تعادل، هشدار را دوباره مسلح می‌کند
+        assert_eq!(imbalance.push(&cfg, &[trade(false, 30.0)]), None);
+        assert_eq!(
+            imbalance.push(&cfg, &[trade(true, 200.0)]),
+            Some(Pressure::Sell)
+        );
+        assert!((imbalance.buy_ratio().unwrap() - 0.12).abs() < 1e-6);
+    }
 }
@@ -0,0 +1,161 @@
+//! فاصله بهترین خرید و فروش دفتر سفارش و تاریخچه کوتاه آن؛ باز شدن اسپرد نشانه کاهش نقدینگی است
+
+use exchange::depth::Depth;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// حداکثر تعداد نمونه‌های نگهداری شده، مستقل از طول بازه
+const MAX_SAMPLES: usize = 300;
+
+/// تنظیمات نمایش اسپرد و نمودار کوچک تاریخچه آن در گوشه پنل
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct BookSpreadConfig {
+    pub enabled: bool,    // نمایش اسپرد فعلی و تاریخچه آن
+    pub window_mins: u32, // طول تاریخچه نمودار کوچک (دقیقه)
+}
+
+impl Default for BookSpreadConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window_mins: 5,
+        }
+    }
+}
+
+impl BookSpreadConfig {
+    pub const WINDOW_RANGE: std::ops::RangeInclusive<u32> = 1..=30;
+
+    pub fn window_ms(&self) -> u64 {
+        u64::from(
+            self.window_mins
+                .clamp(*Self::WINDOW_RANGE.start(), *Self::WINDOW_RANGE.end()),
+        ) * 60_000
+    }
+}
+
+/// اسپرد یک لحظه از دفتر سفارش
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BookSpread {
+    pub absolute: f32, // اختلاف بهترین فروش و بهترین خرید
+    pub bps: f32,      // اختلاف نسبت به قیمت میانی (واحد پایه)
+}
+
+impl BookSpread {
+    /// دفتر خالی، یک‌طرفه یا متقاطع (خرید بالاتر از فروش) اسپرد معتبری ندارد
+    pub fn compute(depth: &Depth) -> Option<Self> {
+        let bid = depth.best_bid()?.to_f32_lossy();
+        let ask = depth.best_ask()?.to_f32_lossy();
+        if ask < bid {
+            return None;
+        }
+
+        let mid = (bid + ask) / 2.0;
+        if mid <= 0.0 {
+            return None;
+        }
+
+        Some(Self {
+            absolute: ask - bid,
+            bps: (ask - bid) / mid * 10_000.0,
+        })
+    }
+}
+
+/// بافر چرخشی محدود از نمونه‌های اسپرد؛ نمونه‌ها با فاصله زمانی یکنواخت ثبت می‌شوند
+/// تا تعداد آن‌ها از `MAX_SAMPLES` بیشتر نشود
+#[derive(Debug, Clone, Default)]
+pub struct SpreadHistory {
+    latest: Option<BookSpread>,
+    samples: VecDeque<(u64, f32)>, // زمان و اسپرد بر حسب واحد پایه
+}
+
+impl SpreadHistory {
+    pub fn push(&mut self, time: u64, spread: Option<BookSpread>, window_ms: u64) {
+        self.latest = spread;
+
+        let min_gap = window_ms / MAX_SAMPLES as u64;
+        if let Some(spread) = spread
+            && self
+                .samples
+                .back()
+                .is_none_or(|(last, _)| time >= last + min_gap)
+        {
+            self.samples.push_back((time, spread.bps));
+        }
+
+        let cutoff = time.saturating_sub(window_ms);
+        while self
+            .samples
+            .front()
+            .is_some_and(|(t, _)| *t < cutoff || self.samples.len() > MAX_SAMPLES)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    /// اسپرد آخرین به‌روزرسانی؛ `None` وقتی دفتر خالی یا متقاطع بود
+    pub fn latest(&self) -> Option<BookSpread> {
+        self.latest
+    }
+
+    /// مقادیر تاریخچه به ترتیب زمانی، بر حسب واحد پایه
+    pub fn values(&self) -> impl Iterator<Item = f32> + '_ {
+        self.samples.iter().map(|(_, bps)| *bps)
+    }
+
+    pub fn clear(&mut self) {
+        self.latest = None;
+        self.samples.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exchange::util::Price;
+    use std::collections::BTreeMap;
+
+    fn depth(bid: Option<f32>, ask: Option<f32>) -> Depth {
+        let level = |price: Option<f32>| {
+            price
+                .map(|p| (Price::from_f32(p), 1.0))
+                .into_iter()
+                .collect::<BTreeMap<_, _>>()
+        };
+
+        Depth {
+            bids: level(bid),
+            asks: level(ask),
+        }
+    }
+
+    #[test]
+    fn spread_needs_a_two_sided_uncrossed_book() {
+        let spread = BookSpread::compute(&depth(Some(99.5), Some(100.5))).unwrap();
+        assert!((spread.absolute - 1.0).abs() < 1e-4);
+        assert!((spread.bps - 100.0).abs() < 1e-2);
+
+        assert_eq!(BookSpread::compute(&depth(Some(100.0), None)), None);
+        assert_eq!(BookSpread::compute(&depth(Some(101.0), Some(100.0))), None);
+    }
+
+    #[test]
+    fn history_is_bounded_and_rolls_off() {
+        let window_ms = 60_000;
+        let spread = BookSpread::compute(&depth(Some(99.0), Some(101.0)));
+        let mut history = SpreadHistory::default();
+
+        // به‌روزرسانی هر ۱۰ میلی‌ثانیه؛ فقط هر ۲۰۰ میلی‌ثانیه یک نمونه ثبت می‌شود
+        for t in (0..120_000).step_by(10) {
+            history.push(t, spread, window_ms);
+        }
+        assert!(history.values().count() <= MAX_SAMPLES);
+        assert!(history.samples.front().unwrap().0 >= 120_000 - 10 - window_ms);
+
+        history.push(120_000, None, window_ms);
+        assert_eq!(history.latest(), None);
+        assert!(history.values().count() > 0);
+    }
+}
@@ -3,9 +3,15 @@ pub mod comparison;
 pub mod heatmap;
 pub mod indicator;
 pub mod kline;
+pub mod session;
+pub mod swings;
+pub mod tick_preset;
 
-use exchange::Timeframe;
+use exchange::util::{Price, PriceStep};
+use exchange::{Timeframe, Trade};
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::time::Duration;
 
 use super::aggr::{
     self,
@@ -14,6 +20,57 @@ use super::aggr::{
 };
 pub use kline::KlineChartKind;
 
+static CANDLE_COUNTDOWN: AtomicBool = AtomicBool::new(true);
+
+/// نمایش زمان باقی‌مانده تا بسته شدن کندل فعلی کنار برچسب آخرین قیمت
+pub fn set_candle_countdown(enabled: bool) {
+    CANDLE_COUNTDOWN.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_candle_countdown_enabled() -> bool {
+    CANDLE_COUNTDOWN.load(Ordering::Relaxed)
+}
+
+/// اندازه و نوع قلم برچسب‌های محورها و نشانگر (Crosshair) نمودارها
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LabelFont {
+    pub size: f32,       // اندازه متن برچسب‌ها
+    pub monospace: bool, // استفاده از قلم هم‌عرض به جای قلم پیش‌فرض
+}
+
+impl LabelFont {
+    pub const SIZE_RANGE: std::ops::RangeInclusive<f32> = 9.0..=20.0;
+}
+
+impl Default for LabelFont {
+    fn default() -> Self {
+        Self {
+            size: 12.0,
+            monospace: true,
+        }
+    }
+}
+
+static LABEL_FONT_SIZE: AtomicU32 = AtomicU32::new(12.0f32.to_bits());
+static LABEL_MONOSPACE: AtomicBool = AtomicBool::new(true);
+
+pub fn set_label_font(font: LabelFont) {
+    let size = font
+        .size
+        .clamp(*LabelFont::SIZE_RANGE.start(), *LabelFont::SIZE_RANGE.end());
+
+    LABEL_FONT_SIZE.store(size.to_bits(), Ordering::Relaxed);
+    LABEL_MONOSPACE.store(font.monospace, Ordering::Relaxed);
+}
+
+pub fn label_font() -> LabelFont {
+    LabelFont {
+        size: f32::from_bits(LABEL_FONT_SIZE.load(Ordering::Relaxed)),
+        monospace: LABEL_MONOSPACE.load(Ordering::Relaxed),
+    }
+}
+
 /// انواع داده‌های قابل نمایش در نمودار
 pub enum PlotData<D: DataPoint> {
     TimeBased(TimeSeries<D>), // داده‌های مبتنی بر زمان
@@ -55,8 +112,131 @@ impl<D: DataPoint> PlotData<D> {
 pub struct ViewConfig {
     pub splits: Vec<f32>,           // تقسیم‌بندی‌های نمودار
     pub autoscale: Option<Autoscale>, // تنظیمات مقیاس‌دهی خودکار
+    #[serde(default)]
+    pub price_decimals: Option<u8>, // تعداد ارقام اعشار نمایشی قیمت؛ در صورت نبود از گام قیمت محاسبه می‌شود
+    #[serde(default)]
+    pub price_lines: PriceLines, // سبک خط آخرین قیمت و خطوط بهترین خرید/فروش
+    #[serde(default)]
+    pub price_scale: PriceScale, // مقیاس محور قیمت (خطی یا لگاریتمی)
+    #[serde(default)]
+    pub reverse_time: bool, // زمان از راست به چپ؛ جدیدترین داده در سمت چپ نمودار
+    #[serde(default)]
+    pub invert_price: bool, // محور قیمت وارونه؛ قیمت‌های بالاتر در پایین نمودار
+    #[serde(default)]
+    pub watermark: bool, // نمایش نماد و بازه به صورت کمرنگ در پس‌زمینه نمودار
+    #[serde(default)]
+    pub measured_moves: Vec<MeasuredMove>, // ابزارهای حرکت اندازه‌گیری‌شده رسم‌شده روی این پنل
+    #[serde(default)]
+    pub autoscale_padding: AutoscalePadding, // فاصله خالی بالا و پایین داده‌ها در مقیاس‌دهی خودکار
+    #[serde(default)]
+    pub price_snap: PriceSnap, // چسباندن قیمت نقاط رسم شده به سطوح رُند
+    #[serde(default)]
+    pub quick_range: Option<QuickRange>, // آخرین بازه زوم سریع انتخاب شده در این پنل
+}
+
+/// یک نقطه ثابت روی نمودار بر حسب زمان و قیمت
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct ChartAnchor {
+    pub time: u64,    // زمان شروع کندل (میلی‌ثانیه)
+    pub price: Price, // قیمت چسبانده شده به گام قیمت
+}
+
+/// ابزار حرکت اندازه‌گیری‌شده (Measured move)؛ طول یک موج اندازه‌گیری‌شده
+/// از نقطه لنگر تکرار می‌شود تا هدف قیمتی و زمانی به دست آید
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct MeasuredMove {
+    pub leg_start: ChartAnchor, // ابتدای موج اندازه‌گیری‌شده
+    pub leg_end: ChartAnchor,   // انتهای موج اندازه‌گیری‌شده
+    pub anchor: ChartAnchor,    // نقطه شروع حرکت پیش‌بینی‌شده
+}
+
+impl MeasuredMove {
+    /// هدف پیش‌بینی‌شده: نقطه لنگر به اضافه تغییر قیمت و زمان موج اندازه‌گیری‌شده
+    pub fn target(&self) -> ChartAnchor {
+        let price_delta = self.leg_end.price.units - self.leg_start.price.units;
+        let time_delta = self.leg_end.time as i64 - self.leg_start.time as i64;
+
+        ChartAnchor {
+            time: self.anchor.time.saturating_add_signed(time_delta),
+            price: Price::from_units(self.anchor.price.units + price_delta),
+        }
+    }
+}
+
+/// نوع مقیاس محور قیمت
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum PriceScale {
+    #[default]
+    Linear, // فاصله برابر برای تغییرات قیمتی برابر
+    Logarithmic, // فاصله برابر برای تغییرات درصدی برابر
 }
 
+impl PriceScale {
+    pub const ALL: [PriceScale; 2] = [PriceScale::Linear, PriceScale::Logarithmic];
+}
+
+impl std::fmt::Display for PriceScale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PriceScale::Linear => write!(f, "Linear"),
+            PriceScale::Logarithmic => write!(f, "Logarithmic"),
+        }
+    }
+}
+
+/// تنظیمات خطوط قیمت روی نمودار
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PriceLines {
+    pub dashed: bool,        // خط‌چین یا ممتد بودن خط آخرین قیمت
+    pub width: f32,          // ضخامت خطوط
+    pub show_label: bool,    // نمایش برچسب قیمت روی خطوط
+    pub bid_ask: bool,       // نمایش خطوط بهترین خرید و فروش از دفتر سفارش
+    pub anchor: PriceAnchor, // قیمت مبنای خط آخرین قیمت
+    pub microprice: bool,    // نمایش خط میانگین وزنی بهترین خرید/فروش (microprice)
+}
+
+impl PriceLines {
+    pub const WIDTH_RANGE: std::ops::RangeInclusive<f32> = 0.5..=4.0;
+}
+
+impl Default for PriceLines {
+    fn default() -> Self {
+        Self {
+            dashed: true,
+            width: 1.0,
+            show_label: false,
+            bid_ask: false,
+            anchor: PriceAnchor::default(),
+            microprice: false,
+        }
+    }
+}
+
+/// قیمت مبنای خط آخرین قیمت؛ قیمت مارک فقط برای قراردادهای دائمی متفاوت است
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum PriceAnchor {
+    #[default]
+    Last, // آخرین قیمت معامله شده
+    Mark, // قیمت مارک صرافی
+}
+
+impl PriceAnchor {
+    pub const ALL: [PriceAnchor; 2] = [PriceAnchor::Last, PriceAnchor::Mark];
+}
+
+impl std::fmt::Display for PriceAnchor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PriceAnchor::Last => write!(f, "Last price"),
+            PriceAnchor::Mark => write!(f, "Mark price"),
+        }
+    }
+}
+
+/// بیشترین تعداد ارقام اعشاری که `Price` پشتیبانی می‌کند
+pub const MAX_PRICE_DECIMALS: u8 = exchange::util::Price::PRICE_SCALE as u8;
+
 /// حالت‌های مختلف مقیاس‌دهی خودکار (Autoscale)
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq)]
 pub enum Autoscale {
@@ -65,6 +245,141 @@ pub enum Autoscale {
     FitToVisible, // برازش بر اساس داده‌های قابل مشاهده
 }
 
+/// فاصله عمودی خالی در مقیاس‌دهی خودکار، به درصد از بازه قیمت نمایشی؛
+/// در هر دو سمت بالا و پایین اعمال می‌شود تا کندل‌ها به لبه نمودار نچسبند
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct AutoscalePadding(pub f32);
+
+impl Default for AutoscalePadding {
+    fn default() -> Self {
+        Self(5.0)
+    }
+}
+
+impl AutoscalePadding {
+    pub const RANGE: std::ops::RangeInclusive<f32> = 0.0..=25.0;
+
+    /// کسری از بازه که به هر سمت اضافه می‌شود
+    pub fn fraction(self) -> f32 {
+        self.0.clamp(*Self::RANGE.start(), *Self::RANGE.end()) / 100.0
+    }
+}
+
+/// گام چسباندن قیمت نقاطی که با کلیک روی نمودار رسم می‌شوند، تا سطوحی مانند ۷۰۰۰۰
+/// به جای ۶۹۹۹۷٫۳ به دست آید
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize)]
+pub enum PriceSnap {
+    #[default]
+    Tick, // گام قیمت نمودار
+    Ticks(u16),     // مضربی از گام قیمت نمودار
+    Increment(f32), // گام قیمت ثابت
+}
+
+impl PriceSnap {
+    pub const TICK_MULTIPLES: [u16; 4] = [5, 10, 25, 100];
+
+    /// گام‌های ثابت پیشنهادی: توان‌های ده و نیمه آن‌ها، از کوچک‌ترین توان ده بزرگ‌تر از گام قیمت
+    pub fn increments(tick_size: f32) -> Vec<f32> {
+        if !tick_size.is_finite() || tick_size <= 0.0 {
+            return vec![];
+        }
+
+        let first = (tick_size * 1.0001).log10().ceil() as i32;
+        (first..first + 3)
+            .flat_map(|exponent| {
+                let base = 10f32.powi(exponent);
+                [base, base * 5.0]
+            })
+            .collect()
+    }
+
+    /// گام چسباندن برای گام قیمت نمودار؛ مقادیر نامعتبر به گام قیمت نمودار برمی‌گردند
+    pub fn step(self, tick_size: PriceStep) -> PriceStep {
+        match self {
+            PriceSnap::Tick => tick_size,
+            PriceSnap::Ticks(multiple) => PriceStep {
+                units: tick_size.units.saturating_mul(i64::from(multiple.max(1))),
+            },
+            PriceSnap::Increment(step) => {
+                let units = (f64::from(step) * 10f64.powi(Price::PRICE_SCALE)).round();
+                if units.is_finite() && units >= 1.0 {
+                    PriceStep {
+                        units: units as i64,
+                    }
+                } else {
+                    tick_size
+                }
+            }
+        }
+    }
+
+    /// نزدیک‌ترین سطح گام چسباندن به قیمت داده شده
+    pub fn snap(self, price: Price, tick_size: PriceStep) -> Price {
+        let step = self.step(tick_size).units;
+        if step <= 1 {
+            return price;
+        }
+
+        let levels = (price.units as f64 / step as f64).round() as i64;
+        Price::from_units(levels.saturating_mul(step))
+    }
+}
+
+/// بازه‌های زوم سریع؛ نمودار طوری بزرگنمایی می‌شود که این بازه تا آخرین کندل را نشان دهد
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum QuickRange {
+    H1,  // یک ساعت
+    H4,  // چهار ساعت
+    D1,  // یک روز
+    W1,  // یک هفته
+    Max, // تمام تاریخچه موجود
+}
+
+impl QuickRange {
+    pub const ALL: [QuickRange; 5] = [
+        QuickRange::H1,
+        QuickRange::H4,
+        QuickRange::D1,
+        QuickRange::W1,
+        QuickRange::Max,
+    ];
+
+    /// بازه‌هایی که کمتر از این تعداد کندل را در بر بگیرند نمایش داده نمی‌شوند
+    const MIN_BARS: u64 = 4;
+
+    /// طول بازه به میلی‌ثانیه؛ `None` برای کل تاریخچه
+    pub fn duration_ms(self) -> Option<u64> {
+        const HOUR: u64 = 60 * 60 * 1000;
+
+        match self {
+            QuickRange::H1 => Some(HOUR),
+            QuickRange::H4 => Some(4 * HOUR),
+            QuickRange::D1 => Some(24 * HOUR),
+            QuickRange::W1 => Some(7 * 24 * HOUR),
+            QuickRange::Max => None,
+        }
+    }
+
+    /// آیا این بازه برای بازه زمانی کندل‌ها معنادار است (دست کم چند کندل را نشان دهد)
+    pub fn fits_timeframe(self, timeframe_ms: u64) -> bool {
+        self.duration_ms()
+            .is_none_or(|duration| duration >= Self::MIN_BARS * timeframe_ms.max(1))
+    }
+}
+
+impl std::fmt::Display for QuickRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuickRange::H1 => write!(f, "1H"),
+            QuickRange::H4 => write!(f, "4H"),
+            QuickRange::D1 => write!(f, "1D"),
+            QuickRange::W1 => write!(f, "1W"),
+            QuickRange::Max => write!(f, "Max"),
+        }
+    }
+}
+
 /// تعیین می‌کند که داده‌های نمودار چگونه در محور افقی (X) تجمیع و نمایش داده شوند
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub enum Basis {
@@ -111,9 +426,87 @@ impl From<exchange::Timeframe> for Basis {
     }
 }
 
+/// سیاست نگهداری معاملات خام در حافظه؛ هر کدام از دو حد زودتر برسد اعمال می‌شود
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TradeRetention {
+    pub max_trades: usize, // حداکثر تعداد معاملات نگهداری شده
+    pub max_age: Duration, // حداکثر عمر معاملات نسبت به آخرین بروزرسانی
+}
+
+impl Default for TradeRetention {
+    fn default() -> Self {
+        TradeRetention {
+            max_trades: 1_000_000,
+            max_age: Duration::from_secs(24 * 60 * 60),
+        }
+    }
+}
+
+impl TradeRetention {
+    /// حذف معاملات قدیمی‌تر از حد مجاز
+    pub fn apply(&self, trades: &mut Vec<Trade>, now_ms: u64) {
+        let cutoff = now_ms.saturating_sub(self.max_age.as_millis() as u64);
+        trades.retain(|trade| trade.time >= cutoff);
+
+        if trades.len() > self.max_trades {
+            // معاملات دریافتی از تاریخچه ممکن است بعد از معاملات زنده اضافه شده باشند
+            if !trades.is_sorted_by_key(|trade| trade.time) {
+                trades.sort_by_key(|trade| trade.time);
+            }
+            let excess = trades.len() - self.max_trades;
+            trades.drain(..excess);
+        }
+    }
+}
+
+/// تخمین حافظه مصرفی تعدادی معامله خام بر حسب بایت
+pub fn trades_memory_usage(count: usize) -> usize {
+    count * std::mem::size_of::<Trade>()
+}
+
 /// انواع مطالعات یا اندیکاتورهای قابل اضافه شدن به نمودار
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub enum Study {
     Heatmap(Vec<heatmap::HeatmapStudy>),   // نقشه حرارتی (Heatmap)
     Footprint(Vec<kline::FootprintStudy>), // نمودار فوت‌پرینت (Footprint)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn price_snap_rounds_to_the_nearest_level() {
+        let tick = PriceStep::from_f32(0.1);
+        let price = Price::from_units(6_999_730_000_000); // ۶۹۹۹۷٫۳
+
+        assert_eq!(PriceSnap::Tick.snap(price, tick), price);
+        assert_eq!(
+            PriceSnap::Ticks(25).snap(price, tick),
+            Price::from_units(6_999_750_000_000)
+        );
+        assert_eq!(
+            PriceSnap::Increment(1000.0).snap(price, tick),
+            Price::from_units(7_000_000_000_000)
+        );
+        // گام نامعتبر به گام قیمت نمودار برمی‌گردد
+        assert_eq!(PriceSnap::Increment(0.0).step(tick), tick);
+
+        assert_eq!(
+            PriceSnap::increments(0.1),
+            vec![1.0, 5.0, 10.0, 50.0, 100.0, 500.0]
+        );
+    }
+
+    #[test]
+    fn quick_ranges_shorter_than_a_few_bars_are_hidden() {
+        let hour = QuickRange::H1.duration_ms().unwrap();
+
+        assert!(QuickRange::H1.fits_timeframe(hour / 60));
+        assert!(!QuickRange::H1.fits_timeframe(hour));
+        assert!(QuickRange::D1.fits_timeframe(hour));
+        assert!(!QuickRange::D1.fits_timeframe(24 * hour));
+        assert!(QuickRange::Max.fits_timeframe(24 * hour));
+    }
+}
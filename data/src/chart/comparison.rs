@@ -6,4 +6,6 @@ use serde::{Deserialize, Serialize};
 pub struct Config {
     pub colors: Vec<(SerTicker, iced_core::Color)>, // رنگ‌های اختصاص داده شده به هر نماد
     pub names: Vec<(SerTicker, String)>,           // نام‌های نمایشی برای هر نماد
+    #[serde(default)]
+    pub anchor: Option<u64>,   // زمان مبنای درصد تغییرات؛ در صورت نبود، لبه چپ نمودار
 }
@@ -1,13 +1,25 @@
 use super::Basis;
 use super::aggr::time::DataPoint;
+use crate::book_spread::BookSpreadConfig;
+use crate::depth_totals::DepthTotalsConfig;
+use crate::walls::WallConfig;
 use exchange::util::{Price, PriceStep};
-use exchange::{adapter::MarketKind, depth::Depth, volume_size_unit};
+use exchange::{TickMultiplier, Timeframe, adapter::MarketKind, depth::Depth, volume_size_unit};
 
 use rustc_hash::{FxBuildHasher, FxHashMap};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
 pub const CLEANUP_THRESHOLD: usize = 4800;
+/// بازه مجاز برای حداکثر تعداد نقاط داده نگهداری شده
+pub const MIN_SNAPSHOTS: usize = CLEANUP_THRESHOLD / 4;
+pub const MAX_SNAPSHOTS: usize = CLEANUP_THRESHOLD * 6;
+/// بازه مجاز سقف حافظه هر نقشه حرارتی (مگابایت)
+pub const MIN_MEMORY_MB: usize = 32;
+pub const MAX_MEMORY_MB: usize = 2048;
+/// بازه مجاز و مقدار پیش‌فرض تعداد سطوح قیمتی رسم شده در هر سمت قیمت میانی
+pub const RENDER_LEVELS_RANGE: std::ops::RangeInclusive<u16> = 10..=1000;
+pub const DEFAULT_RENDER_LEVELS: u16 = 100;
 
 /// Allow up to 500ms delay in order updates before starting a new order run.
 /// Prevents fragmentation(e.g. network latency) when qty and is_bid remain unchanged.
@@ -20,6 +32,28 @@ pub struct Config {
     pub order_size_filter: f32,      // فیلتر اندازه سفارشات
     pub trade_size_scale: Option<i32>, // مقیاس اندازه معاملات
     pub coalescing: Option<CoalesceKind>, // نوع تجمیع سفارشات مشابه
+    #[serde(default = "default_max_snapshots")]
+    pub max_snapshots: usize, // حداکثر تعداد نقاط داده (اسنپ‌شات‌های عمق) در حافظه
+    #[serde(default)]
+    pub time_bucket: Option<Timeframe>, // عرض زمانی هر خانه شبکه (None: برابر با بازه جریان)
+    #[serde(default)]
+    pub price_bucket: Option<TickMultiplier>, // ارتفاع قیمتی هر خانه بر حسب گام دفتر سفارش
+    #[serde(default)]
+    pub walls: WallConfig, // تشخیص و برجسته‌سازی دیوارهای عمق
+    #[serde(default = "default_show_trades")]
+    pub show_trades: bool, // نمایش معاملات تهاجمی به صورت دایره روی نقشه
+    #[serde(default)]
+    pub history_window: HistoryWindow, // بازه زمانی نگهداری اسنپ‌شات‌ها پیش از جدیدترین داده
+    #[serde(default = "default_max_memory_mb")]
+    pub max_memory_mb: usize, // سقف حافظه نقاط داده و سطوح سفارشات (مگابایت)
+    #[serde(default)]
+    pub depth_totals: DepthTotalsConfig, // مجموع خرید/فروش در بازه اطراف قیمت میانی
+    #[serde(default)]
+    pub spread_history: BookSpreadConfig, // اسپرد فعلی و نمودار کوچک تاریخچه آن در گوشه پنل
+    #[serde(default)]
+    pub cell_volume: CellVolumeConfig, // نوار حجم خرید/فروش هر خانه قیمتی در ستون‌های زمانی
+    #[serde(default)]
+    pub render_levels: Option<u16>, // حداکثر سطوح قیمتی رسم شده در هر سمت قیمت میانی (None: همه)
 }
 
 impl Default for Config {
@@ -29,23 +63,189 @@ impl Default for Config {
             order_size_filter: 0.0,
             trade_size_scale: Some(100),
             coalescing: Some(CoalesceKind::Average(0.15)),
+            max_snapshots: CLEANUP_THRESHOLD,
+            time_bucket: None,
+            price_bucket: None,
+            walls: WallConfig::default(),
+            show_trades: true,
+            history_window: HistoryWindow::default(),
+            max_memory_mb: default_max_memory_mb(),
+            depth_totals: DepthTotalsConfig::default(),
+            spread_history: BookSpreadConfig::default(),
+            cell_volume: CellVolumeConfig::default(),
+            render_levels: None,
         }
     }
 }
 
+impl Config {
+    /// سقف حافظه بر حسب بایت، محدود به بازه مجاز
+    pub fn max_memory_bytes(&self) -> usize {
+        self.max_memory_mb.clamp(MIN_MEMORY_MB, MAX_MEMORY_MB) * 1024 * 1024
+    }
+
+    /// بازه زمانی مؤثر هر خانه؛ هیچ‌گاه از بازه دریافت اسنپ‌شات‌ها ریزتر نمی‌شود
+    pub fn cell_basis(&self, stream_basis: Basis) -> Basis {
+        match (stream_basis, self.time_bucket) {
+            (Basis::Time(interval), Some(bucket)) if u64::from(bucket) > u64::from(interval) => {
+                Basis::Time(bucket)
+            }
+            _ => stream_basis,
+        }
+    }
+
+    /// گام قیمتی مؤثر هر خانه به صورت مضربی از گام دفتر سفارش
+    pub fn cell_step(&self, book_step: PriceStep) -> PriceStep {
+        match self.price_bucket {
+            Some(multiplier) if multiplier.0 > 1 => PriceStep {
+                units: book_step.units * i64::from(multiplier.0),
+            },
+            _ => book_step,
+        }
+    }
+
+    /// محدوده قیمتی که عمق در آن رسم می‌شود: محدوده قابل مشاهده، محدود به `render_levels`
+    /// خانه در هر سمت قیمت میانی؛ سطوح ذخیره شده برای تحلیل‌ها دست نمی‌خورند.
+    /// `None` اگر محدوده قابل مشاهده کاملاً بیرون از سطوح رسم شده باشد
+    pub fn render_price_range(
+        &self,
+        highest: Price,
+        lowest: Price,
+        mid_price: Price,
+        step: PriceStep,
+    ) -> Option<(Price, Price)> {
+        let (highest, lowest) = match self.render_levels {
+            Some(levels) => {
+                let levels = levels.clamp(*RENDER_LEVELS_RANGE.start(), *RENDER_LEVELS_RANGE.end());
+                let span = step.units.saturating_mul(i64::from(levels));
+
+                (
+                    highest.min(Price::from_units(mid_price.units.saturating_add(span))),
+                    lowest.max(Price::from_units(mid_price.units.saturating_sub(span))),
+                )
+            }
+            None => (highest, lowest),
+        };
+
+        (lowest <= highest).then_some((highest, lowest))
+    }
+}
+
+fn default_max_snapshots() -> usize {
+    CLEANUP_THRESHOLD
+}
+
+fn default_max_memory_mb() -> usize {
+    256
+}
+
+/// بازه زمانی تاریخچه نقشه حرارتی؛ اسنپ‌شات‌های قدیمی‌تر از آن حذف می‌شوند
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum HistoryWindow {
+    #[default]
+    Unbounded, // فقط سقف تعداد اسنپ‌شات‌ها و حافظه
+    M5,
+    M15,
+    M60,
+}
+
+impl HistoryWindow {
+    pub const ALL: [HistoryWindow; 4] = [
+        HistoryWindow::Unbounded,
+        HistoryWindow::M5,
+        HistoryWindow::M15,
+        HistoryWindow::M60,
+    ];
+
+    pub fn to_millis(self) -> Option<u64> {
+        let minutes = match self {
+            HistoryWindow::Unbounded => return None,
+            HistoryWindow::M5 => 5,
+            HistoryWindow::M15 => 15,
+            HistoryWindow::M60 => 60,
+        };
+        Some(minutes * 60 * 1000)
+    }
+}
+
+impl std::fmt::Display for HistoryWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HistoryWindow::Unbounded => write!(f, "No limit"),
+            HistoryWindow::M5 => write!(f, "5 minutes"),
+            HistoryWindow::M15 => write!(f, "15 minutes"),
+            HistoryWindow::M60 => write!(f, "1 hour"),
+        }
+    }
+}
+
+fn default_show_trades() -> bool {
+    true
+}
+
+/// تنظیمات نوارهای حجم معاملات هر خانه؛ هر ستون زمانی مانند یک پروفایل حجم کوچک رسم می‌شود
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CellVolumeConfig {
+    pub enabled: bool,        // نمایش نوارهای حجم
+    pub rolling_columns: u32, // تعداد ستون‌هایی که بیشینه متحرک حجم روی آن‌ها محاسبه می‌شود
+}
+
+impl Default for CellVolumeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rolling_columns: 60,
+        }
+    }
+}
+
+impl CellVolumeConfig {
+    pub const ROLLING_RANGE: std::ops::RangeInclusive<u32> = 5..=300;
+
+    pub fn rolling_columns(&self) -> usize {
+        self.rolling_columns
+            .clamp(*Self::ROLLING_RANGE.start(), *Self::ROLLING_RANGE.end()) as usize
+    }
+}
+
 /// ساختار نقطه داده برای نقشه حرارتی
 pub struct HeatmapDataPoint {
     pub grouped_trades: Box<[GroupedTrade]>, // معاملات گروه‌بندی شده در سطوح قیمتی
     pub buy_sell: (f32, f32),                // مجموع حجم خرید و فروش
 }
 
+impl HeatmapDataPoint {
+    /// تخمین حافظه مصرفی این نقطه داده بر حسب بایت
+    pub fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.grouped_trades.len() * std::mem::size_of::<GroupedTrade>()
+    }
+
+    /// حجم خرید و فروش هر خانه قیمتی این ستون؛ معاملات دو سمت در یک قیمت با هم جمع می‌شوند
+    pub fn cell_volumes(&self) -> BTreeMap<Price, (f32, f32)> {
+        let mut cells = BTreeMap::<Price, (f32, f32)>::new();
+
+        for trade in &self.grouped_trades {
+            let (buy, sell) = cells.entry(trade.price).or_default();
+            if trade.is_sell {
+                *sell += trade.qty;
+            } else {
+                *buy += trade.qty;
+            }
+        }
+        cells
+    }
+}
+
 impl DataPoint for HeatmapDataPoint {
     fn add_trade(&mut self, trade: &exchange::Trade, step: PriceStep) {
         let grouped_price: Price = trade.price.round_to_side_step(trade.is_sell, step);
 
+        // معاملات هم‌سمت در یک خانه قیمتی با هم جمع می‌شوند تا دایره‌ها روی هم رسم نشوند
         match self
             .grouped_trades
-            .binary_search_by(|probe| probe.compare_with(trade.price, trade.is_sell))
+            .binary_search_by(|probe| probe.compare_with(grouped_price, trade.is_sell))
         {
             Ok(index) => self.grouped_trades[index].qty += trade.qty,
             Err(index) => {
@@ -199,6 +399,11 @@ impl HistoricalDepth {
         let aggr_time = self.aggr_time;
 
         match price_level.last_mut() {
+            // اسنپ‌شات بعدی در همان خانه زمانی جایگزین مقدار قبلی می‌شود؛
+            // در خانه‌های درشت‌تر از بازه جریان، فریم‌های متوالی نباید روی هم جمع شوند
+            Some(last_run) if last_run.start_time == time => {
+                *last_run = OrderRun::new(time, aggr_time, qty, is_bid);
+            }
             Some(last_run) if last_run.is_bid == is_bid => {
                 // اگر زمان زیادی از آخرین بروزرسانی گذشته باشد، یک اجرای جدید شروع می‌شود
                 if time > last_run.until_time + GRACE_PERIOD_MS {
@@ -269,6 +474,17 @@ impl HistoricalDepth {
             })
     }
 
+    /// تخمین حافظه مصرفی سطوح قیمتی و اجراهای سفارش بر حسب بایت
+    pub fn memory_usage(&self) -> usize {
+        self.price_levels
+            .values()
+            .map(|runs| {
+                std::mem::size_of::<(Price, Vec<OrderRun>)>()
+                    + runs.capacity() * std::mem::size_of::<OrderRun>()
+            })
+            .sum()
+    }
+
     /// پاکسازی سطوح قیمتی قدیمی که دیگر در محدوده زمانی نیستند
     pub fn cleanup_old_price_levels(&mut self, oldest_time: u64) {
         self.price_levels.iter_mut().for_each(|(_, runs)| {
@@ -645,3 +861,37 @@ impl std::fmt::Display for ProfileKind {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_levels_cap_the_visible_price_range_around_mid() {
+        let step = PriceStep::from_f32(0.5);
+        let mid = Price::from_f32(100.0);
+        let (highest, lowest) = (Price::from_f32(500.0), Price::from_f32(99.0));
+
+        let uncapped = Config::default();
+        assert_eq!(
+            uncapped.render_price_range(highest, lowest, mid, step),
+            Some((highest, lowest))
+        );
+
+        let capped = Config {
+            render_levels: Some(20),
+            ..Config::default()
+        };
+        assert_eq!(
+            capped.render_price_range(highest, lowest, mid, step),
+            Some((Price::from_units(mid.units + 20 * step.units), lowest))
+        );
+
+        // دور از قیمت میانی هیچ سطحی از دفتر سفارش رسم نمی‌شود
+        let (far_high, far_low) = (Price::from_f32(300.0), Price::from_f32(200.0));
+        assert_eq!(
+            capped.render_price_range(far_high, far_low, mid, step),
+            None
+        );
+    }
+}
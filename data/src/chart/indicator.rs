@@ -1,4 +1,5 @@
 use std::fmt::{self, Debug, Display};
+use std::sync::{LazyLock, RwLock};
 
 use enum_map::Enum;
 use exchange::adapter::MarketKind;
@@ -12,11 +13,71 @@ pub trait Indicator: PartialEq + Display + 'static {
         Self: Sized;
 }
 
+/// اندیکاتورهای فعال که در بازار داده شده قابل استفاده‌اند، با حفظ ترتیب انتخابی کاربر؛
+/// موارد ناسازگار (مثلاً بهره باز در اسپات) از لیست کاربر حذف نمی‌شوند و فقط نادیده گرفته می‌شوند
+pub fn applicable<I: Indicator + Copy>(enabled: &[I], market: MarketKind) -> Vec<I> {
+    let available = I::for_market(market);
+
+    enabled
+        .iter()
+        .copied()
+        .filter(|indicator| available.contains(indicator))
+        .collect()
+}
+
+/// مجموعه اندیکاتورهایی که پنل‌های جدید کندل و نقشه حرارتی با آن ساخته می‌شوند؛
+/// موارد ناسازگار با بازار (مثلاً بهره باز در اسپات) هنگام ساخت پنل کنار گذاشته می‌شوند
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DefaultIndicators {
+    pub kline: Vec<KlineIndicator>, // اندیکاتورهای پیش‌فرض نمودارهای کندل و فوت‌پرینت
+    pub heatmap: Vec<HeatmapIndicator>, // اندیکاتورهای پیش‌فرض نقشه حرارتی
+}
+
+impl DefaultIndicators {
+    /// اندیکاتورهای پیش‌فرض کندل که در بازار داده شده قابل استفاده‌اند
+    pub fn kline_for(&self, market: MarketKind) -> Vec<KlineIndicator> {
+        applicable(&self.kline, market)
+    }
+
+    /// اندیکاتورهای پیش‌فرض نقشه حرارتی که در بازار داده شده قابل استفاده‌اند
+    pub fn heatmap_for(&self, market: MarketKind) -> Vec<HeatmapIndicator> {
+        applicable(&self.heatmap, market)
+    }
+}
+
+impl Default for DefaultIndicators {
+    fn default() -> Self {
+        Self {
+            kline: vec![KlineIndicator::Volume],
+            heatmap: vec![HeatmapIndicator::Volume],
+        }
+    }
+}
+
+static DEFAULT_INDICATORS: LazyLock<RwLock<DefaultIndicators>> =
+    LazyLock::new(|| RwLock::new(DefaultIndicators::default()));
+
+/// تنظیم سراسری اندیکاتورهای پیش‌فرض پنل‌های جدید
+pub fn set_default_indicators(defaults: DefaultIndicators) {
+    *DEFAULT_INDICATORS
+        .write()
+        .unwrap_or_else(|e| e.into_inner()) = defaults;
+}
+
+pub fn default_indicators() -> DefaultIndicators {
+    DEFAULT_INDICATORS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
 /// اندیکاتورهای مربوط به نمودار کندل‌استیک
 #[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize, Eq, Enum)]
 pub enum KlineIndicator {
     Volume,       // حجم معاملات
     OpenInterest, // بهره باز (فقط برای قراردادهای آتی)
+    TradeCount,   // تعداد معاملات هر کندل
 }
 
 impl Indicator for KlineIndicator {
@@ -32,9 +93,18 @@ impl KlineIndicator {
     // Indicator togglers on UI menus depend on these arrays.
     // Every variant needs to be in either SPOT, PERPS or both.
     /// اندیکاتورهای قابل استفاده در بازار اسپات (Spot)
-    const FOR_SPOT: [KlineIndicator; 1] = [KlineIndicator::Volume];
+    const FOR_SPOT: [KlineIndicator; 2] = [KlineIndicator::Volume, KlineIndicator::TradeCount];
     /// اندیکاتورهای قابل استفاده در بازار قراردادهای دائمی (Perpetual)
-    const FOR_PERPS: [KlineIndicator; 2] = [KlineIndicator::Volume, KlineIndicator::OpenInterest];
+    const FOR_PERPS: [KlineIndicator; 3] = [
+        KlineIndicator::Volume,
+        KlineIndicator::OpenInterest,
+        KlineIndicator::TradeCount,
+    ];
+
+    /// اندیکاتورهایی که از معاملات ساخته می‌شوند و برای کندل‌های بدون حجم معنایی ندارند
+    pub fn needs_trades(&self) -> bool {
+        matches!(self, KlineIndicator::Volume | KlineIndicator::TradeCount)
+    }
 }
 
 impl Display for KlineIndicator {
@@ -42,7 +112,70 @@ impl Display for KlineIndicator {
         match self {
             KlineIndicator::Volume => write!(f, "Volume"),
             KlineIndicator::OpenInterest => write!(f, "Open Interest"),
+            KlineIndicator::TradeCount => write!(f, "Trade Count"),
+        }
+    }
+}
+
+/// رنگ و شفافیت اختصاصی یک اندیکاتور به جای رنگ‌های تم
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct IndicatorStyle {
+    pub color: iced_core::Color, // رنگ خط یا ستون‌های خنثی اندیکاتور
+    pub opacity: f32,            // ضریب شفافیت همه اجزای اندیکاتور
+}
+
+impl IndicatorStyle {
+    pub const OPACITY_RANGE: std::ops::RangeInclusive<f32> = 0.1..=1.0;
+
+    /// رنگ با شفافیت اعمال شده
+    pub fn color(&self) -> iced_core::Color {
+        self.fade(self.color)
+    }
+
+    /// اعمال شفافیت این سبک روی یک رنگ دیگر (مثلاً رنگ‌های صعودی و نزولی تم)
+    pub fn fade(&self, color: iced_core::Color) -> iced_core::Color {
+        let opacity = self
+            .opacity
+            .clamp(*Self::OPACITY_RANGE.start(), *Self::OPACITY_RANGE.end());
+        color.scale_alpha(opacity)
+    }
+}
+
+impl Default for IndicatorStyle {
+    fn default() -> Self {
+        Self {
+            color: iced_core::Color::from_rgb8(120, 160, 220),
+            opacity: 1.0,
+        }
+    }
+}
+
+/// سبک‌های اختصاصی اندیکاتورهای نمودار کندل؛ `None` یعنی استفاده از رنگ‌های تم
+#[derive(Debug, Default, Copy, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct IndicatorColors {
+    pub volume: Option<IndicatorStyle>,
+    pub open_interest: Option<IndicatorStyle>,
+    pub trade_count: Option<IndicatorStyle>,
+}
+
+impl IndicatorColors {
+    pub fn get(&self, indicator: KlineIndicator) -> Option<IndicatorStyle> {
+        match indicator {
+            KlineIndicator::Volume => self.volume,
+            KlineIndicator::OpenInterest => self.open_interest,
+            KlineIndicator::TradeCount => self.trade_count,
+        }
+    }
+
+    pub fn with(mut self, indicator: KlineIndicator, style: Option<IndicatorStyle>) -> Self {
+        match indicator {
+            KlineIndicator::Volume => self.volume = style,
+            KlineIndicator::OpenInterest => self.open_interest = style,
+            KlineIndicator::TradeCount => self.trade_count = style,
         }
+        self
     }
 }
 
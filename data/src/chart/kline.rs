@@ -1,9 +1,12 @@
 use exchange::{
-    Kline, Trade,
+    Kline, Timeframe, Trade,
+    adapter::KlinePriceSource,
+    fetcher::TradeSource,
     util::{Price, PriceStep},
 };
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
 
 use crate::aggr::time::DataPoint;
 
@@ -36,6 +39,15 @@ impl KlineDataPoint {
         self.footprint.poc_price()
     }
 
+    /// تعداد معاملات کندل؛ مقدار گزارش‌شده توسط صرافی در اولویت است و در غیر این صورت
+    /// از معاملات ثبت‌شده در فوت‌پرینت شمرده می‌شود
+    pub fn trade_count(&self) -> Option<u64> {
+        self.kline.trade_count.or_else(|| {
+            let counted = self.footprint.trade_count();
+            (counted > 0).then_some(counted)
+        })
+    }
+
     pub fn set_poc_status(&mut self, status: NPoc) {
         self.footprint.set_poc_status(status);
     }
@@ -125,6 +137,15 @@ impl GroupedTrades {
         self.last_time = trade.time;
     }
 
+    fn merge(&mut self, other: &GroupedTrades) {
+        self.buy_qty += other.buy_qty;
+        self.sell_qty += other.sell_qty;
+        self.buy_count += other.buy_count;
+        self.sell_count += other.sell_count;
+        self.first_time = self.first_time.min(other.first_time);
+        self.last_time = self.last_time.max(other.last_time);
+    }
+
     pub fn total_qty(&self) -> f32 {
         self.buy_qty + self.sell_qty
     }
@@ -157,6 +178,14 @@ impl KlineTrades {
         self.trades.values().map(|group| group.last_time).max()
     }
 
+    /// مجموع تعداد معاملات خرید و فروش در تمام سطوح قیمتی
+    pub fn trade_count(&self) -> u64 {
+        self.trades
+            .values()
+            .map(|group| (group.buy_count + group.sell_count) as u64)
+            .sum()
+    }
+
     /// Add trade to the bin at the step multiple computed with side-based rounding.
     /// Intended for order-book ladder/quotes; Floor for sells, ceil for buys.
     /// Introduces side bias at bin edges and should not be used for OHLC/footprint aggregation
@@ -181,6 +210,16 @@ impl KlineTrades {
             .or_insert_with(|| GroupedTrades::new(trade));
     }
 
+    /// افزودن سطوح قیمتی فوت‌پرینت دیگری با همان گام قیمت به این فوت‌پرینت
+    pub fn merge(&mut self, other: &KlineTrades) {
+        for (price, group) in &other.trades {
+            self.trades
+                .entry(*price)
+                .and_modify(|existing| existing.merge(group))
+                .or_insert_with(|| group.clone());
+        }
+    }
+
     /// محاسبه حداکثر مقدار (حجم، دلتا و غیره) در یک محدوده قیمتی
     pub fn max_qty_by<F>(&self, highest: Price, lowest: Price, f: F) -> f32
     where
@@ -326,8 +365,448 @@ impl std::fmt::Display for ClusterKind {
     }
 }
 
+/// تنظیمات ظاهری نمودار کندل‌استیک؛ مقادیر پیش‌فرض همان رفتار قبلی (رنگ‌های تم) است
 #[derive(Debug, Default, Copy, Clone, PartialEq, Deserialize, Serialize)]
-pub struct Config {}
+#[serde(default)]
+pub struct Config {
+    pub candle_colors: Option<CandleColors>, // رنگ‌های اختصاصی؛ در صورت نبود از رنگ‌های تم استفاده می‌شود
+    pub hollow_up: bool,                     // کندل‌های صعودی بدون پرشدگی (توخالی)
+    pub coloring: CandleColoring,            // مبنای تشخیص صعودی یا نزولی بودن کندل
+    pub trade_retention: super::TradeRetention, // حد نگهداری معاملات خام در حافظه
+    pub show_info_box: bool, // نمایش کادر اطلاعات کندل (OHLC، حجم و تغییر) کنار نشانگر
+    pub trade_fetch: TradeFetchConfig, // تنظیمات بازسازی فوت‌پرینت از معاملات گذشته
+    pub print_aggregation: crate::aggr::PrintAggregation, // ادغام معاملات پیاپی هم‌قیمت در فوت‌پرینت
+    pub open_interest: OpenInterestDisplay, // نمایش مقدار مطلق بهره باز یا تغییرات آن
+    pub higher_timeframe: Option<HigherTimeframe>, // نمایش کندل‌های بازه زمانی بالاتر پشت نمودار
+    pub max_history: MaxHistory, // عمق دریافت کندل‌های گذشته با فرمان بارگذاری تاریخچه
+    pub delta_coloring: DeltaColoring, // رنگ‌آمیزی خانه‌های فوت‌پرینت بر اساس دلتای هر سطح
+    pub simplify_below: SimplifyBelow, // ساده‌سازی کندل‌های باریک‌تر از این عرض در بزرگنمایی‌های کم
+    pub order_flow: crate::orderflow::OrderFlowConfig, // تشخیص جذب و تهاجم روی فوت‌پرینت
+    pub price_source: KlinePriceSource, // قیمت مبنای کندل‌ها (آخرین معامله، نشان‌گذاری یا شاخص)
+    pub candle_geometry: CandleGeometry, // فاصله بین کندل‌ها و پهنای بدنه و سایه
+    pub indicator_colors: super::indicator::IndicatorColors, // رنگ و شفافیت اختصاصی اندیکاتورها
+    pub session_levels: super::session::SessionLevelsConfig, // خطوط باز، سقف و کف روز محلی
+    pub swings: super::swings::SwingConfig, // نشانگر سقف و کف‌های چرخشی و برچسب ساختار بازار
+    pub volume_opacity: VolumeOpacity, // کم‌رنگ کردن بدنه کندل‌های کم‌حجم نسبت به میانگین حجم
+}
+
+/// هندسه رسم کندل‌ها، همه به نسبت عرض هر خانه (`cell_width`) تا با بزرگنمایی مقیاس شوند
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CandleGeometry {
+    pub spacing: f32,    // سهم خالی بین دو کندل مجاور از عرض خانه
+    pub body_width: f32, // پهنای بدنه به نسبت عرض خانه
+    pub wick_width: f32, // پهنای سایه به نسبت پهنای بدنه
+}
+
+impl CandleGeometry {
+    pub const SPACING_RANGE: std::ops::RangeInclusive<f32> = 0.0..=0.8;
+    pub const BODY_RANGE: std::ops::RangeInclusive<f32> = 0.1..=1.0;
+    pub const WICK_RANGE: std::ops::RangeInclusive<f32> = 0.05..=1.0;
+
+    /// پهنای بدنه در واحد نمودار؛ هرگز از فضای باقی‌مانده پس از فاصله بیشتر نمی‌شود
+    /// تا کندل‌های مجاور روی هم نیفتند
+    pub fn body(&self, cell_width: f32) -> f32 {
+        let spacing = self
+            .spacing
+            .clamp(*Self::SPACING_RANGE.start(), *Self::SPACING_RANGE.end());
+        let body = self
+            .body_width
+            .clamp(*Self::BODY_RANGE.start(), *Self::BODY_RANGE.end());
+
+        cell_width * body.min(1.0 - spacing)
+    }
+
+    /// پهنای سایه در واحد نمودار
+    pub fn wick(&self, cell_width: f32) -> f32 {
+        self.body(cell_width)
+            * self
+                .wick_width
+                .clamp(*Self::WICK_RANGE.start(), *Self::WICK_RANGE.end())
+    }
+}
+
+impl Default for CandleGeometry {
+    fn default() -> Self {
+        Self {
+            spacing: 0.2,
+            body_width: 0.8,
+            wick_width: 0.25,
+        }
+    }
+}
+
+/// شفافیت بدنه کندل‌ها بر اساس حجم هر کندل نسبت به میانگین متحرک حجم کندل‌های قبلی؛
+/// کندل‌های پرحجم پررنگ می‌مانند و کندل‌های کم‌حجم کم‌رنگ می‌شوند
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct VolumeOpacity {
+    pub enabled: bool, // فعال بودن شفافیت بر اساس حجم
+    pub strength: f32, // شدت کم‌رنگ شدن کندل‌های کم‌حجم
+    pub period: u32,   // تعداد کندل‌های میانگین متحرک حجم
+}
+
+impl Default for VolumeOpacity {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            strength: 0.7,
+            period: 20,
+        }
+    }
+}
+
+impl VolumeOpacity {
+    pub const STRENGTH_RANGE: std::ops::RangeInclusive<f32> = 0.1..=1.0;
+    pub const PERIOD_RANGE: std::ops::RangeInclusive<u32> = 5..=100;
+    /// کمترین شفافیت بدنه، تا کندل‌های کم‌حجم کم‌رنگ شوند ولی ناپدید نشوند
+    pub const MIN_ALPHA: f32 = 0.2;
+
+    fn period(&self) -> usize {
+        self.period
+            .clamp(*Self::PERIOD_RANGE.start(), *Self::PERIOD_RANGE.end()) as usize
+    }
+
+    /// ضریب شفافیت بدنه کندلی با حجم `volume` وقتی میانگین حجم کندل‌های قبل از آن `mean` است؛
+    /// حجم دو برابر میانگین و بیشتر کاملاً پررنگ رسم می‌شود و بدون میانگین کندل تغییری نمی‌کند
+    pub fn alpha(&self, volume: f32, mean: Option<f32>) -> f32 {
+        let Some(mean) = mean.filter(|mean| *mean > f32::EPSILON) else {
+            return 1.0;
+        };
+
+        let weight = (volume / (2.0 * mean)).clamp(0.0, 1.0);
+        let faded = Self::MIN_ALPHA + (1.0 - Self::MIN_ALPHA) * weight;
+        let strength = self
+            .strength
+            .clamp(*Self::STRENGTH_RANGE.start(), *Self::STRENGTH_RANGE.end());
+
+        1.0 - strength * (1.0 - faded)
+    }
+}
+
+/// میانگین متحرک حجم کندل‌های بسته شده پیش از هر کندل، که همراه با سری کندل‌ها به‌روز می‌شود؛
+/// حجم‌ها با واحد نمایش فعلی (پایه یا کوت) به ارزش معادل تبدیل شده‌اند
+#[derive(Debug, Default, Clone)]
+pub struct VolumeBaseline {
+    means: BTreeMap<u64, f32>, // میانگین حجم کندل‌های قبلی به ازای زمان هر کندل بسته شده
+    window: VecDeque<f32>,     // حجم آخرین کندل‌های بسته شده به اندازه دوره میانگین
+    size_in_quote_ccy: Option<bool>,
+    /// زمان آخرین کندل بسته شده بررسی شده؛ `None` یعنی هنوز اسکنی انجام نشده
+    resume_from: Option<u64>,
+}
+
+impl VolumeBaseline {
+    /// زمان کندلی که اسکن بعدی باید از آن شروع شود؛ `None` برای اسکن کامل تاریخچه
+    pub fn resume_from(&self) -> Option<u64> {
+        self.resume_from
+    }
+
+    /// تغییر واحد نمایش حجم، میانگین‌های قبلی را بی‌اعتبار می‌کند
+    pub fn sync_unit(&mut self, size_in_quote_ccy: bool) {
+        if self.size_in_quote_ccy != Some(size_in_quote_ccy) {
+            self.clear();
+            self.size_in_quote_ccy = Some(size_in_quote_ccy);
+        }
+    }
+
+    /// بررسی کندل‌های داده شده (مرتب بر اساس زمان و از `resume_from` به بعد)؛
+    /// آخرین کندل در حال شکل‌گیری است و در میانگین وارد نمی‌شود
+    pub fn update<'a>(
+        &mut self,
+        klines: impl Iterator<Item = &'a Kline>,
+        cfg: &VolumeOpacity,
+        volume_of: impl Fn(&Kline) -> f32,
+    ) {
+        if !cfg.enabled {
+            self.clear();
+            return;
+        }
+
+        let period = cfg.period();
+        let mut klines = klines.peekable();
+
+        while let Some(kline) = klines.next() {
+            if klines.peek().is_none() {
+                break;
+            }
+            if self.resume_from.is_some_and(|last| kline.time <= last) {
+                continue;
+            }
+            self.resume_from = Some(kline.time);
+
+            if let Some(mean) = self.mean() {
+                self.means.insert(kline.time, mean);
+            }
+            self.window.push_back(volume_of(kline));
+            if self.window.len() > period {
+                self.window.pop_front();
+            }
+        }
+    }
+
+    fn mean(&self) -> Option<f32> {
+        (!self.window.is_empty())
+            .then(|| self.window.iter().sum::<f32>() / self.window.len() as f32)
+    }
+
+    /// میانگین حجم کندل‌های پیش از کندل `time`؛ کندل در حال شکل‌گیری با آخرین کندل‌های بسته شده
+    /// مقایسه می‌شود
+    pub fn mean_before(&self, time: u64) -> Option<f32> {
+        match self.resume_from {
+            Some(last) if time > last => self.mean(),
+            _ => self.means.get(&time).copied(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.means.clear();
+        self.window.clear();
+        self.resume_from = None;
+    }
+}
+
+/// عرض کندل روی صفحه (پیکسل) که کمتر از آن، چند کندل مجاور به صورت یک نوار سقف/کف
+/// رسم می‌شوند تا از رسم بی‌فایده بدنه و سایه هزاران کندل جلوگیری شود؛ صفر یعنی غیرفعال
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SimplifyBelow(pub f32);
+
+impl SimplifyBelow {
+    pub const RANGE: std::ops::RangeInclusive<f32> = 0.0..=6.0;
+
+    /// تعداد کندل‌هایی که در هر نوار تجمیع می‌شوند؛ `None` اگر کندل‌ها به اندازه کافی پهن باشند
+    pub fn group_size(self, candle_px: f32) -> Option<usize> {
+        if self.0 <= 0.0 || candle_px <= 0.0 || candle_px >= self.0 {
+            return None;
+        }
+        Some((self.0 / candle_px).ceil() as usize)
+    }
+
+    /// آستانه پنل یا کمینه پروفایل کارایی، هر کدام بزرگ‌تر باشد
+    pub fn at_least(self, min_px: f32) -> Self {
+        Self(self.0.max(min_px))
+    }
+}
+
+impl Default for SimplifyBelow {
+    fn default() -> Self {
+        Self(2.0)
+    }
+}
+
+impl Config {
+    /// بازه زمانی بالاتر مؤثر برای مبنای فعلی نمودار؛
+    /// فقط در حالت زمانی و زمانی که از بازه خود نمودار بزرگ‌تر باشد
+    pub fn effective_higher_timeframe(&self, basis: super::Basis) -> Option<HigherTimeframe> {
+        match (basis, self.higher_timeframe) {
+            (super::Basis::Time(timeframe), Some(htf)) if htf.timeframe > timeframe => Some(htf),
+            _ => None,
+        }
+    }
+}
+
+/// تنظیمات نمایش بازه زمانی بالاتر (MTF) روی نمودار
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
+pub struct HigherTimeframe {
+    pub timeframe: Timeframe, // بازه زمانی بالاتر
+    #[serde(default)]
+    pub style: HigherTimeframeStyle, // نحوه رسم
+}
+
+/// نحوه رسم کندل‌های بازه زمانی بالاتر
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum HigherTimeframeStyle {
+    #[default]
+    Bands,     // محدوده بالاترین و پایین‌ترین قیمت هر کندل
+    CloseLine, // خط پله‌ای قیمت بسته شدن
+}
+
+impl HigherTimeframeStyle {
+    pub const ALL: [HigherTimeframeStyle; 2] =
+        [HigherTimeframeStyle::Bands, HigherTimeframeStyle::CloseLine];
+}
+
+impl std::fmt::Display for HigherTimeframeStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HigherTimeframeStyle::Bands => write!(f, "High/Low bands"),
+            HigherTimeframeStyle::CloseLine => write!(f, "Close line"),
+        }
+    }
+}
+
+/// عمق تاریخچه‌ای که فرمان بارگذاری کندل‌های گذشته تا آن صفحه به صفحه دریافت می‌کند
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum MaxHistory {
+    D1,
+    #[default]
+    D7,
+    D30,
+    D90,
+}
+
+impl MaxHistory {
+    pub const ALL: [MaxHistory; 4] = [
+        MaxHistory::D1,
+        MaxHistory::D7,
+        MaxHistory::D30,
+        MaxHistory::D90,
+    ];
+
+    pub fn days(self) -> u64 {
+        match self {
+            MaxHistory::D1 => 1,
+            MaxHistory::D7 => 7,
+            MaxHistory::D30 => 30,
+            MaxHistory::D90 => 90,
+        }
+    }
+
+    pub fn to_millis(self) -> u64 {
+        self.days() * 24 * 60 * 60 * 1000
+    }
+}
+
+impl std::fmt::Display for MaxHistory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.days() {
+            1 => write!(f, "1 day"),
+            days => write!(f, "{days} days"),
+        }
+    }
+}
+
+/// رنگ‌آمیزی خانه‌های فوت‌پرینت بر اساس دلتای خرید و فروش هر سطح قیمت؛
+/// شدت رنگ با اندازه دلتا نسبت به بزرگ‌ترین دلتای همان کندل تعیین می‌شود
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DeltaColoring {
+    pub enabled: bool,  // فعال بودن رنگ‌آمیزی
+    pub min_ratio: f32, // حداقل نسبت حجم طرف غالب به طرف مقابل برای رنگ‌آمیزی یک خانه
+}
+
+impl DeltaColoring {
+    pub const RATIO_RANGE: std::ops::RangeInclusive<f32> = 1.0..=5.0;
+
+    /// آیا نسبت خرید به فروش یک سطح از آستانه عبور می‌کند؛ سطوح یک‌طرفه همیشه عبور می‌کنند
+    pub fn exceeds_ratio(&self, buy_qty: f32, sell_qty: f32) -> bool {
+        let (dominant, other) = (buy_qty.max(sell_qty), buy_qty.min(sell_qty));
+
+        if dominant <= 0.0 || buy_qty == sell_qty {
+            return false;
+        }
+        other <= 0.0 || dominant / other >= self.min_ratio
+    }
+}
+
+impl Default for DeltaColoring {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_ratio: 1.5,
+        }
+    }
+}
+
+/// تنظیمات دریافت معاملات گذشته برای بازسازی فوت‌پرینت
+#[derive(Debug, Default, Copy, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TradeFetchConfig {
+    pub source: TradeSource, // منبع معاملات (فایل‌های روزانه، API یا هر دو)
+    /// بازه‌ای که معاملات دریافتی در آن به کندل‌های فوت‌پرینت گروه‌بندی می‌شوند؛
+    /// در صورت نبود، بازه زمانی خود نمودار
+    pub base_timeframe: Option<Timeframe>,
+}
+
+impl TradeFetchConfig {
+    /// بازه گروه‌بندی معاملات برای نموداری با بازه `chart`؛
+    /// بازه‌ای که کندل‌های نمودار را دقیق تقسیم نکند نادیده گرفته می‌شود
+    pub fn bucket_timeframe(&self, chart: Timeframe) -> Timeframe {
+        self.base_timeframe
+            .filter(|tf| Self::divides(*tf, chart))
+            .unwrap_or(chart)
+    }
+
+    /// آیا کندل‌های بازه `base` دقیقاً در کندل‌های بازه `chart` جا می‌گیرند
+    pub fn divides(base: Timeframe, chart: Timeframe) -> bool {
+        chart
+            .to_milliseconds()
+            .is_multiple_of(base.to_milliseconds())
+    }
+}
+
+/// رنگ‌های بدنه و سایه کندل‌ها، مستقل از تم
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CandleColors {
+    pub up_body: iced_core::Color,   // بدنه کندل صعودی
+    pub down_body: iced_core::Color, // بدنه کندل نزولی
+    pub up_wick: iced_core::Color,   // سایه کندل صعودی
+    pub down_wick: iced_core::Color, // سایه کندل نزولی
+}
+
+impl Default for CandleColors {
+    fn default() -> Self {
+        let up = iced_core::Color::from_rgb8(81, 205, 160);
+        let down = iced_core::Color::from_rgb8(192, 80, 77);
+
+        CandleColors {
+            up_body: up,
+            down_body: down,
+            up_wick: up,
+            down_wick: down,
+        }
+    }
+}
+
+/// مبنای رنگ‌آمیزی کندل‌ها
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum CandleColoring {
+    #[default]
+    OpenClose, // مقایسه قیمت بسته شدن با قیمت باز شدن همان کندل
+    PrevClose, // مقایسه قیمت بسته شدن با قیمت بسته شدن کندل قبلی
+}
+
+impl CandleColoring {
+    pub const ALL: [CandleColoring; 2] = [CandleColoring::OpenClose, CandleColoring::PrevClose];
+
+    /// آیا کندل با توجه به مبنای انتخاب شده صعودی محسوب می‌شود
+    pub fn is_up(&self, kline: &Kline, prev_close: Option<Price>) -> bool {
+        match (self, prev_close) {
+            (CandleColoring::PrevClose, Some(prev_close)) => kline.close >= prev_close,
+            _ => kline.close >= kline.open,
+        }
+    }
+}
+
+impl std::fmt::Display for CandleColoring {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CandleColoring::OpenClose => write!(f, "Close vs Open"),
+            CandleColoring::PrevClose => write!(f, "Close vs Prev. Close"),
+        }
+    }
+}
+
+/// نحوه نمایش اندیکاتور بهره باز
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum OpenInterestDisplay {
+    #[default]
+    Absolute, // مقدار مطلق بهره باز به صورت خطی
+    Delta,    // تغییر نسبت به دوره قبل به صورت هیستوگرام (افزایش سبز، کاهش قرمز)
+}
+
+impl OpenInterestDisplay {
+    pub const ALL: [OpenInterestDisplay; 2] =
+        [OpenInterestDisplay::Absolute, OpenInterestDisplay::Delta];
+}
+
+impl std::fmt::Display for OpenInterestDisplay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OpenInterestDisplay::Absolute => write!(f, "Absolute"),
+            OpenInterestDisplay::Delta => write!(f, "Change (delta)"),
+        }
+    }
+}
 
 /// روش‌های مقیاس‌بندی خوشه‌های فوت‌پرینت
 #[derive(Default, Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
@@ -445,3 +924,67 @@ impl NPoc {
         *self = NPoc::Naked;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_geometry_matches_previous_candles() {
+        let geometry = CandleGeometry::default();
+        assert!((geometry.body(10.0) - 8.0).abs() < 1e-6);
+        assert!((geometry.wick(10.0) - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn body_never_eats_into_spacing() {
+        let geometry = CandleGeometry {
+            spacing: 0.5,
+            body_width: 1.0,
+            wick_width: 0.1,
+        };
+        assert!((geometry.body(10.0) - 5.0).abs() < 1e-6);
+        assert!((geometry.wick(10.0) - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn volume_opacity_fades_quiet_candles_but_keeps_them_visible() {
+        let cfg = VolumeOpacity {
+            enabled: true,
+            strength: 1.0,
+            period: 5,
+        };
+
+        assert_eq!(cfg.alpha(0.0, None), 1.0);
+        assert!((cfg.alpha(0.0, Some(10.0)) - VolumeOpacity::MIN_ALPHA).abs() < 1e-6);
+        assert!((cfg.alpha(10.0, Some(10.0)) - 0.6).abs() < 1e-6);
+        assert_eq!(cfg.alpha(50.0, Some(10.0)), 1.0);
+
+        let klines: Vec<Kline> = (0..8)
+            .map(|i| Kline {
+                time: i * 60_000,
+                open: Price::from_f32(1.0),
+                high: Price::from_f32(1.0),
+                low: Price::from_f32(1.0),
+                close: Price::from_f32(1.0),
+                volume: (i as f32, 0.0),
+                trade_count: None,
+            })
+            .collect();
+        let mut baseline = VolumeBaseline::default();
+        baseline.update(klines.iter(), &cfg, |kline| kline.volume.0 + kline.volume.1);
+
+        // نخستین کندل میانگینی ندارد و دوره پنج کندلی فقط کندل‌های بسته شده را در بر می‌گیرد
+        assert_eq!(baseline.mean_before(0), None);
+        assert_eq!(baseline.mean_before(2 * 60_000), Some(0.5));
+        assert_eq!(baseline.mean_before(6 * 60_000), Some(3.0));
+        assert_eq!(baseline.mean_before(7 * 60_000), Some(4.0));
+    }
+
+    #[test]
+    fn performance_floor_only_raises_simplify_threshold() {
+        assert_eq!(SimplifyBelow(0.0).at_least(3.0).group_size(1.5), Some(2));
+        assert_eq!(SimplifyBelow(6.0).at_least(3.0).group_size(4.0), Some(2));
+        assert_eq!(SimplifyBelow(2.0).at_least(0.0).group_size(4.0), None);
+    }
+}
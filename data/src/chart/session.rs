@@ -0,0 +1,127 @@
+use crate::UserTimezone;
+use exchange::Kline;
+use exchange::util::Price;
+use serde::{Deserialize, Serialize};
+
+const DAY_MS: u64 = 86_400_000;
+
+/// تنظیمات نمایش سطوح باز، سقف و کف جلسه معاملاتی (روز محلی کاربر) روی نمودار
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SessionLevelsConfig {
+    pub enabled: bool,  // نمایش سطوح جلسه
+    pub open: bool,     // قیمت باز شدن جلسه
+    pub high: bool,     // بالاترین قیمت جلسه
+    pub low: bool,      // پایین‌ترین قیمت جلسه
+    pub previous: bool, // سطوح جلسه قبلی نیز رسم شوند
+}
+
+impl Default for SessionLevelsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            open: true,
+            high: true,
+            low: true,
+            previous: false,
+        }
+    }
+}
+
+/// باز، سقف و کف یک جلسه؛ `start` و `end` مرزهای روز محلی به میلی‌ثانیه UTC هستند
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SessionLevels {
+    pub start: u64,
+    pub end: u64,
+    pub open: Price,
+    pub high: Price,
+    pub low: Price,
+}
+
+impl SessionLevels {
+    /// سطوح از کندل‌های درون جلسه، به ترتیب زمانی؛ بدون کندل `None`
+    pub fn from_klines<'a>(
+        start: u64,
+        end: u64,
+        mut klines: impl Iterator<Item = &'a Kline>,
+    ) -> Option<Self> {
+        let first = klines.next()?;
+
+        Some(klines.fold(
+            SessionLevels {
+                start,
+                end,
+                open: first.open,
+                high: first.high,
+                low: first.low,
+            },
+            |levels, kline| SessionLevels {
+                high: levels.high.max(kline.high),
+                low: levels.low.min(kline.low),
+                ..levels
+            },
+        ))
+    }
+}
+
+/// مرزهای جلسه جاری (شامل `latest`) و جلسه پیش از آن در منطقه زمانی کاربر؛
+/// روزهای کوتاه یا بلند تغییر ساعت تابستانی از نیمه‌شب تا نیمه‌شب محاسبه می‌شوند
+pub fn session_bounds(timezone: UserTimezone, latest: u64) -> Option<[(u64, u64); 2]> {
+    let start = timezone.day_start(latest)?;
+    // چند ساعت بعد از نیمه‌شب روز بعد، تا در روزهای ۲۳ یا ۲۵ ساعته هم روز درست پیدا شود
+    let end = timezone.day_start(start + DAY_MS + DAY_MS / 8)?;
+    let prev_start = timezone.day_start(start.checked_sub(DAY_MS / 8)?)?;
+
+    Some([(start, end), (prev_start, start)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kline(time: u64, open: f32, high: f32, low: f32) -> Kline {
+        Kline {
+            time,
+            open: Price::from_f32(open),
+            high: Price::from_f32(high),
+            low: Price::from_f32(low),
+            close: Price::from_f32(open),
+            volume: (0.0, 0.0),
+            trade_count: None,
+        }
+    }
+
+    #[test]
+    fn sessions_follow_the_local_day() {
+        let midnight_utc = 1_704_067_200_000; // 2024-01-01 00:00 UTC
+        let tehran = UserTimezone::Fixed(210);
+
+        // ساعت ۲۲ UTC روز قبل در تهران ساعت ۰۱:۳۰ روز جدید است
+        let [(start, end), (prev_start, prev_end)] =
+            session_bounds(tehran, midnight_utc - 2 * 3_600_000).unwrap();
+        assert_eq!(start, midnight_utc - 210 * 60_000);
+        assert_eq!(end, start + DAY_MS);
+        assert_eq!((prev_start, prev_end), (start - DAY_MS, start));
+
+        // روز تغییر ساعت تابستانی لندن ۲۳ ساعت است
+        let london = UserTimezone::Named(chrono_tz::Europe::London);
+        let dst_day = 1_711_843_200_000; // 2024-03-31 00:00 UTC
+        let [(start, end), _] = session_bounds(london, dst_day + 12 * 3_600_000).unwrap();
+        assert_eq!((start, end - start), (dst_day, DAY_MS - 3_600_000));
+    }
+
+    #[test]
+    fn levels_track_first_open_and_extremes() {
+        let klines = [
+            kline(0, 100.0, 101.0, 99.0),
+            kline(60_000, 100.5, 104.0, 100.0),
+            kline(120_000, 103.0, 103.5, 97.5),
+        ];
+
+        let levels = SessionLevels::from_klines(0, DAY_MS, klines.iter()).unwrap();
+        assert_eq!(levels.open, Price::from_f32(100.0));
+        assert_eq!(levels.high, Price::from_f32(104.0));
+        assert_eq!(levels.low, Price::from_f32(97.5));
+        assert_eq!(SessionLevels::from_klines(0, DAY_MS, [].iter()), None);
+    }
+}
@@ -0,0 +1,258 @@
+//! تشخیص نقاط چرخش (فرکتال) سقف و کف روی کندل‌ها و برچسب ساختار بازار (HH، LH، HL، LL)
+
+use exchange::Kline;
+use exchange::util::Price;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+
+/// تنظیمات نمایش نقاط چرخش روی نمودار کندل
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SwingConfig {
+    pub enabled: bool,   // نمایش نشانگر نقاط چرخش
+    pub lookback: u32,   // تعداد کندل‌های هر سمت که باید پایین‌تر (یا بالاتر) باشند
+    pub structure: bool, // نمایش برچسب ساختار (سقف/کف بالاتر یا پایین‌تر)
+}
+
+impl Default for SwingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            lookback: 2,
+            structure: true,
+        }
+    }
+}
+
+impl SwingConfig {
+    pub const LOOKBACK_RANGE: std::ops::RangeInclusive<u32> = 1..=10;
+
+    fn lookback(&self) -> usize {
+        self.lookback
+            .clamp(*Self::LOOKBACK_RANGE.start(), *Self::LOOKBACK_RANGE.end()) as usize
+    }
+}
+
+/// جایگاه یک نقطه چرخش نسبت به نقطه چرخش قبلی هم‌نوع خود
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Structure {
+    HigherHigh,
+    LowerHigh,
+    HigherLow,
+    LowerLow,
+}
+
+impl std::fmt::Display for Structure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Structure::HigherHigh => write!(f, "HH"),
+            Structure::LowerHigh => write!(f, "LH"),
+            Structure::HigherLow => write!(f, "HL"),
+            Structure::LowerLow => write!(f, "LL"),
+        }
+    }
+}
+
+/// یک سقف یا کف تأیید شده
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Swing {
+    pub price: Price,
+    pub is_high: bool,
+    pub structure: Option<Structure>, // برای اولین نقطه هر نوع، مرجعی برای مقایسه وجود ندارد
+}
+
+/// نقاط چرخش به ازای زمان کندل؛ فقط کندل‌های بسته شده بررسی می‌شوند و هر نقطه پس از
+/// بسته شدن `lookback` کندل بعدی تأیید می‌شود. برچسب ساختار در لحظه تأیید ثابت می‌شود
+/// و با تشکیل سقف یا کف‌های بعدی تغییر نمی‌کند
+#[derive(Debug, Default, Clone)]
+pub struct SwingTracker {
+    swings: BTreeMap<u64, Vec<Swing>>,
+    window: VecDeque<Kline>, // آخرین کندل‌های بسته شده، به اندازه دو برابر `lookback` به علاوه یک
+    last_high: Option<Price>,
+    last_low: Option<Price>,
+    /// زمان آخرین کندل بسته شده بررسی شده؛ `None` یعنی هنوز اسکنی انجام نشده
+    resume_from: Option<u64>,
+}
+
+impl SwingTracker {
+    /// زمان کندلی که اسکن بعدی باید از آن شروع شود؛ `None` برای اسکن کامل تاریخچه
+    pub fn resume_from(&self) -> Option<u64> {
+        self.resume_from
+    }
+
+    /// بررسی کندل‌های داده شده (مرتب بر اساس زمان و از `resume_from` به بعد)؛
+    /// آخرین کندل در حال شکل‌گیری است و کنار گذاشته می‌شود
+    pub fn update<'a>(&mut self, klines: impl Iterator<Item = &'a Kline>, cfg: &SwingConfig) {
+        if !cfg.enabled {
+            self.clear();
+            return;
+        }
+
+        let lookback = cfg.lookback();
+        let mut klines = klines.peekable();
+
+        while let Some(kline) = klines.next() {
+            if klines.peek().is_none() {
+                break;
+            }
+            if self.resume_from.is_some_and(|last| kline.time <= last) {
+                continue;
+            }
+            self.resume_from = Some(kline.time);
+
+            self.window.push_back(*kline);
+            if self.window.len() > 2 * lookback + 1 {
+                self.window.pop_front();
+            }
+            if self.window.len() == 2 * lookback + 1 {
+                self.confirm_pivot(lookback);
+            }
+        }
+    }
+
+    /// کندل میانی پنجره سقف است اگر از سمت چپ اکیداً بالاتر و از سمت راست پایین‌تر نباشد،
+    /// تا از چند سقف هم‌قیمت پیاپی فقط اولی علامت بخورد؛ کف به همین ترتیب
+    fn confirm_pivot(&mut self, lookback: usize) {
+        let center = self.window[lookback];
+        let left = || self.window.range(..lookback);
+        let right = || self.window.range(lookback + 1..);
+
+        let is_high =
+            left().all(|k| center.high > k.high) && right().all(|k| center.high >= k.high);
+        let is_low = left().all(|k| center.low < k.low) && right().all(|k| center.low <= k.low);
+
+        if is_high {
+            let structure = self.last_high.map(|prev| {
+                if center.high > prev {
+                    Structure::HigherHigh
+                } else {
+                    Structure::LowerHigh
+                }
+            });
+            self.last_high = Some(center.high);
+            self.swings.entry(center.time).or_default().push(Swing {
+                price: center.high,
+                is_high: true,
+                structure,
+            });
+        }
+
+        if is_low {
+            let structure = self.last_low.map(|prev| {
+                if center.low < prev {
+                    Structure::LowerLow
+                } else {
+                    Structure::HigherLow
+                }
+            });
+            self.last_low = Some(center.low);
+            self.swings.entry(center.time).or_default().push(Swing {
+                price: center.low,
+                is_high: false,
+                structure,
+            });
+        }
+    }
+
+    /// نقاط چرخش تأیید شده برای کندلی که در زمان داده شده باز شده است
+    pub fn swings_at(&self, candle_time: u64) -> &[Swing] {
+        self.swings.get(&candle_time).map_or(&[], Vec::as_slice)
+    }
+
+    /// پاک کردن نتایج تا اسکن بعدی کل تاریخچه را دوباره بررسی کند
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kline(time: u64, high: f32, low: f32) -> Kline {
+        Kline {
+            time,
+            open: Price::from_f32(low),
+            high: Price::from_f32(high),
+            low: Price::from_f32(low),
+            close: Price::from_f32(high),
+            volume: (0.0, 0.0),
+            trade_count: None,
+        }
+    }
+
+    fn series(bars: &[(f32, f32)]) -> Vec<Kline> {
+        bars.iter()
+            .enumerate()
+            .map(|(i, (high, low))| kline(i as u64 * 60_000, *high, *low))
+            .collect()
+    }
+
+    #[test]
+    fn pivots_need_closed_candles_on_both_sides() {
+        let cfg = SwingConfig {
+            enabled: true,
+            lookback: 2,
+            structure: true,
+        };
+        let klines = series(&[
+            (10.0, 8.0),
+            (11.0, 9.0),
+            (14.0, 12.0),
+            (12.0, 10.0),
+            (11.0, 9.0),
+            (12.0, 10.0), // در حال شکل‌گیری
+        ]);
+
+        let mut tracker = SwingTracker::default();
+        tracker.update(klines[..5].iter(), &cfg);
+        // کندل سوم فقط یک کندل بسته شده در سمت راست دارد
+        assert!(tracker.swings_at(120_000).is_empty());
+
+        let from = tracker.resume_from().unwrap();
+        tracker.update(klines.iter().filter(|k| k.time >= from), &cfg);
+        let swings = tracker.swings_at(120_000);
+        assert_eq!(swings.len(), 1);
+        assert!(swings[0].is_high);
+        assert_eq!(swings[0].price, Price::from_f32(14.0));
+        assert_eq!(swings[0].structure, None);
+    }
+
+    #[test]
+    fn structure_labels_are_fixed_when_confirmed() {
+        let cfg = SwingConfig {
+            enabled: true,
+            lookback: 1,
+            structure: true,
+        };
+        let klines = series(&[
+            (10.0, 8.0),
+            (12.0, 9.0), // سقف
+            (11.0, 7.0), // کف
+            (13.0, 9.0), // سقف بالاتر
+            (12.0, 8.0), // کف بالاتر
+            (12.5, 8.5), // سقف پایین‌تر
+            (12.0, 5.0), // کف پایین‌تر؛ برچسب کف قبلی تغییر نمی‌کند
+            (14.0, 9.0),
+            (15.0, 10.0),
+        ]);
+
+        let mut tracker = SwingTracker::default();
+        tracker.update(klines.iter(), &cfg);
+
+        let labels = (1..=6)
+            .map(|i| tracker.swings_at(i * 60_000)[0].structure)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            labels,
+            [
+                None,
+                None,
+                Some(Structure::HigherHigh),
+                Some(Structure::HigherLow),
+                Some(Structure::LowerHigh),
+                Some(Structure::LowerLow),
+            ]
+        );
+    }
+}
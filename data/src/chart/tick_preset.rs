@@ -0,0 +1,220 @@
+use crate::layout::pane::ContentKind;
+use exchange::adapter::hyperliquid::allowed_multipliers_for_base_tick;
+use exchange::{SerTicker, TickMultiplier, TickerInfo};
+use serde::{Deserialize, Serialize};
+use std::sync::{LazyLock, RwLock};
+
+/// ضریب گام ذخیره شده یک نماد برای هر نوع نمودار
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TickerPreset {
+    pub heatmap: Option<TickMultiplier>,   // ضریب پیش‌فرض نقشه حرارتی
+    pub footprint: Option<TickMultiplier>, // ضریب پیش‌فرض فوت‌پرینت
+}
+
+/// یک ردیف جدول پیش‌فرض‌ها بر اساس بزرگی قیمت؛ شامل نمادهایی که گام پایه آن‌ها
+/// دست کم `min_ticksize` است (گام پایه نماد با بزرگی قیمت آن بزرگ می‌شود)
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct MagnitudePreset {
+    pub min_ticksize: f32,                 // کمترین گام پایه این ردیف
+    pub heatmap: Option<TickMultiplier>,   // ضریب پیش‌فرض نقشه حرارتی
+    pub footprint: Option<TickMultiplier>, // ضریب پیش‌فرض فوت‌پرینت
+}
+
+/// ضرایب گام پیش‌فرض پنل‌های تازه نقشه حرارتی و فوت‌پرینت؛ ابتدا تنظیم اختصاصی نماد
+/// و سپس بزرگ‌ترین ردیف منطبق جدول بزرگی قیمت بررسی می‌شود و در نبود هر دو،
+/// پیش‌فرض داخلی برنامه به کار می‌رود
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TickPresets {
+    pub tickers: Vec<(SerTicker, TickerPreset)>, // تنظیمات اختصاصی هر نماد
+    pub magnitudes: Vec<MagnitudePreset>,        // جدول جایگزین بر اساس بزرگی قیمت
+}
+
+impl TickPresets {
+    /// ضریب پیش‌فرض پنل تازه از نوع `kind` برای نماد داده شده؛
+    /// در صرافی‌هایی که عمق را سمت سرور تجمیع می‌کنند به نزدیک‌ترین ضریب مجاز نگاشت می‌شود
+    pub fn multiplier_for(
+        &self,
+        kind: ContentKind,
+        ticker_info: TickerInfo,
+    ) -> Option<TickMultiplier> {
+        let key = SerTicker::from_parts(ticker_info.ticker);
+        let base_ticksize: f32 = ticker_info.min_ticksize.into();
+
+        let from_ticker = self
+            .tickers
+            .iter()
+            .find(|(ticker, _)| *ticker == key)
+            .and_then(|(_, preset)| pick(kind, preset.heatmap, preset.footprint));
+
+        let multiplier = from_ticker.or_else(|| {
+            self.magnitudes
+                .iter()
+                .filter(|row| base_ticksize >= row.min_ticksize)
+                .max_by(|a, b| a.min_ticksize.total_cmp(&b.min_ticksize))
+                .and_then(|row| pick(kind, row.heatmap, row.footprint))
+        })?;
+
+        // فوت‌پرینت همیشه سمت برنامه گروه‌بندی می‌شود و هر ضریبی را می‌پذیرد
+        if kind == ContentKind::HeatmapChart && !ticker_info.exchange().is_depth_client_aggr() {
+            Some(nearest_allowed(base_ticksize, multiplier))
+        } else {
+            Some(multiplier)
+        }
+    }
+
+    /// ذخیره ضریب فعلی یک پنل به عنوان پیش‌فرض نماد آن؛ `false` اگر نوع نمودار پشتیبانی نشود
+    pub fn save_for_ticker(
+        &mut self,
+        kind: ContentKind,
+        ticker_info: TickerInfo,
+        multiplier: TickMultiplier,
+    ) -> bool {
+        let key = SerTicker::from_parts(ticker_info.ticker);
+
+        let index = match self.tickers.iter().position(|(ticker, _)| *ticker == key) {
+            Some(index) => index,
+            None => {
+                self.tickers.push((key, TickerPreset::default()));
+                self.tickers.len() - 1
+            }
+        };
+        let preset = &mut self.tickers[index].1;
+
+        match kind {
+            ContentKind::HeatmapChart => preset.heatmap = Some(multiplier),
+            ContentKind::FootprintChart => preset.footprint = Some(multiplier),
+            _ => {
+                if *preset == TickerPreset::default() {
+                    self.tickers.remove(index);
+                }
+                return false;
+            }
+        }
+        true
+    }
+}
+
+fn pick(
+    kind: ContentKind,
+    heatmap: Option<TickMultiplier>,
+    footprint: Option<TickMultiplier>,
+) -> Option<TickMultiplier> {
+    match kind {
+        ContentKind::HeatmapChart => heatmap,
+        ContentKind::FootprintChart => footprint,
+        _ => None,
+    }
+}
+
+/// نزدیک‌ترین ضریب (در مقیاس لگاریتمی) که تجمیع سمت سرور هایپرلیکوئید برای این گام پایه ارائه می‌کند
+fn nearest_allowed(base_ticksize: f32, multiplier: TickMultiplier) -> TickMultiplier {
+    let allowed = allowed_multipliers_for_base_tick(base_ticksize);
+    if allowed.contains(&multiplier.0) {
+        return multiplier;
+    }
+
+    let target = f32::from(multiplier.0.max(1)).log10();
+    allowed
+        .iter()
+        .copied()
+        .min_by(|a, b| {
+            let da = (f32::from(*a).log10() - target).abs();
+            let db = (f32::from(*b).log10() - target).abs();
+            da.total_cmp(&db)
+        })
+        .map_or(multiplier, TickMultiplier)
+}
+
+static TICK_PRESETS: LazyLock<RwLock<TickPresets>> =
+    LazyLock::new(|| RwLock::new(TickPresets::default()));
+
+/// تنظیم سراسری ضرایب گام پیش‌فرض پنل‌های جدید
+pub fn set_tick_presets(presets: TickPresets) {
+    *TICK_PRESETS.write().unwrap_or_else(|e| e.into_inner()) = presets;
+}
+
+pub fn tick_presets() -> TickPresets {
+    TICK_PRESETS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exchange::Ticker;
+    use exchange::adapter::Exchange;
+
+    fn ticker_info(symbol: &str, exchange: Exchange, min_ticksize: f32) -> TickerInfo {
+        TickerInfo::new(Ticker::new(symbol, exchange), min_ticksize, 0.001, None)
+    }
+
+    #[test]
+    fn ticker_override_wins_over_magnitude_table() {
+        let btc = ticker_info("BTCUSDT", Exchange::BinanceLinear, 0.1);
+        let micro = ticker_info("PEPEUSDT", Exchange::BinanceLinear, 0.000_000_1);
+
+        let mut presets = TickPresets {
+            tickers: vec![],
+            magnitudes: vec![
+                MagnitudePreset {
+                    min_ticksize: 0.0,
+                    heatmap: Some(TickMultiplier(1)),
+                    footprint: None,
+                },
+                MagnitudePreset {
+                    min_ticksize: 0.01,
+                    heatmap: Some(TickMultiplier(10)),
+                    footprint: Some(TickMultiplier(25)),
+                },
+            ],
+        };
+
+        assert_eq!(
+            presets.multiplier_for(ContentKind::HeatmapChart, btc),
+            Some(TickMultiplier(10))
+        );
+        assert_eq!(
+            presets.multiplier_for(ContentKind::HeatmapChart, micro),
+            Some(TickMultiplier(1))
+        );
+        assert_eq!(
+            presets.multiplier_for(ContentKind::FootprintChart, micro),
+            None
+        );
+
+        assert!(presets.save_for_ticker(ContentKind::FootprintChart, btc, TickMultiplier(100)));
+        assert!(!presets.save_for_ticker(ContentKind::Ladder, micro, TickMultiplier(5)));
+        assert_eq!(presets.tickers.len(), 1);
+        assert_eq!(
+            presets.multiplier_for(ContentKind::FootprintChart, btc),
+            Some(TickMultiplier(100))
+        );
+        // نقشه حرارتی همان نماد هنوز از جدول بزرگی قیمت پیروی می‌کند
+        assert_eq!(
+            presets.multiplier_for(ContentKind::HeatmapChart, btc),
+            Some(TickMultiplier(10))
+        );
+    }
+
+    #[test]
+    fn server_aggregated_heatmaps_snap_to_allowed_multipliers() {
+        let hype = ticker_info("HYPE", Exchange::HyperliquidLinear, 0.001);
+
+        let mut presets = TickPresets::default();
+        presets.save_for_ticker(ContentKind::HeatmapChart, hype, TickMultiplier(25));
+        presets.save_for_ticker(ContentKind::FootprintChart, hype, TickMultiplier(25));
+
+        assert_eq!(
+            presets.multiplier_for(ContentKind::HeatmapChart, hype),
+            Some(TickMultiplier(10))
+        );
+        assert_eq!(
+            presets.multiplier_for(ContentKind::FootprintChart, hype),
+            Some(TickMultiplier(25))
+        );
+    }
+}
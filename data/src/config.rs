@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 // ماژول‌های مربوط به بخش‌های مختلف تنظیمات برنامه
+pub mod scanner;
 pub mod sidebar;
 pub mod state;
 pub mod theme;
@@ -30,3 +31,38 @@ impl From<ScaleFactor> for f32 {
         value.0
     }
 }
+
+/// فاصله ذخیره خودکار وضعیت برنامه بر حسب ثانیه؛ صفر یعنی غیرفعال
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub struct AutosaveInterval(u64);
+
+impl AutosaveInterval {
+    pub const ALL: [AutosaveInterval; 5] = [
+        AutosaveInterval(0),
+        AutosaveInterval(30),
+        AutosaveInterval(60),
+        AutosaveInterval(300),
+        AutosaveInterval(900),
+    ];
+
+    /// مدت زمان بین ذخیره‌ها؛ در حالت غیرفعال `None` برمی‌گرداند
+    pub fn duration(self) -> Option<std::time::Duration> {
+        (self.0 > 0).then(|| std::time::Duration::from_secs(self.0))
+    }
+}
+
+impl Default for AutosaveInterval {
+    fn default() -> Self {
+        Self(60)
+    }
+}
+
+impl std::fmt::Display for AutosaveInterval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            0 => write!(f, "Off"),
+            secs if secs % 60 == 0 => write!(f, "{}m", secs / 60),
+            secs => write!(f, "{secs}s"),
+        }
+    }
+}
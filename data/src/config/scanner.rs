@@ -0,0 +1,189 @@
+use exchange::Kline;
+use serde::{Deserialize, Serialize};
+
+/// تعداد کندل‌های ساعتی که برای محاسبه معیارها دریافت می‌شوند
+pub const LOOKBACK_BARS: usize = 48;
+
+const ATR_PERIOD: usize = 14; // دوره میانگین دامنه واقعی (ATR)
+const VOLUME_AVG_BARS: usize = 24; // تعداد کندل‌های بسته شده برای میانگین حجم
+
+/// معیارهای محاسبه شده در اسکنر بازار
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum Metric {
+    Change1h,    // درصد تغییر قیمت در ۱ ساعت
+    Change4h,    // درصد تغییر قیمت در ۴ ساعت
+    Change24h,   // درصد تغییر قیمت روزانه
+    VolumeSpike, // نسبت حجم آخرین ساعت به میانگین
+    Volatility,  // ATR به صورت درصدی از قیمت
+}
+
+impl Metric {
+    pub const ALL: [Metric; 5] = [
+        Metric::Change1h,
+        Metric::Change4h,
+        Metric::Change24h,
+        Metric::VolumeSpike,
+        Metric::Volatility,
+    ];
+}
+
+impl std::fmt::Display for Metric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Metric::Change1h => write!(f, "1h %"),
+            Metric::Change4h => write!(f, "4h %"),
+            Metric::Change24h => write!(f, "24h %"),
+            Metric::VolumeSpike => write!(f, "Vol spike"),
+            Metric::Volatility => write!(f, "ATR %"),
+        }
+    }
+}
+
+/// نوع مقایسه در قوانین برجسته‌سازی
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Comparison {
+    Above, // بزرگ‌تر از آستانه
+    Below, // کوچک‌تر از آستانه
+}
+
+impl Comparison {
+    pub const ALL: [Comparison; 2] = [Comparison::Above, Comparison::Below];
+}
+
+impl std::fmt::Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Comparison::Above => write!(f, ">"),
+            Comparison::Below => write!(f, "<"),
+        }
+    }
+}
+
+/// قانون برجسته‌سازی ردیف‌های اسکنر بر اساس آستانه یک معیار
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct HighlightRule {
+    pub metric: Metric,         // معیار مورد بررسی
+    pub comparison: Comparison, // نوع مقایسه
+    pub threshold: f32,         // مقدار آستانه
+}
+
+impl HighlightRule {
+    /// بررسی تطابق معیارهای یک نماد با این قانون
+    pub fn matches(&self, metrics: &ScanMetrics) -> bool {
+        metrics
+            .get(self.metric)
+            .is_some_and(|value| match self.comparison {
+                Comparison::Above => value > self.threshold,
+                Comparison::Below => value < self.threshold,
+            })
+    }
+}
+
+impl std::fmt::Display for HighlightRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.metric, self.comparison, self.threshold)
+    }
+}
+
+/// تنظیمات ذخیره شده اسکنر بازار
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Scanner {
+    pub rules: Vec<HighlightRule>, // قوانین برجسته‌سازی
+    pub top_n: usize,              // تعداد نمادهای پرحجم که اسکن می‌شوند
+}
+
+impl Default for Scanner {
+    fn default() -> Self {
+        Self {
+            rules: vec![HighlightRule {
+                metric: Metric::VolumeSpike,
+                comparison: Comparison::Above,
+                threshold: 3.0,
+            }],
+            top_n: 40,
+        }
+    }
+}
+
+/// معیارهای محاسبه شده برای یک نماد
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScanMetrics {
+    pub change_1h: Option<f32>,    // درصد تغییر ۱ ساعته
+    pub change_4h: Option<f32>,    // درصد تغییر ۴ ساعته
+    pub change_24h: f32,           // درصد تغییر روزانه (از آمار نماد)
+    pub volume_spike: Option<f32>, // نسبت حجم به میانگین
+    pub volatility: Option<f32>,   // ATR درصدی
+}
+
+impl ScanMetrics {
+    /// محاسبه معیارها از کندل‌های ساعتی (مرتب شده از قدیم به جدید)
+    pub fn from_klines(klines: &[Kline], change_24h: f32) -> Self {
+        let closes = klines.iter().map(|k| k.close.to_f32()).collect::<Vec<_>>();
+
+        let change_over = |bars: usize| {
+            let last = *closes.last()?;
+            let base = *closes.get(closes.len().checked_sub(bars + 1)?)?;
+            (base > 0.0).then(|| (last - base) / base * 100.0)
+        };
+
+        Self {
+            change_1h: change_over(1),
+            change_4h: change_over(4),
+            change_24h,
+            volume_spike: volume_spike(klines),
+            volatility: volatility(klines),
+        }
+    }
+
+    pub fn get(&self, metric: Metric) -> Option<f32> {
+        match metric {
+            Metric::Change1h => self.change_1h,
+            Metric::Change4h => self.change_4h,
+            Metric::Change24h => Some(self.change_24h),
+            Metric::VolumeSpike => self.volume_spike,
+            Metric::Volatility => self.volatility,
+        }
+    }
+}
+
+/// نسبت حجم آخرین کندل بسته شده به میانگین کندل‌های قبلی؛
+/// آخرین کندل هنوز در حال شکل‌گیری است و در نظر گرفته نمی‌شود
+fn volume_spike(klines: &[Kline]) -> Option<f32> {
+    let last_closed = klines.len().checked_sub(2)?;
+    let window = &klines[last_closed.saturating_sub(VOLUME_AVG_BARS)..last_closed];
+    if window.is_empty() {
+        return None;
+    }
+
+    let avg = window.iter().map(total_volume).sum::<f32>() / window.len() as f32;
+    (avg > 0.0).then(|| total_volume(&klines[last_closed]) / avg)
+}
+
+/// میانگین دامنه واقعی (ATR) به صورت درصدی از آخرین قیمت
+fn volatility(klines: &[Kline]) -> Option<f32> {
+    let start = klines.len().checked_sub(ATR_PERIOD + 1)?;
+    let atr = klines[start..]
+        .windows(2)
+        .map(|pair| {
+            let prev_close = pair[0].close.to_f32();
+            let (high, low) = (pair[1].high.to_f32(), pair[1].low.to_f32());
+            (high - low)
+                .max((high - prev_close).abs())
+                .max((low - prev_close).abs())
+        })
+        .sum::<f32>()
+        / ATR_PERIOD as f32;
+
+    let last_close = klines.last()?.close.to_f32();
+    (last_close > 0.0).then(|| atr / last_close * 100.0)
+}
+
+/// حجم کل یک کندل؛ برخی صرافی‌ها تفکیک خرید/فروش ندارند و مقدار اول را -1 می‌گذارند
+fn total_volume(kline: &Kline) -> f32 {
+    if kline.volume.0 < 0.0 {
+        kline.volume.1
+    } else {
+        kline.volume.0 + kline.volume.1
+    }
+}
@@ -9,8 +9,10 @@ pub struct Sidebar {
     pub position: Position,                   // موقعیت نوار کناری (چپ یا راست)
     #[serde(skip)]
     pub active_menu: Option<Menu>,            // منوی فعال فعلی
-    #[serde(default)]
+    #[serde(deserialize_with = "crate::util::ok_or_default")]
     pub tickers_table: Option<tickers_table::Settings>, // تنظیمات جدول نمادها
+    pub pinned: bool,  // ثابت بودن نوار؛ در حالت غیرثابت با خروج نشانگر جمع می‌شود
+    pub compact: bool, // ردیف‌های فشرده در جدول نمادها
 }
 
 impl Sidebar {
@@ -24,6 +26,11 @@ impl Sidebar {
         self.position = position;
     }
 
+    /// جمع شدن خودکار فقط وقتی که نوار ثابت نیست، نشانگر روی آن نیست و منویی باز نیست
+    pub fn is_collapsed(&self, hovered: bool) -> bool {
+        !self.pinned && !hovered && self.active_menu.is_none()
+    }
+
     /// بررسی اینکه آیا یک منوی خاص فعال است یا خیر
     pub fn is_menu_active(&self, menu: Menu) -> bool {
         self.active_menu == Some(menu)
@@ -41,6 +48,8 @@ impl Default for Sidebar {
             position: Position::Left,
             active_menu: None,
             tickers_table: None,
+            pinned: true,
+            compact: false,
         }
     }
 }
@@ -73,8 +82,39 @@ impl std::fmt::Display for Position {
 /// انواع منوهای موجود در نوار کناری
 #[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
 pub enum Menu {
-    Layout,      // مدیریت چیدمان
-    Settings,    // تنظیمات عمومی
-    Audio,       // تنظیمات صوتی
-    ThemeEditor, // ویرایشگر تم
+    Layout,       // مدیریت چیدمان
+    Settings,     // تنظیمات عمومی
+    Audio,        // تنظیمات صوتی
+    ThemeEditor,  // ویرایشگر تم
+    Connections,  // وضعیت اتصال جریان‌ها
+    Measurements, // تاریخچه اندازه‌گیری‌های خط‌کش
+    Scanner,      // اسکنر حجم و نوسان بازار
+    CommandPalette, // جستجو و اجرای فرمان‌ها (Ctrl+K)
+    Replay,       // ضبط و بازپخش جلسه
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn older_configs_stay_pinned() {
+        let sidebar: Sidebar = serde_json::from_str(r#"{"position":"Right"}"#).unwrap();
+        assert!(sidebar.pinned);
+        assert!(!sidebar.compact);
+        assert_eq!(sidebar.position, Position::Right);
+    }
+
+    #[test]
+    fn open_menu_keeps_unpinned_sidebar_expanded() {
+        let mut sidebar = Sidebar {
+            pinned: false,
+            ..Sidebar::default()
+        };
+        assert!(sidebar.is_collapsed(false));
+        assert!(!sidebar.is_collapsed(true));
+
+        sidebar.set_menu(Menu::Settings);
+        assert!(!sidebar.is_collapsed(false));
+    }
 }
@@ -1,7 +1,11 @@
-use super::ScaleFactor;
+use super::{AutosaveInterval, ScaleFactor};
+use super::scanner::Scanner;
 use super::sidebar::Sidebar;
 use super::timezone::UserTimezone;
 use crate::layout::WindowSpec;
+use crate::stale::StaleThreshold;
+use crate::tape_speed::TapeSpeedWindow;
+use crate::util::NumberFormat;
 use crate::{AudioStream, Layout, Theme};
 
 use serde::{Deserialize, Serialize};
@@ -20,6 +24,7 @@ pub struct State {
     pub layout_manager: Layouts,          // مدیریت چیدمان‌ها
     pub selected_theme: Theme,            // تم انتخاب شده
     pub custom_theme: Option<Theme>,      // تم سفارشی (در صورت وجود)
+    pub custom_themes: Vec<Theme>,        // تم‌های سفارشی نام‌گذاری شده
     pub main_window: Option<WindowSpec>,  // مشخصات پنجره اصلی
     pub timezone: UserTimezone,           // منطقه زمانی کاربر
     pub sidebar: Sidebar,                 // تنظیمات نوار کناری
@@ -27,25 +32,64 @@ pub struct State {
     pub audio_cfg: AudioStream,           // تنظیمات صوتی
     pub trade_fetch_enabled: bool,        // آیا دریافت تاریخچه معاملات فعال است؟
     pub size_in_quote_ccy: exchange::SizeUnit, // واحد نمایش حجم (پایه یا کوت)
+    pub inverse_size_conversion: bool,    // تبدیل اندازه قراردادهای معکوس به واحد نمایش
+    pub scanner: Scanner,                      // تنظیمات اسکنر بازار
+    pub autosave_interval: AutosaveInterval,   // فاصله ذخیره خودکار وضعیت
+    pub number_format: NumberFormat,           // قالب‌بندی سراسری اعداد (اختصار و جداکننده هزارگان)
+    pub tape_speed_window: TapeSpeedWindow,    // طول پنجره محاسبه سرعت نوار معاملات
+    pub stale_threshold: StaleThreshold,       // آستانه کهنه شدن داده‌های پنل‌ها
+    pub network: exchange::network::NetworkConfig, // پروکسی و دامنه‌های جایگزین صرافی‌ها
+    #[serde(default = "default_candle_countdown")]
+    pub candle_countdown: bool, // نمایش شمارش معکوس بسته شدن کندل روی محور قیمت
+    pub label_font: crate::chart::LabelFont, // اندازه و قلم برچسب‌های محور و نشانگر نمودارها
+    pub default_indicators: crate::chart::indicator::DefaultIndicators, // اندیکاتورهای پنل‌های جدید
+    pub performance_profile: crate::performance::PerformanceProfile, // مصالحه کیفیت نمایش و نرخ فریم
+    pub replay: crate::replay::RecordingCfg,                         // تنظیمات ضبط جلسه برای بازپخش
+    pub focus_follows_hover: bool, // جابجایی فوکوس پنل‌ها با عبور ماوس به جای کلیک
+    pub tick_presets: crate::chart::tick_preset::TickPresets, // ضرایب گام پیش‌فرض پنل‌های جدید
+}
+
+fn default_candle_countdown() -> bool {
+    true
 }
 
 impl State {
+    /// وضعیت یک نصب تازه؛ برخلاف `Default`، پیش‌فرض‌های serde فیلدها (مثل شمارش معکوس کندل)
+    /// هم اعمال می‌شوند و چیدمان پیش‌فرض به عنوان تنها چیدمان قرار می‌گیرد
+    pub fn fresh() -> Self {
+        let layout = crate::Layout::default_for_new_state();
+
+        State {
+            layout_manager: Layouts {
+                active_layout: Some(layout.name.clone()),
+                layouts: vec![layout],
+            },
+            size_in_quote_ccy: exchange::SizeUnit::Base,
+            ..serde_json::from_str("{}").unwrap_or_default()
+        }
+    }
+
     /// ایجاد یک نمونه جدید از وضعیت برنامه با استفاده از اجزای مختلف
     pub fn from_parts(
         layout_manager: Layouts,
         selected_theme: Theme,
         custom_theme: Option<Theme>,
+        custom_themes: Vec<Theme>,
         main_window: Option<WindowSpec>,
         timezone: UserTimezone,
         sidebar: Sidebar,
         scale_factor: ScaleFactor,
         audio_cfg: AudioStream,
         volume_size_unit: exchange::SizeUnit,
+        scanner: Scanner,
+        autosave_interval: AutosaveInterval,
+        replay: crate::replay::RecordingCfg,
     ) -> Self {
         State {
             layout_manager,
             selected_theme: Theme(selected_theme.0),
             custom_theme: custom_theme.map(|t| Theme(t.0)),
+            custom_themes,
             main_window,
             timezone,
             sidebar,
@@ -53,6 +97,20 @@ impl State {
             audio_cfg,
             trade_fetch_enabled: exchange::fetcher::is_trade_fetch_enabled(),
             size_in_quote_ccy: volume_size_unit,
+            inverse_size_conversion: exchange::is_inverse_conversion_enabled(),
+            scanner,
+            autosave_interval,
+            number_format: crate::util::number_format(),
+            tape_speed_window: crate::tape_speed::tape_speed_window(),
+            stale_threshold: crate::stale::stale_threshold(),
+            network: exchange::network::network_config(),
+            candle_countdown: crate::chart::is_candle_countdown_enabled(),
+            label_font: crate::chart::label_font(),
+            default_indicators: crate::chart::indicator::default_indicators(),
+            performance_profile: crate::performance::performance_profile(),
+            replay,
+            focus_follows_hover: crate::layout::dashboard::is_focus_follows_hover(),
+            tick_presets: crate::chart::tick_preset::tick_presets(),
         }
     }
 }
@@ -11,6 +11,7 @@ use palette::{
     rgb::{Rgb, Rgba},
 };
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 
 /// ساختار نگهدارنده تم برنامه (پوششی بر روی تم Iced)
 #[derive(Debug, Clone)]
@@ -60,11 +61,10 @@ impl Serialize for Theme {
             let is_default_theme = custom.to_string() == "Flowsurface";
             let ser_theme = SerTheme {
                 name: if is_default_theme {
-                    "flowsurface"
+                    "flowsurface".to_string()
                 } else {
-                    "custom"
-                }
-                .to_string(),
+                    custom.to_string()
+                },
                 palette: if is_default_theme {
                     None
                 } else {
@@ -147,22 +147,89 @@ impl<'de> Deserialize<'de> for Theme {
 
         let theme = match serialized.name.as_str() {
             "flowsurface" => Theme::default().0,
-            "custom" => {
-                if let Some(palette) = serialized.palette {
-                    iced_core::Theme::Custom(Custom::new("Custom".to_string(), palette).into())
-                } else {
+            name => {
+                let Some(palette) = serialized.palette else {
                     return Err(serde::de::Error::custom(
                         "Custom theme missing palette data",
                     ));
-                }
+                };
+                validate_palette(&palette).map_err(serde::de::Error::custom)?;
+
+                // نسخه‌های قدیمی تنها یک تم سفارشی با نام "custom" ذخیره می‌کردند
+                let name = if name == "custom" { "Custom" } else { name };
+                iced_core::Theme::Custom(Custom::new(name.to_string(), palette).into())
             }
-            _ => return Err(serde::de::Error::custom("Invalid theme")),
         };
 
         Ok(Theme(theme))
     }
 }
 
+/// بررسی معتبر بودن مقادیر رنگ‌های یک پالت (محدوده ۰ تا ۱)
+pub fn validate_palette(palette: &Palette) -> Result<(), String> {
+    let components = [
+        ("background", palette.background),
+        ("text", palette.text),
+        ("primary", palette.primary),
+        ("success", palette.success),
+        ("danger", palette.danger),
+        ("warning", palette.warning),
+    ];
+
+    for (name, color) in components {
+        let channels = [color.r, color.g, color.b, color.a];
+        if channels.iter().any(|c| !(0.0..=1.0).contains(c)) {
+            return Err(format!("Invalid {name} color in palette"));
+        }
+    }
+
+    Ok(())
+}
+
+/// مسیر فایل خروجی یک تم سفارشی در پوشه تم‌ها
+pub fn theme_file_path(name: &str) -> PathBuf {
+    let file_name = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+
+    crate::data_path(Some("themes")).join(format!("{file_name}.json"))
+}
+
+/// ذخیره یک تم سفارشی در فایل JSON برای اشتراک‌گذاری
+pub fn export_theme(theme: &Theme) -> Result<PathBuf, String> {
+    let iced_core::Theme::Custom(custom) = &theme.0 else {
+        return Err("Only custom themes can be exported".to_string());
+    };
+
+    let path = theme_file_path(&custom.to_string());
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+
+    let json = serde_json::to_string_pretty(theme).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| e.to_string())?;
+
+    Ok(path)
+}
+
+/// بارگذاری یک تم سفارشی از فایل JSON و اعتبارسنجی پالت آن
+pub fn import_theme(path: &Path) -> Result<Theme, String> {
+    let json = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let theme: Theme = serde_json::from_str(&json).map_err(|e| e.to_string())?;
+
+    match theme.0 {
+        iced_core::Theme::Custom(_) => Ok(theme),
+        _ => Err("File does not contain a custom theme".to_string()),
+    }
+}
+
 /// تبدیل کد رنگ هگز (Hex) به ساختار Color
 pub fn hex_to_color(hex: &str) -> Option<Color> {
     if hex.len() == 7 || hex.len() == 9 {
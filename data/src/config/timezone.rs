@@ -1,30 +1,57 @@
 use std::fmt;
+use std::str::FromStr;
+use std::sync::RwLock;
 
-use chrono::DateTime;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone};
 use serde::{Deserialize, Serialize};
 
+/// اختلاف‌های ثابت غیر ساعتی رایج با UTC (بر حسب دقیقه)
+const FRACTIONAL_OFFSETS: [i32; 11] = [-570, -210, 210, 270, 330, 345, 390, 525, 570, 630, 765];
+
 /// انواع مناطق زمانی قابل انتخاب توسط کاربر
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum UserTimezone {
     #[default]
-    Utc,   // زمان هماهنگ جهانی (UTC)
-    Local, // زمان محلی سیستم کاربر
+    Utc, // زمان هماهنگ جهانی (UTC)
+    Local,                // زمان محلی سیستم کاربر
+    Named(chrono_tz::Tz), // منطقه زمانی پایگاه داده IANA؛ ساعت تابستانی رعایت می‌شود
+    Fixed(i32),           // اختلاف ثابت با UTC بر حسب دقیقه
 }
 
 impl UserTimezone {
+    /// همه گزینه‌های قابل انتخاب: UTC، محلی، اختلاف‌های ثابت و مناطق نام‌دار
+    pub fn options() -> Vec<UserTimezone> {
+        let mut offsets = (-12..=14)
+            .filter(|hours| *hours != 0)
+            .map(|hours| hours * 60)
+            .chain(FRACTIONAL_OFFSETS)
+            .collect::<Vec<_>>();
+        offsets.sort_unstable();
+
+        [UserTimezone::Utc, UserTimezone::Local]
+            .into_iter()
+            .chain(offsets.into_iter().map(UserTimezone::Fixed))
+            .chain(chrono_tz::TZ_VARIANTS.into_iter().map(UserTimezone::Named))
+            .collect()
+    }
+
+    /// تبدیل یک زمان به منطقه زمانی کاربر با اختلافی که در همان لحظه برقرار بوده است
+    pub fn to_fixed_offset<Tz: TimeZone>(&self, datetime: &DateTime<Tz>) -> DateTime<FixedOffset> {
+        match self {
+            UserTimezone::Utc => datetime.with_timezone(&chrono::Utc).fixed_offset(),
+            UserTimezone::Local => datetime.with_timezone(&chrono::Local).fixed_offset(),
+            UserTimezone::Named(tz) => datetime.with_timezone(tz).fixed_offset(),
+            UserTimezone::Fixed(minutes) => FixedOffset::east_opt(minutes * 60).map_or_else(
+                || datetime.with_timezone(&chrono::Utc).fixed_offset(),
+                |offset| datetime.with_timezone(&offset),
+            ),
+        }
+    }
+
     /// تبدیل برچسب زمانی UTC به منطقه زمانی مناسب و قالب‌بندی آن بر اساس بازه زمانی (Timeframe)
     pub fn format_timestamp(&self, timestamp: i64, timeframe: exchange::Timeframe) -> String {
         if let Some(datetime) = DateTime::from_timestamp(timestamp, 0) {
-            match self {
-                UserTimezone::Local => {
-                    let time_with_zone = datetime.with_timezone(&chrono::Local);
-                    Self::format_by_timeframe(&time_with_zone, timeframe)
-                }
-                UserTimezone::Utc => {
-                    let time_with_zone = datetime.with_timezone(&chrono::Utc);
-                    Self::format_by_timeframe(&time_with_zone, timeframe)
-                }
-            }
+            Self::format_by_timeframe(&self.to_fixed_offset(&datetime), timeframe)
         } else {
             String::new()
         }
@@ -55,36 +82,146 @@ impl UserTimezone {
     /// قالب‌بندی برچسب زمانی برای نمایش در محل نشانگر (Crosshair) با جزئیات بیشتر
     pub fn format_crosshair_timestamp(&self, timestamp_millis: i64, interval: u64) -> String {
         if let Some(datetime) = DateTime::from_timestamp_millis(timestamp_millis) {
+            let datetime = self.to_fixed_offset(&datetime);
+
             if interval < 10000 {
                 return datetime.format("%M:%S.%3f").to_string();
             }
 
-            match self {
-                UserTimezone::Local => datetime
-                    .with_timezone(&chrono::Local)
-                    .format("%a %b %-d %H:%M")
-                    .to_string(),
-                UserTimezone::Utc => datetime
-                    .with_timezone(&chrono::Utc)
-                    .format("%a %b %-d %H:%M")
-                    .to_string(),
-            }
+            datetime.format("%a %b %-d %H:%M").to_string()
         } else {
             String::new()
         }
     }
+
+    /// قالب‌بندی کامل یک زمان (میلی‌ثانیه) در منطقه زمانی کاربر، مانند `2024-01-01 03:30:00`
+    pub fn format_datetime(&self, millis: i64) -> String {
+        DateTime::from_timestamp_millis(millis).map_or_else(String::new, |datetime| {
+            self.to_fixed_offset(&datetime)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        })
+    }
+
+    /// قالب‌بندی کامل یک بازه زمانی (میلی‌ثانیه) به همراه منطقه زمانی، برای کپی در کلیپ‌بورد
+    pub fn format_range(&self, start_millis: i64, end_millis: i64) -> String {
+        format!(
+            "{} - {} ({self})",
+            self.format_datetime(start_millis),
+            self.format_datetime(end_millis)
+        )
+    }
+
+    /// خواندن زمان وارد شده توسط کاربر؛ تاریخ مطلق در همین منطقه زمانی (مانند `2024-03-01 14:30`)
+    /// یا فاصله نسبی از اکنون (مانند `-3d`، `-12h`، `-90m`) و بازگرداندن برچسب زمانی UTC به میلی‌ثانیه
+    pub fn parse_time_input(&self, input: &str, now_millis: u64) -> Option<u64> {
+        let input = input.trim();
+
+        if let Some(offset) = input.strip_prefix('-') {
+            return parse_relative_millis(offset.trim()).map(|ms| now_millis.saturating_sub(ms));
+        }
+
+        let naive = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"]
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(input, format).ok())
+            .or_else(|| {
+                NaiveDate::parse_from_str(input, "%Y-%m-%d")
+                    .ok()
+                    .and_then(|date| date.and_hms_opt(0, 0, 0))
+            })?;
+
+        u64::try_from(self.local_to_utc(naive)?.timestamp_millis()).ok()
+    }
+
+    /// تبدیل یک زمان محلی این منطقه به UTC؛ در ساعت‌های تکراری تغییر ساعت تابستانی زودترین لحظه
+    pub fn local_to_utc(&self, naive: NaiveDateTime) -> Option<DateTime<chrono::Utc>> {
+        match self {
+            UserTimezone::Utc => Some(naive.and_utc()),
+            UserTimezone::Local => chrono::Local
+                .from_local_datetime(&naive)
+                .earliest()
+                .map(|dt| dt.to_utc()),
+            UserTimezone::Named(tz) => tz
+                .from_local_datetime(&naive)
+                .earliest()
+                .map(|dt| dt.to_utc()),
+            UserTimezone::Fixed(minutes) => FixedOffset::east_opt(minutes * 60)
+                .and_then(|offset| offset.from_local_datetime(&naive).single())
+                .map(|dt| dt.to_utc()),
+        }
+    }
+
+    /// شروع روز محلی (نیمه‌شب در این منطقه) که زمان داده شده در آن قرار دارد، به میلی‌ثانیه UTC
+    pub fn day_start(&self, millis: u64) -> Option<u64> {
+        let datetime = DateTime::from_timestamp_millis(i64::try_from(millis).ok()?)?;
+        let midnight = self
+            .to_fixed_offset(&datetime)
+            .date_naive()
+            .and_hms_opt(0, 0, 0)?;
+
+        u64::try_from(self.local_to_utc(midnight)?.timestamp_millis()).ok()
+    }
+
+    /// اختلاف فعلی با UTC برای نمایش، مانند `UTC +03:30`
+    fn current_offset_label(&self) -> String {
+        let offset = self
+            .to_fixed_offset(&chrono::Utc::now())
+            .offset()
+            .local_minus_utc();
+        format_offset(offset / 60)
+    }
+}
+
+fn format_offset(minutes: i32) -> String {
+    let sign = if minutes < 0 { '-' } else { '+' };
+    let minutes = minutes.abs();
+    format!("UTC {sign}{:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+/// خواندن یک فاصله زمانی با واحد `s`، `m`، `h`، `d` یا `w` (مانند `3d`) بر حسب میلی‌ثانیه
+fn parse_relative_millis(value: &str) -> Option<u64> {
+    let unit_ms: u64 = match value.chars().last()? {
+        's' => 1_000,
+        'm' => 60_000,
+        'h' => 3_600_000,
+        'd' => 86_400_000,
+        'w' => 604_800_000,
+        _ => return None,
+    };
+
+    let amount = value[..value.len() - 1].trim().parse::<u64>().ok()?;
+    amount.checked_mul(unit_ms)
+}
+
+/// خواندن اختلاف ثابت به شکل `UTC+05:30` یا `UTC-3`
+fn parse_offset(value: &str) -> Option<i32> {
+    let rest = value
+        .get(..3)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("utc"))
+        .map(|_| value[3..].trim())?;
+
+    let (sign, rest) = match rest.chars().next()? {
+        '+' => (1, &rest[1..]),
+        '-' => (-1, &rest[1..]),
+        _ => return None,
+    };
+
+    let (hours, minutes) = rest.split_once(':').unwrap_or((rest, "0"));
+    let (hours, minutes) = (hours.parse::<i32>().ok()?, minutes.parse::<i32>().ok()?);
+
+    if hours > 14 || minutes >= 60 {
+        return None;
+    }
+    Some(sign * (hours * 60 + minutes))
 }
 
 impl fmt::Display for UserTimezone {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             UserTimezone::Utc => write!(f, "UTC"),
-            UserTimezone::Local => {
-                let local_offset = chrono::Local::now().offset().local_minus_utc();
-                let hours = local_offset / 3600;
-                let minutes = (local_offset % 3600) / 60;
-                write!(f, "Local (UTC {hours:+03}:{minutes:02})")
-            }
+            UserTimezone::Local => write!(f, "Local ({})", self.current_offset_label()),
+            UserTimezone::Named(tz) => write!(f, "{} ({})", tz.name(), self.current_offset_label()),
+            UserTimezone::Fixed(minutes) => write!(f, "{}", format_offset(*minutes)),
         }
     }
 }
@@ -98,7 +235,14 @@ impl<'de> Deserialize<'de> for UserTimezone {
         match timezone_str.to_lowercase().as_str() {
             "utc" => Ok(UserTimezone::Utc),
             "local" => Ok(UserTimezone::Local),
-            _ => Err(serde::de::Error::custom("Invalid UserTimezone")),
+            _ => parse_offset(&timezone_str)
+                .map(UserTimezone::Fixed)
+                .or_else(|| {
+                    chrono_tz::Tz::from_str(&timezone_str)
+                        .ok()
+                        .map(UserTimezone::Named)
+                })
+                .ok_or_else(|| serde::de::Error::custom("Invalid UserTimezone")),
         }
     }
 }
@@ -111,6 +255,78 @@ impl Serialize for UserTimezone {
         match self {
             UserTimezone::Utc => serializer.serialize_str("UTC"),
             UserTimezone::Local => serializer.serialize_str("Local"),
+            UserTimezone::Named(tz) => serializer.serialize_str(tz.name()),
+            UserTimezone::Fixed(minutes) => {
+                serializer.serialize_str(&format_offset(*minutes).replace(' ', ""))
+            }
+        }
+    }
+}
+
+static DISPLAY_TIMEZONE: RwLock<UserTimezone> = RwLock::new(UserTimezone::Utc);
+
+/// تنظیم سراسری منطقه زمانی برای نماهایی که زمان را هنگام دریافت داده قالب‌بندی می‌کنند
+pub fn set_display_timezone(timezone: UserTimezone) {
+    *DISPLAY_TIMEZONE.write().unwrap_or_else(|e| e.into_inner()) = timezone;
+}
+
+pub fn display_timezone() -> UserTimezone {
+    *DISPLAY_TIMEZONE.read().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn named_zones_follow_dst_and_round_trip() {
+        let tz = UserTimezone::Named(chrono_tz::Europe::London);
+        let winter = DateTime::from_timestamp(1_704_067_200, 0).unwrap(); // 2024-01-01
+        let summer = DateTime::from_timestamp(1_719_792_000, 0).unwrap(); // 2024-07-01
+
+        assert_eq!(tz.to_fixed_offset(&winter).offset().local_minus_utc(), 0);
+        assert_eq!(tz.to_fixed_offset(&summer).offset().local_minus_utc(), 3600);
+
+        for tz in [tz, UserTimezone::Fixed(330), UserTimezone::Fixed(-210)] {
+            let json = serde_json::to_string(&tz).unwrap();
+            assert_eq!(serde_json::from_str::<UserTimezone>(&json).unwrap(), tz);
         }
+        assert_eq!(parse_offset("UTC-3"), Some(-180));
+        assert_eq!(parse_offset("UTC+15:00"), None);
+    }
+
+    #[test]
+    fn time_input_accepts_dates_in_zone_and_relative_offsets() {
+        let now = 1_704_067_200_000; // 2024-01-01 00:00 UTC
+
+        assert_eq!(
+            UserTimezone::Utc.parse_time_input("-3d", now),
+            Some(now - 3 * 86_400_000)
+        );
+        assert_eq!(
+            UserTimezone::Utc.parse_time_input(" -90m ", now),
+            Some(now - 90 * 60_000)
+        );
+        assert_eq!(
+            UserTimezone::Fixed(210).parse_time_input("2024-01-01 03:30", 0),
+            Some(now)
+        );
+        assert_eq!(
+            UserTimezone::Named(chrono_tz::Europe::London).parse_time_input("2024-07-01", 0),
+            Some(1_719_788_400_000) // 2024-06-30 23:00 UTC
+        );
+        assert_eq!(UserTimezone::Utc.parse_time_input("-3y", now), None);
+        assert_eq!(UserTimezone::Utc.parse_time_input("65000", now), None);
+    }
+
+    #[test]
+    fn range_is_formatted_in_the_selected_zone() {
+        let start = 1_704_067_200_000; // 2024-01-01 00:00 UTC
+        let end = start + 90 * 60 * 1000;
+
+        assert_eq!(
+            UserTimezone::Fixed(210).format_range(start, end),
+            "2024-01-01 03:30:00 - 2024-01-01 05:00:00 (UTC +03:30)"
+        );
     }
 }
@@ -0,0 +1,105 @@
+//! مجموع نقدینگی خرید و فروش در یک بازه درصدی اطراف قیمت میانی، برای سنجش سریع عمق بازار
+
+use exchange::{depth::Depth, util::Price};
+use serde::{Deserialize, Serialize};
+
+/// تنظیمات نمایش مجموع عمق در نوار عنوان پنل
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DepthTotalsConfig {
+    pub enabled: bool,  // نمایش مجموع خرید/فروش و نسبت عدم تعادل
+    pub band_pct: f32, // نیم‌پهنای بازه بر حسب درصد از قیمت میانی
+}
+
+impl Default for DepthTotalsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            band_pct: 0.5,
+        }
+    }
+}
+
+impl DepthTotalsConfig {
+    pub const BAND_RANGE: std::ops::RangeInclusive<f32> = 0.1..=5.0;
+}
+
+/// مجموع حجم سفارش‌ها در هر سمت؛ واحد آن همان واحد حجم دفتر سفارش است (پایه یا مظنه)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DepthTotals {
+    pub bid: f32, // مجموع سفارش‌های خرید در بازه
+    pub ask: f32, // مجموع سفارش‌های فروش در بازه
+}
+
+impl DepthTotals {
+    /// محاسبه مجموع سطوح بین `mid × (1 ± band_pct / 100)`
+    ///
+    /// دفتر خالی یا یک‌طرفه مقدار صفر برمی‌گرداند، نه NaN.
+    pub fn compute(depth: &Depth, band_pct: f32) -> Self {
+        let Some(mid) = depth.mid_price() else {
+            return Self::default();
+        };
+
+        let mid = mid.to_f32_lossy();
+        let band = band_pct.max(0.0) / 100.0;
+        let lower = Price::from_f32(mid * (1.0 - band));
+        let upper = Price::from_f32(mid * (1.0 + band));
+
+        Self {
+            bid: depth.bids.range(lower..).map(|(_, qty)| qty).sum(),
+            ask: depth.asks.range(..=upper).map(|(_, qty)| qty).sum(),
+        }
+    }
+
+    /// نسبت عدم تعادل بین ۱- (فقط فروش) و ۱+ (فقط خرید)؛ صفر وقتی بازه خالی است
+    pub fn imbalance(&self) -> f32 {
+        let total = self.bid + self.ask;
+        if total > 0.0 {
+            (self.bid - self.ask) / total
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn depth(bids: &[(f32, f32)], asks: &[(f32, f32)]) -> Depth {
+        let levels = |levels: &[(f32, f32)]| {
+            levels
+                .iter()
+                .map(|&(price, qty)| (Price::from_f32(price), qty))
+                .collect::<BTreeMap<_, _>>()
+        };
+
+        Depth {
+            bids: levels(bids),
+            asks: levels(asks),
+        }
+    }
+
+    #[test]
+    fn sums_levels_within_band() {
+        let depth = depth(
+            &[(98.0, 10.0), (99.5, 2.0), (99.9, 1.0)],
+            &[(100.1, 1.0), (100.4, 1.0), (103.0, 10.0)],
+        );
+
+        let totals = DepthTotals::compute(&depth, 0.5);
+        assert_eq!(totals, DepthTotals { bid: 3.0, ask: 2.0 });
+        assert!((totals.imbalance() - 0.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn thin_book_yields_zeros() {
+        let empty = DepthTotals::compute(&Depth::default(), 0.5);
+        assert_eq!(empty, DepthTotals::default());
+        assert_eq!(empty.imbalance(), 0.0);
+
+        let one_sided = DepthTotals::compute(&depth(&[(99.0, 1.0)], &[]), 0.5);
+        assert_eq!(one_sided, DepthTotals::default());
+    }
+}
@@ -1,6 +1,9 @@
 pub use dashboard::Dashboard;
 pub use pane::Pane;
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::InternalError;
 
 // ماژول‌های مربوط به داشبورد و پنل‌ها
 pub mod dashboard;
@@ -22,6 +25,220 @@ impl Default for Layout {
     }
 }
 
+/// نام پوشه‌ای (داخل پوشه داده‌ها) که چیدمان‌های خروجی گرفته شده در آن ذخیره می‌شوند
+pub const LAYOUT_EXPORT_DIR: &str = "layouts";
+
+/// فایل چیدمانی که کاربر به عنوان پیش‌فرض نصب تازه و بازنشانی انتخاب کرده است
+pub const DEFAULT_LAYOUT_PATH: &str = "default-layout.json";
+
+/// فایل نوشته شده هنگام خروجی گرفتن از یک چیدمان
+#[derive(Debug, Clone)]
+pub struct ExportedLayout {
+    pub path: PathBuf, // مسیر کامل فایل JSON
+    pub renamed: bool, // فایلی هم‌نام وجود داشت و نام فایل شماره‌گذاری شد
+}
+
+impl Layout {
+    /// ذخیره این چیدمان به صورت یک فایل JSON قابل اشتراک‌گذاری؛ فایل‌های موجود بازنویسی
+    /// نمی‌شوند و در صورت تکرار نام، شماره‌ای به انتهای آن افزوده می‌شود
+    pub fn export(&self) -> Result<ExportedLayout, InternalError> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| InternalError::Layout(format!("Failed to serialize layout: {e}")))?;
+
+        let base_name = sanitize_file_name(&self.name);
+        let file_stem = unique_file_stem(&base_name, |stem| {
+            crate::data_path(Some(&format!("{LAYOUT_EXPORT_DIR}/{stem}.json"))).exists()
+        });
+        let file_name = format!("{LAYOUT_EXPORT_DIR}/{file_stem}.json");
+
+        crate::write_json_to_file(&json, &file_name)
+            .map_err(|e| InternalError::Layout(format!("Failed to write layout file: {e}")))?;
+
+        Ok(ExportedLayout {
+            path: crate::data_path(Some(&file_name)),
+            renamed: file_stem != base_name,
+        })
+    }
+
+    /// چیدمان داخلی برنامه: نمودار کندل و هیت‌مپ قرارداد دائمی BTCUSDT بایننس
+    pub fn builtin() -> Self {
+        use exchange::adapter::{
+            Exchange, KlinePriceSource, PersistDepth, PersistKline, PersistStreamKind,
+            StreamTicksize,
+        };
+
+        let ticker = exchange::Ticker::new("BTCUSDT", Exchange::BinanceLinear);
+
+        let kline = Pane::KlineChart {
+            layout: crate::chart::ViewConfig::default(),
+            kind: crate::chart::KlineChartKind::Candles,
+            stream_type: vec![PersistStreamKind::Kline(PersistKline {
+                ticker,
+                timeframe: exchange::Timeframe::M15,
+                source: KlinePriceSource::Trade,
+            })],
+            settings: pane::Settings::default(),
+            indicators: vec![],
+            link_group: None,
+        };
+
+        let heatmap = Pane::HeatmapChart {
+            layout: crate::chart::ViewConfig::default(),
+            studies: vec![],
+            stream_type: vec![PersistStreamKind::DepthAndTrades(PersistDepth {
+                ticker,
+                depth_aggr: StreamTicksize::Client,
+                push_freq: exchange::PushFrequency::ServerDefault,
+            })],
+            settings: pane::Settings::default(),
+            indicators: vec![],
+            link_group: None,
+        };
+
+        Self {
+            name: "Layout 1".to_string(),
+            dashboard: Dashboard {
+                pane: Pane::Split {
+                    axis: pane::Axis::Vertical,
+                    ratio: 0.5,
+                    a: Box::new(kline),
+                    b: Box::new(heatmap),
+                },
+                popout: vec![],
+            },
+        }
+    }
+
+    /// چیدمان نصب تازه و بازنشانی: چیدمان انتخاب شده توسط کاربر و در غیر این صورت چیدمان داخلی
+    pub fn default_for_new_state() -> Self {
+        let path = crate::data_path(Some(DEFAULT_LAYOUT_PATH));
+
+        if !path.exists() {
+            return Self::builtin();
+        }
+
+        Self::import(&path).unwrap_or_else(|e| {
+            log::warn!("Ignoring default layout file: {e}");
+            Self::builtin()
+        })
+    }
+
+    /// ذخیره این چیدمان به عنوان پیش‌فرض نصب تازه و بازنشانی، و بازگرداندن مسیر فایل
+    pub fn save_as_default(&self) -> Result<PathBuf, InternalError> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| InternalError::Layout(format!("Failed to serialize layout: {e}")))?;
+
+        crate::write_json_to_file(&json, DEFAULT_LAYOUT_PATH)
+            .map_err(|e| InternalError::Layout(format!("Failed to write layout file: {e}")))?;
+
+        Ok(crate::data_path(Some(DEFAULT_LAYOUT_PATH)))
+    }
+
+    /// خواندن یک چیدمان از فایل JSON
+    ///
+    /// قبل از تبدیل کامل، تیکرهایی که به صرافی‌های ناشناخته (کامپایل نشده) اشاره دارند بررسی می‌شوند
+    /// تا به جای خطای مبهم serde، پیام قابل فهمی نمایش داده شود.
+    pub fn import(path: &Path) -> Result<Self, InternalError> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            InternalError::Layout(format!("Failed to read {}: {e}", path.display()))
+        })?;
+
+        let value: serde_json::Value = serde_json::from_str(&contents)
+            .map_err(|e| InternalError::Layout(format!("Invalid layout file: {e}")))?;
+
+        let mut unsupported = vec![];
+        collect_unsupported_tickers(&value, &mut unsupported);
+
+        if !unsupported.is_empty() {
+            unsupported.sort();
+            unsupported.dedup();
+
+            return Err(InternalError::Layout(format!(
+                "Layout references unsupported exchange(s): {}",
+                unsupported.join(", ")
+            )));
+        }
+
+        serde_json::from_value(value)
+            .map_err(|e| InternalError::Layout(format!("Invalid layout file: {e}")))
+    }
+}
+
+/// فهرست فایل‌های چیدمان موجود در پوشه خروجی، مرتب شده بر اساس نام
+pub fn exported_layouts() -> Vec<PathBuf> {
+    let dir = crate::data_path(Some(LAYOUT_EXPORT_DIR));
+
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+
+    paths.sort();
+    paths
+}
+
+/// جایگزینی کاراکترهای غیرمجاز در نام فایل
+fn sanitize_file_name(name: &str) -> String {
+    let sanitized: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' || c == ' ' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    if sanitized.is_empty() {
+        "layout".to_string()
+    } else {
+        sanitized
+    }
+}
+
+/// اولین نام آزاد به صورت `name`، `name (2)`، `name (3)` و ...
+fn unique_file_stem(base: &str, exists: impl Fn(&str) -> bool) -> String {
+    if !exists(base) {
+        return base.to_string();
+    }
+
+    (2..)
+        .map(|n| format!("{base} ({n})"))
+        .find(|stem| !exists(stem))
+        .expect("Unbounded suffix range always yields a free name")
+}
+
+/// پیمایش بازگشتی JSON و جمع‌آوری پیشوند صرافی تیکرهایی که قابل تبدیل نیستند
+fn collect_unsupported_tickers(value: &serde_json::Value, out: &mut Vec<String>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                if key == "ticker"
+                    && let serde_json::Value::String(s) = value
+                    && serde_json::from_value::<exchange::Ticker>(value.clone()).is_err()
+                {
+                    let exchange = s.split_once(':').map_or(s.as_str(), |(ex, _)| ex);
+                    out.push(exchange.to_string());
+                    continue;
+                }
+                collect_unsupported_tickers(value, out);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_unsupported_tickers(item, out);
+            }
+        }
+        _ => {}
+    }
+}
+
 /// ساختار نگهدارنده ابعاد و موقعیت یک پنجره
 #[derive(Debug, Clone, Copy, Deserialize, Serialize)]
 pub struct Window<T = f32> {
@@ -71,3 +288,33 @@ impl From<(&iced_core::Point, &iced_core::Size)> for WindowSpec {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_layout_round_trips() {
+        let json = serde_json::to_value(Layout::builtin()).unwrap();
+
+        let mut unsupported = vec![];
+        collect_unsupported_tickers(&json, &mut unsupported);
+        assert!(unsupported.is_empty());
+
+        let layout: Layout = serde_json::from_value(json).unwrap();
+        let Pane::Split { a, b, .. } = layout.dashboard.pane else {
+            panic!("builtin layout should be split");
+        };
+        assert!(matches!(*a, Pane::KlineChart { .. }));
+        assert!(matches!(*b, Pane::HeatmapChart { .. }));
+    }
+
+    #[test]
+    fn export_name_gets_suffix_on_collision() {
+        let taken = ["Main", "Main (2)"];
+        let exists = |stem: &str| taken.contains(&stem);
+
+        assert_eq!(unique_file_stem("Scalping", exists), "Scalping");
+        assert_eq!(unique_file_stem("Main", exists), "Main (3)");
+    }
+}
@@ -1,8 +1,20 @@
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use super::{WindowSpec, pane::Pane};
 use crate::util::ok_or_default;
 
+static FOCUS_FOLLOWS_HOVER: AtomicBool = AtomicBool::new(false);
+
+/// فوکوس پنل‌ها با عبور نشانگر ماوس جابجا شود به جای کلیک (به صورت پیش‌فرض غیرفعال)
+pub fn set_focus_follows_hover(enabled: bool) {
+    FOCUS_FOLLOWS_HOVER.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_focus_follows_hover() -> bool {
+    FOCUS_FOLLOWS_HOVER.load(Ordering::Relaxed)
+}
+
 /// ساختار نگهدارنده اطلاعات یک داشبورد شامل پنل اصلی و پنجره‌های پاپ‌اوت
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct Dashboard {
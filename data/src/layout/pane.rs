@@ -1,9 +1,9 @@
-use exchange::adapter::PersistStreamKind;
-use exchange::{TickMultiplier, TickerInfo, Timeframe};
+use exchange::adapter::{Exchange, MarketKind, PersistStreamKind};
+use exchange::{PushFrequency, TickMultiplier, TickerInfo, Timeframe};
 use serde::{Deserialize, Serialize};
 
 use crate::chart::{comparison, heatmap, kline};
-use crate::panel::{ladder, timeandsales};
+use crate::panel::{depth_curve, ladder, spread, timeandsales};
 use crate::util::ok_or_default;
 
 use crate::chart::{
@@ -84,6 +84,27 @@ pub enum Pane {
         #[serde(deserialize_with = "ok_or_default", default)]
         link_group: Option<LinkGroup>,
     },
+    /// منحنی تجمعی عمق بازار
+    DepthCurve {
+        stream_type: Vec<PersistStreamKind>,
+        settings: Settings,
+        #[serde(deserialize_with = "ok_or_default", default)]
+        link_group: Option<LinkGroup>,
+    },
+    /// نمودار نرخ تأمین مالی و بیسیس قراردادهای دائمی
+    FundingChart {
+        stream_type: Vec<PersistStreamKind>,
+        settings: Settings,
+        #[serde(deserialize_with = "ok_or_default", default)]
+        link_group: Option<LinkGroup>,
+    },
+    /// مانیتور اسپرد یک دارایی بین دو بازار
+    SpreadChart {
+        stream_type: Vec<PersistStreamKind>,
+        settings: Settings,
+        #[serde(deserialize_with = "ok_or_default", default)]
+        link_group: Option<LinkGroup>,
+    },
 }
 
 impl Default for Pane {
@@ -99,6 +120,46 @@ pub struct Settings {
     pub tick_multiply: Option<exchange::TickMultiplier>, // ضریب گام قیمت
     pub visual_config: Option<VisualConfig>,             // تنظیمات بصری اختصاصی
     pub selected_basis: Option<Basis>,                   // مبنای انتخاب شده (زمان یا تیک)
+    pub round_steps: bool,                               // گرد کردن گام قیمت به اعداد رُند
+    pub depth_push_freq: Option<Timeframe>,              // فرکانس ارسال عمق؛ None یعنی خودکار
+    pub pin_ticker: bool, // نگه داشتن نماد پنل هنگام تعویض نماد گروه پیوند
+}
+
+impl Settings {
+    /// اندازه گام قیمت با ضریب داده شده؛ در حالت گام رُند، ضریب به نزدیک‌ترین گام رُند گرد می‌شود
+    pub fn tick_size(&self, tm: TickMultiplier, ticker_info: TickerInfo) -> f32 {
+        effective_multiplier(tm, self.round_steps).multiply_with_min_tick_size(ticker_info)
+    }
+}
+
+/// فرکانس ارسال جریان عمق یک پنل؛ انتخاب کاربر به نزدیک‌ترین سطح پشتیبانی شده صرافی نگاشت می‌شود
+/// و در حالت خودکار، هیت‌مپ از بازه زمانی خود پیروی می‌کند
+pub fn depth_push_freq(
+    content_kind: ContentKind,
+    exchange: Exchange,
+    basis: Option<Basis>,
+    selected: Option<Timeframe>,
+) -> PushFrequency {
+    if !exchange.is_custom_push_freq() {
+        return PushFrequency::ServerDefault;
+    }
+
+    match (content_kind, selected, basis) {
+        (ContentKind::HeatmapChart | ContentKind::Ladder, Some(tf), _) => {
+            exchange.nearest_push_freq(tf)
+        }
+        (ContentKind::HeatmapChart, None, Some(Basis::Time(tf)))
+            if exchange.supports_heatmap_timeframe(tf) =>
+        {
+            PushFrequency::Custom(tf)
+        }
+        _ => PushFrequency::ServerDefault,
+    }
+}
+
+/// ضریبی که در عمل برای گروه‌بندی قیمت‌ها استفاده می‌شود
+fn effective_multiplier(tm: TickMultiplier, round_steps: bool) -> TickMultiplier {
+    if round_steps { tm.rounded() } else { tm }
 }
 
 /// گروه‌های پیوند برای همگام‌سازی نمادها بین پنل‌های مختلف
@@ -147,6 +208,8 @@ pub enum VisualConfig {
     Kline(kline::Config),               // تنظیمات کندل‌استیک
     Ladder(ladder::Config),             // تنظیمات نردبان قیمت
     Comparison(comparison::Config),     // تنظیمات نمودار مقایسه‌ای
+    DepthCurve(depth_curve::Config),    // تنظیمات منحنی تجمعی عمق
+    Spread(spread::Config),             // تنظیمات مانیتور اسپرد
 }
 
 impl VisualConfig {
@@ -184,6 +247,20 @@ impl VisualConfig {
             _ => None,
         }
     }
+
+    pub fn depth_curve(&self) -> Option<depth_curve::Config> {
+        match self {
+            Self::DepthCurve(cfg) => Some(*cfg),
+            _ => None,
+        }
+    }
+
+    pub fn spread(&self) -> Option<spread::Config> {
+        match self {
+            Self::Spread(cfg) => Some(*cfg),
+            _ => None,
+        }
+    }
 }
 
 /// انواع محتواهای قابل نمایش در پنل‌ها
@@ -196,10 +273,13 @@ pub enum ContentKind {
     ComparisonChart,  // نمودار مقایسه‌ای
     TimeAndSales,     // لیست معاملات
     Ladder,           // نردبان قیمت
+    DepthCurve,       // منحنی تجمعی عمق
+    FundingChart,     // نرخ تأمین مالی و بیسیس
+    SpreadChart,      // اسپرد بین دو بازار
 }
 
 impl ContentKind {
-    pub const ALL: [ContentKind; 7] = [
+    pub const ALL: [ContentKind; 10] = [
         ContentKind::Starter,
         ContentKind::HeatmapChart,
         ContentKind::FootprintChart,
@@ -207,7 +287,20 @@ impl ContentKind {
         ContentKind::ComparisonChart,
         ContentKind::TimeAndSales,
         ContentKind::Ladder,
+        ContentKind::DepthCurve,
+        ContentKind::FundingChart,
+        ContentKind::SpreadChart,
     ];
+
+    /// آیا پنل با تعویض نماد گروه پیوند، نماد جدید را می‌پذیرد؛ پنل‌های چندنمادی نمادهای
+    /// خود را نگه می‌دارند و نمودار تأمین مالی در بازار اسپات داده‌ای ندارد
+    pub fn follows_group_ticker(self, market: MarketKind) -> bool {
+        match self {
+            ContentKind::Starter | ContentKind::ComparisonChart | ContentKind::SpreadChart => false,
+            ContentKind::FundingChart => market != MarketKind::Spot,
+            _ => true,
+        }
+    }
 }
 
 impl std::fmt::Display for ContentKind {
@@ -220,6 +313,9 @@ impl std::fmt::Display for ContentKind {
             ContentKind::ComparisonChart => "Comparison Chart",
             ContentKind::TimeAndSales => "Time&Sales",
             ContentKind::Ladder => "DOM/Ladder",
+            ContentKind::DepthCurve => "Depth Curve",
+            ContentKind::FundingChart => "Funding & Basis",
+            ContentKind::SpreadChart => "Spread Monitor",
         };
         write!(f, "{s}")
     }
@@ -243,6 +339,8 @@ impl PaneSetup {
         prev_base_ticker: Option<TickerInfo>,
         current_basis: Option<Basis>,
         current_tick_multiplier: Option<TickMultiplier>,
+        round_steps: bool,
+        selected_push_freq: Option<Timeframe>,
     ) -> Self {
         let exchange = base_ticker.ticker.exchange;
 
@@ -268,15 +366,25 @@ impl PaneSetup {
             ContentKind::CandlestickChart | ContentKind::ComparisonChart => {
                 Some(current_basis.unwrap_or(Basis::Time(Timeframe::M15)))
             }
-            ContentKind::Starter | ContentKind::TimeAndSales => None,
+            ContentKind::SpreadChart => Some(current_basis.unwrap_or(Basis::Time(Timeframe::M1))),
+            ContentKind::Starter
+            | ContentKind::TimeAndSales
+            | ContentKind::DepthCurve
+            | ContentKind::FundingChart => None,
         };
 
+        // ضریب پیش‌فرض کاربر برای این نماد یا بزرگی قیمت آن، فقط برای پنل‌های تازه
+        let preset =
+            || crate::chart::tick_preset::tick_presets().multiplier_for(content_kind, base_ticker);
+
         let tick_multiplier = match content_kind {
             ContentKind::HeatmapChart | ContentKind::Ladder => {
                 let tm = if !is_client_aggr && prev_is_client_aggr {
-                    TickMultiplier(10)
+                    preset().unwrap_or(TickMultiplier(10))
                 } else if let Some(tm) = current_tick_multiplier {
                     tm
+                } else if let Some(tm) = preset() {
+                    tm
                 } else if is_client_aggr {
                     TickMultiplier(5)
                 } else {
@@ -284,31 +392,30 @@ impl PaneSetup {
                 };
                 Some(tm)
             }
-            ContentKind::FootprintChart => {
-                Some(current_tick_multiplier.unwrap_or(TickMultiplier(50)))
-            }
+            ContentKind::FootprintChart => Some(
+                current_tick_multiplier
+                    .or_else(preset)
+                    .unwrap_or(TickMultiplier(50)),
+            ),
             ContentKind::CandlestickChart
             | ContentKind::ComparisonChart
             | ContentKind::TimeAndSales
+            | ContentKind::DepthCurve
+            | ContentKind::FundingChart
+            | ContentKind::SpreadChart
             | ContentKind::Starter => current_tick_multiplier,
         };
 
         let tick_size = match tick_multiplier {
-            Some(tm) => tm.multiply_with_min_tick_size(base_ticker),
+            Some(tm) => {
+                effective_multiplier(tm, round_steps).multiply_with_min_tick_size(base_ticker)
+            }
             None => base_ticker.min_ticksize.into(),
         };
 
         let depth_aggr = exchange.stream_ticksize(tick_multiplier, TickMultiplier(50));
 
-        let push_freq = match content_kind {
-            ContentKind::HeatmapChart if exchange.is_custom_push_freq() => match basis {
-                Some(Basis::Time(tf)) if exchange.supports_heatmap_timeframe(tf) => {
-                    exchange::PushFrequency::Custom(tf)
-                }
-                _ => exchange::PushFrequency::ServerDefault,
-            },
-            _ => exchange::PushFrequency::ServerDefault,
-        };
+        let push_freq = depth_push_freq(content_kind, exchange, basis, selected_push_freq);
 
         Self {
             ticker_info: base_ticker,
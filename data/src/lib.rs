@@ -1,20 +1,29 @@
 // ماژول‌های مربوط به مدیریت داده‌ها، تنظیمات و ابزارهای کمکی
 pub mod aggr;
 pub mod audio;
+pub mod book_spread;
 pub mod chart;
 pub mod config;
+pub mod depth_totals;
 pub mod layout;
 pub mod log;
+pub mod orderflow;
 pub mod panel;
+pub mod performance;
+pub mod replay;
+pub mod stale;
+pub mod tape_speed;
 pub mod tickers_table;
 pub mod util;
+pub mod walls;
 
 use std::fs::File;
 use std::io::{Read, Write};
 use std::path::PathBuf;
 
 pub use audio::AudioStream;
-pub use config::ScaleFactor;
+pub use config::{AutosaveInterval, ScaleFactor};
+pub use config::scanner::{self, Scanner};
 pub use config::sidebar::{self, Sidebar};
 pub use config::state::{Layouts, State};
 pub use config::theme::Theme;
@@ -102,6 +111,30 @@ pub fn read_from_file(file_name: &str) -> Result<State, Box<dyn std::error::Erro
     }
 }
 
+/// انتقال فایل وضعیت به یک نسخه پشتیبان تا برنامه با وضعیت پیش‌فرض شروع شود
+///
+/// مسیر فایل پشتیبان را برمی‌گرداند؛ اگر فایل وضعیتی وجود نداشته باشد `None` است.
+pub fn backup_and_clear_state(file_name: &str) -> Result<Option<PathBuf>, InternalError> {
+    let path = data_path(Some(file_name));
+
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let backup_file_name = match file_name.rfind('.') {
+        Some(pos) => format!("{}_backup-{stamp}{}", &file_name[..pos], &file_name[pos..]),
+        None => format!("{file_name}_backup-{stamp}"),
+    };
+    let backup_path = data_path(Some(&backup_file_name));
+
+    std::fs::rename(&path, &backup_path)
+        .map_err(|e| InternalError::Layout(format!("Failed to back up {}: {e}", path.display())))?;
+
+    info!("Backed up state file to '{}'", backup_path.display());
+    Ok(Some(backup_path))
+}
+
 /// باز کردن پوشه داده‌های برنامه در فایل اکسپلورر سیستم‌عامل
 pub fn open_data_folder() -> Result<(), InternalError> {
     let pathbuf = data_path(None);
@@ -141,71 +174,76 @@ pub fn data_path(path_name: Option<&str>) -> PathBuf {
 }
 
 /// پاکسازی فایل‌های قدیمی در یک پوشه مشخص (فایل‌های با عمر بیش از 4 روز)
+///
+/// زیرپوشه‌ها (نماد یا تاریخ، بسته به صرافی) به صورت بازگشتی بررسی و در صورت خالی شدن حذف می‌شوند
 fn cleanup_directory(data_path: &PathBuf) -> usize {
     if !data_path.exists() {
         warn!("Data path {:?} does not exist, skipping cleanup", data_path);
         return 0;
     }
 
-    // الگوی شناسایی فایل‌های فشرده حاوی تاریخ
-    let re =
-        regex::Regex::new(r".*-(\d{4}-\d{2}-\d{2})\.zip$").expect("Cleanup regex pattern is valid");
+    // الگوی شناسایی فایل‌های روزانه حاوی تاریخ (zip یا csv.gz)
+    let re = regex::Regex::new(r"(\d{4}-\d{2}-\d{2})\.(?:zip|csv\.gz)$")
+        .expect("Cleanup regex pattern is valid");
     let today = chrono::Local::now().date_naive();
-    let mut deleted_files = Vec::new();
 
-    let entries = match std::fs::read_dir(data_path) {
+    cleanup_entries(data_path, &re, today)
+}
+
+fn cleanup_entries(dir: &PathBuf, re: &regex::Regex, today: chrono::NaiveDate) -> usize {
+    let entries = match std::fs::read_dir(dir) {
         Ok(entries) => entries,
         Err(e) => {
-            error!("Failed to read data directory {:?}: {}", data_path, e);
+            error!("Failed to read data directory {:?}: {}", dir, e);
             return 0;
         }
     };
 
+    let mut deleted = 0;
+
     for entry in entries.filter_map(Result::ok) {
-        let symbol_dir = match std::fs::read_dir(entry.path()) {
-            Ok(dir) => dir,
-            Err(e) => {
-                error!("Failed to read symbol directory {:?}: {}", entry.path(), e);
-                continue;
+        let path = entry.path();
+
+        if path.is_dir() {
+            deleted += cleanup_entries(&path, re, today);
+
+            // پوشه‌های خالی شده (مثلاً پوشه‌های تاریخ اوکی‌اکس) هم حذف می‌شوند
+            if std::fs::read_dir(&path).is_ok_and(|mut dir| dir.next().is_none()) {
+                let _ = std::fs::remove_dir(&path);
             }
-        };
+            continue;
+        }
 
-        for file in symbol_dir.filter_map(Result::ok) {
-            let path = file.path();
-            let Some(filename) = path.to_str() else {
-                continue;
-            };
+        let Some(filename) = path.to_str() else {
+            continue;
+        };
 
-            // بررسی تاریخ فایل و حذف در صورت قدیمی بودن
-            if let Some(cap) = re.captures(filename)
-                && let Ok(file_date) = chrono::NaiveDate::parse_from_str(&cap[1], "%Y-%m-%d")
-            {
-                let days_old = today.signed_duration_since(file_date).num_days();
-                if days_old > 4 {
-                    if let Err(e) = std::fs::remove_file(&path) {
-                        error!("Failed to remove old file {}: {}", filename, e);
-                    } else {
-                        deleted_files.push(filename.to_string());
-                        info!("Removed old file: {}", filename);
-                    }
+        // بررسی تاریخ فایل و حذف در صورت قدیمی بودن
+        if let Some(cap) = re.captures(filename)
+            && let Ok(file_date) = chrono::NaiveDate::parse_from_str(&cap[1], "%Y-%m-%d")
+        {
+            let days_old = today.signed_duration_since(file_date).num_days();
+            if days_old > 4 {
+                if let Err(e) = std::fs::remove_file(&path) {
+                    error!("Failed to remove old file {}: {}", filename, e);
+                } else {
+                    deleted += 1;
+                    info!("Removed old file: {}", filename);
                 }
             }
         }
     }
 
-    deleted_files.len()
+    deleted
 }
 
-/// پاکسازی داده‌های قدیمی بازار (Binance Futures)
+/// پاکسازی فایل‌های روزانه قدیمی معاملات همه صرافی‌ها
 pub fn cleanup_old_market_data() -> usize {
-    let paths = ["um", "cm"].map(|market_type| {
-        data_path(Some(&format!(
-            "market_data/binance/data/futures/{}/daily/aggTrades",
-            market_type
-        )))
-    });
-
-    let total_deleted: usize = paths.iter().map(cleanup_directory).sum();
+    let total_deleted: usize = exchange::adapter::TRADE_ARCHIVE_DIRS
+        .iter()
+        .map(|dir| data_path(Some(&format!("market_data/{dir}"))))
+        .map(|path| cleanup_directory(&path))
+        .sum();
 
     info!("File cleanup completed. Deleted {} files", total_deleted);
     total_deleted
@@ -0,0 +1,377 @@
+//! تشخیص جذب (حجم بزرگ بدون پیشروی قیمت) و تهاجم (عبور سریع قیمت از سطوح) روی کندل‌های فوت‌پرینت
+
+use crate::chart::kline::KlineTrades;
+use exchange::Kline;
+use exchange::util::{Price, PriceStep};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// بازه زمانی که سطوح تازه باید در آن لمس شوند تا حرکت تهاجمی محسوب شود (میلی‌ثانیه)
+const SWEEP_WINDOW_MS: u64 = 1_000;
+
+/// تنظیمات تشخیص جذب و تهاجم روی فوت‌پرینت
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct OrderFlowConfig {
+    pub enabled: bool,         // برجسته‌سازی سطوح و کندل‌های تشخیص داده شده
+    pub volume_multiple: f32,  // حداقل نسبت حجم سطح به میانگین حجم سطوح همان کندل (جذب)
+    pub max_displacement: u32, // حداکثر فاصله باز و بسته شدن کندل بر حسب تیک (جذب)
+    pub sweep_levels: u32,     // حداقل تعداد سطوح تازه لمس شده در یک ثانیه (تهاجم)
+    pub notify: bool,          // اعلان هنگام تشخیص در کندل جاری
+}
+
+impl Default for OrderFlowConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            volume_multiple: 4.0,
+            max_displacement: 2,
+            sweep_levels: 6,
+            notify: false,
+        }
+    }
+}
+
+impl OrderFlowConfig {
+    pub const MULTIPLE_RANGE: std::ops::RangeInclusive<f32> = 2.0..=15.0;
+    pub const DISPLACEMENT_RANGE: std::ops::RangeInclusive<u32> = 0..=20;
+    pub const SWEEP_RANGE: std::ops::RangeInclusive<u32> = 3..=30;
+}
+
+/// نوع الگوی تشخیص داده شده
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignalKind {
+    Absorption, // حجم بزرگ در یک سطح بدون پیشروی قیمت
+    Aggression, // عبور سریع قیمت از چند سطح پیاپی
+}
+
+impl std::fmt::Display for SignalKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignalKind::Absorption => write!(f, "Absorption"),
+            SignalKind::Aggression => write!(f, "Aggression"),
+        }
+    }
+}
+
+/// یک الگوی تشخیص داده شده در یک کندل
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrderFlowSignal {
+    pub kind: SignalKind,
+    pub price: Price, // سطح جذب، یا آخرین سطح لمس شده در حرکت تهاجمی
+    pub is_buy: bool, // سمت معامله‌گران فعال (خریداران یا فروشندگان)
+    pub qty: f32,     // حجم سطح جذب، یا مجموع حجم سطوح عبور شده
+}
+
+/// الگوی تازه‌ای که در کندل جاری تشخیص داده شد
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrderFlowEvent {
+    pub candle_time: u64,
+    pub signal: OrderFlowSignal,
+}
+
+#[derive(Debug, Default, Clone)]
+struct CandleSignals {
+    signals: Vec<OrderFlowSignal>,
+    announced: Vec<SignalKind>, // انواعی که برای این کندل اعلان شده‌اند تا تکرار نشوند
+}
+
+/// نگهداری نتایج تشخیص به ازای هر کندل؛ فقط کندل در حال شکل‌گیری دوباره بررسی می‌شود
+/// و کندل‌های بسته شده یک بار اسکن می‌شوند
+#[derive(Debug, Default, Clone)]
+pub struct OrderFlowTracker {
+    candles: BTreeMap<u64, CandleSignals>,
+    /// زمان آخرین کندل اسکن شده؛ `None` یعنی هنوز اسکن کاملی انجام نشده
+    resume_from: Option<u64>,
+}
+
+impl OrderFlowTracker {
+    /// زمان کندلی که اسکن بعدی باید از آن شروع شود؛ `None` برای اسکن کامل تاریخچه
+    pub fn resume_from(&self) -> Option<u64> {
+        self.resume_from
+    }
+
+    /// اسکن کندل‌های داده شده (مرتب بر اساس زمان و از `resume_from` به بعد) و برگرداندن
+    /// الگوهای تازه؛ اسکن کامل اولیه اعلانی تولید نمی‌کند تا تاریخچه باعث سیل اعلان نشود
+    pub fn update<'a>(
+        &mut self,
+        candles: impl Iterator<Item = (&'a Kline, &'a KlineTrades)>,
+        cfg: &OrderFlowConfig,
+        step: PriceStep,
+    ) -> Vec<OrderFlowEvent> {
+        if !cfg.enabled {
+            self.clear();
+            return vec![];
+        }
+
+        let is_live = self.resume_from.is_some();
+        let mut events = vec![];
+
+        for (kline, footprint) in candles {
+            let time = kline.time;
+            self.resume_from = Some(time);
+
+            let signals = detect(kline, footprint, cfg, step);
+            if signals.is_empty() {
+                if let Some(entry) = self.candles.get_mut(&time) {
+                    entry.signals.clear();
+                }
+                continue;
+            }
+
+            let entry = self.candles.entry(time).or_default();
+            for signal in &signals {
+                if entry.announced.contains(&signal.kind) {
+                    continue;
+                }
+                entry.announced.push(signal.kind);
+
+                if is_live {
+                    events.push(OrderFlowEvent {
+                        candle_time: time,
+                        signal: *signal,
+                    });
+                }
+            }
+            entry.signals = signals;
+        }
+
+        events
+    }
+
+    /// الگوهای تشخیص داده شده برای کندلی که در زمان داده شده باز شده است
+    pub fn signals_at(&self, candle_time: u64) -> &[OrderFlowSignal] {
+        self.candles
+            .get(&candle_time)
+            .map_or(&[], |entry| &entry.signals)
+    }
+
+    /// پاک کردن نتایج تا اسکن بعدی کل تاریخچه را دوباره بررسی کند
+    pub fn clear(&mut self) {
+        self.candles.clear();
+        self.resume_from = None;
+    }
+}
+
+/// تشخیص الگوهای جذب و تهاجم در یک کندل
+pub fn detect(
+    kline: &Kline,
+    footprint: &KlineTrades,
+    cfg: &OrderFlowConfig,
+    step: PriceStep,
+) -> Vec<OrderFlowSignal> {
+    let mut signals = vec![];
+
+    if let Some(signal) = detect_absorption(kline, footprint, cfg, step) {
+        signals.push(signal);
+    }
+    if let Some(signal) = detect_aggression(footprint, cfg, step) {
+        signals.push(signal);
+    }
+
+    signals
+}
+
+/// سطحی با حجم چند برابر میانگین سطوح کندل، در حالی که قیمت بسته شدن از قیمت باز شدن
+/// بیش از چند تیک فاصله نگرفته است
+fn detect_absorption(
+    kline: &Kline,
+    footprint: &KlineTrades,
+    cfg: &OrderFlowConfig,
+    step: PriceStep,
+) -> Option<OrderFlowSignal> {
+    let trades = &footprint.trades;
+    if trades.len() < 2 || step.units <= 0 {
+        return None;
+    }
+
+    let displacement = (kline.close.units - kline.open.units).abs() / step.units;
+    if displacement > i64::from(cfg.max_displacement) {
+        return None;
+    }
+
+    let total: f32 = trades.values().map(|group| group.total_qty()).sum();
+    let mean = total / trades.len() as f32;
+
+    trades
+        .iter()
+        .filter(|(_, group)| group.total_qty() >= mean * cfg.volume_multiple)
+        .max_by(|(_, a), (_, b)| a.total_qty().total_cmp(&b.total_qty()))
+        .map(|(price, group)| OrderFlowSignal {
+            kind: SignalKind::Absorption,
+            price: *price,
+            is_buy: group.buy_qty >= group.sell_qty,
+            qty: group.total_qty(),
+        })
+}
+
+/// چند سطح پیاپی که اولین معامله آن‌ها در بازه کوتاهی رخ داده، یعنی قیمت سریع
+/// از آن‌ها عبور کرده است
+fn detect_aggression(
+    footprint: &KlineTrades,
+    cfg: &OrderFlowConfig,
+    step: PriceStep,
+) -> Option<OrderFlowSignal> {
+    let needed = cfg.sweep_levels.max(2) as usize;
+    if footprint.trades.len() < needed {
+        return None;
+    }
+
+    let mut levels = footprint
+        .trades
+        .iter()
+        .map(|(price, group)| (group.first_time, *price, group.total_qty()))
+        .collect::<Vec<_>>();
+    levels.sort_unstable_by_key(|(time, price, _)| (*time, *price));
+
+    let mut start = 0;
+    for end in 0..levels.len() {
+        while levels[end].0.saturating_sub(levels[start].0) > SWEEP_WINDOW_MS {
+            start += 1;
+        }
+        if end + 1 - start < needed {
+            continue;
+        }
+
+        let run = &levels[start..=end];
+        let (first, last) = (run[0].1, run[run.len() - 1].1);
+
+        // سطوح تازه باید بازه پیوسته‌ای از قیمت را پوشش دهند، نه معاملات پراکنده
+        let (low, high) = run.iter().fold((first, first), |(lo, hi), (_, p, _)| {
+            (lo.min(*p), hi.max(*p))
+        });
+        let span = Price::steps_between_inclusive(low, high, step)?;
+        if span > run.len() * 2 || first == last {
+            continue;
+        }
+
+        return Some(OrderFlowSignal {
+            kind: SignalKind::Aggression,
+            price: last,
+            is_buy: last > first,
+            qty: run.iter().map(|(_, _, qty)| qty).sum(),
+        });
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chart::kline::KlineDataPoint;
+    use exchange::Trade;
+
+    const STEP: PriceStep = PriceStep { units: 100_000_000 };
+
+    fn candle(open: f32, close: f32, trades: &[(u64, f32, f32, bool)]) -> KlineDataPoint {
+        let mut footprint = KlineTrades::new();
+        for &(time, price, qty, is_sell) in trades {
+            footprint.add_trade_to_nearest_bin(
+                &Trade {
+                    time,
+                    is_sell,
+                    price: Price::from_f32(price),
+                    qty,
+                },
+                STEP,
+            );
+        }
+
+        KlineDataPoint {
+            kline: Kline {
+                time: 0,
+                open: Price::from_f32(open),
+                high: Price::from_f32(open.max(close)),
+                low: Price::from_f32(open.min(close)),
+                close: Price::from_f32(close),
+                volume: (0.0, 0.0),
+                trade_count: None,
+            },
+            footprint,
+        }
+    }
+
+    fn cfg() -> OrderFlowConfig {
+        OrderFlowConfig {
+            enabled: true,
+            volume_multiple: 3.0,
+            max_displacement: 1,
+            sweep_levels: 4,
+            notify: true,
+        }
+    }
+
+    fn detect_in(dp: &KlineDataPoint) -> Vec<OrderFlowSignal> {
+        detect(&dp.kline, &dp.footprint, &cfg(), STEP)
+    }
+
+    fn candles(dp: &KlineDataPoint) -> impl Iterator<Item = (&Kline, &KlineTrades)> {
+        std::iter::once((&dp.kline, &dp.footprint))
+    }
+
+    #[test]
+    fn heavy_level_without_progress_is_absorption() {
+        let trades = [
+            (0, 100.0, 1.0, false),
+            (2_000, 102.0, 1.0, false),
+            (4_000, 101.0, 20.0, true),
+            (6_000, 99.0, 1.0, true),
+            (8_000, 103.0, 1.0, false),
+        ];
+
+        let signals = detect_in(&candle(101.0, 101.0, &trades));
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].kind, SignalKind::Absorption);
+        assert_eq!(signals[0].price, Price::from_f32(101.0).round_to_step(STEP));
+        assert!(!signals[0].is_buy);
+
+        // همان حجم با پیشروی قیمت جذب محسوب نمی‌شود
+        assert!(detect_in(&candle(100.0, 104.0, &trades)).is_empty());
+    }
+
+    #[test]
+    fn fast_run_through_levels_is_aggression() {
+        let fast = (0..5)
+            .map(|i| (i * 100, 100.0 + i as f32, 1.0, false))
+            .collect::<Vec<_>>();
+        let signals = detect_in(&candle(100.0, 104.0, &fast));
+        assert!(matches!(
+            signals.as_slice(),
+            [OrderFlowSignal {
+                kind: SignalKind::Aggression,
+                is_buy: true,
+                ..
+            }]
+        ));
+
+        let slow = (0..5)
+            .map(|i| (i * 2_000, 100.0 + i as f32, 1.0, false))
+            .collect::<Vec<_>>();
+        assert!(detect_in(&candle(100.0, 104.0, &slow)).is_empty());
+    }
+
+    #[test]
+    fn tracker_announces_each_kind_once_per_live_candle() {
+        let trades = [
+            (0, 100.0, 1.0, false),
+            (2_000, 102.0, 1.0, false),
+            (4_000, 101.0, 20.0, true),
+            (6_000, 99.0, 1.0, true),
+            (8_000, 103.0, 1.0, false),
+        ];
+        let dp = candle(101.0, 101.0, &trades);
+        let mut tracker = OrderFlowTracker::default();
+
+        // اسکن اولیه تاریخچه اعلان نمی‌دهد
+        assert!(tracker.update(std::iter::empty(), &cfg(), STEP).is_empty());
+        assert_eq!(tracker.resume_from(), None);
+        assert!(tracker.update(candles(&dp), &cfg(), STEP).is_empty());
+        assert_eq!(tracker.signals_at(0).len(), 1);
+
+        let mut next = dp.clone();
+        next.kline.time = 60_000;
+        assert_eq!(tracker.update(candles(&next), &cfg(), STEP).len(), 1);
+        assert!(tracker.update(candles(&next), &cfg(), STEP).is_empty());
+    }
+}
@@ -1,3 +1,6 @@
-// ماژول‌های مربوط به پنل‌های مختلف (نردبان قیمت و لیست معاملات)
+// ماژول‌های مربوط به پنل‌های مختلف (نردبان قیمت، منحنی عمق، تأمین مالی، اسپرد و لیست معاملات)
+pub mod depth_curve;
+pub mod funding;
 pub mod ladder;
+pub mod spread;
 pub mod timeandsales;
@@ -0,0 +1,97 @@
+use exchange::{depth::Depth, util::Price};
+use serde::{Deserialize, Serialize};
+
+use crate::util::ok_or_default;
+use crate::book_spread::BookSpreadConfig;
+use crate::depth_totals::DepthTotalsConfig;
+use crate::walls::WallConfig;
+
+pub const MIN_RANGE_PCT: f32 = 0.1;
+pub const MAX_RANGE_PCT: f32 = 20.0;
+
+/// تنظیمات مربوط به نمودار تجمعی عمق بازار
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct Config {
+    #[serde(deserialize_with = "ok_or_default", default = "default_range_pct")]
+    pub range_pct: f32, // بازه قیمتی نمایش داده شده (درصد از قیمت میانی در هر سمت)
+    #[serde(deserialize_with = "ok_or_default", default = "default_show_spread")]
+    pub show_spread: bool, // نمایش فاصله خرید و فروش (Spread)
+    #[serde(deserialize_with = "ok_or_default", default)]
+    pub walls: WallConfig, // تشخیص و برجسته‌سازی دیوارهای عمق
+    #[serde(deserialize_with = "ok_or_default", default)]
+    pub depth_totals: DepthTotalsConfig, // مجموع خرید/فروش در بازه اطراف قیمت میانی
+    #[serde(deserialize_with = "ok_or_default", default)]
+    pub spread_history: BookSpreadConfig, // اسپرد فعلی و نمودار کوچک تاریخچه آن در گوشه پنل
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            range_pct: default_range_pct(),
+            show_spread: default_show_spread(),
+            walls: WallConfig::default(),
+            depth_totals: DepthTotalsConfig::default(),
+            spread_history: BookSpreadConfig::default(),
+        }
+    }
+}
+
+fn default_range_pct() -> f32 {
+    2.0
+}
+
+fn default_show_spread() -> bool {
+    true
+}
+
+/// منحنی تجمعی یک سمت دفتر سفارش؛ نقاط از نزدیک‌ترین قیمت به میانه به سمت دور مرتب شده‌اند
+#[derive(Debug, Default, Clone)]
+pub struct CumulativeSide {
+    pub points: Vec<(Price, f32)>, // قیمت و حجم تجمعی تا آن قیمت
+}
+
+impl CumulativeSide {
+    pub fn total(&self) -> f32 {
+        self.points.last().map_or(0.0, |(_, qty)| *qty)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+}
+
+/// ساخت منحنی‌های تجمعی خرید و فروش در محدوده `range_pct` درصد اطراف قیمت میانی
+///
+/// مقادیر سطوح از قبل توسط آداپتور صرافی به واحد نمایش حجم فعال (پایه یا کوت) تبدیل شده‌اند.
+pub fn cumulative_curves(
+    depth: &Depth,
+    range_pct: f32,
+) -> Option<(Price, CumulativeSide, CumulativeSide)> {
+    let mid = depth.mid_price()?;
+
+    let mid_f = mid.to_f32_lossy();
+    let offset = mid_f * range_pct.clamp(MIN_RANGE_PCT, MAX_RANGE_PCT) / 100.0;
+    let (low, high) = (mid_f - offset, mid_f + offset);
+
+    let mut bids = CumulativeSide::default();
+    let mut acc = 0.0;
+    for (price, qty) in depth.bids.iter().rev() {
+        if price.to_f32_lossy() < low {
+            break;
+        }
+        acc += *qty;
+        bids.points.push((*price, acc));
+    }
+
+    let mut asks = CumulativeSide::default();
+    let mut acc = 0.0;
+    for (price, qty) in depth.asks.iter() {
+        if price.to_f32_lossy() > high {
+            break;
+        }
+        acc += *qty;
+        asks.points.push((*price, acc));
+    }
+
+    Some((mid, bids, asks))
+}
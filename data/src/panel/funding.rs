@@ -0,0 +1,131 @@
+use exchange::{FundingRate, Kline};
+
+use std::collections::BTreeMap;
+
+/// حداکثر تعداد نقاط نگه‌داری شده برای هر سری
+const MAX_POINTS: usize = 5000;
+
+/// تاریخچه نرخ تأمین مالی و قیمت‌های قرارداد دائمی و شاخص برای محاسبه بیسیس
+#[derive(Debug, Default, Clone)]
+pub struct FundingHistory {
+    rates: BTreeMap<u64, f32>, // نرخ تأمین مالی در هر زمان تسویه
+    perp: BTreeMap<u64, f32>,  // قیمت پایانی کندل‌های قرارداد دائمی
+    index: BTreeMap<u64, f32>, // قیمت پایانی کندل‌های شاخص
+}
+
+impl FundingHistory {
+    pub fn insert_rates(&mut self, rates: &[FundingRate]) {
+        self.rates.extend(rates.iter().map(|r| (r.time, r.rate)));
+        trim(&mut self.rates);
+    }
+
+    pub fn insert_perp_klines(&mut self, klines: &[Kline]) {
+        self.perp
+            .extend(klines.iter().map(|k| (k.time, k.close.to_f32())));
+        trim(&mut self.perp);
+    }
+
+    pub fn insert_index_klines(&mut self, klines: &[Kline]) {
+        self.index
+            .extend(klines.iter().map(|k| (k.time, k.close.to_f32())));
+        trim(&mut self.index);
+    }
+
+    /// نرخ‌های تأمین مالی به ترتیب زمان
+    pub fn rates(&self) -> impl Iterator<Item = (u64, f32)> + '_ {
+        self.rates.iter().map(|(t, r)| (*t, *r))
+    }
+
+    /// بیسیس (قیمت قرارداد دائمی منهای شاخص) در زمان‌هایی که هر دو قیمت موجود است
+    pub fn basis(&self) -> Vec<(u64, f32)> {
+        self.perp
+            .iter()
+            .filter_map(|(t, perp)| self.index.get(t).map(|index| (*t, perp - index)))
+            .collect()
+    }
+
+    pub fn has_rates(&self) -> bool {
+        !self.rates.is_empty()
+    }
+
+    pub fn has_index(&self) -> bool {
+        !self.index.is_empty()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rates.is_empty() && self.perp.is_empty()
+    }
+
+    /// بازه زمانی پوشش داده شده توسط نرخ‌ها و بیسیس
+    pub fn time_span(&self) -> Option<(u64, u64)> {
+        let firsts = [self.rates.keys().next(), self.perp.keys().next()];
+        let lasts = [self.rates.keys().next_back(), self.perp.keys().next_back()];
+
+        let start = firsts.into_iter().flatten().min()?;
+        let end = lasts.into_iter().flatten().max()?;
+        Some((*start, *end))
+    }
+}
+
+fn trim(series: &mut BTreeMap<u64, f32>) {
+    while series.len() > MAX_POINTS {
+        series.pop_first();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exchange::util::Price;
+
+    fn kline(time: u64, close: f32) -> Kline {
+        let close = Price::from_f32(close);
+        Kline {
+            time,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: (0.0, 0.0),
+            trade_count: None,
+        }
+    }
+
+    #[test]
+    fn basis_only_where_both_prices_exist() {
+        let mut history = FundingHistory::default();
+        history.insert_perp_klines(&[kline(1, 101.0), kline(2, 102.0), kline(3, 99.0)]);
+        history.insert_index_klines(&[kline(2, 100.0), kline(3, 100.0)]);
+
+        let basis = history.basis();
+        assert_eq!(
+            basis.iter().map(|(t, _)| *t).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert!((basis[0].1 - 2.0).abs() < 1e-3);
+        assert!((basis[1].1 + 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn rates_stay_ordered_and_deduplicated() {
+        let mut history = FundingHistory::default();
+        history.insert_rates(&[
+            FundingRate {
+                time: 16,
+                rate: 0.0002,
+            },
+            FundingRate {
+                time: 8,
+                rate: 0.0001,
+            },
+        ]);
+        history.insert_rates(&[FundingRate {
+            time: 16,
+            rate: 0.0003,
+        }]);
+
+        let rates: Vec<_> = history.rates().collect();
+        assert_eq!(rates, vec![(8, 0.0001), (16, 0.0003)]);
+        assert_eq!(history.time_span(), Some((8, 16)));
+    }
+}
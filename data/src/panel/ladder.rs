@@ -1,4 +1,6 @@
+use crate::book_spread::BookSpreadConfig;
 use crate::chart::kline::KlineTrades;
+use crate::depth_totals::DepthTotalsConfig;
 use crate::util::ok_or_default;
 use exchange::{
     Trade,
@@ -21,6 +23,10 @@ pub struct Config {
     #[serde(deserialize_with = "ok_or_default", default)]
     pub show_chase_tracker: bool,   // نمایش ردیاب تعقیب قیمت (Chase Tracker)
     pub trade_retention: Duration,  // مدت زمان نگهداشت معاملات در حافظه
+    #[serde(deserialize_with = "ok_or_default", default)]
+    pub depth_totals: DepthTotalsConfig, // مجموع خرید/فروش در بازه اطراف قیمت میانی
+    #[serde(deserialize_with = "ok_or_default", default)]
+    pub spread_history: BookSpreadConfig, // اسپرد فعلی و نمودار کوچک تاریخچه آن در گوشه پنل
 }
 
 impl Default for Config {
@@ -29,6 +35,8 @@ impl Default for Config {
             show_spread: false,
             show_chase_tracker: true,
             trade_retention: Duration::from_millis(TRADE_RETENTION_MS),
+            depth_totals: DepthTotalsConfig::default(),
+            spread_history: BookSpreadConfig::default(),
         }
     }
 }
@@ -191,7 +199,6 @@ impl TradeStore {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default)]
 /// وضعیت پیشرفت تعقیب قیمت
 #[derive(Debug, Clone, Copy, Default)]
 enum ChaseProgress {
@@ -212,7 +219,6 @@ enum ChaseProgress {
     },
 }
 
-#[derive(Debug, Default)]
 /// ساختار ردیاب تعقیب قیمت (برای نمایش بصری حرکت سریع قیمت)
 #[derive(Debug, Default)]
 pub struct ChaseTracker {
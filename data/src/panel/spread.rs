@@ -0,0 +1,244 @@
+use exchange::Kline;
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+
+/// حداکثر تعداد نقاط نگه‌داری شده برای هر سمت
+const MAX_POINTS: usize = 5000;
+
+/// واحد نمایش اسپرد روی نمودار
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum SpreadUnit {
+    #[default]
+    Bps, // واحد پایه (یک صدم درصد)
+    Absolute, // اختلاف قیمت مطلق
+}
+
+impl SpreadUnit {
+    pub const ALL: [SpreadUnit; 2] = [SpreadUnit::Bps, SpreadUnit::Absolute];
+}
+
+impl std::fmt::Display for SpreadUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpreadUnit::Bps => write!(f, "Basis points"),
+            SpreadUnit::Absolute => write!(f, "Absolute"),
+        }
+    }
+}
+
+/// تنظیمات پنل مانیتور اسپرد بین دو بازار
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    pub unit: SpreadUnit, // واحد نمایش اسپرد
+    pub alert: bool,      // اعلان و صدا هنگام عبور اسپرد از آستانه‌ها
+    pub upper_bps: f32,   // آستانه بالایی هشدار (bps)
+    pub lower_bps: f32,   // آستانه پایینی هشدار (bps)
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            unit: SpreadUnit::default(),
+            alert: false,
+            upper_bps: 20.0,
+            lower_bps: -20.0,
+        }
+    }
+}
+
+impl Config {
+    pub const THRESHOLD_RANGE: std::ops::RangeInclusive<f32> = -200.0..=200.0;
+}
+
+/// سمت‌های اسپرد؛ اسپرد برابر است با سمت اول منهای سمت دوم
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Leg {
+    First,  // نماد پایه پنل
+    Second, // نماد مقایسه شده
+}
+
+/// اسپرد دو سمت در یک زمان هم‌تراز شده
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpreadPoint {
+    pub time: u64,
+    pub abs: f32, // اختلاف قیمت مطلق
+    pub bps: f32, // اختلاف نسبت به میانگین دو قیمت بر حسب bps
+}
+
+impl SpreadPoint {
+    pub fn value(&self, unit: SpreadUnit) -> f32 {
+        match unit {
+            SpreadUnit::Bps => self.bps,
+            SpreadUnit::Absolute => self.abs,
+        }
+    }
+}
+
+/// قیمت میانی یک کندل؛ با گام‌های قیمت متفاوت دو بازار، مقایسه میانه‌ها منصفانه‌تر از آخرین معامله است
+pub fn bar_mid(kline: &Kline) -> f32 {
+    (kline.high.to_f32() + kline.low.to_f32()) / 2.0
+}
+
+/// تاریخچه قیمت میانی دو بازار، هم‌تراز شده روی شبکه زمانی تایم‌فریم
+#[derive(Debug, Default, Clone)]
+pub struct SpreadHistory {
+    first: BTreeMap<u64, f32>,
+    second: BTreeMap<u64, f32>,
+    interval_ms: u64,
+}
+
+impl SpreadHistory {
+    pub fn new(interval_ms: u64) -> Self {
+        Self {
+            interval_ms: interval_ms.max(1),
+            ..Default::default()
+        }
+    }
+
+    pub fn insert(&mut self, leg: Leg, klines: &[Kline]) {
+        let dt = self.interval_ms.max(1);
+        let series = match leg {
+            Leg::First => &mut self.first,
+            Leg::Second => &mut self.second,
+        };
+
+        series.extend(klines.iter().map(|k| ((k.time / dt) * dt, bar_mid(k))));
+        while series.len() > MAX_POINTS {
+            series.pop_first();
+        }
+    }
+
+    /// اسپرد در زمان‌هایی که هر دو قیمت موجود است
+    pub fn points(&self) -> Vec<SpreadPoint> {
+        self.first
+            .iter()
+            .filter_map(|(t, a)| self.second.get(t).map(|b| spread_at(*t, *a, *b)))
+            .collect()
+    }
+
+    /// آخرین زمانی که هر دو سمت قیمت دارند
+    pub fn latest(&self) -> Option<SpreadPoint> {
+        self.first
+            .iter()
+            .rev()
+            .find_map(|(t, a)| self.second.get(t).map(|b| spread_at(*t, *a, *b)))
+    }
+}
+
+fn spread_at(time: u64, a: f32, b: f32) -> SpreadPoint {
+    let abs = a - b;
+    let reference = (a + b) / 2.0;
+    let bps = if reference > 0.0 {
+        abs / reference * 10_000.0
+    } else {
+        0.0
+    };
+
+    SpreadPoint { time, abs, bps }
+}
+
+/// رویداد عبور اسپرد از آستانه‌های هشدار
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpreadEvent {
+    Above(SpreadPoint), // اسپرد از آستانه بالایی بیشتر شد
+    Below(SpreadPoint), // اسپرد از آستانه پایینی کمتر شد
+}
+
+/// وضعیت هشدارها؛ هر آستانه تنها یک بار اعلان می‌دهد تا اسپرد دوباره به داخل بازه برگردد
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SpreadAlerts {
+    above: bool,
+    below: bool,
+}
+
+impl SpreadAlerts {
+    pub fn check(&mut self, cfg: &Config, point: SpreadPoint) -> Option<SpreadEvent> {
+        if !cfg.alert {
+            *self = Self::default();
+            return None;
+        }
+
+        let is_above = point.bps >= cfg.upper_bps;
+        let is_below = point.bps <= cfg.lower_bps;
+
+        let event = if is_above && !self.above {
+            Some(SpreadEvent::Above(point))
+        } else if is_below && !self.below {
+            Some(SpreadEvent::Below(point))
+        } else {
+            None
+        };
+
+        self.above = is_above;
+        self.below = is_below;
+        event
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exchange::util::Price;
+
+    fn kline(time: u64, high: f32, low: f32) -> Kline {
+        Kline {
+            time,
+            open: Price::from_f32(low),
+            high: Price::from_f32(high),
+            low: Price::from_f32(low),
+            close: Price::from_f32(high),
+            volume: (0.0, 0.0),
+            trade_count: None,
+        }
+    }
+
+    #[test]
+    fn spread_aligns_legs_on_timeframe_grid() {
+        let mut history = SpreadHistory::new(60_000);
+        history.insert(
+            Leg::First,
+            &[kline(60_000, 101.0, 99.0), kline(120_000, 102.0, 100.0)],
+        );
+        // سمت دوم با تأخیر چند میلی‌ثانیه‌ای گزارش شده و گام قیمت درشت‌تری دارد
+        history.insert(Leg::Second, &[kline(120_250, 100.5, 99.5)]);
+
+        let points = history.points();
+        assert_eq!(points.len(), 1);
+        assert_eq!(points[0].time, 120_000);
+        assert!((points[0].abs - 1.0).abs() < 1e-3);
+        assert!((points[0].bps - 1.0 / 100.5 * 10_000.0).abs() < 1e-2);
+        assert_eq!(history.latest(), Some(points[0]));
+    }
+
+    #[test]
+    fn alerts_fire_once_per_crossing() {
+        let cfg = Config {
+            alert: true,
+            ..Config::default()
+        };
+        let point = |bps| SpreadPoint {
+            time: 0,
+            abs: 0.0,
+            bps,
+        };
+
+        let mut alerts = SpreadAlerts::default();
+        assert_eq!(alerts.check(&cfg, point(5.0)), None);
+        assert!(matches!(
+            alerts.check(&cfg, point(25.0)),
+            Some(SpreadEvent::Above(_))
+        ));
+        assert_eq!(alerts.check(&cfg, point(30.0)), None);
+        assert_eq!(alerts.check(&cfg, point(0.0)), None);
+        assert!(matches!(
+            alerts.check(&cfg, point(-21.0)),
+            Some(SpreadEvent::Below(_))
+        ));
+        assert!(matches!(
+            alerts.check(&cfg, point(22.0)),
+            Some(SpreadEvent::Above(_))
+        ));
+    }
+}
@@ -3,7 +3,7 @@ use std::time::Duration;
 use exchange::util::Price;
 use serde::{Deserialize, Serialize};
 
-use crate::util::ok_or_default;
+use crate::{aggr::PrintAggregation, util::ok_or_default};
 
 const TRADE_RETENTION_MS: u64 = 120_000;
 
@@ -15,6 +15,12 @@ pub struct Config {
     pub trade_retention: Duration, // مدت زمان نگهداشت معاملات در لیست
     #[serde(deserialize_with = "ok_or_default", default)]
     pub stacked_bar: Option<StackedBar>, // تنظیمات نوار انباشته (Stacked Bar) در پایین لیست
+    #[serde(default)]
+    pub print_aggregation: PrintAggregation, // ادغام معاملات پیاپی هم‌قیمت در نمایش
+    #[serde(default)]
+    pub size_scaling: SizeScaling, // نحوه نمایش اندازه نسبی هر معامله
+    #[serde(default)]
+    pub block_trade_filter: Option<f32>, // آستانه معاملات بلوکی که برجسته و چشمک‌زن نمایش داده می‌شوند
 }
 
 impl Default for Config {
@@ -23,6 +29,54 @@ impl Default for Config {
             trade_size_filter: 0.0,
             trade_retention: Duration::from_millis(TRADE_RETENTION_MS),
             stacked_bar: StackedBar::Compact(StackedBarRatio::default()).into(),
+            print_aggregation: PrintAggregation::default(),
+            size_scaling: SizeScaling::default(),
+            block_trade_filter: None,
+        }
+    }
+}
+
+impl Config {
+    /// آیا اندازه معامله (در واحد حجم فعال) به آستانه معاملات بلوکی می‌رسد؟
+    pub fn is_block_trade(&self, trade_size: f32) -> bool {
+        self.block_trade_filter
+            .is_some_and(|threshold| trade_size >= threshold)
+    }
+}
+
+/// نحوه نمایش اندازه نسبی هر معامله در ردیف آن
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum SizeScaling {
+    #[default]
+    Shade, // پررنگ‌تر شدن پس‌زمینه کل ردیف با بزرگ‌تر شدن معامله
+    Linear, // نوار افقی متناسب با اندازه معامله
+    Sqrt,   // نوار متناسب با جذر اندازه؛ معاملات کوچک‌تر در کنار معاملات بزرگ دیده می‌شوند
+}
+
+impl SizeScaling {
+    pub const ALL: [SizeScaling; 3] = [SizeScaling::Shade, SizeScaling::Linear, SizeScaling::Sqrt];
+
+    /// طول نسبی نوار (۰ تا ۱) برای یک معامله نسبت به بزرگ‌ترین معامله نمایش داده شده
+    pub fn bar_ratio(self, qty: f32, max_qty: f32) -> Option<f32> {
+        if max_qty <= 0.0 {
+            return None;
+        }
+        let ratio = (qty / max_qty).clamp(0.0, 1.0);
+
+        match self {
+            SizeScaling::Shade => None,
+            SizeScaling::Linear => Some(ratio),
+            SizeScaling::Sqrt => Some(ratio.sqrt()),
+        }
+    }
+}
+
+impl std::fmt::Display for SizeScaling {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SizeScaling::Shade => write!(f, "Shade"),
+            SizeScaling::Linear => write!(f, "Bar (linear)"),
+            SizeScaling::Sqrt => write!(f, "Bar (square root)"),
         }
     }
 }
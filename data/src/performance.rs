@@ -0,0 +1,70 @@
+//! پروفایل کارایی: مصالحه بین کیفیت نمایش و نرخ فریم در سیستم‌های ضعیف یا چیدمان‌های پرپنل
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::Duration;
+
+/// سطح کیفیت رسم؛ لبه‌های نرم، فاصله بازرسم و آستانه ساده‌سازی کندل‌ها را تعیین می‌کند
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum PerformanceProfile {
+    #[default]
+    HighQuality, // لبه‌های نرم و بازرسم هر ۱۰۰ میلی‌ثانیه
+    Balanced,    // بازرسم کندتر و ساده‌سازی کندل‌های باریک
+    Performance, // بدون لبه‌های نرم، کمترین نرخ بازرسم
+}
+
+impl PerformanceProfile {
+    pub const ALL: [PerformanceProfile; 3] = [
+        PerformanceProfile::HighQuality,
+        PerformanceProfile::Balanced,
+        PerformanceProfile::Performance,
+    ];
+
+    /// لبه‌های نرم هنگام راه‌اندازی برنامه تنظیم می‌شوند و تغییر آن نیاز به اجرای دوباره دارد
+    pub fn antialiasing(self) -> bool {
+        !matches!(self, PerformanceProfile::Performance)
+    }
+
+    /// فاصله بین تیک‌های بازرسم و به‌روزرسانی دوره‌ای پنل‌ها
+    pub fn tick_interval(self) -> Duration {
+        match self {
+            PerformanceProfile::HighQuality => Duration::from_millis(100),
+            PerformanceProfile::Balanced => Duration::from_millis(200),
+            PerformanceProfile::Performance => Duration::from_millis(400),
+        }
+    }
+
+    /// کمترین عرض کندل (پیکسل) که زیر آن کندل‌ها تجمیع می‌شوند؛
+    /// آستانه بزرگ‌تر تنظیم شده در پنل همچنان معتبر است
+    pub fn min_simplify_px(self) -> f32 {
+        match self {
+            PerformanceProfile::HighQuality => 0.0,
+            PerformanceProfile::Balanced => 3.0,
+            PerformanceProfile::Performance => 5.0,
+        }
+    }
+}
+
+impl std::fmt::Display for PerformanceProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PerformanceProfile::HighQuality => write!(f, "High quality"),
+            PerformanceProfile::Balanced => write!(f, "Balanced"),
+            PerformanceProfile::Performance => write!(f, "Performance"),
+        }
+    }
+}
+
+static PERFORMANCE_PROFILE: AtomicU8 = AtomicU8::new(PerformanceProfile::HighQuality as u8);
+
+pub fn set_performance_profile(profile: PerformanceProfile) {
+    PERFORMANCE_PROFILE.store(profile as u8, Ordering::Relaxed);
+}
+
+pub fn performance_profile() -> PerformanceProfile {
+    let index = PERFORMANCE_PROFILE.load(Ordering::Relaxed);
+    PerformanceProfile::ALL
+        .get(usize::from(index))
+        .copied()
+        .unwrap_or_default()
+}
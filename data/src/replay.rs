@@ -0,0 +1,722 @@
+//! ضبط رویدادهای بازار (عمق، معاملات و کندل‌ها) روی دیسک و بازپخش آن‌ها در داشبورد
+//!
+//! برخلاف ضبط خام عمق (`exchange::capture`) که برای بازتولید مشکلات همگام‌سازی است، این ضبط
+//! رویدادهای نرمال‌شده را نگه می‌دارد تا در بازپخش از همان مسیر بروزرسانی زنده کل رابط کاربری عبور کنند.
+
+use exchange::adapter::{Event, StreamKind};
+use exchange::depth::{Depth, DepthChange, DepthDiff};
+use exchange::util::Price;
+use exchange::{Kline, Trade};
+
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Seek as _, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+pub const RECORDINGS_DIR: &str = "recordings";
+
+/// فاصله زمانی ثبت تصویر کامل عمق هر جریان (Keyframe)؛ پرش در بازپخش از آخرین تصویر کامل
+/// پیش از نقطه مقصد شروع می‌شود، نه از ابتدای جلسه
+const KEYFRAME_INTERVAL_MS: u64 = 60_000;
+
+/// تنظیمات ضبط جلسه
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RecordingCfg {
+    pub budget_mb: u64, // حداکثر حجم هر فایل ضبط (مگابایت)
+}
+
+impl Default for RecordingCfg {
+    fn default() -> Self {
+        Self { budget_mb: 200 }
+    }
+}
+
+impl RecordingCfg {
+    pub const BUDGET_RANGE: std::ops::RangeInclusive<u64> = 10..=2000;
+
+    pub fn budget_bytes(&self) -> u64 {
+        self.budget_mb
+            .clamp(*Self::BUDGET_RANGE.start(), *Self::BUDGET_RANGE.end())
+            * 1024
+            * 1024
+    }
+}
+
+/// خطاهای ضبط
+#[derive(thiserror::Error, Debug, Clone)]
+pub enum RecordError {
+    #[error("Recording size budget reached")]
+    BudgetReached,
+    #[error("Failed to write recording: {0}")]
+    Io(String),
+}
+
+/// معامله قابل ذخیره
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct RecordedTrade {
+    pub time: u64,
+    pub is_sell: bool,
+    pub price: Price,
+    pub qty: f32,
+}
+
+/// کندل قابل ذخیره
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct RecordedKline {
+    pub time: u64,
+    pub open: Price,
+    pub high: Price,
+    pub low: Price,
+    pub close: Price,
+    pub volume: (f32, f32),
+    pub trade_count: Option<u64>,
+}
+
+/// محتوای یک رویداد ضبط شده
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Payload {
+    Depth {
+        update_t: u64,              // زمان بروزرسانی عمق در صرافی
+        snapshot: bool,             // تصویر کامل یا تغییرات
+        bids: Vec<(Price, f32)>,    // سطوح خرید (در تغییرات، حجم صفر یعنی حذف)
+        asks: Vec<(Price, f32)>,    // سطوح فروش
+        trades: Vec<RecordedTrade>, // معاملات همراه بروزرسانی
+    },
+    Kline(RecordedKline),
+}
+
+/// یک خط فایل ضبط: رویداد یک جریان به همراه زمان دریافت آن (میلی‌ثانیه UTC)
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Recorded {
+    pub at: u64,
+    pub stream: StreamKind,
+    pub payload: Payload,
+}
+
+impl Recorded {
+    /// بازسازی رویداد بازار برای عبور از مسیر بروزرسانی زنده
+    pub fn to_event(&self) -> Event {
+        match &self.payload {
+            Payload::Depth {
+                update_t,
+                snapshot,
+                bids,
+                asks,
+                trades,
+            } => {
+                let change = if *snapshot {
+                    DepthChange::Snapshot(Arc::new(Depth {
+                        bids: bids.iter().copied().collect(),
+                        asks: asks.iter().copied().collect(),
+                    }))
+                } else {
+                    DepthChange::Diff(Arc::new(DepthDiff {
+                        bids: bids.clone(),
+                        asks: asks.clone(),
+                    }))
+                };
+                let trades = trades
+                    .iter()
+                    .map(|t| Trade {
+                        time: t.time,
+                        is_sell: t.is_sell,
+                        price: t.price,
+                        qty: t.qty,
+                    })
+                    .collect();
+
+                Event::DepthReceived(self.stream, *update_t, change, trades)
+            }
+            Payload::Kline(k) => Event::KlineReceived(
+                self.stream,
+                Kline {
+                    time: k.time,
+                    open: k.open,
+                    high: k.high,
+                    low: k.low,
+                    close: k.close,
+                    volume: k.volume,
+                    trade_count: k.trade_count,
+                },
+            ),
+        }
+    }
+}
+
+/// نویسنده فایل ضبط یک جلسه با سقف حجم
+pub struct Recorder {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    written: u64,
+    budget: u64,
+    keyframes: FxHashMap<StreamKind, u64>, // زمان آخرین تصویر کامل عمق ثبت شده هر جریان
+}
+
+impl Recorder {
+    /// ایجاد فایل ضبط جدید در پوشه ضبط‌ها با زمان شروع در نام فایل
+    pub fn create(cfg: RecordingCfg) -> std::io::Result<Self> {
+        let dir = crate::data_path(Some(RECORDINGS_DIR));
+        std::fs::create_dir_all(&dir)?;
+
+        let file_name = format!(
+            "session_{}.jsonl",
+            chrono::Utc::now().format("%Y%m%d_%H%M%S")
+        );
+        Self::create_at(dir.join(file_name), cfg.budget_bytes())
+    }
+
+    fn create_at(path: PathBuf, budget: u64) -> std::io::Result<Self> {
+        Ok(Self {
+            writer: BufWriter::new(File::create(&path)?),
+            path,
+            written: 0,
+            budget,
+            keyframes: FxHashMap::default(),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    /// ثبت بروزرسانی عمق؛ اولین رویداد هر جریان و سپس هر `KEYFRAME_INTERVAL_MS` یک رویداد
+    /// به صورت تصویر کامل دفتر سفارش ذخیره می‌شود تا بازپخش از همان نقطه قابل شروع باشد
+    pub fn record_depth(
+        &mut self,
+        at: u64,
+        stream: &StreamKind,
+        update_t: u64,
+        change: &DepthChange,
+        book: &Depth,
+        trades: &[Trade],
+    ) -> Result<(), RecordError> {
+        let snapshot = matches!(change, DepthChange::Snapshot(_))
+            || self
+                .keyframes
+                .get(stream)
+                .is_none_or(|last| at.saturating_sub(*last) >= KEYFRAME_INTERVAL_MS);
+
+        let (bids, asks) = match change {
+            DepthChange::Diff(diff) if !snapshot => (diff.bids.clone(), diff.asks.clone()),
+            _ => (
+                book.bids.iter().map(|(p, q)| (*p, *q)).collect(),
+                book.asks.iter().map(|(p, q)| (*p, *q)).collect(),
+            ),
+        };
+
+        self.write(&Recorded {
+            at,
+            stream: *stream,
+            payload: Payload::Depth {
+                update_t,
+                snapshot,
+                bids,
+                asks,
+                trades: trades
+                    .iter()
+                    .map(|t| RecordedTrade {
+                        time: t.time,
+                        is_sell: t.is_sell,
+                        price: t.price,
+                        qty: t.qty,
+                    })
+                    .collect(),
+            },
+        })?;
+
+        if snapshot {
+            self.keyframes.insert(*stream, at);
+        }
+        Ok(())
+    }
+
+    pub fn record_kline(
+        &mut self,
+        at: u64,
+        stream: &StreamKind,
+        kline: &Kline,
+    ) -> Result<(), RecordError> {
+        self.write(&Recorded {
+            at,
+            stream: *stream,
+            payload: Payload::Kline(RecordedKline {
+                time: kline.time,
+                open: kline.open,
+                high: kline.high,
+                low: kline.low,
+                close: kline.close,
+                volume: kline.volume,
+                trade_count: kline.trade_count,
+            }),
+        })
+    }
+
+    fn write(&mut self, record: &Recorded) -> Result<(), RecordError> {
+        let mut line = serde_json::to_vec(record).map_err(|e| RecordError::Io(e.to_string()))?;
+        line.push(b'\n');
+
+        if self.written + line.len() as u64 > self.budget {
+            return Err(RecordError::BudgetReached);
+        }
+
+        self.writer
+            .write_all(&line)
+            .map_err(|e| RecordError::Io(e.to_string()))?;
+        self.written += line.len() as u64;
+
+        Ok(())
+    }
+
+    /// تخلیه بافر و بستن فایل
+    pub fn finish(mut self) -> Result<PathBuf, RecordError> {
+        self.writer
+            .flush()
+            .map_err(|e| RecordError::Io(e.to_string()))?;
+        Ok(self.path)
+    }
+}
+
+/// فایل‌های ضبط موجود، جدیدترین اول
+pub fn list_recordings() -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(crate::data_path(Some(RECORDINGS_DIR))) else {
+        return vec![];
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+        .collect();
+
+    paths.sort_unstable_by(|a, b| b.cmp(a));
+    paths
+}
+
+/// سرآیند یک خط فایل ضبط؛ برای ساخت فهرست بدون نگه داشتن سطوح عمق و معاملات
+#[derive(Deserialize)]
+struct Header {
+    at: u64,
+    stream: StreamKind,
+    payload: PayloadHeader,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum PayloadHeader {
+    Depth { snapshot: bool },
+    Kline {},
+}
+
+/// تصویر کامل عمق یک جریان در فایل ضبط
+#[derive(Debug, Clone, Copy)]
+struct Keyframe {
+    at: u64,
+    offset: u64, // موقعیت بایت خط در فایل
+    line: usize, // اندیس رویداد
+    stream: StreamKind,
+}
+
+/// نقطه شروع بازپخش پس از پرش: تصویر کامل جریانی که از همه زودتر است
+struct RestartPoint {
+    offset: u64,
+    line: usize,
+    /// اندیس تصویر کامل هر جریان؛ تغییرات عمق آن جریان پیش از این اندیس کنار گذاشته می‌شوند
+    pending: FxHashMap<StreamKind, usize>,
+}
+
+/// فهرست یک فایل ضبط: بازه زمانی، تعداد رویدادها و محل تصاویر کامل عمق
+struct RecordingIndex {
+    start: u64,
+    end: u64,
+    len: usize,
+    keyframes: Vec<Keyframe>,
+}
+
+impl RecordingIndex {
+    /// پیمایش یک‌باره فایل؛ تنها سرآیند خطوط خوانده و نگه داشته می‌شود
+    fn scan(path: &Path) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        let mut reader = BufReader::new(file);
+
+        let mut index = Self {
+            start: u64::MAX,
+            end: 0,
+            len: 0,
+            keyframes: vec![],
+        };
+        let mut line = String::new();
+        let (mut offset, mut line_no) = (0u64, 0usize);
+
+        loop {
+            line.clear();
+            let read = reader.read_line(&mut line).map_err(|e| e.to_string())?;
+            if read == 0 {
+                break;
+            }
+            line_no += 1;
+
+            if !line.trim().is_empty() {
+                let header: Header =
+                    serde_json::from_str(&line).map_err(|e| format!("line {line_no}: {e}"))?;
+
+                if let PayloadHeader::Depth { snapshot: true } = header.payload {
+                    index.keyframes.push(Keyframe {
+                        at: header.at,
+                        offset,
+                        line: index.len,
+                        stream: header.stream,
+                    });
+                }
+                index.start = index.start.min(header.at);
+                index.end = index.end.max(header.at);
+                index.len += 1;
+            }
+            offset += read as u64;
+        }
+
+        Ok(index)
+    }
+
+    /// آخرین تصویر کامل هر جریان تا زمان `position`؛ بدون تصویر کامل، ابتدای فایل
+    fn restart_point(&self, position: u64) -> RestartPoint {
+        let mut latest: FxHashMap<StreamKind, Keyframe> = FxHashMap::default();
+
+        for keyframe in self.keyframes.iter().take_while(|k| k.at <= position) {
+            latest.insert(keyframe.stream, *keyframe);
+        }
+
+        let earliest = latest.values().min_by_key(|keyframe| keyframe.line);
+
+        RestartPoint {
+            offset: earliest.map_or(0, |keyframe| keyframe.offset),
+            line: earliest.map_or(0, |keyframe| keyframe.line),
+            pending: latest
+                .values()
+                .map(|keyframe| (keyframe.stream, keyframe.line))
+                .collect(),
+        }
+    }
+}
+
+/// جابجایی در زمان بازپخش
+#[derive(Debug, Clone, PartialEq)]
+pub enum Seek {
+    Forward(Vec<Recorded>), // رویدادهای بین موقعیت قبلی و جدید باید پخش شوند
+    /// پنل‌ها باید پاک شوند و رویدادها از آخرین تصویر کامل عمق تا موقعیت جدید پخش شوند
+    Restart(Vec<Recorded>),
+}
+
+/// وضعیت بازپخش یک فایل ضبط؛ رویدادها هنگام پخش از فایل خوانده می‌شوند
+pub struct Playback {
+    index: RecordingIndex,
+    reader: BufReader<File>,
+    next: Option<Recorded>, // رویداد بعدی خوانده شده و هنوز پخش نشده
+    cursor: usize,          // اندیس اولین رویداد پخش نشده
+    position: u64,          // زمان فعلی بازپخش (میلی‌ثانیه، بر حسب زمان ضبط)
+    pub playing: bool,
+    pub speed: f32,
+}
+
+impl Playback {
+    pub const SPEEDS: [f32; 6] = [0.25, 0.5, 1.0, 2.0, 5.0, 10.0];
+
+    /// بازپخش از ابتدای ضبط و در حالت توقف؛ ضبط خالی `None`
+    pub fn open(path: &Path) -> Result<Option<Self>, String> {
+        let index = RecordingIndex::scan(path)?;
+        if index.len == 0 {
+            return Ok(None);
+        }
+
+        let file = File::open(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        let mut playback = Self {
+            position: index.start,
+            index,
+            reader: BufReader::new(file),
+            next: None,
+            cursor: 0,
+            playing: false,
+            speed: 1.0,
+        };
+        playback.next = playback.read_next();
+
+        Ok(Some(playback))
+    }
+
+    pub fn start(&self) -> u64 {
+        self.index.start
+    }
+
+    pub fn end(&self) -> u64 {
+        self.index.end
+    }
+
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.len == 0
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next.is_none()
+    }
+
+    /// جلو بردن زمان بازپخش به اندازه زمان سپری شده ضرب در سرعت
+    pub fn advance(&mut self, elapsed: Duration) -> Vec<Recorded> {
+        if !self.playing {
+            return vec![];
+        }
+
+        let step = (elapsed.as_secs_f64() * 1000.0 * f64::from(self.speed)) as u64;
+        let events = self.move_to(self.position.saturating_add(step).min(self.end()));
+
+        if self.is_finished() {
+            self.playing = false;
+        }
+        events
+    }
+
+    /// توقف و پخش دسته بعدی رویدادها (همه رویدادهای هم‌زمان)
+    pub fn step(&mut self) -> Vec<Recorded> {
+        self.playing = false;
+
+        match self.next.as_ref().map(|next| next.at) {
+            Some(at) => self.move_to(at),
+            None => vec![],
+        }
+    }
+
+    /// پرش به یک زمان؛ پرش به عقب، یا پرشی که از یک تصویر کامل عمق می‌گذرد، پنل‌ها را از
+    /// آخرین تصویر کامل پیش از مقصد بازسازی می‌کند تا کل جلسه دوباره پخش نشود
+    pub fn seek(&mut self, position: u64) -> Seek {
+        let position = position.clamp(self.start(), self.end());
+        let restart = self.index.restart_point(position);
+
+        if position >= self.position && restart.line <= self.cursor {
+            return Seek::Forward(self.move_to(position));
+        }
+
+        if let Err(err) = self.reader.seek(SeekFrom::Start(restart.offset)) {
+            log::warn!("Failed to seek recording: {err}");
+            return Seek::Restart(vec![]);
+        }
+        self.cursor = restart.line;
+        self.position = position;
+        self.next = self.read_next();
+
+        let mut events = vec![];
+        while let Some(event) = self.next_until(position) {
+            let is_diff = match event.payload {
+                Payload::Depth { snapshot, .. } => !snapshot,
+                Payload::Kline(_) => false,
+            };
+            let before_keyframe = is_diff
+                && restart
+                    .pending
+                    .get(&event.stream)
+                    .is_some_and(|line| self.cursor <= *line);
+
+            if !before_keyframe {
+                events.push(event);
+            }
+        }
+
+        Seek::Restart(events)
+    }
+
+    fn move_to(&mut self, position: u64) -> Vec<Recorded> {
+        let mut events = vec![];
+        while let Some(event) = self.next_until(position) {
+            events.push(event);
+        }
+
+        self.position = position;
+        events
+    }
+
+    /// رویداد بعدی در صورتی که تا زمان `position` رخ داده باشد
+    fn next_until(&mut self, position: u64) -> Option<Recorded> {
+        if self.next.as_ref()?.at > position {
+            return None;
+        }
+
+        let next = self.read_next();
+        self.cursor += 1;
+        std::mem::replace(&mut self.next, next)
+    }
+
+    fn read_next(&mut self) -> Option<Recorded> {
+        let mut line = String::new();
+
+        loop {
+            line.clear();
+            match self.reader.read_line(&mut line) {
+                Ok(0) => return None,
+                Ok(_) if line.trim().is_empty() => {}
+                Ok(_) => {
+                    return serde_json::from_str(&line)
+                        .inspect_err(|e| log::warn!("Failed to read recorded event: {e}"))
+                        .ok();
+                }
+                Err(err) => {
+                    log::warn!("Failed to read recording: {err}");
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exchange::adapter::{Exchange, StreamTicksize};
+    use exchange::{PushFrequency, Ticker, TickerInfo};
+
+    fn stream() -> StreamKind {
+        StreamKind::DepthAndTrades {
+            ticker_info: TickerInfo::new(
+                Ticker::new("BTCUSDT", Exchange::BinanceLinear),
+                0.1,
+                0.001,
+                None,
+            ),
+            depth_aggr: StreamTicksize::Client,
+            push_freq: PushFrequency::ServerDefault,
+        }
+    }
+
+    fn recorded(at: u64) -> Recorded {
+        Recorded {
+            at,
+            stream: stream(),
+            payload: Payload::Depth {
+                update_t: at,
+                snapshot: false,
+                bids: vec![(Price::from_f32(100.0), 1.5)],
+                asks: vec![],
+                trades: vec![RecordedTrade {
+                    time: at,
+                    is_sell: true,
+                    price: Price::from_f32(100.0),
+                    qty: 0.5,
+                }],
+            },
+        }
+    }
+
+    #[test]
+    fn recorded_events_round_trip() {
+        let event = recorded(1_000);
+        let line = serde_json::to_string(&event).unwrap();
+        assert_eq!(serde_json::from_str::<Recorded>(&line).unwrap(), event);
+
+        let Event::DepthReceived(_, update_t, DepthChange::Diff(diff), trades) = event.to_event()
+        else {
+            panic!("expected a depth diff");
+        };
+        assert_eq!(update_t, 1_000);
+        assert_eq!(diff.bids, vec![(Price::from_f32(100.0), 1.5)]);
+        assert!(trades[0].is_sell);
+    }
+
+    /// فایل موقت با نام یکتا برای آزمون‌های بازپخش
+    fn temp_recording(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "flowsurface_{name}_{}_{}.jsonl",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ))
+    }
+
+    #[test]
+    fn playback_advances_steps_and_seeks() {
+        let path = temp_recording("playback");
+        let lines = [1_000, 1_500, 1_500, 4_000]
+            .map(|at| serde_json::to_string(&recorded(at)).unwrap())
+            .join("\n");
+        std::fs::write(&path, lines).unwrap();
+
+        let mut playback = Playback::open(&path).unwrap().unwrap();
+        assert_eq!(playback.len(), 4);
+
+        // در حالت توقف زمان جلو نمی‌رود
+        assert!(playback.advance(Duration::from_secs(10)).is_empty());
+
+        playback.playing = true;
+        playback.speed = 2.0;
+        assert_eq!(playback.advance(Duration::from_millis(250)).len(), 3);
+        assert_eq!(playback.position(), 1_500);
+
+        assert_eq!(playback.step(), vec![recorded(4_000)]);
+        assert!(!playback.playing);
+        assert!(playback.is_finished());
+
+        assert_eq!(playback.seek(1_200), Seek::Restart(vec![recorded(1_000)]));
+        assert_eq!(
+            playback.seek(2_000),
+            Seek::Forward(vec![recorded(1_500), recorded(1_500)])
+        );
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn seek_restarts_from_latest_keyframe() {
+        let path = temp_recording("keyframes");
+        let mut recorder = Recorder::create_at(path.clone(), u64::MAX).unwrap();
+
+        let diff = DepthChange::Diff(Arc::new(DepthDiff {
+            bids: vec![(Price::from_f32(100.0), 1.0)],
+            asks: vec![],
+        }));
+        for at in (0..=200_000).step_by(10_000) {
+            recorder
+                .record_depth(at, &stream(), at, &diff, &Depth::default(), &[])
+                .unwrap();
+        }
+        recorder.finish().unwrap();
+
+        let mut playback = Playback::open(&path).unwrap().unwrap();
+        assert_eq!(playback.len(), 21);
+
+        let is_snapshot =
+            |event: &Recorded| matches!(event.payload, Payload::Depth { snapshot: true, .. });
+
+        // پرش از روی تصویر کامل ۱۲۰ ثانیه: تنها از همان تصویر کامل پخش می‌شود
+        let Seek::Restart(events) = playback.seek(130_000) else {
+            panic!("expected a restart");
+        };
+        assert_eq!(events.len(), 2);
+        assert!(is_snapshot(&events[0]));
+        assert_eq!(events[0].at, 120_000);
+
+        // پرش به عقب از تصویر کامل ۶۰ ثانیه
+        let Seek::Restart(events) = playback.seek(100_000) else {
+            panic!("expected a restart");
+        };
+        assert_eq!(events.len(), 5);
+        assert_eq!(events[0].at, 60_000);
+
+        let Seek::Forward(events) = playback.seek(110_000) else {
+            panic!("expected a forward seek");
+        };
+        assert_eq!(events.len(), 1);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,105 @@
+//! تشخیص کهنگی داده‌ها: اگر جریان داده یک پنل مدتی پیامی نفرستد، داده‌های آن کهنه به حساب می‌آید
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// کمترین آستانه خودکار؛ حتی پنل‌های ۱۰۰ میلی‌ثانیه‌ای چند ثانیه وقفه را تحمل می‌کنند
+const MIN_AUTO_THRESHOLD_MS: u64 = 15_000;
+/// بیشترین آستانه خودکار، برای بازه‌های زمانی بلند
+const MAX_AUTO_THRESHOLD_MS: u64 = 300_000;
+
+/// مدت زمان بدون داده‌ای که پس از آن داده‌های پنل کهنه نمایش داده می‌شوند
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum StaleThreshold {
+    Off,
+    #[default]
+    Auto, // متناسب با بازه زمانی پنل
+    S10,
+    S30,
+    M1,
+    M5,
+}
+
+impl StaleThreshold {
+    pub const ALL: [StaleThreshold; 6] = [
+        StaleThreshold::Off,
+        StaleThreshold::Auto,
+        StaleThreshold::S10,
+        StaleThreshold::S30,
+        StaleThreshold::M1,
+        StaleThreshold::M5,
+    ];
+
+    /// آستانه بر حسب میلی‌ثانیه برای پنلی با بازه زمانی `interval_ms`؛ در حالت غیرفعال `None`
+    ///
+    /// در حالت خودکار آستانه یک بیستم بازه زمانی پنل است، محدود به ۱۰ ثانیه تا ۵ دقیقه؛
+    /// پنل‌های بدون بازه زمانی (مثلاً مبنای تیکی) کمترین آستانه را می‌گیرند.
+    pub fn to_millis(self, interval_ms: Option<u64>) -> Option<u64> {
+        match self {
+            StaleThreshold::Off => None,
+            StaleThreshold::Auto => Some(
+                interval_ms
+                    .map_or(0, |ms| ms / 20)
+                    .clamp(MIN_AUTO_THRESHOLD_MS, MAX_AUTO_THRESHOLD_MS),
+            ),
+            StaleThreshold::S10 => Some(10_000),
+            StaleThreshold::S30 => Some(30_000),
+            StaleThreshold::M1 => Some(60_000),
+            StaleThreshold::M5 => Some(300_000),
+        }
+    }
+}
+
+impl std::fmt::Display for StaleThreshold {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StaleThreshold::Off => write!(f, "Off"),
+            StaleThreshold::Auto => write!(f, "Auto"),
+            StaleThreshold::S10 => write!(f, "10s"),
+            StaleThreshold::S30 => write!(f, "30s"),
+            StaleThreshold::M1 => write!(f, "1m"),
+            StaleThreshold::M5 => write!(f, "5m"),
+        }
+    }
+}
+
+static STALE_THRESHOLD: AtomicU8 = AtomicU8::new(StaleThreshold::Auto as u8);
+
+pub fn set_stale_threshold(threshold: StaleThreshold) {
+    STALE_THRESHOLD.store(threshold as u8, Ordering::Relaxed);
+}
+
+pub fn stale_threshold() -> StaleThreshold {
+    let index = STALE_THRESHOLD.load(Ordering::Relaxed);
+    StaleThreshold::ALL
+        .get(usize::from(index))
+        .copied()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_threshold_scales_with_interval() {
+        let auto = |interval_ms| StaleThreshold::Auto.to_millis(interval_ms);
+
+        // هیت‌مپ ۱۰۰ میلی‌ثانیه‌ای و مبنای تیکی
+        assert_eq!(auto(Some(100)), Some(MIN_AUTO_THRESHOLD_MS));
+        assert_eq!(auto(None), Some(MIN_AUTO_THRESHOLD_MS));
+        // ۱۵ دقیقه
+        assert_eq!(auto(Some(900_000)), Some(45_000));
+        // روزانه
+        assert_eq!(auto(Some(86_400_000)), Some(MAX_AUTO_THRESHOLD_MS));
+    }
+
+    #[test]
+    fn fixed_thresholds_ignore_interval() {
+        assert_eq!(
+            StaleThreshold::S30.to_millis(Some(86_400_000)),
+            Some(30_000)
+        );
+        assert_eq!(StaleThreshold::Off.to_millis(Some(100)), None);
+    }
+}
@@ -0,0 +1,165 @@
+//! سرعت نوار معاملات: تعداد معاملات و حجم در ثانیه در یک پنجره زمانی متحرک
+
+use exchange::Trade;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// طول پنجره زمانی محاسبه سرعت نوار
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum TapeSpeedWindow {
+    S5,
+    #[default]
+    S10,
+    S30,
+    S60,
+}
+
+impl TapeSpeedWindow {
+    pub const ALL: [TapeSpeedWindow; 4] = [
+        TapeSpeedWindow::S5,
+        TapeSpeedWindow::S10,
+        TapeSpeedWindow::S30,
+        TapeSpeedWindow::S60,
+    ];
+
+    pub fn to_millis(self) -> u64 {
+        match self {
+            TapeSpeedWindow::S5 => 5_000,
+            TapeSpeedWindow::S10 => 10_000,
+            TapeSpeedWindow::S30 => 30_000,
+            TapeSpeedWindow::S60 => 60_000,
+        }
+    }
+}
+
+impl std::fmt::Display for TapeSpeedWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}s", self.to_millis() / 1_000)
+    }
+}
+
+static TAPE_SPEED_WINDOW: AtomicU8 = AtomicU8::new(TapeSpeedWindow::S10 as u8);
+
+pub fn set_tape_speed_window(window: TapeSpeedWindow) {
+    TAPE_SPEED_WINDOW.store(window as u8, Ordering::Relaxed);
+}
+
+pub fn tape_speed_window() -> TapeSpeedWindow {
+    let index = TAPE_SPEED_WINDOW.load(Ordering::Relaxed);
+    TapeSpeedWindow::ALL
+        .get(usize::from(index))
+        .copied()
+        .unwrap_or_default()
+}
+
+/// سرعت نوار در پنجره فعلی
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct TapeRate {
+    pub trades_per_sec: f32,
+    pub volume_per_sec: f32, // بر حسب واحد نمایش حجم فعال، همان مقداری که فهرست معاملات نشان می‌دهد
+}
+
+/// یک دسته معامله دریافتی به همراه زمان محلی دریافت آن
+#[derive(Debug, Clone, Copy)]
+struct Batch {
+    received_at: u64,
+    trades: usize,
+    volume: f32,
+}
+
+/// پنجره متحرک معاملات دریافتی؛ زمان دریافت محلی مبنا است تا با توقف معاملات مقدار به صفر برسد
+#[derive(Debug, Default, Clone)]
+pub struct TapeSpeed {
+    batches: VecDeque<Batch>,
+}
+
+impl TapeSpeed {
+    /// ثبت یک دسته معامله دریافت شده در زمان `now` (میلی‌ثانیه)
+    ///
+    /// مقدار معاملات از پیش توسط آداپتور صرافی به واحد حجم فعال (پایه یا قیمت‌گذاری) تبدیل شده است
+    pub fn push(&mut self, trades: &[Trade], now: u64) {
+        if !trades.is_empty() {
+            self.batches.push_back(Batch {
+                received_at: now,
+                trades: trades.len(),
+                volume: trades.iter().map(|trade| trade.qty).sum(),
+            });
+        }
+        self.prune(now);
+    }
+
+    /// سرعت معاملات در پنجره منتهی به `now`
+    pub fn rate(&self, now: u64) -> TapeRate {
+        let window = tape_speed_window().to_millis();
+        let since = now.saturating_sub(window);
+
+        let (trades, volume) = self
+            .batches
+            .iter()
+            .filter(|batch| batch.received_at > since)
+            .fold((0, 0.0), |(trades, volume), batch| {
+                (trades + batch.trades, volume + batch.volume)
+            });
+
+        let secs = window as f32 / 1_000.0;
+        TapeRate {
+            trades_per_sec: trades as f32 / secs,
+            volume_per_sec: volume / secs,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.batches.clear();
+    }
+
+    /// حذف دسته‌هایی که از بزرگ‌ترین پنجره ممکن هم قدیمی‌تر هستند
+    fn prune(&mut self, now: u64) {
+        let since = now.saturating_sub(TapeSpeedWindow::S60.to_millis());
+        while self
+            .batches
+            .front()
+            .is_some_and(|batch| batch.received_at <= since)
+        {
+            self.batches.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exchange::util::Price;
+
+    fn trades(count: usize, qty: f32) -> Vec<Trade> {
+        (0..count)
+            .map(|i| Trade {
+                time: i as u64,
+                is_sell: false,
+                price: Price::from_f32(100.0),
+                qty,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rate_is_averaged_over_the_window() {
+        let mut speed = TapeSpeed::default();
+        speed.push(&trades(30, 2.0), 1_000);
+        speed.push(&trades(20, 1.0), 5_000);
+
+        let rate = speed.rate(6_000);
+        let secs = tape_speed_window().to_millis() as f32 / 1_000.0;
+        assert_eq!(rate.trades_per_sec, 50.0 / secs);
+        assert_eq!(rate.volume_per_sec, 80.0 / secs);
+    }
+
+    #[test]
+    fn rate_decays_to_zero_without_new_trades() {
+        let mut speed = TapeSpeed::default();
+        speed.push(&trades(10, 1.0), 1_000);
+
+        let later = 1_000 + TapeSpeedWindow::S60.to_millis();
+        assert_eq!(speed.rate(later), TapeRate::default());
+    }
+}
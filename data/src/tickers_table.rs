@@ -3,15 +3,122 @@ use exchange::{
     adapter::{Exchange, ExchangeInclusive, MarketKind},
 };
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::util::glob_match;
 
 /// تنظیمات مربوط به جدول نمادهای معاملاتی
+///
+/// فیلدهایی که در وضعیت‌های ذخیره شده قدیمی وجود ندارند با مقادیر پیش‌فرض پر می‌شوند.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
 pub struct Settings {
     pub favorited_tickers: Vec<Ticker>,      // لیست نمادهای مورد علاقه
     pub show_favorites: bool,                // نمایش فقط مورد علاقه‌ها
-    pub selected_sort_option: SortOptions,   // گزینه مرتب‌سازی انتخاب شده
+    pub selected_sort_option: SortOptions,   // گزینه مرتب‌سازی انتخاب شده (ستون و جهت)
     pub selected_exchanges: Vec<ExchangeInclusive>, // صرافی‌های انتخاب شده
     pub selected_markets: Vec<MarketKind>,   // بازارهای انتخاب شده (Spot, Futures, ...)
+    pub search_query: String,                // متن جستجوی جدول
+    pub stats_refresh_secs: u64,             // فاصله بروزرسانی آمار هر صرافی (ثانیه)
+    pub symbol_filters: Vec<SymbolFilter>,   // الگوهای شمول/حذف نمادها برای هر صرافی
+    pub columns: Vec<ColumnCfg>,             // ترتیب و عرض ستون‌های عددی کارت نمادها
+    pub fixed_decimals: bool,                // اعشار ثابت قیمت بر اساس حداقل گام قیمت هر نماد
+    pub quote_filter: QuoteFilter,           // محدود کردن جدول به یک ارز مظنه
+    pub group_by_quote: bool,                // گروه‌بندی نمادها زیر سرتیتر ارز مظنه
+}
+
+impl Settings {
+    pub const REFRESH_SECS_RANGE: std::ops::RangeInclusive<u64> = 5..=120;
+
+    /// فاصله بروزرسانی آمار هر صرافی، محدود به بازه مجاز
+    pub fn stats_refresh_interval(&self) -> Duration {
+        Duration::from_secs(self.stats_refresh_secs.clamp(
+            *Self::REFRESH_SECS_RANGE.start(),
+            *Self::REFRESH_SECS_RANGE.end(),
+        ))
+    }
+
+    /// ستون‌های ذخیره شده بدون تکرار، با عرض مجاز و ستون‌های جاافتاده در انتها
+    pub fn table_columns(&self) -> Vec<ColumnCfg> {
+        let mut columns: Vec<ColumnCfg> = Vec::with_capacity(TableColumn::ALL.len());
+
+        for cfg in self.columns.iter().chain(ColumnCfg::defaults().iter()) {
+            if !columns.iter().any(|c| c.column == cfg.column) {
+                columns.push(ColumnCfg {
+                    width: cfg.width.clamp(
+                        *ColumnCfg::WIDTH_RANGE.start(),
+                        *ColumnCfg::WIDTH_RANGE.end(),
+                    ),
+                    ..*cfg
+                });
+            }
+        }
+
+        columns
+    }
+}
+
+/// ستون‌های عددی کارت نمادها
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum TableColumn {
+    Price,  // آخرین قیمت
+    Change, // درصد تغییرات روزانه
+    Volume, // حجم روزانه
+}
+
+impl TableColumn {
+    pub const ALL: [TableColumn; 3] =
+        [TableColumn::Price, TableColumn::Change, TableColumn::Volume];
+}
+
+impl std::fmt::Display for TableColumn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TableColumn::Price => write!(f, "Price"),
+            TableColumn::Change => write!(f, "Change"),
+            TableColumn::Volume => write!(f, "Volume"),
+        }
+    }
+}
+
+/// یک ستون عددی و عرض آن (پیکسل)
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct ColumnCfg {
+    pub column: TableColumn,
+    pub width: f32,
+}
+
+impl ColumnCfg {
+    pub const WIDTH_RANGE: std::ops::RangeInclusive<f32> = 36.0..=160.0;
+
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            ColumnCfg {
+                column: TableColumn::Price,
+                width: 60.0,
+            },
+            ColumnCfg {
+                column: TableColumn::Change,
+                width: 48.0,
+            },
+            ColumnCfg {
+                column: TableColumn::Volume,
+                width: 48.0,
+            },
+        ]
+    }
+}
+
+/// جدا کردن بخش صحیح و اعشاری یک عدد قالب‌بندی شده برای هم‌ترازی روی ممیز؛
+/// پیشوند و پسوندهایی مثل `$`، `%` یا `m` همراه بخش مربوطه می‌مانند
+pub fn split_decimal(formatted: &str) -> (&str, &str) {
+    let point = formatted.find('.').unwrap_or_else(|| {
+        formatted
+            .rfind(|c: char| c.is_ascii_digit())
+            .map_or(formatted.len(), |i| i + 1)
+    });
+
+    formatted.split_at(point)
 }
 
 impl Default for Settings {
@@ -22,10 +129,142 @@ impl Default for Settings {
             selected_sort_option: SortOptions::VolumeDesc,
             selected_exchanges: ExchangeInclusive::ALL.to_vec(),
             selected_markets: MarketKind::ALL.into_iter().collect(),
+            search_query: String::new(),
+            stats_refresh_secs: 15,
+            symbol_filters: SymbolFilter::defaults(),
+            columns: ColumnCfg::defaults(),
+            fixed_decimals: false,
+            quote_filter: QuoteFilter::All,
+            group_by_quote: false,
+        }
+    }
+}
+
+/// الگوهای glob برای محدود کردن نمادهای یک صرافی و/یا نوع بازار در جدول
+///
+/// هر قانونی که به نماد مربوط باشد باید آن را بپذیرد: اگر `include` خالی نباشد نماد باید با یکی
+/// از آن‌ها تطبیق کند و نباید با هیچ‌یک از `exclude` تطبیق کند. الگوها روی نماد اصلی صرافی
+/// (مثلاً `BTCUSDT` یا `BTC3L_USDT`) و بدون حساسیت به بزرگی حروف اعمال می‌شوند.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct SymbolFilter {
+    pub exchange: Option<ExchangeInclusive>, // `None` یعنی همه صرافی‌ها
+    pub market: Option<MarketKind>,          // `None` یعنی همه بازارها
+    pub include: Vec<String>,                // فقط این نمادها (خالی یعنی همه)
+    pub exclude: Vec<String>,                // نمادهای پنهان
+}
+
+impl SymbolFilter {
+    /// پیش‌فرض: پنهان کردن توکن‌های اهرمی (UP/DOWN/BULL/BEAR و 3L/3S و مشابه) در بازار اسپات
+    pub fn defaults() -> Vec<Self> {
+        let exclude = [
+            "???*UPUSDT",
+            "???*DOWNUSDT",
+            "*BULL*",
+            "*BEAR*",
+            "*3L_USDT",
+            "*3S_USDT",
+            "*5L_USDT",
+            "*5S_USDT",
+            "*3LUSDT",
+            "*3SUSDT",
+            "*5LUSDT",
+            "*5SUSDT",
+        ];
+
+        vec![Self {
+            exchange: None,
+            market: Some(MarketKind::Spot),
+            include: vec![],
+            exclude: exclude.iter().map(|p| (*p).to_string()).collect(),
+        }]
+    }
+
+    fn applies_to(&self, ticker: &Ticker) -> bool {
+        self.exchange
+            .is_none_or(|exchange| exchange == ExchangeInclusive::of(ticker.exchange))
+            && self
+                .market
+                .is_none_or(|market| market == ticker.market_type())
+    }
+
+    fn allows(&self, symbol: &str) -> bool {
+        let included =
+            self.include.is_empty() || self.include.iter().any(|p| glob_match(p, symbol));
+
+        included && !self.exclude.iter().any(|p| glob_match(p, symbol))
+    }
+
+    /// آیا نماد از همه قانون‌های مربوط به صرافی و بازارش عبور می‌کند
+    pub fn is_allowed(filters: &[Self], ticker: &Ticker) -> bool {
+        let (symbol, _) = ticker.to_full_symbol_and_type();
+
+        filters
+            .iter()
+            .filter(|filter| filter.applies_to(ticker))
+            .all(|filter| filter.allows(&symbol))
+    }
+}
+
+/// فاصله بین دو درخواست پیاپی وقتی بروزرسانی صرافی‌ها در طول یک دوره پخش می‌شود
+///
+/// هر صرافی در هر دوره یک بار بروز می‌شود و درخواست‌ها به جای ارسال همزمان با فاصله مساوی ارسال می‌شوند.
+pub fn staggered_period(interval: Duration, exchanges: usize) -> Duration {
+    const MIN_PERIOD: Duration = Duration::from_millis(500);
+
+    (interval / exchanges.max(1) as u32).max(MIN_PERIOD)
+}
+
+/// فیلتر جدول بر اساس ارز مظنه نماد (مثلاً فقط جفت‌های USDT)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum QuoteFilter {
+    #[default]
+    All, // همه ارزهای مظنه
+    Only(String), // فقط نمادهای با این ارز مظنه
+}
+
+impl QuoteFilter {
+    pub fn matches(&self, ticker: &Ticker) -> bool {
+        match self {
+            QuoteFilter::All => true,
+            QuoteFilter::Only(quote) => ticker.quote_asset() == Some(quote.as_str()),
         }
     }
 }
 
+impl std::fmt::Display for QuoteFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuoteFilter::All => write!(f, "All quotes"),
+            QuoteFilter::Only(quote) => write!(f, "{quote}"),
+        }
+    }
+}
+
+/// گروه‌بندی ردیف‌ها بر اساس ارز مظنه با حفظ ترتیب مرتب‌سازی درون هر گروه؛
+/// گروه‌های پرتعدادتر اول می‌آیند و نمادهای بدون ارز مظنه شناخته شده در انتها
+pub fn quote_groups<'a>(
+    rows: &[&'a TickerRowData],
+) -> Vec<(Option<&'static str>, Vec<&'a TickerRowData>)> {
+    let mut groups: Vec<(Option<&'static str>, Vec<&'a TickerRowData>)> = vec![];
+
+    for row in rows {
+        let quote = row.ticker.quote_asset();
+        match groups.iter_mut().find(|(q, _)| *q == quote) {
+            Some((_, group)) => group.push(row),
+            None => groups.push((quote, vec![row])),
+        }
+    }
+
+    groups.sort_by(|(qa, a), (qb, b)| {
+        qa.is_none()
+            .cmp(&qb.is_none())
+            .then(b.len().cmp(&a.len()))
+            .then(qa.cmp(qb))
+    });
+    groups
+}
+
 /// گزینه‌های مرتب‌سازی جدول
 #[derive(Default, Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
 pub enum SortOptions {
@@ -54,13 +293,26 @@ pub struct TickerRowData {
     pub is_favorited: bool,                 // آیا در لیست علاقه‌مندی‌هاست؟
 }
 
+impl TickerDisplayData {
+    /// متن نمایشی یک ستون عددی
+    pub fn column_value(&self, column: TableColumn) -> &str {
+        match column {
+            TableColumn::Price => &self.last_price_display,
+            TableColumn::Change => &self.daily_change_pct,
+            TableColumn::Volume => &self.volume_display,
+        }
+    }
+}
+
 /// داده‌های آماده برای نمایش در رابط کاربری
 #[derive(Clone)]
 pub struct TickerDisplayData {
     pub display_ticker: String,               // نام نمایشی نماد
     pub daily_change_pct: String,             // درصد تغییرات روزانه
     pub volume_display: String,               // حجم نمایشی (اختصاری)
-    pub mark_price_display: String,           // قیمت لحظه‌ای نمایشی
+    pub last_price_display: String,           // آخرین قیمت نمایشی
+    pub mark_price_display: String,           // قیمت مارک نمایشی
+    pub index_price_display: String,          // قیمت شاخص نمایشی
     pub price_unchanged_part: String,         // بخش بدون تغییر قیمت (برای هایلایت)
     pub price_changed_part: String,           // بخش تغییر یافته قیمت
     pub price_change_direction: PriceChangeDirection, // جهت تغییر قیمت
@@ -68,20 +320,23 @@ pub struct TickerDisplayData {
 }
 
 /// محاسبه داده‌های نمایشی بر اساس آمار فعلی و قیمت قبلی
+///
+/// با `decimals` قیمت‌ها با تعداد اعشار ثابت (معمولاً از حداقل گام قیمت نماد) نمایش داده می‌شوند.
 pub fn compute_display_data(
     ticker: &Ticker,
     stats: &TickerStats,
     previous_price: Option<f32>,
+    decimals: Option<usize>,
 ) -> TickerDisplayData {
     let (display_ticker, _market) = ticker.display_symbol_and_type();
 
-    let current_price = stats.mark_price;
+    let current_price = stats.last_price;
     let (price_unchanged_part, price_changed_part, price_change_direction) =
         if let Some(prev_price) = previous_price {
-            split_price_changes(prev_price, current_price)
+            split_price_changes(prev_price, current_price, decimals)
         } else {
             (
-                current_price.to_string(),
+                price_display(current_price, decimals),
                 String::new(),
                 PriceChangeDirection::Unchanged,
             )
@@ -91,7 +346,9 @@ pub fn compute_display_data(
         display_ticker,
         daily_change_pct: super::util::pct_change(stats.daily_price_chg),
         volume_display: super::util::currency_abbr(stats.daily_volume),
-        mark_price_display: stats.mark_price.to_string(),
+        last_price_display: price_display(stats.last_price, decimals),
+        mark_price_display: price_display(stats.mark_price, decimals),
+        index_price_display: price_display(stats.index_price, decimals),
         price_unchanged_part,
         price_changed_part,
         price_change_direction,
@@ -99,21 +356,30 @@ pub fn compute_display_data(
     }
 }
 
+/// نمایش قیمت با اعمال جداکننده هزارگان در صورت فعال بودن
+fn price_display(price: f32, decimals: Option<usize>) -> String {
+    match decimals {
+        Some(decimals) => crate::util::format_price(price, decimals),
+        None => crate::util::number_format().separate(price.to_string()),
+    }
+}
+
 /// تشخیص بخش‌های تغییر یافته و ثابت قیمت برای هایلایت کردن در UI
 fn split_price_changes(
     previous_price: f32,
     current_price: f32,
+    decimals: Option<usize>,
 ) -> (String, String, PriceChangeDirection) {
     if previous_price == current_price {
         return (
-            current_price.to_string(),
+            price_display(current_price, decimals),
             String::new(),
             PriceChangeDirection::Unchanged,
         );
     }
 
-    let prev_str = previous_price.to_string();
-    let curr_str = current_price.to_string();
+    let prev_str = price_display(previous_price, decimals);
+    let curr_str = price_display(current_price, decimals);
 
     let direction = if current_price > previous_price {
         PriceChangeDirection::Increased
@@ -142,3 +408,166 @@ fn split_price_changes(
 
     (unchanged_part, changed_part, direction)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::sidebar::{Position, Sidebar};
+
+    #[test]
+    fn legacy_settings_fill_missing_fields_with_defaults() {
+        let json = r#"{
+            "favorited_tickers": [],
+            "show_favorites": true,
+            "selected_sort_option": "ChangeDesc",
+            "selected_exchanges": ["Binance"]
+        }"#;
+
+        let settings: Settings = serde_json::from_str(json).unwrap();
+        assert!(settings.show_favorites);
+        assert_eq!(settings.selected_sort_option, SortOptions::ChangeDesc);
+        assert_eq!(
+            settings.selected_exchanges,
+            vec![ExchangeInclusive::Binance]
+        );
+        assert_eq!(
+            settings.selected_markets,
+            Settings::default().selected_markets
+        );
+        assert!(settings.search_query.is_empty());
+        assert_eq!(settings.stats_refresh_interval(), Duration::from_secs(15));
+    }
+
+    #[test]
+    fn refresh_is_spread_across_exchanges() {
+        let interval = Duration::from_secs(15);
+
+        assert_eq!(staggered_period(interval, 0), interval);
+        assert_eq!(staggered_period(interval, 5), Duration::from_secs(3));
+        assert_eq!(
+            staggered_period(Duration::from_secs(5), 20),
+            Duration::from_millis(500)
+        );
+    }
+
+    #[test]
+    fn default_filters_hide_spot_leveraged_tokens() {
+        let filters = SymbolFilter::defaults();
+        let allowed = |symbol: &str, exchange| {
+            SymbolFilter::is_allowed(&filters, &Ticker::new(symbol, exchange))
+        };
+
+        assert!(!allowed("BTCUPUSDT", Exchange::BinanceSpot));
+        assert!(!allowed("BTC3L_USDT", Exchange::GateioSpot));
+        assert!(allowed("JUPUSDT", Exchange::BinanceSpot));
+        assert!(allowed("BTCUSDT", Exchange::BinanceSpot));
+        // قانون پیش‌فرض فقط روی اسپات اعمال می‌شود
+        assert!(allowed("ETHBULLUSDT", Exchange::BinanceLinear));
+    }
+
+    #[test]
+    fn include_patterns_restrict_one_exchange() {
+        let filters = vec![SymbolFilter {
+            exchange: Some(ExchangeInclusive::Bybit),
+            include: vec!["BTC*".to_string(), "ETH*".to_string()],
+            ..SymbolFilter::default()
+        }];
+
+        assert!(SymbolFilter::is_allowed(
+            &filters,
+            &Ticker::new("ETHUSDT", Exchange::BybitLinear)
+        ));
+        assert!(!SymbolFilter::is_allowed(
+            &filters,
+            &Ticker::new("DOGEUSDT", Exchange::BybitLinear)
+        ));
+        assert!(SymbolFilter::is_allowed(
+            &filters,
+            &Ticker::new("DOGEUSDT", Exchange::BinanceLinear)
+        ));
+    }
+
+    #[test]
+    fn columns_are_deduplicated_and_completed() {
+        let settings = Settings {
+            columns: vec![
+                ColumnCfg {
+                    column: TableColumn::Volume,
+                    width: 500.0,
+                },
+                ColumnCfg {
+                    column: TableColumn::Volume,
+                    width: 60.0,
+                },
+            ],
+            ..Settings::default()
+        };
+
+        let columns = settings.table_columns();
+        let order: Vec<_> = columns.iter().map(|c| c.column).collect();
+        assert_eq!(
+            order,
+            vec![TableColumn::Volume, TableColumn::Price, TableColumn::Change]
+        );
+        assert_eq!(columns[0].width, *ColumnCfg::WIDTH_RANGE.end());
+    }
+
+    #[test]
+    fn rows_group_by_quote_keeping_sort_order() {
+        let row = |symbol: &str| TickerRowData {
+            exchange: Exchange::BinanceSpot,
+            ticker: Ticker::new(symbol, Exchange::BinanceSpot),
+            stats: TickerStats {
+                last_price: 1.0,
+                mark_price: 1.0,
+                index_price: 1.0,
+                daily_price_chg: 0.0,
+                daily_volume: 0.0,
+            },
+            previous_stats: None,
+            is_favorited: false,
+        };
+        let rows = [row("BTCUSDC"), row("BTCUSDT"), row("WEIRD"), row("ETHUSDT")];
+        let refs: Vec<&TickerRowData> = rows.iter().collect();
+
+        let groups: Vec<(Option<&str>, String)> = quote_groups(&refs)
+            .into_iter()
+            .map(|(quote, rows)| {
+                let symbols: Vec<String> = rows.iter().map(|r| r.ticker.to_string()).collect();
+                (quote, symbols.join(","))
+            })
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                (Some("USDT"), "BTCUSDT,ETHUSDT".to_string()),
+                (Some("USDC"), "BTCUSDC".to_string()),
+                (None, "WEIRD".to_string()),
+            ]
+        );
+
+        let only_usdc = QuoteFilter::Only("USDC".to_string());
+        assert!(only_usdc.matches(&rows[0].ticker));
+        assert!(!only_usdc.matches(&rows[1].ticker));
+    }
+
+    #[test]
+    fn numbers_split_on_the_decimal_point() {
+        assert_eq!(split_decimal("67,123.50"), ("67,123", ".50"));
+        assert_eq!(split_decimal("+1.25%"), ("+1", ".25%"));
+        assert_eq!(split_decimal("$2,345,678"), ("$2,345,678", ""));
+        assert_eq!(split_decimal("12k"), ("12", "k"));
+    }
+
+    #[test]
+    fn invalid_table_settings_keep_rest_of_sidebar() {
+        let json = r#"{
+            "position": "Right",
+            "tickers_table": { "selected_sort_option": "Unknown" }
+        }"#;
+
+        let sidebar: Sidebar = serde_json::from_str(json).unwrap();
+        assert_eq!(sidebar.position, Position::Right);
+        assert_eq!(sidebar.tickers_table, None);
+    }
+}
@@ -1,5 +1,6 @@
 use chrono::{DateTime, Datelike, Timelike};
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 const DAY_MS: u64 = 86_400_000;
 const HOUR_MS: u64 = 3_600_000;
@@ -16,33 +17,56 @@ where
     Ok(T::deserialize(v).unwrap_or_default())
 }
 
+/// سطوح اختصار اعداد: (آستانه، مقسوم‌علیه، ارقام اعشار، پسوند) از بزرگ به کوچک
+const ABBR_TIERS: [(f32, f32, usize, &str); 10] = [
+    (1_000_000_000.0, 1_000_000_000.0, 2, "b"),
+    (1_000_000.0, 1_000_000.0, 2, "m"),
+    (10_000.0, 1_000.0, 1, "k"),
+    (1_000.0, 1_000.0, 2, "k"),
+    (100.0, 1.0, 0, ""),
+    (10.0, 1.0, 1, ""),
+    (1.0, 1.0, 2, ""),
+    (0.001, 1.0, 3, ""),
+    (0.0001, 1.0, 4, ""),
+    (0.00001, 1.0, 5, ""),
+];
+
 /// اختصار اعداد بزرگ (مثلاً 1.5m برای یک میلیون و پانصد هزار)
+///
+/// سطح بر اساس مقدار گرد شده انتخاب می‌شود تا مثلاً 999,999 به جای 1000.0k به صورت 1.00m نمایش داده شود
 pub fn abbr_large_numbers(value: f32) -> String {
     let abs_value = value.abs();
+    if abs_value == 0.0 {
+        return "0".to_string();
+    }
+    if !abs_value.is_finite() {
+        return value.to_string();
+    }
+
     let sign = if value < 0.0 { "-" } else { "" };
 
-    match abs_value {
-        v if v >= 1_000_000_000.0 => {
-            format!("{}{:.3}b", sign, v / 100_000_000.0)
-        }
-        v if v >= 1_000_000.0 => format!("{}{:.2}m", sign, v / 1_000_000.0),
-        v if v >= 10_000.0 => format!("{}{:.1}k", sign, v / 1_000.0),
-        v if v >= 1_000.0 => format!("{}{:.2}k", sign, v / 1_000.0),
-        v if v >= 100.0 => format!("{}{:.0}", sign, v),
-        v if v >= 10.0 => format!("{}{:.1}", sign, v),
-        v if v >= 1.0 => format!("{}{:.2}", sign, v),
-        v if v >= 0.001 => format!("{}{:.3}", sign, v),
-        v if v >= 0.0001 => format!("{}{:.4}", sign, v),
-        v if v >= 0.00001 => format!("{}{:.5}", sign, v),
-        _ => {
-            if abs_value == 0.0 {
-                "0".to_string()
-            } else {
-                let s = format!("{}{:.3}", sign, abs_value);
-                s.trim_end_matches('0').trim_end_matches('.').to_string()
-            }
-        }
+    let rounded_at = |tier: usize| {
+        let (_, divisor, decimals, _) = ABBR_TIERS[tier];
+        let scale = 10_f32.powi(decimals as i32);
+        (abs_value / divisor * scale).round() / scale
+    };
+
+    let Some(mut tier) = ABBR_TIERS
+        .iter()
+        .position(|(threshold, ..)| abs_value >= *threshold)
+    else {
+        // مقادیر بسیار کوچک به صورت نماد علمی نمایش داده می‌شوند تا به صفر گرد نشوند
+        return format!("{sign}{abs_value:.1e}");
+    };
+
+    // اگر مقدار گرد شده به آستانه سطح بالاتر برسد، همان سطح بالاتر استفاده می‌شود
+    if tier > 0 && rounded_at(tier) * ABBR_TIERS[tier].1 >= ABBR_TIERS[tier - 1].0 {
+        tier -= 1;
     }
+
+    let (_, _, decimals, suffix) = ABBR_TIERS[tier];
+    let rounded = rounded_at(tier);
+    format!("{sign}{rounded:.decimals$}{suffix}")
 }
 
 /// شمارش تعداد ارقام اعشار یک عدد
@@ -57,64 +81,123 @@ pub fn count_decimals(value: f32) -> usize {
 
 /// فرمت‌بندی اعداد با استفاده از کاما برای جدا کردن هزارگان
 pub fn format_with_commas(num: f32) -> String {
+    group_thousands(&format_full(num))
+}
+
+/// نمایش کامل یک عدد با تعداد اعشار متناسب با بزرگی آن (بدون اختصار)
+fn format_full(num: f32) -> String {
     if num == 0.0 {
         return "0".to_string();
     }
 
-    let abs_num = num.abs();
-    let decimals = match abs_num {
+    let decimals = match num.abs() {
         n if n >= 1000.0 => 0,
         n if n >= 100.0 => 1,
         n if n >= 10.0 => 2,
         _ => 3,
     };
 
-    let is_negative = num < 0.0;
-
-    if abs_num < 1000.0 {
-        return format!(
-            "{}{:.*}",
-            if is_negative { "-" } else { "" },
-            decimals,
-            abs_num
-        );
-    }
-
-    let s = format!("{:.*}", decimals, abs_num);
+    format!("{num:.decimals$}")
+}
 
-    let (integer_part, decimal_part) = match s.find('.') {
-        Some(pos) => (&s[..pos], Some(&s[pos..])),
-        None => (s.as_str(), None),
+/// درج جداکننده هزارگان در بخش صحیح یک عدد قالب‌بندی شده (با حفظ علامت و بخش اعشاری)
+fn group_thousands(formatted: &str) -> String {
+    let (sign, unsigned) = match formatted.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", formatted),
     };
 
-    let mut result = {
-        let num_commas = (integer_part.len() - 1) / 3;
-        let decimal_len = decimal_part.map_or(0, str::len);
-
-        String::with_capacity(
-            usize::from(is_negative) + integer_part.len() + num_commas + decimal_len,
-        )
+    let (integer_part, decimal_part) = match unsigned.find('.') {
+        Some(pos) => unsigned.split_at(pos),
+        None => (unsigned, ""),
     };
 
-    if is_negative {
-        result.push('-');
+    if !integer_part.bytes().all(|b| b.is_ascii_digit()) {
+        return formatted.to_string();
     }
 
+    let mut result = String::with_capacity(formatted.len() + integer_part.len() / 3);
+    result.push_str(sign);
+
     let digits_len = integer_part.len();
     for (i, ch) in integer_part.chars().enumerate() {
         result.push(ch);
 
         let pos_from_right = digits_len - i - 1;
-        if i < digits_len - 1 && pos_from_right % 3 == 0 {
+        if pos_from_right > 0 && pos_from_right % 3 == 0 {
             result.push(',');
         }
     }
 
-    if let Some(decimal) = decimal_part {
-        result.push_str(decimal);
+    result.push_str(decimal_part);
+    result
+}
+
+/// تنظیمات سراسری قالب‌بندی اعداد در محورها، جدول نمادها، نوار معاملات و اندیکاتورها
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct NumberFormat {
+    pub abbreviate: bool,          // اختصار حجم‌های بزرگ (1.23m، 4.50b)
+    pub thousands_separator: bool, // جداکننده هزارگان برای قیمت‌ها و حجم‌های کامل
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            abbreviate: true,
+            thousands_separator: false,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// قالب‌بندی حجم؛ در حالت اختصار به صورت فشرده و در غیر این صورت کامل
+    pub fn volume(&self, value: f32) -> String {
+        if self.abbreviate {
+            abbr_large_numbers(value)
+        } else {
+            self.separate(format_full(value))
+        }
     }
 
-    result
+    /// قالب‌بندی قیمت با تعداد اعشار پنل؛ قیمت‌ها هیچ‌گاه اختصار نمی‌شوند
+    pub fn price(&self, value: f32, decimals: usize) -> String {
+        self.separate(format!("{value:.decimals$}"))
+    }
+
+    /// درج جداکننده هزارگان در یک عدد از پیش قالب‌بندی شده، در صورت فعال بودن
+    pub fn separate(&self, formatted: String) -> String {
+        if self.thousands_separator {
+            group_thousands(&formatted)
+        } else {
+            formatted
+        }
+    }
+}
+
+static ABBREVIATE_NUMBERS: AtomicBool = AtomicBool::new(true);
+static THOUSANDS_SEPARATOR: AtomicBool = AtomicBool::new(false);
+
+pub fn set_number_format(format: NumberFormat) {
+    ABBREVIATE_NUMBERS.store(format.abbreviate, Ordering::Relaxed);
+    THOUSANDS_SEPARATOR.store(format.thousands_separator, Ordering::Relaxed);
+}
+
+pub fn number_format() -> NumberFormat {
+    NumberFormat {
+        abbreviate: ABBREVIATE_NUMBERS.load(Ordering::Relaxed),
+        thousands_separator: THOUSANDS_SEPARATOR.load(Ordering::Relaxed),
+    }
+}
+
+/// قالب‌بندی حجم بر اساس تنظیمات سراسری
+pub fn format_volume(value: f32) -> String {
+    number_format().volume(value)
+}
+
+/// قالب‌بندی قیمت با تعداد اعشار مشخص بر اساس تنظیمات سراسری
+pub fn format_price(value: f32, decimals: usize) -> String {
+    number_format().price(value, decimals)
 }
 
 /// گرد کردن قیمت به نزدیک‌ترین گام قیمت (Tick Size)
@@ -133,6 +216,11 @@ pub fn round_to_next_tick(value: f32, tick_size: f32, down: bool) -> f32 {
 
 /// اختصار مبالغ ارزی (مثلاً $1.5m)
 pub fn currency_abbr(price: f32) -> String {
+    let format = number_format();
+    if !format.abbreviate {
+        return format!("${}", format.volume(price));
+    }
+
     match price {
         p if p > 1_000_000_000.0 => format!("${:.2}b", p / 1_000_000_000.0),
         p if p > 1_000_000.0 => format!("${:.1}m", p / 1_000_000.0),
@@ -201,6 +289,106 @@ pub fn format_duration_ms(diff_ms: u64) -> String {
     }
 }
 
+/// فرمت‌بندی حجم حافظه به صورت خوانا (مثلاً 12.5 MB)
+pub fn format_bytes(bytes: usize) -> String {
+    const KB: f64 = 1024.0;
+    const MB: f64 = KB * 1024.0;
+    const GB: f64 = MB * 1024.0;
+
+    let bytes = bytes as f64;
+    if bytes >= GB {
+        format!("{:.2} GB", bytes / GB)
+    } else if bytes >= MB {
+        format!("{:.1} MB", bytes / MB)
+    } else if bytes >= KB {
+        format!("{:.0} KB", bytes / KB)
+    } else {
+        format!("{bytes} B")
+    }
+}
+
+/// امتیاز تطبیق فازی؛ همه حروف عبارت جستجو (بدون حساسیت به بزرگی حروف) باید به ترتیب
+/// در متن آمده باشند. حروف پیاپی و حروف ابتدای کلمه امتیاز بیشتری دارند و `None` یعنی عدم تطبیق
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let mut score = 0;
+    let mut prev_match: Option<usize> = None;
+    let mut chars = candidate.chars().enumerate().peekable();
+    let mut prev_char: Option<char> = None;
+
+    for q in query.chars().filter(|c| !c.is_whitespace()) {
+        let q = q.to_ascii_lowercase();
+
+        loop {
+            let (idx, c) = chars.next()?;
+            let before = prev_char.replace(c);
+
+            if c.to_ascii_lowercase() != q {
+                continue;
+            }
+
+            score += 1;
+            if prev_match.is_some_and(|prev| prev + 1 == idx) {
+                score += 5;
+            }
+            if before.is_none_or(|b| !b.is_alphanumeric()) {
+                score += 8;
+            }
+            if let Some(prev) = prev_match {
+                score -= (idx - prev - 1).min(3) as i32;
+            }
+
+            prev_match = Some(idx);
+            break;
+        }
+    }
+
+    Some(score)
+}
+
+/// تطبیق الگوی glob بدون حساسیت به بزرگی حروف؛ `*` هر رشته (حتی خالی) و `?` دقیقاً یک نویسه
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().map(|c| c.to_ascii_lowercase()).collect();
+    let text: Vec<char> = text.chars().map(|c| c.to_ascii_lowercase()).collect();
+
+    let (mut p, mut t) = (0, 0);
+    // آخرین `*` دیده شده و جایی از متن که از آن به بعد پوشش داده می‌شود
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, covered)) => {
+                    p = star + 1;
+                    t = covered + 1;
+                    backtrack = Some((star, covered + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// تطبیق تقسیم‌های ذخیره شده با تعداد پنل‌های اندیکاتور فعلی؛
+/// در صورت ناهمخوانی، تقسیم‌ها با حفظ اندازه نمودار اصلی از نو محاسبه می‌شوند
+pub fn fit_panel_splits(splits: &[f32], active_indicators: usize) -> Vec<f32> {
+    if splits.len() == active_indicators.max(1) {
+        return splits.to_vec();
+    }
+
+    let main_split = splits.first().copied().unwrap_or(0.8);
+    calc_panel_splits(main_split, active_indicators, None)
+}
+
 /// محاسبه تقسیم‌بندی پنل‌ها (Splits) هنگام اضافه کردن پنل جدید
 /// تضمین می‌کند که ارتفاع پنل‌ها از `MIN_PANEL_HEIGHT` کمتر نشود
 pub fn calc_panel_splits(
@@ -271,3 +459,114 @@ pub fn reset_to_start_of_month_utc(dt: DateTime<chrono::Utc>) -> DateTime<chrono
 pub fn reset_to_start_of_year_utc(dt: DateTime<chrono::Utc>) -> DateTime<chrono::Utc> {
     reset_to_start_of_month_utc(dt.with_month(1).unwrap_or(dt))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_matching() {
+        assert_eq!(fuzzy_score("xyz", "Open settings"), None);
+        assert_eq!(fuzzy_score("tso", "Open settings"), None);
+        assert!(fuzzy_score("", "anything").is_some());
+
+        // حروف ابتدای کلمات و حروف پیاپی بر حروف پراکنده ترجیح دارند
+        let initials = fuzzy_score("os", "Open settings").unwrap();
+        let scattered = fuzzy_score("os", "Focus").unwrap();
+        assert!(initials > scattered);
+
+        let prefix = fuzzy_score("btc", "BTCUSDT").unwrap();
+        let spread = fuzzy_score("btc", "BlasT Coin").unwrap();
+        assert!(prefix > spread);
+    }
+
+    #[test]
+    fn glob_patterns() {
+        assert!(glob_match("*UPUSDT", "btcupusdt"));
+        assert!(glob_match("BTC?L_*", "BTC3L_USDT"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("*BULL*", "ETHBULLUSDT"));
+        assert!(!glob_match("*BULL*", "ETHUSDT"));
+        assert!(!glob_match("???*UPUSDT", "JUPUSDT"));
+        assert!(!glob_match("BTC", "BTCUSDT"));
+    }
+
+    #[test]
+    fn persisted_splits_fit_applicable_indicators() {
+        use crate::chart::indicator::{KlineIndicator, applicable};
+        use exchange::adapter::MarketKind;
+
+        // بهره باز ذخیره شده روی نماد اسپات نادیده گرفته می‌شود و تنها حجم پنل می‌گیرد
+        let enabled = [KlineIndicator::Volume, KlineIndicator::OpenInterest];
+        let active = applicable(&enabled, MarketKind::Spot);
+        assert_eq!(active, vec![KlineIndicator::Volume]);
+
+        let persisted = [0.6, 0.8];
+        assert_eq!(fit_panel_splits(&persisted, active.len()), vec![0.6]);
+
+        // روی قرارداد دائمی هر دو پنل برقرارند و تقسیم‌ها دست نمی‌خورند
+        let active = applicable(&enabled, MarketKind::LinearPerps);
+        assert_eq!(
+            fit_panel_splits(&persisted, active.len()),
+            persisted.to_vec()
+        );
+    }
+
+    #[test]
+    fn abbreviation_boundaries() {
+        assert_eq!(abbr_large_numbers(999.0), "999");
+        assert_eq!(abbr_large_numbers(1_000.0), "1.00k");
+        assert_eq!(abbr_large_numbers(9_999.0), "10.0k");
+        assert_eq!(abbr_large_numbers(10_000.0), "10.0k");
+        assert_eq!(abbr_large_numbers(1_230_000.0), "1.23m");
+        // گرد شدن به سطح بعدی منتقل می‌شود، نه 1000.0k
+        assert_eq!(abbr_large_numbers(999_999.0), "1.00m");
+        assert_eq!(abbr_large_numbers(4_500_000_000.0), "4.50b");
+        assert_eq!(abbr_large_numbers(0.99999), "1.00");
+    }
+
+    #[test]
+    fn negative_values() {
+        assert_eq!(abbr_large_numbers(-1_500.0), "-1.50k");
+        assert_eq!(abbr_large_numbers(-2_000_000.0), "-2.00m");
+        assert_eq!(format_with_commas(-1_234_567.0), "-1,234,567");
+        assert_eq!(format_with_commas(-12.5), "-12.50");
+
+        let format = NumberFormat {
+            abbreviate: false,
+            thousands_separator: true,
+        };
+        assert_eq!(format.price(-65_432.1, 1), "-65,432.1");
+    }
+
+    #[test]
+    fn very_small_values() {
+        assert_eq!(abbr_large_numbers(0.0), "0");
+        assert_eq!(abbr_large_numbers(0.0012), "0.001");
+        assert_eq!(abbr_large_numbers(0.00005), "0.00005");
+        assert_eq!(abbr_large_numbers(0.000_001_2), "1.2e-6");
+        assert_eq!(abbr_large_numbers(-0.000_001_2), "-1.2e-6");
+        assert_eq!(format_with_commas(0.0005), "0.001");
+    }
+
+    #[test]
+    fn number_format_modes() {
+        let compact = NumberFormat::default();
+        assert_eq!(compact.volume(2_345_678.0), "2.35m");
+        assert_eq!(compact.price(67_123.5, 1), "67123.5");
+
+        let separated = NumberFormat {
+            abbreviate: false,
+            thousands_separator: true,
+        };
+        assert_eq!(separated.volume(2_345_678.0), "2,345,678");
+        assert_eq!(separated.price(67_123.5, 2), "67,123.50");
+        assert_eq!(separated.price(999.0, 0), "999");
+
+        let plain = NumberFormat {
+            abbreviate: false,
+            thousands_separator: false,
+        };
+        assert_eq!(plain.volume(2_345_678.0), "2345678");
+    }
+}
@@ -0,0 +1,283 @@
+//! تشخیص دیوارهای عمق بازار (سفارش‌های بزرگ نسبت به سطوح اطراف) و پیگیری ماندگاری آن‌ها
+
+use exchange::{SizeUnit, depth::Depth, util::Price, volume_size_unit};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// تعداد سطوح نزدیک به قیمت میانی که در هر سمت بررسی می‌شوند
+const MAX_LEVELS: usize = 100;
+/// تعداد سطوح همسایه در هر طرف یک سطح برای محاسبه میانه محلی
+const NEIGHBORS: usize = 10;
+/// مدت زمانی که یک سطح باید پایدار بماند تا دیوار محسوب شود (میلی‌ثانیه)
+const CONFIRM_MS: u64 = 2_000;
+/// مدت زمانی که یک دیوار تایید شده می‌تواند ناپدید شود بدون آنکه برداشته شده تلقی شود (میلی‌ثانیه)
+const GRACE_MS: u64 = 1_000;
+
+/// تنظیمات تشخیص و نمایش دیوارهای عمق بازار
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct WallConfig {
+    pub enabled: bool,     // برجسته‌سازی دیوارها
+    pub multiple: f32,     // حداقل نسبت حجم سطح به میانه سطوح اطراف
+    pub min_notional: f32, // حداقل ارزش دلاری سطح
+    pub notify: bool,      // اعلان و صدا هنگام ظاهر یا برداشته شدن دیوار
+}
+
+impl Default for WallConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            multiple: 5.0,
+            min_notional: 100_000.0,
+            notify: false,
+        }
+    }
+}
+
+impl WallConfig {
+    pub const MULTIPLE_RANGE: std::ops::RangeInclusive<f32> = 2.0..=20.0;
+    pub const MIN_NOTIONAL_RANGE: std::ops::RangeInclusive<f32> = 10_000.0..=10_000_000.0;
+}
+
+/// سمت دیوار در دفتر سفارش
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WallSide {
+    Bid, // دیوار خرید
+    Ask, // دیوار فروش
+}
+
+impl std::fmt::Display for WallSide {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WallSide::Bid => write!(f, "Bid"),
+            WallSide::Ask => write!(f, "Ask"),
+        }
+    }
+}
+
+/// رویدادهای تغییر وضعیت دیوارها
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WallEvent {
+    /// دیوار جدید پس از پایداری تایید شد
+    Appeared {
+        side: WallSide,
+        price: Price,
+        qty: f32,
+    },
+    /// دیوار تایید شده برداشته شد
+    Pulled {
+        side: WallSide,
+        price: Price,
+        qty: f32,
+    },
+}
+
+/// یک دیوار در حال پیگیری
+#[derive(Debug, Clone, Copy)]
+struct TrackedWall {
+    qty: f32,        // آخرین حجم مشاهده شده
+    first_seen: u64, // زمان اولین مشاهده
+    last_seen: u64,  // زمان آخرین مشاهده
+    confirmed: bool, // آیا به اندازه کافی پایدار مانده است
+}
+
+/// پیگیری دیوارها بین بروزرسانی‌های عمق تا سطوح چشمک‌زن اعلان تکراری ایجاد نکنند
+#[derive(Debug, Default, Clone)]
+pub struct WallTracker {
+    walls: BTreeMap<(WallSide, Price), TrackedWall>,
+}
+
+impl WallTracker {
+    /// بروزرسانی با آخرین وضعیت عمق بازار و برگرداندن رویدادهای جدید
+    pub fn update(&mut self, depth: &Depth, cfg: &WallConfig, now: u64) -> Vec<WallEvent> {
+        if !cfg.enabled {
+            self.walls.clear();
+            return vec![];
+        }
+
+        let detected = detect_walls(depth, cfg);
+        let mut events = vec![];
+
+        for &(side, price, qty) in &detected {
+            let wall = self.walls.entry((side, price)).or_insert(TrackedWall {
+                qty,
+                first_seen: now,
+                last_seen: now,
+                confirmed: false,
+            });
+            wall.qty = qty;
+            wall.last_seen = now;
+
+            if !wall.confirmed && now.saturating_sub(wall.first_seen) >= CONFIRM_MS {
+                wall.confirmed = true;
+                events.push(WallEvent::Appeared { side, price, qty });
+            }
+        }
+
+        self.walls.retain(|&(side, price), wall| {
+            if wall.last_seen == now {
+                return true;
+            }
+            if !wall.confirmed {
+                return false;
+            }
+            if now.saturating_sub(wall.last_seen) < GRACE_MS {
+                return true;
+            }
+
+            events.push(WallEvent::Pulled {
+                side,
+                price,
+                qty: wall.qty,
+            });
+            false
+        });
+
+        events
+    }
+
+    /// دیوارهای تایید شده فعلی
+    pub fn walls(&self) -> impl Iterator<Item = (WallSide, Price, f32)> + '_ {
+        self.walls
+            .iter()
+            .filter(|(_, wall)| wall.confirmed)
+            .map(|(&(side, price), wall)| (side, price, wall.qty))
+    }
+
+    pub fn clear(&mut self) {
+        self.walls.clear();
+    }
+}
+
+/// یافتن سطوحی که حجم آن‌ها از ضریب مشخصی از میانه سطوح اطراف بیشتر است
+pub fn detect_walls(depth: &Depth, cfg: &WallConfig) -> Vec<(WallSide, Price, f32)> {
+    let bids = depth
+        .bids
+        .iter()
+        .rev()
+        .take(MAX_LEVELS)
+        .map(|(p, q)| (*p, *q))
+        .collect::<Vec<_>>();
+    let asks = depth
+        .asks
+        .iter()
+        .take(MAX_LEVELS)
+        .map(|(p, q)| (*p, *q))
+        .collect::<Vec<_>>();
+
+    let mut walls = detect_side(&bids, cfg, WallSide::Bid);
+    walls.extend(detect_side(&asks, cfg, WallSide::Ask));
+    walls
+}
+
+/// سطوح یک سمت باید از نزدیک‌ترین قیمت به میانه به سمت دور مرتب باشند
+fn detect_side(
+    levels: &[(Price, f32)],
+    cfg: &WallConfig,
+    side: WallSide,
+) -> Vec<(WallSide, Price, f32)> {
+    let in_quote = volume_size_unit() == SizeUnit::Quote;
+    let mut neighbors = Vec::with_capacity(NEIGHBORS * 2);
+
+    levels
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, &(price, qty))| {
+            let notional = if in_quote {
+                qty
+            } else {
+                qty * price.to_f32_lossy()
+            };
+            if notional < cfg.min_notional {
+                return None;
+            }
+
+            neighbors.clear();
+            neighbors.extend(
+                levels[idx.saturating_sub(NEIGHBORS)..(idx + NEIGHBORS + 1).min(levels.len())]
+                    .iter()
+                    .filter(|(p, _)| *p != price)
+                    .map(|(_, q)| *q),
+            );
+
+            let median = median(&mut neighbors)?;
+            (qty >= median * cfg.multiple).then_some((side, price, qty))
+        })
+        .collect()
+}
+
+fn median(values: &mut [f32]) -> Option<f32> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let mid = values.len() / 2;
+    let (_, median, _) = values.select_nth_unstable_by(mid, f32::total_cmp);
+    Some(*median)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn depth_with_bids(levels: &[(f32, f32)]) -> Depth {
+        let mut depth = Depth::default();
+        for &(price, qty) in levels {
+            depth.bids.insert(Price::from_f32(price), qty);
+        }
+        depth
+    }
+
+    fn cfg() -> WallConfig {
+        WallConfig {
+            enabled: true,
+            multiple: 5.0,
+            min_notional: 0.0,
+            notify: true,
+        }
+    }
+
+    fn book(wall_qty: f32) -> Depth {
+        let mut levels = (0..30).map(|i| (100.0 - i as f32, 1.0)).collect::<Vec<_>>();
+        levels[10].1 = wall_qty;
+        depth_with_bids(&levels)
+    }
+
+    #[test]
+    fn detects_level_above_local_median() {
+        let walls = detect_walls(&book(8.0), &cfg());
+        assert_eq!(walls, vec![(WallSide::Bid, Price::from_f32(90.0), 8.0)]);
+
+        assert!(detect_walls(&book(3.0), &cfg()).is_empty());
+    }
+
+    #[test]
+    fn flickering_level_does_not_notify() {
+        let mut tracker = WallTracker::default();
+
+        assert!(tracker.update(&book(8.0), &cfg(), 0).is_empty());
+        assert!(tracker.update(&book(1.0), &cfg(), 500).is_empty());
+        assert!(tracker.update(&book(8.0), &cfg(), 1_000).is_empty());
+        assert!(tracker.update(&book(8.0), &cfg(), 2_500).is_empty());
+
+        let events = tracker.update(&book(8.0), &cfg(), 3_000);
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], WallEvent::Appeared { .. }));
+        assert_eq!(tracker.walls().count(), 1);
+    }
+
+    #[test]
+    fn confirmed_wall_is_pulled_after_grace_period() {
+        let mut tracker = WallTracker::default();
+        tracker.update(&book(8.0), &cfg(), 0);
+        tracker.update(&book(8.0), &cfg(), 2_000);
+
+        // ناپدید شدن کوتاه مدت هنوز برداشته شدن محسوب نمی‌شود
+        assert!(tracker.update(&book(1.0), &cfg(), 2_500).is_empty());
+        assert_eq!(tracker.walls().count(), 1);
+
+        let events = tracker.update(&book(1.0), &cfg(), 3_500);
+        assert!(matches!(events.as_slice(), [WallEvent::Pulled { qty, .. }] if *qty == 8.0));
+        assert_eq!(tracker.walls().count(), 0);
+    }
+}
@@ -1,16 +1,19 @@
 use super::{Ticker, Timeframe};
 use crate::{
-    Kline, OpenInterest, Price, PushFrequency, TickMultiplier, TickerInfo, TickerStats, Trade,
-    depth::Depth,
+    FundingRate, Kline, OpenInterest, Price, PushFrequency, TickMultiplier, TickerInfo,
+    TickerStats, Trade, depth::DepthChange,
 };
 
 use enum_map::{Enum, EnumMap};
 use rustc_hash::{FxHashMap, FxHashSet};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, str::FromStr, sync::Arc};
+use std::{collections::HashMap, str::FromStr};
 
 pub mod binance;
+pub mod bitget;
 pub mod bybit;
+pub mod coinbase;
+pub mod gateio;
 pub mod hyperliquid;
 pub mod okex;
 
@@ -76,10 +79,12 @@ impl ResolvedStream {
                     StreamKind::Kline {
                         ticker_info,
                         timeframe,
+                        source,
                     } => {
                         let persist_kline = PersistKline {
                             ticker: ticker_info.ticker,
                             timeframe,
+                            source,
                         };
                         PersistStreamKind::Kline(persist_kline)
                     }
@@ -128,6 +133,8 @@ pub enum AdapterError {
     WebsocketError(String),            // خطای وب‌سوکت
     #[error("Invalid request: {0}")]
     InvalidRequest(String),            // درخواست نامعتبر
+    #[error("Timed out: {0}")]
+    Timeout(String),                   // پایان مهلت یکی از مراحل اتصال
 }
 
 impl AdapterError {
@@ -149,6 +156,10 @@ impl AdapterError {
                 log::error!("Adapter websocket error: {err}");
                 "Realtime connection error. Trying to reconnect..."
             }
+            AdapterError::Timeout(err) => {
+                log::error!("Adapter timeout: {err}");
+                "Connection to the exchange timed out. Trying to reconnect..."
+            }
         }
     }
 }
@@ -203,6 +214,8 @@ pub enum StreamKind {
     Kline {
         ticker_info: TickerInfo,
         timeframe: Timeframe,
+        #[serde(default)]
+        source: KlinePriceSource,
     },
     /// جریان داده‌های عمق بازار و معاملات (Depth and Trades)
     DepthAndTrades {
@@ -232,15 +245,83 @@ impl StreamKind {
         }
     }
 
+    /// کندل‌های قیمت معاملات که از جریان وب‌سوکت کندل صرافی می‌آیند
     pub fn as_kline_stream(&self) -> Option<(TickerInfo, Timeframe)> {
         match self {
             StreamKind::Kline {
                 ticker_info,
                 timeframe,
+                source: KlinePriceSource::Trade,
             } => Some((*ticker_info, *timeframe)),
             _ => None,
         }
     }
+
+    /// کندل‌های قیمت نشان‌گذاری یا شاخص که به صورت دوره‌ای از REST دریافت می‌شوند
+    pub fn as_price_kline_stream(&self) -> Option<(TickerInfo, Timeframe, KlinePriceSource)> {
+        match self {
+            StreamKind::Kline {
+                ticker_info,
+                timeframe,
+                source,
+            } if *source != KlinePriceSource::Trade => Some((*ticker_info, *timeframe, *source)),
+            _ => None,
+        }
+    }
+}
+
+/// قیمتی که کندل‌ها از آن ساخته می‌شوند
+///
+/// کندل‌های قیمت نشان‌گذاری (Mark) و شاخص (Index) فقط برای قراردادهای دائمی بایننس و بای‌بیت
+/// در دسترس هستند و حجم معاملات ندارند.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum KlinePriceSource {
+    #[default]
+    Trade, // قیمت آخرین معامله
+    Mark,  // قیمت نشان‌گذاری (مبنای لیکوئید شدن)
+    Index, // قیمت شاخص اسپات
+}
+
+impl KlinePriceSource {
+    pub const ALL: [KlinePriceSource; 3] = [
+        KlinePriceSource::Trade,
+        KlinePriceSource::Mark,
+        KlinePriceSource::Index,
+    ];
+
+    /// منابع قیمتی که صرافی برای این بازار ارائه می‌دهد
+    pub fn available(exchange: Exchange) -> &'static [KlinePriceSource] {
+        match exchange {
+            Exchange::BinanceLinear
+            | Exchange::BinanceInverse
+            | Exchange::BybitLinear
+            | Exchange::BybitInverse => &Self::ALL,
+            _ => &[KlinePriceSource::Trade],
+        }
+    }
+
+    /// منبع انتخاب شده در صورت پشتیبانی، در غیر این صورت قیمت معاملات
+    pub fn resolve(self, exchange: Exchange) -> Self {
+        if Self::available(exchange).contains(&self) {
+            self
+        } else {
+            KlinePriceSource::Trade
+        }
+    }
+
+    pub fn has_volume(self) -> bool {
+        self == KlinePriceSource::Trade
+    }
+}
+
+impl std::fmt::Display for KlinePriceSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KlinePriceSource::Trade => write!(f, "Last"),
+            KlinePriceSource::Mark => write!(f, "Mark"),
+            KlinePriceSource::Index => write!(f, "Index"),
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -284,10 +365,12 @@ impl UniqueStreams {
     fn update_specs_for_exchange(&mut self, exchange: Exchange) {
         let depth_streams = self.depth_streams(Some(exchange));
         let kline_streams = self.kline_streams(Some(exchange));
+        let price_kline_streams = self.price_kline_streams(Some(exchange));
 
         self.specs[exchange] = Some(StreamSpecs {
             depth: depth_streams,
             kline: kline_streams,
+            price_kline: price_kline_streams,
         });
     }
 
@@ -322,6 +405,22 @@ impl UniqueStreams {
         self.streams(exchange_filter, |_, stream| stream.as_kline_stream())
     }
 
+    pub fn price_kline_streams(
+        &self,
+        exchange_filter: Option<Exchange>,
+    ) -> Vec<(TickerInfo, Timeframe, KlinePriceSource)> {
+        self.streams(exchange_filter, |_, stream| stream.as_price_kline_stream())
+    }
+
+    pub fn contains(&self, stream: &StreamKind) -> bool {
+        let ticker_info = stream.ticker_info();
+
+        self.streams[ticker_info.exchange()]
+            .as_ref()
+            .and_then(|ticker_map| ticker_map.get(&ticker_info))
+            .is_some_and(|streams| streams.contains(stream))
+    }
+
     pub fn combined_used(&self) -> impl Iterator<Item = (Exchange, &StreamSpecs)> {
         self.specs
             .iter()
@@ -353,6 +452,8 @@ pub struct PersistDepth {
 pub struct PersistKline {
     pub ticker: Ticker,
     pub timeframe: Timeframe,
+    #[serde(default)]
+    pub source: KlinePriceSource,
 }
 
 impl From<StreamKind> for PersistStreamKind {
@@ -361,9 +462,11 @@ impl From<StreamKind> for PersistStreamKind {
             StreamKind::Kline {
                 ticker_info,
                 timeframe,
+                source,
             } => PersistStreamKind::Kline(PersistKline {
                 ticker: ticker_info.ticker,
                 timeframe,
+                source,
             }),
             StreamKind::DepthAndTrades {
                 ticker_info,
@@ -390,6 +493,7 @@ impl PersistStreamKind {
                 .map(|ti| StreamKind::Kline {
                     ticker_info: ti,
                     timeframe: k.timeframe,
+                    source: k.source,
                 })
                 .ok_or_else(|| format!("TickerInfo not found for {}", k.ticker)),
             PersistStreamKind::DepthAndTrades(d) => resolver(&d.ticker)
@@ -423,6 +527,7 @@ fn default_push_freq() -> PushFrequency {
 pub struct StreamSpecs {
     pub depth: Vec<(TickerInfo, StreamTicksize, PushFrequency)>,
     pub kline: Vec<(TickerInfo, Timeframe)>,
+    pub price_kline: Vec<(TickerInfo, Timeframe, KlinePriceSource)>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
@@ -431,14 +536,20 @@ pub enum ExchangeInclusive {
     Binance,
     Hyperliquid,
     Okex,
+    Gateio,
+    Coinbase,
+    Bitget,
 }
 
 impl ExchangeInclusive {
-    pub const ALL: [ExchangeInclusive; 4] = [
+    pub const ALL: [ExchangeInclusive; 7] = [
         ExchangeInclusive::Bybit,
         ExchangeInclusive::Binance,
         ExchangeInclusive::Hyperliquid,
         ExchangeInclusive::Okex,
+        ExchangeInclusive::Gateio,
+        ExchangeInclusive::Coinbase,
+        ExchangeInclusive::Bitget,
     ];
 
     pub fn of(ex: Exchange) -> Self {
@@ -449,10 +560,31 @@ impl ExchangeInclusive {
             }
             Exchange::HyperliquidLinear | Exchange::HyperliquidSpot => Self::Hyperliquid,
             Exchange::OkexLinear | Exchange::OkexInverse | Exchange::OkexSpot => Self::Okex,
+            Exchange::GateioLinear | Exchange::GateioSpot => Self::Gateio,
+            Exchange::CoinbaseSpot => Self::Coinbase,
+            Exchange::BitgetLinear | Exchange::BitgetSpot => Self::Bitget,
         }
     }
 }
 
+impl std::fmt::Display for ExchangeInclusive {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ExchangeInclusive::Bybit => "Bybit",
+                ExchangeInclusive::Binance => "Binance",
+                ExchangeInclusive::Hyperliquid => "Hyperliquid",
+                ExchangeInclusive::Okex => "OKX",
+                ExchangeInclusive::Gateio => "Gate",
+                ExchangeInclusive::Coinbase => "Coinbase",
+                ExchangeInclusive::Bitget => "Bitget",
+            }
+        )
+    }
+}
+
 /// لیست صرافی‌های پشتیبانی شده
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, Enum)]
 pub enum Exchange {
@@ -467,6 +599,11 @@ pub enum Exchange {
     OkexLinear,
     OkexInverse,
     OkexSpot,
+    GateioLinear,
+    GateioSpot,
+    CoinbaseSpot,
+    BitgetLinear,
+    BitgetSpot,
 }
 
 impl std::fmt::Display for Exchange {
@@ -486,6 +623,11 @@ impl std::fmt::Display for Exchange {
                 Exchange::OkexLinear => "Okex Linear",
                 Exchange::OkexInverse => "Okex Inverse",
                 Exchange::OkexSpot => "Okex Spot",
+                Exchange::GateioLinear => "Gateio Linear",
+                Exchange::GateioSpot => "Gateio Spot",
+                Exchange::CoinbaseSpot => "Coinbase Spot",
+                Exchange::BitgetLinear => "Bitget Linear",
+                Exchange::BitgetSpot => "Bitget Spot",
             }
         )
     }
@@ -507,13 +649,18 @@ impl FromStr for Exchange {
             "Okex Linear" => Ok(Exchange::OkexLinear),
             "Okex Inverse" => Ok(Exchange::OkexInverse),
             "Okex Spot" => Ok(Exchange::OkexSpot),
+            "Gateio Linear" => Ok(Exchange::GateioLinear),
+            "Gateio Spot" => Ok(Exchange::GateioSpot),
+            "Coinbase Spot" => Ok(Exchange::CoinbaseSpot),
+            "Bitget Linear" => Ok(Exchange::BitgetLinear),
+            "Bitget Spot" => Ok(Exchange::BitgetSpot),
             _ => Err(format!("Invalid exchange: {}", s)),
         }
     }
 }
 
 impl Exchange {
-    pub const ALL: [Exchange; 11] = [
+    pub const ALL: [Exchange; 16] = [
         Exchange::BinanceLinear,
         Exchange::BinanceInverse,
         Exchange::BinanceSpot,
@@ -525,6 +672,11 @@ impl Exchange {
         Exchange::OkexLinear,
         Exchange::OkexInverse,
         Exchange::OkexSpot,
+        Exchange::GateioLinear,
+        Exchange::GateioSpot,
+        Exchange::CoinbaseSpot,
+        Exchange::BitgetLinear,
+        Exchange::BitgetSpot,
     ];
 
     pub fn market_type(&self) -> MarketKind {
@@ -532,14 +684,19 @@ impl Exchange {
             Exchange::BinanceLinear
             | Exchange::BybitLinear
             | Exchange::HyperliquidLinear
-            | Exchange::OkexLinear => MarketKind::LinearPerps,
+            | Exchange::OkexLinear
+            | Exchange::GateioLinear
+            | Exchange::BitgetLinear => MarketKind::LinearPerps,
             Exchange::BinanceInverse | Exchange::BybitInverse | Exchange::OkexInverse => {
                 MarketKind::InversePerps
             }
             Exchange::BinanceSpot
             | Exchange::BybitSpot
             | Exchange::HyperliquidSpot
-            | Exchange::OkexSpot => MarketKind::Spot,
+            | Exchange::OkexSpot
+            | Exchange::GateioSpot
+            | Exchange::CoinbaseSpot
+            | Exchange::BitgetSpot => MarketKind::Spot,
         }
     }
 
@@ -550,6 +707,44 @@ impl Exchange {
         )
     }
 
+    /// آیا تعداد سطوح تصویر اولیه عمق بازار بر اساس ضریب گام پنل تنظیم می‌شود
+    pub fn is_depth_limit_adaptive(&self) -> bool {
+        matches!(
+            self,
+            Exchange::BinanceLinear | Exchange::BinanceInverse | Exchange::BinanceSpot
+        )
+    }
+
+    /// منابع معاملات گذشته این صرافی برای ساخت فوت‌پرینت؛ `None` اگر هیچ منبعی نداشته باشد
+    pub fn trade_history_source(&self) -> Option<crate::fetcher::TradeSource> {
+        use crate::fetcher::TradeSource;
+
+        match self {
+            Exchange::BinanceLinear
+            | Exchange::BinanceInverse
+            | Exchange::BinanceSpot
+            | Exchange::OkexLinear
+            | Exchange::OkexInverse
+            | Exchange::OkexSpot => Some(TradeSource::Both),
+            // بای‌بیت API دریافت معاملات گذشته بر اساس زمان ندارد
+            Exchange::BybitLinear | Exchange::BybitInverse | Exchange::BybitSpot => {
+                Some(TradeSource::Historical)
+            }
+            Exchange::HyperliquidLinear
+            | Exchange::HyperliquidSpot
+            | Exchange::GateioLinear
+            | Exchange::GateioSpot
+            | Exchange::CoinbaseSpot
+            | Exchange::BitgetLinear
+            | Exchange::BitgetSpot => None,
+        }
+    }
+
+    /// آیا معاملات گذشته برای ساخت فوت‌پرینت از این صرافی قابل دریافت است
+    pub fn supports_trade_fetch(&self) -> bool {
+        self.trade_history_source().is_some()
+    }
+
     pub fn is_custom_push_freq(&self) -> bool {
         matches!(
             self,
@@ -571,6 +766,25 @@ impl Exchange {
         }
     }
 
+    /// نزدیک‌ترین فرکانس پشتیبانی شده به بازه درخواستی؛ سریع‌ترین گزینه‌ای که از بازه
+    /// درخواستی تندتر نباشد، و اگر چنین گزینه‌ای نباشد سریع‌ترین گزینه موجود
+    pub fn nearest_push_freq(&self, tf: Timeframe) -> PushFrequency {
+        let allowed = self
+            .allowed_push_freqs()
+            .iter()
+            .filter_map(|freq| match freq {
+                PushFrequency::Custom(allowed) => Some(*allowed),
+                PushFrequency::ServerDefault => None,
+            });
+
+        allowed
+            .clone()
+            .filter(|allowed| allowed.to_milliseconds() <= tf.to_milliseconds())
+            .max_by_key(|allowed| allowed.to_milliseconds())
+            .or_else(|| allowed.min_by_key(|allowed| allowed.to_milliseconds()))
+            .map_or(PushFrequency::ServerDefault, PushFrequency::Custom)
+    }
+
     pub fn supports_heatmap_timeframe(&self, tf: Timeframe) -> bool {
         match self {
             Exchange::BybitSpot => tf != Timeframe::MS100,
@@ -592,6 +806,8 @@ impl Exchange {
                 | Exchange::HyperliquidLinear
                 | Exchange::OkexLinear
                 | Exchange::OkexInverse
+                | Exchange::GateioLinear
+                | Exchange::BitgetLinear
         )
     }
 
@@ -613,7 +829,7 @@ impl Exchange {
 pub enum Event {
     Connected(Exchange),                // اتصال برقرار شد
     Disconnected(Exchange, String),     // اتصال قطع شد
-    DepthReceived(StreamKind, u64, Arc<Depth>, Box<[Trade]>), // داده‌های عمق بازار دریافت شد
+    DepthReceived(StreamKind, u64, DepthChange, Box<[Trade]>), // داده‌های عمق بازار دریافت شد
     KlineReceived(StreamKind, Kline),   // داده‌های کندل دریافت شد
 }
 
@@ -660,6 +876,9 @@ pub async fn fetch_ticker_info(
         Exchange::OkexLinear | Exchange::OkexInverse | Exchange::OkexSpot => {
             okex::fetch_ticksize(market_type).await
         }
+        Exchange::GateioLinear | Exchange::GateioSpot => gateio::fetch_ticksize(market_type).await,
+        Exchange::CoinbaseSpot => coinbase::fetch_ticksize(market_type).await,
+        Exchange::BitgetLinear | Exchange::BitgetSpot => bitget::fetch_ticksize(market_type).await,
     }
 }
 
@@ -681,20 +900,35 @@ pub async fn fetch_ticker_prices(
         Exchange::OkexLinear | Exchange::OkexInverse | Exchange::OkexSpot => {
             okex::fetch_ticker_prices(market_type).await
         }
+        Exchange::GateioLinear | Exchange::GateioSpot => {
+            gateio::fetch_ticker_prices(market_type).await
+        }
+        Exchange::CoinbaseSpot => coinbase::fetch_ticker_prices(market_type).await,
+        Exchange::BitgetLinear | Exchange::BitgetSpot => {
+            bitget::fetch_ticker_prices(market_type).await
+        }
     }
 }
 
 pub async fn fetch_klines(
     ticker_info: TickerInfo,
     timeframe: Timeframe,
+    source: KlinePriceSource,
     range: Option<(u64, u64)>,
 ) -> Result<Vec<Kline>, AdapterError> {
-    match ticker_info.ticker.exchange {
+    let exchange = ticker_info.ticker.exchange;
+    if !KlinePriceSource::available(exchange).contains(&source) {
+        return Err(AdapterError::InvalidRequest(format!(
+            "{source} price klines are not available on {exchange}"
+        )));
+    }
+
+    match exchange {
         Exchange::BinanceLinear | Exchange::BinanceInverse | Exchange::BinanceSpot => {
-            binance::fetch_klines(ticker_info, timeframe, range).await
+            binance::fetch_klines(ticker_info, timeframe, source, range).await
         }
         Exchange::BybitLinear | Exchange::BybitInverse | Exchange::BybitSpot => {
-            bybit::fetch_klines(ticker_info, timeframe, range).await
+            bybit::fetch_klines(ticker_info, timeframe, source, range).await
         }
         Exchange::HyperliquidLinear | Exchange::HyperliquidSpot => {
             hyperliquid::fetch_klines(ticker_info, timeframe, range).await
@@ -702,6 +936,101 @@ pub async fn fetch_klines(
         Exchange::OkexLinear | Exchange::OkexInverse | Exchange::OkexSpot => {
             okex::fetch_klines(ticker_info, timeframe, range).await
         }
+        Exchange::GateioLinear | Exchange::GateioSpot => {
+            gateio::fetch_klines(ticker_info, timeframe, range).await
+        }
+        Exchange::CoinbaseSpot => coinbase::fetch_klines(ticker_info, timeframe, range).await,
+        Exchange::BitgetLinear | Exchange::BitgetSpot => {
+            bitget::fetch_klines(ticker_info, timeframe, range).await
+        }
+    }
+}
+
+/// فاصله دریافت دوباره کندل‌های جاری قیمت نشان‌گذاری و شاخص
+const PRICE_KLINE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// جریان کندل‌های قیمت نشان‌گذاری و شاخص
+///
+/// صرافی‌ها برای این کندل‌ها جریان وب‌سوکت یکسانی ندارند، پس کندل بسته شده قبلی و کندل جاری
+/// هر چند ثانیه از همان نقطه REST که تاریخچه را می‌دهد دوباره دریافت می‌شوند.
+pub fn connect_price_kline_stream(
+    streams: Vec<(TickerInfo, Timeframe, KlinePriceSource)>,
+    exchange: Exchange,
+) -> impl iced_futures::futures::Stream<Item = Event> {
+    use iced_futures::futures::SinkExt;
+
+    iced_futures::stream::channel(100, async move |mut output| {
+        let mut connected = false;
+
+        loop {
+            let mut failure = None;
+
+            for &(ticker_info, timeframe, source) in &streams {
+                let now = chrono::Utc::now().timestamp_millis() as u64;
+                let interval = timeframe.to_milliseconds();
+                let range = (now.saturating_sub(interval * 2), now);
+
+                match fetch_klines(ticker_info, timeframe, source, Some(range)).await {
+                    Ok(mut klines) => {
+                        klines.sort_by_key(|kline| kline.time);
+
+                        let stream = StreamKind::Kline {
+                            ticker_info,
+                            timeframe,
+                            source,
+                        };
+                        for kline in klines.iter().rev().take(2).rev() {
+                            let _ = output.send(Event::KlineReceived(stream, *kline)).await;
+                        }
+                    }
+                    Err(err) => failure = Some(err.to_string()),
+                }
+            }
+
+            match failure {
+                Some(reason) => {
+                    connected = false;
+                    let _ = output.send(Event::Disconnected(exchange, reason)).await;
+                }
+                None if !connected => {
+                    connected = true;
+                    let _ = output.send(Event::Connected(exchange)).await;
+                }
+                None => {}
+            }
+
+            tokio::time::sleep(PRICE_KLINE_POLL_INTERVAL).await;
+        }
+    })
+}
+
+/// زیرپوشه فایل‌های روزانه معاملات هر صرافی در پوشه داده‌های بازار
+pub const TRADE_ARCHIVE_DIRS: [&str; 3] = ["binance", "bybit", "okex"];
+
+/// دریافت معاملات گذشته از زمان داده شده؛ فایل‌های روزانه هر صرافی در زیرپوشه خودش
+/// در `data_path` ذخیره می‌شوند
+pub async fn fetch_trades(
+    ticker_info: TickerInfo,
+    from_time: u64,
+    data_path: std::path::PathBuf,
+    source: crate::fetcher::TradeSource,
+) -> Result<Vec<Trade>, AdapterError> {
+    match ticker_info.ticker.exchange {
+        Exchange::BinanceLinear | Exchange::BinanceInverse | Exchange::BinanceSpot => {
+            let data_path = data_path.join(TRADE_ARCHIVE_DIRS[0]);
+            binance::fetch_trades(ticker_info, from_time, data_path, source).await
+        }
+        Exchange::BybitLinear | Exchange::BybitInverse | Exchange::BybitSpot => {
+            let data_path = data_path.join(TRADE_ARCHIVE_DIRS[1]);
+            bybit::fetch_trades(ticker_info, from_time, data_path, source).await
+        }
+        Exchange::OkexLinear | Exchange::OkexInverse | Exchange::OkexSpot => {
+            let data_path = data_path.join(TRADE_ARCHIVE_DIRS[2]);
+            okex::fetch_trades(ticker_info, from_time, data_path, source).await
+        }
+        _ => Err(AdapterError::InvalidRequest(
+            "Trade fetching is not supported for this exchange".to_string(),
+        )),
     }
 }
 
@@ -723,3 +1052,81 @@ pub async fn fetch_open_interest(
         _ => Err(AdapterError::InvalidRequest("Invalid exchange".to_string())),
     }
 }
+
+/// تاریخچه نرخ تأمین مالی؛ فقط برای قراردادهای دائمی در دسترس است
+pub async fn fetch_funding_history(
+    ticker: Ticker,
+    range: Option<(u64, u64)>,
+) -> Result<Vec<FundingRate>, AdapterError> {
+    match ticker.exchange {
+        Exchange::BinanceLinear | Exchange::BinanceInverse => {
+            binance::fetch_funding_history(ticker, range).await
+        }
+        Exchange::BybitLinear | Exchange::BybitInverse => {
+            bybit::fetch_funding_history(ticker, range).await
+        }
+        Exchange::OkexLinear | Exchange::OkexInverse => {
+            okex::fetch_funding_history(ticker, range).await
+        }
+        _ => Err(AdapterError::InvalidRequest(
+            "Funding history is not supported for this exchange".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn price_source_falls_back_to_trades() {
+        assert_eq!(
+            KlinePriceSource::Mark.resolve(Exchange::BinanceInverse),
+            KlinePriceSource::Mark
+        );
+        assert_eq!(
+            KlinePriceSource::Index.resolve(Exchange::BybitSpot),
+            KlinePriceSource::Trade
+        );
+        assert_eq!(
+            KlinePriceSource::Mark.resolve(Exchange::OkexLinear),
+            KlinePriceSource::Trade
+        );
+    }
+
+    #[test]
+    fn push_freq_maps_to_supported_levels() {
+        assert_eq!(
+            Exchange::BybitLinear.nearest_push_freq(Timeframe::MS200),
+            PushFrequency::Custom(Timeframe::MS100)
+        );
+        assert_eq!(
+            Exchange::BybitSpot.nearest_push_freq(Timeframe::MS100),
+            PushFrequency::Custom(Timeframe::MS200)
+        );
+        assert_eq!(
+            Exchange::BybitSpot.nearest_push_freq(Timeframe::MS1000),
+            PushFrequency::Custom(Timeframe::MS300)
+        );
+        assert_eq!(
+            Exchange::BinanceLinear.nearest_push_freq(Timeframe::MS100),
+            PushFrequency::ServerDefault
+        );
+    }
+
+    #[test]
+    fn persisted_kline_without_source_uses_trades() {
+        let ticker = Ticker::new("BTCUSDT", Exchange::BinanceLinear);
+        let mut value = serde_json::to_value(PersistKline {
+            ticker,
+            timeframe: Timeframe::M15,
+            source: KlinePriceSource::Mark,
+        })
+        .unwrap();
+        value.as_object_mut().unwrap().remove("source");
+
+        let restored: PersistKline = serde_json::from_value(value).unwrap();
+        assert_eq!(restored.source, KlinePriceSource::Trade);
+        assert_eq!(restored.ticker, ticker);
+    }
+}
@@ -1,19 +1,22 @@
 use super::{
     super::{
-        Exchange, Kline, MarketKind, OpenInterest, Price, PushFrequency, SizeUnit, StreamKind,
-        Ticker, TickerInfo, TickerStats, Timeframe, Trade,
-        adapter::StreamTicksize,
+        Exchange, FundingRate, Kline, MarketKind, OpenInterest, Price, PushFrequency, SizeUnit,
+        StreamKind, TickMultiplier, Ticker, TickerInfo, TickerStats, Timeframe, Trade,
+        adapter::{KlinePriceSource, StreamTicksize},
+        archive,
+        capture::{self, CaptureRecord, DepthCapture, ReplayReport, ResyncEvent},
         connect::{State, connect_ws},
         de_string_to_f32,
         depth::{DeOrder, DepthPayload, DepthUpdate, LocalDepthCache},
-        is_symbol_supported,
+        inverse_contracts_to_size, is_inverse_conversion_enabled, is_symbol_supported,
+        fetcher::TradeSource,
         limiter::{self, RateLimiter},
+        network::Endpoint,
         str_f32_parse, volume_size_unit,
     },
-    AdapterError, Event,
+    AdapterError, Event, ExchangeInclusive,
 };
 
-use csv::ReaderBuilder;
 use fastwebsockets::OpCode;
 use iced_futures::{
     futures::{SinkExt, Stream, channel::mpsc},
@@ -23,11 +26,12 @@ use serde::Deserialize;
 use sonic_rs::{FastStr, to_object_iter_unchecked};
 use tokio::sync::Mutex;
 
-use std::{collections::HashMap, io::BufReader, path::PathBuf, sync::LazyLock, time::Duration};
-
-const SPOT_DOMAIN: &str = "https://api.binance.com";
-const LINEAR_PERP_DOMAIN: &str = "https://fapi.binance.com";
-const INVERSE_PERP_DOMAIN: &str = "https://dapi.binance.com";
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+    time::Duration,
+};
 
 static SPOT_LIMITER: LazyLock<Mutex<BinanceLimiter>> =
     LazyLock::new(|| Mutex::new(BinanceLimiter::new(SPOT_LIMIT, REFILL_RATE)));
@@ -95,11 +99,11 @@ fn limiter_from_market_type(market: MarketKind) -> &'static Mutex<BinanceLimiter
     }
 }
 
-fn ws_domain_from_market_type(market: MarketKind) -> &'static str {
+fn ws_domain_from_market_type(market: MarketKind) -> String {
     match market {
-        MarketKind::Spot => "stream.binance.com",
-        MarketKind::LinearPerps => "fstream.binance.com",
-        MarketKind::InversePerps => "dstream.binance.com",
+        MarketKind::Spot => Endpoint::BinanceSpotWs.resolve(),
+        MarketKind::LinearPerps => Endpoint::BinanceLinearWs.resolve(),
+        MarketKind::InversePerps => Endpoint::BinanceInverseWs.resolve(),
     }
 }
 
@@ -200,6 +204,58 @@ struct PerpDepth {
     asks: Vec<DeOrder>,
 }
 
+/// وضعیت ترتیب یک رویداد عمق نسبت به دفتر سفارش محلی
+enum DepthSequence {
+    Stale,                    // قدیمی‌تر از تصویر فعلی؛ نادیده گرفته می‌شود
+    FirstEventGap,            // اولین رویداد پس از تصویر با آن پیوسته نیست؛ نیاز به همگام‌سازی مجدد
+    Continues,                // ادامه پیوسته دنباله
+    Broken { expected: u64 }, // شکاف در دنباله؛ اتصال باید از نو برقرار شود
+}
+
+impl SonicDepth {
+    fn time(&self) -> u64 {
+        match self {
+            SonicDepth::Spot(de) => de.time,
+            SonicDepth::Perp(de) => de.time,
+        }
+    }
+
+    fn final_id(&self) -> u64 {
+        match self {
+            SonicDepth::Spot(de) => de.final_id,
+            SonicDepth::Perp(de) => de.final_id,
+        }
+    }
+
+    /// شناسه‌ای که رویداد قبلی باید به آن ختم شده باشد
+    fn expected_prev_id(&self) -> u64 {
+        match self {
+            SonicDepth::Spot(de) => de.first_id.saturating_sub(1),
+            SonicDepth::Perp(de) => de.prev_final_id,
+        }
+    }
+
+    /// بررسی ترتیب رویداد طبق قوانین همگام‌سازی دفتر سفارش بایننس
+    fn sequence(&self, last_update_id: u64, prev_id: u64) -> DepthSequence {
+        let (first_id, final_id) = match self {
+            SonicDepth::Spot(de) => (de.first_id, de.final_id),
+            SonicDepth::Perp(de) => (de.first_id, de.final_id),
+        };
+
+        if final_id <= last_update_id || last_update_id == 0 {
+            DepthSequence::Stale
+        } else if prev_id == 0 && first_id > last_update_id + 1 {
+            DepthSequence::FirstEventGap
+        } else if prev_id == 0 || prev_id == self.expected_prev_id() {
+            DepthSequence::Continues
+        } else {
+            DepthSequence::Broken {
+                expected: self.expected_prev_id(),
+            }
+        }
+    }
+}
+
 /// انواع داده‌های دریافتی از جریان وب‌سوکت بایننس
 enum StreamData {
     Trade(SonicTrade),         // داده معامله
@@ -293,18 +349,25 @@ async fn try_resync(
     state: &mut State,
     output: &mut mpsc::Sender<Event>,
     already_fetching: &mut bool,
+    capture: &mut Option<DepthCapture>,
 ) {
     let ticker = ticker_info.ticker;
+    let market = ticker_info.market_type();
+    let depth_limit = depth_limit(market, depth_multiplier(&ticker));
 
     let (tx, rx) = tokio::sync::oneshot::channel();
     *already_fetching = true;
 
     tokio::spawn(async move {
-        let result = fetch_depth(&ticker, contract_size).await;
+        let result = fetch_depth_text(&ticker, depth_limit).await;
         let _ = tx.send(result);
     });
 
-    match rx.await {
+    let result = rx
+        .await
+        .map(|res| res.and_then(|text| parse_snapshot(&text, market, contract_size, capture)));
+
+    match result {
         Ok(Ok(depth)) => {
             orderbook.update(DepthUpdate::Snapshot(depth), ticker_info.min_ticksize);
         }
@@ -331,6 +394,63 @@ async fn try_resync(
     *already_fetching = false;
 }
 
+/// ریزترین ضریب گام پنل‌های هر نماد؛ بیرون از شناسه اشتراک نگه داشته می‌شود تا تغییر ضریب
+/// اتصال وب‌سوکت را قطع نکند و تنها در دریافت بعدی تصویر اولیه اعمال شود
+static DEPTH_MULTIPLIERS: LazyLock<std::sync::RwLock<HashMap<Ticker, TickMultiplier>>> =
+    LazyLock::new(|| std::sync::RwLock::new(HashMap::new()));
+
+/// ثبت ریزترین ضریب گام هر نماد برای تعیین اندازه تصویر اولیه دفتر سفارش
+pub fn set_depth_multipliers(multipliers: HashMap<Ticker, TickMultiplier>) {
+    if let Ok(mut current) = DEPTH_MULTIPLIERS.write() {
+        *current = multipliers;
+    }
+}
+
+fn depth_multiplier(ticker: &Ticker) -> Option<TickMultiplier> {
+    DEPTH_MULTIPLIERS
+        .read()
+        .ok()
+        .and_then(|multipliers| multipliers.get(ticker).copied())
+}
+
+/// تعداد سطوح تصویر اولیه دفتر سفارش بر اساس ضریب گام فعال
+///
+/// ضریب ۱ عمق کامل را نگه می‌دارد و با درشت‌تر شدن ضریب، محدودیت (و وزن درخواست) پله‌پله کم می‌شود؛
+/// آستانه‌ها ضریب‌هایی هستند که از آن به بعد یک پله پایین‌تر دریافت می‌شود
+fn depth_limit(market: MarketKind, tick_multiplier: Option<TickMultiplier>) -> u16 {
+    const THRESHOLDS: [u16; 3] = [2, 5, 25];
+
+    let tiers: [u16; 4] = match market {
+        MarketKind::Spot => [5000, 1000, 500, 100],
+        MarketKind::LinearPerps | MarketKind::InversePerps => [1000, 500, 100, 50],
+    };
+
+    let multiplier = tick_multiplier.map_or(1, |m| m.0);
+    let step = THRESHOLDS.iter().filter(|&&t| multiplier >= t).count();
+
+    tiers[step]
+}
+
+/// وزن درخواست عمق بازار طبق جدول بایننس؛ برای محدودیت‌های نامعتبر `None` برمی‌گرداند
+fn depth_weight(market: MarketKind, depth_limit: u16) -> Option<usize> {
+    match market {
+        MarketKind::Spot => match depth_limit {
+            1..=100 => Some(5),
+            101..=500 => Some(25),
+            501..=1000 => Some(50),
+            1001..=5000 => Some(250),
+            _ => None,
+        },
+        MarketKind::LinearPerps | MarketKind::InversePerps => match depth_limit {
+            5 | 10 | 20 | 50 => Some(2),
+            100 => Some(5),
+            500 => Some(10),
+            1000 => Some(20),
+            _ => None,
+        },
+    }
+}
+
 #[allow(unused_assignments)]
 /// برقراری اتصال به جریان داده‌های بازار (عمق و معاملات) بایننس
 pub fn connect_market_stream(
@@ -349,6 +469,7 @@ pub fn connect_market_stream(
         let mut trades_buffer: Vec<Trade> = Vec::new();
         let mut already_fetching: bool = false;
         let mut prev_id: u64 = 0;
+        let mut capture = DepthCapture::start(ticker_info);
 
         let contract_size = get_contract_size(&ticker, market);
         let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
@@ -363,49 +484,60 @@ pub fn connect_market_stream(
                     let streams = format!("{stream_1}/{stream_2}");
                     let url = format!("wss://{domain}/stream?streams={streams}");
 
-                    if let Ok(websocket) = connect_ws(domain, &url).await {
-                        let (tx, rx) = tokio::sync::oneshot::channel();
-
-                        tokio::spawn(async move {
-                            let result = fetch_depth(&ticker, contract_size).await;
-                            let _ = tx.send(result);
-                        });
-                        match rx.await {
-                            Ok(Ok(depth)) => {
-                                orderbook
-                                    .update(DepthUpdate::Snapshot(depth), ticker_info.min_ticksize);
-                                prev_id = 0;
-
-                                state = State::Connected(websocket);
-
-                                let _ = output.send(Event::Connected(exchange)).await;
-                            }
-                            Ok(Err(e)) => {
-                                let _ = output
-                                    .send(Event::Disconnected(
-                                        exchange,
-                                        format!("Depth fetch failed: {e}"),
-                                    ))
-                                    .await;
-                            }
-                            Err(e) => {
-                                let _ = output
-                                    .send(Event::Disconnected(
-                                        exchange,
-                                        format!("Channel error: {e}"),
-                                    ))
-                                    .await;
+                    match connect_ws(&domain, &url, ExchangeInclusive::Binance).await {
+                        Ok(websocket) => {
+                            let (tx, rx) = tokio::sync::oneshot::channel();
+                            let depth_limit = depth_limit(market, depth_multiplier(&ticker));
+
+                            tokio::spawn(async move {
+                                let result = fetch_depth_text(&ticker, depth_limit).await;
+                                let _ = tx.send(result);
+                            });
+                            let result = rx.await.map(|res| {
+                                res.and_then(|text| {
+                                    parse_snapshot(&text, market, contract_size, &mut capture)
+                                })
+                            });
+                            match result {
+                                Ok(Ok(depth)) => {
+                                    orderbook.update(
+                                        DepthUpdate::Snapshot(depth),
+                                        ticker_info.min_ticksize,
+                                    );
+                                    prev_id = 0;
+
+                                    state = State::Connected(websocket);
+
+                                    let _ = output.send(Event::Connected(exchange)).await;
+                                }
+                                Ok(Err(e)) => {
+                                    let _ = output
+                                        .send(Event::Disconnected(
+                                            exchange,
+                                            format!("Depth fetch failed: {e}"),
+                                        ))
+                                        .await;
+                                }
+                                Err(e) => {
+                                    let _ = output
+                                        .send(Event::Disconnected(
+                                            exchange,
+                                            format!("Channel error: {e}"),
+                                        ))
+                                        .await;
+                                }
                             }
                         }
-                    } else {
-                        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+                        Err(err) => {
+                            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
 
-                        let _ = output
-                            .send(Event::Disconnected(
-                                exchange,
-                                "Failed to connect to websocket".to_string(),
-                            ))
-                            .await;
+                            let _ = output
+                                .send(Event::Disconnected(
+                                    exchange,
+                                    format!("Failed to connect: {err}"),
+                                ))
+                                .await;
+                        }
                     }
                 }
                 State::Connected(ws) => {
@@ -417,13 +549,11 @@ pub fn connect_market_stream(
                                         StreamData::Trade(de_trade) => {
                                             let price = Price::from_f32(de_trade.price)
                                                 .round_to_min_tick(ticker_info.min_ticksize);
-                                            let qty = contract_size.map_or(
-                                                if size_in_quote_ccy {
-                                                    (de_trade.qty * de_trade.price).round()
-                                                } else {
-                                                    de_trade.qty
-                                                },
-                                                |size| de_trade.qty * size,
+                                            let qty = calc_qty(
+                                                de_trade.qty,
+                                                de_trade.price,
+                                                contract_size,
+                                                size_in_quote_ccy,
                                             );
 
                                             let trade = Trade {
@@ -435,144 +565,75 @@ pub fn connect_market_stream(
 
                                             trades_buffer.push(trade);
                                         }
-                                        StreamData::Depth(depth_type) => {
+                                        StreamData::Depth(de_depth) => {
                                             if already_fetching {
                                                 log::warn!("Already fetching...\n");
                                                 continue;
                                             }
 
-                                            let last_update_id = orderbook.last_update_id;
-
-                                            match depth_type {
-                                                SonicDepth::Perp(ref de_depth) => {
-                                                    if (de_depth.final_id <= last_update_id)
-                                                        || last_update_id == 0
-                                                    {
-                                                        continue;
-                                                    }
-
-                                                    if prev_id == 0
-                                                        && (de_depth.first_id > last_update_id + 1)
-                                                        || (last_update_id + 1 > de_depth.final_id)
-                                                    {
-                                                        log::warn!(
-                                                            "Out of sync at first event. Trying to resync...\n"
-                                                        );
-
-                                                        try_resync(
-                                                            exchange,
-                                                            ticker_info,
-                                                            contract_size,
-                                                            &mut orderbook,
-                                                            &mut state,
-                                                            &mut output,
-                                                            &mut already_fetching,
-                                                        )
-                                                        .await;
-                                                    }
-
-                                                    if (prev_id == 0)
-                                                        || (prev_id == de_depth.prev_final_id)
-                                                    {
-                                                        orderbook.update(
-                                                            DepthUpdate::Diff(new_depth_cache(
-                                                                &depth_type,
-                                                                contract_size,
-                                                            )),
-                                                            ticker_info.min_ticksize,
-                                                        );
-
-                                                        let _ = output
-                                                            .send(Event::DepthReceived(
-                                                                StreamKind::DepthAndTrades {
-                                                                    ticker_info,
-                                                                    depth_aggr:
-                                                                        StreamTicksize::Client,
-                                                                    push_freq,
-                                                                },
-                                                                de_depth.time,
-                                                                orderbook.depth.clone(),
-                                                                std::mem::take(&mut trades_buffer)
-                                                                    .into_boxed_slice(),
-                                                            ))
-                                                            .await;
-
-                                                        prev_id = de_depth.final_id;
-                                                    } else {
-                                                        state = State::Disconnected;
-                                                        let _ = output.send(
-                                                                Event::Disconnected(
-                                                                    exchange,
-                                                                    format!("Out of sync. Expected update_id: {}, got: {}", de_depth.prev_final_id, prev_id)
-                                                                )
-                                                            ).await;
-                                                    }
+                                            if let Some(capture) = capture.as_mut() {
+                                                capture.record_frame(&msg.payload[..]);
+                                            }
+
+                                            match de_depth
+                                                .sequence(orderbook.last_update_id, prev_id)
+                                            {
+                                                DepthSequence::Stale => continue,
+                                                DepthSequence::FirstEventGap => {
+                                                    log::warn!(
+                                                        "Out of sync at first event. Trying to resync...\n"
+                                                    );
+
+                                                    try_resync(
+                                                        exchange,
+                                                        ticker_info,
+                                                        contract_size,
+                                                        &mut orderbook,
+                                                        &mut state,
+                                                        &mut output,
+                                                        &mut already_fetching,
+                                                        &mut capture,
+                                                    )
+                                                    .await;
                                                 }
-                                                SonicDepth::Spot(ref de_depth) => {
-                                                    if (de_depth.final_id <= last_update_id)
-                                                        || last_update_id == 0
-                                                    {
-                                                        continue;
-                                                    }
-
-                                                    if prev_id == 0
-                                                        && (de_depth.first_id > last_update_id + 1)
-                                                        || (last_update_id + 1 > de_depth.final_id)
-                                                    {
-                                                        log::warn!(
-                                                            "Out of sync at first event. Trying to resync...\n"
-                                                        );
-
-                                                        try_resync(
+                                                DepthSequence::Continues => {}
+                                                DepthSequence::Broken { expected } => {
+                                                    state = State::Disconnected;
+                                                    let _ = output
+                                                        .send(Event::Disconnected(
                                                             exchange,
-                                                            ticker_info,
-                                                            contract_size,
-                                                            &mut orderbook,
-                                                            &mut state,
-                                                            &mut output,
-                                                            &mut already_fetching,
-                                                        )
+                                                            format!(
+                                                                "Out of sync. Expected update_id: {expected}, got: {prev_id}"
+                                                            ),
+                                                        ))
                                                         .await;
-                                                    }
-
-                                                    if (prev_id == 0)
-                                                        || (prev_id == de_depth.first_id - 1)
-                                                    {
-                                                        orderbook.update(
-                                                            DepthUpdate::Diff(new_depth_cache(
-                                                                &depth_type,
-                                                                contract_size,
-                                                            )),
-                                                            ticker_info.min_ticksize,
-                                                        );
-
-                                                        let _ = output
-                                                            .send(Event::DepthReceived(
-                                                                StreamKind::DepthAndTrades {
-                                                                    ticker_info,
-                                                                    depth_aggr:
-                                                                        StreamTicksize::Client,
-                                                                    push_freq,
-                                                                },
-                                                                de_depth.time,
-                                                                orderbook.depth.clone(),
-                                                                std::mem::take(&mut trades_buffer)
-                                                                    .into_boxed_slice(),
-                                                            ))
-                                                            .await;
-
-                                                        prev_id = de_depth.final_id;
-                                                    } else {
-                                                        state = State::Disconnected;
-                                                        let _ = output.send(
-                                                                Event::Disconnected(
-                                                                    exchange,
-                                                                    format!("Out of sync. Expected update_id: {}, got: {}", de_depth.final_id, prev_id)
-                                                                )
-                                                            ).await;
-                                                    }
+                                                    continue;
                                                 }
                                             }
+
+                                            orderbook.update(
+                                                DepthUpdate::Diff(new_depth_cache(
+                                                    &de_depth,
+                                                    contract_size,
+                                                )),
+                                                ticker_info.min_ticksize,
+                                            );
+
+                                            let _ = output
+                                                .send(Event::DepthReceived(
+                                                    StreamKind::DepthAndTrades {
+                                                        ticker_info,
+                                                        depth_aggr: StreamTicksize::Client,
+                                                        push_freq,
+                                                    },
+                                                    de_depth.time(),
+                                                    orderbook.publish(),
+                                                    std::mem::take(&mut trades_buffer)
+                                                        .into_boxed_slice(),
+                                                ))
+                                                .await;
+
+                                            prev_id = de_depth.final_id();
                                         }
                                         _ => {}
                                     }
@@ -605,6 +666,103 @@ pub fn connect_market_stream(
     })
 }
 
+/// بازپخش یک فایل ضبط عمق با همان منطق همگام‌سازی جریان زنده
+///
+/// در هنگام همگام‌سازی مجدد به جای درخواست REST، تصویر ضبط شده بعدی استفاده می‌شود تا نتیجه قطعی باشد
+pub fn replay_depth_capture(path: &Path) -> Result<ReplayReport, AdapterError> {
+    let records = capture::read_capture(path).map_err(AdapterError::ParseError)?;
+    replay_depth_records(records)
+}
+
+fn replay_depth_records(records: Vec<CaptureRecord>) -> Result<ReplayReport, AdapterError> {
+    let mut records = records.into_iter().enumerate().peekable();
+
+    let Some((_, CaptureRecord::Header { ticker_info })) = records.next() else {
+        return Err(AdapterError::ParseError(
+            "Capture is missing its header".to_string(),
+        ));
+    };
+
+    let market = ticker_info.market_type();
+    let contract_size = get_contract_size(&ticker_info.ticker, market);
+
+    let mut orderbook = LocalDepthCache::default();
+    let mut prev_id: u64 = 0;
+    let mut report = ReplayReport::default();
+
+    let apply_snapshot = |orderbook: &mut LocalDepthCache, body: &str| {
+        let depth = parse_depth(body, market, contract_size)?;
+        orderbook.update(DepthUpdate::Snapshot(depth), ticker_info.min_ticksize);
+        Ok::<_, AdapterError>(())
+    };
+
+    while let Some((idx, record)) = records.next() {
+        match record {
+            CaptureRecord::Header { .. } => {
+                return Err(AdapterError::ParseError(format!(
+                    "Unexpected header at record {idx}"
+                )));
+            }
+            CaptureRecord::Snapshot { body } => {
+                apply_snapshot(&mut orderbook, &body)?;
+                report.snapshots += 1;
+                prev_id = 0;
+            }
+            CaptureRecord::Frame { body } => {
+                let Ok(StreamData::Depth(de_depth)) = feed_de(body.as_bytes(), market) else {
+                    continue;
+                };
+                report.frames += 1;
+
+                match de_depth.sequence(orderbook.last_update_id, prev_id) {
+                    DepthSequence::Stale => {
+                        report.stale += 1;
+                        continue;
+                    }
+                    DepthSequence::FirstEventGap => {
+                        report.resyncs.push(ResyncEvent {
+                            record: idx,
+                            reason: format!(
+                                "Out of sync at first event: update_id {} after snapshot {}",
+                                de_depth.final_id(),
+                                orderbook.last_update_id
+                            ),
+                        });
+
+                        // جریان زنده در این نقطه تصویر جدیدی دریافت و بلافاصله پس از فریم ثبت کرده است
+                        if let Some((_, CaptureRecord::Snapshot { body })) =
+                            records.next_if(|(_, r)| matches!(r, CaptureRecord::Snapshot { .. }))
+                        {
+                            apply_snapshot(&mut orderbook, &body)?;
+                            report.snapshots += 1;
+                        }
+                    }
+                    DepthSequence::Continues => {}
+                    DepthSequence::Broken { expected } => {
+                        report.resyncs.push(ResyncEvent {
+                            record: idx,
+                            reason: format!(
+                                "Out of sync. Expected update_id: {expected}, got: {prev_id}"
+                            ),
+                        });
+                        continue;
+                    }
+                }
+
+                orderbook.update(
+                    DepthUpdate::Diff(new_depth_cache(&de_depth, contract_size)),
+                    ticker_info.min_ticksize,
+                );
+                report.diffs_applied += 1;
+                prev_id = de_depth.final_id();
+            }
+        }
+    }
+
+    report.last_update_id = orderbook.last_update_id;
+    Ok(report)
+}
+
 /// برقراری اتصال به جریان داده‌های کندل (Kline) بایننس
 pub fn connect_kline_stream(
     streams: Vec<(TickerInfo, Timeframe)>,
@@ -640,18 +798,21 @@ pub fn connect_kline_stream(
                     let domain = ws_domain_from_market_type(market);
                     let url = format!("wss://{domain}/stream?streams={stream_str}");
 
-                    if let Ok(websocket) = connect_ws(domain, &url).await {
-                        state = State::Connected(websocket);
-                        let _ = output.send(Event::Connected(exchange)).await;
-                    } else {
-                        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+                    match connect_ws(&domain, &url, ExchangeInclusive::Binance).await {
+                        Ok(websocket) => {
+                            state = State::Connected(websocket);
+                            let _ = output.send(Event::Connected(exchange)).await;
+                        }
+                        Err(err) => {
+                            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
 
-                        let _ = output
-                            .send(Event::Disconnected(
-                                exchange,
-                                "Failed to connect to websocket".to_string(),
-                            ))
-                            .await;
+                            let _ = output
+                                .send(Event::Disconnected(
+                                    exchange,
+                                    format!("Failed to connect: {err}"),
+                                ))
+                                .await;
+                        }
                     }
                 }
                 State::Connected(ws) => match ws.read_frame().await {
@@ -665,7 +826,20 @@ pub fn connect_kline_stream(
                                     let sell_volume = de_kline.volume - buy_volume;
 
                                     if let Some(c_size) = get_contract_size(&ticker, market) {
-                                        (buy_volume * c_size, sell_volume * c_size)
+                                        (
+                                            calc_qty(
+                                                buy_volume,
+                                                de_kline.close,
+                                                Some(c_size),
+                                                size_in_quote_ccy,
+                                            ),
+                                            calc_qty(
+                                                sell_volume,
+                                                de_kline.close,
+                                                Some(c_size),
+                                                size_in_quote_ccy,
+                                            ),
+                                        )
                                     } else if size_in_quote_ccy {
                                         (
                                             (buy_volume * de_kline.close).round(),
@@ -699,6 +873,7 @@ pub fn connect_kline_stream(
                                                 StreamKind::Kline {
                                                     ticker_info,
                                                     timeframe,
+                                                    source: KlinePriceSource::Trade,
                                                 },
                                                 kline,
                                             ))
@@ -776,21 +951,28 @@ fn new_depth_cache(depth: &SonicDepth, contract_size: Option<f32>) -> DepthPaylo
     }
 }
 
-async fn fetch_depth(
-    ticker: &Ticker,
+/// تجزیه پاسخ خام تصویر دفتر سفارش و ثبت آن در فایل ضبط (در صورت فعال بودن)
+fn parse_snapshot(
+    text: &str,
+    market: MarketKind,
     contract_size: Option<f32>,
+    capture: &mut Option<DepthCapture>,
 ) -> Result<DepthPayload, AdapterError> {
+    if let Some(capture) = capture.as_mut() {
+        capture.record_snapshot(text);
+    }
+
+    parse_depth(text, market, contract_size)
+}
+
+/// دریافت متن خام تصویر دفتر سفارش از REST
+async fn fetch_depth_text(ticker: &Ticker, depth_limit: u16) -> Result<String, AdapterError> {
     let (symbol_str, market_type) = ticker.to_full_symbol_and_type();
 
     let base_url = match market_type {
-        MarketKind::Spot => SPOT_DOMAIN.to_string() + "/api/v3/depth",
-        MarketKind::LinearPerps => LINEAR_PERP_DOMAIN.to_string() + "/fapi/v1/depth",
-        MarketKind::InversePerps => INVERSE_PERP_DOMAIN.to_string() + "/dapi/v1/depth",
-    };
-
-    let depth_limit = match market_type {
-        MarketKind::Spot => 5000,
-        MarketKind::LinearPerps | MarketKind::InversePerps => 1000,
+        MarketKind::Spot => Endpoint::BinanceSpotRest.resolve() + "/api/v3/depth",
+        MarketKind::LinearPerps => Endpoint::BinanceLinearRest.resolve() + "/fapi/v1/depth",
+        MarketKind::InversePerps => Endpoint::BinanceInverseRest.resolve() + "/dapi/v1/depth",
     };
 
     let url = format!(
@@ -800,32 +982,25 @@ async fn fetch_depth(
         depth_limit
     );
 
-    let weight = match market_type {
-        MarketKind::Spot => match depth_limit {
-            ..=100_i32 => 5,
-            101_i32..=500_i32 => 25,
-            501_i32..=1000_i32 => 50,
-            1001_i32..=5000_i32 => 250,
-            _ => panic!("Invalid depth limit for Spot market"),
-        },
-        MarketKind::LinearPerps | MarketKind::InversePerps => match depth_limit {
-            ..100 => 2,
-            100 => 5,
-            500 => 10,
-            1000 => 20,
-            _ => panic!("Invalid depth limit for Perp market"),
-        },
-    };
+    let weight = depth_weight(market_type, depth_limit).ok_or_else(|| {
+        AdapterError::InvalidRequest(format!("Invalid depth limit: {depth_limit}"))
+    })?;
 
     let limiter = limiter_from_market_type(market_type);
-    let text = crate::limiter::http_request_with_limiter(&url, limiter, weight, None, None).await?;
+    crate::limiter::http_request_with_limiter(&url, limiter, weight, None, None).await
+}
 
+fn parse_depth(
+    text: &str,
+    market: MarketKind,
+    contract_size: Option<f32>,
+) -> Result<DepthPayload, AdapterError> {
     let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
 
-    match market_type {
+    match market {
         MarketKind::Spot => {
             let fetched_depth: FetchedSpotDepth =
-                serde_json::from_str(&text).map_err(|e| AdapterError::ParseError(e.to_string()))?;
+                serde_json::from_str(text).map_err(|e| AdapterError::ParseError(e.to_string()))?;
 
             let depth = DepthPayload {
                 last_update_id: fetched_depth.update_id,
@@ -852,7 +1027,7 @@ async fn fetch_depth(
         }
         MarketKind::LinearPerps | MarketKind::InversePerps => {
             let fetched_depth: FetchedPerpDepth =
-                serde_json::from_str(&text).map_err(|e| AdapterError::ParseError(e.to_string()))?;
+                serde_json::from_str(text).map_err(|e| AdapterError::ParseError(e.to_string()))?;
 
             let depth = DepthPayload {
                 last_update_id: fetched_depth.update_id,
@@ -880,8 +1055,18 @@ async fn fetch_depth(
     }
 }
 
+/// محاسبه مقدار بر اساس واحد نمایش؛ قراردادهای معکوس در صورت فعال بودن تبدیل با
+/// [`inverse_contracts_to_size`] تبدیل می‌شوند و در غیر این صورت ارزش اسمی USD آن‌ها نمایش داده می‌شود
 fn calc_qty(qty: f32, price: f32, contract_size: Option<f32>, size_in_quote_ccy: bool) -> f32 {
     match contract_size {
+        Some(size) if is_inverse_conversion_enabled() => {
+            let unit = if size_in_quote_ccy {
+                SizeUnit::Quote
+            } else {
+                SizeUnit::Base
+            };
+            inverse_contracts_to_size(qty, price, size, unit)
+        }
         Some(size) => qty * size,
         None => {
             if size_in_quote_ccy {
@@ -921,14 +1106,19 @@ impl From<FetchedKlines> for Kline {
             low: Price::from_f32(fetched.3),
             close: Price::from_f32(fetched.4),
             volume: (fetched.9, sell_volume),
+            trade_count: Some(u64::from(fetched.8)),
         }
     }
 }
 
 /// دریافت داده‌های کندل (Kline) از طریق API بایننس
+///
+/// کندل‌های قیمت نشان‌گذاری و شاخص از نقاط جداگانه قراردادهای دائمی دریافت می‌شوند؛
+/// کندل شاخص به جای نماد قرارداد با نام جفت ارز (مثلاً BTCUSD برای BTCUSD_PERP) درخواست می‌شود.
 pub async fn fetch_klines(
     ticker_info: TickerInfo,
     timeframe: Timeframe,
+    source: KlinePriceSource,
     range: Option<(u64, u64)>,
 ) -> Result<Vec<Kline>, AdapterError> {
     let ticker = ticker_info.ticker;
@@ -936,13 +1126,22 @@ pub async fn fetch_klines(
     let (symbol_str, market_type) = ticker.to_full_symbol_and_type();
     let timeframe_str = timeframe.to_string();
 
+    let (path, symbol_param) = match source {
+        KlinePriceSource::Trade => ("klines", format!("symbol={symbol_str}")),
+        KlinePriceSource::Mark => ("markPriceKlines", format!("symbol={symbol_str}")),
+        KlinePriceSource::Index => (
+            "indexPriceKlines",
+            format!("pair={}", symbol_str.trim_end_matches("_PERP")),
+        ),
+    };
+
     let base_url = match market_type {
-        MarketKind::Spot => SPOT_DOMAIN.to_string() + "/api/v3/klines",
-        MarketKind::LinearPerps => LINEAR_PERP_DOMAIN.to_string() + "/fapi/v1/klines",
-        MarketKind::InversePerps => INVERSE_PERP_DOMAIN.to_string() + "/dapi/v1/klines",
+        MarketKind::Spot => Endpoint::BinanceSpotRest.resolve() + "/api/v3/" + path,
+        MarketKind::LinearPerps => Endpoint::BinanceLinearRest.resolve() + "/fapi/v1/" + path,
+        MarketKind::InversePerps => Endpoint::BinanceInverseRest.resolve() + "/dapi/v1/" + path,
     };
 
-    let mut url = format!("{base_url}?symbol={symbol_str}&interval={timeframe_str}");
+    let mut url = format!("{base_url}?{symbol_param}&interval={timeframe_str}");
 
     let limit_param = if let Some((start, end)) = range {
         let interval_ms = timeframe.to_milliseconds();
@@ -1016,9 +1215,14 @@ pub async fn fetch_klines(
                     };
 
                     let sell_volume = k.5 - k.9;
-                    (k.9 * contract_size, sell_volume * contract_size)
+                    (
+                        calc_qty(k.9, k.4, Some(contract_size), size_in_quote_ccy),
+                        calc_qty(sell_volume, k.4, Some(contract_size), size_in_quote_ccy),
+                    )
                 }
             },
+            // for mark and index candles this field counts price samples, not trades
+            trade_count: source.has_volume().then_some(u64::from(k.8)),
         })
         .collect();
 
@@ -1030,9 +1234,18 @@ pub async fn fetch_ticksize(
     market: MarketKind,
 ) -> Result<HashMap<Ticker, Option<TickerInfo>>, AdapterError> {
     let (url, _weight) = match market {
-        MarketKind::Spot => (SPOT_DOMAIN.to_string() + "/api/v3/exchangeInfo", 20),
-        MarketKind::LinearPerps => (LINEAR_PERP_DOMAIN.to_string() + "/fapi/v1/exchangeInfo", 1),
-        MarketKind::InversePerps => (INVERSE_PERP_DOMAIN.to_string() + "/dapi/v1/exchangeInfo", 1),
+        MarketKind::Spot => (
+            Endpoint::BinanceSpotRest.resolve() + "/api/v3/exchangeInfo",
+            20,
+        ),
+        MarketKind::LinearPerps => (
+            Endpoint::BinanceLinearRest.resolve() + "/fapi/v1/exchangeInfo",
+            1,
+        ),
+        MarketKind::InversePerps => (
+            Endpoint::BinanceInverseRest.resolve() + "/dapi/v1/exchangeInfo",
+            1,
+        ),
     };
 
     let response_text = crate::limiter::HTTP_CLIENT
@@ -1126,9 +1339,18 @@ pub async fn fetch_ticker_prices(
     market: MarketKind,
 ) -> Result<HashMap<Ticker, TickerStats>, AdapterError> {
     let (url, weight) = match market {
-        MarketKind::Spot => (SPOT_DOMAIN.to_string() + "/api/v3/ticker/24hr", 80),
-        MarketKind::LinearPerps => (LINEAR_PERP_DOMAIN.to_string() + "/fapi/v1/ticker/24hr", 40),
-        MarketKind::InversePerps => (INVERSE_PERP_DOMAIN.to_string() + "/dapi/v1/ticker/24hr", 40),
+        MarketKind::Spot => (
+            Endpoint::BinanceSpotRest.resolve() + "/api/v3/ticker/24hr",
+            80,
+        ),
+        MarketKind::LinearPerps => (
+            Endpoint::BinanceLinearRest.resolve() + "/fapi/v1/ticker/24hr",
+            40,
+        ),
+        MarketKind::InversePerps => (
+            Endpoint::BinanceInverseRest.resolve() + "/dapi/v1/ticker/24hr",
+            40,
+        ),
     };
 
     let limiter = limiter_from_market_type(market);
@@ -1182,7 +1404,9 @@ pub async fn fetch_ticker_prices(
         };
 
         let ticker_stats = TickerStats {
+            last_price,
             mark_price: last_price,
+            index_price: last_price,
             daily_price_chg: price_change_pt,
             daily_volume: match market {
                 MarketKind::Spot | MarketKind::LinearPerps => volume,
@@ -1208,6 +1432,61 @@ struct DeOpenInterest {
     pub sum: f32,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeFundingRate {
+    pub funding_time: u64,
+    #[serde(deserialize_with = "de_string_to_f32")]
+    pub funding_rate: f32,
+}
+
+/// دریافت تاریخچه نرخ تأمین مالی قراردادهای دائمی از بایننس
+pub async fn fetch_funding_history(
+    ticker: Ticker,
+    range: Option<(u64, u64)>,
+) -> Result<Vec<FundingRate>, AdapterError> {
+    let (ticker_str, market) = ticker.to_full_symbol_and_type();
+
+    let base_url = match market {
+        MarketKind::LinearPerps => Endpoint::BinanceLinearRest.resolve() + "/fapi/v1/fundingRate",
+        MarketKind::InversePerps => Endpoint::BinanceInverseRest.resolve() + "/dapi/v1/fundingRate",
+        MarketKind::Spot => {
+            return Err(AdapterError::InvalidRequest(
+                "Funding rates are only available for perpetuals".to_string(),
+            ));
+        }
+    };
+
+    let mut url = format!("{base_url}?symbol={ticker_str}&limit=1000");
+    if let Some((start, end)) = range {
+        url.push_str(&format!("&startTime={start}&endTime={end}"));
+    }
+
+    let limiter = limiter_from_market_type(market);
+    let text = crate::limiter::http_request_with_limiter(&url, limiter, 1, None, None).await?;
+
+    let rates: Vec<DeFundingRate> = serde_json::from_str(&text).map_err(|e| {
+        log::error!(
+            "Failed to parse response from {}: {}\nResponse: {}",
+            url,
+            e,
+            text
+        );
+        AdapterError::ParseError(format!("Failed to parse funding rates: {e}"))
+    })?;
+
+    let mut funding: Vec<FundingRate> = rates
+        .into_iter()
+        .map(|x| FundingRate {
+            time: x.funding_time,
+            rate: x.funding_rate,
+        })
+        .collect();
+    funding.sort_by_key(|x| x.time);
+
+    Ok(funding)
+}
+
 const THIRTY_DAYS_MS: u64 = 30 * 24 * 60 * 60 * 1000; // 30 days in milliseconds
 
 /// # Panics
@@ -1224,12 +1503,12 @@ pub async fn fetch_historical_oi(
 
     let (base_url, pair_str, weight) = match market {
         MarketKind::LinearPerps => (
-            LINEAR_PERP_DOMAIN.to_string() + "/futures/data/openInterestHist",
+            Endpoint::BinanceLinearRest.resolve() + "/futures/data/openInterestHist",
             format!("?symbol={ticker_str}",),
             12,
         ),
         MarketKind::InversePerps => (
-            INVERSE_PERP_DOMAIN.to_string() + "/futures/data/openInterestHist",
+            Endpoint::BinanceInverseRest.resolve() + "/futures/data/openInterestHist",
             format!(
                 "?pair={}&contractType=PERPETUAL",
                 ticker_str
@@ -1311,36 +1590,17 @@ pub async fn fetch_historical_oi(
     Ok(open_interest)
 }
 
-/// دریافت معاملات (Trades) از بایننس (با اولویت داده‌های محلی و سپس API)
+/// دریافت معاملات (Trades) از بایننس از زمان داده شده، بسته به منبع انتخاب شده
+/// (فایل‌های فشرده روزانه، API یا هر دو)
 pub async fn fetch_trades(
     ticker_info: TickerInfo,
     from_time: u64,
     data_path: PathBuf,
+    source: TradeSource,
 ) -> Result<Vec<Trade>, AdapterError> {
-    let today_midnight = chrono::Utc::now()
-        .date_naive()
-        .and_hms_opt(0, 0, 0)
-        .unwrap()
-        .and_utc();
-
-    if from_time as i64 >= today_midnight.timestamp_millis() {
-        return fetch_intraday_trades(ticker_info, from_time).await;
-    }
+    let archive = Archive::new(ticker_info);
 
-    let from_date = chrono::DateTime::from_timestamp_millis(from_time as i64)
-        .ok_or_else(|| AdapterError::ParseError("Invalid timestamp".into()))?
-        .date_naive();
-
-    match get_hist_trades(ticker_info, from_date, data_path).await {
-        Ok(trades) => Ok(trades),
-        Err(e) => {
-            log::warn!(
-                "Historical trades fetch failed: {}, falling back to intraday fetch",
-                e
-            );
-            fetch_intraday_trades(ticker_info, from_time).await
-        }
-    }
+    archive::fetch_trades(&archive, from_time, &data_path, source).await
 }
 
 pub async fn fetch_intraday_trades(
@@ -1351,9 +1611,15 @@ pub async fn fetch_intraday_trades(
     let (symbol_str, market_type) = ticker.to_full_symbol_and_type();
 
     let (base_url, weight) = match market_type {
-        MarketKind::Spot => (SPOT_DOMAIN.to_string() + "/api/v3/aggTrades", 4),
-        MarketKind::LinearPerps => (LINEAR_PERP_DOMAIN.to_string() + "/fapi/v1/aggTrades", 20),
-        MarketKind::InversePerps => (INVERSE_PERP_DOMAIN.to_string() + "/dapi/v1/aggTrades", 20),
+        MarketKind::Spot => (Endpoint::BinanceSpotRest.resolve() + "/api/v3/aggTrades", 4),
+        MarketKind::LinearPerps => (
+            Endpoint::BinanceLinearRest.resolve() + "/fapi/v1/aggTrades",
+            20,
+        ),
+        MarketKind::InversePerps => (
+            Endpoint::BinanceInverseRest.resolve() + "/dapi/v1/aggTrades",
+            20,
+        ),
     };
 
     let mut url = format!("{base_url}?symbol={symbol_str}&limit=1000",);
@@ -1367,6 +1633,7 @@ pub async fn fetch_intraday_trades(
             .map_err(|e| AdapterError::ParseError(format!("Failed to parse trades: {e}")))?;
 
         let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
+        let contract_size = get_contract_size(&ticker, market_type);
 
         de_trades
             .into_iter()
@@ -1374,11 +1641,12 @@ pub async fn fetch_intraday_trades(
                 time: de_trade.time,
                 is_sell: de_trade.is_sell,
                 price: Price::from_f32(de_trade.price).round_to_min_tick(ticker_info.min_ticksize),
-                qty: if size_in_quote_ccy {
-                    (de_trade.qty * de_trade.price).round()
-                } else {
-                    de_trade.qty
-                },
+                qty: calc_qty(
+                    de_trade.qty,
+                    de_trade.price,
+                    contract_size,
+                    size_in_quote_ccy,
+                ),
             })
             .collect()
     };
@@ -1386,121 +1654,179 @@ pub async fn fetch_intraday_trades(
     Ok(trades)
 }
 
-pub async fn get_hist_trades(
+/// فایل‌های روزانه aggTrades در data.binance.vision
+struct Archive {
     ticker_info: TickerInfo,
-    date: chrono::NaiveDate,
-    base_path: PathBuf,
-) -> Result<Vec<Trade>, AdapterError> {
-    let ticker = ticker_info.ticker;
-    let (symbol, market_type) = ticker.to_full_symbol_and_type();
+    contract_size: Option<f32>,
+    size_in_quote_ccy: bool,
+}
 
-    let market_subpath = match market_type {
-        MarketKind::Spot => format!("data/spot/daily/aggTrades/{symbol}"),
-        MarketKind::LinearPerps => {
-            format!("data/futures/um/daily/aggTrades/{symbol}")
-        }
-        MarketKind::InversePerps => {
-            format!("data/futures/cm/daily/aggTrades/{symbol}")
+impl Archive {
+    fn new(ticker_info: TickerInfo) -> Self {
+        let ticker = ticker_info.ticker;
+
+        Self {
+            ticker_info,
+            contract_size: get_contract_size(&ticker, ticker.market_type()),
+            size_in_quote_ccy: volume_size_unit() == SizeUnit::Quote,
         }
-    };
+    }
+}
 
-    let zip_file_name = format!(
-        "{}-aggTrades-{}.zip",
-        symbol.to_uppercase(),
-        date.format("%Y-%m-%d"),
-    );
+impl archive::TradeHistory for Archive {
+    type Archive = Self;
 
-    let base_path = base_path.join(&market_subpath);
+    fn archive(&self) -> Option<&Self> {
+        Some(self)
+    }
 
-    std::fs::create_dir_all(&base_path)
-        .map_err(|e| AdapterError::ParseError(format!("Failed to create directories: {e}")))?;
+    fn has_intraday(&self) -> bool {
+        true
+    }
 
-    let zip_path = format!("{market_subpath}/{zip_file_name}",);
-    let base_zip_path = base_path.join(&zip_file_name);
+    fn fetch_intraday(
+        &self,
+        from: u64,
+    ) -> impl Future<Output = Result<Vec<Trade>, AdapterError>> + Send {
+        fetch_intraday_trades(self.ticker_info, from)
+    }
+}
 
-    if std::fs::metadata(&base_zip_path).is_ok() {
-        log::info!("Using cached {}", zip_path);
-    } else {
-        let url = format!("https://data.binance.vision/{zip_path}");
+impl archive::TradeArchive for Archive {
+    fn base_url(&self) -> &'static str {
+        "https://data.binance.vision"
+    }
 
-        log::info!("Downloading from {}", url);
+    fn file_path(&self, date: chrono::NaiveDate) -> String {
+        let (symbol, market_type) = self.ticker_info.ticker.to_full_symbol_and_type();
 
-        let resp = reqwest::get(&url).await.map_err(AdapterError::FetchError)?;
+        let market_subpath = match market_type {
+            MarketKind::Spot => "spot",
+            MarketKind::LinearPerps => "futures/um",
+            MarketKind::InversePerps => "futures/cm",
+        };
 
-        if !resp.status().is_success() {
-            return Err(AdapterError::InvalidRequest(format!(
-                "Failed to fetch from {}: {}",
-                url,
-                resp.status()
-            )));
-        }
+        format!(
+            "data/{market_subpath}/daily/aggTrades/{symbol}/{}-aggTrades-{}.zip",
+            symbol.to_uppercase(),
+            date.format("%Y-%m-%d"),
+        )
+    }
 
-        let body = resp.bytes().await.map_err(AdapterError::FetchError)?;
+    fn compression(&self) -> archive::Compression {
+        archive::Compression::Zip
+    }
 
-        std::fs::write(&base_zip_path, &body).map_err(|e| {
-            AdapterError::ParseError(format!("Failed to write zip file: {e}, {base_zip_path:?}"))
-        })?;
+    fn has_headers(&self) -> bool {
+        false
     }
 
-    match std::fs::File::open(&base_zip_path) {
-        Ok(file) => {
-            let mut archive = zip::ZipArchive::new(file)
-                .map_err(|e| AdapterError::ParseError(format!("Failed to unzip file: {e}")))?;
-
-            let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
-
-            let mut trades = Vec::new();
-            for i in 0..archive.len() {
-                let csv_file = archive
-                    .by_index(i)
-                    .map_err(|e| AdapterError::ParseError(format!("Failed to read csv: {e}")))?;
-
-                let mut csv_reader = ReaderBuilder::new()
-                    .has_headers(false)
-                    .from_reader(BufReader::new(csv_file));
-
-                trades.extend(csv_reader.records().filter_map(|record| {
-                    record.ok().and_then(|record| {
-                        let time = record[5].parse::<u64>().ok()?;
-                        let is_sell = record[6].parse::<bool>().ok()?;
-                        let price_f32 = str_f32_parse(&record[1]);
-
-                        let price =
-                            Price::from_f32(price_f32).round_to_min_tick(ticker_info.min_ticksize);
-
-                        let mut qty = str_f32_parse(&record[2]);
-
-                        qty = if size_in_quote_ccy {
-                            (qty * price_f32).round()
-                        } else {
-                            qty
-                        };
-
-                        Some(Trade {
-                            time,
-                            is_sell,
-                            price,
-                            qty,
-                        })
-                    })
-                }));
-            }
+    fn parse_record(&self, record: &csv::StringRecord) -> Option<Trade> {
+        let time = record.get(5)?.parse::<u64>().ok()?;
+        let is_sell = record.get(6)?.parse::<bool>().ok()?;
+        let price_f32 = str_f32_parse(record.get(1)?);
+
+        Some(Trade {
+            time,
+            is_sell,
+            price: Price::from_f32(price_f32).round_to_min_tick(self.ticker_info.min_ticksize),
+            qty: calc_qty(
+                str_f32_parse(record.get(2)?),
+                price_f32,
+                self.contract_size,
+                self.size_in_quote_ccy,
+            ),
+        })
+    }
+}
 
-            if let Some(latest_trade) = trades.last() {
-                match fetch_intraday_trades(ticker_info, latest_trade.time).await {
-                    Ok(intraday_trades) => {
-                        trades.extend(intraday_trades);
-                    }
-                    Err(e) => {
-                        log::error!("Failed to fetch intraday trades: {}", e);
-                    }
-                }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn depth_limit_has_matching_weight() {
+        for market in [
+            MarketKind::Spot,
+            MarketKind::LinearPerps,
+            MarketKind::InversePerps,
+        ] {
+            for multiplier in TickMultiplier::ALL {
+                let limit = depth_limit(market, Some(multiplier));
+                assert!(
+                    depth_weight(market, limit).is_some(),
+                    "no weight for {market:?} limit {limit} ({multiplier})"
+                );
             }
+        }
 
-            Ok(trades)
+        // ضریب ۱ عمق کامل را با همان وزن قبلی دریافت می‌کند
+        assert_eq!(depth_limit(MarketKind::Spot, None), 5000);
+        assert_eq!(depth_weight(MarketKind::Spot, 5000), Some(250));
+        assert_eq!(
+            depth_limit(MarketKind::LinearPerps, Some(TickMultiplier(1))),
+            1000
+        );
+        assert_eq!(depth_weight(MarketKind::LinearPerps, 1000), Some(20));
+
+        // ضریب درشت‌تر هرگز سطوح بیشتر یا وزن سنگین‌تری دریافت نمی‌کند و درشت‌ترین ضریب کمتر از ضریب ۱ می‌گیرد
+        for market in [MarketKind::Spot, MarketKind::LinearPerps] {
+            let limits: Vec<u16> = TickMultiplier::ALL
+                .iter()
+                .map(|m| depth_limit(market, Some(*m)))
+                .collect();
+
+            assert!(
+                limits.windows(2).all(|w| w[0] >= w[1]),
+                "{market:?} limits grow with the multiplier: {limits:?}"
+            );
+            assert!(limits.last() < limits.first());
+
+            let weights: Vec<usize> = limits
+                .iter()
+                .filter_map(|&limit| depth_weight(market, limit))
+                .collect();
+            assert!(weights.windows(2).all(|w| w[0] >= w[1]));
         }
-        Err(e) => Err(AdapterError::ParseError(format!(
-            "Failed to open compressed file: {e}"
-        ))),
+    }
+
+    #[test]
+    fn replay_reports_resyncs() {
+        let ticker_info = TickerInfo::new(
+            Ticker::new("BTCUSDT", Exchange::BinanceSpot),
+            0.01,
+            0.00001,
+            None,
+        );
+        let snapshot = |id: u64| CaptureRecord::Snapshot {
+            body: format!(
+                r#"{{"lastUpdateId":{id},"bids":[["100.0","1.0"]],"asks":[["100.5","1.0"]]}}"#
+            ),
+        };
+        let frame = |first: u64, last: u64| CaptureRecord::Frame {
+            body: format!(
+                r#"{{"stream":"btcusdt@depth@100ms","data":{{"E":1,"U":{first},"u":{last},"b":[["100.0","2.0"]],"a":[]}}}}"#
+            ),
+        };
+
+        let records = vec![
+            CaptureRecord::Header { ticker_info },
+            snapshot(100),
+            frame(90, 100),  // قدیمی‌تر از تصویر
+            frame(110, 115), // شکاف در اولین رویداد
+            snapshot(112),
+            frame(116, 120),
+            frame(125, 130), // شکاف در دنباله
+        ];
+
+        let report = replay_depth_records(records).unwrap();
+
+        assert_eq!(report.frames, 4);
+        assert_eq!(report.stale, 1);
+        assert_eq!(report.snapshots, 2);
+        assert_eq!(report.diffs_applied, 2);
+        assert_eq!(report.resyncs.len(), 2);
+        assert_eq!(report.resyncs[0].record, 3);
+        assert_eq!(report.last_update_id, 120);
     }
 }
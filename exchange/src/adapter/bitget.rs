@@ -0,0 +1,1012 @@
+use crate::{
+    Price, PushFrequency, SizeUnit,
+    adapter::{StreamKind, StreamTicksize},
+    limiter::{self, RateLimiter},
+    network::Endpoint,
+    volume_size_unit,
+};
+
+use super::{
+    super::{
+        Exchange, Kline, MarketKind, Ticker, TickerInfo, TickerStats, Timeframe, Trade,
+        connect::{State, connect_ws},
+        is_symbol_supported,
+    },
+    AdapterError, Event, ExchangeInclusive, KlinePriceSource,
+};
+
+use super::super::depth::{DeOrder, DepthPayload, DepthUpdate, LocalDepthCache};
+
+use fastwebsockets::{Frame, OpCode};
+use iced_futures::{
+    futures::{SinkExt, Stream, channel::mpsc},
+    stream,
+};
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::LazyLock,
+    time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
+
+const LIMIT: usize = 20;
+
+const REFILL_RATE: Duration = Duration::from_secs(1);
+const LIMITER_BUFFER_PCT: f32 = 0.05;
+
+/// فاصله ارسال پیام `ping` متنی؛ سرور پس از ۲ دقیقه بدون پیام اتصال را می‌بندد
+const PING_INTERVAL: Duration = Duration::from_secs(25);
+
+/// تعداد سطوح هر سمت که در محاسبه checksum دفتر سفارش شرکت می‌کنند
+const CHECKSUM_LEVELS: usize = 25;
+
+/// نوع محصول فیوچرز دائمی با تسویه USDT در API نسخه ۲
+const PRODUCT_TYPE: &str = "USDT-FUTURES";
+
+/// پسوند قدیمی نمادهای فیوچرز USDT-M؛ نماد داخلی برای یکتا ماندن آن را نگه می‌دارد
+const LINEAR_SUFFIX: &str = "_UMCBL";
+
+static BITGET_LIMITER: LazyLock<Mutex<BitgetLimiter>> =
+    LazyLock::new(|| Mutex::new(BitgetLimiter::new(LIMIT, REFILL_RATE)));
+
+/// محدودکننده نرخ اختصاصی برای بیت‌گت (Bitget)
+pub struct BitgetLimiter {
+    bucket: limiter::FixedWindowBucket,
+}
+
+impl BitgetLimiter {
+    pub fn new(limit: usize, refill_rate: Duration) -> Self {
+        let effective_limit = (limit as f32 * (1.0 - LIMITER_BUFFER_PCT)) as usize;
+        Self {
+            bucket: limiter::FixedWindowBucket::new(effective_limit, refill_rate),
+        }
+    }
+}
+
+impl RateLimiter for BitgetLimiter {
+    fn prepare_request(&mut self, weight: usize) -> Option<Duration> {
+        self.bucket.calculate_wait_time(weight)
+    }
+
+    fn update_from_response(&mut self, _response: &reqwest::Response, weight: usize) {
+        self.bucket.consume_tokens(weight);
+    }
+
+    fn should_exit_on_response(&self, response: &reqwest::Response) -> bool {
+        response.status() == 429
+    }
+}
+
+fn exchange_from_market_type(market: MarketKind) -> Exchange {
+    match market {
+        MarketKind::Spot => Exchange::BitgetSpot,
+        MarketKind::LinearPerps | MarketKind::InversePerps => Exchange::BitgetLinear,
+    }
+}
+
+/// مقدار `instType` در پیام‌های وب‌سوکت بر اساس نوع بازار
+fn inst_type(market: MarketKind) -> &'static str {
+    match market {
+        MarketKind::Spot => "SPOT",
+        MarketKind::LinearPerps | MarketKind::InversePerps => PRODUCT_TYPE,
+    }
+}
+
+fn ws_domain_and_url() -> (String, String) {
+    let domain = Endpoint::BitgetWs.resolve();
+    let url = format!("wss://{domain}/v2/ws/public");
+    (domain, url)
+}
+
+/// حذف پسوندهای قدیمی بیت‌گت (`_UMCBL`، `_SPBL` و ...) از نماد
+pub fn normalize_symbol(symbol: &str) -> &str {
+    ["_UMCBL", "_DMCBL", "_CMCBL", "_SPBL"]
+        .iter()
+        .find_map(|suffix| symbol.strip_suffix(suffix))
+        .unwrap_or(symbol)
+}
+
+/// ساخت نماد با نام داخلی پسونددار برای فیوچرز و نام نمایشی تمیز
+fn ticker_from_symbol(symbol: &str, exchange: Exchange) -> Ticker {
+    let clean = normalize_symbol(symbol);
+
+    match exchange.market_type() {
+        MarketKind::Spot => Ticker::new(clean, exchange),
+        MarketKind::LinearPerps | MarketKind::InversePerps => {
+            Ticker::new_with_display(&format!("{clean}{LINEAR_SUFFIX}"), exchange, Some(clean))
+        }
+    }
+}
+
+/// نمادی که API نسخه ۲ انتظار دارد (بدون پسوند)
+fn api_symbol(ticker: &Ticker) -> (String, MarketKind) {
+    let (symbol, market) = ticker.to_full_symbol_and_type();
+    (normalize_symbol(&symbol).to_string(), market)
+}
+
+fn ws_request(op: &str, market: MarketKind, channel: &str, inst_id: &str) -> Value {
+    serde_json::json!({
+        "op": op,
+        "args": [{
+            "instType": inst_type(market),
+            "channel": channel,
+            "instId": inst_id,
+        }],
+    })
+}
+
+/// معامله دریافتی از جریان داده بیت‌گت
+struct SonicTrade {
+    pub time: u64,     // زمان معامله (میلی‌ثانیه)
+    pub price: f32,    // قیمت
+    pub qty: f32,      // مقدار به ارز پایه
+    pub is_sell: bool, // سمت معامله
+}
+
+/// یک سطح دفتر سفارش با رشته‌های اصلی سرور که برای checksum لازم هستند
+#[derive(Clone)]
+struct RawLevel {
+    price: String,
+    size: String,
+}
+
+impl RawLevel {
+    fn to_order(&self) -> Option<DeOrder> {
+        Some(DeOrder {
+            price: self.price.parse().ok()?,
+            qty: self.size.parse().ok()?,
+        })
+    }
+}
+
+/// پیام کانال `books`: تصویر کامل در ابتدای اشتراک و سپس بروزرسانی‌ها
+struct SonicDepth {
+    pub is_snapshot: bool,     // آیا تصویر کامل است
+    pub seq: u64,              // شماره ترتیبی پیام
+    pub time: u64,             // زمان بروزرسانی
+    pub checksum: Option<i32>, // CRC32 امضاشده ۲۵ سطح اول
+    pub bids: Vec<RawLevel>,   // لیست خرید
+    pub asks: Vec<RawLevel>,   // لیست فروش
+}
+
+enum StreamData {
+    Trade(Vec<SonicTrade>),
+    Depth(SonicDepth),
+    Pong,
+}
+
+fn value_to_f32(v: &Value) -> Option<f32> {
+    match v {
+        Value::String(s) => s.parse::<f32>().ok(),
+        Value::Number(n) => n.as_f64().map(|x| x as f32),
+        _ => None,
+    }
+}
+
+fn value_to_u64(v: &Value) -> Option<u64> {
+    match v {
+        Value::String(s) => s.parse::<u64>().ok(),
+        Value::Number(n) => n.as_u64(),
+        _ => None,
+    }
+}
+
+fn parse_raw_levels(v: Option<&Value>) -> Vec<RawLevel> {
+    let Some(arr) = v.and_then(|v| v.as_array()) else {
+        return Vec::new();
+    };
+
+    arr.iter()
+        .filter_map(|level| {
+            Some(RawLevel {
+                price: level.get(0)?.as_str()?.to_string(),
+                size: level.get(1)?.as_str()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn parse_trade(v: &Value) -> Option<SonicTrade> {
+    Some(SonicTrade {
+        time: value_to_u64(v.get("ts")?)?,
+        price: value_to_f32(v.get("price")?)?,
+        qty: value_to_f32(v.get("size")?)?,
+        is_sell: v.get("side").and_then(|s| s.as_str()) == Some("sell"),
+    })
+}
+
+fn feed_de(slice: &[u8]) -> Result<StreamData, AdapterError> {
+    if slice == b"pong" {
+        return Ok(StreamData::Pong);
+    }
+
+    let v: Value =
+        serde_json::from_slice(slice).map_err(|e| AdapterError::ParseError(e.to_string()))?;
+
+    if let Some(event) = v.get("event").and_then(|e| e.as_str()) {
+        if event == "error" {
+            return Err(AdapterError::WebsocketError(format!(
+                "{}: {}",
+                v["code"], v["msg"]
+            )));
+        }
+        return Err(AdapterError::ParseError(format!(
+            "Unhandled event: {event}"
+        )));
+    }
+
+    let channel = v["arg"]["channel"].as_str().unwrap_or("");
+    let action = v["action"].as_str().unwrap_or("");
+
+    let Some(items) = v.get("data").and_then(|d| d.as_array()) else {
+        return Err(AdapterError::ParseError("Missing data".to_string()));
+    };
+
+    match channel {
+        // تصویر اولیه کانال معاملات شامل معاملات گذشته است و نادیده گرفته می‌شود
+        "trade" if action == "snapshot" => Ok(StreamData::Trade(Vec::new())),
+        "trade" => Ok(StreamData::Trade(
+            items.iter().filter_map(parse_trade).collect(),
+        )),
+        "books" => {
+            let Some(book) = items.first() else {
+                return Err(AdapterError::ParseError("Empty depth message".to_string()));
+            };
+
+            Ok(StreamData::Depth(SonicDepth {
+                is_snapshot: action == "snapshot",
+                seq: book.get("seq").and_then(value_to_u64).unwrap_or(0),
+                time: book.get("ts").and_then(value_to_u64).unwrap_or(0),
+                checksum: book
+                    .get("checksum")
+                    .and_then(|c| c.as_i64())
+                    .map(|c| c as i32),
+                bids: parse_raw_levels(book.get("bids")),
+                asks: parse_raw_levels(book.get("asks")),
+            }))
+        }
+        _ => Err(AdapterError::ParseError(format!(
+            "Unknown channel: {channel}"
+        ))),
+    }
+}
+
+/// نسخه‌ای از سطوح دفتر سفارش با رشته‌های اصلی سرور
+///
+/// `LocalDepthCache` قیمت‌ها را گرد کرده و مقادیر را به واحد نمایش تبدیل می‌کند،
+/// پس checksum بیت‌گت فقط با همین رشته‌ها قابل بازسازی است.
+#[derive(Default)]
+struct ChecksumBook {
+    bids: BTreeMap<Price, RawLevel>,
+    asks: BTreeMap<Price, RawLevel>,
+}
+
+impl ChecksumBook {
+    fn apply(&mut self, depth: &SonicDepth) {
+        if depth.is_snapshot {
+            self.bids.clear();
+            self.asks.clear();
+        }
+
+        for (levels, book) in [(&depth.bids, &mut self.bids), (&depth.asks, &mut self.asks)] {
+            for level in levels {
+                let Some(order) = level.to_order() else {
+                    continue;
+                };
+                let price = Price::from_f32(order.price);
+
+                if order.qty == 0.0 {
+                    book.remove(&price);
+                } else {
+                    book.insert(price, level.clone());
+                }
+            }
+        }
+    }
+
+    /// CRC32 رشته `bid1:size1:ask1:size1:bid2:...` روی ۲۵ سطح اول هر سمت
+    fn checksum(&self) -> i32 {
+        let mut bids = self.bids.values().rev().take(CHECKSUM_LEVELS);
+        let mut asks = self.asks.values().take(CHECKSUM_LEVELS);
+
+        let mut parts = Vec::with_capacity(CHECKSUM_LEVELS * 4);
+        for _ in 0..CHECKSUM_LEVELS {
+            for level in [bids.next(), asks.next()].into_iter().flatten() {
+                parts.push(level.price.as_str());
+                parts.push(level.size.as_str());
+            }
+        }
+
+        crc32(parts.join(":").as_bytes()) as i32
+    }
+}
+
+/// CRC32 استاندارد (IEEE) برای اعتبارسنجی دفتر سفارش
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+async fn try_connect(
+    subscriptions: &[Value],
+    exchange: Exchange,
+    output: &mut mpsc::Sender<Event>,
+) -> State {
+    let (domain, url) = ws_domain_and_url();
+
+    match connect_ws(&domain, &url, ExchangeInclusive::Bitget).await {
+        Ok(mut websocket) => {
+            for sub in subscriptions {
+                if let Err(e) = websocket
+                    .write_frame(Frame::text(fastwebsockets::Payload::Borrowed(
+                        sub.to_string().as_bytes(),
+                    )))
+                    .await
+                {
+                    let _ = output
+                        .send(Event::Disconnected(
+                            exchange,
+                            format!("Failed subscribing: {e}"),
+                        ))
+                        .await;
+                    return State::Disconnected;
+                }
+            }
+
+            let _ = output.send(Event::Connected(exchange)).await;
+            State::Connected(websocket)
+        }
+        Err(err) => {
+            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+            let _ = output
+                .send(Event::Disconnected(
+                    exchange,
+                    format!("Failed to connect: {err}"),
+                ))
+                .await;
+            State::Disconnected
+        }
+    }
+}
+
+/// ارسال پیام ping متنی در صورت گذشت بازه مشخص از آخرین ارسال
+async fn keep_alive(state: &mut State, last_ping: &mut Instant) {
+    if last_ping.elapsed() < PING_INTERVAL {
+        return;
+    }
+    *last_ping = Instant::now();
+
+    if let State::Connected(ws) = state
+        && let Err(e) = ws
+            .write_frame(Frame::text(fastwebsockets::Payload::Borrowed(b"ping")))
+            .await
+    {
+        log::warn!("Bitget ping failed: {e}");
+        *state = State::Disconnected;
+    }
+}
+
+/// لغو و تمدید اشتراک کانال `books` تا سرور تصویر کامل تازه‌ای بفرستد
+async fn resubscribe_depth(state: &mut State, market: MarketKind, symbol: &str) {
+    let State::Connected(ws) = state else {
+        return;
+    };
+
+    for op in ["unsubscribe", "subscribe"] {
+        let request = ws_request(op, market, "books", symbol);
+
+        if let Err(e) = ws
+            .write_frame(Frame::text(fastwebsockets::Payload::Borrowed(
+                request.to_string().as_bytes(),
+            )))
+            .await
+        {
+            log::warn!("Bitget depth resubscribe failed: {e}");
+            *state = State::Disconnected;
+            return;
+        }
+    }
+}
+
+/// برقراری اتصال به جریان داده‌های بازار (عمق و معاملات) بیت‌گت
+pub fn connect_market_stream(
+    ticker_info: TickerInfo,
+    push_freq: PushFrequency,
+) -> impl Stream<Item = Event> {
+    stream::channel(100, async move |mut output| {
+        let mut state: State = State::Disconnected;
+
+        let (symbol_str, market_type) = api_symbol(&ticker_info.ticker);
+        let exchange = exchange_from_market_type(market_type);
+
+        let subscriptions = [
+            ws_request("subscribe", market_type, "trade", &symbol_str),
+            ws_request("subscribe", market_type, "books", &symbol_str),
+        ];
+
+        let mut trades_buffer: Vec<Trade> = vec![];
+        let mut orderbook = LocalDepthCache::default();
+        let mut checksum_book = ChecksumBook::default();
+        let mut last_ping = Instant::now();
+
+        let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
+        let contract_size = ticker_info.contract_size.map(f32::from);
+
+        loop {
+            match &mut state {
+                State::Disconnected => {
+                    state = try_connect(&subscriptions, exchange, &mut output).await;
+
+                    if matches!(state, State::Connected(_)) {
+                        orderbook = LocalDepthCache::default();
+                        checksum_book = ChecksumBook::default();
+                        last_ping = Instant::now();
+                    }
+                }
+                State::Connected(ws) => match ws.read_frame().await {
+                    Ok(msg) => match msg.opcode {
+                        OpCode::Text => {
+                            match feed_de(&msg.payload[..]) {
+                                Ok(StreamData::Trade(de_trades)) => {
+                                    for de_trade in &de_trades {
+                                        let price = Price::from_f32(de_trade.price)
+                                            .round_to_min_tick(ticker_info.min_ticksize);
+
+                                        trades_buffer.push(Trade {
+                                            time: de_trade.time,
+                                            is_sell: de_trade.is_sell,
+                                            price,
+                                            qty: calc_qty(
+                                                de_trade.qty,
+                                                de_trade.price,
+                                                size_in_quote_ccy,
+                                                contract_size,
+                                            ),
+                                        });
+                                    }
+                                }
+                                Ok(StreamData::Depth(de_depth)) => {
+                                    // بروزرسانی‌های پیش از رسیدن تصویر کامل قابل اعمال نیستند
+                                    if !de_depth.is_snapshot && orderbook.last_update_id == 0 {
+                                        continue;
+                                    }
+
+                                    checksum_book.apply(&de_depth);
+
+                                    let depth = new_depth_cache(
+                                        &de_depth,
+                                        size_in_quote_ccy,
+                                        contract_size,
+                                    );
+                                    let update = if de_depth.is_snapshot {
+                                        DepthUpdate::Snapshot(depth)
+                                    } else {
+                                        DepthUpdate::Diff(depth)
+                                    };
+                                    orderbook.update(update, ticker_info.min_ticksize);
+
+                                    if let Some(expected) = de_depth.checksum
+                                        && checksum_book.checksum() != expected
+                                    {
+                                        log::warn!(
+                                            "Bitget checksum mismatch for {symbol_str} at seq {}. Trying to resync...\n",
+                                            de_depth.seq
+                                        );
+
+                                        orderbook = LocalDepthCache::default();
+                                        checksum_book = ChecksumBook::default();
+                                        resubscribe_depth(&mut state, market_type, &symbol_str)
+                                            .await;
+                                        continue;
+                                    }
+
+                                    let _ = output
+                                        .send(Event::DepthReceived(
+                                            StreamKind::DepthAndTrades {
+                                                ticker_info,
+                                                depth_aggr: StreamTicksize::Client,
+                                                push_freq,
+                                            },
+                                            de_depth.time,
+                                            orderbook.publish(),
+                                            std::mem::take(&mut trades_buffer).into_boxed_slice(),
+                                        ))
+                                        .await;
+                                }
+                                Ok(StreamData::Pong) => {}
+                                Err(AdapterError::WebsocketError(e)) => {
+                                    log::error!("Bitget stream error: {e}");
+                                }
+                                Err(_) => {}
+                            }
+
+                            keep_alive(&mut state, &mut last_ping).await;
+                        }
+                        OpCode::Close => {
+                            state = State::Disconnected;
+                            let _ = output
+                                .send(Event::Disconnected(
+                                    exchange,
+                                    "Connection closed".to_string(),
+                                ))
+                                .await;
+                        }
+                        _ => {}
+                    },
+                    Err(e) => {
+                        state = State::Disconnected;
+                        let _ = output
+                            .send(Event::Disconnected(
+                                exchange,
+                                "Error reading frame: ".to_string() + &e.to_string(),
+                            ))
+                            .await;
+                    }
+                },
+            }
+        }
+    })
+}
+
+/// برقراری اتصال به جریان داده‌های کندل (Kline) بیت‌گت
+pub fn connect_kline_stream(
+    streams: Vec<(TickerInfo, Timeframe)>,
+    market_type: MarketKind,
+) -> impl Stream<Item = Event> {
+    stream::channel(100, async move |mut output| {
+        let mut state = State::Disconnected;
+
+        let exchange = exchange_from_market_type(market_type);
+
+        let mut subscriptions = Vec::with_capacity(streams.len());
+        let mut lookup = HashMap::new();
+        for (ticker_info, timeframe) in &streams {
+            if let Some(channel) = timeframe_to_ws_channel(*timeframe) {
+                let (symbol, _mt) = api_symbol(&ticker_info.ticker);
+
+                subscriptions.push(ws_request("subscribe", market_type, channel, &symbol));
+                lookup.insert((channel, symbol), (*ticker_info, *timeframe));
+            }
+        }
+
+        let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
+        let mut last_ping = Instant::now();
+
+        loop {
+            match &mut state {
+                State::Disconnected => {
+                    state = try_connect(&subscriptions, exchange, &mut output).await;
+                    last_ping = Instant::now();
+                }
+                State::Connected(ws) => match ws.read_frame().await {
+                    Ok(msg) => match msg.opcode {
+                        OpCode::Text => {
+                            if let Ok(v) = serde_json::from_slice::<Value>(&msg.payload[..])
+                                && let (Some(channel), Some(inst_id)) =
+                                    (v["arg"]["channel"].as_str(), v["arg"]["instId"].as_str())
+                                && let Some((ticker_info, timeframe)) =
+                                    timeframe_from_ws_channel(channel)
+                                        .and_then(|channel| {
+                                            lookup.get(&(channel, inst_id.to_string()))
+                                        })
+                                        .copied()
+                                && let Some(rows) = v["data"].as_array()
+                            {
+                                // تصویر اولیه شامل تاریخچه است و فقط آخرین کندل آن لازم است
+                                let rows = if v["action"].as_str() == Some("snapshot") {
+                                    rows.last().into_iter().collect::<Vec<_>>()
+                                } else {
+                                    rows.iter().collect()
+                                };
+
+                                let contract_size = ticker_info.contract_size.map(f32::from);
+
+                                for row in rows {
+                                    if let Some(kline) = parse_kline_row(
+                                        row,
+                                        ticker_info,
+                                        size_in_quote_ccy,
+                                        contract_size,
+                                    ) {
+                                        let _ = output
+                                            .send(Event::KlineReceived(
+                                                StreamKind::Kline {
+                                                    ticker_info,
+                                                    timeframe,
+                                                    source: KlinePriceSource::Trade,
+                                                },
+                                                kline,
+                                            ))
+                                            .await;
+                                    }
+                                }
+                            }
+
+                            keep_alive(&mut state, &mut last_ping).await;
+                        }
+                        OpCode::Close => {
+                            state = State::Disconnected;
+                            let _ = output
+                                .send(Event::Disconnected(
+                                    exchange,
+                                    "Connection closed".to_string(),
+                                ))
+                                .await;
+                        }
+                        _ => {}
+                    },
+                    Err(e) => {
+                        state = State::Disconnected;
+                        let _ = output
+                            .send(Event::Disconnected(
+                                exchange,
+                                "Error reading frame: ".to_string() + &e.to_string(),
+                            ))
+                            .await;
+                    }
+                },
+            }
+        }
+    })
+}
+
+/// ردیف کندل در REST و وب‌سوکت: `[ts, open, high, low, close, base_vol, ...]`
+fn parse_kline_row(
+    row: &Value,
+    ticker_info: TickerInfo,
+    size_in_quote_ccy: bool,
+    contract_size: Option<f32>,
+) -> Option<Kline> {
+    let time = row.get(0).and_then(value_to_u64)?;
+    let open = row.get(1).and_then(value_to_f32)?;
+    let high = row.get(2).and_then(value_to_f32)?;
+    let low = row.get(3).and_then(value_to_f32)?;
+    let close = row.get(4).and_then(value_to_f32)?;
+
+    let volume = row.get(5).and_then(value_to_f32).map_or(0.0, |qty| {
+        calc_qty(qty, close, size_in_quote_ccy, contract_size)
+    });
+
+    Some(Kline::new(
+        time,
+        open,
+        high,
+        low,
+        close,
+        (-1.0, volume),
+        ticker_info.min_ticksize,
+    ))
+}
+
+fn new_depth_cache(
+    depth: &SonicDepth,
+    size_in_quote_ccy: bool,
+    contract_size: Option<f32>,
+) -> DepthPayload {
+    let convert = |levels: &[RawLevel]| {
+        levels
+            .iter()
+            .filter_map(RawLevel::to_order)
+            .map(|x| DeOrder {
+                price: x.price,
+                qty: calc_qty(x.qty, x.price, size_in_quote_ccy, contract_size),
+            })
+            .collect()
+    };
+
+    DepthPayload {
+        // شماره ترتیبی تصویر کامل می‌تواند صفر باشد؛ صفر به معنای نبود تصویر است
+        last_update_id: depth.seq.max(1),
+        time: depth.time,
+        bids: convert(&depth.bids),
+        asks: convert(&depth.asks),
+    }
+}
+
+/// مقادیر فیوچرز USDT-M بیت‌گت به ارز پایه هستند؛ اندازه قرارداد فقط در صورت وجود اعمال می‌شود
+fn calc_qty(qty: f32, price: f32, size_in_quote_ccy: bool, contract_size: Option<f32>) -> f32 {
+    let base_qty = contract_size.map_or(qty, |cs| qty * cs);
+
+    if size_in_quote_ccy {
+        base_qty * price
+    } else {
+        base_qty
+    }
+}
+
+fn timeframe_to_ws_channel(tf: Timeframe) -> Option<&'static str> {
+    Some(match tf {
+        Timeframe::M1 => "candle1m",
+        Timeframe::M5 => "candle5m",
+        Timeframe::M15 => "candle15m",
+        Timeframe::M30 => "candle30m",
+        Timeframe::H1 => "candle1H",
+        Timeframe::H4 => "candle4H",
+        Timeframe::H12 => "candle12H",
+        Timeframe::D1 => "candle1D",
+        _ => return None,
+    })
+}
+
+fn timeframe_from_ws_channel(channel: &str) -> Option<&'static str> {
+    Timeframe::KLINE
+        .into_iter()
+        .filter_map(timeframe_to_ws_channel)
+        .find(|c| *c == channel)
+}
+
+fn timeframe_to_granularity(tf: Timeframe, market: MarketKind) -> Option<&'static str> {
+    let is_spot = market == MarketKind::Spot;
+
+    Some(match tf {
+        Timeframe::M1 if is_spot => "1min",
+        Timeframe::M1 => "1m",
+        Timeframe::M5 if is_spot => "5min",
+        Timeframe::M5 => "5m",
+        Timeframe::M15 if is_spot => "15min",
+        Timeframe::M15 => "15m",
+        Timeframe::M30 if is_spot => "30min",
+        Timeframe::M30 => "30m",
+        Timeframe::H1 if is_spot => "1h",
+        Timeframe::H1 => "1H",
+        Timeframe::H4 if is_spot => "4h",
+        Timeframe::H4 => "4H",
+        Timeframe::H12 if is_spot => "12h",
+        Timeframe::H12 => "12H",
+        Timeframe::D1 if is_spot => "1day",
+        Timeframe::D1 => "1D",
+        _ => return None,
+    })
+}
+
+/// دریافت پاسخ REST و بازگرداندن فیلد `data` در صورت موفقیت
+async fn fetch_data(url: &str) -> Result<Value, AdapterError> {
+    let mut doc: Value =
+        limiter::http_parse_with_limiter(url, &BITGET_LIMITER, 1, None, None).await?;
+
+    if doc["code"].as_str() != Some("00000") {
+        return Err(AdapterError::InvalidRequest(format!(
+            "Bitget error {}: {}",
+            doc["code"], doc["msg"]
+        )));
+    }
+
+    Ok(doc["data"].take())
+}
+
+/// دریافت اطلاعات نمادها (گام قیمت و ...) از بیت‌گت
+pub async fn fetch_ticksize(
+    market_type: MarketKind,
+) -> Result<HashMap<Ticker, Option<TickerInfo>>, AdapterError> {
+    let exchange = exchange_from_market_type(market_type);
+
+    let url = match market_type {
+        MarketKind::Spot => format!(
+            "{}/api/v2/spot/public/symbols",
+            Endpoint::BitgetRest.resolve()
+        ),
+        MarketKind::LinearPerps | MarketKind::InversePerps => format!(
+            "{}/api/v2/mix/market/contracts?productType={PRODUCT_TYPE}",
+            Endpoint::BitgetRest.resolve()
+        ),
+    };
+
+    let data = fetch_data(&url).await?;
+
+    let list = data
+        .as_array()
+        .ok_or_else(|| AdapterError::ParseError("Result list is not an array".to_string()))?;
+
+    let mut map = HashMap::new();
+
+    for item in list {
+        let parsed = match market_type {
+            MarketKind::Spot => {
+                if item["quoteCoin"].as_str() != Some("USDT")
+                    || item["status"].as_str() != Some("online")
+                {
+                    continue;
+                }
+
+                let min_ticksize =
+                    value_to_u64(&item["pricePrecision"]).map(|p| 10f32.powi(-(p as i32)));
+                let min_qty =
+                    value_to_u64(&item["quantityPrecision"]).map(|p| 10f32.powi(-(p as i32)));
+
+                (min_ticksize, min_qty)
+            }
+            MarketKind::LinearPerps | MarketKind::InversePerps => {
+                if item["symbolStatus"].as_str() != Some("normal") {
+                    continue;
+                }
+
+                // گام قیمت = priceEndStep × 10^-pricePlace
+                let min_ticksize = value_to_u64(&item["pricePlace"]).map(|p| {
+                    let end_step = value_to_f32(&item["priceEndStep"]).unwrap_or(1.0);
+                    end_step * 10f32.powi(-(p as i32))
+                });
+                let min_qty = value_to_f32(&item["minTradeNum"]);
+
+                (min_ticksize, min_qty)
+            }
+        };
+
+        let Some(symbol) = item["symbol"].as_str() else {
+            continue;
+        };
+
+        if !is_symbol_supported(symbol, exchange, true) {
+            continue;
+        }
+
+        let (min_ticksize, min_qty) = parsed;
+
+        let min_ticksize = min_ticksize
+            .ok_or_else(|| AdapterError::ParseError("Tick size not found".to_string()))?;
+        let min_qty =
+            min_qty.ok_or_else(|| AdapterError::ParseError("Min qty not found".to_string()))?;
+
+        let ticker = ticker_from_symbol(symbol, exchange);
+        // مقادیر فیوچرز USDT-M به ارز پایه گزارش می‌شوند، پس اندازه قرارداد لازم نیست
+        let info = TickerInfo::new(ticker, min_ticksize, min_qty, None);
+
+        map.insert(ticker, Some(info));
+    }
+
+    Ok(map)
+}
+
+/// دریافت قیمت‌های فعلی و آمار ۲۴ ساعته نمادها از بیت‌گت
+pub async fn fetch_ticker_prices(
+    market_type: MarketKind,
+) -> Result<HashMap<Ticker, TickerStats>, AdapterError> {
+    let exchange = exchange_from_market_type(market_type);
+
+    let url = match market_type {
+        MarketKind::Spot => format!(
+            "{}/api/v2/spot/market/tickers",
+            Endpoint::BitgetRest.resolve()
+        ),
+        MarketKind::LinearPerps | MarketKind::InversePerps => format!(
+            "{}/api/v2/mix/market/tickers?productType={PRODUCT_TYPE}",
+            Endpoint::BitgetRest.resolve()
+        ),
+    };
+
+    let data = fetch_data(&url).await?;
+
+    let list = data
+        .as_array()
+        .ok_or_else(|| AdapterError::ParseError("Result list is not an array".to_string()))?;
+
+    let mut map = HashMap::new();
+
+    for item in list {
+        let Some(symbol) = item["symbol"].as_str() else {
+            continue;
+        };
+
+        if !symbol.ends_with("USDT") || !is_symbol_supported(symbol, exchange, false) {
+            continue;
+        }
+
+        let (Some(last_price), Some(daily_price_chg), Some(daily_volume)) = (
+            value_to_f32(&item["lastPr"]),
+            value_to_f32(&item["change24h"]),
+            value_to_f32(&item["quoteVolume"]),
+        ) else {
+            continue;
+        };
+
+        map.insert(
+            ticker_from_symbol(symbol, exchange),
+            TickerStats {
+                last_price,
+                mark_price: value_to_f32(&item["markPrice"]).unwrap_or(last_price),
+                index_price: value_to_f32(&item["indexPrice"]).unwrap_or(last_price),
+                // تغییر ۲۴ ساعته به صورت نسبت (0.0123) گزارش می‌شود
+                daily_price_chg: daily_price_chg * 100.0,
+                daily_volume,
+            },
+        );
+    }
+
+    Ok(map)
+}
+
+/// دریافت داده‌های کندل (Kline) از طریق API بیت‌گت
+pub async fn fetch_klines(
+    ticker_info: TickerInfo,
+    timeframe: Timeframe,
+    range: Option<(u64, u64)>,
+) -> Result<Vec<Kline>, AdapterError> {
+    let (symbol_str, market) = api_symbol(&ticker_info.ticker);
+    let contract_size = ticker_info.contract_size.map(f32::from);
+
+    let granularity = timeframe_to_granularity(timeframe, market).ok_or_else(|| {
+        AdapterError::InvalidRequest(format!("Unsupported timeframe: {timeframe}"))
+    })?;
+
+    let mut url = match market {
+        MarketKind::Spot => format!(
+            "{}/api/v2/spot/market/candles?symbol={symbol_str}&granularity={granularity}",
+            Endpoint::BitgetRest.resolve()
+        ),
+        MarketKind::LinearPerps | MarketKind::InversePerps => format!(
+            "{}/api/v2/mix/market/candles?symbol={symbol_str}&productType={PRODUCT_TYPE}&granularity={granularity}",
+            Endpoint::BitgetRest.resolve()
+        ),
+    };
+
+    match range {
+        Some((start, end)) => {
+            // بیت‌گت حداکثر ۱۰۰۰ کندل در هر درخواست برمی‌گرداند
+            let max_span = timeframe.to_milliseconds() * 999;
+            let start = start.max(end.saturating_sub(max_span));
+            url.push_str(&format!("&startTime={start}&endTime={end}&limit=1000"));
+        }
+        None => url.push_str("&limit=400"),
+    }
+
+    let data = fetch_data(&url).await?;
+
+    let list = data
+        .as_array()
+        .ok_or_else(|| AdapterError::ParseError("Kline result is not an array".to_string()))?;
+
+    let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
+
+    let mut klines: Vec<Kline> = list
+        .iter()
+        .filter_map(|row| parse_kline_row(row, ticker_info, size_in_quote_ccy, contract_size))
+        .collect();
+
+    klines.sort_by_key(|k| k.time);
+    Ok(klines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_legacy_suffixes() {
+        assert_eq!(normalize_symbol("BTCUSDT_UMCBL"), "BTCUSDT");
+        assert_eq!(normalize_symbol("ETHUSDT_SPBL"), "ETHUSDT");
+        assert_eq!(normalize_symbol("SOLUSDT"), "SOLUSDT");
+
+        let ticker = ticker_from_symbol("BTCUSDT", Exchange::BitgetLinear);
+        assert_eq!(ticker.to_string(), "BTCUSDT_UMCBL");
+        assert_eq!(ticker.display_symbol(), Some("BTCUSDT"));
+    }
+
+    #[test]
+    fn checksum_interleaves_top_levels() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+
+        let level = |price: &str, size: &str| RawLevel {
+            price: price.to_string(),
+            size: size.to_string(),
+        };
+        let snapshot = SonicDepth {
+            is_snapshot: true,
+            seq: 1,
+            time: 0,
+            checksum: None,
+            bids: vec![level("99.5", "2"), level("100.0", "1.50")],
+            asks: vec![level("100.5", "3")],
+        };
+
+        let mut book = ChecksumBook::default();
+        book.apply(&snapshot);
+        assert_eq!(book.checksum(), crc32(b"100.0:1.50:100.5:3:99.5:2") as i32);
+    }
+}
@@ -1,16 +1,19 @@
 use super::{
     super::{
-        Exchange, Kline, MarketKind, OpenInterest, Price, PushFrequency, SizeUnit, StreamKind,
-        Ticker, TickerInfo, TickerStats, Timeframe, Trade,
-        adapter::StreamTicksize,
+        Exchange, FundingRate, Kline, MarketKind, OpenInterest, Price, PushFrequency, SizeUnit,
+        StreamKind, Ticker, TickerInfo, TickerStats, Timeframe, Trade,
+        adapter::{KlinePriceSource, StreamTicksize},
+        archive,
         connect::{State, connect_ws},
         de_string_to_f32, de_string_to_u64,
         depth::{DeOrder, DepthPayload, DepthUpdate, LocalDepthCache},
+        fetcher::TradeSource,
         is_symbol_supported,
         limiter::{self, http_request_with_limiter},
+        network::Endpoint,
         volume_size_unit,
     },
-    AdapterError, Event,
+    AdapterError, Event, ExchangeInclusive,
 };
 
 use fastwebsockets::{Frame, OpCode};
@@ -22,10 +25,7 @@ use serde_json::{Value, json};
 use sonic_rs::{Deserialize, JsonValueTrait, to_object_iter_unchecked};
 use tokio::sync::Mutex;
 
-use std::{collections::HashMap, sync::LazyLock, time::Duration};
-
-const WS_DOMAIN: &str = "stream.bybit.com";
-const FETCH_DOMAIN: &str = "https://api.bybit.com";
+use std::{collections::HashMap, path::PathBuf, sync::LazyLock, time::Duration};
 
 static BYBIT_LIMITER: LazyLock<Mutex<BybitLimiter>> =
     LazyLock::new(|| Mutex::new(BybitLimiter::new(LIMIT, REFILL_RATE)));
@@ -260,9 +260,10 @@ async fn try_connect(
         MarketKind::LinearPerps => Exchange::BybitLinear,
         MarketKind::InversePerps => Exchange::BybitInverse,
     };
+    let domain = Endpoint::BybitWs.resolve();
     let url = format!(
         "wss://{}/v5/public/{}",
-        WS_DOMAIN,
+        domain,
         match market_type {
             MarketKind::Spot => "spot",
             MarketKind::LinearPerps => "linear",
@@ -270,7 +271,7 @@ async fn try_connect(
         }
     );
 
-    match connect_ws(WS_DOMAIN, &url).await {
+    match connect_ws(&domain, &url, ExchangeInclusive::Bybit).await {
         Ok(mut websocket) => {
             if let Err(e) = websocket
                 .write_frame(Frame::text(fastwebsockets::Payload::Borrowed(
@@ -427,7 +428,7 @@ pub fn connect_market_stream(
                                                         push_freq,
                                                     },
                                                     time,
-                                                    orderbook.depth.clone(),
+                                                    orderbook.publish(),
                                                     std::mem::take(&mut trades_buffer)
                                                         .into_boxed_slice(),
                                                 ))
@@ -543,6 +544,7 @@ pub fn connect_kline_stream(
                                                     StreamKind::Kline {
                                                         ticker_info,
                                                         timeframe,
+                                                        source: KlinePriceSource::Trade,
                                                     },
                                                     kline,
                                                 ))
@@ -634,7 +636,8 @@ pub async fn fetch_historical_oi(
     };
 
     let mut url = format!(
-        "{FETCH_DOMAIN}/v5/market/open-interest?category=linear&symbol={ticker_str}&intervalTime={period_str}",
+        "{}/v5/market/open-interest?category=linear&symbol={ticker_str}&intervalTime={period_str}",
+        Endpoint::BybitRest.resolve(),
     );
 
     if let Some((start, end)) = range {
@@ -694,6 +697,81 @@ pub async fn fetch_historical_oi(
     Ok(open_interest)
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeFundingRate {
+    #[serde(deserialize_with = "de_string_to_f32")]
+    pub funding_rate: f32,
+    #[serde(deserialize_with = "de_string_to_u64")]
+    pub funding_rate_timestamp: u64,
+}
+
+/// دریافت تاریخچه نرخ تأمین مالی قراردادهای دائمی از بای‌بیت
+pub async fn fetch_funding_history(
+    ticker: Ticker,
+    range: Option<(u64, u64)>,
+) -> Result<Vec<FundingRate>, AdapterError> {
+    let (ticker_str, market) = ticker.to_full_symbol_and_type();
+
+    let category = match market {
+        MarketKind::LinearPerps => "linear",
+        MarketKind::InversePerps => "inverse",
+        MarketKind::Spot => {
+            return Err(AdapterError::InvalidRequest(
+                "Funding rates are only available for perpetuals".to_string(),
+            ));
+        }
+    };
+
+    let mut url = format!(
+        "{}/v5/market/funding/history?category={category}&symbol={}&limit=200",
+        Endpoint::BybitRest.resolve(),
+        ticker_str.to_uppercase(),
+    );
+
+    if let Some((start, end)) = range {
+        url.push_str(&format!("&startTime={start}&endTime={end}"));
+    }
+
+    let response_text = http_request_with_limiter(&url, &BYBIT_LIMITER, 1, None, None).await?;
+
+    let content: Value = sonic_rs::from_str(&response_text).map_err(|e| {
+        log::error!(
+            "Failed to parse JSON from {}: {}\nResponse: {}",
+            url,
+            e,
+            response_text
+        );
+        AdapterError::ParseError(e.to_string())
+    })?;
+
+    let result_list = content["result"]["list"].as_array().ok_or_else(|| {
+        log::error!("Result list is not an array in response: {}", response_text);
+        AdapterError::ParseError("Result list is not an array".to_string())
+    })?;
+
+    let rates: Vec<DeFundingRate> = serde_json::from_value(json!(result_list)).map_err(|e| {
+        log::error!(
+            "Failed to parse funding rate array: {}\nResponse: {}",
+            e,
+            response_text
+        );
+        AdapterError::ParseError(format!("Failed to parse funding rates: {e}"))
+    })?;
+
+    // بای‌بیت جدیدترین نرخ را اول برمی‌گرداند
+    let mut funding: Vec<FundingRate> = rates
+        .into_iter()
+        .map(|x| FundingRate {
+            time: x.funding_rate_timestamp,
+            rate: x.funding_rate,
+        })
+        .collect();
+    funding.sort_by_key(|x| x.time);
+
+    Ok(funding)
+}
+
 #[allow(dead_code)]
 #[derive(Deserialize, Debug)]
 struct ApiResponse {
@@ -722,9 +800,12 @@ fn parse_kline_field<T: std::str::FromStr>(field: Option<&str>) -> Result<T, Ada
 }
 
 /// دریافت داده‌های کندل (Kline) از طریق API بای‌بیت
+///
+/// کندل‌های قیمت نشان‌گذاری و شاخص فقط قیمت دارند و حجم آن‌ها صفر در نظر گرفته می‌شود.
 pub async fn fetch_klines(
     ticker_info: TickerInfo,
     timeframe: Timeframe,
+    source: KlinePriceSource,
     range: Option<(u64, u64)>,
 ) -> Result<Vec<Kline>, AdapterError> {
     let ticker = ticker_info.ticker;
@@ -744,8 +825,15 @@ pub async fn fetch_klines(
         MarketKind::InversePerps => "inverse",
     };
 
+    let path = match source {
+        KlinePriceSource::Trade => "kline",
+        KlinePriceSource::Mark => "mark-price-kline",
+        KlinePriceSource::Index => "index-price-kline",
+    };
+
     let mut url = format!(
-        "{FETCH_DOMAIN}/v5/market/kline?category={}&symbol={}&interval={}",
+        "{}/v5/market/{path}?category={}&symbol={}&interval={}",
+        Endpoint::BybitRest.resolve(),
         market,
         symbol_str.to_uppercase(),
         timeframe_str
@@ -776,7 +864,10 @@ pub async fn fetch_klines(
             let low = parse_kline_field::<f32>(kline[3].as_str())?;
             let close = parse_kline_field::<f32>(kline[4].as_str())?;
 
-            let mut volume = parse_kline_field::<f32>(kline[5].as_str())?;
+            let mut volume = match kline.get(5) {
+                Some(field) => parse_kline_field::<f32>(field.as_str())?,
+                None => 0.0,
+            };
             volume = if size_in_quote_ccy {
                 (volume * close).round()
             } else {
@@ -812,7 +903,10 @@ pub async fn fetch_ticksize(
         MarketKind::InversePerps => "inverse",
     };
 
-    let url = format!("{FETCH_DOMAIN}/v5/market/instruments-info?category={market}&limit=1000",);
+    let url = format!(
+        "{}/v5/market/instruments-info?category={market}&limit=1000",
+        Endpoint::BybitRest.resolve(),
+    );
 
     let response_text = crate::limiter::HTTP_CLIENT
         .get(&url)
@@ -896,7 +990,10 @@ pub async fn fetch_ticker_prices(
         MarketKind::InversePerps => "inverse",
     };
 
-    let url = format!("{FETCH_DOMAIN}/v5/market/tickers?category={market}");
+    let url = format!(
+        "{}/v5/market/tickers?category={market}",
+        Endpoint::BybitRest.resolve()
+    );
 
     let parsed_response: Value =
         limiter::http_parse_with_limiter(&url, &BYBIT_LIMITER, 1, None, None).await?;
@@ -916,11 +1013,26 @@ pub async fn fetch_ticker_prices(
             continue;
         }
 
-        let mark_price = item["lastPrice"]
+        let last_price = item["lastPrice"]
             .as_str()
-            .ok_or_else(|| AdapterError::ParseError("Mark price not found".to_string()))?
+            .ok_or_else(|| AdapterError::ParseError("Last price not found".to_string()))?
             .parse::<f32>()
-            .map_err(|_| AdapterError::ParseError("Failed to parse mark price".to_string()))?;
+            .map_err(|_| AdapterError::ParseError("Failed to parse last price".to_string()))?;
+
+        // پاسخ بازار اسپات قیمت مارک و شاخص ندارد؛ در این حالت آخرین قیمت استفاده می‌شود
+        let optional_price = |key: &str| {
+            item[key]
+                .as_str()
+                .and_then(|s| s.parse::<f32>().ok())
+                .filter(|price| *price > 0.0)
+                .unwrap_or(last_price)
+        };
+        let (mark_price, index_price) = match market_type {
+            MarketKind::Spot => (last_price, last_price),
+            MarketKind::LinearPerps | MarketKind::InversePerps => {
+                (optional_price("markPrice"), optional_price("indexPrice"))
+            }
+        };
 
         let daily_price_chg = item["price24hPcnt"]
             .as_str()
@@ -939,11 +1051,13 @@ pub async fn fetch_ticker_prices(
         let volume_in_usd = if market_type == MarketKind::InversePerps {
             daily_volume
         } else {
-            daily_volume * mark_price
+            daily_volume * last_price
         };
 
         let ticker_stats = TickerStats {
+            last_price,
             mark_price,
+            index_price,
             daily_price_chg: daily_price_chg * 100.0,
             daily_volume: volume_in_usd,
         };
@@ -953,3 +1067,92 @@ pub async fn fetch_ticker_prices(
 
     Ok(ticker_prices_map)
 }
+
+/// دریافت معاملات بای‌بیت از فایل‌های روزانه public.bybit.com
+///
+/// بای‌بیت API مناسبی برای دریافت معاملات گذشته بر اساس زمان ندارد، پس معاملات امروز
+/// فقط از جریان زنده دریافت می‌شوند.
+pub async fn fetch_trades(
+    ticker_info: TickerInfo,
+    from_time: u64,
+    data_path: PathBuf,
+    source: TradeSource,
+) -> Result<Vec<Trade>, AdapterError> {
+    let archive = Archive {
+        ticker_info,
+        size_in_quote_ccy: volume_size_unit() == SizeUnit::Quote
+            && ticker_info.market_type() != MarketKind::InversePerps,
+    };
+
+    archive::fetch_trades(&archive, from_time, &data_path, source).await
+}
+
+/// فایل‌های روزانه معاملات در public.bybit.com
+struct Archive {
+    ticker_info: TickerInfo,
+    size_in_quote_ccy: bool,
+}
+
+impl archive::TradeHistory for Archive {
+    type Archive = Self;
+
+    fn archive(&self) -> Option<&Self> {
+        Some(self)
+    }
+}
+
+impl archive::TradeArchive for Archive {
+    fn base_url(&self) -> &'static str {
+        "https://public.bybit.com"
+    }
+
+    fn file_path(&self, date: chrono::NaiveDate) -> String {
+        let (symbol, market_type) = self.ticker_info.ticker.to_full_symbol_and_type();
+        let date = date.format("%Y-%m-%d");
+
+        match market_type {
+            MarketKind::Spot => format!("spot/{symbol}/{symbol}_{date}.csv.gz"),
+            MarketKind::LinearPerps | MarketKind::InversePerps => {
+                format!("trading/{symbol}/{symbol}{date}.csv.gz")
+            }
+        }
+    }
+
+    fn compression(&self) -> archive::Compression {
+        archive::Compression::Gzip
+    }
+
+    fn has_headers(&self) -> bool {
+        true
+    }
+
+    fn parse_record(&self, record: &csv::StringRecord) -> Option<Trade> {
+        // اسپات: id,timestamp(ms),price,volume,side
+        // قراردادها: timestamp(s),symbol,side,size,price,...
+        let (time, is_sell, qty, price) = match self.ticker_info.market_type() {
+            MarketKind::Spot => (
+                record.get(1)?.parse::<u64>().ok()?,
+                record.get(4)?.eq_ignore_ascii_case("sell"),
+                record.get(3)?.parse::<f32>().ok()?,
+                record.get(2)?.parse::<f32>().ok()?,
+            ),
+            MarketKind::LinearPerps | MarketKind::InversePerps => (
+                (record.get(0)?.parse::<f64>().ok()? * 1000.0).round() as u64,
+                record.get(2)?.eq_ignore_ascii_case("sell"),
+                record.get(3)?.parse::<f32>().ok()?,
+                record.get(4)?.parse::<f32>().ok()?,
+            ),
+        };
+
+        Some(Trade {
+            time,
+            is_sell,
+            price: Price::from_f32(price).round_to_min_tick(self.ticker_info.min_ticksize),
+            qty: if self.size_in_quote_ccy {
+                (qty * price).round()
+            } else {
+                qty
+            },
+        })
+    }
+}
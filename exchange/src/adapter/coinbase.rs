@@ -0,0 +1,677 @@
+use crate::{
+    Price, PushFrequency, SizeUnit,
+    adapter::{StreamKind, StreamTicksize},
+    limiter::{self, RateLimiter},
+    network::Endpoint,
+    volume_size_unit,
+};
+
+use super::{
+    super::{
+        Exchange, Kline, MarketKind, Ticker, TickerInfo, TickerStats, Timeframe, Trade,
+        connect::{State, connect_ws},
+        is_symbol_supported,
+    },
+    AdapterError, Event, ExchangeInclusive, KlinePriceSource,
+};
+
+use super::super::depth::{DeOrder, DepthPayload, DepthUpdate, LocalDepthCache};
+
+use fastwebsockets::{Frame, OpCode};
+use iced_futures::{
+    futures::{SinkExt, Stream, channel::mpsc},
+    stream,
+};
+use serde_json::Value;
+use std::{collections::HashMap, sync::LazyLock, time::Duration};
+use tokio::sync::Mutex;
+
+const LIMIT: usize = 10;
+
+const REFILL_RATE: Duration = Duration::from_secs(1);
+const LIMITER_BUFFER_PCT: f32 = 0.05;
+
+/// حداکثر تعداد کندل در هر درخواست
+const KLINE_LIMIT: u64 = 350;
+
+/// فاصله دریافت دوره‌ای کندل‌ها؛ کانال `candles` کوین‌بیس فقط بازه ۵ دقیقه را پشتیبانی می‌کند
+const KLINE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+static COINBASE_LIMITER: LazyLock<Mutex<CoinbaseLimiter>> =
+    LazyLock::new(|| Mutex::new(CoinbaseLimiter::new(LIMIT, REFILL_RATE)));
+
+/// محدودکننده نرخ اختصاصی برای کوین‌بیس (Coinbase)
+pub struct CoinbaseLimiter {
+    bucket: limiter::FixedWindowBucket,
+}
+
+impl CoinbaseLimiter {
+    pub fn new(limit: usize, refill_rate: Duration) -> Self {
+        let effective_limit = ((limit as f32 * (1.0 - LIMITER_BUFFER_PCT)) as usize).max(1);
+        Self {
+            bucket: limiter::FixedWindowBucket::new(effective_limit, refill_rate),
+        }
+    }
+}
+
+impl RateLimiter for CoinbaseLimiter {
+    fn prepare_request(&mut self, weight: usize) -> Option<Duration> {
+        self.bucket.calculate_wait_time(weight)
+    }
+
+    fn update_from_response(&mut self, _response: &reqwest::Response, weight: usize) {
+        self.bucket.consume_tokens(weight);
+    }
+
+    fn should_exit_on_response(&self, response: &reqwest::Response) -> bool {
+        response.status() == 429
+    }
+}
+
+/// معامله دریافتی از کانال `market_trades`
+struct SonicTrade {
+    pub time: u64,     // زمان معامله (میلی‌ثانیه)
+    pub price: f32,    // قیمت
+    pub qty: f32,      // مقدار به ارز پایه
+    pub is_sell: bool, // سمت معامله
+}
+
+/// رویداد کانال `level2`؛ اولین رویداد تصویر کامل و بقیه تغییرات هستند
+struct SonicDepth {
+    pub is_snapshot: bool,  // آیا تصویر کامل دفتر سفارش است
+    pub time: u64,          // زمان بروزرسانی
+    pub bids: Vec<DeOrder>, // لیست خرید
+    pub asks: Vec<DeOrder>, // لیست فروش
+}
+
+enum StreamData {
+    Trade(Vec<SonicTrade>),
+    Depth(SonicDepth),
+    Heartbeat,
+}
+
+/// پیام دریافتی همراه با شماره ترتیبی اتصال (`sequence_num`)
+struct SonicMessage {
+    pub sequence: u64,
+    pub data: StreamData,
+}
+
+fn value_to_f32(v: &Value) -> Option<f32> {
+    match v {
+        Value::String(s) => s.parse::<f32>().ok(),
+        Value::Number(n) => n.as_f64().map(|x| x as f32),
+        _ => None,
+    }
+}
+
+fn value_to_u64(v: &Value) -> Option<u64> {
+    match v {
+        Value::String(s) => s.parse::<u64>().ok(),
+        Value::Number(n) => n.as_u64(),
+        _ => None,
+    }
+}
+
+/// تبدیل زمان RFC3339 کوین‌بیس به میلی‌ثانیه یونیکس
+fn rfc3339_to_ms(v: &Value) -> Option<u64> {
+    let s = v.as_str()?;
+    chrono::DateTime::parse_from_rfc3339(s)
+        .ok()
+        .map(|dt| dt.timestamp_millis() as u64)
+}
+
+fn parse_trade(v: &Value) -> Option<SonicTrade> {
+    Some(SonicTrade {
+        time: rfc3339_to_ms(v.get("time")?)?,
+        price: value_to_f32(v.get("price")?)?,
+        qty: value_to_f32(v.get("size")?)?,
+        is_sell: v.get("side").and_then(|s| s.as_str()) == Some("SELL"),
+    })
+}
+
+fn parse_depth_event(event: &Value, time: u64) -> SonicDepth {
+    let mut bids = Vec::new();
+    let mut asks = Vec::new();
+
+    if let Some(updates) = event.get("updates").and_then(|u| u.as_array()) {
+        for update in updates {
+            let (Some(price), Some(qty)) = (
+                update.get("price_level").and_then(value_to_f32),
+                update.get("new_quantity").and_then(value_to_f32),
+            ) else {
+                continue;
+            };
+
+            match update.get("side").and_then(|s| s.as_str()) {
+                Some("bid") => bids.push(DeOrder { price, qty }),
+                Some("offer" | "ask") => asks.push(DeOrder { price, qty }),
+                _ => {}
+            }
+        }
+    }
+
+    SonicDepth {
+        is_snapshot: event.get("type").and_then(|t| t.as_str()) == Some("snapshot"),
+        time,
+        bids,
+        asks,
+    }
+}
+
+fn feed_de(slice: &[u8]) -> Result<SonicMessage, AdapterError> {
+    let v: Value =
+        serde_json::from_slice(slice).map_err(|e| AdapterError::ParseError(e.to_string()))?;
+
+    if v.get("type").and_then(|t| t.as_str()) == Some("error") {
+        return Err(AdapterError::WebsocketError(
+            v.get("message")
+                .and_then(|m| m.as_str())
+                .unwrap_or("Unknown error")
+                .to_string(),
+        ));
+    }
+
+    let channel = v.get("channel").and_then(|c| c.as_str()).unwrap_or("");
+    let sequence = v.get("sequence_num").and_then(value_to_u64).unwrap_or(0);
+    let time = v.get("timestamp").and_then(rfc3339_to_ms).unwrap_or(0);
+
+    let events = v
+        .get("events")
+        .and_then(|e| e.as_array())
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    let data = match channel {
+        "l2_data" => {
+            let Some(event) = events.first() else {
+                return Err(AdapterError::ParseError("Empty depth event".to_string()));
+            };
+            StreamData::Depth(parse_depth_event(event, time))
+        }
+        "market_trades" => {
+            // رویداد snapshot شامل معاملات قبلی است و در جریان زنده استفاده نمی‌شود
+            let trades = events
+                .iter()
+                .filter(|e| e.get("type").and_then(|t| t.as_str()) == Some("update"))
+                .filter_map(|e| e.get("trades").and_then(|t| t.as_array()))
+                .flatten()
+                .filter_map(parse_trade)
+                .collect();
+            StreamData::Trade(trades)
+        }
+        "heartbeats" | "subscriptions" => StreamData::Heartbeat,
+        _ => {
+            return Err(AdapterError::ParseError(format!(
+                "Unknown channel: {channel}"
+            )));
+        }
+    };
+
+    Ok(SonicMessage { sequence, data })
+}
+
+async fn try_connect(
+    subscriptions: &[Value],
+    exchange: Exchange,
+    output: &mut mpsc::Sender<Event>,
+) -> State {
+    let domain = Endpoint::CoinbaseWs.resolve();
+    let url = format!("wss://{domain}");
+
+    match connect_ws(&domain, &url, ExchangeInclusive::Coinbase).await {
+        Ok(mut websocket) => {
+            for sub in subscriptions {
+                if let Err(e) = websocket
+                    .write_frame(Frame::text(fastwebsockets::Payload::Borrowed(
+                        sub.to_string().as_bytes(),
+                    )))
+                    .await
+                {
+                    let _ = output
+                        .send(Event::Disconnected(
+                            exchange,
+                            format!("Failed subscribing: {e}"),
+                        ))
+                        .await;
+                    return State::Disconnected;
+                }
+            }
+
+            let _ = output.send(Event::Connected(exchange)).await;
+            State::Connected(websocket)
+        }
+        Err(err) => {
+            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+            let _ = output
+                .send(Event::Disconnected(
+                    exchange,
+                    format!("Failed to connect: {err}"),
+                ))
+                .await;
+            State::Disconnected
+        }
+    }
+}
+
+fn subscribe_msg(channel: &str, product_id: &str) -> Value {
+    serde_json::json!({
+        "type": "subscribe",
+        "product_ids": [product_id],
+        "channel": channel,
+    })
+}
+
+/// برقراری اتصال به جریان داده‌های بازار (عمق و معاملات) کوین‌بیس
+pub fn connect_market_stream(
+    ticker_info: TickerInfo,
+    push_freq: PushFrequency,
+) -> impl Stream<Item = Event> {
+    stream::channel(100, async move |mut output| {
+        let mut state: State = State::Disconnected;
+
+        let exchange = Exchange::CoinbaseSpot;
+        let (symbol_str, _) = ticker_info.ticker.to_full_symbol_and_type();
+
+        // کانال heartbeats از بسته شدن اتصال در زمان کم بودن بروزرسانی‌ها جلوگیری می‌کند
+        let subscriptions = [
+            subscribe_msg("heartbeats", &symbol_str),
+            subscribe_msg("level2", &symbol_str),
+            subscribe_msg("market_trades", &symbol_str),
+        ];
+
+        let mut trades_buffer: Vec<Trade> = vec![];
+        let mut orderbook = LocalDepthCache::default();
+        let mut prev_seq: Option<u64> = None;
+
+        let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
+
+        loop {
+            match &mut state {
+                State::Disconnected => {
+                    state = try_connect(&subscriptions, exchange, &mut output).await;
+
+                    if matches!(state, State::Connected(_)) {
+                        orderbook = LocalDepthCache::default();
+                        trades_buffer.clear();
+                        prev_seq = None;
+                    }
+                }
+                State::Connected(ws) => match ws.read_frame().await {
+                    Ok(msg) => match msg.opcode {
+                        OpCode::Text => match feed_de(&msg.payload[..]) {
+                            Ok(SonicMessage { sequence, data }) => {
+                                // شماره ترتیبی برای هر اتصال پیوسته است؛ شکاف یعنی پیام از دست رفته
+                                if let Some(prev) = prev_seq
+                                    && sequence != prev + 1
+                                {
+                                    state = State::Disconnected;
+                                    let _ = output
+                                        .send(Event::Disconnected(
+                                            exchange,
+                                            format!(
+                                                "Out of sync. Expected sequence: {}, got: {}",
+                                                prev + 1,
+                                                sequence
+                                            ),
+                                        ))
+                                        .await;
+                                    continue;
+                                }
+                                prev_seq = Some(sequence);
+
+                                match data {
+                                    StreamData::Trade(de_trades) => {
+                                        for de_trade in &de_trades {
+                                            let price = Price::from_f32(de_trade.price)
+                                                .round_to_min_tick(ticker_info.min_ticksize);
+
+                                            trades_buffer.push(Trade {
+                                                time: de_trade.time,
+                                                is_sell: de_trade.is_sell,
+                                                price,
+                                                qty: calc_qty(
+                                                    de_trade.qty,
+                                                    de_trade.price,
+                                                    size_in_quote_ccy,
+                                                ),
+                                            });
+                                        }
+                                    }
+                                    StreamData::Depth(de_depth) => {
+                                        let depth =
+                                            new_depth_cache(&de_depth, sequence, size_in_quote_ccy);
+
+                                        if de_depth.is_snapshot {
+                                            orderbook.update(
+                                                DepthUpdate::Snapshot(depth),
+                                                ticker_info.min_ticksize,
+                                            );
+                                        } else if orderbook.last_update_id != 0 {
+                                            orderbook.update(
+                                                DepthUpdate::Diff(depth),
+                                                ticker_info.min_ticksize,
+                                            );
+
+                                            let _ = output
+                                                .send(Event::DepthReceived(
+                                                    StreamKind::DepthAndTrades {
+                                                        ticker_info,
+                                                        depth_aggr: StreamTicksize::Client,
+                                                        push_freq,
+                                                    },
+                                                    de_depth.time,
+                                                    orderbook.publish(),
+                                                    std::mem::take(&mut trades_buffer)
+                                                        .into_boxed_slice(),
+                                                ))
+                                                .await;
+                                        }
+                                    }
+                                    StreamData::Heartbeat => {}
+                                }
+                            }
+                            Err(AdapterError::WebsocketError(e)) => {
+                                log::error!("Coinbase stream error: {e}");
+                            }
+                            Err(_) => {}
+                        },
+                        OpCode::Close => {
+                            state = State::Disconnected;
+                            let _ = output
+                                .send(Event::Disconnected(
+                                    exchange,
+                                    "Connection closed".to_string(),
+                                ))
+                                .await;
+                        }
+                        _ => {}
+                    },
+                    Err(e) => {
+                        state = State::Disconnected;
+                        let _ = output
+                            .send(Event::Disconnected(
+                                exchange,
+                                "Error reading frame: ".to_string() + &e.to_string(),
+                            ))
+                            .await;
+                    }
+                },
+            }
+        }
+    })
+}
+
+/// دریافت دوره‌ای آخرین کندل‌ها از API کوین‌بیس به جای جریان وب‌سوکت
+pub fn connect_kline_stream(
+    streams: Vec<(TickerInfo, Timeframe)>,
+    _market_type: MarketKind,
+) -> impl Stream<Item = Event> {
+    stream::channel(100, async move |mut output| {
+        let exchange = Exchange::CoinbaseSpot;
+        let mut connected = false;
+
+        loop {
+            let mut failure = None;
+
+            for (ticker_info, timeframe) in &streams {
+                let tf_ms = timeframe.to_milliseconds();
+                let now = chrono::Utc::now().timestamp_millis() as u64;
+                let range = (now.saturating_sub(tf_ms * 2), now);
+
+                match fetch_klines(*ticker_info, *timeframe, Some(range)).await {
+                    Ok(klines) => {
+                        for kline in klines {
+                            let _ = output
+                                .send(Event::KlineReceived(
+                                    StreamKind::Kline {
+                                        ticker_info: *ticker_info,
+                                        timeframe: *timeframe,
+                                        source: KlinePriceSource::Trade,
+                                    },
+                                    kline,
+                                ))
+                                .await;
+                        }
+                    }
+                    Err(AdapterError::InvalidRequest(_)) => {}
+                    Err(e) => {
+                        failure = Some(e);
+                        break;
+                    }
+                }
+            }
+
+            match failure {
+                None if !connected => {
+                    connected = true;
+                    let _ = output.send(Event::Connected(exchange)).await;
+                }
+                Some(e) if connected => {
+                    connected = false;
+                    let _ = output
+                        .send(Event::Disconnected(
+                            exchange,
+                            format!("Kline fetch failed: {e}"),
+                        ))
+                        .await;
+                }
+                _ => {}
+            }
+
+            tokio::time::sleep(KLINE_POLL_INTERVAL).await;
+        }
+    })
+}
+
+fn new_depth_cache(depth: &SonicDepth, sequence: u64, size_in_quote_ccy: bool) -> DepthPayload {
+    let convert = |orders: &[DeOrder]| {
+        orders
+            .iter()
+            .map(|x| DeOrder {
+                price: x.price,
+                qty: calc_qty(x.qty, x.price, size_in_quote_ccy),
+            })
+            .collect()
+    };
+
+    DepthPayload {
+        last_update_id: sequence.max(1),
+        time: depth.time,
+        bids: convert(&depth.bids),
+        asks: convert(&depth.asks),
+    }
+}
+
+fn calc_qty(qty: f32, price: f32, size_in_quote_ccy: bool) -> f32 {
+    if size_in_quote_ccy { qty * price } else { qty }
+}
+
+fn timeframe_to_granularity(tf: Timeframe) -> Option<&'static str> {
+    Some(match tf {
+        Timeframe::M1 => "ONE_MINUTE",
+        Timeframe::M5 => "FIVE_MINUTE",
+        Timeframe::M15 => "FIFTEEN_MINUTE",
+        Timeframe::M30 => "THIRTY_MINUTE",
+        Timeframe::H1 => "ONE_HOUR",
+        Timeframe::H2 => "TWO_HOUR",
+        Timeframe::D1 => "ONE_DAY",
+        _ => return None,
+    })
+}
+
+/// دریافت لیست محصولات اسپات با قیمت و آمار ۲۴ ساعته
+async fn fetch_products() -> Result<Vec<Value>, AdapterError> {
+    let url = format!(
+        "{}/api/v3/brokerage/market/products?product_type=SPOT",
+        Endpoint::CoinbaseRest.resolve()
+    );
+
+    let doc: Value =
+        limiter::http_parse_with_limiter(&url, &COINBASE_LIMITER, 1, None, None).await?;
+
+    doc.get("products")
+        .and_then(|p| p.as_array())
+        .cloned()
+        .ok_or_else(|| AdapterError::ParseError("Products list not found".to_string()))
+}
+
+/// محصولات قابل معامله با ارز مظنه دلار
+fn is_tradable_usd_product(item: &Value) -> bool {
+    item["quote_currency_id"].as_str() == Some("USD")
+        && item["status"].as_str() == Some("online")
+        && item["trading_disabled"].as_bool() != Some(true)
+        && item["is_disabled"].as_bool() != Some(true)
+}
+
+/// دریافت اطلاعات نمادها (گام قیمت و ...) از کوین‌بیس
+pub async fn fetch_ticksize(
+    _market_type: MarketKind,
+) -> Result<HashMap<Ticker, Option<TickerInfo>>, AdapterError> {
+    let exchange = Exchange::CoinbaseSpot;
+
+    let mut map = HashMap::new();
+
+    for item in fetch_products().await? {
+        if !is_tradable_usd_product(&item) {
+            continue;
+        }
+
+        let Some(symbol) = item["product_id"].as_str() else {
+            continue;
+        };
+
+        if !is_symbol_supported(symbol, exchange, true) {
+            continue;
+        }
+
+        let min_ticksize = value_to_f32(&item["quote_increment"])
+            .ok_or_else(|| AdapterError::ParseError("Tick size not found".to_string()))?;
+        let min_qty = value_to_f32(&item["base_min_size"])
+            .or_else(|| value_to_f32(&item["base_increment"]))
+            .ok_or_else(|| AdapterError::ParseError("Min qty not found".to_string()))?;
+
+        let ticker = Ticker::new(symbol, exchange);
+        let info = TickerInfo::new(ticker, min_ticksize, min_qty, None);
+
+        map.insert(ticker, Some(info));
+    }
+
+    Ok(map)
+}
+
+/// دریافت قیمت‌های فعلی و آمار ۲۴ ساعته نمادها از کوین‌بیس
+pub async fn fetch_ticker_prices(
+    _market_type: MarketKind,
+) -> Result<HashMap<Ticker, TickerStats>, AdapterError> {
+    let exchange = Exchange::CoinbaseSpot;
+
+    let mut map = HashMap::new();
+
+    for item in fetch_products().await? {
+        if !is_tradable_usd_product(&item) {
+            continue;
+        }
+
+        let Some(symbol) = item["product_id"].as_str() else {
+            continue;
+        };
+
+        if !is_symbol_supported(symbol, exchange, false) {
+            continue;
+        }
+
+        let (Some(last_price), Some(daily_price_chg)) = (
+            value_to_f32(&item["price"]),
+            value_to_f32(&item["price_percentage_change_24h"]),
+        ) else {
+            continue;
+        };
+
+        let daily_volume = value_to_f32(&item["approximate_quote_24h_volume"])
+            .or_else(|| value_to_f32(&item["volume_24h"]).map(|v| v * last_price))
+            .unwrap_or(0.0);
+
+        map.insert(
+            Ticker::new(symbol, exchange),
+            TickerStats {
+                last_price,
+                mark_price: last_price,
+                index_price: last_price,
+                daily_price_chg,
+                daily_volume,
+            },
+        );
+    }
+
+    Ok(map)
+}
+
+/// دریافت داده‌های کندل (Kline) از طریق API کوین‌بیس
+pub async fn fetch_klines(
+    ticker_info: TickerInfo,
+    timeframe: Timeframe,
+    range: Option<(u64, u64)>,
+) -> Result<Vec<Kline>, AdapterError> {
+    let (symbol_str, _) = ticker_info.ticker.to_full_symbol_and_type();
+
+    let granularity = timeframe_to_granularity(timeframe).ok_or_else(|| {
+        AdapterError::InvalidRequest(format!("Unsupported timeframe: {timeframe}"))
+    })?;
+
+    // کوین‌بیس حداکثر ۳۵۰ کندل در هر درخواست برمی‌گرداند و بازه زمانی اجباری است
+    let max_span = timeframe.to_milliseconds() * (KLINE_LIMIT - 1);
+    let (start, end) = match range {
+        Some((start, end)) => (start.max(end.saturating_sub(max_span)), end),
+        None => {
+            let now = chrono::Utc::now().timestamp_millis() as u64;
+            (now.saturating_sub(max_span), now)
+        }
+    };
+
+    let url = format!(
+        "{}/api/v3/brokerage/market/products/{symbol_str}/candles?start={}&end={}&granularity={granularity}&limit={KLINE_LIMIT}",
+        Endpoint::CoinbaseRest.resolve(),
+        start / 1000,
+        end / 1000
+    );
+
+    let doc: Value =
+        limiter::http_parse_with_limiter(&url, &COINBASE_LIMITER, 1, None, None).await?;
+
+    let list = doc
+        .get("candles")
+        .and_then(|c| c.as_array())
+        .ok_or_else(|| AdapterError::ParseError("Candles list not found".to_string()))?;
+
+    let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
+
+    let mut klines: Vec<Kline> = Vec::with_capacity(list.len());
+
+    for row in list {
+        let (Some(ts), Some(open), Some(high), Some(low), Some(close), volume) = (
+            value_to_u64(&row["start"]),
+            value_to_f32(&row["open"]),
+            value_to_f32(&row["high"]),
+            value_to_f32(&row["low"]),
+            value_to_f32(&row["close"]),
+            value_to_f32(&row["volume"]),
+        ) else {
+            continue;
+        };
+
+        let volume_in_display = volume.map_or(0.0, |qty| calc_qty(qty, close, size_in_quote_ccy));
+
+        klines.push(Kline::new(
+            ts * 1000,
+            open,
+            high,
+            low,
+            close,
+            (-1.0, volume_in_display),
+            ticker_info.min_ticksize,
+        ));
+    }
+
+    klines.sort_by_key(|k| k.time);
+    Ok(klines)
+}
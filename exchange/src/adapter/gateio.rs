@@ -0,0 +1,1059 @@
+use crate::{
+    Price, PushFrequency, SizeUnit,
+    adapter::{StreamKind, StreamTicksize},
+    limiter::{self, RateLimiter},
+    network::Endpoint,
+    volume_size_unit,
+};
+
+use super::{
+    super::{
+        Exchange, Kline, MarketKind, Ticker, TickerInfo, TickerStats, Timeframe, Trade,
+        connect::{State, connect_ws},
+        is_symbol_supported,
+    },
+    AdapterError, Event, ExchangeInclusive, KlinePriceSource,
+};
+
+use super::super::depth::{DeOrder, DepthPayload, DepthUpdate, LocalDepthCache};
+
+use fastwebsockets::{Frame, OpCode};
+use iced_futures::{
+    futures::{SinkExt, Stream, channel::mpsc},
+    stream,
+};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    sync::LazyLock,
+    time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
+
+const LIMIT: usize = 200;
+
+const REFILL_RATE: Duration = Duration::from_secs(10);
+const LIMITER_BUFFER_PCT: f32 = 0.05;
+
+/// فاصله ارسال پیام ping در سطح برنامه به سرور
+const PING_INTERVAL: Duration = Duration::from_secs(15);
+
+const DEPTH_LIMIT: usize = 100;
+
+static GATEIO_LIMITER: LazyLock<Mutex<GateioLimiter>> =
+    LazyLock::new(|| Mutex::new(GateioLimiter::new(LIMIT, REFILL_RATE)));
+
+/// محدودکننده نرخ اختصاصی برای گیت (Gate.io)
+pub struct GateioLimiter {
+    bucket: limiter::FixedWindowBucket,
+}
+
+impl GateioLimiter {
+    pub fn new(limit: usize, refill_rate: Duration) -> Self {
+        let effective_limit = (limit as f32 * (1.0 - LIMITER_BUFFER_PCT)) as usize;
+        Self {
+            bucket: limiter::FixedWindowBucket::new(effective_limit, refill_rate),
+        }
+    }
+}
+
+impl RateLimiter for GateioLimiter {
+    fn prepare_request(&mut self, weight: usize) -> Option<Duration> {
+        self.bucket.calculate_wait_time(weight)
+    }
+
+    fn update_from_response(&mut self, _response: &reqwest::Response, weight: usize) {
+        self.bucket.consume_tokens(weight);
+    }
+
+    fn should_exit_on_response(&self, response: &reqwest::Response) -> bool {
+        response.status() == 429
+    }
+}
+
+fn exchange_from_market_type(market: MarketKind) -> Exchange {
+    match market {
+        MarketKind::Spot => Exchange::GateioSpot,
+        MarketKind::LinearPerps | MarketKind::InversePerps => Exchange::GateioLinear,
+    }
+}
+
+/// پیشوند نام کانال‌های وب‌سوکت بر اساس نوع بازار
+fn channel_prefix(market: MarketKind) -> &'static str {
+    match market {
+        MarketKind::Spot => "spot",
+        MarketKind::LinearPerps | MarketKind::InversePerps => "futures",
+    }
+}
+
+fn ws_domain_and_url(market: MarketKind) -> (String, String) {
+    match market {
+        MarketKind::Spot => {
+            let domain = Endpoint::GateioSpotWs.resolve();
+            let url = format!("wss://{domain}/ws/v4/");
+            (domain, url)
+        }
+        MarketKind::LinearPerps | MarketKind::InversePerps => {
+            let domain = Endpoint::GateioFuturesWs.resolve();
+            let url = format!("wss://{domain}/v4/ws/usdt");
+            (domain, url)
+        }
+    }
+}
+
+fn unix_secs() -> u64 {
+    chrono::Utc::now().timestamp() as u64
+}
+
+/// ساخت پیام با پوشش استاندارد کانال/رویداد گیت
+fn envelope(channel: &str, event: Option<&str>, payload: Option<Value>) -> Value {
+    let mut msg = serde_json::json!({
+        "time": unix_secs(),
+        "channel": channel,
+    });
+    if let Some(event) = event {
+        msg["event"] = Value::from(event);
+    }
+    if let Some(payload) = payload {
+        msg["payload"] = payload;
+    }
+    msg
+}
+
+/// معامله دریافتی از جریان داده گیت
+struct SonicTrade {
+    pub time: u64,     // زمان معامله (میلی‌ثانیه)
+    pub price: f32,    // قیمت
+    pub qty: f32,      // مقدار (برای فیوچرز به تعداد قرارداد)
+    pub is_sell: bool, // سمت معامله
+}
+
+/// بروزرسانی عمق بازار گیت با شناسه‌های ترتیبی `U`/`u`
+struct SonicDepth {
+    pub first_id: u64,      // اولین شناسه بروزرسانی (U)
+    pub final_id: u64,      // آخرین شناسه بروزرسانی (u)
+    pub time: u64,          // زمان بروزرسانی
+    pub bids: Vec<DeOrder>, // لیست خرید
+    pub asks: Vec<DeOrder>, // لیست فروش
+}
+
+enum StreamData {
+    Trade(Vec<SonicTrade>),
+    Depth(SonicDepth),
+    Pong,
+}
+
+fn value_to_f32(v: &Value) -> Option<f32> {
+    match v {
+        Value::String(s) => s.parse::<f32>().ok(),
+        Value::Number(n) => n.as_f64().map(|x| x as f32),
+        _ => None,
+    }
+}
+
+fn value_to_u64(v: &Value) -> Option<u64> {
+    match v {
+        Value::String(s) => s
+            .parse::<u64>()
+            .ok()
+            .or_else(|| s.parse::<f64>().ok().map(|x| x as u64)),
+        Value::Number(n) => n.as_u64().or_else(|| n.as_f64().map(|x| x as u64)),
+        _ => None,
+    }
+}
+
+/// سطوح دفتر سفارش در اسپات به صورت آرایه `[price, amount]` و در فیوچرز به صورت `{p, s}` است
+fn parse_levels(v: Option<&Value>) -> Vec<DeOrder> {
+    let Some(arr) = v.and_then(|v| v.as_array()) else {
+        return Vec::new();
+    };
+
+    arr.iter()
+        .filter_map(|level| match level {
+            Value::Array(pair) => Some(DeOrder {
+                price: value_to_f32(pair.first()?)?,
+                qty: value_to_f32(pair.get(1)?)?,
+            }),
+            Value::Object(map) => Some(DeOrder {
+                price: value_to_f32(map.get("p")?)?,
+                qty: value_to_f32(map.get("s")?)?,
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+fn parse_trade(v: &Value, market: MarketKind) -> Option<SonicTrade> {
+    let price = value_to_f32(v.get("price")?)?;
+    let time = v.get("create_time_ms").and_then(value_to_u64).or_else(|| {
+        v.get("create_time")
+            .and_then(value_to_u64)
+            .map(|t| t * 1000)
+    })?;
+
+    match market {
+        MarketKind::Spot => Some(SonicTrade {
+            time,
+            price,
+            qty: value_to_f32(v.get("amount")?)?,
+            is_sell: v.get("side").and_then(|s| s.as_str()) == Some("sell"),
+        }),
+        MarketKind::LinearPerps | MarketKind::InversePerps => {
+            let size = value_to_f32(v.get("size")?)?;
+            Some(SonicTrade {
+                time,
+                price,
+                qty: size.abs(),
+                is_sell: size < 0.0,
+            })
+        }
+    }
+}
+
+fn feed_de(slice: &[u8], market: MarketKind) -> Result<StreamData, AdapterError> {
+    let v: Value =
+        serde_json::from_slice(slice).map_err(|e| AdapterError::ParseError(e.to_string()))?;
+
+    let channel = v.get("channel").and_then(|c| c.as_str()).unwrap_or("");
+    let event = v.get("event").and_then(|e| e.as_str()).unwrap_or("");
+
+    if channel.ends_with(".pong") {
+        return Ok(StreamData::Pong);
+    }
+
+    if let Some(err) = v.get("error")
+        && !err.is_null()
+    {
+        return Err(AdapterError::WebsocketError(err.to_string()));
+    }
+
+    if event != "update" {
+        return Err(AdapterError::ParseError(format!(
+            "Unhandled event: {event} on {channel}"
+        )));
+    }
+
+    let Some(result) = v.get("result") else {
+        return Err(AdapterError::ParseError("Missing result".to_string()));
+    };
+
+    if channel.ends_with(".trades") {
+        let trades = match result {
+            Value::Array(items) => items
+                .iter()
+                .filter_map(|t| parse_trade(t, market))
+                .collect(),
+            Value::Object(_) => parse_trade(result, market).into_iter().collect(),
+            _ => Vec::new(),
+        };
+        return Ok(StreamData::Trade(trades));
+    }
+
+    if channel.ends_with(".order_book_update") {
+        let first_id = result.get("U").and_then(value_to_u64);
+        let final_id = result.get("u").and_then(value_to_u64);
+
+        let (Some(first_id), Some(final_id)) = (first_id, final_id) else {
+            return Err(AdapterError::ParseError(
+                "Depth update without sequence ids".to_string(),
+            ));
+        };
+
+        return Ok(StreamData::Depth(SonicDepth {
+            first_id,
+            final_id,
+            time: result.get("t").and_then(value_to_u64).unwrap_or(0),
+            bids: parse_levels(result.get("b")),
+            asks: parse_levels(result.get("a")),
+        }));
+    }
+
+    Err(AdapterError::ParseError("Unknown data".to_string()))
+}
+
+async fn try_connect(
+    subscriptions: &[Value],
+    market: MarketKind,
+    exchange: Exchange,
+    output: &mut mpsc::Sender<Event>,
+) -> State {
+    let (domain, url) = ws_domain_and_url(market);
+
+    match connect_ws(&domain, &url, ExchangeInclusive::Gateio).await {
+        Ok(mut websocket) => {
+            for sub in subscriptions {
+                if let Err(e) = websocket
+                    .write_frame(Frame::text(fastwebsockets::Payload::Borrowed(
+                        sub.to_string().as_bytes(),
+                    )))
+                    .await
+                {
+                    let _ = output
+                        .send(Event::Disconnected(
+                            exchange,
+                            format!("Failed subscribing: {e}"),
+                        ))
+                        .await;
+                    return State::Disconnected;
+                }
+            }
+
+            let _ = output.send(Event::Connected(exchange)).await;
+            State::Connected(websocket)
+        }
+        Err(err) => {
+            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+            let _ = output
+                .send(Event::Disconnected(
+                    exchange,
+                    format!("Failed to connect: {err}"),
+                ))
+                .await;
+            State::Disconnected
+        }
+    }
+}
+
+/// ارسال پیام ping سطح برنامه در صورت گذشت بازه مشخص از آخرین ارسال
+async fn keep_alive(state: &mut State, market: MarketKind, last_ping: &mut Instant) {
+    if last_ping.elapsed() < PING_INTERVAL {
+        return;
+    }
+    *last_ping = Instant::now();
+
+    if let State::Connected(ws) = state {
+        let ping = envelope(&format!("{}.ping", channel_prefix(market)), None, None);
+
+        if let Err(e) = ws
+            .write_frame(Frame::text(fastwebsockets::Payload::Borrowed(
+                ping.to_string().as_bytes(),
+            )))
+            .await
+        {
+            log::warn!("Gate.io ping failed: {e}");
+            *state = State::Disconnected;
+        }
+    }
+}
+
+async fn try_resync(
+    exchange: Exchange,
+    ticker_info: TickerInfo,
+    contract_size: Option<f32>,
+    orderbook: &mut LocalDepthCache,
+    output: &mut mpsc::Sender<Event>,
+    already_fetching: &mut bool,
+) {
+    let ticker = ticker_info.ticker;
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    *already_fetching = true;
+
+    tokio::spawn(async move {
+        let result = fetch_depth(&ticker, contract_size).await;
+        let _ = tx.send(result);
+    });
+
+    match rx.await {
+        Ok(Ok(depth)) => {
+            orderbook.update(DepthUpdate::Snapshot(depth), ticker_info.min_ticksize);
+        }
+        Ok(Err(e)) => {
+            let _ = output
+                .send(Event::Disconnected(
+                    exchange,
+                    format!("Depth fetch failed: {e}"),
+                ))
+                .await;
+        }
+        Err(e) => {
+            let _ = output
+                .send(Event::Disconnected(
+                    exchange,
+                    format!("Failed to send fetched depth for {ticker}, error: {e}"),
+                ))
+                .await;
+        }
+    }
+    *already_fetching = false;
+}
+
+/// برقراری اتصال به جریان داده‌های بازار (عمق و معاملات) گیت
+pub fn connect_market_stream(
+    ticker_info: TickerInfo,
+    push_freq: PushFrequency,
+) -> impl Stream<Item = Event> {
+    stream::channel(100, async move |mut output| {
+        let mut state: State = State::Disconnected;
+
+        let ticker = ticker_info.ticker;
+
+        let (symbol_str, market_type) = ticker.to_full_symbol_and_type();
+        let exchange = exchange_from_market_type(market_type);
+        let prefix = channel_prefix(market_type);
+
+        let depth_payload = match market_type {
+            MarketKind::Spot => serde_json::json!([symbol_str, "100ms"]),
+            MarketKind::LinearPerps | MarketKind::InversePerps => {
+                serde_json::json!([symbol_str, "100ms", DEPTH_LIMIT.to_string()])
+            }
+        };
+
+        let subscriptions = [
+            envelope(
+                &format!("{prefix}.trades"),
+                Some("subscribe"),
+                Some(serde_json::json!([symbol_str])),
+            ),
+            envelope(
+                &format!("{prefix}.order_book_update"),
+                Some("subscribe"),
+                Some(depth_payload),
+            ),
+        ];
+
+        let mut trades_buffer: Vec<Trade> = vec![];
+        let mut orderbook = LocalDepthCache::default();
+        let mut already_fetching: bool = false;
+        let mut prev_id: u64 = 0;
+        let mut last_ping = Instant::now();
+
+        let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
+        let contract_size = ticker_info.contract_size.map(f32::from);
+
+        loop {
+            match &mut state {
+                State::Disconnected => {
+                    state = try_connect(&subscriptions, market_type, exchange, &mut output).await;
+
+                    if matches!(state, State::Connected(_)) {
+                        orderbook = LocalDepthCache::default();
+                        prev_id = 0;
+                        last_ping = Instant::now();
+
+                        try_resync(
+                            exchange,
+                            ticker_info,
+                            contract_size,
+                            &mut orderbook,
+                            &mut output,
+                            &mut already_fetching,
+                        )
+                        .await;
+                    }
+                }
+                State::Connected(ws) => match ws.read_frame().await {
+                    Ok(msg) => match msg.opcode {
+                        OpCode::Text => {
+                            match feed_de(&msg.payload[..], market_type) {
+                                Ok(StreamData::Trade(de_trades)) => {
+                                    for de_trade in &de_trades {
+                                        let price = Price::from_f32(de_trade.price)
+                                            .round_to_min_tick(ticker_info.min_ticksize);
+
+                                        trades_buffer.push(Trade {
+                                            time: de_trade.time,
+                                            is_sell: de_trade.is_sell,
+                                            price,
+                                            qty: calc_qty(
+                                                de_trade.qty,
+                                                de_trade.price,
+                                                size_in_quote_ccy,
+                                                contract_size,
+                                            ),
+                                        });
+                                    }
+                                }
+                                Ok(StreamData::Depth(de_depth)) => {
+                                    if already_fetching {
+                                        log::warn!("Already fetching...\n");
+                                        continue;
+                                    }
+
+                                    let last_update_id = orderbook.last_update_id;
+
+                                    if de_depth.final_id <= last_update_id || last_update_id == 0 {
+                                        continue;
+                                    }
+
+                                    if prev_id == 0 && de_depth.first_id > last_update_id + 1 {
+                                        log::warn!(
+                                            "Out of sync at first event. Trying to resync...\n"
+                                        );
+
+                                        try_resync(
+                                            exchange,
+                                            ticker_info,
+                                            contract_size,
+                                            &mut orderbook,
+                                            &mut output,
+                                            &mut already_fetching,
+                                        )
+                                        .await;
+                                        continue;
+                                    }
+
+                                    if prev_id == 0 || prev_id + 1 == de_depth.first_id {
+                                        let depth = new_depth_cache(
+                                            &de_depth,
+                                            size_in_quote_ccy,
+                                            contract_size,
+                                        );
+                                        orderbook.update(
+                                            DepthUpdate::Diff(depth),
+                                            ticker_info.min_ticksize,
+                                        );
+
+                                        let _ = output
+                                            .send(Event::DepthReceived(
+                                                StreamKind::DepthAndTrades {
+                                                    ticker_info,
+                                                    depth_aggr: StreamTicksize::Client,
+                                                    push_freq,
+                                                },
+                                                de_depth.time,
+                                                orderbook.publish(),
+                                                std::mem::take(&mut trades_buffer)
+                                                    .into_boxed_slice(),
+                                            ))
+                                            .await;
+
+                                        prev_id = de_depth.final_id;
+                                    } else {
+                                        state = State::Disconnected;
+                                        let _ = output
+                                            .send(Event::Disconnected(
+                                                exchange,
+                                                format!(
+                                                    "Out of sync. Expected update_id: {}, got: {}",
+                                                    prev_id + 1,
+                                                    de_depth.first_id
+                                                ),
+                                            ))
+                                            .await;
+                                        continue;
+                                    }
+                                }
+                                Ok(StreamData::Pong) => {}
+                                Err(AdapterError::WebsocketError(e)) => {
+                                    log::error!("Gate.io stream error: {e}");
+                                }
+                                Err(_) => {}
+                            }
+
+                            keep_alive(&mut state, market_type, &mut last_ping).await;
+                        }
+                        OpCode::Close => {
+                            state = State::Disconnected;
+                            let _ = output
+                                .send(Event::Disconnected(
+                                    exchange,
+                                    "Connection closed".to_string(),
+                                ))
+                                .await;
+                        }
+                        _ => {}
+                    },
+                    Err(e) => {
+                        state = State::Disconnected;
+                        let _ = output
+                            .send(Event::Disconnected(
+                                exchange,
+                                "Error reading frame: ".to_string() + &e.to_string(),
+                            ))
+                            .await;
+                    }
+                },
+            }
+        }
+    })
+}
+
+/// برقراری اتصال به جریان داده‌های کندل (Kline) گیت
+pub fn connect_kline_stream(
+    streams: Vec<(TickerInfo, Timeframe)>,
+    market_type: MarketKind,
+) -> impl Stream<Item = Event> {
+    stream::channel(100, async move |mut output| {
+        let mut state = State::Disconnected;
+
+        let exchange = exchange_from_market_type(market_type);
+        let prefix = channel_prefix(market_type);
+
+        let mut subscriptions = Vec::with_capacity(streams.len());
+        let mut lookup = HashMap::new();
+        for (ticker_info, timeframe) in &streams {
+            if let Some(interval) = timeframe_to_gate_interval(*timeframe, market_type) {
+                let (symbol, _mt) = ticker_info.ticker.to_full_symbol_and_type();
+
+                subscriptions.push(envelope(
+                    &format!("{prefix}.candlesticks"),
+                    Some("subscribe"),
+                    Some(serde_json::json!([interval, symbol])),
+                ));
+                lookup.insert(format!("{interval}_{symbol}"), (*ticker_info, *timeframe));
+            }
+        }
+
+        let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
+        let mut last_ping = Instant::now();
+
+        loop {
+            match &mut state {
+                State::Disconnected => {
+                    state = try_connect(&subscriptions, market_type, exchange, &mut output).await;
+                    last_ping = Instant::now();
+                }
+                State::Connected(ws) => match ws.read_frame().await {
+                    Ok(msg) => match msg.opcode {
+                        OpCode::Text => {
+                            if let Ok(v) = serde_json::from_slice::<Value>(&msg.payload[..])
+                                && v["channel"]
+                                    .as_str()
+                                    .is_some_and(|c| c.ends_with(".candlesticks"))
+                                && v["event"].as_str() == Some("update")
+                            {
+                                let rows: Vec<&Value> = match &v["result"] {
+                                    Value::Array(items) => items.iter().collect(),
+                                    obj @ Value::Object(_) => vec![obj],
+                                    _ => vec![],
+                                };
+
+                                for row in rows {
+                                    let Some((ticker_info, timeframe)) =
+                                        row["n"].as_str().and_then(|n| lookup.get(n)).copied()
+                                    else {
+                                        continue;
+                                    };
+
+                                    let contract_size = ticker_info.contract_size.map(f32::from);
+
+                                    if let Some(kline) = parse_ws_kline(
+                                        row,
+                                        market_type,
+                                        ticker_info,
+                                        size_in_quote_ccy,
+                                        contract_size,
+                                    ) {
+                                        let _ = output
+                                            .send(Event::KlineReceived(
+                                                StreamKind::Kline {
+                                                    ticker_info,
+                                                    timeframe,
+                                                    source: KlinePriceSource::Trade,
+                                                },
+                                                kline,
+                                            ))
+                                            .await;
+                                    }
+                                }
+                            }
+
+                            keep_alive(&mut state, market_type, &mut last_ping).await;
+                        }
+                        OpCode::Close => {
+                            state = State::Disconnected;
+                            let _ = output
+                                .send(Event::Disconnected(
+                                    exchange,
+                                    "Connection closed".to_string(),
+                                ))
+                                .await;
+                        }
+                        _ => {}
+                    },
+                    Err(e) => {
+                        state = State::Disconnected;
+                        let _ = output
+                            .send(Event::Disconnected(
+                                exchange,
+                                "Error reading frame: ".to_string() + &e.to_string(),
+                            ))
+                            .await;
+                    }
+                },
+            }
+        }
+    })
+}
+
+fn parse_ws_kline(
+    row: &Value,
+    market: MarketKind,
+    ticker_info: TickerInfo,
+    size_in_quote_ccy: bool,
+    contract_size: Option<f32>,
+) -> Option<Kline> {
+    let time = value_to_u64(&row["t"])? * 1000;
+    let open = value_to_f32(&row["o"])?;
+    let high = value_to_f32(&row["h"])?;
+    let low = value_to_f32(&row["l"])?;
+    let close = value_to_f32(&row["c"])?;
+
+    // در اسپات `a` حجم به ارز پایه و در فیوچرز `v` حجم به تعداد قرارداد است
+    let volume = match market {
+        MarketKind::Spot => value_to_f32(&row["a"]),
+        MarketKind::LinearPerps | MarketKind::InversePerps => value_to_f32(&row["v"]),
+    }
+    .map_or(0.0, |qty| {
+        calc_qty(qty, close, size_in_quote_ccy, contract_size)
+    });
+
+    Some(Kline::new(
+        time,
+        open,
+        high,
+        low,
+        close,
+        (-1.0, volume),
+        ticker_info.min_ticksize,
+    ))
+}
+
+fn new_depth_cache(
+    depth: &SonicDepth,
+    size_in_quote_ccy: bool,
+    contract_size: Option<f32>,
+) -> DepthPayload {
+    let convert = |orders: &[DeOrder]| {
+        orders
+            .iter()
+            .map(|x| DeOrder {
+                price: x.price,
+                qty: calc_qty(x.qty, x.price, size_in_quote_ccy, contract_size),
+            })
+            .collect()
+    };
+
+    DepthPayload {
+        last_update_id: depth.final_id,
+        time: depth.time,
+        bids: convert(&depth.bids),
+        asks: convert(&depth.asks),
+    }
+}
+
+/// دریافت تصویر کامل دفتر سفارش همراه با شناسه بروزرسانی برای همگام‌سازی
+async fn fetch_depth(
+    ticker: &Ticker,
+    contract_size: Option<f32>,
+) -> Result<DepthPayload, AdapterError> {
+    let (symbol_str, market_type) = ticker.to_full_symbol_and_type();
+
+    let url = match market_type {
+        MarketKind::Spot => format!(
+            "{}/api/v4/spot/order_book?currency_pair={symbol_str}&limit={DEPTH_LIMIT}&with_id=true",
+            Endpoint::GateioRest.resolve()
+        ),
+        MarketKind::LinearPerps | MarketKind::InversePerps => format!(
+            "{}/api/v4/futures/usdt/order_book?contract={symbol_str}&limit={DEPTH_LIMIT}&with_id=true",
+            Endpoint::GateioRest.resolve()
+        ),
+    };
+
+    let doc: Value = limiter::http_parse_with_limiter(&url, &GATEIO_LIMITER, 1, None, None).await?;
+
+    let last_update_id = doc
+        .get("id")
+        .and_then(value_to_u64)
+        .ok_or_else(|| AdapterError::ParseError("Depth snapshot id not found".to_string()))?;
+
+    // اسپات زمان را به میلی‌ثانیه و فیوچرز به ثانیه (اعشاری) برمی‌گرداند
+    let time = doc
+        .get("current")
+        .and_then(|v| match v {
+            Value::Number(n) => n.as_f64(),
+            Value::String(s) => s.parse::<f64>().ok(),
+            _ => None,
+        })
+        .map_or(0, |t| {
+            if t < 1e12 {
+                (t * 1000.0) as u64
+            } else {
+                t as u64
+            }
+        });
+
+    let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
+
+    let snapshot = SonicDepth {
+        first_id: last_update_id,
+        final_id: last_update_id,
+        time,
+        bids: parse_levels(doc.get("bids")),
+        asks: parse_levels(doc.get("asks")),
+    };
+
+    Ok(new_depth_cache(&snapshot, size_in_quote_ccy, contract_size))
+}
+
+fn calc_qty(qty: f32, price: f32, size_in_quote_ccy: bool, contract_size: Option<f32>) -> f32 {
+    let base_qty = contract_size.map_or(qty, |cs| qty * cs);
+
+    if size_in_quote_ccy {
+        base_qty * price
+    } else {
+        base_qty
+    }
+}
+
+fn timeframe_to_gate_interval(tf: Timeframe, market: MarketKind) -> Option<&'static str> {
+    let is_futures = market != MarketKind::Spot;
+
+    Some(match tf {
+        Timeframe::M1 => "1m",
+        Timeframe::M5 => "5m",
+        Timeframe::M15 => "15m",
+        Timeframe::M30 => "30m",
+        Timeframe::H1 => "1h",
+        Timeframe::H2 if is_futures => "2h",
+        Timeframe::H4 => "4h",
+        Timeframe::H12 if is_futures => "12h",
+        Timeframe::D1 => "1d",
+        _ => return None,
+    })
+}
+
+/// دریافت اطلاعات نمادها (گام قیمت و ...) از گیت
+pub async fn fetch_ticksize(
+    market_type: MarketKind,
+) -> Result<HashMap<Ticker, Option<TickerInfo>>, AdapterError> {
+    let exchange = exchange_from_market_type(market_type);
+
+    let url = match market_type {
+        MarketKind::Spot => format!(
+            "{}/api/v4/spot/currency_pairs",
+            Endpoint::GateioRest.resolve()
+        ),
+        MarketKind::LinearPerps | MarketKind::InversePerps => {
+            format!(
+                "{}/api/v4/futures/usdt/contracts",
+                Endpoint::GateioRest.resolve()
+            )
+        }
+    };
+
+    let doc: Value = limiter::http_parse_with_limiter(&url, &GATEIO_LIMITER, 1, None, None).await?;
+
+    let list = doc
+        .as_array()
+        .ok_or_else(|| AdapterError::ParseError("Result list is not an array".to_string()))?;
+
+    let mut map = HashMap::new();
+
+    for item in list {
+        let parsed = match market_type {
+            MarketKind::Spot => {
+                if item["quote"].as_str() != Some("USDT")
+                    || item["trade_status"].as_str() != Some("tradable")
+                {
+                    continue;
+                }
+
+                let Some(symbol) = item["id"].as_str() else {
+                    continue;
+                };
+                let min_ticksize = item["precision"].as_i64().map(|p| 10f32.powi(-(p as i32)));
+                let min_qty = item["min_base_amount"]
+                    .as_str()
+                    .and_then(|s| s.parse::<f32>().ok());
+
+                (symbol, min_ticksize, min_qty, None)
+            }
+            MarketKind::LinearPerps | MarketKind::InversePerps => {
+                if item["in_delisting"].as_bool() == Some(true) {
+                    continue;
+                }
+
+                let Some(symbol) = item["name"].as_str() else {
+                    continue;
+                };
+                let min_ticksize = item["order_price_round"]
+                    .as_str()
+                    .and_then(|s| s.parse::<f32>().ok());
+                let min_qty = item["order_size_min"].as_f64().map(|x| x as f32);
+                let contract_size = item["quanto_multiplier"]
+                    .as_str()
+                    .and_then(|s| s.parse::<f32>().ok());
+
+                (symbol, min_ticksize, min_qty, contract_size)
+            }
+        };
+
+        let (symbol, min_ticksize, min_qty, contract_size) = parsed;
+
+        if !is_symbol_supported(symbol, exchange, true) {
+            continue;
+        }
+
+        // نماد ناقص کنار گذاشته می‌شود تا بارگذاری بقیه نمادها متوقف نشود
+        let Some(min_ticksize) = min_ticksize else {
+            log::warn!("Gate.io {symbol}: tick size not found, skipping");
+            continue;
+        };
+        let Some(min_qty) = min_qty else {
+            log::warn!("Gate.io {symbol}: min qty not found, skipping");
+            continue;
+        };
+
+        let ticker = Ticker::new(symbol, exchange);
+        let info = TickerInfo::new(ticker, min_ticksize, min_qty, contract_size);
+
+        map.insert(ticker, Some(info));
+    }
+
+    Ok(map)
+}
+
+/// دریافت قیمت‌های فعلی و آمار ۲۴ ساعته نمادها از گیت
+pub async fn fetch_ticker_prices(
+    market_type: MarketKind,
+) -> Result<HashMap<Ticker, TickerStats>, AdapterError> {
+    let exchange = exchange_from_market_type(market_type);
+
+    let (url, symbol_key, volume_key) = match market_type {
+        MarketKind::Spot => (
+            format!("{}/api/v4/spot/tickers", Endpoint::GateioRest.resolve()),
+            "currency_pair",
+            "quote_volume",
+        ),
+        MarketKind::LinearPerps | MarketKind::InversePerps => (
+            format!(
+                "{}/api/v4/futures/usdt/tickers",
+                Endpoint::GateioRest.resolve()
+            ),
+            "contract",
+            "volume_24h_quote",
+        ),
+    };
+
+    let doc: Value = limiter::http_parse_with_limiter(&url, &GATEIO_LIMITER, 1, None, None).await?;
+
+    let list = doc
+        .as_array()
+        .ok_or_else(|| AdapterError::ParseError("Result list is not an array".to_string()))?;
+
+    let mut map = HashMap::new();
+
+    for item in list {
+        let Some(symbol) = item[symbol_key].as_str() else {
+            continue;
+        };
+
+        if !symbol.ends_with("_USDT") || !is_symbol_supported(symbol, exchange, false) {
+            continue;
+        }
+
+        let (Some(last_price), Some(daily_price_chg), Some(daily_volume)) = (
+            value_to_f32(&item["last"]),
+            value_to_f32(&item["change_percentage"]),
+            value_to_f32(&item[volume_key]),
+        ) else {
+            continue;
+        };
+
+        map.insert(
+            Ticker::new(symbol, exchange),
+            TickerStats {
+                last_price,
+                mark_price: last_price,
+                index_price: last_price,
+                daily_price_chg,
+                daily_volume,
+            },
+        );
+    }
+
+    Ok(map)
+}
+
+/// دریافت داده‌های کندل (Kline) از طریق API گیت
+pub async fn fetch_klines(
+    ticker_info: TickerInfo,
+    timeframe: Timeframe,
+    range: Option<(u64, u64)>,
+) -> Result<Vec<Kline>, AdapterError> {
+    let ticker = ticker_info.ticker;
+
+    let (symbol_str, market) = ticker.to_full_symbol_and_type();
+    let contract_size = ticker_info.contract_size.map(f32::from);
+
+    let interval = timeframe_to_gate_interval(timeframe, market).ok_or_else(|| {
+        AdapterError::InvalidRequest(format!("Unsupported timeframe: {timeframe}"))
+    })?;
+
+    let mut url = match market {
+        MarketKind::Spot => format!(
+            "{}/api/v4/spot/candlesticks?currency_pair={symbol_str}&interval={interval}",
+            Endpoint::GateioRest.resolve()
+        ),
+        MarketKind::LinearPerps | MarketKind::InversePerps => format!(
+            "{}/api/v4/futures/usdt/candlesticks?contract={symbol_str}&interval={interval}",
+            Endpoint::GateioRest.resolve()
+        ),
+    };
+
+    match range {
+        Some((start, end)) => {
+            // گیت حداکثر ۱۰۰۰ کندل در هر درخواست برمی‌گرداند
+            let max_span = timeframe.to_milliseconds() * 999;
+            let start = start.max(end.saturating_sub(max_span));
+            url.push_str(&format!("&from={}&to={}", start / 1000, end / 1000));
+        }
+        None => url.push_str("&limit=400"),
+    }
+
+    let doc: Value = limiter::http_parse_with_limiter(&url, &GATEIO_LIMITER, 1, None, None).await?;
+
+    let list = doc
+        .as_array()
+        .ok_or_else(|| AdapterError::ParseError("Kline result is not an array".to_string()))?;
+
+    let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
+
+    let mut klines: Vec<Kline> = Vec::with_capacity(list.len());
+
+    for row in list {
+        // اسپات: [t, quote_vol, close, high, low, open, base_vol, closed]
+        // فیوچرز: {t, v, c, h, l, o, sum}
+        let fields = match market {
+            MarketKind::Spot => (
+                row.get(0).and_then(value_to_u64),
+                row.get(5).and_then(value_to_f32),
+                row.get(3).and_then(value_to_f32),
+                row.get(4).and_then(value_to_f32),
+                row.get(2).and_then(value_to_f32),
+                row.get(6).and_then(value_to_f32),
+            ),
+            MarketKind::LinearPerps | MarketKind::InversePerps => (
+                row.get("t").and_then(value_to_u64),
+                row.get("o").and_then(value_to_f32),
+                row.get("h").and_then(value_to_f32),
+                row.get("l").and_then(value_to_f32),
+                row.get("c").and_then(value_to_f32),
+                row.get("v").and_then(value_to_f32),
+            ),
+        };
+
+        let (Some(ts), Some(open), Some(high), Some(low), Some(close), volume) = fields else {
+            continue;
+        };
+
+        let volume_in_display = volume.map_or(0.0, |qty| {
+            calc_qty(qty, close, size_in_quote_ccy, contract_size)
+        });
+
+        klines.push(Kline::new(
+            ts * 1000,
+            open,
+            high,
+            low,
+            close,
+            (-1.0, volume_in_display),
+            ticker_info.min_ticksize,
+        ));
+    }
+
+    klines.sort_by_key(|k| k.time);
+    Ok(klines)
+}
@@ -2,18 +2,17 @@ use super::{
     super::{
         Exchange, Kline, MarketKind, Price, PushFrequency, SizeUnit, StreamKind, TickMultiplier,
         Ticker, TickerInfo, TickerStats, Timeframe, Trade,
-        connect::{State, connect_ws},
+        connect::{Connection, State, connect_ws},
         de_string_to_f32,
         depth::{DeOrder, DepthPayload, DepthUpdate, LocalDepthCache},
         limiter::{self, RateLimiter},
+        network::Endpoint,
         volume_size_unit,
     },
-    AdapterError, Event,
+    AdapterError, Event, ExchangeInclusive, KlinePriceSource,
 };
 
-use fastwebsockets::{FragmentCollector, Frame, OpCode};
-use hyper::upgrade::Upgraded;
-use hyper_util::rt::TokioIo;
+use fastwebsockets::{Frame, OpCode};
 use iced_futures::{
     futures::{SinkExt, Stream},
     stream,
@@ -22,12 +21,13 @@ use reqwest::Method;
 use serde::Deserialize;
 use serde_json::{Value, json};
 
-use std::{collections::HashMap, sync::LazyLock, time::Duration};
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, RwLock},
+    time::Duration,
+};
 use tokio::sync::Mutex;
 
-const API_DOMAIN: &str = "https://api.hyperliquid.xyz";
-const WS_DOMAIN: &str = "api.hyperliquid.xyz";
-
 const _MAX_DECIMALS_SPOT: u8 = 8;
 const MAX_DECIMALS_PERP: u8 = 6;
 
@@ -52,6 +52,41 @@ pub fn allowed_multipliers_for_base_tick(base_ticksize: f32) -> &'static [u16] {
     }
 }
 
+/// ضریب گام واقعی و پیکربندی سمت سرور برای یک ضریب دلخواه کاربر؛
+/// سرور فقط گام‌های 1-2-5 × 10^k را پشتیبانی می‌کند، پس ضریب به نزدیک‌ترین مقدار قابل ارائه نگاشت می‌شود
+pub fn effective_depth_multiplier(base_ticksize: f32, multiplier: u16) -> (u16, DepthFeedConfig) {
+    if base_ticksize <= 0.0 {
+        return (1, DepthFeedConfig::full_precision());
+    }
+
+    // گام پایه ۱ بین ناحیه مرزی و سرریز مبهم است؛ فقط توان‌های ۱۰ در هر دو یکسان هستند
+    let multiplier = if base_ticksize >= 1.0 {
+        let target = f32::from(multiplier.max(1)).log10();
+        MULTS_SAFE
+            .iter()
+            .copied()
+            .min_by(|a, b| {
+                let da = (f32::from(*a).log10() - target).abs();
+                let db = (f32::from(*b).log10() - target).abs();
+                da.total_cmp(&db)
+            })
+            .unwrap_or(1)
+    } else {
+        multiplier
+    };
+
+    // قیمت نمونه‌ای در میانه دهه‌ای که گام پایه به آن تعلق دارد
+    let reference_price = base_ticksize.min(1.0) * 1.5 * 10_f32.powi(SIG_FIG_LIMIT - 1);
+
+    let cfg = config_from_multiplier(reference_price, multiplier);
+    let tick = depth_tick_from_cfg(reference_price, cfg);
+    let effective = (tick / base_ticksize.min(1.0))
+        .round()
+        .clamp(1.0, f32::from(u16::MAX)) as u16;
+
+    (effective, cfg)
+}
+
 pub fn exact_multipliers_for_price(price: f32) -> &'static [u16] {
     if price <= 0.0 {
         return MULTS_FRACTIONAL;
@@ -79,6 +114,27 @@ const LIMITER_BUFFER_PCT: f32 = 0.05;
 static HYPERLIQUID_LIMITER: LazyLock<Mutex<HyperliquidLimiter>> =
     LazyLock::new(|| Mutex::new(HyperliquidLimiter::new(LIMIT, REFILL_RATE)));
 
+/// پیکربندی‌های عمقی که سرور برایشان دفتر سفارشات null برگرداند و جریان با دقت کامل جایگزین شد
+static DEPTH_FALLBACKS: LazyLock<RwLock<HashMap<(Ticker, u16), DepthFeedConfig>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// پیکربندی رد شده برای این نماد و ضریب، اگر جریان فعال به دقت کامل برگشته باشد
+pub fn depth_fallback(ticker: Ticker, multiplier: u16) -> Option<DepthFeedConfig> {
+    DEPTH_FALLBACKS
+        .read()
+        .ok()
+        .and_then(|fallbacks| fallbacks.get(&(ticker, multiplier)).copied())
+}
+
+fn set_depth_fallback(ticker: Ticker, multiplier: u16, rejected: Option<DepthFeedConfig>) {
+    if let Ok(mut fallbacks) = DEPTH_FALLBACKS.write() {
+        match rejected {
+            Some(cfg) => fallbacks.insert((ticker, multiplier), cfg),
+            None => fallbacks.remove(&(ticker, multiplier)),
+        };
+    }
+}
+
 /// محدودکننده نرخ اختصاصی برای هایپرلیکویید
 pub struct HyperliquidLimiter {
     bucket: limiter::FixedWindowBucket,
@@ -220,7 +276,7 @@ enum StreamData {
 pub async fn fetch_ticksize(
     market: MarketKind,
 ) -> Result<HashMap<Ticker, Option<TickerInfo>>, AdapterError> {
-    let url = format!("{}/info", API_DOMAIN);
+    let url = format!("{}/info", Endpoint::HyperliquidRest.resolve());
 
     let (endpoint_type, exchange) = match market {
         MarketKind::LinearPerps => ("metaAndAssetCtxs", Exchange::HyperliquidLinear),
@@ -401,6 +457,16 @@ impl DepthFeedConfig {
     }
 }
 
+impl std::fmt::Display for DepthFeedConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.n_sig_figs, self.mantissa) {
+            (None, _) => write!(f, "full precision"),
+            (Some(n), None) => write!(f, "nSigFigs {n}"),
+            (Some(n), Some(m)) => write!(f, "nSigFigs {n}, mantissa {m}"),
+        }
+    }
+}
+
 impl Default for DepthFeedConfig {
     fn default() -> Self {
         Self {
@@ -579,7 +645,7 @@ fn compute_tick_size(price: f32, sz_decimals: u32, market: MarketKind) -> f32 {
 pub async fn fetch_ticker_prices(
     market: MarketKind,
 ) -> Result<HashMap<Ticker, TickerStats>, AdapterError> {
-    let url = format!("{}/info", API_DOMAIN);
+    let url = format!("{}/info", Endpoint::HyperliquidRest.resolve());
 
     let mids = fetch_all_mids(&url).await?;
 
@@ -698,7 +764,9 @@ async fn process_spot_ticker_stats(
             ticker_stats_map.insert(
                 ticker,
                 TickerStats {
+                    last_price: ctx.mark_price,
                     mark_price: ctx.mark_price,
+                    index_price: ctx.mark_price,
                     daily_price_chg,
                     daily_volume: ctx.day_notional_volume,
                 },
@@ -750,7 +818,9 @@ fn find_asset_stats(
         };
 
         return Ok(Some(TickerStats {
+            last_price: mid_price,
             mark_price: mid_price,
+            index_price: mid_price,
             daily_price_chg,
             daily_volume: day_ntl_vlm,
         }));
@@ -768,7 +838,7 @@ pub async fn fetch_klines(
     let ticker = ticker_info.ticker;
     let interval = timeframe.to_string();
 
-    let url = format!("{}/info", API_DOMAIN);
+    let url = format!("{}/info", Endpoint::HyperliquidRest.resolve());
     // Use the internal symbol (e.g., "@107" for spot, "BTC" for perps)
     let (symbol_str, _) = ticker.to_full_symbol_and_type();
 
@@ -822,7 +892,8 @@ pub async fn fetch_klines(
                 hl_kline.close,
                 (-1.0, volume),
                 ticker_info.min_ticksize,
-            );
+            )
+            .with_trade_count(hl_kline.trade_count);
             klines.push(kline);
         }
     }
@@ -830,12 +901,35 @@ pub async fn fetch_klines(
     Ok(klines)
 }
 
-async fn connect_websocket(
-    domain: &str,
-    path: &str,
-) -> Result<FragmentCollector<TokioIo<Upgraded>>, AdapterError> {
+async fn connect_websocket(path: &str) -> Result<Connection, AdapterError> {
+    let domain = Endpoint::HyperliquidWs.resolve();
     let url = format!("wss://{}{}", domain, path);
-    connect_ws(domain, &url).await
+    connect_ws(&domain, &url, ExchangeInclusive::Hyperliquid).await
+}
+
+/// پیام اشتراک یا لغو اشتراک `l2Book`؛ لغو اشتراک باید دقیقاً همان پارامترهای اشتراک را داشته باشد
+fn depth_subscription_msg(method: &str, symbol: &str, cfg: DepthFeedConfig) -> Value {
+    let mut msg = json!({
+        "method": method,
+        "subscription": {
+            "type": "l2Book",
+            "coin": symbol,
+        }
+    });
+    if let Some(n) = cfg.n_sig_figs {
+        msg["subscription"]["nSigFigs"] = json!(n);
+    }
+    if let (Some(m), Some(5)) = (cfg.mantissa, cfg.n_sig_figs) {
+        msg["subscription"]["mantissa"] = json!(m);
+    }
+    msg
+}
+
+/// سرور برای برخی ترکیب‌های nSigFigs/mantissa به جای دفتر سفارشات مقدار null برمی‌گرداند
+fn is_null_book(payload: &[u8]) -> bool {
+    serde_json::from_slice::<Value>(payload).is_ok_and(|json| {
+        json["channel"] == "l2Book" && (json["data"].is_null() || json["data"]["levels"].is_null())
+    })
 }
 
 fn parse_websocket_message(payload: &[u8]) -> Result<StreamData, AdapterError> {
@@ -887,6 +981,7 @@ pub fn connect_market_stream(
 
         let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
         let user_multiplier = tick_multiplier.unwrap_or(TickMultiplier(1)).0;
+        let mut depth_cfg = DepthFeedConfig::full_precision();
 
         let (symbol_str, _) = ticker.to_full_symbol_and_type();
 
@@ -917,23 +1012,22 @@ pub fn connect_market_stream(
                         user_multiplier
                     );
 
-                    let depth_cfg = config_from_multiplier(price, user_multiplier);
+                    depth_cfg = config_from_multiplier(price, user_multiplier);
 
-                    match connect_websocket(WS_DOMAIN, "/ws").await {
+                    if !depth_cfg.is_full() && !has_book(&symbol_str, depth_cfg).await {
+                        log::warn!(
+                            "Hyperliquid returned no book for {symbol_str} with {depth_cfg}, falling back to full precision"
+                        );
+                        set_depth_fallback(ticker, user_multiplier, Some(depth_cfg));
+                        depth_cfg = DepthFeedConfig::full_precision();
+                    } else {
+                        set_depth_fallback(ticker, user_multiplier, None);
+                    }
+
+                    match connect_websocket("/ws").await {
                         Ok(mut websocket) => {
-                            let mut depth_subscription = json!({
-                                "method": "subscribe",
-                                "subscription": {
-                                    "type": "l2Book",
-                                    "coin": symbol_str,
-                                }
-                            });
-                            if let Some(n) = depth_cfg.n_sig_figs {
-                                depth_subscription["subscription"]["nSigFigs"] = json!(n);
-                            }
-                            if let (Some(m), Some(5)) = (depth_cfg.mantissa, depth_cfg.n_sig_figs) {
-                                depth_subscription["subscription"]["mantissa"] = json!(m);
-                            }
+                            let depth_subscription =
+                                depth_subscription_msg("subscribe", &symbol_str, depth_cfg);
 
                             log::debug!(
                                 "Hyperliquid WS Depth Subscription: {}",
@@ -995,7 +1089,34 @@ pub fn connect_market_stream(
                     match websocket.read_frame().await {
                         Ok(msg) => match msg.opcode {
                             OpCode::Text => {
-                                if let Ok(stream_data) = parse_websocket_message(&msg.payload) {
+                                // فقط اشتراک عمق با دقت کامل جایگزین می‌شود؛ اشتراک معاملات روی همان اتصال باقی می‌ماند
+                                if !depth_cfg.is_full() && is_null_book(&msg.payload) {
+                                    log::warn!(
+                                        "Hyperliquid l2Book for {symbol_str} with {depth_cfg} is null, resubscribing with full precision"
+                                    );
+
+                                    let unsubscribe = depth_subscription_msg(
+                                        "unsubscribe",
+                                        &symbol_str,
+                                        depth_cfg,
+                                    );
+                                    set_depth_fallback(ticker, user_multiplier, Some(depth_cfg));
+                                    depth_cfg = DepthFeedConfig::full_precision();
+                                    let subscribe =
+                                        depth_subscription_msg("subscribe", &symbol_str, depth_cfg);
+
+                                    for payload in [unsubscribe, subscribe] {
+                                        let _ = websocket
+                                            .write_frame(Frame::text(
+                                                fastwebsockets::Payload::Borrowed(
+                                                    payload.to_string().as_bytes(),
+                                                ),
+                                            ))
+                                            .await;
+                                    }
+                                } else if let Ok(stream_data) =
+                                    parse_websocket_message(&msg.payload)
+                                {
                                     match stream_data {
                                         StreamData::Trade(trades) => {
                                             for hl_trade in trades {
@@ -1058,7 +1179,7 @@ pub fn connect_market_stream(
                                                 ),
                                                 push_freq,
                                             };
-                                            let current_depth = local_depth_cache.depth.clone();
+                                            let current_depth = local_depth_cache.publish();
                                             let trades = std::mem::take(&mut trades_buffer)
                                                 .into_boxed_slice();
 
@@ -1124,7 +1245,7 @@ pub fn connect_kline_stream(
 
         loop {
             match &mut state {
-                State::Disconnected => match connect_websocket(WS_DOMAIN, "/ws").await {
+                State::Disconnected => match connect_websocket("/ws").await {
                     Ok(mut websocket) => {
                         for (ticker_info, timeframe) in &streams {
                             let ticker = ticker_info.ticker;
@@ -1189,11 +1310,13 @@ pub fn connect_kline_stream(
                                     hl_kline.close,
                                     (-1.0, volume),
                                     ticker_info.min_ticksize,
-                                );
+                                )
+                                .with_trade_count(hl_kline.trade_count);
 
                                 let stream_kind = StreamKind::Kline {
                                     ticker_info: *ticker_info,
                                     timeframe: *timeframe,
+                                    source: KlinePriceSource::Trade,
                                 };
                                 let _ = output.send(Event::KlineReceived(stream_kind, kline)).await;
                             }
@@ -1227,12 +1350,21 @@ pub fn connect_kline_stream(
     })
 }
 
+/// بررسی اینکه سرور برای این پیکربندی دفتر سفارشات غیرخالی برمی‌گرداند؛ خطای شبکه رد پیکربندی به حساب نمی‌آید
+async fn has_book(symbol: &str, cfg: DepthFeedConfig) -> bool {
+    match fetch_orderbook(symbol, Some(cfg)).await {
+        Ok(depth) => !(depth.bids.is_empty() && depth.asks.is_empty()),
+        Err(AdapterError::ParseError(_)) => false,
+        Err(_) => true,
+    }
+}
+
 async fn fetch_orderbook(
     symbol: &str,
     cfg: Option<DepthFeedConfig>,
 ) -> Result<DepthPayload, AdapterError> {
     log::debug!("Fetching orderbook for symbol: '{}'", symbol);
-    let url = format!("{}/info", API_DOMAIN);
+    let url = format!("{}/info", Endpoint::HyperliquidRest.resolve());
 
     let mut body = json!({
         "type": "l2Book",
@@ -1306,6 +1438,21 @@ async fn fetch_orderbook(
 mod tests {
     use super::*;
 
+    #[test]
+    fn null_book_is_detected_and_unsubscribe_mirrors_subscribe() {
+        assert!(is_null_book(br#"{"channel":"l2Book","data":null}"#));
+        assert!(!is_null_book(
+            br#"{"channel":"l2Book","data":{"coin":"BTC","levels":[[],[]],"time":1}}"#
+        ));
+        assert!(!is_null_book(br#"{"channel":"trades","data":null}"#));
+
+        let cfg = DepthFeedConfig::new(Some(5), Some(2));
+        let subscribe = depth_subscription_msg("subscribe", "BTC", cfg);
+        let unsubscribe = depth_subscription_msg("unsubscribe", "BTC", cfg);
+        assert_eq!(subscribe["subscription"], unsubscribe["subscription"]);
+        assert_eq!(subscribe["subscription"]["mantissa"], 2);
+    }
+
     fn smallest_positive_gap(mut prices: Vec<f32>) -> Option<f32> {
         prices.sort_by(|a, b| b.partial_cmp(a).unwrap());
         let mut best: Option<f32> = None;
@@ -1320,6 +1467,30 @@ mod tests {
         best
     }
 
+    #[test]
+    fn effective_depth_multiplier_snaps_custom_values() {
+        // ۳۷ به ۵۰ نگاشت می‌شود اما مانتیس فقط با ۵ رقم معنادار مجاز است، پس گام ۱۰ برابر می‌شود
+        let (effective, cfg) = effective_depth_multiplier(0.1, 37);
+        assert_eq!(effective, 10);
+        assert_eq!(cfg.n_sig_figs, Some(4));
+        assert_eq!(cfg.mantissa, None);
+
+        let (effective, cfg) = effective_depth_multiplier(0.1, 2);
+        assert_eq!(effective, 2);
+        assert_eq!(cfg.n_sig_figs, Some(5));
+        assert_eq!(cfg.mantissa, Some(2));
+
+        let (effective, cfg) = effective_depth_multiplier(0.001, 1);
+        assert_eq!(effective, 1);
+        assert!(cfg.is_full());
+
+        // گام پایه ۱ فقط توان‌های ۱۰ را می‌پذیرد
+        let (effective, _) = effective_depth_multiplier(1.0, 20);
+        assert_eq!(effective, 10);
+        let (effective, _) = effective_depth_multiplier(1.0, 5000);
+        assert_eq!(effective, 1000);
+    }
+
     #[tokio::test]
     async fn manual_depth_cfg() {
         let symbol = "BTC";
@@ -1,7 +1,11 @@
 use crate::{
-    OpenInterest, Price, PushFrequency, SizeUnit,
+    FundingRate, OpenInterest, Price, PushFrequency, SizeUnit,
     adapter::{StreamKind, StreamTicksize},
+    archive,
+    fetcher::TradeSource,
+    inverse_contracts_to_size, is_inverse_conversion_enabled,
     limiter::{self, RateLimiter},
+    network::Endpoint,
     volume_size_unit,
 };
 
@@ -12,7 +16,7 @@ use super::{
         de_string_to_f32, de_string_to_u64, is_symbol_supported,
         limiter::HTTP_CLIENT,
     },
-    AdapterError, Event,
+    AdapterError, Event, ExchangeInclusive, KlinePriceSource,
 };
 
 use super::super::depth::{DeOrder, DepthPayload, DepthUpdate, LocalDepthCache};
@@ -24,11 +28,9 @@ use iced_futures::{
 };
 use serde::Deserialize;
 use serde_json::Value;
-use std::{collections::HashMap, sync::LazyLock, time::Duration};
+use std::{collections::HashMap, path::PathBuf, sync::LazyLock, time::Duration};
 use tokio::sync::Mutex;
 
-const WS_DOMAIN: &str = "ws.okx.com";
-
 const LIMIT: usize = 20;
 
 const REFILL_RATE: Duration = Duration::from_secs(2);
@@ -166,9 +168,10 @@ async fn try_connect(
     output: &mut mpsc::Sender<Event>,
     topic: &str,
 ) -> State {
-    let url = format!("wss://{WS_DOMAIN}/ws/v5/{topic}");
+    let domain = Endpoint::OkexWs.resolve();
+    let url = format!("wss://{domain}/ws/v5/{topic}");
 
-    match connect_ws(WS_DOMAIN, &url).await {
+    match connect_ws(&domain, &url, ExchangeInclusive::Okex).await {
         Ok(mut websocket) => {
             if let Err(e) = websocket
                 .write_frame(Frame::text(fastwebsockets::Payload::Borrowed(
@@ -314,7 +317,7 @@ pub fn connect_market_stream(
                                                         push_freq,
                                                     },
                                                     time,
-                                                    orderbook.depth.clone(),
+                                                    orderbook.publish(),
                                                     std::mem::take(&mut trades_buffer)
                                                         .into_boxed_slice(),
                                                 ))
@@ -478,6 +481,7 @@ pub fn connect_kline_stream(
                                                 StreamKind::Kline {
                                                     ticker_info,
                                                     timeframe,
+                                                    source: KlinePriceSource::Trade,
                                                 },
                                                 kline,
                                             ))
@@ -522,6 +526,15 @@ fn calc_qty(
     let is_inverse = matches!(market, MarketKind::InversePerps);
 
     match contract_size {
+        // تبدیل اختیاری قراردادهای معکوس؛ بدون آن، حالت Base همان تعداد قرارداد را نمایش می‌دهد
+        Some(cs) if is_inverse && is_inverse_conversion_enabled() => {
+            let unit = if size_in_quote_ccy {
+                SizeUnit::Quote
+            } else {
+                SizeUnit::Base
+            };
+            inverse_contracts_to_size(qty, price, cs, unit)
+        }
         Some(cs) => {
             if is_inverse {
                 if size_in_quote_ccy { qty * cs } else { qty }
@@ -541,6 +554,195 @@ fn calc_qty(
     }
 }
 
+/// دریافت معاملات اوکی‌اکس از فایل‌های روزانه CDN و API معاملات گذشته، بسته به منبع انتخاب شده
+pub async fn fetch_trades(
+    ticker_info: TickerInfo,
+    from_time: u64,
+    data_path: PathBuf,
+    source: TradeSource,
+) -> Result<Vec<Trade>, AdapterError> {
+    let archive = Archive {
+        ticker_info,
+        contract_size: ticker_info.contract_size.map(f32::from),
+        size_in_quote_ccy: volume_size_unit() == SizeUnit::Quote,
+    };
+
+    archive::fetch_trades(&archive, from_time, &data_path, source).await
+}
+
+/// بازه زمانی اولیه برای جستجوی معاملات پس از زمان شروع
+const INTRADAY_WINDOW_MS: u64 = 60_000;
+/// حداکثر تعداد صفحه‌های ۱۰۰ تایی برای پوشش یک بازه
+const INTRADAY_MAX_PAGES: usize = 10;
+
+/// دریافت معاملات از زمان `from` به بعد از API معاملات گذشته اوکی‌اکس
+///
+/// این API فقط از جدید به قدیم صفحه‌بندی می‌کند؛ پس از انتهای یک بازه زمانی به عقب
+/// برمی‌گردیم تا به `from` برسیم. اگر بازه خالی باشد بزرگ‌تر و اگر بیش از حد شلوغ باشد
+/// کوچک‌تر می‌شود تا بین معاملات دریافت شده فاصله‌ای نماند.
+pub async fn fetch_intraday_trades(
+    ticker_info: TickerInfo,
+    from: u64,
+) -> Result<Vec<Trade>, AdapterError> {
+    let now_ms = chrono::Utc::now().timestamp_millis().max(0) as u64;
+    let mut window = INTRADAY_WINDOW_MS;
+
+    loop {
+        let until = from.saturating_add(window).min(now_ms);
+        let (trades, complete) = fetch_trades_window(ticker_info, from, until).await?;
+
+        if !complete && window > 1_000 {
+            window /= 2;
+        } else if trades.is_empty() && until < now_ms {
+            window = window.saturating_mul(4);
+        } else {
+            return Ok(trades);
+        }
+    }
+}
+
+/// معاملات بین `from` و `until`؛ مقدار دوم نشان می‌دهد که آیا صفحه‌ها تا `from` رسیده‌اند
+async fn fetch_trades_window(
+    ticker_info: TickerInfo,
+    from: u64,
+    until: u64,
+) -> Result<(Vec<Trade>, bool), AdapterError> {
+    let mut trades = vec![];
+    let mut cursor = until;
+
+    for _ in 0..INTRADAY_MAX_PAGES {
+        let page = fetch_history_trades_page(ticker_info, cursor).await?;
+
+        let Some(oldest) = page.iter().map(|trade| trade.time).min() else {
+            return Ok((sorted_trades(trades), true));
+        };
+
+        trades.extend(page.into_iter().filter(|trade| trade.time >= from));
+
+        if oldest <= from {
+            return Ok((sorted_trades(trades), true));
+        }
+        cursor = oldest;
+    }
+
+    Ok((sorted_trades(trades), false))
+}
+
+fn sorted_trades(mut trades: Vec<Trade>) -> Vec<Trade> {
+    trades.sort_by_key(|trade| trade.time);
+    trades
+}
+
+/// یک صفحه از معاملات پیش از زمان `before`، از جدید به قدیم
+async fn fetch_history_trades_page(
+    ticker_info: TickerInfo,
+    before: u64,
+) -> Result<Vec<Trade>, AdapterError> {
+    let (symbol_str, market) = ticker_info.ticker.to_full_symbol_and_type();
+    let contract_size = ticker_info.contract_size.map(f32::from);
+    let size_in_quote_ccy = volume_size_unit() == SizeUnit::Quote;
+
+    let url = format!(
+        "{}/api/v5/market/history-trades?instId={symbol_str}&type=2&after={before}&limit=100",
+        Endpoint::OkexRest.resolve()
+    );
+
+    let doc: Value = limiter::http_parse_with_limiter(&url, &OKEX_LIMITER, 1, None, None).await?;
+
+    let list = doc["data"]
+        .as_array()
+        .ok_or_else(|| AdapterError::ParseError("Trades result is not an array".to_string()))?;
+
+    let trades = list
+        .iter()
+        .filter_map(|row| {
+            let price = row["px"].as_str()?.parse::<f32>().ok()?;
+            let qty = row["sz"].as_str()?.parse::<f32>().ok()?;
+
+            Some(Trade {
+                time: row["ts"].as_str()?.parse::<u64>().ok()?,
+                is_sell: row["side"].as_str()?.eq_ignore_ascii_case("sell"),
+                price: Price::from_f32(price).round_to_min_tick(ticker_info.min_ticksize),
+                qty: calc_qty(qty, price, size_in_quote_ccy, contract_size, market),
+            })
+        })
+        .collect();
+
+    Ok(trades)
+}
+
+/// فایل‌های روزانه معاملات اوکی‌اکس؛ مرز روز فایل‌ها بر اساس UTC+8 است
+struct Archive {
+    ticker_info: TickerInfo,
+    contract_size: Option<f32>,
+    size_in_quote_ccy: bool,
+}
+
+impl archive::TradeHistory for Archive {
+    type Archive = Self;
+
+    fn archive(&self) -> Option<&Self> {
+        Some(self)
+    }
+
+    fn has_intraday(&self) -> bool {
+        true
+    }
+
+    fn fetch_intraday(
+        &self,
+        from: u64,
+    ) -> impl Future<Output = Result<Vec<Trade>, AdapterError>> + Send {
+        fetch_intraday_trades(self.ticker_info, from)
+    }
+}
+
+impl archive::TradeArchive for Archive {
+    fn base_url(&self) -> &'static str {
+        "https://www.okx.com/cdn/okex/traderecords/trades/daily"
+    }
+
+    fn file_path(&self, date: chrono::NaiveDate) -> String {
+        let (symbol, _) = self.ticker_info.ticker.to_full_symbol_and_type();
+
+        format!(
+            "{}/{symbol}-trades-{}.zip",
+            date.format("%Y%m%d"),
+            date.format("%Y-%m-%d"),
+        )
+    }
+
+    fn compression(&self) -> archive::Compression {
+        archive::Compression::Zip
+    }
+
+    fn has_headers(&self) -> bool {
+        true
+    }
+
+    fn day_offset_hours(&self) -> i64 {
+        8
+    }
+
+    fn parse_record(&self, record: &csv::StringRecord) -> Option<Trade> {
+        // instrument_name,trade_id,side,price,size,created_time
+        let price = record.get(3)?.parse::<f32>().ok()?;
+
+        Some(Trade {
+            time: record.get(5)?.parse::<u64>().ok()?,
+            is_sell: record.get(2)?.eq_ignore_ascii_case("sell"),
+            price: Price::from_f32(price).round_to_min_tick(self.ticker_info.min_ticksize),
+            qty: calc_qty(
+                record.get(4)?.parse::<f32>().ok()?,
+                price,
+                self.size_in_quote_ccy,
+                self.contract_size,
+                self.ticker_info.market_type(),
+            ),
+        })
+    }
+}
+
 fn okx_inst_type(m: MarketKind) -> &'static str {
     match m {
         MarketKind::Spot => "SPOT",
@@ -570,7 +772,8 @@ pub async fn fetch_ticksize(
 ) -> Result<std::collections::HashMap<Ticker, Option<TickerInfo>>, AdapterError> {
     let inst_type = okx_inst_type(market_type);
     let url = format!(
-        "https://www.okx.com/api/v5/public/instruments?instType={}",
+        "{}/api/v5/public/instruments?instType={}",
+        Endpoint::OkexRest.resolve(),
         inst_type
     );
 
@@ -653,7 +856,8 @@ pub async fn fetch_ticker_prices(
 ) -> Result<std::collections::HashMap<Ticker, TickerStats>, AdapterError> {
     let inst_type = okx_inst_type(market_type);
     let url = format!(
-        "https://www.okx.com/api/v5/market/tickers?instType={}",
+        "{}/api/v5/market/tickers?instType={}",
+        Endpoint::OkexRest.resolve(),
         inst_type
     );
 
@@ -714,7 +918,9 @@ pub async fn fetch_ticker_prices(
         map.insert(
             Ticker::new(symbol, exchange),
             TickerStats {
+                last_price,
                 mark_price: last_price,
+                index_price: last_price,
                 daily_price_chg,
                 daily_volume: volume_usd,
             },
@@ -724,6 +930,51 @@ pub async fn fetch_ticker_prices(
     Ok(map)
 }
 
+/// دریافت تاریخچه نرخ تأمین مالی قراردادهای دائمی از اوکی‌اکس
+pub async fn fetch_funding_history(
+    ticker: Ticker,
+    range: Option<(u64, u64)>,
+) -> Result<Vec<FundingRate>, AdapterError> {
+    let (ticker_str, market) = ticker.to_full_symbol_and_type();
+
+    if market == MarketKind::Spot {
+        return Err(AdapterError::InvalidRequest(
+            "Funding rates are only available for perpetuals".to_string(),
+        ));
+    }
+
+    let mut url = format!(
+        "{}/api/v5/public/funding-rate-history?instId={ticker_str}&limit=100",
+        Endpoint::OkexRest.resolve()
+    );
+
+    if let Some((start, end)) = range {
+        url.push_str(&format!("&before={start}&after={end}"));
+    }
+
+    let doc: Value = limiter::http_parse_with_limiter(&url, &OKEX_LIMITER, 1, None, None).await?;
+
+    let list = doc["data"]
+        .as_array()
+        .ok_or_else(|| AdapterError::ParseError("Funding result is not an array".to_string()))?;
+
+    let mut funding: Vec<FundingRate> = list
+        .iter()
+        .filter_map(|row| {
+            let time = row["fundingTime"].as_str()?.parse::<u64>().ok()?;
+            let rate = row["realizedRate"]
+                .as_str()
+                .or_else(|| row["fundingRate"].as_str())?
+                .parse::<f32>()
+                .ok()?;
+            Some(FundingRate { time, rate })
+        })
+        .collect();
+    funding.sort_by_key(|x| x.time);
+
+    Ok(funding)
+}
+
 /// دریافت داده‌های کندل (Kline) از طریق API اوکی‌اکس
 pub async fn fetch_klines(
     ticker_info: TickerInfo,
@@ -740,7 +991,8 @@ pub async fn fetch_klines(
     })?;
 
     let mut url = format!(
-        "https://www.okx.com/api/v5/market/history-candles?instId={}&bar={}&limit={}",
+        "{}/api/v5/market/history-candles?instId={}&bar={}&limit={}",
+        Endpoint::OkexRest.resolve(),
         symbol_str,
         bar,
         match range {
@@ -818,8 +1070,6 @@ pub async fn fetch_klines(
     Ok(klines)
 }
 
-const TRADING_STATS_DOMAIN: &str = "https://www.okx.com/api/v5/rubik/stat";
-
 /// دریافت تاریخچه بهره باز (Open Interest) از اوکی‌اکس
 pub async fn fetch_historical_oi(
     ticker: Ticker,
@@ -831,8 +1081,10 @@ pub async fn fetch_historical_oi(
     let bar = timeframe_to_okx_bar(period)
         .ok_or_else(|| AdapterError::InvalidRequest(format!("Unsupported timeframe: {period}")))?;
 
-    let mut url = TRADING_STATS_DOMAIN.to_string()
-        + format!("/contracts/open-interest-history?instId={ticker_str}&period={bar}").as_str();
+    let mut url = format!(
+        "{}/api/v5/rubik/stat/contracts/open-interest-history?instId={ticker_str}&period={bar}",
+        Endpoint::OkexRest.resolve()
+    );
 
     if let Some((start, end)) = range {
         url.push_str(&format!("&begin={start}&end={end}"));
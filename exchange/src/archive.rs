@@ -0,0 +1,330 @@
+//! فایل‌های روزانه معاملات صرافی‌ها برای ساخت فوت‌پرینت فراتر از بازه‌ای که API پوشش می‌دهد
+//!
+//! هر آداپتور با پیاده‌سازی [`TradeHistory`] مشخص می‌کند معاملات گذشته را از کجا می‌دهد:
+//! API صرافی (با صفحه‌بندی مخصوص خودش) و در صورت وجود، فایل‌های روزانه‌ای که با
+//! [`TradeArchive`] آدرس و ساختار CSV آن‌ها تعریف می‌شود. دانلود، ذخیره در پوشه داده‌ها،
+//! باز کردن فایل فشرده و پیمایش روزها در این ماژول مشترک است.
+
+use crate::{Trade, adapter::AdapterError, fetcher::TradeSource, limiter};
+
+use chrono::{DateTime, Duration, NaiveDate};
+use csv::{ReaderBuilder, StringRecord};
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+/// منابع معاملات گذشته یک صرافی برای ساخت فوت‌پرینت
+pub trait TradeHistory {
+    type Archive: TradeArchive;
+
+    /// فایل‌های روزانه صرافی؛ `None` برای صرافی‌هایی که فایل روزانه منتشر نمی‌کنند
+    fn archive(&self) -> Option<&Self::Archive>;
+
+    /// آیا API صرافی معاملات گذشته را بر اساس زمان برمی‌گرداند؟
+    fn has_intraday(&self) -> bool {
+        false
+    }
+
+    /// معاملات از زمان `from` به بعد از API صرافی، مرتب بر اساس زمان؛
+    /// صفحه‌بندی درخواست‌ها بر عهده پیاده‌سازی است
+    fn fetch_intraday(
+        &self,
+        _from: u64,
+    ) -> impl Future<Output = Result<Vec<Trade>, AdapterError>> + Send {
+        std::future::ready(Err(AdapterError::InvalidRequest(
+            "Intraday trades are not available for this exchange, use archives instead".into(),
+        )))
+    }
+}
+
+/// قالب فشرده‌سازی فایل‌های روزانه
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    Zip,  // یک یا چند فایل CSV داخل یک فایل ZIP
+    Gzip, // یک فایل CSV فشرده شده با gzip
+}
+
+/// منبع فایل‌های روزانه معاملات یک صرافی
+pub trait TradeArchive {
+    /// آدرس پایه سرور فایل‌ها، بدون `/` پایانی
+    fn base_url(&self) -> &'static str;
+
+    /// مسیر نسبی فایل یک روز؛ همین مسیر برای ذخیره فایل در پوشه داده‌ها استفاده می‌شود
+    fn file_path(&self, date: NaiveDate) -> String;
+
+    fn compression(&self) -> Compression;
+
+    /// آیا سطر اول فایل CSV عنوان ستون‌هاست؟
+    fn has_headers(&self) -> bool;
+
+    /// اختلاف مرز روز فایل‌ها با UTC بر حسب ساعت (مثلاً ۸ برای فایل‌هایی که بر اساس UTC+8 تقسیم شده‌اند)
+    fn day_offset_hours(&self) -> i64 {
+        0
+    }
+
+    /// تبدیل یک سطر CSV به معامله؛ سطرهای نامعتبر نادیده گرفته می‌شوند
+    fn parse_record(&self, record: &StringRecord) -> Option<Trade>;
+
+    /// روزی از فایل‌ها که زمان داده شده (میلی‌ثانیه) در آن قرار دارد
+    fn archive_date(&self, time_ms: u64) -> Option<NaiveDate> {
+        DateTime::from_timestamp_millis(time_ms as i64)
+            .map(|time| (time + Duration::hours(self.day_offset_hours())).date_naive())
+    }
+}
+
+/// دریافت معاملات از زمان داده شده، بسته به منبع انتخاب شده (فایل‌های روزانه، API یا هر دو)
+///
+/// برای صرافی‌هایی که فقط یکی از دو منبع را دارند، حالت `Both` به همان منبع محدود می‌شود.
+pub async fn fetch_trades(
+    history: &impl TradeHistory,
+    from_time: u64,
+    base_path: &Path,
+    source: TradeSource,
+) -> Result<Vec<Trade>, AdapterError> {
+    let has_intraday = history.has_intraday();
+    let fetch_intraday = async |from: u64| {
+        if has_intraday || source == TradeSource::Intraday {
+            history.fetch_intraday(from).await
+        } else {
+            Ok(vec![])
+        }
+    };
+
+    let Some(archive) = history.archive() else {
+        return match source {
+            TradeSource::Historical => Err(AdapterError::InvalidRequest(
+                "Daily archives are not available for this exchange, use the API instead".into(),
+            )),
+            TradeSource::Both | TradeSource::Intraday => fetch_intraday(from_time).await,
+        };
+    };
+
+    let now_ms = chrono::Utc::now().timestamp_millis().max(0) as u64;
+    let (Some(mut from_date), Some(today)) = (
+        archive.archive_date(from_time),
+        archive.archive_date(now_ms),
+    ) else {
+        return Err(AdapterError::ParseError("Invalid timestamp".into()));
+    };
+
+    let is_today = from_date >= today;
+
+    match source {
+        TradeSource::Intraday => return fetch_intraday(from_time).await,
+        TradeSource::Both if is_today => return fetch_intraday(from_time).await,
+        // فایل روزانه امروز هنوز منتشر نشده است
+        TradeSource::Historical if is_today => return Ok(vec![]),
+        TradeSource::Both | TradeSource::Historical => {}
+    }
+
+    let mut trades = loop {
+        let hist_trades = match fetch_day(archive, from_date, base_path).await {
+            Ok(trades) => trades,
+            Err(e) if source == TradeSource::Both => {
+                log::warn!("Historical trades fetch failed: {e}, falling back to intraday fetch");
+                return fetch_intraday(from_time).await;
+            }
+            Err(e) => return Err(e),
+        };
+
+        // فایل روزانه کل روز را در بر می‌گیرد؛ معاملات پیش از زمان شروع کنار گذاشته می‌شوند
+        let trades = hist_trades
+            .into_iter()
+            .filter(|trade| trade.time >= from_time)
+            .collect::<Vec<_>>();
+
+        // اگر فقط معاملات همان لحظه شروع باقی مانده باشد، این روز تمام شده است
+        if trades.iter().any(|trade| trade.time > from_time) {
+            break trades;
+        }
+
+        // همه معاملات این روز قبلاً دریافت شده‌اند؛ سراغ روز بعد می‌رویم
+        match from_date.succ_opt() {
+            Some(next) if next < today => from_date = next,
+            _ if source == TradeSource::Both => return fetch_intraday(from_time).await,
+            _ => return Ok(vec![]),
+        }
+    };
+
+    if source == TradeSource::Both
+        && has_intraday
+        && let Some(latest_trade_t) = trades.last().map(|trade| trade.time)
+    {
+        match fetch_intraday(latest_trade_t).await {
+            Ok(intraday_trades) => trades.extend(intraday_trades),
+            Err(e) => log::error!("Failed to fetch intraday trades: {e}"),
+        }
+    }
+
+    Ok(trades)
+}
+
+/// معاملات یک روز؛ فایل در صورت نبود در پوشه داده‌ها دانلود و ذخیره می‌شود
+pub async fn fetch_day(
+    archive: &impl TradeArchive,
+    date: NaiveDate,
+    base_path: &Path,
+) -> Result<Vec<Trade>, AdapterError> {
+    let file_path = archive.file_path(date);
+    let cached_path = base_path.join(&file_path);
+
+    if std::fs::metadata(&cached_path).is_ok() {
+        log::info!("Using cached {file_path}");
+    } else {
+        let url = format!("{}/{file_path}", archive.base_url());
+
+        log::info!("Downloading from {url}");
+
+        let resp = limiter::HTTP_CLIENT
+            .get(&url)
+            .send()
+            .await
+            .map_err(AdapterError::FetchError)?;
+
+        if !resp.status().is_success() {
+            return Err(AdapterError::InvalidRequest(format!(
+                "Failed to fetch from {}: {}",
+                url,
+                resp.status()
+            )));
+        }
+
+        let body = resp.bytes().await.map_err(AdapterError::FetchError)?;
+
+        if let Some(parent) = cached_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                AdapterError::ParseError(format!("Failed to create directories: {e}"))
+            })?;
+        }
+        std::fs::write(&cached_path, &body).map_err(|e| {
+            AdapterError::ParseError(format!("Failed to write archive: {e}, {cached_path:?}"))
+        })?;
+    }
+
+    let file = std::fs::File::open(&cached_path)
+        .map_err(|e| AdapterError::ParseError(format!("Failed to open compressed file: {e}")))?;
+
+    let mut trades = Vec::new();
+
+    match archive.compression() {
+        Compression::Zip => {
+            let mut zip = zip::ZipArchive::new(file)
+                .map_err(|e| AdapterError::ParseError(format!("Failed to unzip file: {e}")))?;
+
+            for i in 0..zip.len() {
+                let csv_file = zip
+                    .by_index(i)
+                    .map_err(|e| AdapterError::ParseError(format!("Failed to read csv: {e}")))?;
+
+                read_csv(archive, csv_file, &mut trades);
+            }
+        }
+        Compression::Gzip => {
+            read_csv(archive, flate2::read::GzDecoder::new(file), &mut trades);
+        }
+    }
+
+    // برخی صرافی‌ها معاملات را از جدید به قدیم ذخیره می‌کنند
+    trades.sort_by_key(|trade| trade.time);
+
+    Ok(trades)
+}
+
+fn read_csv(archive: &impl TradeArchive, reader: impl Read, trades: &mut Vec<Trade>) {
+    let mut csv_reader = ReaderBuilder::new()
+        .has_headers(archive.has_headers())
+        .from_reader(BufReader::new(reader));
+
+    trades.extend(
+        csv_reader
+            .records()
+            .filter_map(|record| archive.parse_record(&record.ok()?)),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Hk;
+
+    impl TradeArchive for Hk {
+        fn base_url(&self) -> &'static str {
+            ""
+        }
+
+        fn file_path(&self, _date: NaiveDate) -> String {
+            String::new()
+        }
+
+        fn compression(&self) -> Compression {
+            Compression::Zip
+        }
+
+        fn has_headers(&self) -> bool {
+            true
+        }
+
+        fn day_offset_hours(&self) -> i64 {
+            8
+        }
+
+        fn parse_record(&self, _record: &StringRecord) -> Option<Trade> {
+            None
+        }
+    }
+
+    /// صرافی‌ای که فقط API معاملات گذشته دارد
+    struct ApiOnly;
+
+    impl TradeHistory for ApiOnly {
+        type Archive = Hk;
+
+        fn archive(&self) -> Option<&Hk> {
+            None
+        }
+
+        fn has_intraday(&self) -> bool {
+            true
+        }
+
+        fn fetch_intraday(
+            &self,
+            from: u64,
+        ) -> impl Future<Output = Result<Vec<Trade>, AdapterError>> + Send {
+            std::future::ready(Ok(vec![Trade {
+                time: from,
+                is_sell: false,
+                price: crate::Price::from_f32(1.0),
+                qty: 1.0,
+            }]))
+        }
+    }
+
+    #[tokio::test]
+    async fn missing_archive_falls_back_to_api() {
+        let path = Path::new("");
+
+        let trades = fetch_trades(&ApiOnly, 42, path, TradeSource::Both)
+            .await
+            .unwrap();
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].time, 42);
+
+        assert!(
+            fetch_trades(&ApiOnly, 42, path, TradeSource::Historical)
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn archive_date_follows_day_offset() {
+        // 2024-01-01 17:00 UTC در فایل‌های UTC+8 به روز ۲ ژانویه تعلق دارد
+        let time_ms = 1_704_128_400_000;
+
+        assert_eq!(
+            Hk.archive_date(time_ms),
+            NaiveDate::from_ymd_opt(2024, 1, 2)
+        );
+    }
+}
@@ -0,0 +1,141 @@
+//! ضبط فریم‌های خام عمق بازار برای بازتولید مشکلات همگام‌سازی دفتر سفارش
+//!
+//! هر فایل ضبط شامل یک سرآیند با مشخصات نماد و سپس به ترتیب زمان دریافت، متن خام
+//! تصاویر REST و فریم‌های وب‌سوکت عمق است. بازپخش همان مسیر `DepthUpdate` را طی می‌کند.
+
+use crate::TickerInfo;
+
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::RwLock,
+};
+
+static CAPTURE_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// فعال یا غیرفعال کردن ضبط عمق بازار در پوشه مشخص شده
+pub fn set_depth_capture_dir(dir: Option<PathBuf>) {
+    if let Ok(mut capture_dir) = CAPTURE_DIR.write() {
+        *capture_dir = dir;
+    }
+}
+
+pub fn depth_capture_dir() -> Option<PathBuf> {
+    CAPTURE_DIR.read().ok().and_then(|dir| dir.clone())
+}
+
+/// یک رکورد در فایل ضبط (هر خط یک رکورد JSON)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CaptureRecord {
+    Header { ticker_info: TickerInfo }, // مشخصات نماد ضبط شده
+    Snapshot { body: String },          // پاسخ خام REST تصویر دفتر سفارش
+    Frame { body: String },             // فریم خام وب‌سوکت عمق
+}
+
+/// نویسنده فایل ضبط برای یک جریان عمق
+pub struct DepthCapture {
+    path: PathBuf,
+    writer: BufWriter<File>,
+}
+
+impl DepthCapture {
+    /// ایجاد فایل ضبط جدید با نام نماد و زمان شروع
+    pub fn create(dir: &Path, ticker_info: TickerInfo) -> std::io::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+
+        let (symbol, _) = ticker_info.ticker.to_full_symbol_and_type();
+        let file_name = format!(
+            "{:?}_{}_{}.jsonl",
+            ticker_info.exchange(),
+            symbol,
+            chrono::Utc::now().format("%Y%m%d-%H%M%S"),
+        );
+        let path = dir.join(file_name);
+
+        let mut capture = Self {
+            writer: BufWriter::new(File::create(&path)?),
+            path,
+        };
+        capture.record(&CaptureRecord::Header { ticker_info });
+
+        Ok(capture)
+    }
+
+    /// ایجاد ضبط در صورت فعال بودن آن؛ خطاها فقط ثبت می‌شوند تا جریان زنده متوقف نشود
+    pub fn start(ticker_info: TickerInfo) -> Option<Self> {
+        let dir = depth_capture_dir()?;
+
+        match Self::create(&dir, ticker_info) {
+            Ok(capture) => {
+                log::info!("Capturing depth frames to {}", capture.path.display());
+                Some(capture)
+            }
+            Err(e) => {
+                log::error!("Failed to create depth capture in {}: {e}", dir.display());
+                None
+            }
+        }
+    }
+
+    pub fn record(&mut self, record: &CaptureRecord) {
+        let result = serde_json::to_writer(&mut self.writer, record)
+            .map_err(std::io::Error::from)
+            .and_then(|()| self.writer.write_all(b"\n"));
+
+        if let Err(e) = result {
+            log::error!("Failed to write depth capture {}: {e}", self.path.display());
+        }
+    }
+
+    pub fn record_snapshot(&mut self, body: &str) {
+        self.record(&CaptureRecord::Snapshot {
+            body: body.to_string(),
+        });
+        // تصاویر نادر هستند؛ با هر تصویر بافر تخلیه می‌شود تا فایل در صورت قطع برنامه قابل استفاده بماند
+        if let Err(e) = self.writer.flush() {
+            log::error!("Failed to flush depth capture {}: {e}", self.path.display());
+        }
+    }
+
+    pub fn record_frame(&mut self, body: &[u8]) {
+        self.record(&CaptureRecord::Frame {
+            body: String::from_utf8_lossy(body).into_owned(),
+        });
+    }
+}
+
+/// خواندن تمام رکوردهای یک فایل ضبط
+pub fn read_capture(path: &Path) -> Result<Vec<CaptureRecord>, String> {
+    let file = File::open(path).map_err(|e| format!("{}: {e}", path.display()))?;
+
+    BufReader::new(file)
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(l) if l.trim().is_empty()))
+        .map(|(idx, line)| {
+            let line = line.map_err(|e| e.to_string())?;
+            serde_json::from_str(&line).map_err(|e| format!("line {}: {e}", idx + 1))
+        })
+        .collect()
+}
+
+/// یک رویداد ناهمگامی که در هنگام بازپخش مشاهده شده است
+#[derive(Debug, Clone)]
+pub struct ResyncEvent {
+    pub record: usize,  // شماره رکورد در فایل ضبط
+    pub reason: String, // توضیح علت
+}
+
+/// گزارش نهایی بازپخش یک فایل ضبط
+#[derive(Debug, Clone, Default)]
+pub struct ReplayReport {
+    pub frames: usize,             // تعداد فریم‌های عمق خوانده شده
+    pub snapshots: usize,          // تعداد تصاویر اعمال شده
+    pub diffs_applied: usize,      // تعداد تغییرات اعمال شده
+    pub stale: usize,              // فریم‌های قدیمی‌تر از تصویر که نادیده گرفته شدند
+    pub resyncs: Vec<ResyncEvent>, // رویدادهای ناهمگامی
+    pub last_update_id: u64,       // شناسه آخرین بروزرسانی پس از بازپخش
+}
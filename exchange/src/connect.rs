@@ -1,6 +1,9 @@
-use crate::adapter::AdapterError;
+mod deflate;
+
+use crate::adapter::{AdapterError, ExchangeInclusive};
 use bytes::Bytes;
-use fastwebsockets::FragmentCollector;
+use deflate::InflateStream;
+use fastwebsockets::{FragmentCollector, Role, WebSocket};
 use http_body_util::Empty;
 use hyper::{
     Request,
@@ -8,34 +11,73 @@ use hyper::{
     upgrade::Upgraded,
 };
 use hyper_util::rt::TokioIo;
+use reqwest::Url;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
 use tokio_rustls::{
     TlsConnector,
     rustls::{ClientConfig, OwnedTrustAnchor},
 };
 
+/// اتصال وب‌سوکت برقرار شده؛ فریم‌های فشرده پیش از رسیدن به `fastwebsockets` باز می‌شوند
+pub type Connection = FragmentCollector<InflateStream<TokioIo<Upgraded>>>;
+
 /// وضعیت اتصال وب‌سوکت
 #[allow(clippy::large_enum_variant)]
 pub enum State {
-    Disconnected,                                   // قطع شده
-    Connected(FragmentCollector<TokioIo<Upgraded>>), // متصل شده
+    Disconnected,          // قطع شده
+    Connected(Connection), // متصل شده
 }
 
-/// برقراری اتصال وب‌سوکت امن (WSS)
+/// برقراری اتصال وب‌سوکت امن (WSS)؛ هر مرحله محدودیت زمانی دارد تا اتصال معلق
+/// به جای انتظار بی‌پایان با خطای `Timeout` به منطق اتصال مجدد برگردد.
+/// اگر فشرده‌سازی برای صرافی فعال باشد `permessage-deflate` پیشنهاد می‌شود
+/// و در صورت نپذیرفتن سرور، اتصال بدون فشرده‌سازی ادامه می‌یابد
 pub async fn connect_ws(
     domain: &str,
     url: &str,
-) -> Result<
-    fastwebsockets::FragmentCollector<hyper_util::rt::TokioIo<hyper::upgrade::Upgraded>>,
-    AdapterError,
-> {
+    venue: ExchangeInclusive,
+) -> Result<Connection, AdapterError> {
+    let connect_timeout = crate::network::connect_timeout();
+    let handshake_timeout = crate::network::handshake_timeout();
+    let compression = crate::network::ws_compression(venue);
+
     // ۱. راه‌اندازی اتصال TCP
-    let tcp_stream = setup_tcp(domain).await?;
+    let tcp_stream = with_timeout("Connecting", domain, connect_timeout, setup_tcp(domain)).await?;
     // ۲. ارتقا به لایه امن TLS
-    let tls_stream = upgrade_to_tls(domain, tcp_stream).await?;
+    let tls_stream = with_timeout(
+        "TLS handshake",
+        domain,
+        handshake_timeout,
+        upgrade_to_tls(domain, tcp_stream),
+    )
+    .await?;
 
     // ۳. انجام دست‌دهی (Handshake) وب‌سوکت
-    upgrade_to_websocket(domain, tls_stream, url).await
+    with_timeout(
+        "Websocket handshake",
+        domain,
+        handshake_timeout,
+        upgrade_to_websocket(domain, tls_stream, url, compression),
+    )
+    .await
+}
+
+/// اجرای یک مرحله اتصال با محدودیت زمانی
+async fn with_timeout<T>(
+    stage: &str,
+    domain: &str,
+    limit: Duration,
+    step: impl Future<Output = Result<T, AdapterError>>,
+) -> Result<T, AdapterError> {
+    tokio::time::timeout(limit, step).await.map_err(|_| {
+        AdapterError::Timeout(format!(
+            "{stage} to {domain} took longer than {}s",
+            limit.as_secs()
+        ))
+    })?
 }
 
 /// ساختار کمکی برای اجرای کارهای ناهمگام در پس‌زمینه
@@ -51,12 +93,155 @@ where
     }
 }
 
-/// راه‌اندازی اتصال TCP به دامنه مورد نظر روی پورت ۴۴۳
+/// راه‌اندازی اتصال TCP به دامنه مورد نظر روی پورت ۴۴۳ (در صورت تنظیم پروکسی از طریق تونل آن)
 async fn setup_tcp(domain: &str) -> Result<TcpStream, AdapterError> {
     let addr = format!("{domain}:443");
-    TcpStream::connect(&addr)
+
+    if let Some(proxy) = crate::network::proxy() {
+        return tunnel_through_proxy(&proxy, &addr).await;
+    }
+
+    let mut addrs = tokio::net::lookup_host(&addr)
         .await
-        .map_err(|e| AdapterError::WebsocketError(e.to_string()))
+        .map_err(|e| AdapterError::WebsocketError(format!("DNS lookup for {domain} failed: {e}")))?
+        .collect::<Vec<_>>();
+
+    if crate::network::prefer_ipv4() {
+        prefer_ipv4(&mut addrs);
+    }
+
+    // نشانی‌ها به ترتیب امتحان می‌شوند؛ خطای آخرین تلاش گزارش می‌شود
+    let mut last_error = None;
+    for addr in addrs {
+        match TcpStream::connect(addr).await {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last_error = Some(e),
+        }
+    }
+
+    Err(AdapterError::WebsocketError(match last_error {
+        Some(e) => e.to_string(),
+        None => format!("No addresses found for {domain}"),
+    }))
+}
+
+/// نشانی‌های IPv4 به ابتدای فهرست منتقل می‌شوند؛ ترتیب نسبی هر گروه حفظ می‌شود
+fn prefer_ipv4(addrs: &mut [SocketAddr]) {
+    addrs.sort_by_key(|addr| !addr.is_ipv4());
+}
+
+/// ایجاد تونل TCP با درخواست `CONNECT` روی پروکسی HTTP
+async fn tunnel_through_proxy(proxy: &Url, addr: &str) -> Result<TcpStream, AdapterError> {
+    let host = proxy
+        .host_str()
+        .ok_or_else(|| AdapterError::WebsocketError("Proxy URL has no host".to_string()))?;
+    let port = proxy.port_or_known_default().unwrap_or(80);
+
+    let mut stream = TcpStream::connect((host, port))
+        .await
+        .map_err(|e| AdapterError::WebsocketError(format!("Proxy connection failed: {e}")))?;
+
+    let mut request = format!("CONNECT {addr} HTTP/1.1\r\nHost: {addr}\r\n");
+    if !proxy.username().is_empty() {
+        let credentials = format!(
+            "{}:{}",
+            percent_decode(proxy.username()),
+            percent_decode(proxy.password().unwrap_or_default())
+        );
+        request.push_str(&format!(
+            "Proxy-Authorization: Basic {}\r\n",
+            base64_encode(credentials.as_bytes())
+        ));
+    }
+    request.push_str("\r\n");
+
+    stream
+        .write_all(request.as_bytes())
+        .await
+        .map_err(|e| AdapterError::WebsocketError(e.to_string()))?;
+
+    // خواندن هدرهای پاسخ تا خط خالی؛ داده‌ای پس از آن ارسال نمی‌شود تا دست‌دهی TLS آغاز شود
+    let mut response = Vec::with_capacity(256);
+    let mut byte = [0u8; 1];
+    while !response.ends_with(b"\r\n\r\n") {
+        if response.len() > 8 * 1024 {
+            return Err(AdapterError::WebsocketError(
+                "Proxy response headers too large".to_string(),
+            ));
+        }
+        let read = stream
+            .read(&mut byte)
+            .await
+            .map_err(|e| AdapterError::WebsocketError(e.to_string()))?;
+        if read == 0 {
+            return Err(AdapterError::WebsocketError(
+                "Proxy closed the connection".to_string(),
+            ));
+        }
+        response.push(byte[0]);
+    }
+
+    let response = String::from_utf8_lossy(&response);
+    let status_line = response.lines().next().unwrap_or_default();
+    let status = status_line.split_whitespace().nth(1);
+    if status != Some("200") {
+        return Err(AdapterError::WebsocketError(format!(
+            "Proxy refused tunnel to {addr}: {status_line}"
+        )));
+    }
+
+    Ok(stream)
+}
+
+/// رمزگشایی کاراکترهای درصدی در نام کاربری و رمز عبور آدرس پروکسی
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// کدگذاری Base64 استاندارد برای هدر احراز هویت پروکسی
+fn base64_encode(input: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut output = String::with_capacity(input.len().div_ceil(3) * 4);
+    for chunk in input.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                output.push(char::from(ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize]));
+            } else {
+                output.push('=');
+            }
+        }
+    }
+    output
 }
 
 /// ایجاد تنظیمات و کانکتور TLS با استفاده از گواهی‌های ریشه سیستم
@@ -99,8 +284,9 @@ async fn upgrade_to_websocket(
     domain: &str,
     tls_stream: tokio_rustls::client::TlsStream<TcpStream>,
     url: &str,
-) -> Result<FragmentCollector<TokioIo<Upgraded>>, AdapterError> {
-    let req: Request<Empty<Bytes>> = Request::builder()
+    compression: bool,
+) -> Result<Connection, AdapterError> {
+    let mut builder = Request::builder()
         .method("GET")
         .uri(url)
         .header("Host", domain)
@@ -110,13 +296,64 @@ async fn upgrade_to_websocket(
             "Sec-WebSocket-Key",
             fastwebsockets::handshake::generate_key(),
         )
-        .header("Sec-WebSocket-Version", "13")
+        .header("Sec-WebSocket-Version", "13");
+    if compression {
+        builder = builder.header("Sec-WebSocket-Extensions", deflate::OFFER);
+    }
+    let req: Request<Empty<Bytes>> = builder
         .body(Empty::<Bytes>::new())
         .map_err(|e| AdapterError::WebsocketError(e.to_string()))?;
 
-    let (ws, _) = fastwebsockets::handshake::client(&SpawnExecutor, req, tls_stream)
+    let (ws, response) = fastwebsockets::handshake::client(&SpawnExecutor, req, tls_stream)
         .await
         .map_err(|e| AdapterError::WebsocketError(e.to_string()))?;
 
-    Ok(FragmentCollector::new(ws))
+    // سروری که افزونه را نپذیرد آن را در پاسخ تکرار نمی‌کند و فریم‌ها خام می‌مانند
+    let negotiated = compression
+        && deflate::negotiated(
+            response
+                .headers()
+                .get("sec-websocket-extensions")
+                .and_then(|value| value.to_str().ok()),
+        );
+    if compression && !negotiated {
+        log::debug!("{domain}: permessage-deflate was not accepted, using plain frames");
+    }
+
+    let io = InflateStream::new(ws.into_inner(), negotiated, domain);
+    Ok(FragmentCollector::new(WebSocket::after_handshake(
+        io,
+        Role::Client,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ipv4_addresses_are_tried_first() {
+        let mut addrs: Vec<SocketAddr> = [
+            "[2001:db8::1]:443",
+            "192.0.2.1:443",
+            "[2001:db8::2]:443",
+            "192.0.2.2:443",
+        ]
+        .iter()
+        .map(|addr| addr.parse().unwrap())
+        .collect();
+
+        prefer_ipv4(&mut addrs);
+
+        let ordered = addrs.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(
+            ordered,
+            [
+                "192.0.2.1:443",
+                "192.0.2.2:443",
+                "[2001:db8::1]:443",
+                "[2001:db8::2]:443"
+            ]
+        );
+    }
 }
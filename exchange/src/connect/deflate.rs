@@ -0,0 +1,341 @@
+//! باز کردن فشرده‌سازی `permessage-deflate` (RFC 7692) پیش از رسیدن فریم‌ها به `fastwebsockets`
+//!
+//! `fastwebsockets` فریم‌هایی با بیت RSV1 را رد می‌کند، پس این لایه بین اتصال ارتقا یافته و
+//! وب‌سوکت قرار می‌گیرد: فریم‌های فشرده سرور باز شده و بدون RSV1 دوباره قاب‌بندی می‌شوند.
+//! فریم‌های ارسالی کلاینت فشرده نمی‌شوند که طبق RFC مجاز است.
+
+use flate2::{Decompress, FlushDecompress};
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+/// مقدار هدر `Sec-WebSocket-Extensions` درخواست ارتقا
+pub const OFFER: &str = "permessage-deflate; client_max_window_bits";
+
+/// انتهای حذف شده هر پیام فشرده که پیش از باز کردن به آن اضافه می‌شود
+const MESSAGE_TAIL: [u8; 4] = [0x00, 0x00, 0xff, 0xff];
+
+/// فاصله گزارش میزان صرفه‌جویی در لاگ (بایت باز شده)
+const LOG_EVERY_BYTES: u64 = 16 * 1024 * 1024;
+
+const RSV1: u8 = 0b0100_0000;
+const FIN: u8 = 0b1000_0000;
+
+/// آیا پاسخ سرور فشرده‌سازی را پذیرفته است
+pub fn negotiated(extensions: Option<&str>) -> bool {
+    extensions.is_some_and(|value| {
+        value
+            .split(',')
+            .any(|ext| ext.split(';').next().map(str::trim) == Some("permessage-deflate"))
+    })
+}
+
+/// جریان اتصال با باز کردن اختیاری فریم‌های فشرده؛ در حالت غیرفعال بدون تغییر عبور می‌کند
+pub struct InflateStream<S> {
+    inner: S,
+    inflater: Option<Inflater>,
+}
+
+struct Inflater {
+    decompress: Decompress,
+    wire: Vec<u8>,       // بایت‌های خام دریافتی که هنوز فریم کامل نشده‌اند
+    ready: Vec<u8>,      // فریم‌های بازنویسی شده آماده تحویل
+    ready_pos: usize,    // مقدار خوانده شده از `ready`
+    in_compressed: bool, // فریم‌های ادامه متعلق به یک پیام فشرده‌اند
+    wire_bytes: u64,     // حجم فشرده دریافتی
+    inflated_bytes: u64, // حجم پس از باز کردن
+    next_log: u64,
+    label: String,
+}
+
+impl<S> InflateStream<S> {
+    pub fn new(inner: S, enabled: bool, label: &str) -> Self {
+        Self {
+            inner,
+            inflater: enabled.then(|| Inflater {
+                decompress: Decompress::new(false),
+                wire: Vec::new(),
+                ready: Vec::new(),
+                ready_pos: 0,
+                in_compressed: false,
+                wire_bytes: 0,
+                inflated_bytes: 0,
+                next_log: LOG_EVERY_BYTES,
+                label: label.to_string(),
+            }),
+        }
+    }
+}
+
+impl Inflater {
+    /// پردازش فریم‌های کامل موجود در `wire` و افزودن نسخه بازنویسی شده آن‌ها به `ready`
+    fn drain_frames(&mut self) -> io::Result<()> {
+        while let Some((header_len, payload_len, mask)) = parse_header(&self.wire) {
+            let frame_len = header_len + payload_len;
+            if self.wire.len() < frame_len {
+                break;
+            }
+
+            let first = self.wire[0];
+            let opcode = first & 0x0f;
+            let fin = first & FIN != 0;
+            let mut payload = self.wire[header_len..frame_len].to_vec();
+            self.wire.drain(..frame_len);
+
+            if let Some(mask) = mask {
+                payload
+                    .iter_mut()
+                    .enumerate()
+                    .for_each(|(i, byte)| *byte ^= mask[i % 4]);
+            }
+
+            let is_control = opcode >= 0x8;
+            let compressed = !is_control
+                && ((opcode != 0x0 && first & RSV1 != 0) || (opcode == 0x0 && self.in_compressed));
+
+            if !is_control && opcode != 0x0 {
+                self.in_compressed = compressed && !fin;
+            } else if opcode == 0x0 && fin {
+                self.in_compressed = false;
+            }
+
+            if compressed {
+                self.wire_bytes += payload.len() as u64;
+                if fin {
+                    payload.extend_from_slice(&MESSAGE_TAIL);
+                }
+                payload = self.inflate(&payload)?;
+                self.inflated_bytes += payload.len() as u64;
+                self.log_savings();
+            }
+
+            write_frame(&mut self.ready, first & !RSV1, &payload);
+        }
+        Ok(())
+    }
+
+    fn inflate(&mut self, input: &[u8]) -> io::Result<Vec<u8>> {
+        let mut output = Vec::with_capacity(input.len() * 4);
+        let mut consumed = 0;
+
+        loop {
+            if output.len() == output.capacity() {
+                output.reserve(output.capacity().max(1024));
+            }
+
+            let (in_before, out_before) = (self.decompress.total_in(), self.decompress.total_out());
+            self.decompress
+                .decompress_vec(&input[consumed..], &mut output, FlushDecompress::Sync)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            consumed += (self.decompress.total_in() - in_before) as usize;
+
+            let progressed = self.decompress.total_in() != in_before
+                || self.decompress.total_out() != out_before;
+            if !progressed || (consumed >= input.len() && output.len() < output.capacity()) {
+                return Ok(output);
+            }
+        }
+    }
+
+    fn log_savings(&mut self) {
+        if self.inflated_bytes < self.next_log {
+            return;
+        }
+        self.next_log = self.inflated_bytes + LOG_EVERY_BYTES;
+
+        log::debug!(
+            "Websocket compression for {}: {} wire bytes for {} bytes of messages ({:.0}% saved)",
+            self.label,
+            self.wire_bytes,
+            self.inflated_bytes,
+            (1.0 - self.wire_bytes as f64 / self.inflated_bytes as f64) * 100.0
+        );
+    }
+}
+
+/// طول سرآیند، طول محتوا و کلید ماسک فریم؛ `None` تا وقتی سرآیند کامل نرسیده است
+fn parse_header(buf: &[u8]) -> Option<(usize, usize, Option<[u8; 4]>)> {
+    if buf.len() < 2 {
+        return None;
+    }
+
+    let masked = buf[1] & 0x80 != 0;
+    let (mut header_len, payload_len) = match buf[1] & 0x7f {
+        126 => (
+            4,
+            u16::from_be_bytes(buf.get(2..4)?.try_into().ok()?) as usize,
+        ),
+        127 => (
+            10,
+            u64::from_be_bytes(buf.get(2..10)?.try_into().ok()?) as usize,
+        ),
+        len => (2, len as usize),
+    };
+
+    let mask = if masked {
+        let mask: [u8; 4] = buf.get(header_len..header_len + 4)?.try_into().ok()?;
+        header_len += 4;
+        Some(mask)
+    } else {
+        None
+    };
+
+    Some((header_len, payload_len, mask))
+}
+
+/// قاب‌بندی یک فریم بدون ماسک
+fn write_frame(out: &mut Vec<u8>, first: u8, payload: &[u8]) {
+    out.push(first);
+    match payload.len() {
+        len if len < 126 => out.push(len as u8),
+        len if len <= u16::MAX as usize => {
+            out.push(126);
+            out.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            out.push(127);
+            out.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    out.extend_from_slice(payload);
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for InflateStream<S> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        let Some(inflater) = this.inflater.as_mut() else {
+            return Pin::new(&mut this.inner).poll_read(cx, buf);
+        };
+
+        loop {
+            if inflater.ready_pos < inflater.ready.len() {
+                let available = &inflater.ready[inflater.ready_pos..];
+                let n = available.len().min(buf.remaining());
+                buf.put_slice(&available[..n]);
+                inflater.ready_pos += n;
+
+                if inflater.ready_pos == inflater.ready.len() {
+                    inflater.ready.clear();
+                    inflater.ready_pos = 0;
+                }
+                return Poll::Ready(Ok(()));
+            }
+
+            let mut chunk = [0u8; 8192];
+            let mut chunk_buf = ReadBuf::new(&mut chunk);
+            match Pin::new(&mut this.inner).poll_read(cx, &mut chunk_buf) {
+                Poll::Ready(Ok(())) => {
+                    let filled = chunk_buf.filled();
+                    // پایان جریان؛ بایت‌های ناقص باقی‌مانده کنار گذاشته می‌شوند
+                    if filled.is_empty() {
+                        return Poll::Ready(Ok(()));
+                    }
+                    inflater.wire.extend_from_slice(filled);
+                    inflater.drain_frames()?;
+                }
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for InflateStream<S> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().inner).poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().inner).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{Compress, Compression, FlushCompress};
+    use tokio::io::AsyncReadExt;
+
+    /// فشرده‌سازی یک پیام با زمینه مشترک، به شکلی که سرور ارسال می‌کند
+    fn deflate(compress: &mut Compress, message: &[u8]) -> Vec<u8> {
+        let mut out = Vec::with_capacity(message.len() + 64);
+        compress
+            .compress_vec(message, &mut out, FlushCompress::Sync)
+            .unwrap();
+        assert!(out.ends_with(&MESSAGE_TAIL));
+        out.truncate(out.len() - MESSAGE_TAIL.len());
+        out
+    }
+
+    fn read_frames(mut buf: &[u8]) -> Vec<(u8, Vec<u8>)> {
+        let mut frames = vec![];
+        while let Some((header_len, payload_len, _)) = parse_header(buf) {
+            frames.push((buf[0], buf[header_len..header_len + payload_len].to_vec()));
+            buf = &buf[header_len + payload_len..];
+        }
+        frames
+    }
+
+    #[tokio::test]
+    async fn compressed_frames_are_rewritten_as_plain_frames() {
+        let mut compress = Compress::new(Compression::default(), false);
+        let first = br#"{"bids":[["100.0","1.5"]],"asks":[["100.5","2.0"]]}"#.repeat(20);
+        let second = br#"{"bids":[["100.0","1.6"]],"asks":[["100.5","2.0"]]}"#.repeat(20);
+
+        let mut wire = vec![];
+        write_frame(&mut wire, FIN | RSV1 | 0x1, &deflate(&mut compress, &first));
+        // یک پیام فشرده در دو فریم، با یک ping میان آن‌ها
+        let split = deflate(&mut compress, &second);
+        let (head, tail) = split.split_at(split.len() / 2);
+        write_frame(&mut wire, RSV1 | 0x1, head);
+        write_frame(&mut wire, FIN | 0x9, b"ping");
+        write_frame(&mut wire, FIN, tail);
+        write_frame(&mut wire, FIN | 0x1, b"plain");
+
+        let mut stream = InflateStream::new(wire.as_slice(), true, "test");
+        let mut output = vec![];
+        stream.read_to_end(&mut output).await.unwrap();
+
+        let frames = read_frames(&output);
+        assert_eq!(frames[0], (FIN | 0x1, first.clone()));
+        assert_eq!(frames[2], (FIN | 0x9, b"ping".to_vec()));
+        assert_eq!([frames[1].1.clone(), frames[3].1.clone()].concat(), second);
+        assert_eq!(frames[3].0, FIN);
+        assert_eq!(frames[4], (FIN | 0x1, b"plain".to_vec()));
+        assert!(frames.iter().all(|(first, _)| first & RSV1 == 0));
+    }
+
+    #[test]
+    fn negotiation_reads_the_response_header() {
+        assert!(negotiated(Some(
+            "permessage-deflate; server_max_window_bits=15"
+        )));
+        assert!(!negotiated(Some("x-webkit-deflate-frame")));
+        assert!(!negotiated(None));
+    }
+}
@@ -1,9 +1,10 @@
-use crate::{MinTicksize, Price};
+use crate::{MinTicksize, Price, Ticker, adapter::StreamKind};
 
 use serde::Deserializer;
 use serde::de::Error as SerdeError;
 use serde_json::Value;
 
+use rustc_hash::FxHashMap;
 use std::{collections::BTreeMap, sync::Arc};
 
 /// ساختار کمکی برای دی‌سریال‌سازی یک سطح قیمتی در دفتر سفارش
@@ -84,15 +85,31 @@ impl std::fmt::Debug for Depth {
 }
 
 impl Depth {
-    fn update(&mut self, diff: &DepthPayload, min_ticksize: MinTicksize) {
-        Self::diff_price_levels(&mut self.bids, &diff.bids, min_ticksize);
-        Self::diff_price_levels(&mut self.asks, &diff.asks, min_ticksize);
+    fn update(
+        &mut self,
+        diff: &DepthPayload,
+        min_ticksize: MinTicksize,
+        mut record: Option<&mut DepthDiff>,
+    ) {
+        Self::diff_price_levels(
+            &mut self.bids,
+            &diff.bids,
+            min_ticksize,
+            record.as_deref_mut().map(|d| &mut d.bids),
+        );
+        Self::diff_price_levels(
+            &mut self.asks,
+            &diff.asks,
+            min_ticksize,
+            record.map(|d| &mut d.asks),
+        );
     }
 
     fn diff_price_levels(
         price_map: &mut BTreeMap<Price, f32>,
         orders: &[DeOrder],
         min_ticksize: MinTicksize,
+        mut record: Option<&mut Vec<(Price, f32)>>,
     ) {
         orders.iter().for_each(|order| {
             let order = Order {
@@ -100,6 +117,10 @@ impl Depth {
                 qty: order.qty,
             };
 
+            if let Some(levels) = record.as_deref_mut() {
+                levels.push((order.price, order.qty));
+            }
+
             if order.qty == 0.0 {
                 price_map.remove(&order.price);
             } else {
@@ -108,6 +129,19 @@ impl Depth {
         });
     }
 
+    /// اعمال تغییرات نرمال‌شده (قیمت‌های گرد شده) روی یک نسخه محلی
+    pub fn apply_diff(&mut self, diff: &DepthDiff) {
+        for (levels, price_map) in [(&diff.bids, &mut self.bids), (&diff.asks, &mut self.asks)] {
+            for &(price, qty) in levels {
+                if qty == 0.0 {
+                    price_map.remove(&price);
+                } else {
+                    price_map.insert(price, qty);
+                }
+            }
+        }
+    }
+
     fn replace_all(&mut self, snapshot: &DepthPayload, min_ticksize: MinTicksize) {
         self.bids = snapshot
             .bids
@@ -131,12 +165,58 @@ impl Depth {
             .collect::<BTreeMap<Price, f32>>();
     }
 
+    pub fn best_bid(&self) -> Option<Price> {
+        self.bids.last_key_value().map(|(price, _)| *price)
+    }
+
+    pub fn best_ask(&self) -> Option<Price> {
+        self.asks.first_key_value().map(|(price, _)| *price)
+    }
+
     pub fn mid_price(&self) -> Option<Price> {
         match (self.asks.first_key_value(), self.bids.last_key_value()) {
             (Some((ask_price, _)), Some((bid_price, _))) => Some((*ask_price + *bid_price) / 2),
             _ => None,
         }
     }
+
+    /// میانگین وزنی بهترین خرید و فروش بر اساس حجم طرف مقابل
+    ///
+    /// اگر حجم یکی از دو طرف صفر باشد، به میانه ساده بازمی‌گردد.
+    pub fn microprice(&self) -> Option<Price> {
+        let (&ask_price, &ask_qty) = self.asks.first_key_value()?;
+        let (&bid_price, &bid_qty) = self.bids.last_key_value()?;
+
+        if bid_qty <= 0.0 || ask_qty <= 0.0 {
+            return Some((ask_price + bid_price) / 2);
+        }
+
+        let (bid_qty, ask_qty) = (f64::from(bid_qty), f64::from(ask_qty));
+        let weighted = (bid_price.units as f64 * ask_qty + ask_price.units as f64 * bid_qty)
+            / (bid_qty + ask_qty);
+
+        Some(Price::from_units(weighted.round() as i64))
+    }
+}
+
+/// تغییرات سطوح قیمت از آخرین انتشار؛ مقدار صفر یعنی حذف سطح
+#[derive(Debug, Clone, Default)]
+pub struct DepthDiff {
+    pub bids: Vec<(Price, f32)>, // تغییرات سمت خرید
+    pub asks: Vec<(Price, f32)>, // تغییرات سمت فروش
+}
+
+impl DepthDiff {
+    pub fn is_empty(&self) -> bool {
+        self.bids.is_empty() && self.asks.is_empty()
+    }
+}
+
+/// شکل اشتراکی عمق بازار که همراه رویداد ارسال می‌شود
+#[derive(Debug, Clone)]
+pub enum DepthChange {
+    Snapshot(Arc<Depth>), // تصویر کامل؛ فقط پس از همگام‌سازی یا اتصال مجدد ارسال می‌شود
+    Diff(Arc<DepthDiff>), // تغییرات از آخرین انتشار
 }
 
 /// حافظه موقت محلی برای نگهداری و بروزرسانی عمق بازار یک نماد
@@ -144,7 +224,9 @@ impl Depth {
 pub struct LocalDepthCache {
     pub last_update_id: u64, // آخرین شناسه بروزرسانی اعمال شده
     pub time: u64,           // زمان آخرین بروزرسانی
-    pub depth: Arc<Depth>,   // وضعیت فعلی عمق بازار (به صورت اشتراکی)
+    pub depth: Depth,        // وضعیت فعلی عمق بازار
+    pending: DepthDiff,      // تغییرات منتشر نشده
+    published: bool,         // آیا مصرف‌کننده تصویر کامل فعلی را دریافت کرده است
 }
 
 impl LocalDepthCache {
@@ -154,16 +236,191 @@ impl LocalDepthCache {
                 self.last_update_id = snapshot.last_update_id;
                 self.time = snapshot.time;
 
-                let depth = Arc::make_mut(&mut self.depth);
-                depth.replace_all(&snapshot, min_ticksize);
+                self.depth.replace_all(&snapshot, min_ticksize);
+
+                self.pending = DepthDiff::default();
+                self.published = false;
             }
             DepthUpdate::Diff(diff) => {
                 self.last_update_id = diff.last_update_id;
                 self.time = diff.time;
 
-                let depth = Arc::make_mut(&mut self.depth);
-                depth.update(&diff, min_ticksize);
+                let record = self.published.then_some(&mut self.pending);
+                self.depth.update(&diff, min_ticksize, record);
+            }
+        }
+    }
+
+    /// آماده‌سازی داده برای ارسال؛ پس از هر تصویر کامل یک بار کل دفتر و در غیر این صورت فقط تغییرات
+    pub fn publish(&mut self) -> DepthChange {
+        if self.published {
+            DepthChange::Diff(Arc::new(std::mem::take(&mut self.pending)))
+        } else {
+            self.published = true;
+            self.pending = DepthDiff::default();
+            DepthChange::Snapshot(Arc::new(self.depth.clone()))
+        }
+    }
+}
+
+/// نسخه‌های محلی دفتر سفارش در سمت مصرف‌کننده که با تغییرات دریافتی بروز نگه داشته می‌شوند
+#[derive(Default)]
+pub struct DepthReplicas {
+    books: FxHashMap<StreamKind, Depth>,
+}
+
+impl DepthReplicas {
+    /// اعمال تغییر دریافتی روی نسخه مربوط به جریان و بازگرداندن وضعیت فعلی آن
+    pub fn apply(&mut self, stream: &StreamKind, change: &DepthChange) -> &Depth {
+        let book = self.books.entry(*stream).or_default();
+
+        match change {
+            DepthChange::Snapshot(depth) => {
+                book.clone_from(depth);
+            }
+            DepthChange::Diff(diff) => {
+                book.apply_diff(diff);
             }
         }
+
+        book
+    }
+
+    /// حذف همه نسخه‌ها؛ تغییر بعدی هر جریان از یک دفتر خالی شروع می‌شود
+    pub fn clear(&mut self) {
+        self.books.clear();
+    }
+
+    /// جریان‌هایی که نسخه محلی دارند
+    pub fn streams(&self) -> impl Iterator<Item = &StreamKind> {
+        self.books.keys()
+    }
+
+    /// حذف نسخه جریانی که اشتراکش لغو شده است
+    pub fn remove(&mut self, stream: &StreamKind) {
+        self.books.remove(stream);
+    }
+
+    /// حذف نسخه‌های یک نماد هنگام اشتراک دوباره جریان‌های آن
+    pub fn remove_ticker(&mut self, ticker: &Ticker) {
+        self.books
+            .retain(|stream, _| stream.ticker_info().ticker != *ticker);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.books.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn depth_stream() -> StreamKind {
+        StreamKind::DepthAndTrades {
+            ticker_info: crate::TickerInfo::new(
+                crate::Ticker::new("BTCUSDT", crate::adapter::Exchange::BinanceLinear),
+                0.1,
+                0.001,
+                None,
+            ),
+            depth_aggr: crate::adapter::StreamTicksize::Client,
+            push_freq: crate::PushFrequency::ServerDefault,
+        }
+    }
+
+    #[test]
+    fn microprice_leans_towards_thinner_side() {
+        let depth = Depth {
+            bids: BTreeMap::from([(Price::from_f32(99.0), 1.0), (Price::from_f32(100.0), 3.0)]),
+            asks: BTreeMap::from([(Price::from_f32(101.0), 1.0)]),
+        };
+        assert_eq!(depth.microprice(), Some(Price::from_f32(100.75)));
+
+        let one_sided = Depth {
+            bids: BTreeMap::from([(Price::from_f32(100.0), 0.0)]),
+            asks: BTreeMap::from([(Price::from_f32(101.0), 2.0)]),
+        };
+        assert_eq!(one_sided.microprice(), one_sided.mid_price());
+    }
+
+    fn payload(id: u64, bids: &[(f32, f32)], asks: &[(f32, f32)]) -> DepthPayload {
+        let to_orders = |levels: &[(f32, f32)]| {
+            levels
+                .iter()
+                .map(|&(price, qty)| DeOrder { price, qty })
+                .collect()
+        };
+
+        DepthPayload {
+            last_update_id: id,
+            time: id,
+            bids: to_orders(bids),
+            asks: to_orders(asks),
+        }
+    }
+
+    #[test]
+    fn replica_follows_source_through_diffs() {
+        let min_ticksize = MinTicksize::from(0.1);
+        let stream = depth_stream();
+
+        let mut source = LocalDepthCache::default();
+        let mut replicas = DepthReplicas::default();
+
+        source.update(
+            DepthUpdate::Snapshot(payload(1, &[(99.9, 1.0), (99.8, 2.0)], &[(100.0, 1.5)])),
+            min_ticksize,
+        );
+        assert!(matches!(source.publish(), DepthChange::Snapshot(_)));
+
+        source.update(
+            DepthUpdate::Snapshot(payload(2, &[(99.9, 1.0), (99.8, 2.0)], &[(100.0, 1.5)])),
+            min_ticksize,
+        );
+        let change = source.publish();
+        replicas.apply(&stream, &change);
+
+        source.update(
+            DepthUpdate::Diff(payload(3, &[(99.8, 0.0), (99.7, 4.0)], &[(100.1, 3.0)])),
+            min_ticksize,
+        );
+        source.update(
+            DepthUpdate::Diff(payload(4, &[(99.9, 5.0)], &[(100.0, 0.0)])),
+            min_ticksize,
+        );
+
+        let change = source.publish();
+        assert!(matches!(change, DepthChange::Diff(_)));
+
+        let replica = replicas.apply(&stream, &change);
+        assert_eq!(replica.bids, source.depth.bids);
+        assert_eq!(replica.asks, source.depth.asks);
+
+        let DepthChange::Diff(empty) = source.publish() else {
+            panic!("expected a diff after the initial snapshot");
+        };
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn replicas_are_dropped_with_their_streams() {
+        let stream = depth_stream();
+        let mut replicas = DepthReplicas::default();
+
+        let book = Depth {
+            bids: BTreeMap::from([(Price::from_f32(99.0), 1.0)]),
+            asks: BTreeMap::from([(Price::from_f32(101.0), 1.0)]),
+        };
+        replicas.apply(&stream, &DepthChange::Snapshot(Arc::new(book)));
+
+        assert_eq!(replicas.streams().collect::<Vec<_>>(), vec![&stream]);
+
+        replicas.remove_ticker(&stream.ticker_info().ticker);
+        assert!(replicas.is_empty());
+
+        replicas.apply(&stream, &DepthChange::Snapshot(Arc::new(Depth::default())));
+        replicas.remove(&stream);
+        assert!(replicas.is_empty());
     }
 }
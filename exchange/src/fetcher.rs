@@ -1,6 +1,12 @@
-use crate::adapter::StreamKind;
-use crate::{Kline, OpenInterest, Trade};
+use crate::adapter::{Exchange, StreamKind, fetch_ticker_info, fetch_ticker_prices};
+use crate::{FundingRate, Kline, OpenInterest, Ticker, TickerInfo, TickerStats, Timeframe, Trade};
 
+use iced_futures::futures::{
+    FutureExt, Stream, StreamExt,
+    future::BoxFuture,
+    stream::{self, FuturesUnordered},
+};
+use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -16,6 +22,33 @@ pub fn is_trade_fetch_enabled() -> bool {
     TRADE_FETCH_ENABLED.load(Ordering::Relaxed)
 }
 
+/// منبع معاملات برای بازسازی فوت‌پرینت از داده‌های گذشته
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum TradeSource {
+    #[default]
+    Both,       // فایل‌های فشرده روزانه و در صورت نبود، API
+    Historical, // فقط فایل‌های فشرده روزانه (تا پایان دیروز)
+    Intraday,   // فقط API معاملات
+}
+
+impl TradeSource {
+    pub const ALL: [TradeSource; 3] = [
+        TradeSource::Both,
+        TradeSource::Historical,
+        TradeSource::Intraday,
+    ];
+}
+
+impl std::fmt::Display for TradeSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TradeSource::Both => write!(f, "Archives + API"),
+            TradeSource::Historical => write!(f, "Archives only"),
+            TradeSource::Intraday => write!(f, "API only"),
+        }
+    }
+}
+
 /// انواع داده‌های دریافت شده از صرافی
 #[derive(Debug, Clone)]
 pub enum FetchedData {
@@ -31,6 +64,15 @@ pub enum FetchedData {
         data: Vec<OpenInterest>,   // لیست بهره باز (Open Interest)
         req_id: Option<uuid::Uuid>, // شناسه درخواست
     },
+    Funding {
+        data: Vec<FundingRate>,     // تاریخچه نرخ تأمین مالی
+        req_id: Option<uuid::Uuid>, // شناسه درخواست
+    },
+    KlineBackfill {
+        batch: Vec<Kline>, // یک صفحه از کندل‌های گذشته، مرتب و بدون تکرار
+        /// نتیجه نهایی بارگذاری؛ تا زمانی که صفحه‌ها در حال دریافت هستند `None` است
+        outcome: Option<Result<(), String>>,
+    },
 }
 
 /// خطاهای مربوط به درخواست‌های دریافت داده
@@ -68,7 +110,7 @@ impl RequestHandler {
         let request = FetchRequest::new(fetch);
         let id = Uuid::new_v4();
 
-        if let Some((existing_id, existing_req)) = self.requests.iter().find_map(|(k, v)| {
+        if let Some((existing_id, existing_req)) = self.requests.iter_mut().find_map(|(k, v)| {
             if v.same_with(&request) {
                 Some((*k, v))
             } else {
@@ -81,6 +123,7 @@ impl RequestHandler {
                     // retry completed requests after a cooldown
                     // to handle data source failures or outdated results gracefully
                     if chrono::Utc::now().timestamp_millis() as u64 - ts > 30_000 {
+                        existing_req.status = RequestStatus::Pending;
                         Ok(Some(existing_id))
                     } else {
                         Ok(None)
@@ -94,6 +137,19 @@ impl RequestHandler {
         Ok(Some(id))
     }
 
+    /// آیا پاسخ این درخواست هنوز مورد انتظار است؟
+    /// درخواست‌هایی که پس از تعویض نماد یا بازه زمانی کنار گذاشته شده‌اند، دیگر جاری نیستند
+    pub fn is_current(&self, id: Uuid) -> bool {
+        self.requests
+            .get(&id)
+            .is_some_and(|request| request.status == RequestStatus::Pending)
+    }
+
+    /// کنار گذاشتن همه درخواست‌های ثبت شده؛ پاسخ‌های دیرهنگام آن‌ها نادیده گرفته می‌شوند
+    pub fn supersede(&mut self) {
+        self.requests.clear();
+    }
+
     pub fn mark_completed(&mut self, id: Uuid) {
         if let Some(request) = self.requests.get_mut(&id) {
             let timestamp = chrono::Utc::now().timestamp_millis() as u64;
@@ -121,9 +177,11 @@ impl Default for RequestHandler {
 /// محدوده و نوع داده برای دریافت
 #[derive(PartialEq, Debug, Clone, Copy)]
 pub enum FetchRange {
-    Kline(u64, u64),        // دریافت کندل‌ها در بازه زمانی (شروع، پایان)
-    OpenInterest(u64, u64), // دریافت بهره باز در بازه زمانی
-    Trades(u64, u64),       // دریافت معاملات در بازه زمانی
+    Kline(u64, u64),         // دریافت کندل‌ها در بازه زمانی (شروع، پایان)
+    OpenInterest(u64, u64),  // دریافت بهره باز در بازه زمانی
+    Funding(u64, u64),       // دریافت تاریخچه نرخ تأمین مالی در بازه زمانی
+    Trades(u64, u64),        // دریافت معاملات در بازه زمانی
+    KlineBackfill(u64, u64), // دریافت صفحه به صفحه کندل‌های یک بازه طولانی
 }
 
 #[derive(PartialEq, Debug)]
@@ -146,6 +204,7 @@ impl FetchRequest {
             (FetchRange::OpenInterest(s1, e1), FetchRange::OpenInterest(s2, e2)) => {
                 e1 == e2 && s1 == s2
             }
+            (FetchRange::Funding(s1, e1), FetchRange::Funding(s2, e2)) => e1 == e2 && s1 == s2,
             _ => false,
         }
     }
@@ -193,7 +252,233 @@ impl Clone for FetchSpec {
 /// انواع وضعیت‌های اطلاع‌رسانی در حال دریافت داده
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum InfoKind {
-    FetchingKlines,         // در حال دریافت کندل‌ها
-    FetchingTrades(usize),  // در حال دریافت معاملات (به همراه تعداد)
-    FetchingOI,             // در حال دریافت بهره باز
+    FetchingKlines,               // در حال دریافت کندل‌ها
+    FetchingTrades(usize, u8),    // در حال دریافت معاملات (تعداد و درصد پیشرفت)
+    FetchingOI,                   // در حال دریافت بهره باز
+    FetchingFunding,              // در حال دریافت تاریخچه نرخ تأمین مالی
+    BackfillingKlines(usize, u8), // در حال بارگذاری تاریخچه کندل‌ها (تعداد و درصد پیشرفت)
+}
+
+/// حداکثر تعداد کندل هر درخواست؛ سقف مشترک API صرافی‌ها
+pub const BACKFILL_PAGE_LIMIT: u64 = 1000;
+
+/// صفحه‌بندی دریافت کندل‌های یک بازه طولانی از جدیدترین به قدیمی‌ترین
+///
+/// برخی صرافی‌ها کمتر از سقف صفحه یا فقط آخرین کندل‌های بازه را برمی‌گردانند،
+/// پس صفحه بعدی از قدیمی‌ترین کندل دریافت شده ادامه پیدا می‌کند، نه از ابتدای صفحه قبلی.
+#[derive(Debug, Clone)]
+pub struct KlineBackfill {
+    range: (u64, u64),     // بازه درخواستی (شروع، پایان)
+    interval_ms: u64,      // طول هر کندل
+    cursor: Option<u64>,   // پایان صفحه بعدی؛ `None` یعنی پایان کار
+    earliest: Option<u64>, // قدیمی‌ترین کندل تحویل داده شده
+    fetched: usize,        // تعداد کندل‌های تحویل داده شده
+}
+
+impl KlineBackfill {
+    pub fn new((from, to): (u64, u64), timeframe: Timeframe) -> Self {
+        Self {
+            range: (from, to),
+            interval_ms: timeframe.to_milliseconds().max(1),
+            cursor: (from < to).then_some(to),
+            earliest: None,
+            fetched: 0,
+        }
+    }
+
+    /// بازه درخواست بعدی با حداکثر `BACKFILL_PAGE_LIMIT` کندل
+    pub fn next_page(&self) -> Option<(u64, u64)> {
+        let end = self.cursor?;
+        let span = (BACKFILL_PAGE_LIMIT - 1) * self.interval_ms;
+        Some((end.saturating_sub(span).max(self.range.0), end))
+    }
+
+    /// حذف کندل‌های تکراری یا خارج از بازه یک صفحه و جابه‌جایی مکان‌نما به صفحه بعد
+    pub fn accept(&mut self, page: (u64, u64), mut klines: Vec<Kline>) -> Vec<Kline> {
+        let (from, to) = self.range;
+
+        klines.retain(|kline| {
+            kline.time >= from
+                && kline.time <= to
+                && self.earliest.is_none_or(|earliest| kline.time < earliest)
+        });
+        klines.sort_by_key(|kline| kline.time);
+        klines.dedup_by_key(|kline| kline.time);
+
+        let next_end = klines.first().map_or(page.0, |kline| kline.time);
+        self.cursor = next_end
+            .checked_sub(self.interval_ms)
+            .filter(|&cursor| cursor >= from);
+
+        if let Some(first) = klines.first() {
+            self.earliest = Some(first.time);
+        }
+        self.fetched += klines.len();
+
+        klines
+    }
+
+    pub fn fetched(&self) -> usize {
+        self.fetched
+    }
+
+    /// درصد پوشش بازه درخواستی از جدیدترین کندل به عقب
+    pub fn progress(&self) -> u8 {
+        let (from, to) = self.range;
+        let Some(cursor) = self.cursor else {
+            return 100;
+        };
+        let done = to.saturating_sub(cursor) as f64 / to.saturating_sub(from).max(1) as f64;
+        (done * 100.0).clamp(0.0, 100.0) as u8
+    }
+}
+
+/// وضعیت بارگذاری اطلاعات اولیه (نمادها و قیمت‌ها) یک صرافی
+#[derive(Debug, Clone, PartialEq)]
+pub enum LoadState {
+    Loading,        // در حال دریافت
+    Ready,          // اطلاعات نمادها دریافت شد
+    Failed(String), // دریافت با خطا مواجه شد
+}
+
+/// نتایج جزئی دریافت اطلاعات بازار که به محض آماده شدن هر صرافی ارسال می‌شوند
+#[derive(Debug, Clone)]
+pub enum MetadataUpdate {
+    TickersInfo(Exchange, Result<HashMap<Ticker, Option<TickerInfo>>, String>), // اطلاعات نمادها
+    TickerStats(Exchange, Result<HashMap<Ticker, TickerStats>, String>), // آمار و قیمت نمادها
+}
+
+impl MetadataUpdate {
+    pub fn exchange(&self) -> Exchange {
+        match self {
+            MetadataUpdate::TickersInfo(exchange, _) | MetadataUpdate::TickerStats(exchange, _) => {
+                *exchange
+            }
+        }
+    }
+}
+
+/// دریافت همزمان اطلاعات نمادها و سپس قیمت‌ها برای همه صرافی‌ها
+///
+/// درخواست‌های هر صرافی از محدودکننده نرخ همان صرافی عبور می‌کنند، بنابراین اجرای همزمان
+/// بازارهای مختلف یک صرافی از سقف مجاز آن عبور نمی‌کند. خطای یک صرافی فقط به عنوان نتیجه
+/// همان صرافی گزارش می‌شود و بقیه ادامه می‌دهند.
+pub fn fetch_market_metadata(
+    exchanges: impl IntoIterator<Item = Exchange>,
+) -> impl Stream<Item = MetadataUpdate> {
+    let pending: FuturesUnordered<BoxFuture<'static, MetadataUpdate>> =
+        exchanges.into_iter().map(tickers_info_future).collect();
+
+    stream::unfold(pending, |mut pending| async move {
+        let update = pending.next().await?;
+
+        // قیمت‌ها فقط پس از دریافت موفق اطلاعات نمادهای همان صرافی درخواست می‌شوند
+        if let MetadataUpdate::TickersInfo(exchange, Ok(_)) = &update {
+            pending.push(ticker_stats_future(*exchange));
+        }
+
+        Some((update, pending))
+    })
+}
+
+fn tickers_info_future(exchange: Exchange) -> BoxFuture<'static, MetadataUpdate> {
+    async move {
+        let result = fetch_ticker_info(exchange)
+            .await
+            .map_err(|err| err.to_string());
+        MetadataUpdate::TickersInfo(exchange, result)
+    }
+    .boxed()
+}
+
+fn ticker_stats_future(exchange: Exchange) -> BoxFuture<'static, MetadataUpdate> {
+    async move {
+        let result = fetch_ticker_prices(exchange)
+            .await
+            .map_err(|err| err.to_string());
+        MetadataUpdate::TickerStats(exchange, result)
+    }
+    .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn superseded_request_is_not_current() {
+        let mut handler = RequestHandler::new();
+
+        let first = handler
+            .add_request(FetchRange::Kline(0, 60_000))
+            .unwrap()
+            .unwrap();
+        assert!(handler.is_current(first));
+
+        // switching tickers right away issues the same range again for the new symbol
+        handler.supersede();
+        let second = handler
+            .add_request(FetchRange::Kline(0, 60_000))
+            .unwrap()
+            .unwrap();
+
+        assert!(!handler.is_current(first));
+        assert!(handler.is_current(second));
+
+        // a late response for the first request must not revive it
+        handler.mark_completed(first);
+        assert!(!handler.is_current(first));
+
+        handler.mark_completed(second);
+        assert!(!handler.is_current(second));
+    }
+
+    fn klines(times: impl IntoIterator<Item = u64>) -> Vec<Kline> {
+        times
+            .into_iter()
+            .map(|time| Kline {
+                time,
+                open: crate::Price::from_f32(1.0),
+                high: crate::Price::from_f32(1.0),
+                low: crate::Price::from_f32(1.0),
+                close: crate::Price::from_f32(1.0),
+                volume: (0.0, 0.0),
+                trade_count: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn backfill_pages_from_latest_and_skips_overlaps() {
+        let minute = 60_000;
+        let to = 2_500 * minute;
+        let mut backfill = KlineBackfill::new((0, to), Timeframe::M1);
+
+        let page = backfill.next_page().unwrap();
+        assert_eq!(page, (to - 999 * minute, to));
+
+        // an exchange capping pages at 300 candles returns only the latest part
+        let batch = backfill.accept(page, klines((2_201..=2_500).map(|i| i * minute)));
+        assert_eq!(batch.len(), 300);
+        assert_eq!(backfill.next_page().unwrap().1, 2_200 * minute);
+
+        // overlapping and duplicated candles of the next page are dropped
+        let page = backfill.next_page().unwrap();
+        let mut overlapping = klines((2_100..=2_210).map(|i| i * minute));
+        overlapping.extend(klines([2_150 * minute]));
+        let batch = backfill.accept(page, overlapping);
+        assert_eq!(batch.len(), 101);
+        assert!(batch.windows(2).all(|w| w[0].time < w[1].time));
+
+        // an empty page skips past the gap instead of stalling
+        let page = backfill.next_page().unwrap();
+        assert!(backfill.accept(page, vec![]).is_empty());
+        assert_eq!(backfill.next_page().unwrap().1, page.0 - minute);
+
+        while let Some(page) = backfill.next_page() {
+            backfill.accept(page, vec![]);
+        }
+        assert_eq!(backfill.progress(), 100);
+        assert_eq!(backfill.fetched(), 401);
+    }
 }
@@ -1,8 +1,11 @@
 pub mod adapter;
+mod archive;
+pub mod capture;
 pub mod connect;
 pub mod depth;
 pub mod fetcher;
 mod limiter;
+pub mod network;
 pub mod util;
 
 use crate::util::{ContractSize, MinQtySize, MinTicksize, Price};
@@ -12,7 +15,7 @@ use adapter::{Exchange, MarketKind, StreamKind};
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
-use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 use std::{fmt, hash::Hash};
 
 /// واحد نمایش مقادیر حجم/تعداد
@@ -21,7 +24,8 @@ use std::{fmt, hash::Hash};
 /// - `Quote`: نمایش بر اساس ارزش ارز کوت (مثلاً معادل USD/USDT)
 ///
 /// نکته: فقط برای بازارهای فیوچرز خطی (Linear Perpetual) و اسپات (Spot) اعمال می‌شود.
-/// بازارهای فیوچرز معکوس (Inverse Perpetual) همیشه بر اساس USD نمایش داده می‌شوند.
+/// بازارهای فیوچرز معکوس (Inverse Perpetual) به صورت پیش‌فرض مانند قبل نمایش داده می‌شوند،
+/// مگر اینکه تبدیل آن‌ها با [`set_inverse_conversion`] فعال شده باشد.
 #[repr(u8)]
 #[derive(Default, Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
 pub enum SizeUnit {
@@ -44,6 +48,39 @@ pub fn volume_size_unit() -> SizeUnit {
     }
 }
 
+static INVERSE_CONVERSION: AtomicBool = AtomicBool::new(false);
+
+/// فعال‌سازی تبدیل اندازه قراردادهای معکوس به واحد انتخاب شده در [`SizeUnit`]
+///
+/// در حالت غیرفعال، رفتار قبلی هر صرافی حفظ می‌شود (مثلاً تعداد قرارداد در OKX)
+pub fn set_inverse_conversion(enabled: bool) {
+    INVERSE_CONVERSION.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_inverse_conversion_enabled() -> bool {
+    INVERSE_CONVERSION.load(Ordering::Relaxed)
+}
+
+/// تبدیل تعداد قراردادهای معکوس به واحد نمایش
+///
+/// هر قرارداد معکوس ارزش اسمی ثابتی بر حسب USD دارد (مثلاً ۱۰۰ دلار برای `BTCUSD_PERP`)، پس:
+/// - ارزش اسمی (Quote) = تعداد قرارداد × اندازه قرارداد
+/// - مقدار ارز پایه (Base) = ارزش اسمی ÷ قیمت
+pub fn inverse_contracts_to_size(
+    contracts: f32,
+    price: f32,
+    contract_size: f32,
+    unit: SizeUnit,
+) -> f32 {
+    let notional = contracts * contract_size;
+
+    match unit {
+        SizeUnit::Quote => notional,
+        SizeUnit::Base if price > 0.0 => notional / price,
+        SizeUnit::Base => 0.0,
+    }
+}
+
 /// فرکانس مورد نظر برای بروزرسانی‌های عمق دفتر سفارش (Orderbook Depth)
 ///
 /// فواصل زمانی انتخاب شده توسط کاربر را به سطوح عمق خاص هر صرافی نگاشت می‌کند.
@@ -223,6 +260,11 @@ impl SerTicker {
             Exchange::OkexLinear => "OkexLinear",
             Exchange::OkexInverse => "OkexInverse",
             Exchange::OkexSpot => "OkexSpot",
+            Exchange::GateioLinear => "GateioLinear",
+            Exchange::GateioSpot => "GateioSpot",
+            Exchange::CoinbaseSpot => "CoinbaseSpot",
+            Exchange::BitgetLinear => "BitgetLinear",
+            Exchange::BitgetSpot => "BitgetSpot",
         }
     }
 
@@ -239,6 +281,11 @@ impl SerTicker {
             "OkexLinear" => Ok(Exchange::OkexLinear),
             "OkexInverse" => Ok(Exchange::OkexInverse),
             "OkexSpot" => Ok(Exchange::OkexSpot),
+            "GateioLinear" => Ok(Exchange::GateioLinear),
+            "GateioSpot" => Ok(Exchange::GateioSpot),
+            "CoinbaseSpot" => Ok(Exchange::CoinbaseSpot),
+            "BitgetLinear" => Ok(Exchange::BitgetLinear),
+            "BitgetSpot" => Ok(Exchange::BitgetSpot),
             _ => Err(format!("Unknown exchange: {}", s)),
         }
     }
@@ -406,12 +453,56 @@ impl Ticker {
                 // The "P" suffix will be added later in compute_display_data for all perpetual contracts
                 result.push_str("USDT");
             }
+            // Bitget futures symbols carry a `_UMCBL` suffix internally, e.g. when restored
+            // from a `SerTicker` without a display symbol
+            if matches!(self.exchange, Exchange::BitgetLinear | Exchange::BitgetSpot) {
+                result = adapter::bitget::normalize_symbol(&result).to_owned();
+            }
             result
         };
 
         (result, market_kind)
     }
 
+    /// ارزهای مظنه شناخته شده، به ترتیب طول تا پسوند بلندتر (مثلاً `USDT`) بر `USD` مقدم باشد
+    pub const QUOTE_ASSETS: [&'static str; 16] = [
+        "FDUSD", "USDT", "USDC", "USDE", "BUSD", "TUSD", "USD1", "EURC", "USD", "EUR", "TRY",
+        "BRL", "DAI", "BTC", "ETH", "BNB",
+    ];
+
+    /// ارز مظنه نماد از روی نام نمایشی آن؛ قالب‌های صرافی‌ها (`BTC-USDT-SWAP`، `BTC_USDT`،
+    /// `BTCUSD_PERP`) پشتیبانی می‌شوند و برای اسپات Hyperliquid نام نمایشی (`HYPEUSDC`) ملاک است.
+    /// پرپچوال‌های Hyperliquid و قراردادهای USDC بای‌بیت (`BTCPERP`) با USDC تسویه می‌شوند.
+    pub fn quote_asset(&self) -> Option<&'static str> {
+        let symbol = match self.exchange {
+            Exchange::HyperliquidLinear => return Some("USDC"),
+            Exchange::BybitLinear if self.as_str().ends_with("PERP") => return Some("USDC"),
+            Exchange::BitgetLinear | Exchange::BitgetSpot => {
+                adapter::bitget::normalize_symbol(self.display_as_str())
+            }
+            _ => self.display_as_str(),
+        };
+
+        let mut segments = symbol.split(['-', '_', '/']);
+        let first = segments.next()?;
+
+        // جفت‌های جداشده با خط تیره یا زیرخط؛ بخش‌های بعدی مثل `SWAP` یا تاریخ سررسید نادیده گرفته می‌شوند
+        if let Some(quote) = segments.find_map(|segment| {
+            Self::QUOTE_ASSETS
+                .into_iter()
+                .find(|quote| segment.eq_ignore_ascii_case(quote))
+        }) {
+            return Some(quote);
+        }
+
+        Self::QUOTE_ASSETS.into_iter().find(|quote| {
+            first.len() > quote.len()
+                && first
+                    .get(first.len() - quote.len()..)
+                    .is_some_and(|suffix| suffix.eq_ignore_ascii_case(quote))
+        })
+    }
+
     pub fn market_type(&self) -> MarketKind {
         self.exchange.market_type()
     }
@@ -600,12 +691,13 @@ pub struct Trade {
 /// اطلاعات یک کندل (Kline)
 #[derive(Debug, Clone, Copy)]
 pub struct Kline {
-    pub time: u64,          // زمان شروع کندل
-    pub open: Price,        // قیمت باز شدن
-    pub high: Price,        // بالاترین قیمت
-    pub low: Price,         // پایین‌ترین قیمت
-    pub close: Price,       // قیمت بسته شدن
-    pub volume: (f32, f32), // حجم (خرید، فروش)
+    pub time: u64,                // زمان شروع کندل
+    pub open: Price,              // قیمت باز شدن
+    pub high: Price,              // بالاترین قیمت
+    pub low: Price,               // پایین‌ترین قیمت
+    pub close: Price,             // قیمت بسته شدن
+    pub volume: (f32, f32),       // حجم (خرید، فروش)
+    pub trade_count: Option<u64>, // تعداد معاملات کندل (در صورت ارائه توسط صرافی)
 }
 
 impl Kline {
@@ -625,13 +717,22 @@ impl Kline {
             low: Price::from_f32(low).round_to_min_tick(MinTicksize::from(min_ticksize)),
             close: Price::from_f32(close).round_to_min_tick(MinTicksize::from(min_ticksize)),
             volume,
+            trade_count: None,
         }
     }
+
+    /// ثبت تعداد معاملات گزارش‌شده توسط صرافی برای این کندل
+    pub fn with_trade_count(mut self, trade_count: u64) -> Self {
+        self.trade_count = Some(trade_count);
+        self
+    }
 }
 
 #[derive(Debug, Clone, Copy, Deserialize)]
 pub struct TickerStats {
-    pub mark_price: f32,
+    pub last_price: f32,  // آخرین قیمت معامله شده
+    pub mark_price: f32,  // قیمت مارک (در بازار اسپات برابر آخرین قیمت)
+    pub index_price: f32, // قیمت شاخص (در بازار اسپات برابر آخرین قیمت)
     pub daily_price_chg: f32,
     pub daily_volume: f32,
 }
@@ -683,6 +784,13 @@ pub struct OpenInterest {
     pub value: f32,
 }
 
+/// نرخ تأمین مالی (Funding) قرارداد دائمی در یک زمان تسویه
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FundingRate {
+    pub time: u64, // زمان تسویه (میلی‌ثانیه)
+    pub rate: f32, // نرخ به صورت کسری؛ 0.0001 یعنی 0.01%
+}
+
 fn str_f32_parse(s: &str) -> f32 {
     s.parse::<f32>().unwrap_or_else(|e| {
         log::error!("Failed to parse float: {}, error: {}", s, e);
@@ -742,9 +850,130 @@ impl TickMultiplier {
 
         round_to_decimal_places(raw, decimal_places)
     }
+
+    /// کوچک‌ترین ضریب «رُند» بزرگ‌تر یا مساوی این ضریب (۱، ۲، ۲.۵ یا ۵ برابر توانی از ۱۰)
+    ///
+    /// چون حداقل گام قیمت توانی از ۱۰ است، گام حاصل هم عددی رُند و مضربی از حداقل گام می‌شود؛
+    /// مثلاً ضریب ۳۷ روی گام 0.001 به جای 0.037 گام 0.05 می‌دهد.
+    pub fn rounded(&self) -> TickMultiplier {
+        let target = u32::from(self.0);
+        let mut scale: u32 = 1;
+
+        while scale <= 100_000 {
+            // گام‌های رُند به دهم واحد؛ 2.5 فقط از ده برابر به بعد عدد صحیح است
+            for tenths in [10, 20, 25, 50] {
+                let candidate = tenths * scale;
+
+                if candidate.is_multiple_of(10) && candidate / 10 >= target {
+                    return u16::try_from(candidate / 10).map_or(*self, TickMultiplier);
+                }
+            }
+            scale *= 10;
+        }
+
+        *self
+    }
 }
 
 fn round_to_decimal_places(value: f32, places: u32) -> f32 {
     let factor = 10.0f32.powi(places as i32);
     (value * factor).round() / factor
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inverse_contracts_btcusd_perp() {
+        // BTCUSD_PERP: هر قرارداد معادل ۱۰۰ دلار
+        let contract_size = 100.0;
+
+        let usd = inverse_contracts_to_size(25.0, 50_000.0, contract_size, SizeUnit::Quote);
+        assert_eq!(usd, 2_500.0);
+
+        let btc = inverse_contracts_to_size(25.0, 50_000.0, contract_size, SizeUnit::Base);
+        assert!((btc - 0.05).abs() < 1e-6);
+
+        assert_eq!(
+            inverse_contracts_to_size(25.0, 0.0, contract_size, SizeUnit::Base),
+            0.0
+        );
+    }
+
+    #[test]
+    fn quote_asset_handles_exchange_formats() {
+        let quote = |symbol: &str, exchange: Exchange| Ticker::new(symbol, exchange).quote_asset();
+
+        assert_eq!(quote("BTCUSDT", Exchange::BinanceLinear), Some("USDT"));
+        assert_eq!(quote("BTCUSD_PERP", Exchange::BinanceInverse), Some("USD"));
+        assert_eq!(quote("ETHFDUSD", Exchange::BinanceSpot), Some("FDUSD"));
+        assert_eq!(quote("ETHBTC", Exchange::BinanceSpot), Some("BTC"));
+        assert_eq!(quote("BTC-USDT-SWAP", Exchange::OkexLinear), Some("USDT"));
+        assert_eq!(quote("BTC_USDC", Exchange::GateioSpot), Some("USDC"));
+        assert_eq!(quote("BTC-USD", Exchange::CoinbaseSpot), Some("USD"));
+        assert_eq!(quote("BTCPERP", Exchange::BybitLinear), Some("USDC"));
+        assert_eq!(quote("BTC", Exchange::HyperliquidLinear), Some("USDC"));
+        assert_eq!(quote("USDT", Exchange::BinanceSpot), None);
+
+        let hype = Ticker::new_with_display("@107", Exchange::HyperliquidSpot, Some("HYPEUSDC"));
+        assert_eq!(hype.quote_asset(), Some("USDC"));
+    }
+
+    #[test]
+    fn rounded_multiplier_snaps_up_to_round_steps() {
+        let rounded = |m: u16| TickMultiplier(m).rounded().0;
+
+        assert_eq!(rounded(1), 1);
+        assert_eq!(rounded(3), 5);
+        assert_eq!(rounded(7), 10);
+        assert_eq!(rounded(21), 25);
+        assert_eq!(rounded(37), 50);
+        assert_eq!(rounded(120), 200);
+        assert_eq!(rounded(260), 500);
+        assert_eq!(rounded(2000), 2000);
+
+        for tm in TickMultiplier::ALL {
+            assert_eq!(tm.rounded(), tm);
+        }
+    }
+
+    #[test]
+    fn rounded_tick_sizes_stay_on_min_tick_grid() {
+        let ticker = Ticker::new("BTCUSDT", Exchange::BinanceLinear);
+
+        for (min_tick, multiplier, expected) in [
+            (0.001, 37, 0.05),
+            (0.01, 3, 0.05),
+            (0.1, 21, 2.5),
+            (1.0, 13, 20.0),
+            (0.000_01, 260, 0.005),
+        ] {
+            let info = TickerInfo::new(ticker, min_tick, 1.0, None);
+            let step = TickMultiplier(multiplier)
+                .rounded()
+                .multiply_with_min_tick_size(info);
+
+            assert!(
+                (step - expected).abs() < min_tick / 10.0,
+                "{multiplier}x of {min_tick}: {step}"
+            );
+            let ticks = step / min_tick;
+            assert!((ticks - ticks.round()).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn dashed_symbol_round_trip() {
+        // نمادهای کوین‌بیس به شکل `BTC-USD` هستند
+        assert!(is_symbol_supported("BTC-USD", Exchange::CoinbaseSpot, false));
+
+        let ticker = Ticker::new("BTC-USD", Exchange::CoinbaseSpot);
+        let json = serde_json::to_string(&SerTicker::from_parts(ticker)).unwrap();
+        assert_eq!(json, "\"CoinbaseSpot:BTC-USD\"");
+
+        let parsed: SerTicker = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.ticker, ticker);
+        assert_eq!(parsed.exchange, Exchange::CoinbaseSpot);
+    }
+}
@@ -5,7 +5,7 @@ use serde_json::Value;
 use std::sync::LazyLock;
 use std::time::{Duration, Instant};
 
-pub static HTTP_CLIENT: LazyLock<Client> = LazyLock::new(Client::new);
+pub static HTTP_CLIENT: LazyLock<Client> = LazyLock::new(crate::network::build_http_client);
 
 /// ویژگی (Trait) برای مدیریت محدودیت نرخ درخواست (Rate Limiting)
 pub trait RateLimiter: Send + Sync {
@@ -173,36 +173,57 @@ impl FixedWindowBucket {
 /// دلیل محدودیت نرخ پویا
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DynamicLimitReason {
-    HeaderRate,      // بر اساس هدرهای پاسخ صرافی
-    FixedWindowRate, // بر اساس پنجره زمانی ثابت (حالت جایگزین)
+    HeaderRate,  // بر اساس هدرهای پاسخ صرافی
+    ElapsedRate, // بر اساس تخمین بازسازی زمانی (پیش از اولین هدر یا پس از پایان پنجره آن)
 }
 
+/// فاصله اطمینان پس از مرز پنجره تا ساعت صرافی هم ریست شده باشد
+const WINDOW_SAFETY_MARGIN: Duration = Duration::from_millis(500);
+
 /// محدودکننده نرخ پویا که از گزارش‌های خود صرافی استفاده می‌کند
 ///
-/// در صورت عدم وجود داده از صرافی، به حالت پنجره زمانی ثابت (Fallback) سوئیچ می‌کند
+/// هدر صرافی وزن مصرف شده در پنجره ثابت جاری را گزارش می‌کند؛ تا پایان همان پنجره وزن آزاد نمی‌شود
+/// و در صورت کمبود تا مرز پنجره صبر می‌شود. پیش از اولین هدر و پس از پایان پنجره آخرین هدر، وزن
+/// به نسبت زمان سپری شده بازسازی می‌شود؛ پس از هدر، بازسازی از وزن گزارش شده آغاز می‌شود
 pub struct DynamicBucket {
-    max_weight: usize,          // حداکثر وزن مجاز
-    current_used_weight: usize, // وزن استفاده شده فعلی (بر اساس گزارش صرافی)
-    last_updated: Instant,      // زمان آخرین بروزرسانی از صرافی
-    refill_rate: Duration,      // نرخ بازسازی
-    fallback_bucket: FixedWindowBucket, // سطل جایگزین برای مواقعی که داده‌ای در دسترس نیست
+    max_weight: usize,           // حداکثر وزن مجاز
+    used_weight: f32,            // وزن استفاده شده تخمینی
+    last_refill: Instant,        // زمان آخرین بازسازی تخمین
+    window_end: Option<Instant>, // پایان پنجره‌ای که آخرین هدر صرافی به آن تعلق دارد
+    refill_rate: Duration,       // طول پنجره زمانی صرافی
 }
 
 impl DynamicBucket {
     pub fn new(max_weight: usize, refill_rate: Duration) -> Self {
+        Self::new_at(max_weight, refill_rate, Instant::now())
+    }
+
+    fn new_at(max_weight: usize, refill_rate: Duration, now: Instant) -> Self {
         Self {
             max_weight,
-            current_used_weight: 0,
-            last_updated: Instant::now(),
+            used_weight: 0.0,
+            last_refill: now,
+            window_end: None,
             refill_rate,
-            fallback_bucket: FixedWindowBucket::new(max_weight, refill_rate),
         }
     }
 
     pub fn update_weight(&mut self, new_weight: usize) {
+        let since_epoch = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default();
+        let period_ms = self.refill_rate.as_millis().max(1);
+        let into_window = Duration::from_millis((since_epoch.as_millis() % period_ms) as u64);
+
+        self.update_weight_at(new_weight, Instant::now(), into_window);
+    }
+
+    /// `into_window` زمان سپری شده از ابتدای پنجره جاری صرافی است
+    fn update_weight_at(&mut self, new_weight: usize, now: Instant, into_window: Duration) {
         if new_weight > 0 {
-            self.current_used_weight = new_weight;
-            self.last_updated = Instant::now();
+            self.used_weight = new_weight as f32;
+            self.last_refill = now;
+            self.window_end = Some(now + self.refill_rate.saturating_sub(into_window));
         }
     }
 
@@ -210,45 +231,165 @@ impl DynamicBucket {
         &mut self,
         weight: usize,
     ) -> (Option<Duration>, Option<DynamicLimitReason>) {
-        let now = Instant::now();
-        let elapsed = now.duration_since(self.last_updated);
+        self.prepare_request_at(weight, Instant::now())
+    }
 
-        if elapsed <= self.refill_rate && self.current_used_weight > 0 {
-            self.prepare_with_header_data(weight)
-        } else {
-            self.prepare_with_fallback(weight)
+    fn prepare_request_at(
+        &mut self,
+        weight: usize,
+        now: Instant,
+    ) -> (Option<Duration>, Option<DynamicLimitReason>) {
+        match self.window_end {
+            Some(window_end) if now < window_end => {
+                self.prepare_with_header_data(weight, now, window_end)
+            }
+            // پنجره هدر تمام شده؛ وزن گزارش شده از زمان دریافت هدر به نسبت زمان بازسازی می‌شود
+            Some(_) => {
+                self.window_end = None;
+                self.prepare_with_elapsed_refill(weight, now)
+            }
+            None => self.prepare_with_elapsed_refill(weight, now),
         }
     }
 
+    /// وزن گزارش شده تا مرز پنجره ثابت باقی می‌ماند
     fn prepare_with_header_data(
-        &self,
+        &mut self,
         weight: usize,
+        now: Instant,
+        window_end: Instant,
     ) -> (Option<Duration>, Option<DynamicLimitReason>) {
-        let available = self.max_weight.saturating_sub(self.current_used_weight);
-
-        if available >= weight {
+        let available = self.max_weight as f32 - self.used_weight;
+        if available >= weight as f32 {
+            self.used_weight += weight as f32;
             return (None, None);
         }
 
-        let current_time = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default();
-
-        let period_seconds = self.refill_rate.as_secs();
-        let seconds_in_period = current_time.as_secs() % period_seconds;
-        let wait_time = Duration::from_secs(period_seconds - seconds_in_period)
-            .saturating_add(Duration::from_millis(500));
+        let wait_time = window_end
+            .saturating_duration_since(now)
+            .saturating_add(WINDOW_SAFETY_MARGIN);
 
         (Some(wait_time), Some(DynamicLimitReason::HeaderRate))
     }
 
-    fn prepare_with_fallback(
+    fn prepare_with_elapsed_refill(
         &mut self,
         weight: usize,
+        now: Instant,
     ) -> (Option<Duration>, Option<DynamicLimitReason>) {
-        match self.fallback_bucket.calculate_wait_time(weight) {
-            None => (None, None),
-            Some(wait_time) => (Some(wait_time), Some(DynamicLimitReason::FixedWindowRate)),
+        self.refill(now);
+
+        let available = self.max_weight as f32 - self.used_weight;
+        if available >= weight as f32 {
+            self.used_weight += weight as f32;
+            return (None, None);
         }
+
+        // زمان لازم تا بازسازی وزن کسری؛ درخواست پس از انتظار ارسال می‌شود، پس وزن آن از هم‌اکنون رزرو می‌شود
+        let deficit = weight as f32 - available;
+        let wait_ms =
+            self.refill_rate.as_secs_f32() * 1000.0 * deficit / self.max_weight.max(1) as f32;
+        let wait_time = Duration::from_millis(wait_ms.ceil() as u64);
+        self.used_weight += weight as f32;
+
+        (Some(wait_time), Some(DynamicLimitReason::ElapsedRate))
+    }
+
+    /// بازسازی وزن مصرف شده به نسبت زمان سپری شده، محدود به ظرفیت
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        if self.refill_rate.is_zero() {
+            self.used_weight = 0.0;
+        } else {
+            let credit =
+                self.max_weight as f32 * elapsed.as_secs_f32() / self.refill_rate.as_secs_f32();
+            self.used_weight = (self.used_weight - credit).max(0.0);
+        }
+        self.last_refill = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REFILL: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn header_weight_waits_for_window_boundary() {
+        let start = Instant::now();
+        let mut bucket = DynamicBucket::new_at(1200, REFILL, start);
+
+        // هدر ۲۰ ثانیه پس از شروع پنجره دریافت شده است؛ تا مرز پنجره ۴۰ ثانیه مانده
+        bucket.update_weight_at(1190, start, Duration::from_secs(20));
+        assert_eq!(bucket.prepare_request_at(10, start), (None, None));
+
+        // گذشت زمان درون پنجره وزنی آزاد نمی‌کند
+        let later = start + Duration::from_secs(30);
+        let (wait, reason) = bucket.prepare_request_at(20, later);
+        assert_eq!(wait, Some(Duration::from_secs(10) + WINDOW_SAFETY_MARGIN));
+        assert_eq!(reason, Some(DynamicLimitReason::HeaderRate));
+    }
+
+    #[test]
+    fn stale_header_refills_from_reported_weight() {
+        let start = Instant::now();
+        let mut bucket = DynamicBucket::new_at(1200, REFILL, start);
+
+        // هدر ۵۰ ثانیه پس از شروع پنجره؛ پنجره ۱۰ ثانیه بعد تمام می‌شود
+        bucket.update_weight_at(1200, start, Duration::from_secs(50));
+
+        // ۱۵ ثانیه پس از هدر یک چهارم ظرفیت از وزن گزارش شده آزاد شده است
+        let later = start + Duration::from_secs(15);
+        assert_eq!(bucket.prepare_request_at(300, later), (None, None));
+
+        let (wait, reason) = bucket.prepare_request_at(20, later);
+        assert_eq!(wait, Some(Duration::from_secs(1)));
+        assert_eq!(reason, Some(DynamicLimitReason::ElapsedRate));
+    }
+
+    #[test]
+    fn idle_after_header_restores_full_capacity() {
+        let start = Instant::now();
+        let mut bucket = DynamicBucket::new_at(1200, REFILL, start);
+
+        bucket.update_weight_at(1200, start, Duration::ZERO);
+
+        let idle = start + Duration::from_secs(600);
+        assert_eq!(bucket.prepare_request_at(1200, idle), (None, None));
+
+        // وزن مصرف شده پس از بی‌کاری هم حساب می‌شود
+        let (wait, reason) = bucket.prepare_request_at(12, idle);
+        assert_eq!(wait, Some(Duration::from_millis(600)));
+        assert_eq!(reason, Some(DynamicLimitReason::ElapsedRate));
+    }
+
+    #[test]
+    fn refills_proportionally_before_first_header() {
+        let start = Instant::now();
+        let mut bucket = DynamicBucket::new_at(1200, REFILL, start);
+
+        assert_eq!(bucket.prepare_request_at(1200, start), (None, None));
+        let (wait, reason) = bucket.prepare_request_at(20, start);
+        assert_eq!(wait, Some(Duration::from_secs(1)));
+        assert_eq!(reason, Some(DynamicLimitReason::ElapsedRate));
+
+        // پس از ۳۰ ثانیه بی‌کاری نیمی از ظرفیت بازسازی شده است
+        let later = start + Duration::from_secs(31);
+        let (wait, _) = bucket.prepare_request_at(500, later);
+        assert_eq!(wait, None);
+    }
+
+    #[test]
+    fn refill_is_clamped_to_capacity() {
+        let start = Instant::now();
+        let mut bucket = DynamicBucket::new_at(100, REFILL, start);
+
+        let idle = start + Duration::from_secs(600);
+        assert_eq!(bucket.prepare_request_at(100, idle), (None, None));
+
+        let (wait, reason) = bucket.prepare_request_at(10, idle);
+        assert_eq!(wait, Some(Duration::from_secs(6)));
+        assert_eq!(reason, Some(DynamicLimitReason::ElapsedRate));
     }
 }
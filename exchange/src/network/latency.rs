@@ -0,0 +1,103 @@
+//! آزمون تأخیر دامنه‌های REST صرافی‌ها؛ همه دامنه‌ها همزمان و با مهلت محدود آزموده می‌شوند
+
+use super::{Endpoint, network_config};
+use crate::limiter::HTTP_CLIENT;
+
+use iced_futures::futures::future::join_all;
+use std::time::{Duration, Instant};
+
+/// حداکثر زمان انتظار برای هر درخواست آزمون
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+/// تعداد درخواست‌های هر دامنه؛ درخواست اول شامل دست‌دهی TLS است و کمترین زمان گزارش می‌شود
+const PROBE_SAMPLES: usize = 3;
+
+/// تأخیر اندازه‌گیری شده یک دامنه
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    pub domain: String,            // آدرس کامل دامنه REST
+    pub latency: Option<Duration>, // کمترین زمان پاسخ؛ `None` در صورت خطا یا پایان مهلت
+}
+
+/// نتیجه آزمون دامنه‌های یک نقطه اتصال REST
+#[derive(Debug, Clone, PartialEq)]
+pub struct LatencyReport {
+    pub endpoint: Endpoint,
+    pub candidates: Vec<Candidate>,
+}
+
+impl LatencyReport {
+    /// سریع‌ترین دامنه پاسخ‌دهنده
+    pub fn fastest(&self) -> Option<&Candidate> {
+        self.candidates
+            .iter()
+            .filter_map(|candidate| candidate.latency.map(|latency| (latency, candidate)))
+            .min_by_key(|(latency, _)| *latency)
+            .map(|(_, candidate)| candidate)
+    }
+}
+
+/// آزمون همزمان دامنه‌های رسمی همه نقاط اتصال REST به همراه دامنه جایگزین کاربر
+pub async fn probe_latencies() -> Vec<LatencyReport> {
+    let overrides = network_config().endpoints;
+
+    let probes = Endpoint::ALL
+        .into_iter()
+        .filter(|endpoint| !endpoint.is_websocket())
+        .map(|endpoint| {
+            let mut domains: Vec<String> = endpoint
+                .known_domains()
+                .into_iter()
+                .map(str::to_string)
+                .collect();
+
+            if let Some(value) = overrides.get(&endpoint)
+                && let Ok(value) = super::parse_endpoint(endpoint, value)
+                && !domains.contains(&value)
+            {
+                domains.push(value);
+            }
+
+            probe_endpoint(endpoint, domains)
+        });
+
+    join_all(probes).await
+}
+
+async fn probe_endpoint(endpoint: Endpoint, domains: Vec<String>) -> LatencyReport {
+    let candidates = join_all(domains.into_iter().map(|domain| async move {
+        let latency = probe_domain(&domain).await;
+        Candidate { domain, latency }
+    }))
+    .await;
+
+    LatencyReport {
+        endpoint,
+        candidates,
+    }
+}
+
+/// هر پاسخ HTTP، حتی با کد خطا، نشان‌دهنده دسترس‌پذیری دامنه است
+async fn probe_domain(domain: &str) -> Option<Duration> {
+    let mut fastest: Option<Duration> = None;
+
+    for _ in 0..PROBE_SAMPLES {
+        let started = Instant::now();
+
+        match tokio::time::timeout(PROBE_TIMEOUT, HTTP_CLIENT.head(domain).send()).await {
+            Ok(Ok(_)) => {
+                let elapsed = started.elapsed();
+                fastest = Some(fastest.map_or(elapsed, |fastest| fastest.min(elapsed)));
+            }
+            Ok(Err(e)) => {
+                log::debug!("Latency probe to {domain} failed: {e}");
+                break;
+            }
+            Err(_) => {
+                log::debug!("Latency probe to {domain} timed out");
+                break;
+            }
+        }
+    }
+
+    fastest
+}
@@ -0,0 +1,112 @@
+//! شمارش تخصیص‌های حافظه انتشار تغییرات عمق در برابر کپی کامل دفتر سفارش
+//!
+//! تخصیص‌دهنده سراسری شمارنده تنها در این فایل آزمون تعریف می‌شود تا آزمون‌های کتابخانه روی
+//! تخصیص‌دهنده سیستم اجرا شوند.
+
+use flowsurface_exchange::adapter::{Exchange, StreamKind, StreamTicksize};
+use flowsurface_exchange::depth::{
+    DeOrder, DepthPayload, DepthReplicas, DepthUpdate, LocalDepthCache,
+};
+use flowsurface_exchange::util::MinTicksize;
+use flowsurface_exchange::{PushFrequency, Ticker, TickerInfo};
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+/// شمارنده تخصیص‌های حافظه هر رشته؛ آزمون‌ها موازی اجرا می‌شوند و شمارش سراسری مخلوط می‌شود
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
+
+fn payload(id: u64, bids: &[(f32, f32)], asks: &[(f32, f32)]) -> DepthPayload {
+    let to_orders = |levels: &[(f32, f32)]| {
+        levels
+            .iter()
+            .map(|&(price, qty)| DeOrder { price, qty })
+            .collect()
+    };
+
+    DepthPayload {
+        last_update_id: id,
+        time: id,
+        bids: to_orders(bids),
+        asks: to_orders(asks),
+    }
+}
+
+#[test]
+fn diff_publish_allocates_less_than_full_clone() {
+    let min_ticksize = MinTicksize::from(0.1);
+    let stream = StreamKind::DepthAndTrades {
+        ticker_info: TickerInfo::new(
+            Ticker::new("BTCUSDT", Exchange::BinanceLinear),
+            0.1,
+            0.001,
+            None,
+        ),
+        depth_aggr: StreamTicksize::Client,
+        push_freq: PushFrequency::ServerDefault,
+    };
+
+    // دفتر ۱۰۰۰ سطحی در هر سمت، مشابه تصویر اولیه کامل بازار دائمی بایننس
+    let bids: Vec<(f32, f32)> = (0..1000)
+        .map(|i| (50_000.0 - i as f32 * 0.1, 1.0))
+        .collect();
+    let asks: Vec<(f32, f32)> = (0..1000)
+        .map(|i| (50_000.1 + i as f32 * 0.1, 1.0))
+        .collect();
+
+    let mut source = LocalDepthCache::default();
+    let mut replicas = DepthReplicas::default();
+
+    source.update(
+        DepthUpdate::Snapshot(payload(1, &bids, &asks)),
+        min_ticksize,
+    );
+    replicas.apply(&stream, &source.publish());
+
+    let diff = payload(2, &[(49_999.9, 3.0), (49_000.0, 2.0)], &[(50_000.1, 0.0)]);
+    source.update(DepthUpdate::Diff(diff), min_ticksize);
+
+    // روش قبلی: کپی کامل دفتر در هر بروزرسانی
+    let (full_clone, clone_allocs) = count_allocations(|| source.depth.clone());
+
+    // روش فعلی: ارسال تغییرات و اعمال آن روی نسخه سمت مصرف‌کننده
+    let (_, diff_allocs) = count_allocations(|| {
+        let change = source.publish();
+        replicas.apply(&stream, &change);
+    });
+
+    assert_eq!(
+        replicas.apply(&stream, &source.publish()).bids,
+        full_clone.bids
+    );
+    assert!(
+        diff_allocs * 10 < clone_allocs,
+        "diff path allocated {diff_allocs} times, full clone {clone_allocs} times"
+    );
+}
@@ -40,6 +40,17 @@ impl std::fmt::Display for SoundType {
     }
 }
 
+impl From<data::audio::Sample> for SoundType {
+    fn from(sample: data::audio::Sample) -> Self {
+        match sample {
+            data::audio::Sample::TypewriterClick => SoundType::Buy,
+            data::audio::Sample::DryPopUp => SoundType::HardBuy,
+            data::audio::Sample::TypewriterHit => SoundType::Sell,
+            data::audio::Sample::FoamSplash => SoundType::HardSell,
+        }
+    }
+}
+
 impl From<SoundType> for usize {
     fn from(sound_type: SoundType) -> Self {
         sound_type as usize
@@ -134,6 +145,11 @@ impl SoundCache {
 
     /// پخش یک صدا بر اساس نوع آن
     pub fn play(&mut self, sound_type: SoundType) -> Result<(), String> {
+        self.play_scaled(sound_type, 1.0)
+    }
+
+    /// پخش یک صدا با ضریب صدای اختصاصی (۰ تا ۱) نسبت به صدای کلی
+    pub fn play_scaled(&mut self, sound_type: SoundType, gain: f32) -> Result<(), String> {
         // اگر برنامه بی‌صدا باشد، پخش نمی‌کنیم
         let Some(base_volume) = self.volume else {
             return Ok(());
@@ -165,7 +181,7 @@ impl SoundCache {
             1
         };
 
-        let adjusted_volume = base_volume / (overlap_count as f32);
+        let adjusted_volume = base_volume * gain.clamp(0.0, 1.0) / (overlap_count as f32);
 
         let sink = match rodio::Sink::try_new(&self.stream_handle) {
             Ok(sink) => sink,
@@ -8,8 +8,14 @@ mod scale;          // مقیاس‌بندی محورها
 use crate::style;
 use crate::widget::multi_split::{DRAG_SIZE, MultiSplit};
 use crate::widget::tooltip;
-use data::chart::{Autoscale, Basis, PlotData, ViewConfig, indicator::Indicator};
+use data::chart::{
+    Autoscale, AutoscalePadding, Basis, ChartAnchor, MAX_PRICE_DECIMALS, MeasuredMove, PlotData,
+    PriceAnchor, PriceLines, PriceScale, PriceSnap, QuickRange, ViewConfig, indicator::Indicator,
+};
+use data::layout::pane::LinkGroup;
 use exchange::TickerInfo;
+use exchange::adapter::StreamKind;
+use exchange::depth::Depth;
 use exchange::fetcher::{FetchRange, FetchRequests, FetchSpec, RequestHandler};
 use exchange::util::{Price, PriceStep};
 use scale::linear::PriceInfoLabel;
@@ -19,11 +25,21 @@ use iced::theme::palette::Extended;
 use iced::widget::canvas::{self, Cache, Canvas, Event, Frame, LineDash, Path, Stroke};
 use iced::{
     Alignment, Element, Length, Point, Rectangle, Size, Theme, Vector, keyboard, mouse, padding,
-    widget::{button, center, column, container, mouse_area, row, rule, text},
+    widget::{button, center, column, container, mouse_area, opaque, pin, row, rule, stack, text},
 };
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 const ZOOM_SENSITIVITY: f32 = 30.0; // حساسیت زوم
 const TEXT_SIZE: f32 = 12.0;        // اندازه متن
+const RULER_MIN_DRAG: f32 = 4.0;    // حداقل جابجایی موس برای ثبت اندازه‌گیری خط‌کش
+const CONTEXT_MENU_WIDTH: f32 = 180.0; // عرض منوی زمینه
+/// وضعیت نگه داشتن Shift؛ رویدادهای چرخ موس کلیدهای کمکی را همراه ندارند
+static SHIFT_HELD: AtomicBool = AtomicBool::new(false);
+/// وضعیت نگه داشتن Alt؛ نقاط رسم شده بدون چسباندن قیمت قرار می‌گیرند
+static ALT_HELD: AtomicBool = AtomicBool::new(false);
+
+const BOOK_TOP_STALE: Duration = Duration::from_secs(5); // پس از این مدت بدون بروزرسانی عمق، خطوط خرید/فروش پنهان می‌شوند
 
 /// انواع تعاملات کاربر با نمودار
 #[derive(Default, Debug, Clone, Copy)]
@@ -40,6 +56,9 @@ pub enum Interaction {
     Ruler {              // استفاده از خط‌کش برای اندازه‌گیری
         start: Option<Point>,
     },
+    MeasuredMove {       // ابزار حرکت اندازه‌گیری‌شده؛ ابتدا و انتهای موج، سپس کلیک سوم روی نقطه لنگر
+        points: [Option<Point>; 2],
+    },
 }
 
 /// مشخص می‌کند که کدام محور کلیک شده است
@@ -55,12 +74,101 @@ pub enum Message {
     Translated(Vector),      // جابجایی نمودار
     Scaled(f32, Vector),     // تغییر مقیاس (زوم)
     AutoscaleToggled,        // تغییر وضعیت مقیاس خودکار
-    CrosshairMoved,          // جابجایی نشانگر (Crosshair)
+    CrosshairMoved(Option<u64>),   // جابجایی نشانگر (Crosshair) به همراه زمان زیر نشانگر
     YScaling(f32, f32, bool),// تغییر مقیاس محور Y
     XScaling(f32, f32, bool),// تغییر مقیاس محور X
     BoundsChanged(Rectangle),// تغییر محدوده‌ی نمایش
     SplitDragged(usize, f32),// کشیدن جداکننده پنل‌ها
     DoubleClick(AxisScaleClicked), // دو بار کلیک روی محورها
+    PriceDecimalsChanged(Option<u8>), // تغییر تعداد ارقام اعشار نمایشی (None = خودکار)
+    PriceLinesChanged(PriceLines),    // تغییر سبک خطوط قیمت
+    PriceScaleChanged(PriceScale),    // تغییر مقیاس محور قیمت (خطی یا لگاریتمی)
+    ReverseTimeToggled(bool),         // نمایش زمان از راست به چپ
+    InvertPriceToggled(bool),         // وارونه کردن محور قیمت
+    WatermarkToggled(bool),           // نمایش/عدم نمایش واترمارک نماد
+    AutoscalePaddingChanged(AutoscalePadding), // تغییر فاصله خالی بالا و پایین در مقیاس‌دهی خودکار
+    PriceSnapChanged(PriceSnap),      // تغییر گام چسباندن قیمت نقاط رسم شده
+    RulerCompleted(Point, Point),     // پایان اندازه‌گیری خط‌کش بین دو نقطه
+    MeasuredMoveCompleted(Point, Point, Point), // ثبت حرکت اندازه‌گیری‌شده (ابتدا و انتهای موج، نقطه لنگر)
+    MeasuredMoveRemoved,              // حذف آخرین حرکت اندازه‌گیری‌شده
+    ContextMenuToggled(Option<Point>), // باز کردن منوی زمینه در محل کلیک راست یا بستن آن
+    CopyRequested(CopyTarget),        // کپی یکی از موارد منوی زمینه در کلیپ‌بورد
+    GoToTime(u64),                    // مرکز کردن نمودار روی یک زمان مشخص (میلی‌ثانیه UTC)
+    GoToPrice(Price),                 // مرکز کردن نمودار روی یک قیمت مشخص
+    QuickRangeSelected(QuickRange),   // زوم سریع روی یک بازه زمانی تا آخرین کندل
+}
+
+/// مواردی که از منوی زمینه نمودار در کلیپ‌بورد کپی می‌شوند
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyTarget {
+    Price,             // قیمت زیر نشانگر در محل کلیک راست
+    Symbol,            // نماد
+    SymbolAndExchange, // نماد به همراه صرافی
+    TimeRange,         // بازه زمانی قابل مشاهده
+}
+
+impl CopyTarget {
+    const ALL: [CopyTarget; 4] = [
+        CopyTarget::Price,
+        CopyTarget::Symbol,
+        CopyTarget::SymbolAndExchange,
+        CopyTarget::TimeRange,
+    ];
+}
+
+impl std::fmt::Display for CopyTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CopyTarget::Price => write!(f, "Copy price"),
+            CopyTarget::Symbol => write!(f, "Copy symbol"),
+            CopyTarget::SymbolAndExchange => write!(f, "Copy symbol with exchange"),
+            CopyTarget::TimeRange => write!(f, "Copy visible time range"),
+        }
+    }
+}
+
+/// منوی زمینه باز؛ قیمت هنگام باز شدن ثبت می‌شود تا جابجایی بعدی نمودار آن را تغییر ندهد
+#[derive(Debug, Clone, Copy)]
+struct ContextMenu {
+    position: Point, // محل کلیک راست نسبت به محدوده نمودار
+    price: Price,    // قیمت زیر نشانگر در همان لحظه
+}
+
+/// فاصله افقی یک اندازه‌گیری خط‌کش؛ زمانی یا بر اساس تعداد معاملات
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RulerSpan {
+    Duration(u64), // مدت زمان به میلی‌ثانیه
+    Ticks(u64),    // تعداد معاملات
+}
+
+impl std::fmt::Display for RulerSpan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RulerSpan::Duration(ms) => write!(f, "{}", data::util::format_duration_ms(*ms)),
+            RulerSpan::Ticks(ticks) => write!(f, "{ticks} ticks"),
+        }
+    }
+}
+
+/// نتیجه یک اندازه‌گیری کامل شده با خط‌کش، با قیمت‌های قالب‌بندی شده مانند محور قیمت
+#[derive(Debug, Clone, PartialEq)]
+pub struct RulerMeasurement {
+    pub basis: Basis,
+    pub start_price: String,
+    pub end_price: String,
+    pub pct_change: f32,
+    pub span: RulerSpan,
+    pub bars: u64,
+}
+
+/// مقادیر خط‌کش به همراه ناحیه چسبانده شده برای رسم
+struct Ruler {
+    rect: Rectangle,
+    start_price: Price,
+    end_price: Price,
+    pct_change: f32,
+    span: RulerSpan,
+    bars: u64,
 }
 
 /// تریت اصلی برای انواع مختلف نمودارها
@@ -96,6 +204,9 @@ pub trait Chart: PlotConstants + canvas::Program<Message> {
 
     /// بررسی خالی بودن داده‌های نمودار
     fn is_empty(&self) -> bool;
+
+    /// بازه زمانی داده‌های بارگذاری شده؛ `None` یعنی نمودار از زوم سریع پشتیبانی نمی‌کند
+    fn loaded_timerange(&self) -> Option<(u64, u64)>;
 }
 
 /// مدیریت تعاملات بوم (Canvas) مانند کلیک، درگ و اسکرول
@@ -120,12 +231,24 @@ fn canvas_interaction<T: Chart>(
             Interaction::Panning { .. } | Interaction::Zoomin { .. } => {
                 *interaction = Interaction::None;
             }
+            // پایان کشیدن خط‌کش؛ رها کردن بدون جابجایی اندازه‌گیری را باز نگه می‌دارد
+            Interaction::Ruler { start: Some(start) } => {
+                if let Some(end) = cursor.position_in(bounds)
+                    && start.distance(end) > RULER_MIN_DRAG
+                {
+                    let start = *start;
+                    *interaction = Interaction::None;
+                    return Some(
+                        canvas::Action::publish(Message::RulerCompleted(start, end)).and_capture(),
+                    );
+                }
+            }
             _ => {}
         }
     }
 
-    // بازنشانی خط‌کش اگر موس از محدوده خارج شود
-    if let Interaction::Ruler { .. } = interaction
+    // بازنشانی خط‌کش و ابزار حرکت اندازه‌گیری‌شده اگر موس از محدوده خارج شود
+    if let Interaction::Ruler { .. } | Interaction::MeasuredMove { .. } = interaction
         && cursor_position.is_none()
     {
         *interaction = Interaction::None;
@@ -140,6 +263,24 @@ fn canvas_interaction<T: Chart>(
                 mouse::Event::ButtonPressed(button) => {
                     let cursor_in_bounds = cursor_position?;
 
+                    // کلیک راست منوی زمینه را باز می‌کند و هر کلیک دیگری منوی باز را می‌بندد
+                    if let mouse::Button::Right = button
+                        && let Interaction::None = interaction
+                    {
+                        return Some(
+                            canvas::Action::publish(Message::ContextMenuToggled(
+                                cursor.position_in(bounds),
+                            ))
+                            .and_capture(),
+                        );
+                    }
+                    if state.context_menu.is_some() {
+                        return Some(
+                            canvas::Action::publish(Message::ContextMenuToggled(None))
+                                .and_capture(),
+                        );
+                    }
+
                     if let mouse::Button::Left = button {
                         match interaction {
                             Interaction::None
@@ -157,9 +298,42 @@ fn canvas_interaction<T: Chart>(
                                     start: Some(cursor_in_bounds),
                                 };
                             }
-                            Interaction::Ruler { .. } => {
+                            Interaction::Ruler { start } => {
+                                let start = *start;
                                 *interaction = Interaction::None;
+
+                                // کلیک دوم پس از جابجایی نیز اندازه‌گیری را کامل می‌کند
+                                if let Some(start) = start
+                                    && let Some(end) = cursor.position_in(bounds)
+                                    && start.distance(end) > RULER_MIN_DRAG
+                                {
+                                    return Some(
+                                        canvas::Action::publish(Message::RulerCompleted(
+                                            start, end,
+                                        ))
+                                        .and_capture(),
+                                    );
+                                }
                             }
+                            Interaction::MeasuredMove { points } => match *points {
+                                [Some(leg_start), Some(leg_end)] => {
+                                    *interaction = Interaction::None;
+                                    return Some(
+                                        canvas::Action::publish(Message::MeasuredMoveCompleted(
+                                            leg_start,
+                                            leg_end,
+                                            cursor_in_bounds,
+                                        ))
+                                        .and_capture(),
+                                    );
+                                }
+                                [Some(leg_start), None] => {
+                                    *points = [Some(leg_start), Some(cursor_in_bounds)];
+                                }
+                                [None, _] => {
+                                    *points = [Some(cursor_in_bounds), None];
+                                }
+                            },
                         }
                     }
                     Some(canvas::Action::request_redraw().and_capture())
@@ -173,8 +347,16 @@ fn canvas_interaction<T: Chart>(
                         );
                         Some(canvas::Action::publish(msg).and_capture())
                     }
-                    Interaction::None | Interaction::Ruler { .. } => {
-                        Some(canvas::Action::publish(Message::CrosshairMoved))
+                    Interaction::None
+                    | Interaction::Ruler { .. }
+                    | Interaction::MeasuredMove { .. } => {
+                        let hovered_time = cursor
+                            .position_in(bounds)
+                            .and_then(|position| state.time_at_x(position.x, bounds.size()));
+
+                        Some(canvas::Action::publish(Message::CrosshairMoved(
+                            hovered_time,
+                        )))
                     }
                     _ => None,
                 },
@@ -193,18 +375,36 @@ fn canvas_interaction<T: Chart>(
                     }
 
                     let cursor_to_center = cursor.position_from(bounds.center())?;
-                    let y = match delta {
-                        mouse::ScrollDelta::Lines { y, .. }
-                        | mouse::ScrollDelta::Pixels { y, .. } => y,
+                    let (x, y) = match delta {
+                        mouse::ScrollDelta::Lines { x, y }
+                        | mouse::ScrollDelta::Pixels { x, y } => (x, y),
                     };
 
+                    // Shift + چرخ فقط محور زمان را تغییر می‌دهد؛ برخی سیستم‌ها در این حالت
+                    // اسکرول را افقی گزارش می‌کنند
+                    if SHIFT_HELD.load(Ordering::Relaxed) {
+                        if let Interaction::Ruler { start: None } = interaction {
+                            *interaction = Interaction::None;
+                        }
+
+                        let delta = if *y != 0.0 { *y } else { *x };
+                        return Some(
+                            canvas::Action::publish(wheel_time_scaling(
+                                delta,
+                                cursor_to_center.x,
+                                state.layout.autoscale,
+                            ))
+                            .and_capture(),
+                        );
+                    }
+
                     // مدیریت زوم در حالت FitToVisible
                     if let Some(Autoscale::FitToVisible) = state.layout.autoscale {
                         return Some(
-                            canvas::Action::publish(Message::XScaling(
-                                y / 2.0,
+                            canvas::Action::publish(wheel_time_scaling(
+                                *y,
                                 cursor_to_center.x,
-                                false,
+                                state.layout.autoscale,
                             ))
                             .and_capture(),
                         );
@@ -279,6 +479,11 @@ fn canvas_interaction<T: Chart>(
             }
         }
         // رویدادهای کیبورد
+        Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+            SHIFT_HELD.store(modifiers.shift(), Ordering::Relaxed);
+            ALT_HELD.store(modifiers.alt(), Ordering::Relaxed);
+            None
+        }
         Event::Keyboard(keyboard_event) => {
             cursor_position?;
             match keyboard_event {
@@ -291,8 +496,29 @@ fn canvas_interaction<T: Chart>(
                     // لغو تعامل با Escape
                     keyboard::Key::Named(keyboard::key::Named::Escape) => {
                         *interaction = Interaction::None;
+                        if chart.state().context_menu.is_some() {
+                            return Some(
+                                canvas::Action::publish(Message::ContextMenuToggled(None))
+                                    .and_capture(),
+                            );
+                        }
                         Some(canvas::Action::request_redraw().and_capture())
                     }
+                    // شروع ابزار حرکت اندازه‌گیری‌شده با M؛ فقط برای نمودارهای زمانی
+                    keyboard::Key::Character("m")
+                        if matches!(chart.state().basis, Basis::Time(_)) =>
+                    {
+                        *interaction = Interaction::MeasuredMove {
+                            points: [None, None],
+                        };
+                        Some(canvas::Action::request_redraw().and_capture())
+                    }
+                    // حذف آخرین حرکت اندازه‌گیری‌شده با Backspace
+                    keyboard::Key::Named(keyboard::key::Named::Backspace)
+                        if !chart.state().layout.measured_moves.is_empty() =>
+                    {
+                        Some(canvas::Action::publish(Message::MeasuredMoveRemoved).and_capture())
+                    }
                     _ => None,
                 },
                 _ => None,
@@ -302,15 +528,30 @@ fn canvas_interaction<T: Chart>(
     }
 }
 
+/// تغییر مقیاس زمان با چرخ موس، مشترک بین نمودار (با Shift) و محور X؛
+/// در حالت FitToVisible لنگر روی آخرین کندل می‌ماند
+fn wheel_time_scaling(
+    delta: f32,
+    cursor_to_center_x: f32,
+    autoscale: Option<Autoscale>,
+) -> Message {
+    if autoscale == Some(Autoscale::FitToVisible) {
+        Message::XScaling(delta / 2.0, cursor_to_center_x, false)
+    } else {
+        Message::XScaling(delta, cursor_to_center_x, true)
+    }
+}
+
 /// اکشن‌های خروجی از به‌روزرسانی نمودار
 pub enum Action {
     ErrorOccurred(data::InternalError), // وقوع خطا
     RequestFetch(FetchRequests),        // درخواست دریافت داده‌های جدید
 }
 
-/// به‌روزرسانی وضعیت نمودار بر اساس پیام‌های دریافتی
-pub fn update<T: Chart>(chart: &mut T, message: &Message) {
-    match message {
+/// به‌روزرسانی وضعیت نمودار بر اساس پیام‌های دریافتی؛ خروجی نشان می‌دهد که آیا تنظیمات
+/// ذخیره‌شدنی نمایش (`ViewConfig`) تغییر کرده است
+pub fn update<T: Chart>(chart: &mut T, message: &Message) -> bool {
+    let layout_changed = match message {
         Message::DoubleClick(scale) => {
             let default_chart_width = T::default_cell_width(chart);
             let autoscaled_coords = chart.autoscaled_coords();
@@ -323,6 +564,7 @@ pub fn update<T: Chart>(chart: &mut T, message: &Message) {
                 AxisScaleClicked::X => {
                     state.cell_width = default_chart_width;
                     state.translation = autoscaled_coords;
+                    state.layout.quick_range = None;
                 }
                 // بازنشانی محور Y با دو بار کلیک
                 AxisScaleClicked::Y => {
@@ -334,6 +576,7 @@ pub fn update<T: Chart>(chart: &mut T, message: &Message) {
                     }
                 }
             }
+            true
         }
         // اعمال جابجایی نمودار
         Message::Translated(translation) => {
@@ -341,9 +584,10 @@ pub fn update<T: Chart>(chart: &mut T, message: &Message) {
 
             if let Some(Autoscale::FitToVisible) = state.layout.autoscale {
                 state.translation.x = translation.x;
+                false
             } else {
                 state.translation = *translation;
-                state.layout.autoscale = None;
+                state.layout.autoscale.take().is_some()
             }
         }
         // اعمال تغییر مقیاس (زوم)
@@ -352,7 +596,7 @@ pub fn update<T: Chart>(chart: &mut T, message: &Message) {
             state.scaling = *scaling;
             state.translation = *translation;
 
-            state.layout.autoscale = None;
+            state.layout.autoscale.take().is_some()
         }
         // تغییر وضعیت مقیاس‌بندی خودکار
         Message::AutoscaleToggled => {
@@ -377,6 +621,7 @@ pub fn update<T: Chart>(chart: &mut T, message: &Message) {
             if state.layout.autoscale.is_some() {
                 state.scaling = 1.0;
             }
+            true
         }
         Message::XScaling(delta, cursor_to_center_x, is_wheel_scroll) => {
             let min_cell_width = T::min_cell_width(chart);
@@ -387,9 +632,11 @@ pub fn update<T: Chart>(chart: &mut T, message: &Message) {
             if !(*delta < 0.0 && state.cell_width > min_cell_width
                 || *delta > 0.0 && state.cell_width < max_cell_width)
             {
-                return;
+                return false;
             }
 
+            let previous = (state.layout.autoscale, state.layout.quick_range);
+
             let is_fit_to_visible_zoom =
                 !is_wheel_scroll && matches!(state.layout.autoscale, Some(Autoscale::FitToVisible));
 
@@ -404,6 +651,9 @@ pub fn update<T: Chart>(chart: &mut T, message: &Message) {
             let new_width = (state.cell_width * (1.0 + delta / zoom_factor))
                 .clamp(min_cell_width, max_cell_width);
 
+            // زوم دستی، بازه زوم سریع انتخاب شده را لغو می‌کند
+            state.layout.quick_range = None;
+
             if is_fit_to_visible_zoom {
                 let anchor_interval = {
                     let latest_x_coord = state.interval_to_x(state.latest_x);
@@ -411,7 +661,7 @@ pub fn update<T: Chart>(chart: &mut T, message: &Message) {
                         state.latest_x
                     } else {
                         let visible_region = state.visible_region(state.bounds.size());
-                        state.x_to_interval(visible_region.x + visible_region.width)
+                        state.x_to_interval(state.latest_edge_x(&visible_region))
                     }
                 };
 
@@ -460,12 +710,14 @@ pub fn update<T: Chart>(chart: &mut T, message: &Message) {
                     state.layout.autoscale = None;
                 }
             }
+            previous != (state.layout.autoscale, state.layout.quick_range)
         }
         Message::YScaling(delta, cursor_to_center_y, is_wheel_scroll) => {
             let min_cell_height = T::min_cell_height(chart);
             let max_cell_height = T::max_cell_height(chart);
 
             let state = chart.mut_state();
+            let previous = state.layout.autoscale;
 
             if state.layout.autoscale == Some(Autoscale::FitToVisible) {
                 state.layout.autoscale = None;
@@ -499,6 +751,7 @@ pub fn update<T: Chart>(chart: &mut T, message: &Message) {
                     state.layout.autoscale = None;
                 }
             }
+            previous != state.layout.autoscale
         }
         Message::BoundsChanged(bounds) => {
             let state = chart.mut_state();
@@ -511,8 +764,9 @@ pub fn update<T: Chart>(chart: &mut T, message: &Message) {
             state.bounds = *bounds;
 
             if state.layout.autoscale != Some(Autoscale::CenterLatest) {
-                state.translation.x += center_delta_x;
+                state.translation.x += center_delta_x * state.x_sign();
             }
+            false
         }
         Message::SplitDragged(split, size) => {
             let state = chart.mut_state();
@@ -520,10 +774,210 @@ pub fn update<T: Chart>(chart: &mut T, message: &Message) {
             if let Some(split) = state.layout.splits.get_mut(*split) {
                 *split = (size * 100.0).round() / 100.0;
             }
+            true
         }
-        Message::CrosshairMoved => return chart.invalidate_crosshair(),
-    }
+        Message::CrosshairMoved(_) | Message::RulerCompleted(..) => {
+            chart.invalidate_crosshair();
+            return false;
+        }
+        Message::PriceDecimalsChanged(decimals) => {
+            chart.mut_state().layout.price_decimals = decimals.map(|d| d.min(MAX_PRICE_DECIMALS));
+            true
+        }
+        Message::PriceLinesChanged(price_lines) => {
+            chart.mut_state().layout.price_lines = *price_lines;
+            true
+        }
+        Message::PriceScaleChanged(price_scale) => {
+            // نقشه حرارتی همیشه خطی است؛ پس از تغییر مقیاس، نمودار دوباره برازش می‌شود
+            if chart.supports_fit_autoscaling() {
+                let state = chart.mut_state();
+                state.layout.price_scale = *price_scale;
+                state.layout.autoscale = Some(Autoscale::FitToVisible);
+                state.scaling = 1.0;
+            }
+            true
+        }
+        // نمای فعلی قرینه می‌شود تا همان بازه پیش از تغییر جهت در دید باقی بماند
+        Message::ReverseTimeToggled(reverse_time) => {
+            let state = chart.mut_state();
+            if state.layout.reverse_time != *reverse_time {
+                state.layout.reverse_time = *reverse_time;
+                state.translation.x = -state.translation.x;
+            }
+            true
+        }
+        Message::InvertPriceToggled(invert_price) => {
+            let state = chart.mut_state();
+            if state.layout.invert_price != *invert_price {
+                state.layout.invert_price = *invert_price;
+                state.translation.y = -state.translation.y;
+            }
+            true
+        }
+        Message::WatermarkToggled(watermark) => {
+            chart.mut_state().layout.watermark = *watermark;
+            true
+        }
+        Message::AutoscalePaddingChanged(padding) => {
+            chart.mut_state().layout.autoscale_padding = *padding;
+            true
+        }
+        Message::PriceSnapChanged(price_snap) => {
+            chart.mut_state().layout.price_snap = *price_snap;
+            true
+        }
+        Message::MeasuredMoveCompleted(leg_start, leg_end, anchor) => {
+            let state = chart.mut_state();
+            let bounds = state.bounds.size();
+
+            let measured_move = MeasuredMove {
+                leg_start: state.chart_anchor(bounds, *leg_start),
+                leg_end: state.chart_anchor(bounds, *leg_end),
+                anchor: state.chart_anchor(bounds, *anchor),
+            };
+            state.layout.measured_moves.push(measured_move);
+            true
+        }
+        Message::MeasuredMoveRemoved => chart.mut_state().layout.measured_moves.pop().is_some(),
+        Message::ContextMenuToggled(position) => {
+            let state = chart.mut_state();
+            let bounds = state.bounds.size();
+
+            state.context_menu = position.map(|position| {
+                let region = state.visible_region(bounds);
+                ContextMenu {
+                    position,
+                    price: state.screen_y_to_price(position.y, bounds, region),
+                }
+            });
+            false
+        }
+        Message::CopyRequested(_) => {
+            chart.mut_state().context_menu = None;
+            false
+        }
+        Message::GoToTime(time) => {
+            let state = chart.mut_state();
+            let recenter = state.center_on_time(*time)
+                && state.layout.autoscale == Some(Autoscale::CenterLatest);
+
+            if recenter {
+                state.layout.autoscale = None;
+            }
+            recenter
+        }
+        Message::GoToPrice(price) => {
+            let state = chart.mut_state();
+            state.center_on_price(*price);
+            state.layout.autoscale.take().is_some()
+        }
+        Message::QuickRangeSelected(range) => {
+            chart.mut_state().layout.quick_range = Some(*range);
+            frame_quick_range(chart);
+            true
+        }
+    };
     chart.invalidate_all();
+
+    layout_changed
+}
+
+/// زوم افقی روی بازه زوم سریع انتخاب شده، طوری که آخرین کندل در لبه نمودار قرار گیرد؛
+/// بازه "Max" تمام داده‌های بارگذاری شده را نشان می‌دهد
+pub fn frame_quick_range<T: Chart>(chart: &mut T) {
+    let loaded = chart.loaded_timerange();
+    let min_cell_width = T::min_cell_width(chart);
+    let max_cell_width = T::max_cell_width(chart);
+
+    let state = chart.mut_state();
+    let (Some(range), Basis::Time(timeframe), Some((earliest, latest))) =
+        (state.layout.quick_range, state.basis, loaded)
+    else {
+        return;
+    };
+
+    let width = state.bounds.width / state.scaling;
+    if width <= 0.0 {
+        return;
+    }
+
+    let interval = timeframe.to_milliseconds().max(1);
+    let span = range
+        .duration_ms()
+        .unwrap_or_else(|| latest.saturating_sub(earliest) + interval);
+    let bars = (span / interval).max(1) as f32;
+
+    state.cell_width = (width / bars).clamp(min_cell_width, max_cell_width);
+    // لبه بیرونی آخرین کندل روی لبه ناحیه قابل مشاهده
+    state.translation.x = (width - state.cell_width) / 2.0 * state.x_sign();
+
+    if state.layout.autoscale == Some(Autoscale::CenterLatest) {
+        state.layout.autoscale = None;
+    }
+}
+
+/// ثبت زمان زیر نشانگر نمودار؛ در صورت تغییر نسبت به حرکت قبلی `true` برمی‌گرداند
+/// تا فقط تغییرات واقعی به پنل‌های هم‌گروه ارسال شوند
+pub fn track_hovered_time<T: Chart>(chart: &mut T, message: &Message) -> bool {
+    let Message::CrosshairMoved(time) = message else {
+        return false;
+    };
+
+    let state = chart.mut_state();
+    if state.hovered_time == *time {
+        return false;
+    }
+    state.hovered_time = *time;
+    true
+}
+
+/// اندازه‌گیری نهایی خط‌کش، اگر پیام پایان یک اندازه‌گیری باشد
+pub fn completed_ruler<T: Chart>(chart: &T, message: &Message) -> Option<RulerMeasurement> {
+    let Message::RulerCompleted(start, end) = message else {
+        return None;
+    };
+
+    let state = chart.state();
+    let ruler = state.measure_ruler(state.bounds.size(), *start, *end);
+
+    Some(RulerMeasurement {
+        basis: state.basis,
+        start_price: state.format_price(ruler.start_price),
+        end_price: state.format_price(ruler.end_price),
+        pct_change: ruler.pct_change,
+        span: ruler.span,
+        bars: ruler.bars,
+    })
+}
+
+/// متن کپی شده برای یک مورد منوی زمینه؛ باید پیش از `update` فراخوانی شود که منو را می‌بندد
+pub fn clipboard_text<T: Chart>(chart: &T, message: &Message) -> Option<String> {
+    let Message::CopyRequested(target) = message else {
+        return None;
+    };
+
+    let state = chart.state();
+    let ticker = state.ticker_info.ticker;
+
+    match target {
+        CopyTarget::Price => state.context_menu.map(|menu| {
+            data::util::format_price(menu.price.to_f32_lossy(), state.display_decimals())
+        }),
+        CopyTarget::Symbol => Some(ticker.to_string()),
+        CopyTarget::SymbolAndExchange => Some(ticker.symbol_and_exchange_string()),
+        CopyTarget::TimeRange => chart.visible_timerange().map(|(earliest, latest)| {
+            data::config::timezone::display_timezone().format_range(earliest as i64, latest as i64)
+        }),
+    }
+}
+
+/// تنظیم زمان نشانگر همگام‌شده از پنل دیگری در همان گروه پیوند
+pub fn sync_crosshair<T: Chart>(chart: &mut T, synced: Option<(u64, LinkGroup)>) {
+    if chart.state().synced_crosshair != synced {
+        chart.mut_state().synced_crosshair = synced;
+        chart.invalidate_crosshair();
+    }
 }
 
 /// رندر کردن نمای نمودار
@@ -551,6 +1005,7 @@ pub fn view<'a, T: Chart>(
         chart_bounds: state.bounds,
         interval_keys: chart.interval_keys(),
         autoscaling: state.layout.autoscale,
+        reverse_time: state.layout.reverse_time,
     })
     .width(Length::Fill)
     .height(Length::Fill);
@@ -593,19 +1048,35 @@ pub fn view<'a, T: Chart>(
             labels_cache: &state.cache.y_labels,
             translation_y: state.translation.y,
             scaling: state.scaling,
-            decimals: state.decimals,
+            decimals: state.display_decimals(),
             min: state.base_price_y.to_f32_lossy(),
             last_price: state.last_price,
             tick_size: state.tick_size.to_f32_lossy(),
             cell_height: state.cell_height,
             basis: state.basis,
+            latest_x: state.latest_x,
             chart_bounds: state.bounds,
+            price_scale: state.layout.price_scale,
+            invert_price: state.layout.invert_price,
         })
         .width(Length::Fill)
         .height(Length::Fill);
 
+        let canvas: Element<_> = Canvas::new(chart)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into();
+        let canvas = match quick_range_buttons(chart) {
+            Some(quick_ranges) => stack![canvas, quick_ranges].into(),
+            None => canvas,
+        };
+        let canvas = match state.context_menu {
+            Some(menu) => stack![canvas, context_menu(menu.position, state.bounds.size())].into(),
+            None => canvas,
+        };
+
         let main_chart: Element<_> = row![
-            container(Canvas::new(chart).width(Length::Fill).height(Length::Fill))
+            container(canvas)
                 .width(Length::FillPortion(10))
                 .height(Length::FillPortion(120)),
             rule::vertical(1).style(style::split_ruler),
@@ -635,6 +1106,9 @@ pub fn view<'a, T: Chart>(
         }
     };
 
+    // ارتفاع محور X متناسب با اندازه قلم برچسب‌ها
+    let x_axis_height = Length::Fixed(scale::label_text_size() + 14.0);
+
     // ترکیب نهایی بخش‌های مختلف نمودار
     column![
         content,
@@ -646,14 +1120,82 @@ pub fn view<'a, T: Chart>(
             )
             .padding(padding::right(1))
             .width(Length::FillPortion(10))
-            .height(Length::Fixed(26.0)),
-            buttons.width(y_labels_width).height(Length::Fixed(26.0))
+            .height(x_axis_height),
+            buttons.width(y_labels_width).height(x_axis_height)
         ]
     ]
     .padding(padding::left(1).right(1).bottom(1))
     .into()
 }
 
+/// دکمه‌های زوم سریع در گوشه پایین نمودار، کنار دکمه مقیاس‌بندی خودکار؛
+/// بازه‌هایی که کمتر از چند کندل را در بر می‌گیرند نمایش داده نمی‌شوند
+fn quick_range_buttons<'a, T: Chart>(chart: &T) -> Option<Element<'a, Message>> {
+    let state = chart.state();
+    let Basis::Time(timeframe) = state.basis else {
+        return None;
+    };
+    chart.loaded_timerange()?;
+
+    let active = state.layout.quick_range;
+    let buttons = QuickRange::ALL
+        .into_iter()
+        .filter(|range| range.fits_timeframe(timeframe.to_milliseconds()))
+        .fold(row![].spacing(2), |buttons, range| {
+            let is_active = active == Some(range);
+            buttons.push(
+                button(text(range.to_string()).size(10))
+                    .padding([1, 4])
+                    .on_press(Message::QuickRangeSelected(range))
+                    .style(move |theme: &Theme, status| {
+                        style::button::transparent(theme, status, is_active)
+                    }),
+            )
+        });
+
+    Some(
+        container(opaque(
+            container(buttons).padding(2).style(style::chart_modal),
+        ))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(4)
+        .align_x(Alignment::End)
+        .align_y(Alignment::End)
+        .into(),
+    )
+}
+
+/// منوی زمینه کلیک راست در محل کلیک؛ طوری جابجا می‌شود که از محدوده نمودار بیرون نزند
+fn context_menu<'a>(position: Point, bounds: Size) -> Element<'a, Message> {
+    let items = CopyTarget::ALL
+        .iter()
+        .fold(column![].spacing(2), |items, target| {
+            items.push(
+                button(text(target.to_string()).size(12))
+                    .width(Length::Fill)
+                    .on_press(Message::CopyRequested(*target))
+                    .style(|theme: &Theme, status| {
+                        style::button::transparent(theme, status, false)
+                    }),
+            )
+        });
+
+    let menu_height = CopyTarget::ALL.len() as f32 * (TEXT_SIZE + 16.0);
+    let x = position.x.min(bounds.width - CONTEXT_MENU_WIDTH).max(0.0);
+    let y = position.y.min(bounds.height - menu_height).max(0.0);
+
+    pin(opaque(
+        container(items)
+            .width(CONTEXT_MENU_WIDTH)
+            .padding(4)
+            .style(style::chart_modal),
+    ))
+    .x(x)
+    .y(y)
+    .into()
+}
+
 /// ثابت‌های مربوط به رسم نمودار
 pub trait PlotConstants {
     fn min_scaling(&self) -> f32;        // حداقل ضریب بزرگنمایی
@@ -705,6 +1247,20 @@ pub struct ViewState {
     decimals: usize,            // تعداد ارقام اعشار قیمت
     ticker_info: TickerInfo,    // اطلاعات نماد معاملاتی
     layout: ViewConfig,         // تنظیمات چیدمان و نمایش
+    hovered_time: Option<u64>,                  // زمان زیر نشانگر همین نمودار
+    synced_crosshair: Option<(u64, LinkGroup)>, // زمان نشانگر پنل‌های هم‌گروه
+    book_top: Option<BookTop>,                  // بهترین قیمت خرید/فروش از آخرین بروزرسانی عمق
+    mark_price: Option<Price>,                  // آخرین قیمت مارک دریافت شده از آمار نماد
+    context_menu: Option<ContextMenu>,          // منوی زمینه باز شده با کلیک راست
+}
+
+/// بهترین قیمت‌های دفتر سفارش به همراه زمان دریافت
+#[derive(Debug, Clone, Copy)]
+struct BookTop {
+    bid: Option<Price>,
+    ask: Option<Price>,
+    microprice: Option<Price>,
+    received: Instant,
 }
 
 impl ViewState {
@@ -732,9 +1288,29 @@ impl ViewState {
             decimals,
             ticker_info,
             layout,
+            hovered_time: None,
+            synced_crosshair: None,
+            book_top: None,
+            mark_price: None,
+            context_menu: None,
         }
     }
 
+    /// ثبت بهترین قیمت خرید و فروش از آخرین وضعیت دفتر سفارش
+    pub fn set_book_top(&mut self, depth: &Depth) {
+        self.book_top = Some(BookTop {
+            bid: depth.best_bid(),
+            ask: depth.best_ask(),
+            microprice: depth.microprice(),
+            received: Instant::now(),
+        });
+    }
+
+    /// ثبت قیمت مارک از آمار نماد؛ برای بازار اسپات برابر آخرین قیمت است
+    pub fn set_mark_price(&mut self, mark_price: f32) {
+        self.mark_price = (mark_price > 0.0).then(|| Price::from_f32(mark_price));
+    }
+
     #[inline]
     fn price_unit() -> i64 {
         10i64.pow(Price::PRICE_SCALE as u32)
@@ -753,6 +1329,22 @@ impl ViewState {
         }
     }
 
+    /// مرکز کردن ناحیه قابل مشاهده روی کندل شامل زمان داده شده؛ در حالت تیک محور افقی زمان نیست
+    fn center_on_time(&mut self, time: u64) -> bool {
+        let Basis::Time(timeframe) = self.basis else {
+            return false;
+        };
+        let interval = timeframe.to_milliseconds();
+
+        self.translation.x = -self.interval_to_x((time / interval) * interval);
+        true
+    }
+
+    /// مرکز کردن ناحیه قابل مشاهده روی قیمت داده شده
+    fn center_on_price(&mut self, price: Price) {
+        self.translation.y = -self.price_to_y(price);
+    }
+
     /// بررسی اینکه آیا یک مقدار در محور X قابل مشاهده است یا خیر
     fn is_interval_x_visible(&self, interval_x: f32) -> bool {
         let region = self.visible_region(self.bounds.size());
@@ -760,28 +1352,47 @@ impl ViewState {
         interval_x >= region.x && interval_x <= region.x + region.width
     }
 
+    /// جهت محور X؛ در حالت زمان راست به چپ مختصات قرینه می‌شوند
+    fn x_sign(&self) -> f32 {
+        if self.layout.reverse_time { -1.0 } else { 1.0 }
+    }
+
+    /// جهت محور Y؛ در حالت قیمت وارونه مختصات قرینه می‌شوند
+    fn y_sign(&self) -> f32 {
+        if self.layout.invert_price { -1.0 } else { 1.0 }
+    }
+
+    /// مختصات X لبه‌ای از ناحیه قابل مشاهده که به سمت داده‌های جدیدتر است
+    fn latest_edge_x(&self, region: &Rectangle) -> f32 {
+        if self.layout.reverse_time {
+            region.x
+        } else {
+            region.x + region.width
+        }
+    }
+
     fn interval_range(&self, region: &Rectangle) -> (u64, u64) {
+        let left = self.x_to_interval(region.x);
+        let right = self.x_to_interval(region.x + region.width);
+        let (lower, upper) = (left.min(right), left.max(right));
+
         match self.basis {
-            Basis::Tick(_) => (
-                self.x_to_interval(region.x + region.width),
-                self.x_to_interval(region.x),
-            ),
+            Basis::Tick(_) => (lower, upper),
             Basis::Time(timeframe) => {
                 let interval = timeframe.to_milliseconds();
                 (
-                    self.x_to_interval(region.x).saturating_sub(interval / 2),
-                    self.x_to_interval(region.x + region.width)
-                        .saturating_add(interval / 2),
+                    lower.saturating_sub(interval / 2),
+                    upper.saturating_add(interval / 2),
                 )
             }
         }
     }
 
     fn price_range(&self, region: &Rectangle) -> (Price, Price) {
-        let highest = self.y_to_price(region.y);
-        let lowest = self.y_to_price(region.y + region.height);
+        let top = self.y_to_price(region.y);
+        let bottom = self.y_to_price(region.y + region.height);
 
-        (highest, lowest)
+        (top.max(bottom), top.min(bottom))
     }
 
     /// تبدیل مقدار محور X (زمان یا تیک) به مختصات پیکسلی
@@ -792,14 +1403,16 @@ impl ViewState {
                 let cell_width = f64::from(self.cell_width);
 
                 let diff = value as f64 - self.latest_x as f64;
-                (diff / interval * cell_width) as f32
+                (diff / interval * cell_width) as f32 * self.x_sign()
             }
-            Basis::Tick(_) => -((value as f32) * self.cell_width),
+            Basis::Tick(_) => -((value as f32) * self.cell_width) * self.x_sign(),
         }
     }
 
     /// تبدیل مختصات پیکسلی X به مقدار محور (زمان یا تیک)
     fn x_to_interval(&self, x: f32) -> u64 {
+        let x = x * self.x_sign();
+
         match self.basis {
             Basis::Time(timeframe) => {
                 let interval = timeframe.to_milliseconds();
@@ -819,21 +1432,116 @@ impl ViewState {
         }
     }
 
+    /// زمان متناظر با مختصات X صفحه؛ برای نمودارهای مبتنی بر تیک زمانی وجود ندارد
+    fn time_at_x(&self, x: f32, bounds: Size) -> Option<u64> {
+        let Basis::Time(_) = self.basis else {
+            return None;
+        };
+
+        let region = self.visible_region(bounds);
+        let earliest = self.x_to_interval(region.x) as f64;
+        let latest = self.x_to_interval(region.x + region.width) as f64;
+
+        let ratio = f64::from(x / bounds.width);
+        Some((earliest + ratio * (latest - earliest)).max(0.0) as u64)
+    }
+
+    /// رسم خط عمودی نشانگر همگام‌شده، قفل شده روی کندل همین نمودار که آن زمان را در بر دارد
+    fn draw_synced_crosshair(&self, frame: &mut Frame, bounds: Size) {
+        let (Some((time, group)), Basis::Time(timeframe)) = (self.synced_crosshair, self.basis)
+        else {
+            return;
+        };
+
+        let interval = timeframe.to_milliseconds();
+        let snapped = (time / interval) * interval;
+
+        let region = self.visible_region(bounds);
+        let x = (self.interval_to_x(snapped) - region.x) / region.width * bounds.width;
+
+        if !(0.0..=bounds.width).contains(&x) {
+            return;
+        }
+
+        frame.stroke(
+            &Path::line(Point::new(x, 0.0), Point::new(x, bounds.height)),
+            Stroke::with_color(
+                Stroke {
+                    width: 1.0,
+                    line_dash: LineDash {
+                        segments: &[4.0, 4.0],
+                        offset: 8,
+                    },
+                    ..Default::default()
+                },
+                style::link_group_color(group).scale_alpha(0.8),
+            ),
+        );
+    }
+
+    /// اندازه یک گام قیمت بر حسب واحد قیمت؛ برای گام صفر یک واحد کامل در نظر گرفته می‌شود
+    fn tick_unit(&self) -> f32 {
+        if self.tick_size.units == 0 {
+            1.0
+        } else {
+            self.tick_size.to_f32_lossy()
+        }
+    }
+
+    /// برازش بازه قیمتی قابل مشاهده در مقیاس لگاریتمی با ۵ درصد حاشیه در فضای لگاریتمی
+    fn fit_log_price_range(&mut self, lowest: f32, highest: f32) {
+        let lowest = lowest.max(self.tick_unit());
+        if highest <= lowest || self.bounds.height <= f32::EPSILON {
+            return;
+        }
+
+        let (ln_low, ln_high) = (lowest.ln(), highest.ln());
+        let padding = (ln_high - ln_low) * self.layout.autoscale_padding.fraction();
+        let ln_span = (ln_high - ln_low) + (2.0 * padding);
+        let padded_highest = (ln_high + padding).exp();
+
+        // y = cell_height * base / tick * (ln(base) - ln(price))
+        let pixels_per_ln = self.bounds.height / ln_span;
+        self.cell_height = pixels_per_ln * self.tick_unit() / padded_highest;
+        self.base_price_y = Price::from_f32(padded_highest);
+        self.translation.y = -self.bounds.height / 2.0 * self.y_sign();
+    }
+
     /// تبدیل قیمت به مختصات پیکسلی Y
     fn price_to_y(&self, price: Price) -> f32 {
-        if self.tick_size.units == 0 {
+        let y = if self.layout.price_scale == PriceScale::Logarithmic {
+            scale::logarithmic::price_to_y(
+                price.to_f32_lossy(),
+                self.base_price_y.to_f32_lossy(),
+                self.tick_unit(),
+                self.cell_height,
+            )
+        } else if self.tick_size.units == 0 {
             let one = Self::price_unit() as f32;
             let delta_units = (self.base_price_y.units - price.units) as f32;
-            return (delta_units / one) * self.cell_height;
-        }
+            (delta_units / one) * self.cell_height
+        } else {
+            let delta_units = self.base_price_y.units - price.units;
+            let ticks = (delta_units as f32) / (self.tick_size.units as f32);
+            ticks * self.cell_height
+        };
 
-        let delta_units = self.base_price_y.units - price.units;
-        let ticks = (delta_units as f32) / (self.tick_size.units as f32);
-        ticks * self.cell_height
+        y * self.y_sign()
     }
 
     /// تبدیل مختصات پیکسلی Y به قیمت
     fn y_to_price(&self, y: f32) -> Price {
+        let y = y * self.y_sign();
+
+        if self.layout.price_scale == PriceScale::Logarithmic {
+            return Price::from_f32_lossy(scale::logarithmic::y_to_price(
+                y,
+                self.base_price_y.to_f32_lossy(),
+                self.tick_unit(),
+                self.cell_height,
+            ));
+        }
+
         if self.tick_size.units == 0 {
             let one = Self::price_unit() as f32;
             let delta_units = ((y / self.cell_height) * one).round() as i64;
@@ -845,87 +1553,269 @@ impl ViewState {
         Price::from_units(self.base_price_y.units - delta_units)
     }
 
-    /// رسم نشانگر (Crosshair) و خط‌کش (Ruler) روی نمودار
-    fn draw_crosshair(
+    /// تبدیل مختصات Y روی صفحه (نسبت به بالای نمودار) به قیمت
+    fn screen_y_to_price(&self, y: f32, bounds: Size, region: Rectangle) -> Price {
+        self.y_to_price(region.y + (y / bounds.height) * region.height)
+    }
+
+    /// تبدیل قیمت به مختصات Y روی صفحه (نسبت به بالای نمودار)
+    fn price_to_screen_y(&self, price: Price, bounds: Size, region: Rectangle) -> f32 {
+        (self.price_to_y(price) - region.y) / region.height * bounds.height
+    }
+
+    /// برازش خطی بازه قیمتی روی محوری افقی به طول واحد؛ برای پنل‌هایی که قیمت را روی محور X نشان می‌دهند
+    pub fn fit_horizontal_price_range(&mut self, low: Price, high: Price) {
+        let step_units = if self.tick_size.units == 0 {
+            Self::price_unit()
+        } else {
+            self.tick_size.units
+        };
+        let span_units = (high.units - low.units).max(1);
+
+        self.cell_height = step_units as f32 / span_units as f32;
+        self.base_price_y = high;
+    }
+
+    /// مختصات X قیمت روی محوری به عرض داده شده که با `fit_horizontal_price_range` برازش شده است
+    pub fn price_to_screen_x(&self, price: Price, width: f32) -> f32 {
+        (1.0 - self.price_to_y(price)) * width
+    }
+
+    /// قیمت متناظر با مختصات X روی محور افقی برازش شده
+    pub fn screen_x_to_price(&self, x: f32, width: f32) -> Price {
+        self.y_to_price(1.0 - x / width.max(1.0))
+    }
+
+    /// چسباندن قیمت به گام قیمت نمودار
+    pub fn snap_price(&self, price: Price) -> Price {
+        if self.tick_size.units == 0 {
+            let tick_size = self.tick_size.to_f32_lossy();
+            Price::from_f32_lossy((price.to_f32_lossy() / tick_size).round() * tick_size)
+        } else {
+            let tick_units = self.tick_size.units;
+            let tick_index = price.units.div_euclid(tick_units);
+            Price::from_units(tick_index * tick_units)
+        }
+    }
+
+    /// چسباندن قیمت یک نقطه رسم شده به گام تنظیم شده پنل؛ با نگه داشتن Alt نقطه آزادانه قرار می‌گیرد
+    fn snap_drawing_price(&self, price: Price) -> Price {
+        if ALT_HELD.load(Ordering::Relaxed) {
+            return price;
+        }
+
+        match self.layout.price_snap {
+            PriceSnap::Tick => self.snap_price(price),
+            price_snap => price_snap.snap(price, self.tick_size),
+        }
+    }
+
+    /// تبدیل یک نقطه صفحه به زمان کندل و قیمت چسبانده شده
+    fn chart_anchor(&self, bounds: Size, point: Point) -> ChartAnchor {
+        let region = self.visible_region(bounds);
+        let (time, _) = self.snap_x_to_index(point.x, bounds, region);
+        let price = self.snap_drawing_price(self.screen_y_to_price(point.y, bounds, region));
+
+        ChartAnchor { time, price }
+    }
+
+    /// مختصات صفحه یک نقطه ثابت نمودار
+    fn anchor_to_screen(&self, anchor: ChartAnchor, bounds: Size, region: Rectangle) -> Point {
+        Point::new(
+            (self.interval_to_x(anchor.time) - region.x) / region.width * bounds.width,
+            self.price_to_screen_y(anchor.price, bounds, region),
+        )
+    }
+
+    /// رسم حرکت‌های اندازه‌گیری‌شده ذخیره شده و پیش‌نمایش ابزار در حال رسم
+    fn draw_measured_moves(
         &self,
         frame: &mut Frame,
         theme: &Theme,
         bounds: Size,
-        cursor_position: Point,
         interaction: &Interaction,
-    ) -> (f32, u64) {
+        cursor_position: Option<Point>,
+    ) {
+        let Basis::Time(_) = self.basis else {
+            return;
+        };
+
         let region = self.visible_region(bounds);
-        let dashed_line = style::dashed_line(theme);
 
-        let highest_p: Price = self.y_to_price(region.y);
-        let lowest_p: Price = self.y_to_price(region.y + region.height);
-        let highest: f32 = highest_p.to_f32_lossy();
-        let lowest: f32 = lowest_p.to_f32_lossy();
+        for measured_move in &self.layout.measured_moves {
+            self.draw_measured_move(frame, theme, bounds, region, measured_move);
+        }
 
-        let tick_size = self.tick_size.to_f32_lossy();
+        let (Interaction::MeasuredMove { points }, Some(cursor)) = (interaction, cursor_position)
+        else {
+            return;
+        };
 
-        if let Interaction::Ruler { start: Some(start) } = interaction {
-            let p1 = *start;
-            let p2 = cursor_position;
+        match *points {
+            [Some(leg_start), Some(leg_end)] => {
+                let measured_move = MeasuredMove {
+                    leg_start: self.chart_anchor(bounds, leg_start),
+                    leg_end: self.chart_anchor(bounds, leg_end),
+                    anchor: self.chart_anchor(bounds, cursor),
+                };
+                self.draw_measured_move(frame, theme, bounds, region, &measured_move);
+            }
+            [Some(leg_start), None] => {
+                let start = self.chart_anchor(bounds, leg_start);
+                let end = self.chart_anchor(bounds, cursor);
 
-            let snap_y = |y: f32| {
-                let ratio = y / bounds.height;
-                let price = highest + ratio * (lowest - highest);
+                frame.stroke(
+                    &Path::line(
+                        self.anchor_to_screen(start, bounds, region),
+                        self.anchor_to_screen(end, bounds, region),
+                    ),
+                    style::dashed_line(theme),
+                );
+            }
+            [None, _] => {}
+        }
+    }
 
-                let rounded_price_p = if self.tick_size.units == 0 {
-                    Price::from_f32_lossy((price / tick_size).round() * tick_size)
-                } else {
-                    let p = Price::from_f32_lossy(price);
-                    let tick_units = self.tick_size.units;
-                    let tick_index = p.units.div_euclid(tick_units);
-                    Price::from_units(tick_index * tick_units)
-                };
-                let rounded_price = rounded_price_p.to_f32_lossy();
-                let snap_ratio = (rounded_price - highest) / (lowest - highest);
-                snap_ratio * bounds.height
-            };
+    /// رسم یک حرکت اندازه‌گیری‌شده: موج مرجع، خط پیش‌بینی و نشانگر هدف با برچسب قیمت
+    fn draw_measured_move(
+        &self,
+        frame: &mut Frame,
+        theme: &Theme,
+        bounds: Size,
+        region: Rectangle,
+        measured_move: &MeasuredMove,
+    ) {
+        let palette = theme.extended_palette();
+        let color = palette.primary.strong.color;
 
-            let snap_x = |x: f32| {
-                let (_, snap_ratio) = self.snap_x_to_index(x, bounds, region);
-                snap_ratio * bounds.width
-            };
+        let target = measured_move.target();
 
-            let snapped_p1_x = snap_x(p1.x);
-            let snapped_p1_y = snap_y(p1.y);
-            let snapped_p2_x = snap_x(p2.x);
-            let snapped_p2_y = snap_y(p2.y);
+        let leg_start = self.anchor_to_screen(measured_move.leg_start, bounds, region);
+        let leg_end = self.anchor_to_screen(measured_move.leg_end, bounds, region);
+        let anchor = self.anchor_to_screen(measured_move.anchor, bounds, region);
+        let target_point = self.anchor_to_screen(target, bounds, region);
 
-            let price1 = self.y_to_price(snapped_p1_y);
-            let price2 = self.y_to_price(snapped_p2_y);
+        frame.stroke(&Path::line(leg_start, leg_end), style::dashed_line(theme));
+        frame.stroke(
+            &Path::line(anchor, target_point),
+            Stroke::with_color(Stroke::default().with_width(1.5), color),
+        );
 
-            let pct = if price1.to_f32_lossy() == 0.0 {
+        for point in [leg_start, leg_end, anchor] {
+            frame.fill(&Path::circle(point, 2.5), color.scale_alpha(0.6));
+        }
+        frame.fill(&Path::circle(target_point, 4.0), color);
+
+        let pct_change = {
+            let from = measured_move.anchor.price.to_f32_lossy();
+            if from == 0.0 {
                 0.0
             } else {
-                ((price2.to_f32_lossy() - price1.to_f32_lossy()) / price1.to_f32_lossy()) * 100.0
-            };
-            let pct_text = format!("{:.2}%", pct);
+                (target.price.to_f32_lossy() - from) / from * 100.0
+            }
+        };
+        let label = format!(
+            "{} ({:+.2}%) · {}",
+            self.format_price(target.price),
+            pct_change,
+            data::util::format_duration_ms(target.time.abs_diff(measured_move.anchor.time)),
+        );
 
-            let interval_diff: String = match self.basis {
-                Basis::Time(_) => {
-                    let (timestamp1, _) = self.snap_x_to_index(p1.x, bounds, region);
-                    let (timestamp2, _) = self.snap_x_to_index(p2.x, bounds, region);
+        let text_size = scale::label_text_size();
+        let text_width = (label.len() as f32) * text_size * 0.6;
+        let label_pos = Point::new(target_point.x + 8.0, target_point.y - text_size * 0.6);
 
-                    let diff_ms: u64 = timestamp1.abs_diff(timestamp2);
-                    data::util::format_duration_ms(diff_ms)
-                }
-                Basis::Tick(_) => {
-                    let (tick1, _) = self.snap_x_to_index(p1.x, bounds, region);
-                    let (tick2, _) = self.snap_x_to_index(p2.x, bounds, region);
+        frame.fill_rectangle(
+            Point::new(label_pos.x - 4.0, label_pos.y - 2.0),
+            Size::new(text_width + 8.0, text_size * 1.2 + 4.0),
+            palette.background.weakest.color.scale_alpha(0.9),
+        );
+        frame.fill_text(canvas::Text {
+            content: label,
+            position: label_pos,
+            color: palette.background.base.text,
+            size: iced::Pixels(text_size - 1.0),
+            font: scale::label_font(),
+            ..canvas::Text::default()
+        });
+    }
 
-                    let tick_diff = tick1.abs_diff(tick2);
-                    format!("{} ticks", tick_diff)
-                }
-            };
+    /// محاسبه اندازه‌گیری خط‌کش بین دو نقطه؛ نقاط به نزدیک‌ترین گام قیمت و بازه چسبانده می‌شوند
+    fn measure_ruler(&self, bounds: Size, p1: Point, p2: Point) -> Ruler {
+        let region = self.visible_region(bounds);
+
+        let snap_y = |y: f32| {
+            let rounded_price = self.snap_price(self.screen_y_to_price(y, bounds, region));
+            (
+                rounded_price,
+                self.price_to_screen_y(rounded_price, bounds, region),
+            )
+        };
+
+        let (index1, snap_ratio1) = self.snap_x_to_index(p1.x, bounds, region);
+        let (index2, snap_ratio2) = self.snap_x_to_index(p2.x, bounds, region);
+
+        let snapped_p1_x = snap_ratio1 * bounds.width;
+        let (start_price, snapped_p1_y) = snap_y(p1.y);
+        let snapped_p2_x = snap_ratio2 * bounds.width;
+        let (end_price, snapped_p2_y) = snap_y(p2.y);
+
+        let pct_change = if start_price.to_f32_lossy() == 0.0 {
+            0.0
+        } else {
+            ((end_price.to_f32_lossy() - start_price.to_f32_lossy()) / start_price.to_f32_lossy())
+                * 100.0
+        };
 
-            let rect_x = snapped_p1_x.min(snapped_p2_x);
-            let rect_y = snapped_p1_y.min(snapped_p2_y);
-            let rect_w = (snapped_p1_x - snapped_p2_x).abs();
-            let rect_h = (snapped_p1_y - snapped_p2_y).abs();
+        let index_diff = index1.abs_diff(index2);
+        let (span, bars) = match self.basis {
+            Basis::Time(timeframe) => (
+                RulerSpan::Duration(index_diff),
+                (index_diff / timeframe.to_milliseconds()).max(1),
+            ),
+            Basis::Tick(aggregation) => (
+                RulerSpan::Ticks(index_diff),
+                (index_diff / u64::from(aggregation.0)).max(1),
+            ),
+        };
+
+        Ruler {
+            rect: Rectangle {
+                x: snapped_p1_x.min(snapped_p2_x),
+                y: snapped_p1_y.min(snapped_p2_y),
+                width: (snapped_p1_x - snapped_p2_x).abs(),
+                height: (snapped_p1_y - snapped_p2_y).abs(),
+            },
+            start_price,
+            end_price,
+            pct_change,
+            span,
+            bars,
+        }
+    }
+
+    /// رسم نشانگر (Crosshair) و خط‌کش (Ruler) روی نمودار
+    fn draw_crosshair(
+        &self,
+        frame: &mut Frame,
+        theme: &Theme,
+        bounds: Size,
+        cursor_position: Point,
+        interaction: &Interaction,
+    ) -> (f32, u64) {
+        let region = self.visible_region(bounds);
+        let dashed_line = style::dashed_line(theme);
+
+        let tick_size = self.tick_size.to_f32_lossy();
+
+        if let Interaction::Ruler { start: Some(start) } = interaction {
+            let p2 = cursor_position;
+            let ruler = self.measure_ruler(bounds, *start, p2);
+
+            let rect_x = ruler.rect.x;
+            let rect_y = ruler.rect.y;
+            let rect_w = ruler.rect.width;
+            let rect_h = ruler.rect.height;
 
             let palette = theme.extended_palette();
 
@@ -961,30 +1851,14 @@ impl ViewState {
                 _ => text_corner,
             };
 
-            let datapoints_text = match self.basis {
-                Basis::Time(timeframe) => {
-                    let interval_ms = timeframe.to_milliseconds();
-                    let (timestamp1, _) = self.snap_x_to_index(p1.x, bounds, region);
-                    let (timestamp2, _) = self.snap_x_to_index(p2.x, bounds, region);
-
-                    let diff_ms = timestamp1.abs_diff(timestamp2);
-                    let datapoints = (diff_ms / interval_ms).max(1);
-                    format!("{} bars", datapoints)
-                }
-                Basis::Tick(aggregation) => {
-                    let (tick1, _) = self.snap_x_to_index(p1.x, bounds, region);
-                    let (tick2, _) = self.snap_x_to_index(p2.x, bounds, region);
-
-                    let tick_diff = tick1.abs_diff(tick2);
-                    let datapoints = (tick_diff / u64::from(aggregation.0)).max(1);
-                    format!("{} bars", datapoints)
-                }
-            };
-
-            let label_text = format!("{}, {} | {}", datapoints_text, interval_diff, pct_text);
+            let label_text = format!(
+                "{} bars, {} | {:.2}%",
+                ruler.bars, ruler.span, ruler.pct_change
+            );
 
-            let text_width = (label_text.len() as f32) * TEXT_SIZE * 0.6;
-            let text_height = TEXT_SIZE * 1.2;
+            let text_size = scale::label_text_size();
+            let text_width = (label_text.len() as f32) * text_size * 0.6;
+            let text_height = text_size * 1.2;
             let rect_padding = 4.0;
 
             let (bg_x, bg_y) = match idx {
@@ -1020,7 +1894,7 @@ impl ViewState {
                 content: label_text,
                 position: text_pos,
                 color: palette.background.base.text,
-                size: iced::Pixels(11.0),
+                size: iced::Pixels(text_size - 1.0),
                 align_x: match idx {
                     0 | 2 => Alignment::Start.into(),
                     1 | 3 => Alignment::End.into(),
@@ -1031,22 +1905,23 @@ impl ViewState {
                     2 | 3 => Alignment::End.into(),
                     _ => Alignment::Center.into(),
                 },
-                font: style::AZERET_MONO,
+                font: scale::label_font(),
                 ..Default::default()
             });
         }
 
         // Horizontal price line
-        let crosshair_ratio = cursor_position.y / bounds.height;
-        let crosshair_price = highest + crosshair_ratio * (lowest - highest);
+        let crosshair_price = self
+            .screen_y_to_price(cursor_position.y, bounds, region)
+            .to_f32_lossy();
 
         let rounded_price = (crosshair_price / tick_size).round() * tick_size;
-        let snap_ratio = (rounded_price - highest) / (lowest - highest);
+        let snapped_y = self.price_to_screen_y(Price::from_f32_lossy(rounded_price), bounds, region);
 
         frame.stroke(
             &Path::line(
-                Point::new(0.0, snap_ratio * bounds.height),
-                Point::new(bounds.width, snap_ratio * bounds.height),
+                Point::new(0.0, snapped_y),
+                Point::new(bounds.width, snapped_y),
             ),
             dashed_line,
         );
@@ -1075,7 +1950,7 @@ impl ViewState {
                 let snapped_crosshair = cell_index * self.cell_width;
                 let snap_ratio = (snapped_crosshair - chart_x_min) / (chart_x_max - chart_x_min);
 
-                let rounded_tick = (-cell_index as u64) * (u64::from(aggregation.0));
+                let rounded_tick = (-cell_index * self.x_sign()) as u64 * u64::from(aggregation.0);
 
                 frame.stroke(
                     &Path::line(
@@ -1098,45 +1973,204 @@ impl ViewState {
     ) {
         if let Some(price) = &self.last_price {
             let (last_price, line_color) = price.get_with_color(palette);
-            let y_pos = self.price_to_y(last_price);
+            let style = self.layout.price_lines;
 
-            let marker_line = Stroke::with_color(
-                Stroke {
-                    width: 1.0,
-                    line_dash: LineDash {
-                        segments: &[2.0, 2.0],
-                        offset: 4,
-                    },
-                    ..Default::default()
-                },
+            let line_price = match (style.anchor, self.mark_price) {
+                (PriceAnchor::Mark, Some(mark_price)) => mark_price,
+                _ => last_price,
+            };
+
+            let line_dash = if style.dashed {
+                LineDash {
+                    segments: &[2.0, 2.0],
+                    offset: 4,
+                }
+            } else {
+                LineDash::default()
+            };
+
+            self.draw_price_line(
+                frame,
+                (0.0, self.latest_edge_x(&region)),
+                line_price,
                 line_color.scale_alpha(0.5),
+                line_dash,
             );
+        }
 
-            frame.stroke(
-                &Path::line(
-                    Point::new(0.0, y_pos),
-                    Point::new(region.x + region.width, y_pos),
-                ),
-                marker_line,
+        self.draw_book_top_lines(frame, palette, region);
+    }
+
+    /// رسم خطوط بهترین خرید/فروش و microprice؛ اگر عمق بازار اخیراً بروز نشده باشد چیزی رسم نمی‌شود
+    fn draw_book_top_lines(
+        &self,
+        frame: &mut canvas::Frame,
+        palette: &Extended,
+        region: Rectangle,
+    ) {
+        let style = self.layout.price_lines;
+        if !style.bid_ask && !style.microprice {
+            return;
+        }
+
+        let Some(book_top) = self
+            .book_top
+            .filter(|top| top.received.elapsed() < BOOK_TOP_STALE)
+        else {
+            return;
+        };
+
+        let line_dash = LineDash {
+            segments: &[6.0, 3.0],
+            offset: 0,
+        };
+
+        let lines = [
+            (book_top.bid, palette.success.base.color),
+            (book_top.ask, palette.danger.base.color),
+        ];
+
+        if style.bid_ask {
+            for (price, color) in lines {
+                if let Some(price) = price {
+                    self.draw_price_line(
+                        frame,
+                        (region.x, region.x + region.width),
+                        price,
+                        color.scale_alpha(0.7),
+                        line_dash,
+                    );
+                }
+            }
+        }
+
+        // نقطه‌چین تا از خط آخرین قیمت و خطوط خرید/فروش قابل تشخیص باشد
+        if style.microprice
+            && let Some(microprice) = book_top.microprice
+        {
+            self.draw_price_line(
+                frame,
+                (region.x, region.x + region.width),
+                microprice,
+                palette.primary.strong.color,
+                LineDash {
+                    segments: &[1.0, 2.0],
+                    offset: 0,
+                },
             );
         }
     }
 
+    /// رسم یک خط افقی قیمت بین دو مختصات X به همراه برچسب اختیاری در انتهای آن
+    fn draw_price_line(
+        &self,
+        frame: &mut canvas::Frame,
+        (start_x, end_x): (f32, f32),
+        price: Price,
+        color: iced::Color,
+        line_dash: LineDash<'_>,
+    ) {
+        let style = self.layout.price_lines;
+        let y_pos = self.price_to_y(price);
+
+        let stroke = Stroke::with_color(
+            Stroke {
+                width: style.width,
+                line_dash,
+                ..Default::default()
+            },
+            color,
+        );
+
+        frame.stroke(
+            &Path::line(Point::new(start_x, y_pos), Point::new(end_x, y_pos)),
+            stroke,
+        );
+
+        if style.show_label {
+            let text_size = 10.0 / self.scaling;
+            let content = self.format_price(price);
+            let text_width = content.len() as f32 * text_size * 0.65;
+
+            // برچسب در انتهای خط و داخل نمودار؛ در حالت زمان راست به چپ خط به سمت چپ ادامه دارد
+            let label_x = if end_x >= start_x {
+                end_x - text_width - 4.0 / self.scaling
+            } else {
+                end_x + 4.0 / self.scaling
+            };
+
+            frame.fill_text(canvas::Text {
+                content,
+                position: Point::new(label_x, y_pos - 2.0 / self.scaling),
+                size: iced::Pixels(text_size),
+                color,
+                font: style::AZERET_MONO,
+                align_y: iced::alignment::Vertical::Bottom,
+                ..canvas::Text::default()
+            });
+        }
+    }
+
+    /// نماد و بازه نمودار با متن بزرگ و بسیار کمرنگ در مرکز پس‌زمینه؛ پیش از تبدیل‌های مختصات رسم می‌شود
+    fn draw_watermark(&self, frame: &mut Frame, palette: &Extended) {
+        if !self.layout.watermark {
+            return;
+        }
+
+        let (symbol, _) = self.ticker_info.ticker.display_symbol_and_type();
+
+        frame.fill_text(canvas::Text {
+            content: format!("{symbol} · {}", self.basis),
+            position: frame.center(),
+            size: iced::Pixels((frame.width() / 8.0).clamp(18.0, 72.0)),
+            color: palette.background.base.text.scale_alpha(0.06),
+            align_x: Alignment::Center.into(),
+            align_y: iced::alignment::Vertical::Center,
+            ..canvas::Text::default()
+        });
+    }
+
     /// دریافت تنظیمات فعلی چیدمان
     fn layout(&self) -> ViewConfig {
         let layout = &self.layout;
         ViewConfig {
             splits: layout.splits.clone(),
             autoscale: layout.autoscale,
+            price_decimals: layout.price_decimals,
+            price_lines: layout.price_lines,
+            price_scale: layout.price_scale,
+            reverse_time: layout.reverse_time,
+            invert_price: layout.invert_price,
+            watermark: layout.watermark,
+            measured_moves: layout.measured_moves.clone(),
+            autoscale_padding: layout.autoscale_padding,
+            price_snap: layout.price_snap,
+            quick_range: layout.quick_range,
+        }
+    }
+
+    /// تعداد ارقام اعشار نمایشی؛ مقدار انتخابی کاربر یا مقدار محاسبه شده از گام قیمت
+    fn display_decimals(&self) -> usize {
+        self.layout
+            .price_decimals
+            .map_or(self.decimals, |d| usize::from(d.min(MAX_PRICE_DECIMALS)))
+    }
+
+    /// قالب‌بندی قیمت برای نمایش؛ فقط نمایش تغییر می‌کند و خود قیمت روی گام واقعی باقی می‌ماند
+    pub fn format_price(&self, price: Price) -> String {
+        match self.layout.price_decimals {
+            Some(_) => data::util::format_price(price.to_f32_lossy(), self.display_decimals()),
+            None => {
+                data::util::number_format().separate(price.to_string(self.ticker_info.min_ticksize))
+            }
         }
     }
 
     /// محاسبه عرض مورد نیاز برای برچسب‌های محور Y
     fn y_labels_width(&self) -> Length {
-        let precision = self.ticker_info.min_ticksize;
-
-        let value = self.base_price_y.to_string(precision);
-        let width = (value.len() as f32 * TEXT_SIZE * 0.8).max(72.0);
+        let value = self.format_price(self.base_price_y);
+        let text_size = scale::label_text_size();
+        let width = (value.len() as f32 * text_size * 0.8).max(text_size * 6.0);
 
         Length::Fixed(width.ceil())
     }
@@ -1155,7 +2189,7 @@ impl ViewState {
 
                 let rounded_timestamp = (millis_at_x / (interval as f64)).round() as u64 * interval;
 
-                let snap_ratio = if latest - earliest > 0.0 {
+                let snap_ratio = if latest != earliest {
                     ((rounded_timestamp as f64 - earliest) / (latest - earliest)) as f32
                 } else {
                     0.5
@@ -1176,7 +2210,7 @@ impl ViewState {
                     0.5
                 };
 
-                let rounded_tick = (-cell_index as u64) * u64::from(aggregation.0);
+                let rounded_tick = (-cell_index * self.x_sign()) as u64 * u64::from(aggregation.0);
 
                 (rounded_tick, snap_ratio)
             }
@@ -1186,12 +2220,21 @@ impl ViewState {
 
 /// ارسال درخواست دریافت داده‌های جدید (Fetch)
 fn request_fetch(handler: &mut RequestHandler, range: FetchRange) -> Option<Action> {
+    request_stream_fetch(handler, range, None)
+}
+
+/// ارسال درخواست دریافت داده برای یک جریان مشخص؛ در صورت نبود، جریان اصلی پنل استفاده می‌شود
+fn request_stream_fetch(
+    handler: &mut RequestHandler,
+    range: FetchRange,
+    stream: Option<StreamKind>,
+) -> Option<Action> {
     match handler.add_request(range) {
         Ok(Some(req_id)) => {
             let fetch_spec = FetchSpec {
                 req_id,
                 fetch: range,
-                stream: None,
+                stream,
             };
             let fetch = FetchRequests::from([fetch_spec]);
             Some(Action::RequestFetch(fetch))
@@ -1274,3 +2317,160 @@ fn draw_volume_bar(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data::aggr::TickCount;
+    use exchange::{Ticker, Timeframe, adapter::Exchange};
+
+    const DIRECTIONS: [(bool, bool); 4] =
+        [(false, false), (true, false), (false, true), (true, true)];
+
+    fn view_state(basis: Basis, (reverse_time, invert_price): (bool, bool)) -> ViewState {
+        let ticker_info = TickerInfo::new(
+            Ticker::new("BTCUSDT", Exchange::BinanceLinear),
+            0.5,
+            0.001,
+            None,
+        );
+        let layout = ViewConfig {
+            splits: vec![],
+            autoscale: None,
+            price_decimals: None,
+            price_lines: PriceLines::default(),
+            price_scale: PriceScale::Linear,
+            reverse_time,
+            invert_price,
+            watermark: false,
+            measured_moves: vec![],
+            autoscale_padding: AutoscalePadding::default(),
+            price_snap: PriceSnap::default(),
+            quick_range: None,
+        };
+
+        let mut state = ViewState::new(
+            basis,
+            PriceStep::from_f32(0.5),
+            1,
+            ticker_info,
+            layout,
+            4.0,
+            2.0,
+        );
+        state.latest_x = 1_700_000_040_000;
+        state.base_price_y = Price::from_f32(100.0);
+        state
+    }
+
+    #[test]
+    fn go_to_centers_the_visible_region() {
+        for direction in DIRECTIONS {
+            let mut state = view_state(Basis::Time(Timeframe::M1), direction);
+            let target = state.latest_x - 2_000 * 60_000;
+
+            assert!(state.center_on_time(target + 25_000));
+            state.center_on_price(Price::from_f32(80.0));
+
+            let region = state.visible_region(state.bounds.size());
+            let center_x = region.x + region.width / 2.0;
+            let center_y = region.y + region.height / 2.0;
+            assert_eq!(state.x_to_interval(center_x), target);
+            assert_eq!(state.y_to_price(center_y), Price::from_f32(80.0));
+        }
+
+        let mut state = view_state(Basis::Tick(TickCount(50)), (false, false));
+        assert!(!state.center_on_time(0));
+    }
+
+    #[test]
+    fn axis_mappings_round_trip_in_every_direction() {
+        for direction in DIRECTIONS {
+            let state = view_state(Basis::Time(Timeframe::M1), direction);
+            for bars in [0, 1, 25, 300] {
+                let time = state.latest_x - bars * 60_000;
+                assert_eq!(state.x_to_interval(state.interval_to_x(time)), time);
+            }
+
+            let state = view_state(Basis::Tick(TickCount(50)), direction);
+            for tick in [0, 1, 7, 420] {
+                assert_eq!(state.x_to_interval(state.interval_to_x(tick)), tick);
+            }
+
+            for steps in [-20, -1, 0, 47] {
+                let price = state.base_price_y.add_steps(steps, state.tick_size);
+                // مختصات Y از f32 عبور می‌کند؛ قیمت برگشتی روی همان گام قیمت قرار می‌گیرد
+                let round_trip = state.y_to_price(state.price_to_y(price));
+                assert_eq!(round_trip.round_to_step(state.tick_size), price);
+            }
+        }
+    }
+
+    #[test]
+    fn reversed_axes_mirror_coordinates() {
+        let state = view_state(Basis::Time(Timeframe::M1), (false, false));
+        let flipped = view_state(Basis::Time(Timeframe::M1), (true, true));
+
+        let earlier = state.latest_x - 10 * 60_000;
+        let price = Price::from_f32(95.0);
+
+        assert!(state.interval_to_x(earlier) < 0.0);
+        assert_eq!(
+            flipped.interval_to_x(earlier),
+            -state.interval_to_x(earlier)
+        );
+        assert_eq!(flipped.price_to_y(price), -state.price_to_y(price));
+    }
+
+    #[test]
+    fn horizontal_price_range_spans_the_width() {
+        let mut state = view_state(Basis::Time(Timeframe::M1), (false, false));
+        let (low, high) = (Price::from_f32(90.0), Price::from_f32(110.0));
+        state.fit_horizontal_price_range(low, high);
+
+        let width = 400.0;
+        assert!(state.price_to_screen_x(low, width).abs() < 1e-3);
+        assert!((state.price_to_screen_x(high, width) - width).abs() < 1e-3);
+        assert!((state.price_to_screen_x(Price::from_f32(100.0), width) - 200.0).abs() < 1e-3);
+
+        let price = state.snap_price(state.screen_x_to_price(300.0, width));
+        assert_eq!(price, Price::from_f32(105.0));
+    }
+
+    #[test]
+    fn measured_move_anchors_survive_screen_round_trip() {
+        let bounds = Size::new(800.0, 600.0);
+
+        for direction in DIRECTIONS {
+            let state = view_state(Basis::Time(Timeframe::M1), direction);
+            let region = state.visible_region(bounds);
+
+            let measured_move = MeasuredMove {
+                leg_start: ChartAnchor {
+                    time: state.latest_x - 30 * 60_000,
+                    price: state.base_price_y.add_steps(-4, state.tick_size),
+                },
+                leg_end: ChartAnchor {
+                    time: state.latest_x - 20 * 60_000,
+                    price: state.base_price_y.add_steps(7, state.tick_size),
+                },
+                anchor: ChartAnchor {
+                    time: state.latest_x - 10 * 60_000,
+                    price: state.base_price_y,
+                },
+            };
+
+            let target = measured_move.target();
+            assert_eq!(target.time, state.latest_x);
+            assert_eq!(
+                target.price,
+                state.base_price_y.add_steps(11, state.tick_size)
+            );
+
+            for anchor in [measured_move.leg_start, measured_move.anchor, target] {
+                let point = state.anchor_to_screen(anchor, bounds, region);
+                assert_eq!(state.chart_anchor(bounds, point), anchor);
+            }
+        }
+    }
+}
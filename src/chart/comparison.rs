@@ -3,7 +3,7 @@ use crate::widget::chart::{Series, Zoom, domain};
 
 use data::chart::Basis;
 use data::chart::comparison::Config;
-use exchange::adapter::StreamKind;
+use exchange::adapter::{KlinePriceSource, StreamKind};
 use exchange::fetcher::{FetchRange, FetchSpec, RequestHandler};
 use exchange::{Kline, SerTicker, TickerInfo, Timeframe};
 
@@ -32,6 +32,7 @@ pub struct ComparisonChart {
     pub config: data::chart::comparison::Config,
     pub series_editor: series_editor::TickerSeriesEditor,
     cache_rev: u64,
+    anchor: Option<u64>,
 }
 
 #[derive(Debug, Clone)]
@@ -81,6 +82,7 @@ impl ComparisonChart {
                 .collect(),
             selected_tickers: tickers.to_vec(),
             pan: DEFAULT_PAN_POINTS,
+            anchor: cfg.anchor,
             config: cfg,
             series_editor: series_editor::TickerSeriesEditor::default(),
             cache_rev: 0,
@@ -109,6 +111,11 @@ impl ComparisonChart {
                     self.pan = DEFAULT_PAN_POINTS;
                     None
                 }
+                LineComparisonEvent::AnchorChanged(anchor) => {
+                    self.anchor = anchor;
+                    self.config.anchor = anchor;
+                    None
+                }
             },
             Message::Editor(msg) => self.series_editor.update(msg),
             Message::OpenEditorFor(ticker_info) => self.open_editor_for_ticker(ticker_info),
@@ -124,6 +131,7 @@ impl ComparisonChart {
             .with_timezone(timezone)
             .with_zoom(self.zoom)
             .with_pan(self.pan)
+            .with_anchor(self.anchor)
             .version(self.cache_rev)
             .into();
 
@@ -296,6 +304,7 @@ impl ComparisonChart {
                 Some(StreamKind::Kline {
                     ticker_info: ticker,
                     timeframe: self.timeframe,
+                    source: KlinePriceSource::Trade,
                 }),
             ));
         }
@@ -440,7 +449,11 @@ impl ComparisonChart {
                 names.push((ser_ticker, name.clone()));
             }
         }
-        data::chart::comparison::Config { colors, names }
+        data::chart::comparison::Config {
+            colors,
+            names,
+            anchor: self.anchor,
+        }
     }
 
     fn color_for_or_default(&self, ticker_info: &TickerInfo) -> iced::Color {
@@ -470,6 +483,7 @@ impl ComparisonChart {
             streams.push(StreamKind::Kline {
                 ticker_info: t,
                 timeframe: self.timeframe,
+                source: KlinePriceSource::Trade,
             });
         }
         streams
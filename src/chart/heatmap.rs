@@ -6,10 +6,10 @@ use crate::{
     style,
 };
 use data::chart::{
-    Basis, ViewConfig,
+    Basis, PriceScale, ViewConfig,
     heatmap::{
-        CLEANUP_THRESHOLD, Config, HeatmapDataPoint, HeatmapStudy, HistoricalDepth, ProfileKind,
-        QtyScale,
+        Config, GroupedTrade, HeatmapDataPoint, HeatmapStudy, HistoricalDepth, MAX_SNAPSHOTS,
+        MIN_SNAPSHOTS, ProfileKind, QtyScale,
     },
     indicator::HeatmapIndicator,
 };
@@ -17,6 +17,7 @@ use data::util::{abbr_large_numbers, count_decimals};
 use data::{
     aggr::time::{DataPoint, TimeSeries},
     chart::Autoscale,
+    walls::{WallEvent, WallSide, WallTracker},
 };
 use exchange::{
     TickerInfo, Trade,
@@ -46,6 +47,8 @@ const MIN_CELL_HEIGHT: f32 = 1.0;
 
 const DEFAULT_CELL_WIDTH: f32 = 3.0;
 
+const MEMORY_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
 const TOOLTIP_WIDTH: f32 = 198.0;
 const TOOLTIP_HEIGHT: f32 = 66.0;
 const TOOLTIP_PADDING: f32 = 12.0;
@@ -108,6 +111,11 @@ impl Chart for HeatmapChart {
     fn is_empty(&self) -> bool {
         self.trades.datapoints.is_empty()
     }
+
+    fn loaded_timerange(&self) -> Option<(u64, u64)> {
+        // heatmaps only hold what was streamed since the pane opened
+        None
+    }
 }
 
 impl PlotConstants for HeatmapChart {
@@ -153,7 +161,11 @@ pub struct HeatmapChart {
     pause_buffer: Vec<(u64, Box<[Trade]>, Depth)>,
     heatmap: HistoricalDepth,
     visual_config: Config,
+    stream_basis: Basis,
+    book_step: PriceStep,
     study_configurator: study::Configurator<HeatmapStudy>,
+    walls: WallTracker,
+    memory_checked_at: Option<Instant>,
     last_tick: Instant,
     pub studies: Vec<HeatmapStudy>,
 }
@@ -168,7 +180,11 @@ impl HeatmapChart {
         config: Option<Config>,
         studies: Vec<HeatmapStudy>,
     ) -> Self {
-        let step = PriceStep::from_f32(tick_size);
+        let visual_config = config.unwrap_or_default();
+
+        let book_step = PriceStep::from_f32(tick_size);
+        let cell_basis = visual_config.cell_basis(basis);
+        let step = visual_config.cell_step(book_step);
 
         let mut indicators = EnumMap::default();
         for &indicator in enabled_indicators {
@@ -177,16 +193,26 @@ impl HeatmapChart {
             });
         }
 
-        let heatmap = HistoricalDepth::new(ticker_info.min_qty.into(), step, basis);
+        let heatmap = HistoricalDepth::new(ticker_info.min_qty.into(), step, cell_basis);
 
         let view_state = ViewState::new(
-            basis,
+            cell_basis,
             step,
             count_decimals(tick_size),
             ticker_info,
             ViewConfig {
                 splits: layout.splits,
                 autoscale: Some(Autoscale::CenterLatest),
+                price_decimals: layout.price_decimals,
+                price_lines: layout.price_lines,
+                price_scale: PriceScale::Linear,
+                reverse_time: false,
+                invert_price: layout.invert_price,
+                watermark: layout.watermark,
+                measured_moves: layout.measured_moves,
+                autoscale_padding: layout.autoscale_padding,
+                price_snap: layout.price_snap,
+                quick_range: layout.quick_range,
             },
             DEFAULT_CELL_WIDTH,
             4.0,
@@ -197,9 +223,13 @@ impl HeatmapChart {
             indicators,
             pause_buffer: vec![],
             heatmap,
-            trades: TimeSeries::<HeatmapDataPoint>::new(basis, step),
-            visual_config: config.unwrap_or_default(),
+            trades: TimeSeries::<HeatmapDataPoint>::new(cell_basis, step),
+            visual_config,
+            stream_basis: basis,
+            book_step,
             study_configurator: study::Configurator::new(),
+            walls: WallTracker::default(),
+            memory_checked_at: None,
             studies,
             last_tick: Instant::now(),
         }
@@ -210,23 +240,33 @@ impl HeatmapChart {
         trades_buffer: &[Trade],
         depth_update_t: u64,
         depth: &Depth,
-    ) {
+    ) -> Vec<WallEvent> {
+        let wall_events = self
+            .walls
+            .update(depth, &self.visual_config.walls, depth_update_t);
+
         let chart = &mut self.chart;
 
         let mid_price = depth.mid_price().unwrap_or(chart.base_price_y);
         chart.last_price = Some(PriceInfoLabel::Neutral(mid_price));
+        chart.set_book_top(depth);
 
         // if current orderbook not visible, pause the data insertion and buffer them instead
         let is_paused = { chart.translation.x * chart.scaling > chart.bounds.width / 2.0 };
 
         if is_paused {
+            // keep the paused backlog within the same snapshot cap as the chart itself
+            if self.pause_buffer.len() >= self.max_snapshots() {
+                self.pause_buffer.remove(0);
+            }
+
             self.pause_buffer.push((
                 depth_update_t,
                 trades_buffer.to_vec().into_boxed_slice(),
                 depth.clone(),
             ));
 
-            return;
+            return wall_events;
         } else if !self.pause_buffer.is_empty() {
             self.pause_buffer.sort_by_key(|(time, _, _)| *time);
 
@@ -238,28 +278,107 @@ impl HeatmapChart {
         }
 
         self.process_datapoint(trades_buffer, depth_update_t, depth);
+
+        wall_events
     }
 
+    fn max_snapshots(&self) -> usize {
+        self.visual_config
+            .max_snapshots
+            .clamp(MIN_SNAPSHOTS, MAX_SNAPSHOTS)
+    }
+
+    /// Number of retained datapoints and their estimated memory footprint in bytes,
+    /// including the order runs and any paused depth snapshots
+    pub fn memory_usage(&self) -> (usize, usize) {
+        let datapoints = self.trades.datapoints.len();
+
+        let datapoints_bytes = self
+            .trades
+            .datapoints
+            .values()
+            .map(HeatmapDataPoint::memory_usage)
+            .sum::<usize>();
+
+        let paused_bytes = self
+            .pause_buffer
+            .iter()
+            .map(|(_, trades, depth)| {
+                data::chart::trades_memory_usage(trades.len())
+                    + (depth.bids.len() + depth.asks.len()) * std::mem::size_of::<(Price, f32)>()
+            })
+            .sum::<usize>();
+
+        (
+            datapoints,
+            datapoints_bytes + paused_bytes + self.heatmap.memory_usage(),
+        )
+    }
+
+    /// Drops the oldest snapshots beyond the snapshot cap, the history window or the memory cap
     fn cleanup_old_data(&mut self) {
-        if self.trades.datapoints.len() > CLEANUP_THRESHOLD {
-            let keys_to_remove = self
-                .trades
-                .datapoints
-                .keys()
-                .take(CLEANUP_THRESHOLD / 10)
-                .copied()
-                .collect::<Vec<u64>>();
+        let max_snapshots = self.max_snapshots();
+        let len = self.trades.datapoints.len();
 
-            for key in keys_to_remove {
-                self.trades.datapoints.remove(&key);
-            }
+        let mut excess = if len > max_snapshots {
+            (len - max_snapshots).max(max_snapshots / 10)
+        } else {
+            0
+        };
 
-            if let Some(oldest_time) = self.trades.datapoints.keys().next().copied() {
-                self.heatmap.cleanup_old_price_levels(oldest_time);
-            }
+        if let (Some(window), Some(latest)) = (
+            self.visual_config.history_window.to_millis(),
+            self.trades.latest_timestamp(),
+        ) {
+            let cutoff = latest.saturating_sub(window);
+            excess = excess.max(self.trades.datapoints.range(..cutoff).count());
+        }
+
+        excess = excess.max(self.memory_excess());
+
+        if excess == 0 {
+            return;
+        }
+
+        let keys_to_remove = self
+            .trades
+            .datapoints
+            .keys()
+            .take(excess)
+            .copied()
+            .collect::<Vec<u64>>();
+
+        for key in keys_to_remove {
+            self.trades.datapoints.remove(&key);
+        }
+
+        if let Some(oldest_time) = self.trades.datapoints.keys().next().copied() {
+            self.heatmap.cleanup_old_price_levels(oldest_time);
         }
     }
 
+    /// Snapshots to drop so the estimated footprint fits the memory cap; measuring walks
+    /// every order run, so it runs at most once per `MEMORY_CHECK_INTERVAL`
+    fn memory_excess(&mut self) -> usize {
+        if self
+            .memory_checked_at
+            .is_some_and(|at| at.elapsed() < MEMORY_CHECK_INTERVAL)
+        {
+            return 0;
+        }
+        self.memory_checked_at = Some(Instant::now());
+
+        let (snapshots, bytes) = self.memory_usage();
+        let cap = self.visual_config.max_memory_bytes();
+        if bytes <= cap || snapshots == 0 {
+            return 0;
+        }
+
+        // order runs are trimmed along with the snapshots, so shrink both by the overshoot ratio
+        let overshoot = 1.0 - cap as f64 / bytes as f64;
+        ((snapshots as f64 * overshoot).ceil() as usize).max(1)
+    }
+
     fn process_datapoint(&mut self, trades_buffer: &[Trade], depth_update: u64, depth: &Depth) {
         let chart = &mut self.chart;
 
@@ -301,19 +420,33 @@ impl HeatmapChart {
     }
 
     pub fn set_visual_config(&mut self, visual_config: Config) {
+        let grid_changed = visual_config.time_bucket != self.visual_config.time_bucket
+            || visual_config.price_bucket != self.visual_config.price_bucket;
+
         self.visual_config = visual_config;
+
+        if grid_changed {
+            self.rebuild_grid();
+        }
         self.invalidate(Some(Instant::now()));
     }
 
-    pub fn set_basis(&mut self, basis: Basis) {
+    /// Re-derives the cell size from the stream resolution and the configured buckets,
+    /// dropping history that was aggregated with the previous cell size
+    fn rebuild_grid(&mut self) {
+        let basis = self.visual_config.cell_basis(self.stream_basis);
+        let step = self.visual_config.cell_step(self.book_step);
+
         self.chart.basis = basis;
+        self.chart.tick_size = step;
 
-        self.trades.datapoints.clear();
-        self.heatmap = HistoricalDepth::new(
-            self.chart.ticker_info.min_qty.into(),
-            self.chart.tick_size,
-            basis,
-        );
+        self.trades = TimeSeries::<HeatmapDataPoint>::new(basis, step);
+        self.heatmap = HistoricalDepth::new(self.chart.ticker_info.min_qty.into(), step, basis);
+    }
+
+    pub fn set_basis(&mut self, basis: Basis) {
+        self.stream_basis = basis;
+        self.rebuild_grid();
 
         let chart = &mut self.chart;
         chart.translation = Vector::new(
@@ -367,19 +500,16 @@ impl HeatmapChart {
     pub fn change_tick_size(&mut self, new_tick_size: f32) {
         let chart_state = self.mut_state();
 
-        let basis = chart_state.basis;
-        let step = PriceStep::from_f32(new_tick_size);
-
         chart_state.cell_height = 4.0;
-        chart_state.tick_size = step;
         chart_state.decimals = count_decimals(new_tick_size);
 
-        self.trades.datapoints.clear();
-        self.heatmap = HistoricalDepth::new(self.chart.ticker_info.min_qty.into(), step, basis);
+        self.book_step = PriceStep::from_f32(new_tick_size);
+        self.rebuild_grid();
     }
 
+    /// Tick size of the order book stream, before any price bucketing
     pub fn tick_size(&self) -> f32 {
-        self.chart.tick_size.to_f32_lossy()
+        self.book_step.to_f32_lossy()
     }
 
     pub fn toggle_indicator(&mut self, indicator: HeatmapIndicator) {
@@ -416,26 +546,33 @@ impl HeatmapChart {
         self.last_tick
     }
 
+    /// Depth intensity is normalized over the rendered levels only, so a far-away wall
+    /// outside the cap doesn't wash out the visible book
     fn calc_qty_scales(
         &self,
         earliest: u64,
         latest: u64,
-        highest: Price,
-        lowest: Price,
+        depth_range: Option<(Price, Price)>,
     ) -> QtyScale {
         let market_type = self.chart.ticker_info.market_type();
 
-        let (max_trade_qty, max_aggr_volume) =
-            self.trades.max_trade_qty_and_aggr_volume(earliest, latest);
-
-        let max_depth_qty = self.heatmap.max_depth_qty_in_range(
-            earliest,
-            latest,
-            highest,
-            lowest,
-            market_type,
-            self.visual_config.order_size_filter,
-        );
+        // circles are scaled against the largest print that is actually drawn, so the
+        // filter doesn't leave everything tiny next to a hidden outlier
+        let is_visible = |trade: &GroupedTrade| self.is_trade_visible(trade);
+        let (max_trade_qty, max_aggr_volume) = self
+            .trades
+            .max_trade_qty_and_aggr_volume(earliest, latest, is_visible);
+
+        let max_depth_qty = depth_range.map_or(0.0, |(highest, lowest)| {
+            self.heatmap.max_depth_qty_in_range(
+                earliest,
+                latest,
+                highest,
+                lowest,
+                market_type,
+                self.visual_config.order_size_filter,
+            )
+        });
 
         QtyScale {
             max_trade_qty,
@@ -443,6 +580,19 @@ impl HeatmapChart {
             max_depth_qty,
         }
     }
+
+    /// Whether a print is drawn; the size filter is compared against its quote notional
+    fn is_trade_visible(&self, trade: &GroupedTrade) -> bool {
+        let size_in_quote_ccy = volume_size_unit() == exchange::SizeUnit::Quote;
+
+        let trade_size = self.chart.ticker_info.market_type().qty_in_quote_value(
+            trade.qty,
+            trade.price,
+            size_in_quote_ccy,
+        );
+
+        self.visual_config.show_trades && trade_size > self.visual_config.trade_size_filter
+    }
 }
 
 impl canvas::Program<Message> for HeatmapChart {
@@ -478,6 +628,8 @@ impl canvas::Program<Message> for HeatmapChart {
         let palette = theme.extended_palette();
 
         let heatmap = chart.cache.main.draw(renderer, bounds_size, |frame| {
+            chart.draw_watermark(frame, palette);
+
             let center = Vector::new(bounds.width / 2.0, bounds.height / 2.0);
 
             frame.translate(center);
@@ -494,7 +646,16 @@ impl canvas::Program<Message> for HeatmapChart {
             }
 
             let cell_height = chart.cell_height;
-            let qty_scales = self.calc_qty_scales(earliest, latest, highest, lowest);
+
+            // far levels stay cached for analytics, only their drawing is capped around mid
+            let mid_price = chart
+                .last_price
+                .map_or(chart.base_price_y, PriceInfoLabel::price);
+            let depth_range =
+                self.visual_config
+                    .render_price_range(highest, lowest, mid_price, chart.tick_size);
+
+            let qty_scales = self.calc_qty_scales(earliest, latest, depth_range);
 
             let max_depth_qty = qty_scales.max_depth_qty;
             let (max_aggr_volume, max_trade_qty) =
@@ -504,76 +665,80 @@ impl canvas::Program<Message> for HeatmapChart {
 
             let volume_indicator = self.indicators[HeatmapIndicator::Volume].is_some();
 
-            if let Some(merge_strat) = self.visual_config().coalescing {
-                let coalesced_visual_runs = self.heatmap.coalesced_runs(
-                    earliest,
-                    latest,
-                    highest,
-                    lowest,
-                    market_type,
-                    self.visual_config.order_size_filter,
-                    merge_strat,
-                );
+            if let Some((highest, lowest)) = depth_range {
+                if let Some(merge_strat) = self.visual_config().coalescing {
+                    let coalesced_visual_runs = self.heatmap.coalesced_runs(
+                        earliest,
+                        latest,
+                        highest,
+                        lowest,
+                        market_type,
+                        self.visual_config.order_size_filter,
+                        merge_strat,
+                    );
 
-                for (price_of_run, visual_run) in coalesced_visual_runs {
-                    let y_position = chart.price_to_y(price_of_run);
+                    for (price_of_run, visual_run) in coalesced_visual_runs {
+                        let y_position = chart.price_to_y(price_of_run);
 
-                    let run_start_time_clipped = visual_run.start_time.max(earliest);
-                    let run_until_time_clipped = visual_run.until_time.min(latest);
+                        let run_start_time_clipped = visual_run.start_time.max(earliest);
+                        let run_until_time_clipped = visual_run.until_time.min(latest);
 
-                    if run_start_time_clipped >= run_until_time_clipped {
-                        continue;
-                    }
+                        if run_start_time_clipped >= run_until_time_clipped {
+                            continue;
+                        }
 
-                    let start_x = chart.interval_to_x(run_start_time_clipped);
-                    let end_x = chart.interval_to_x(run_until_time_clipped).min(0.0);
+                        let start_x = chart.interval_to_x(run_start_time_clipped);
+                        let end_x = chart.interval_to_x(run_until_time_clipped).min(0.0);
 
-                    let width = end_x - start_x;
+                        let width = end_x - start_x;
 
-                    if width > 0.001 {
-                        let color_alpha = (visual_run.qty() / max_depth_qty).min(1.0);
+                        if width > 0.001 {
+                            let color_alpha = (visual_run.qty() / max_depth_qty).min(1.0);
 
-                        frame.fill_rectangle(
-                            Point::new(start_x, y_position - (cell_height / 2.0)),
-                            Size::new(width, cell_height),
-                            depth_color(palette, visual_run.is_bid, color_alpha),
-                        );
+                            frame.fill_rectangle(
+                                Point::new(start_x, y_position - (cell_height / 2.0)),
+                                Size::new(width, cell_height),
+                                depth_color(palette, visual_run.is_bid, color_alpha),
+                            );
+                        }
                     }
+                } else {
+                    self.heatmap
+                        .iter_time_filtered(earliest, latest, highest, lowest)
+                        .for_each(|(price, runs)| {
+                            let y_position = chart.price_to_y(*price);
+
+                            runs.iter()
+                                .filter(|run| {
+                                    let order_size = market_type.qty_in_quote_value(
+                                        run.qty(),
+                                        *price,
+                                        size_in_quote_ccy,
+                                    );
+                                    order_size > self.visual_config.order_size_filter
+                                })
+                                .for_each(|run| {
+                                    let start_x = chart.interval_to_x(run.start_time.max(earliest));
+                                    let end_x =
+                                        chart.interval_to_x(run.until_time.min(latest)).min(0.0);
+
+                                    let width = end_x - start_x;
+
+                                    let color_alpha = (run.qty() / max_depth_qty).min(1.0);
+
+                                    frame.fill_rectangle(
+                                        Point::new(start_x, y_position - (cell_height / 2.0)),
+                                        Size::new(width, cell_height),
+                                        depth_color(palette, run.is_bid, color_alpha),
+                                    );
+                                });
+                        });
                 }
-            } else {
-                self.heatmap
-                    .iter_time_filtered(earliest, latest, highest, lowest)
-                    .for_each(|(price, runs)| {
-                        let y_position = chart.price_to_y(*price);
-
-                        runs.iter()
-                            .filter(|run| {
-                                let order_size = market_type.qty_in_quote_value(
-                                    run.qty(),
-                                    *price,
-                                    size_in_quote_ccy,
-                                );
-                                order_size > self.visual_config.order_size_filter
-                            })
-                            .for_each(|run| {
-                                let start_x = chart.interval_to_x(run.start_time.max(earliest));
-                                let end_x =
-                                    chart.interval_to_x(run.until_time.min(latest)).min(0.0);
-
-                                let width = end_x - start_x;
-
-                                let color_alpha = (run.qty() / max_depth_qty).min(1.0);
-
-                                frame.fill_rectangle(
-                                    Point::new(start_x, y_position - (cell_height / 2.0)),
-                                    Size::new(width, cell_height),
-                                    depth_color(palette, run.is_bid, color_alpha),
-                                );
-                            });
-                    });
             }
 
-            if let Some(latest_timestamp) = self.trades.latest_timestamp() {
+            if let Some(latest_timestamp) = self.trades.latest_timestamp()
+                && let Some((highest, lowest)) = depth_range
+            {
                 let max_qty = self
                     .heatmap
                     .latest_order_runs(highest, lowest, latest_timestamp)
@@ -613,22 +778,94 @@ impl canvas::Program<Message> for HeatmapChart {
                 }
             };
 
+            if self.visual_config.walls.enabled {
+                let text_size = 9.0 / chart.scaling;
+
+                for (side, price, qty) in self.walls.walls() {
+                    let price = price.round_to_step(chart.tick_size);
+                    if price > highest || price < lowest {
+                        continue;
+                    }
+
+                    let y_position = chart.price_to_y(price);
+                    let color = depth_color(palette, side == WallSide::Bid, 0.8);
+
+                    frame.fill_rectangle(
+                        Point::new(region.x, y_position - 0.5 / chart.scaling),
+                        Size::new(region.width, 1.0 / chart.scaling),
+                        color,
+                    );
+
+                    frame.fill_text(canvas::Text {
+                        content: data::util::format_volume(qty),
+                        position: Point::new(52.0, y_position),
+                        size: iced::Pixels(text_size),
+                        color,
+                        font: style::AZERET_MONO,
+                        align_y: Alignment::Center.into(),
+                        ..canvas::Text::default()
+                    });
+                }
+            }
+
+            let cell_volume = self.visual_config.cell_volume;
+            let cell_volume_maxima = cell_volume.enabled.then(|| {
+                self.trades
+                    .rolling_max_cell_volume(earliest, latest, cell_volume.rolling_columns())
+            });
+
+            let mut prints: Vec<&GroupedTrade> = vec![];
+
             self.trades
                 .datapoints
                 .range(earliest..=latest)
                 .for_each(|(time, dp)| {
                     let x_position = chart.interval_to_x(*time);
 
-                    dp.grouped_trades.iter().for_each(|trade| {
-                        let y_position = chart.price_to_y(trade.price);
+                    // per-cell traded volume, drawn as a small profile across the time column
+                    if let Some(max_qty) = cell_volume_maxima
+                        .as_ref()
+                        .and_then(|maxima| maxima.get(time))
+                        .filter(|max_qty| **max_qty > 0.0)
+                    {
+                        let bar_length = chart.cell_width * 0.9;
 
-                        let trade_size = market_type.qty_in_quote_value(
-                            trade.qty,
-                            trade.price,
-                            size_in_quote_ccy,
+                        for (price, (buy_qty, sell_qty)) in dp.cell_volumes() {
+                            if price > highest || price < lowest {
+                                continue;
+                            }
+
+                            super::draw_volume_bar(
+                                frame,
+                                x_position - bar_length / 2.0,
+                                chart.price_to_y(price),
+                                buy_qty,
+                                sell_qty,
+                                *max_qty,
+                                bar_length,
+                                cell_height * 0.5,
+                                palette.success.base.color,
+                                palette.danger.base.color,
+                                0.6,
+                                true,
+                            );
+                        }
+                    }
+
+                    if self.visual_config.show_trades {
+                        // larger prints first, so a smaller opposite-side print
+                        // in the same cell stays visible on top
+                        prints.clear();
+                        prints.extend(
+                            dp.grouped_trades
+                                .iter()
+                                .filter(|trade| self.is_trade_visible(trade)),
                         );
+                        prints.sort_unstable_by(|a, b| b.qty.total_cmp(&a.qty));
+
+                        for trade in &prints {
+                            let y_position = chart.price_to_y(trade.price);
 
-                        if trade_size > self.visual_config.trade_size_filter {
                             let color = if trade.is_sell {
                                 palette.danger.base.color
                             } else {
@@ -652,7 +889,7 @@ impl canvas::Program<Message> for HeatmapChart {
                                 color,
                             );
                         }
-                    });
+                    }
 
                     if volume_indicator {
                         let bar_width = (chart.cell_width / 2.0) * 0.9;
@@ -735,6 +972,8 @@ impl canvas::Program<Message> for HeatmapChart {
                 );
             }
 
+            chart.draw_book_top_lines(frame, palette, region);
+
             let is_paused = chart.translation.x * chart.scaling > chart.bounds.width / 2.0;
             if is_paused {
                 let bar_width = 8.0 / chart.scaling;
@@ -766,6 +1005,14 @@ impl canvas::Program<Message> for HeatmapChart {
 
         if !self.is_empty() {
             let crosshair = chart.cache.crosshair.draw(renderer, bounds_size, |frame| {
+                chart.draw_measured_moves(
+                    frame,
+                    theme,
+                    bounds_size,
+                    interaction,
+                    cursor.position_in(bounds),
+                );
+
                 if let Some(cursor_position) = cursor.position_in(bounds) {
                     let (cursor_at_price, cursor_at_time) = chart.draw_crosshair(
                         frame,
@@ -775,8 +1022,10 @@ impl canvas::Program<Message> for HeatmapChart {
                         interaction,
                     );
 
-                    if matches!(interaction, Interaction::Panning { .. })
-                        || matches!(interaction, Interaction::Ruler { start } if start.is_some())
+                    if matches!(
+                        interaction,
+                        Interaction::Panning { .. } | Interaction::MeasuredMove { .. }
+                    ) || matches!(interaction, Interaction::Ruler { start } if start.is_some())
                     {
                         return;
                     }
@@ -887,6 +1136,8 @@ impl canvas::Program<Message> for HeatmapChart {
                             }
                         }
                     }
+                } else {
+                    chart.draw_synced_crosshair(frame, bounds_size);
                 }
             });
 
@@ -905,7 +1156,7 @@ impl canvas::Program<Message> for HeatmapChart {
         match interaction {
             Interaction::Panning { .. } => mouse::Interaction::Grabbing,
             Interaction::Zoomin { .. } => mouse::Interaction::ZoomIn,
-            Interaction::None | Interaction::Ruler { .. } => {
+            Interaction::None | Interaction::Ruler { .. } | Interaction::MeasuredMove { .. } => {
                 if cursor.is_over(bounds) {
                     return mouse::Interaction::Crosshair;
                 }
@@ -4,11 +4,11 @@ pub mod plot;
 use super::scale::linear;
 use super::{Interaction, Message};
 use crate::chart::{
-    Caches, TEXT_SIZE, ViewState,
+    Caches, ViewState,
     indicator::plot::{AnySeries, ChartCanvas, Plot},
-    scale::{AxisLabel, LabelContent, calc_label_rect},
+    scale::{AxisLabel, LabelContent, calc_label_rect, label_text_size},
 };
-use data::util::{abbr_large_numbers, round_to_tick};
+use data::util::{format_volume, round_to_tick};
 
 use iced::{
     Element, Event, Length, Rectangle, Renderer, Theme, mouse,
@@ -103,11 +103,12 @@ impl canvas::Program<Message> for IndicatorLabel<'_> {
         let tick_size = data::util::guesstimate_ticks(range);
 
         let labels = self.label_cache.draw(renderer, bounds.size(), |frame| {
+            let text_size = label_text_size();
             let mut all_labels = linear::generate_labels(
                 bounds,
                 self.min,
                 self.max,
-                TEXT_SIZE,
+                text_size,
                 palette.background.base.text,
                 None,
             );
@@ -126,16 +127,16 @@ impl canvas::Program<Message> for IndicatorLabel<'_> {
                 );
 
                 let label = LabelContent {
-                    content: abbr_large_numbers(rounded_value),
+                    content: format_volume(rounded_value),
                     background_color: Some(palette.secondary.base.color),
                     text_color: palette.secondary.base.text,
-                    text_size: TEXT_SIZE,
+                    text_size,
                 };
 
                 let y_position = bounds.height - ((rounded_value - lowest) / range * bounds.height);
 
                 all_labels.push(AxisLabel::Y {
-                    bounds: calc_label_rect(y_position, 1, TEXT_SIZE, bounds),
+                    bounds: calc_label_rect(y_position, 1, text_size, bounds),
                     value_label: label,
                     timer_label: None,
                 });
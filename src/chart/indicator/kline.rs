@@ -2,11 +2,12 @@ use crate::chart::{Message, ViewState};
 
 use data::chart::PlotData;
 use data::chart::indicator::KlineIndicator;
-use data::chart::kline::KlineDataPoint;
+use data::chart::kline::{Config, KlineDataPoint};
 use exchange::fetcher::FetchRange;
 use exchange::{Kline, Timeframe, Trade};
 
 pub mod open_interest;
+pub mod trade_count;
 pub mod volume;
 
 pub trait KlineIndicatorImpl {
@@ -47,6 +48,9 @@ pub trait KlineIndicatorImpl {
     fn on_basis_change(&mut self, _source: &PlotData<KlineDataPoint>) {}
 
     fn on_open_interest(&mut self, _pairs: &[exchange::OpenInterest]) {}
+
+    /// Chart's visual config has changed, or the indicator was just created
+    fn on_visual_config(&mut self, _config: &Config) {}
 }
 
 pub struct FetchCtx<'a> {
@@ -63,5 +67,8 @@ pub fn make_empty(which: KlineIndicator) -> Box<dyn KlineIndicatorImpl> {
         KlineIndicator::OpenInterest => {
             Box::new(super::kline::open_interest::OpenInterestIndicator::new())
         }
+        KlineIndicator::TradeCount => {
+            Box::new(super::kline::trade_count::TradeCountIndicator::new())
+        }
     }
 }
@@ -3,11 +3,19 @@ use crate::chart::{
     indicator::{
         indicator_row,
         kline::{FetchCtx, KlineIndicatorImpl},
-        plot::{PlotTooltip, line::LinePlot},
+        plot::{
+            PlotTooltip,
+            bar::{BarClass, BarPlot},
+            line::LinePlot,
+        },
     },
 };
 
-use data::chart::{PlotData, kline::KlineDataPoint};
+use data::chart::{
+    PlotData,
+    indicator::{IndicatorStyle, KlineIndicator},
+    kline::{Config, KlineDataPoint, OpenInterestDisplay},
+};
 use data::util::format_with_commas;
 use exchange::{Kline, Timeframe, Trade};
 use exchange::{adapter::Exchange, fetcher::FetchRange};
@@ -18,6 +26,9 @@ use std::{collections::BTreeMap, ops::RangeInclusive};
 pub struct OpenInterestIndicator {
     cache: Caches,
     pub data: BTreeMap<u64, f32>,
+    display: OpenInterestDisplay,
+    deltas: BTreeMap<u64, f32>,
+    style: Option<IndicatorStyle>,
 }
 
 impl OpenInterestIndicator {
@@ -25,9 +36,23 @@ impl OpenInterestIndicator {
         Self {
             cache: Caches::default(),
             data: BTreeMap::new(),
+            display: OpenInterestDisplay::default(),
+            deltas: BTreeMap::new(),
+            style: None,
         }
     }
 
+    /// Change between consecutive OI values, keyed by the later one's time;
+    /// the first point has no prior value so it's left out
+    fn rebuild_deltas(&mut self) {
+        self.deltas = self
+            .data
+            .values()
+            .zip(self.data.iter().skip(1))
+            .map(|(prev, (time, value))| (*time, value - prev))
+            .collect();
+    }
+
     fn indicator_elem<'a>(
         &'a self,
         main_chart: &'a ViewState,
@@ -60,6 +85,25 @@ impl OpenInterestIndicator {
             }
         }
 
+        if self.display == OpenInterestDisplay::Delta {
+            let tooltip = |delta: &f32, _next: Option<&f32>| {
+                let sign = if *delta >= 0.0 { "+" } else { "" };
+                PlotTooltip::new(format!("OI Change: {}{}", sign, format_with_commas(*delta)))
+            };
+
+            // sign of the change decides the color: rising OI is a build-up, falling an unwind
+            let bar_kind = |delta: &f32| BarClass::Overlay { overlay: *delta };
+            let value_fn = |delta: &f32| delta.abs();
+
+            let plot = BarPlot::new(value_fn, bar_kind)
+                .bar_width_factor(0.9)
+                .padding(0.08)
+                .style(self.style)
+                .with_tooltip(tooltip);
+
+            return indicator_row(main_chart, &self.cache, plot, &self.deltas, visible_range);
+        }
+
         let tooltip = |value: &f32, next: Option<&f32>| {
             let value_text = format!("Open Interest: {}", format_with_commas(*value));
             let change_text = if let Some(next_value) = next {
@@ -79,6 +123,7 @@ impl OpenInterestIndicator {
             .show_points(true)
             .point_radius_factor(0.2)
             .padding(0.08)
+            .style(self.style)
             .with_tooltip(tooltip);
 
         indicator_row(main_chart, &self.cache, plot, &self.data, visible_range)
@@ -97,7 +142,11 @@ impl OpenInterestIndicator {
     }
 
     pub fn is_supported_exchange(exchange: Exchange) -> bool {
-        exchange.is_perps() && exchange != Exchange::HyperliquidLinear
+        exchange.is_perps()
+            && !matches!(
+                exchange,
+                Exchange::HyperliquidLinear | Exchange::GateioLinear | Exchange::BitgetLinear
+            )
     }
 
     pub fn is_supported_timeframe(timeframe: Timeframe) -> bool {
@@ -168,6 +217,16 @@ impl KlineIndicatorImpl for OpenInterestIndicator {
 
     fn on_open_interest(&mut self, data: &[exchange::OpenInterest]) {
         self.data.extend(data.iter().map(|oi| (oi.time, oi.value)));
+        self.rebuild_deltas();
         self.clear_all_caches();
     }
+
+    fn on_visual_config(&mut self, config: &Config) {
+        let style = config.indicator_colors.get(KlineIndicator::OpenInterest);
+        if self.display != config.open_interest || self.style != style {
+            self.display = config.open_interest;
+            self.style = style;
+            self.clear_all_caches();
+        }
+    }
 }
@@ -0,0 +1,136 @@
+use crate::chart::{
+    Caches, Message, ViewState,
+    indicator::{
+        indicator_row,
+        kline::KlineIndicatorImpl,
+        plot::{
+            PlotTooltip,
+            bar::{BarClass, BarPlot},
+        },
+    },
+};
+
+use data::chart::indicator::{IndicatorStyle, KlineIndicator};
+use data::chart::{
+    PlotData,
+    kline::{Config, KlineDataPoint},
+};
+use data::util::format_with_commas;
+use exchange::{Kline, Trade};
+
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+pub struct TradeCountIndicator {
+    cache: Caches,
+    data: BTreeMap<u64, u64>,
+    style: Option<IndicatorStyle>,
+}
+
+impl TradeCountIndicator {
+    pub fn new() -> Self {
+        Self {
+            cache: Caches::default(),
+            data: BTreeMap::new(),
+            style: None,
+        }
+    }
+
+    fn indicator_elem<'a>(
+        &'a self,
+        main_chart: &'a ViewState,
+        visible_range: RangeInclusive<u64>,
+    ) -> iced::Element<'a, Message> {
+        let tooltip = |count: &u64, _next: Option<&u64>| {
+            PlotTooltip::new(format!("Trades: {}", format_with_commas(*count as f32)))
+        };
+
+        let plot = BarPlot::new(|count: &u64| *count as f32, |_: &u64| BarClass::Single)
+            .bar_width_factor(0.9)
+            .style(self.style)
+            .with_tooltip(tooltip);
+
+        indicator_row(main_chart, &self.cache, plot, &self.data, visible_range)
+    }
+}
+
+impl KlineIndicatorImpl for TradeCountIndicator {
+    fn clear_all_caches(&mut self) {
+        self.cache.clear_all();
+    }
+
+    fn clear_crosshair_caches(&mut self) {
+        self.cache.clear_crosshair();
+    }
+
+    fn element<'a>(
+        &'a self,
+        chart: &'a ViewState,
+        visible_range: RangeInclusive<u64>,
+    ) -> iced::Element<'a, Message> {
+        self.indicator_elem(chart, visible_range)
+    }
+
+    fn rebuild_from_source(&mut self, source: &PlotData<KlineDataPoint>) {
+        match source {
+            PlotData::TimeBased(timeseries) => {
+                self.data = timeseries.trade_count_data(0);
+            }
+            PlotData::TickBased(tickseries) => {
+                self.data = tickseries.trade_count_data();
+            }
+        }
+        self.clear_all_caches();
+    }
+
+    fn on_insert_klines(&mut self, klines: &[Kline]) {
+        for kline in klines {
+            if let Some(count) = kline.trade_count {
+                self.data.insert(kline.time, count);
+            }
+        }
+        self.clear_all_caches();
+    }
+
+    fn on_insert_trades(
+        &mut self,
+        trades: &[Trade],
+        old_dp_len: usize,
+        source: &PlotData<KlineDataPoint>,
+    ) {
+        match source {
+            PlotData::TimeBased(timeseries) => {
+                let Some(earliest) = trades.iter().map(|t| t.time).min() else {
+                    return;
+                };
+                let interval = timeseries.interval.to_milliseconds();
+                let bucket_start = (earliest / interval) * interval;
+
+                self.data.extend(timeseries.trade_count_data(bucket_start));
+            }
+            PlotData::TickBased(tickseries) => {
+                let start_idx = old_dp_len.saturating_sub(1);
+                for (idx, dp) in tickseries.datapoints.iter().enumerate().skip(start_idx) {
+                    self.data.insert(idx as u64, dp.tick_count as u64);
+                }
+            }
+        }
+        self.clear_all_caches();
+    }
+
+    fn on_ticksize_change(&mut self, source: &PlotData<KlineDataPoint>) {
+        self.rebuild_from_source(source);
+    }
+
+    fn on_basis_change(&mut self, source: &PlotData<KlineDataPoint>) {
+        self.rebuild_from_source(source);
+    }
+
+    fn on_visual_config(&mut self, config: &Config) {
+        let style = config.indicator_colors.get(KlineIndicator::TradeCount);
+        if self.style != style {
+            self.style = style;
+            self.clear_all_caches();
+        }
+    }
+}
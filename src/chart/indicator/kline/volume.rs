@@ -10,7 +10,11 @@ use crate::chart::{
     },
 };
 
-use data::chart::{PlotData, kline::KlineDataPoint};
+use data::chart::indicator::{IndicatorStyle, KlineIndicator};
+use data::chart::{
+    PlotData,
+    kline::{Config, KlineDataPoint},
+};
 use data::util::format_with_commas;
 use exchange::{Kline, Trade};
 
@@ -20,6 +24,7 @@ use std::ops::RangeInclusive;
 pub struct VolumeIndicator {
     cache: Caches,
     data: BTreeMap<u64, (f32, f32)>,
+    style: Option<IndicatorStyle>,
 }
 
 impl VolumeIndicator {
@@ -27,6 +32,7 @@ impl VolumeIndicator {
         Self {
             cache: Caches::default(),
             data: BTreeMap::new(),
+            style: None,
         }
     }
 
@@ -61,6 +67,7 @@ impl VolumeIndicator {
 
         let plot = BarPlot::new(value_fn, bar_kind)
             .bar_width_factor(0.9)
+            .style(self.style)
             .with_tooltip(tooltip);
 
         indicator_row(main_chart, &self.cache, plot, &self.data, visible_range)
@@ -130,4 +137,12 @@ impl KlineIndicatorImpl for VolumeIndicator {
     fn on_basis_change(&mut self, source: &PlotData<KlineDataPoint>) {
         self.rebuild_from_source(source);
     }
+
+    fn on_visual_config(&mut self, config: &Config) {
+        let style = config.indicator_colors.get(KlineIndicator::Volume);
+        if self.style != style {
+            self.style = style;
+            self.clear_all_caches();
+        }
+    }
 }
@@ -203,7 +203,14 @@ where
             canvas::Event::Mouse(mouse::Event::CursorMoved { .. }) => {
                 let msg = matches!(*interaction, Interaction::None)
                     .then(|| cursor.is_over(bounds))
-                    .and_then(|over| over.then_some(Message::CrosshairMoved));
+                    .and_then(|over| {
+                        over.then(|| {
+                            let time = cursor
+                                .position_in(bounds)
+                                .and_then(|position| self.ctx.time_at_x(position.x, bounds.size()));
+                            Message::CrosshairMoved(time)
+                        })
+                    });
                 let action = msg.map_or(canvas::Action::request_redraw(), canvas::Action::publish);
                 Some(match interaction {
                     Interaction::None => action,
@@ -281,7 +288,7 @@ where
                             as u64
                             * step as u64;
 
-                        let sr = if latest <= earliest {
+                        let sr = if latest == earliest {
                             0.5
                         } else {
                             ((rx as f64 - earliest) / (latest - earliest)) as f32
@@ -1,5 +1,6 @@
 use std::ops::RangeInclusive;
 
+use data::chart::indicator::IndicatorStyle;
 use iced::{Point, Size, Theme, widget::canvas};
 
 use crate::chart::{
@@ -37,6 +38,8 @@ pub struct BarPlot<V, CL, T> {
     pub classify: CL, // Single vs Overlay with signed overlay
     pub tooltip: Option<TooltipFn<T>>,
     pub baseline: Baseline,
+    /// User color/opacity override; replaces the neutral color, up/down colors only fade
+    pub style: Option<IndicatorStyle>,
     _phantom: std::marker::PhantomData<T>,
 }
 
@@ -50,6 +53,7 @@ impl<V, CL, T> BarPlot<V, CL, T> {
             classify,
             tooltip: None,
             baseline: Baseline::Zero,
+            style: None,
             _phantom: std::marker::PhantomData,
         }
     }
@@ -69,6 +73,11 @@ impl<V, CL, T> BarPlot<V, CL, T> {
         self
     }
 
+    pub fn style(mut self, style: Option<IndicatorStyle>) -> Self {
+        self.style = style;
+        self
+    }
+
     pub fn with_tooltip<F>(mut self, tooltip: F) -> Self
     where
         F: Fn(&T, Option<&T>) -> PlotTooltip + 'static,
@@ -131,6 +140,11 @@ where
         let palette = theme.extended_palette();
         let bar_width = ctx.cell_width * self.bar_width_factor;
 
+        let neutral = self
+            .style
+            .map_or(palette.secondary.strong.color, |style| style.color());
+        let fade = |color: iced::Color| self.style.map_or(color, |style| style.fade(color));
+
         let baseline_value = match self.baseline {
             Baseline::Zero => 0.0,
             Baseline::Min => scale.min, // extents min
@@ -161,20 +175,25 @@ where
                     frame.fill_rectangle(
                         Point::new(left, top_y),
                         Size::new(bar_width, h_total),
-                        palette.secondary.strong.color,
+                        neutral,
                     );
                 }
                 BarClass::Overlay { overlay } => {
-                    let base_color = if overlay >= 0.0 {
+                    let base_color = fade(if overlay >= 0.0 {
                         palette.success.base.color
                     } else {
                         palette.danger.base.color
-                    };
+                    });
 
+                    let total_color = if self.style.is_some() {
+                        neutral
+                    } else {
+                        base_color
+                    };
                     frame.fill_rectangle(
                         Point::new(left, top_y),
                         Size::new(bar_width, h_total),
-                        base_color.scale_alpha(0.3),
+                        total_color.scale_alpha(0.3),
                     );
 
                     let ov_abs = overlay.abs().max(0.0);
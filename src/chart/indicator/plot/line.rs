@@ -1,5 +1,6 @@
 use std::ops::RangeInclusive;
 
+use data::chart::indicator::IndicatorStyle;
 use iced::{
     Theme,
     widget::canvas::{self, Path, Stroke},
@@ -18,6 +19,8 @@ pub struct LinePlot<V, T> {
     pub stroke_width: f32,
     pub show_points: bool,
     pub point_radius_factor: f32,
+    /// User color/opacity override of the theme's line color
+    pub style: Option<IndicatorStyle>,
     _phantom: std::marker::PhantomData<T>,
 }
 
@@ -32,6 +35,7 @@ impl<V, T> LinePlot<V, T> {
             stroke_width: 1.0,
             show_points: true,
             point_radius_factor: 0.2,
+            style: None,
             _phantom: std::marker::PhantomData,
         }
     }
@@ -59,6 +63,11 @@ impl<V, T> LinePlot<V, T> {
         self
     }
 
+    pub fn style(mut self, style: Option<IndicatorStyle>) -> Self {
+        self.style = style;
+        self
+    }
+
     pub fn with_tooltip<F>(mut self, tooltip: F) -> Self
     where
         F: Fn(&T, Option<&T>) -> PlotTooltip + 'static,
@@ -114,7 +123,9 @@ where
         scale: &YScale,
     ) {
         let palette = theme.extended_palette();
-        let color = palette.secondary.strong.color;
+        let color = self
+            .style
+            .map_or(palette.secondary.strong.color, |style| style.color());
 
         let stroke = Stroke::with_color(
             Stroke {
@@ -1,22 +1,31 @@
 use super::{
     Action, Basis, Chart, Interaction, Message, PlotConstants, PlotData, TEXT_SIZE, ViewState,
-    indicator, request_fetch, scale::linear::PriceInfoLabel,
+    indicator, request_fetch, request_stream_fetch, scale::linear::PriceInfoLabel,
 };
 use crate::chart::indicator::kline::KlineIndicatorImpl;
-use crate::{modal::pane::settings::study, style};
+use crate::modal::pane::settings::{CandleColorTarget, study};
+use crate::style;
+use data::aggr::PrintAggregation;
 use data::aggr::ticks::TickAggr;
 use data::aggr::time::TimeSeries;
 use data::chart::Autoscale;
-use data::chart::kline::ClusterScaling;
+use data::chart::kline::{
+    CandleColors, ClusterScaling, Config, DeltaColoring, HigherTimeframe, HigherTimeframeStyle,
+    TradeFetchConfig, VolumeBaseline,
+};
+use data::chart::session::{self, SessionLevels, SessionLevelsConfig};
+use data::chart::swings::{Swing, SwingTracker};
 use data::chart::{
-    KlineChartKind, ViewConfig,
-    indicator::{Indicator, KlineIndicator},
+    KlineChartKind, PriceScale, QuickRange, ViewConfig,
+    indicator::KlineIndicator,
     kline::{ClusterKind, FootprintStudy, KlineDataPoint, KlineTrades, NPoc, PointOfControl},
 };
-use data::util::{abbr_large_numbers, count_decimals};
+use data::orderflow::{OrderFlowEvent, OrderFlowSignal, OrderFlowTracker, SignalKind};
+use data::util::{abbr_large_numbers, count_decimals, format_price, format_volume};
 use exchange::util::{Price, PriceStep};
 use exchange::{
-    Kline, OpenInterest as OIData, TickerInfo, Trade,
+    Kline, OpenInterest as OIData, SizeUnit, TickerInfo, Trade,
+    adapter::{KlinePriceSource, MarketKind, StreamKind},
     fetcher::{FetchRange, RequestHandler},
 };
 
@@ -26,8 +35,49 @@ use iced::widget::canvas::{self, Event, Geometry, Path, Stroke};
 use iced::{Alignment, Element, Point, Rectangle, Renderer, Size, Theme, Vector, mouse};
 
 use enum_map::EnumMap;
+use std::collections::BTreeMap;
 use std::time::Instant;
 
+/// Minimum exchange-time gap between two trade retention passes
+const RETENTION_CHECK_INTERVAL_MS: u64 = 30_000;
+
+/// Print aggregation only shapes footprint clusters; candle charts always use raw trades
+fn print_aggregation(kind: &KlineChartKind, config: &Config) -> PrintAggregation {
+    match kind {
+        KlineChartKind::Footprint { .. } => config.print_aggregation,
+        KlineChartKind::Candles => PrintAggregation {
+            enabled: false,
+            ..config.print_aggregation
+        },
+    }
+}
+
+/// Mark and index candles only apply to candlestick charts on markets that offer them;
+/// footprints are built from trades and always use the last traded price
+pub fn price_source(
+    kind: &KlineChartKind,
+    config: &Config,
+    ticker_info: TickerInfo,
+) -> KlinePriceSource {
+    match kind {
+        KlineChartKind::Candles => config.price_source.resolve(ticker_info.exchange()),
+        KlineChartKind::Footprint { .. } => KlinePriceSource::Trade,
+    }
+}
+
+/// Indicators that get a panel: those applicable to the market, minus the trade-based ones
+/// when the candles come from a price source without volume
+fn shown_indicators(
+    enabled: &[KlineIndicator],
+    market: MarketKind,
+    source: KlinePriceSource,
+) -> Vec<KlineIndicator> {
+    data::chart::indicator::applicable(enabled, market)
+        .into_iter()
+        .filter(|indicator| source.has_volume() || !indicator.needs_trades())
+        .collect()
+}
+
 impl Chart for KlineChart {
     type IndicatorKind = KlineIndicator;
 
@@ -62,11 +112,8 @@ impl Chart for KlineChart {
         let market = chart_state.ticker_info.market_type();
         let mut elements = vec![];
 
-        for selected_indicator in enabled {
-            if !KlineIndicator::for_market(market).contains(selected_indicator) {
-                continue;
-            }
-            if let Some(indi) = self.indicators[*selected_indicator].as_ref() {
+        for selected_indicator in shown_indicators(enabled, market, self.price_source()) {
+            if let Some(indi) = self.indicators[selected_indicator].as_ref() {
                 elements.push(indi.element(chart_state, earliest..=latest));
             }
         }
@@ -85,9 +132,13 @@ impl Chart for KlineChart {
             Basis::Time(timeframe) => {
                 let interval = timeframe.to_milliseconds();
 
+                let (left, right) = (
+                    chart.x_to_interval(region.x),
+                    chart.x_to_interval(region.x + region.width),
+                );
                 let (earliest, latest) = (
-                    chart.x_to_interval(region.x) - (interval / 2),
-                    chart.x_to_interval(region.x + region.width) + (interval / 2),
+                    left.min(right) - (interval / 2),
+                    left.max(right) + (interval / 2),
                 );
 
                 Some((earliest, latest))
@@ -122,7 +173,7 @@ impl Chart for KlineChart {
                     - (8.0 * chart.cell_width / chart.scaling)
             }
         };
-        Vector::new(x_translation, chart.translation.y)
+        Vector::new(x_translation * chart.x_sign(), chart.translation.y)
     }
 
     fn supports_fit_autoscaling(&self) -> bool {
@@ -135,6 +186,15 @@ impl Chart for KlineChart {
             PlotData::TickBased(tick_aggr) => tick_aggr.datapoints.is_empty(),
         }
     }
+
+    fn loaded_timerange(&self) -> Option<(u64, u64)> {
+        match &self.data_source {
+            PlotData::TimeBased(timeseries) if !timeseries.datapoints.is_empty() => {
+                Some(timeseries.timerange())
+            }
+            _ => None,
+        }
+    }
 }
 
 impl PlotConstants for KlineChart {
@@ -167,16 +227,68 @@ impl PlotConstants for KlineChart {
     }
 }
 
+/// Progress of a historical trades fetch used to rebuild footprints
+enum TradeFetch {
+    Idle,
+    Running(Box<RunningTradeFetch>),
+    /// Stopped by the user, no new fetch starts until the fetch settings change
+    Cancelled,
+}
+
+struct RunningTradeFetch {
+    range: (u64, u64),
+    fetched_until: u64,
+    /// Candles that only exist because they were rebuilt from the fetched trades
+    rebuilt: Vec<u64>,
+    handle: Option<Handle>,
+}
+
+/// Paginated kline download reaching back `max_history`, newest page first
+struct RunningBackfill {
+    range: (u64, u64),
+    fetched_from: u64,
+    fetched: usize,
+    handle: Option<Handle>,
+}
+
+/// Candles of the higher timeframe overlay, fetched and updated apart from the primary series
+struct HigherTfSeries {
+    config: HigherTimeframe,
+    klines: BTreeMap<u64, Kline>,
+    request_handler: RequestHandler,
+}
+
+impl HigherTfSeries {
+    fn for_config(config: &Config, basis: Basis) -> Option<Box<Self>> {
+        config.effective_higher_timeframe(basis).map(|config| {
+            Box::new(HigherTfSeries {
+                config,
+                klines: BTreeMap::new(),
+                request_handler: RequestHandler::new(),
+            })
+        })
+    }
+}
+
 pub struct KlineChart {
     chart: ViewState,
     data_source: PlotData<KlineDataPoint>,
     raw_trades: Vec<Trade>,
     indicators: EnumMap<KlineIndicator, Option<Box<dyn KlineIndicatorImpl>>>,
-    fetching_trades: (bool, Option<Handle>),
+    trade_fetch: TradeFetch,
+    backfill: Option<RunningBackfill>,
     pub(crate) kind: KlineChartKind,
     request_handler: RequestHandler,
     study_configurator: study::Configurator<FootprintStudy>,
     last_tick: Instant,
+    visual_config: Config,
+    pub candle_color_editing: Option<CandleColorTarget>,
+    pub indicator_color_editing: Option<KlineIndicator>,
+    last_retention_check: u64,
+    higher_tf: Option<Box<HigherTfSeries>>,
+    order_flow: OrderFlowTracker,
+    swings: SwingTracker,
+    volume_baseline: VolumeBaseline,
 }
 
 impl KlineChart {
@@ -189,13 +301,32 @@ impl KlineChart {
         enabled_indicators: &[KlineIndicator],
         ticker_info: TickerInfo,
         kind: &KlineChartKind,
+        config: Option<Config>,
     ) -> Self {
+        let visual_config = config.unwrap_or_default();
+        // indicators that don't apply to this market or price source stay in the pane's list
+        // but get no panel, so the splits must be sized for the shown ones only
+        let enabled_indicators = shown_indicators(
+            enabled_indicators,
+            ticker_info.market_type(),
+            price_source(kind, &visual_config, ticker_info),
+        );
+        let layout = ViewConfig {
+            splits: data::util::fit_panel_splits(&layout.splits, enabled_indicators.len()),
+            ..layout
+        };
+        let price_scale = match kind {
+            KlineChartKind::Candles => layout.price_scale,
+            KlineChartKind::Footprint { .. } => PriceScale::Linear,
+        };
+        let prints = print_aggregation(kind, &visual_config).apply(&raw_trades);
+
         match basis {
             Basis::Time(interval) => {
                 let step = PriceStep::from_f32(tick_size);
 
                 let timeseries = TimeSeries::<KlineDataPoint>::new(interval, step, klines_raw)
-                    .with_trades(&raw_trades);
+                    .with_trades(&prints);
 
                 let base_price_y = timeseries.base_price();
                 let latest_x = timeseries.latest_timestamp().unwrap_or(0);
@@ -231,6 +362,16 @@ impl KlineChart {
                     ViewConfig {
                         splits: layout.splits,
                         autoscale: Some(Autoscale::FitToVisible),
+                        price_decimals: layout.price_decimals,
+                        price_lines: layout.price_lines,
+                        price_scale,
+                        reverse_time: layout.reverse_time,
+                        invert_price: layout.invert_price,
+                        watermark: layout.watermark,
+                        measured_moves: layout.measured_moves,
+                        autoscale_padding: layout.autoscale_padding,
+                        price_snap: layout.price_snap,
+                        quick_range: layout.quick_range,
                     },
                     cell_width,
                     cell_height,
@@ -248,13 +389,14 @@ impl KlineChart {
                             - (8.0 * chart.cell_width / chart.scaling)
                     }
                 };
-                chart.translation.x = x_translation;
+                chart.translation.x = x_translation * chart.x_sign();
 
                 let data_source = PlotData::TimeBased(timeseries);
 
                 let mut indicators = EnumMap::default();
-                for &i in enabled_indicators {
+                for &i in &enabled_indicators {
                     let mut indi = indicator::kline::make_empty(i);
+                    indi.on_visual_config(&visual_config);
                     indi.rebuild_from_source(&data_source);
                     indicators[i] = Some(indi);
                 }
@@ -264,11 +406,20 @@ impl KlineChart {
                     data_source,
                     raw_trades,
                     indicators,
-                    fetching_trades: (false, None),
+                    trade_fetch: TradeFetch::Idle,
+                    backfill: None,
                     request_handler: RequestHandler::new(),
                     kind: kind.clone(),
                     study_configurator: study::Configurator::new(),
                     last_tick: Instant::now(),
+                    higher_tf: HigherTfSeries::for_config(&visual_config, basis),
+                    visual_config,
+                    candle_color_editing: None,
+                    indicator_color_editing: None,
+                    last_retention_check: 0,
+                    order_flow: OrderFlowTracker::default(),
+                    swings: SwingTracker::default(),
+                    volume_baseline: VolumeBaseline::default(),
                 }
             }
             Basis::Tick(interval) => {
@@ -291,6 +442,16 @@ impl KlineChart {
                     ViewConfig {
                         splits: layout.splits,
                         autoscale: Some(Autoscale::FitToVisible),
+                        price_decimals: layout.price_decimals,
+                        price_lines: layout.price_lines,
+                        price_scale,
+                        reverse_time: layout.reverse_time,
+                        invert_price: layout.invert_price,
+                        watermark: layout.watermark,
+                        measured_moves: layout.measured_moves,
+                        autoscale_padding: layout.autoscale_padding,
+                        price_snap: layout.price_snap,
+                        quick_range: layout.quick_range,
                     },
                     cell_width,
                     cell_height,
@@ -306,13 +467,14 @@ impl KlineChart {
                             - (8.0 * chart.cell_width / chart.scaling)
                     }
                 };
-                chart.translation.x = x_translation;
+                chart.translation.x = x_translation * chart.x_sign();
 
-                let data_source = PlotData::TickBased(TickAggr::new(interval, step, &raw_trades));
+                let data_source = PlotData::TickBased(TickAggr::new(interval, step, &prints));
 
                 let mut indicators = EnumMap::default();
-                for &i in enabled_indicators {
+                for &i in &enabled_indicators {
                     let mut indi = indicator::kline::make_empty(i);
+                    indi.on_visual_config(&visual_config);
                     indi.rebuild_from_source(&data_source);
                     indicators[i] = Some(indi);
                 }
@@ -322,11 +484,20 @@ impl KlineChart {
                     data_source,
                     raw_trades,
                     indicators,
-                    fetching_trades: (false, None),
+                    trade_fetch: TradeFetch::Idle,
+                    backfill: None,
                     request_handler: RequestHandler::new(),
                     kind: kind.clone(),
                     study_configurator: study::Configurator::new(),
                     last_tick: Instant::now(),
+                    higher_tf: HigherTfSeries::for_config(&visual_config, basis),
+                    visual_config,
+                    candle_color_editing: None,
+                    indicator_color_editing: None,
+                    last_retention_check: 0,
+                    order_flow: OrderFlowTracker::default(),
+                    swings: SwingTracker::default(),
+                    volume_baseline: VolumeBaseline::default(),
                 }
             }
         }
@@ -386,15 +557,56 @@ impl KlineChart {
                     }
                 }
 
+                // higher timeframe overlay, through its own kline stream
+                if let Some(series) = self.higher_tf.as_mut() {
+                    let timeframe = series.config.timeframe;
+                    let htf_earliest = earliest.saturating_sub(timeframe.to_milliseconds());
+
+                    let range = match series.klines.first_key_value() {
+                        None => Some(FetchRange::Kline(
+                            htf_earliest,
+                            chrono::Utc::now().timestamp_millis() as u64,
+                        )),
+                        Some((&first, _)) if visible_earliest < first => {
+                            Some(FetchRange::Kline(htf_earliest, first))
+                        }
+                        Some(_) => None,
+                    };
+
+                    if let Some(range) = range {
+                        let stream = StreamKind::Kline {
+                            ticker_info: self.chart.ticker_info,
+                            timeframe,
+                            source: price_source(
+                                &self.kind,
+                                &self.visual_config,
+                                self.chart.ticker_info,
+                            ),
+                        };
+
+                        if let Some(action) =
+                            request_stream_fetch(&mut series.request_handler, range, Some(stream))
+                        {
+                            return Some(action);
+                        }
+                    }
+                }
+
                 // priority 2, trades fetch
-                if !self.fetching_trades.0
+                if matches!(self.trade_fetch, TradeFetch::Idle)
                     && exchange::fetcher::is_trade_fetch_enabled()
+                    && self.chart.ticker_info.exchange().supports_trade_fetch()
                     && let Some((fetch_from, fetch_to)) =
                         timeseries.suggest_trade_fetch_range(visible_earliest, visible_latest)
                 {
                     let range = FetchRange::Trades(fetch_from, fetch_to);
                     if let Some(action) = request_fetch(&mut self.request_handler, range) {
-                        self.fetching_trades = (true, None);
+                        self.trade_fetch = TradeFetch::Running(Box::new(RunningTradeFetch {
+                            range: (fetch_from, fetch_to),
+                            fetched_until: fetch_from,
+                            rebuilt: vec![],
+                            handle: None,
+                        }));
                         return Some(action);
                     }
                 }
@@ -439,16 +651,164 @@ impl KlineChart {
     }
 
     pub fn reset_request_handler(&mut self) {
-        self.request_handler = RequestHandler::new();
-        self.fetching_trades = (false, None);
+        self.request_handler.supersede();
+        self.trade_fetch = TradeFetch::Idle;
+        self.backfill = None;
+    }
+
+    /// Requests klines from `max_history` ago up to the earliest loaded candle
+    pub fn start_backfill(&mut self) -> Option<Action> {
+        if self.backfill.is_some() {
+            return None;
+        }
+        let PlotData::TimeBased(timeseries) = &self.data_source else {
+            return None;
+        };
+
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+        let from = now.saturating_sub(self.visual_config.max_history.to_millis());
+        let to = if timeseries.datapoints.is_empty() {
+            now
+        } else {
+            timeseries.timerange().0
+        };
+
+        if from >= to {
+            return None;
+        }
+
+        let action = request_fetch(
+            &mut self.request_handler,
+            FetchRange::KlineBackfill(from, to),
+        );
+        if action.is_some() {
+            self.backfill = Some(RunningBackfill {
+                range: (from, to),
+                fetched_from: to,
+                fetched: 0,
+                handle: None,
+            });
+        }
+        action
+    }
+
+    pub fn set_backfill_handle(&mut self, handle: Handle) {
+        if let Some(backfill) = &mut self.backfill {
+            backfill.handle = Some(handle);
+        }
+    }
+
+    pub fn is_backfilling(&self) -> bool {
+        self.backfill.is_some()
+    }
+
+    /// Candles received so far and the share of the requested range they cover, in percent
+    pub fn backfill_progress(&self) -> (usize, u8) {
+        match &self.backfill {
+            Some(backfill) if backfill.range.1 > backfill.range.0 => {
+                let (from, to) = backfill.range;
+                let done = to.saturating_sub(backfill.fetched_from) as f64 / (to - from) as f64;
+                (backfill.fetched, (done * 100.0).clamp(0.0, 100.0) as u8)
+            }
+            _ => (0, 0),
+        }
+    }
+
+    /// Aborts the running backfill; pages that already arrived stay on the chart
+    pub fn cancel_backfill(&mut self) {
+        // dropping the handle aborts the download task
+        self.backfill = None;
     }
 
     pub fn raw_trades(&self) -> Vec<Trade> {
         self.raw_trades.clone()
     }
 
+    fn print_aggregation(&self) -> PrintAggregation {
+        print_aggregation(&self.kind, &self.visual_config)
+    }
+
+    /// Refills footprints from the retained raw trades, e.g. after the print aggregation changed
+    fn rebuild_from_raw_trades(&mut self) {
+        let prints = self.print_aggregation().apply(&self.raw_trades);
+        self.order_flow.clear();
+
+        match self.data_source {
+            PlotData::TimeBased(ref mut timeseries) => {
+                timeseries.clear_trades();
+                timeseries.insert_trades_existing_buckets(&prints);
+            }
+            PlotData::TickBased(ref mut tick_aggr) => {
+                *tick_aggr = TickAggr::new(tick_aggr.interval, tick_aggr.tick_size, &prints);
+
+                self.indicators
+                    .values_mut()
+                    .filter_map(Option::as_mut)
+                    .for_each(|indi| indi.on_basis_change(&self.data_source));
+            }
+        }
+    }
+
     pub fn set_handle(&mut self, handle: Handle) {
-        self.fetching_trades.1 = Some(handle);
+        if let TradeFetch::Running(fetch) = &mut self.trade_fetch {
+            fetch.handle = Some(handle);
+        }
+    }
+
+    pub fn is_fetching_trades(&self) -> bool {
+        matches!(self.trade_fetch, TradeFetch::Running(_))
+    }
+
+    /// Share of the requested range covered so far, in percent
+    pub fn trade_fetch_progress(&self) -> u8 {
+        match &self.trade_fetch {
+            TradeFetch::Running(fetch) if fetch.range.1 > fetch.range.0 => {
+                let (from, to) = fetch.range;
+                let done = fetch.fetched_until.saturating_sub(from) as f64 / (to - from) as f64;
+                (done * 100.0).clamp(0.0, 100.0) as u8
+            }
+            _ => 0,
+        }
+    }
+
+    pub fn trade_fetch_config(&self) -> TradeFetchConfig {
+        self.visual_config.trade_fetch
+    }
+
+    /// Aborts the running trades fetch and drops whatever it already inserted,
+    /// so footprints look the same as before the fetch started
+    pub fn cancel_trade_fetch(&mut self) {
+        let TradeFetch::Running(fetch) = &mut self.trade_fetch else {
+            return;
+        };
+        let (from, to) = fetch.range;
+        let rebuilt = std::mem::take(&mut fetch.rebuilt);
+
+        // dropping the handle aborts the download task
+        self.trade_fetch = TradeFetch::Cancelled;
+
+        self.raw_trades
+            .retain(|trade| trade.time < from || trade.time > to);
+
+        let prints = self.print_aggregation().apply(&self.raw_trades);
+        if let PlotData::TimeBased(ref mut timeseries) = self.data_source {
+            for time in &rebuilt {
+                timeseries.datapoints.remove(time);
+            }
+            timeseries.clear_trades();
+            timeseries.insert_trades_existing_buckets(&prints);
+        }
+
+        if !rebuilt.is_empty() {
+            self.swings.clear();
+            self.volume_baseline.clear();
+            self.indicators
+                .values_mut()
+                .filter_map(Option::as_mut)
+                .for_each(|indi| indi.rebuild_from_source(&self.data_source));
+        }
+
+        self.invalidate(None);
     }
 
     pub fn tick_size(&self) -> f32 {
@@ -493,6 +853,50 @@ impl KlineChart {
         self.chart.layout()
     }
 
+    pub fn visual_config(&self) -> Config {
+        self.visual_config
+    }
+
+    pub fn set_visual_config(&mut self, visual_config: Config) {
+        if visual_config.trade_fetch != self.visual_config.trade_fetch
+            && matches!(self.trade_fetch, TradeFetch::Cancelled)
+        {
+            self.trade_fetch = TradeFetch::Idle;
+        }
+        let rebuild_prints =
+            visual_config.print_aggregation != self.visual_config.print_aggregation;
+        if visual_config.order_flow != self.visual_config.order_flow {
+            self.order_flow.clear();
+        }
+        if visual_config.swings != self.visual_config.swings {
+            self.swings.clear();
+        }
+        if visual_config.volume_opacity.period != self.visual_config.volume_opacity.period {
+            self.volume_baseline.clear();
+        }
+        self.visual_config = visual_config;
+        self.sync_higher_tf();
+
+        self.indicators
+            .values_mut()
+            .filter_map(Option::as_mut)
+            .for_each(|indi| indi.on_visual_config(&visual_config));
+
+        if rebuild_prints {
+            self.rebuild_from_raw_trades();
+        }
+        self.invalidate(None);
+    }
+
+    fn candle_colors(&self, palette: &Extended) -> CandleColors {
+        self.visual_config.candle_colors.unwrap_or(CandleColors {
+            up_body: palette.success.base.color,
+            down_body: palette.danger.base.color,
+            up_wick: palette.success.base.color,
+            down_wick: palette.danger.base.color,
+        })
+    }
+
     pub fn set_cluster_kind(&mut self, new_kind: ClusterKind) {
         if let KlineChartKind::Footprint {
             ref mut clusters, ..
@@ -527,12 +931,14 @@ impl KlineChart {
         chart.cell_height *= new_tick_size / chart.tick_size.to_f32_lossy();
         chart.tick_size = step;
 
+        let prints = self.print_aggregation().apply(&self.raw_trades);
+        self.order_flow.clear();
         match self.data_source {
             PlotData::TickBased(ref mut tick_aggr) => {
-                tick_aggr.change_tick_size(new_tick_size, &self.raw_trades);
+                tick_aggr.change_tick_size(new_tick_size, &prints);
             }
             PlotData::TimeBased(ref mut timeseries) => {
-                timeseries.change_tick_size(new_tick_size, &self.raw_trades);
+                timeseries.change_tick_size(new_tick_size, &prints);
             }
         }
 
@@ -547,6 +953,9 @@ impl KlineChart {
     pub fn set_basis(&mut self, new_basis: Basis) -> Option<Action> {
         self.chart.last_price = None;
         self.chart.basis = new_basis;
+        self.order_flow.clear();
+        self.swings.clear();
+        self.volume_baseline.clear();
 
         match new_basis {
             Basis::Time(interval) => {
@@ -556,7 +965,8 @@ impl KlineChart {
             }
             Basis::Tick(tick_count) => {
                 let step = self.chart.tick_size;
-                let tick_aggr = TickAggr::new(tick_count, step, &self.raw_trades);
+                let prints = self.print_aggregation().apply(&self.raw_trades);
+                let tick_aggr = TickAggr::new(tick_count, step, &prints);
                 self.data_source = PlotData::TickBased(tick_aggr);
             }
         }
@@ -567,9 +977,68 @@ impl KlineChart {
             .for_each(|indi| indi.on_basis_change(&self.data_source));
 
         self.reset_request_handler();
+        self.higher_tf = HigherTfSeries::for_config(&self.visual_config, new_basis);
         self.invalidate(Some(Instant::now()))
     }
 
+    /// Keeps the overlay series in line with the config, refetching only if its timeframe changed
+    fn sync_higher_tf(&mut self) {
+        let wanted = self
+            .visual_config
+            .effective_higher_timeframe(self.chart.basis);
+
+        match (&mut self.higher_tf, wanted) {
+            (Some(series), Some(config)) if series.config.timeframe == config.timeframe => {
+                series.config = config;
+            }
+            _ => self.higher_tf = HigherTfSeries::for_config(&self.visual_config, self.chart.basis),
+        }
+    }
+
+    /// Kline stream feeding the higher timeframe overlay, if one is shown
+    pub fn higher_tf_stream(&self) -> Option<StreamKind> {
+        self.higher_tf.as_ref().map(|series| StreamKind::Kline {
+            ticker_info: self.chart.ticker_info,
+            timeframe: series.config.timeframe,
+            source: self.price_source(),
+        })
+    }
+
+    /// Price the candles are built from, after falling back for markets without it
+    pub fn price_source(&self) -> KlinePriceSource {
+        price_source(&self.kind, &self.visual_config, self.chart.ticker_info)
+    }
+
+    pub fn insert_higher_tf_klines(&mut self, req_id: uuid::Uuid, klines: &[Kline]) {
+        let Some(series) = self.higher_tf.as_mut() else {
+            return;
+        };
+
+        if !series.request_handler.is_current(req_id) {
+            log::debug!("Ignoring higher timeframe klines for superseded request {req_id}");
+            return;
+        }
+
+        series
+            .klines
+            .extend(klines.iter().map(|kline| (kline.time, *kline)));
+
+        if klines.is_empty() {
+            series
+                .request_handler
+                .mark_failed(req_id, "No data received".to_string());
+        } else {
+            series.request_handler.mark_completed(req_id);
+        }
+        self.invalidate(None);
+    }
+
+    pub fn update_higher_tf_kline(&mut self, kline: &Kline) {
+        if let Some(series) = self.higher_tf.as_mut() {
+            series.klines.insert(kline.time, *kline);
+        }
+    }
+
     pub fn studies(&self) -> Option<Vec<FootprintStudy>> {
         match &self.kind {
             KlineChartKind::Footprint { studies, .. } => Some(studies.clone()),
@@ -588,13 +1057,24 @@ impl KlineChart {
         self.invalidate(None);
     }
 
-    pub fn insert_trades_buffer(&mut self, trades_buffer: &[Trade]) {
+    pub fn set_book_top(&mut self, depth: &exchange::depth::Depth) {
+        self.chart.set_book_top(depth);
+    }
+
+    pub fn set_mark_price(&mut self, mark_price: f32) {
+        self.chart.set_mark_price(mark_price);
+    }
+
+    /// Returns order flow patterns newly spotted in the forming candle
+    pub fn insert_trades_buffer(&mut self, trades_buffer: &[Trade]) -> Vec<OrderFlowEvent> {
         self.raw_trades.extend_from_slice(trades_buffer);
 
+        let prints = self.print_aggregation().apply(trades_buffer);
+
         match self.data_source {
             PlotData::TickBased(ref mut tick_aggr) => {
                 let old_dp_len = tick_aggr.datapoints.len();
-                tick_aggr.insert_trades(trades_buffer);
+                tick_aggr.insert_trades(&prints);
 
                 if let Some(last_dp) = tick_aggr.datapoints.last() {
                     self.chart.last_price =
@@ -606,40 +1086,196 @@ impl KlineChart {
                 self.indicators
                     .values_mut()
                     .filter_map(Option::as_mut)
-                    .for_each(|indi| {
-                        indi.on_insert_trades(trades_buffer, old_dp_len, &self.data_source)
-                    });
+                    .for_each(|indi| indi.on_insert_trades(&prints, old_dp_len, &self.data_source));
 
                 self.invalidate(None);
             }
             PlotData::TimeBased(ref mut timeseries) => {
-                timeseries.insert_trades_existing_buckets(trades_buffer);
+                timeseries.insert_trades_existing_buckets(&prints);
+            }
+        }
+
+        self.scan_order_flow()
+    }
+
+    /// Runs absorption/aggression detection over the candles not yet settled, which after
+    /// a reset means the whole history
+    fn scan_order_flow(&mut self) -> Vec<OrderFlowEvent> {
+        if !matches!(self.kind, KlineChartKind::Footprint { .. }) {
+            return vec![];
+        }
+
+        let cfg = self.visual_config.order_flow;
+        let step = self.chart.tick_size;
+        let from = self.order_flow.resume_from();
+
+        match &self.data_source {
+            PlotData::TimeBased(timeseries) => self.order_flow.update(
+                timeseries
+                    .datapoints
+                    .range(from.unwrap_or(0)..)
+                    .map(|(_, dp)| (&dp.kline, &dp.footprint)),
+                &cfg,
+                step,
+            ),
+            PlotData::TickBased(tick_aggr) => {
+                let start = from.map_or(0, |from| {
+                    tick_aggr
+                        .datapoints
+                        .partition_point(|dp| dp.kline.time < from)
+                });
+                self.order_flow
+                    .update(
+                    tick_aggr.datapoints[start..]
+                        .iter()
+                        .map(|dp| (&dp.kline, &dp.footprint)),
+                    &cfg,
+                    step,
+                )
+            }
+        }
+    }
+
+    /// Confirms swing points on the candles closed since the last scan
+    fn scan_swings(&mut self) {
+        let cfg = self.visual_config.swings;
+        let from = self.swings.resume_from();
+
+        match &self.data_source {
+            PlotData::TimeBased(timeseries) => self.swings.update(
+                timeseries
+                    .datapoints
+                    .range(from.unwrap_or(0)..)
+                    .map(|(_, dp)| &dp.kline),
+                &cfg,
+            ),
+            PlotData::TickBased(tick_aggr) => {
+                let start = from.map_or(0, |from| {
+                    tick_aggr
+                        .datapoints
+                        .partition_point(|dp| dp.kline.time < from)
+                });
+                self.swings.update(
+                    tick_aggr.datapoints[start..].iter().map(|dp| &dp.kline),
+                    &cfg,
+                );
+            }
+        }
+    }
+
+    /// Extends the rolling volume mean behind volume-weighted candle opacity
+    fn scan_volume_baseline(&mut self) {
+        let cfg = self.visual_config.volume_opacity;
+        let market = self.chart.ticker_info.market_type();
+        let size_in_quote_ccy = exchange::volume_size_unit() == SizeUnit::Quote;
+        let volume_of = |kline: &Kline| candle_volume_value(kline, market, size_in_quote_ccy);
+
+        self.volume_baseline.sync_unit(size_in_quote_ccy);
+        let from = self.volume_baseline.resume_from();
+
+        match &self.data_source {
+            PlotData::TimeBased(timeseries) => self.volume_baseline.update(
+                timeseries
+                    .datapoints
+                    .range(from.unwrap_or(0)..)
+                    .map(|(_, dp)| &dp.kline),
+                &cfg,
+                volume_of,
+            ),
+            PlotData::TickBased(tick_aggr) => {
+                let start = from.map_or(0, |from| {
+                    tick_aggr
+                        .datapoints
+                        .partition_point(|dp| dp.kline.time < from)
+                });
+                self.volume_baseline.update(
+                    tick_aggr.datapoints[start..].iter().map(|dp| &dp.kline),
+                    &cfg,
+                    volume_of,
+                );
             }
         }
     }
 
+    /// Drops raw trades outside the configured retention; aggregated footprints are kept
+    pub fn apply_trade_retention(&mut self, now_ms: u64) {
+        if now_ms.saturating_sub(self.last_retention_check) < RETENTION_CHECK_INTERVAL_MS {
+            return;
+        }
+        self.last_retention_check = now_ms;
+
+        self.visual_config
+            .trade_retention
+            .apply(&mut self.raw_trades, now_ms);
+    }
+
+    pub fn retained_trades(&self) -> usize {
+        self.raw_trades.len()
+    }
+
+    /// Groups fetched trades into footprint candles of the configured base timeframe and
+    /// folds those into the chart's candles, rebuilding candles the kline history lacks
     pub fn insert_raw_trades(&mut self, raw_trades: Vec<Trade>, is_batches_done: bool) {
-        match self.data_source {
+        self.order_flow.clear();
+
+        let prints = self.print_aggregation().apply(&raw_trades);
+        let rebuilt = match self.data_source {
             PlotData::TickBased(ref mut tick_aggr) => {
-                tick_aggr.insert_trades(&raw_trades);
+                tick_aggr.insert_trades(&prints);
+                vec![]
             }
             PlotData::TimeBased(ref mut timeseries) => {
-                timeseries.insert_trades_existing_buckets(&raw_trades);
+                let bucket = self
+                    .visual_config
+                    .trade_fetch
+                    .bucket_timeframe(timeseries.interval);
+                let candles = TimeSeries::from_trades(bucket, timeseries.tick_size, &prints);
+
+                timeseries.merge_footprint_candles(candles)
+            }
+        };
+
+        if !rebuilt.is_empty() {
+            self.swings.clear();
+            self.volume_baseline.clear();
+            self.indicators
+                .values_mut()
+                .filter_map(Option::as_mut)
+                .for_each(|indi| indi.on_insert_klines(&rebuilt));
+        }
+
+        if let TradeFetch::Running(fetch) = &mut self.trade_fetch {
+            fetch.rebuilt.extend(rebuilt.iter().map(|kline| kline.time));
+
+            if let Some(last) = raw_trades.last() {
+                fetch.fetched_until = last.time;
             }
         }
 
         self.raw_trades.extend(raw_trades);
 
         if is_batches_done {
-            self.fetching_trades = (false, None);
+            self.trade_fetch = TradeFetch::Idle;
         }
     }
 
     pub fn insert_hist_klines(&mut self, req_id: uuid::Uuid, klines_raw: &[Kline]) {
+        if !self.request_handler.is_current(req_id) {
+            log::debug!("Ignoring klines for superseded request {req_id}");
+            return;
+        }
+
+        self.order_flow.clear();
+        self.swings.clear();
+        self.volume_baseline.clear();
         match self.data_source {
             PlotData::TimeBased(ref mut timeseries) => {
+                let is_first_load = timeseries.datapoints.is_empty();
+
                 timeseries.insert_klines(klines_raw);
-                timeseries.insert_trades_existing_buckets(&self.raw_trades);
+                timeseries.insert_trades_existing_buckets(
+                    &print_aggregation(&self.kind, &self.visual_config).apply(&self.raw_trades),
+                );
 
                 self.indicators
                     .values_mut()
@@ -652,14 +1288,64 @@ impl KlineChart {
                 } else {
                     self.request_handler.mark_completed(req_id);
                 }
+
+                // a restored pane reapplies its persisted quick range once candles exist
+                if is_first_load {
+                    super::frame_quick_range(self);
+                }
                 self.invalidate(None);
             }
             PlotData::TickBased(_) => {}
         }
     }
 
+    pub fn insert_backfill_klines(&mut self, klines_raw: &[Kline], is_done: bool) {
+        let Some(backfill) = &mut self.backfill else {
+            return;
+        };
+
+        if let Some(first) = klines_raw.first() {
+            backfill.fetched_from = backfill.fetched_from.min(first.time);
+        }
+        backfill.fetched += klines_raw.len();
+
+        if is_done {
+            self.backfill = None;
+        }
+
+        if let PlotData::TimeBased(ref mut timeseries) = self.data_source
+            && !klines_raw.is_empty()
+        {
+            timeseries.insert_klines(klines_raw);
+            timeseries.insert_trades_existing_buckets(
+                &print_aggregation(&self.kind, &self.visual_config).apply(&self.raw_trades),
+            );
+            self.order_flow.clear();
+            self.swings.clear();
+            self.volume_baseline.clear();
+
+            self.indicators
+                .values_mut()
+                .filter_map(Option::as_mut)
+                .for_each(|indi| indi.on_insert_klines(klines_raw));
+
+            self.invalidate(None);
+        }
+
+        // "Max" frames the whole history once the backfill has reached as far back as it goes
+        if is_done && self.chart.layout.quick_range == Some(QuickRange::Max) {
+            super::frame_quick_range(self);
+            self.invalidate(None);
+        }
+    }
+
     pub fn insert_open_interest(&mut self, req_id: Option<uuid::Uuid>, oi_data: &[OIData]) {
         if let Some(req_id) = req_id {
+            if !self.request_handler.is_current(req_id) {
+                log::debug!("Ignoring open interest for superseded request {req_id}");
+                return;
+            }
+
             if oi_data.is_empty() {
                 self.request_handler
                     .mark_failed(req_id, "No data received".to_string());
@@ -714,6 +1400,13 @@ impl KlineChart {
     }
 
     pub fn invalidate(&mut self, now: Option<Instant>) -> Option<Action> {
+        // history changed since the last scan; settle it silently so highlights come back
+        if self.order_flow.resume_from().is_none() {
+            self.scan_order_flow();
+        }
+        self.scan_swings();
+        self.scan_volume_baseline();
+
         let chart = &mut self.chart;
 
         if let Some(autoscale) = chart.layout.autoscale {
@@ -729,7 +1422,7 @@ impl KlineChart {
                                 - (8.0 * chart.cell_width / chart.scaling)
                         }
                     };
-                    chart.translation.x = x_translation;
+                    chart.translation.x = x_translation * chart.x_sign();
 
                     let calculate_target_y = |kline: exchange::Kline| -> f32 {
                         let y_low = chart.price_to_y(kline.low);
@@ -746,7 +1439,12 @@ impl KlineChart {
                             let visible_y_top = view_center_y_centered - visible_half_height;
                             let visible_y_bottom = view_center_y_centered + visible_half_height;
 
-                            let padding = chart.cell_height;
+                            // never more than half the view, so the last price stays visible
+                            let padding = (2.0
+                                * visible_half_height
+                                * chart.layout.autoscale_padding.fraction())
+                            .max(chart.cell_height)
+                            .min(visible_half_height);
 
                             if y_close < visible_y_top {
                                 target_y_translation = -(y_close - padding + visible_half_height);
@@ -767,18 +1465,23 @@ impl KlineChart {
                         .data_source
                         .visible_price_range(start_interval, end_interval)
                     {
-                        let padding = (highest - lowest) * 0.05;
-                        let price_span = (highest - lowest) + (2.0 * padding);
-
-                        if price_span > 0.0 && chart.bounds.height > f32::EPSILON {
-                            let padded_highest = highest + padding;
-                            let chart_height = chart.bounds.height;
-                            let tick_size = chart.tick_size.to_f32_lossy();
-
-                            if tick_size > 0.0 {
-                                chart.cell_height = (chart_height * tick_size) / price_span;
-                                chart.base_price_y = Price::from_f32(padded_highest);
-                                chart.translation.y = -chart_height / 2.0;
+                        if chart.layout.price_scale == PriceScale::Logarithmic {
+                            chart.fit_log_price_range(lowest, highest);
+                        } else {
+                            let padding =
+                                (highest - lowest) * chart.layout.autoscale_padding.fraction();
+                            let price_span = (highest - lowest) + (2.0 * padding);
+
+                            if price_span > 0.0 && chart.bounds.height > f32::EPSILON {
+                                let padded_highest = highest + padding;
+                                let chart_height = chart.bounds.height;
+                                let tick_size = chart.tick_size.to_f32_lossy();
+
+                                if tick_size > 0.0 {
+                                    chart.cell_height = (chart_height * tick_size) / price_span;
+                                    chart.base_price_y = Price::from_f32(padded_highest);
+                                    chart.translation.y = -chart_height / 2.0 * chart.y_sign();
+                                }
                             }
                         }
                     }
@@ -799,13 +1502,23 @@ impl KlineChart {
         }
     }
 
+    pub fn has_open_interest(&self) -> bool {
+        self.indicators[KlineIndicator::OpenInterest].is_some()
+    }
+
     pub fn toggle_indicator(&mut self, indicator: KlineIndicator) {
+        let market = self.chart.ticker_info.market_type();
+        if shown_indicators(&[indicator], market, self.price_source()).is_empty() {
+            return;
+        }
+
         let prev_indi_count = self.indicators.values().filter(|v| v.is_some()).count();
 
         if self.indicators[indicator].is_some() {
             self.indicators[indicator] = None;
         } else {
             let mut box_indi = indicator::kline::make_empty(indicator);
+            box_indi.on_visual_config(&self.visual_config);
             box_indi.rebuild_from_source(&self.data_source);
             self.indicators[indicator] = Some(box_indi);
         }
@@ -852,6 +1565,8 @@ impl canvas::Program<Message> for KlineChart {
         let palette = theme.extended_palette();
 
         let klines = chart.cache.main.draw(renderer, bounds_size, |frame| {
+            chart.draw_watermark(frame, palette);
+
             let center = Vector::new(bounds.width / 2.0, bounds.height / 2.0);
 
             frame.translate(center);
@@ -864,6 +1579,10 @@ impl canvas::Program<Message> for KlineChart {
             let price_to_y = |price| chart.price_to_y(price);
             let interval_to_x = |interval| chart.interval_to_x(interval);
 
+            if let Some(series) = &self.higher_tf {
+                draw_higher_timeframe(frame, series, chart, earliest, latest, palette);
+            }
+
             match &self.kind {
                 KlineChartKind::Footprint {
                     clusters,
@@ -916,6 +1635,16 @@ impl canvas::Program<Message> for KlineChart {
                         should_show_text(cell_height_unscaled, cell_width_unscaled, min_w)
                     };
 
+                    let delta_shading =
+                        self.visual_config
+                            .delta_coloring
+                            .enabled
+                            .then(|| DeltaShading {
+                                coloring: self.visual_config.delta_coloring,
+                                market_type: chart.ticker_info.market_type(),
+                                size_in_quote_ccy: exchange::volume_size_unit() == SizeUnit::Quote,
+                            });
+
                     draw_all_npocs(
                         &self.data_source,
                         frame,
@@ -931,6 +1660,7 @@ impl canvas::Program<Message> for KlineChart {
                         *clusters,
                         content_spacing,
                         imbalance.is_some(),
+                        chart.x_sign(),
                     );
 
                     render_data_source(
@@ -939,10 +1669,23 @@ impl canvas::Program<Message> for KlineChart {
                         earliest,
                         latest,
                         interval_to_x,
-                        |frame, x_position, kline, trades| {
+                        |frame, x_position, kline, trades, _| {
                             let cluster_scaling =
                                 effective_cluster_qty(*scaling, max_cluster_qty, trades, *clusters);
 
+                            if let Some(shading) = delta_shading {
+                                draw_delta_shading(
+                                    frame,
+                                    price_to_y,
+                                    x_position,
+                                    chart.cell_width,
+                                    chart.cell_height,
+                                    trades,
+                                    shading,
+                                    palette,
+                                );
+                            }
+
                             draw_clusters(
                                 frame,
                                 price_to_y,
@@ -961,28 +1704,122 @@ impl canvas::Program<Message> for KlineChart {
                                 *clusters,
                                 content_spacing,
                             );
+
+                            if self.visual_config.order_flow.enabled {
+                                draw_order_flow(
+                                    frame,
+                                    price_to_y,
+                                    x_position,
+                                    chart.cell_width,
+                                    chart.cell_height,
+                                    self.order_flow.signals_at(kline.time),
+                                    palette,
+                                );
+                            }
                         },
                     );
                 }
                 KlineChartKind::Candles => {
-                    let candle_width = chart.cell_width * 0.8;
-
-                    render_data_source(
-                        &self.data_source,
+                    let geometry = self.visual_config.candle_geometry;
+                    let candle_width = geometry.body(chart.cell_width);
+                    let wick_width = geometry.wick(chart.cell_width);
+                    let colors = self.candle_colors(palette);
+
+                    let candle_px = chart.cell_width * chart.scaling;
+                    let simplify_below = self
+                        .visual_config
+                        .simplify_below
+                        .at_least(data::performance::performance_profile().min_simplify_px());
+
+                    if let Some(group_size) = simplify_below.group_size(candle_px) {
+                        draw_candle_bands(
+                            &self.data_source,
+                            frame,
+                            earliest,
+                            latest,
+                            interval_to_x,
+                            price_to_y,
+                            chart.cell_width * group_size as f32,
+                            &colors,
+                        );
+                    } else {
+                        let volume_opacity = self.visual_config.volume_opacity;
+                        let market = chart.ticker_info.market_type();
+                        let size_in_quote_ccy = exchange::volume_size_unit() == SizeUnit::Quote;
+
+                        render_data_source(
+                            &self.data_source,
+                            frame,
+                            earliest,
+                            latest,
+                            interval_to_x,
+                            |frame, x_position, kline, _, prev| {
+                                let is_up = self
+                                    .visual_config
+                                    .coloring
+                                    .is_up(kline, prev.map(|k| k.close));
+
+                                let body_alpha = if volume_opacity.enabled {
+                                    volume_opacity.alpha(
+                                        candle_volume_value(kline, market, size_in_quote_ccy),
+                                        self.volume_baseline.mean_before(kline.time),
+                                    )
+                                } else {
+                                    1.0
+                                };
+
+                                draw_candle_dp(
+                                    frame,
+                                    price_to_y,
+                                    candle_width,
+                                    wick_width,
+                                    &colors,
+                                    self.visual_config.hollow_up,
+                                    x_position,
+                                    kline,
+                                    is_up,
+                                    body_alpha,
+                                );
+                            },
+                        );
+                    }
+                }
+            }
+
+            if self.visual_config.swings.enabled {
+                let show_structure = self.visual_config.swings.structure;
+
+                render_data_source(
+                    &self.data_source,
+                    frame,
+                    earliest,
+                    latest,
+                    interval_to_x,
+                    |frame, x_position, kline, _, _| {
+                        draw_swings(
+                            frame,
+                            chart,
+                            x_position,
+                            self.swings.swings_at(kline.time),
+                            show_structure,
+                            palette,
+                        );
+                    },
+                );
+            }
+
+            if let PlotData::TimeBased(timeseries) = &self.data_source {
+                let gaps = timeseries.kline_gaps(earliest, latest);
+                draw_data_gaps(frame, chart, &gaps, region, palette);
+
+                if self.visual_config.session_levels.enabled {
+                    draw_session_levels(
                         frame,
-                        earliest,
-                        latest,
-                        interval_to_x,
-                        |frame, x_position, kline, _| {
-                            draw_candle_dp(
-                                frame,
-                                price_to_y,
-                                candle_width,
-                                palette,
-                                x_position,
-                                kline,
-                            );
-                        },
+                        chart,
+                        timeseries,
+                        self.visual_config.session_levels,
+                        region,
+                        palette,
                     );
                 }
             }
@@ -991,21 +1828,65 @@ impl canvas::Program<Message> for KlineChart {
         });
 
         let crosshair = chart.cache.crosshair.draw(renderer, bounds_size, |frame| {
+            chart.draw_measured_moves(
+                frame,
+                theme,
+                bounds_size,
+                interaction,
+                cursor.position_in(bounds),
+            );
+
             if let Some(cursor_position) = cursor.position_in(bounds) {
                 let (_, rounded_aggregation) =
                     chart.draw_crosshair(frame, theme, bounds_size, cursor_position, interaction);
 
                 draw_crosshair_tooltip(
                     &self.data_source,
-                    &chart.ticker_info,
+                    chart,
                     frame,
                     palette,
                     rounded_aggregation,
                 );
+
+                if let PlotData::TimeBased(timeseries) = &self.data_source {
+                    let region = chart.visible_region(bounds_size);
+                    let (earliest, latest) = chart.interval_range(&region);
+
+                    draw_gap_tooltip(
+                        frame,
+                        palette,
+                        &timeseries.kline_gaps(earliest, latest),
+                        timeseries.interval.to_milliseconds(),
+                        rounded_aggregation,
+                    );
+                }
+
+                if self.visual_config.show_info_box {
+                    draw_bar_info_box(
+                        &self.data_source,
+                        chart.display_decimals(),
+                        frame,
+                        palette,
+                        rounded_aggregation,
+                        cursor_position,
+                    );
+                }
+            } else {
+                chart.draw_synced_crosshair(frame, bounds_size);
             }
         });
 
-        vec![klines, crosshair]
+        let mut geometries = vec![klines, crosshair];
+
+        if let KlineChartKind::Footprint { .. } = self.kind
+            && self.visual_config.delta_coloring.enabled
+        {
+            let mut frame = canvas::Frame::new(renderer, bounds_size);
+            draw_delta_legend(&mut frame, palette, self.visual_config.delta_coloring);
+            geometries.push(frame.into_geometry());
+        }
+
+        geometries
     }
 
     fn mouse_interaction(
@@ -1017,7 +1898,7 @@ impl canvas::Program<Message> for KlineChart {
         match interaction {
             Interaction::Panning { .. } => mouse::Interaction::Grabbing,
             Interaction::Zoomin { .. } => mouse::Interaction::ZoomIn,
-            Interaction::None | Interaction::Ruler { .. } => {
+            Interaction::None | Interaction::Ruler { .. } | Interaction::MeasuredMove { .. } => {
                 if cursor.is_over(bounds) {
                     mouse::Interaction::Crosshair
                 } else {
@@ -1073,40 +1954,281 @@ fn draw_footprint_kline(
     );
 }
 
+/// Draws the higher timeframe candles behind the primary ones, each spanning the primary
+/// candles it covers
+fn draw_higher_timeframe(
+    frame: &mut canvas::Frame,
+    series: &HigherTfSeries,
+    chart: &ViewState,
+    earliest: u64,
+    latest: u64,
+    palette: &Extended,
+) {
+    let Basis::Time(timeframe) = chart.basis else {
+        return;
+    };
+
+    let htf_ms = series.config.timeframe.to_milliseconds();
+    let last_cell_offset = htf_ms.saturating_sub(timeframe.to_milliseconds());
+    // half a cell towards the earlier side, so bands cover their first and last candles
+    let half_cell = chart.cell_width / 2.0 * chart.x_sign();
+
+    let line = |color: iced::Color| {
+        Stroke::with_color(
+            Stroke {
+                width: 1.0,
+                ..Default::default()
+            },
+            color,
+        )
+    };
+
+    let mut prev_close: Option<Point> = None;
+
+    for kline in series
+        .klines
+        .range(earliest.saturating_sub(htf_ms)..=latest)
+        .map(|(_, kline)| kline)
+    {
+        let start_x = chart.interval_to_x(kline.time) - half_cell;
+        let end_x = chart.interval_to_x(kline.time + last_cell_offset) + half_cell;
+
+        let color = if kline.close >= kline.open {
+            palette.success.base.color
+        } else {
+            palette.danger.base.color
+        };
+
+        match series.config.style {
+            HigherTimeframeStyle::Bands => {
+                let y_high = chart.price_to_y(kline.high);
+                let y_low = chart.price_to_y(kline.low);
+                let band = Rectangle {
+                    x: start_x.min(end_x),
+                    y: y_high.min(y_low),
+                    width: (end_x - start_x).abs(),
+                    height: (y_low - y_high).abs(),
+                };
+
+                frame.fill_rectangle(band.position(), band.size(), color.scale_alpha(0.08));
+                frame.stroke(
+                    &Path::rectangle(band.position(), band.size()),
+                    line(color.scale_alpha(0.4)),
+                );
+            }
+            HigherTimeframeStyle::CloseLine => {
+                let y_close = chart.price_to_y(kline.close);
+
+                if let Some(prev) = prev_close {
+                    frame.stroke(
+                        &Path::line(prev, Point::new(start_x, y_close)),
+                        line(palette.secondary.strong.color.scale_alpha(0.6)),
+                    );
+                }
+                frame.stroke(
+                    &Path::line(Point::new(start_x, y_close), Point::new(end_x, y_close)),
+                    line(palette.secondary.strong.color),
+                );
+
+                prev_close = Some(Point::new(end_x, y_close));
+            }
+        }
+    }
+}
+
+/// Open, high and low of the user's local trading day as lines from the session start to the
+/// latest edge, labeled with their prices; the previous session's levels are dashed and fainter
+fn draw_session_levels(
+    frame: &mut canvas::Frame,
+    chart: &ViewState,
+    timeseries: &TimeSeries<KlineDataPoint>,
+    config: SessionLevelsConfig,
+    region: Rectangle,
+    palette: &Extended,
+) {
+    let Basis::Time(timeframe) = chart.basis else {
+        return;
+    };
+    // a daily or longer candle is a session of its own
+    if timeframe.to_milliseconds() >= 86_400_000 || timeseries.datapoints.is_empty() {
+        return;
+    }
+
+    let timezone = data::config::timezone::display_timezone();
+    let Some(sessions) = session::session_bounds(timezone, timeseries.timerange().1) else {
+        return;
+    };
+
+    let half_cell = chart.cell_width / 2.0 * chart.x_sign();
+    let end_x = chart.latest_edge_x(&region);
+    let text_size = 10.0 / chart.scaling;
+
+    for (is_previous, (start, end)) in [(false, sessions[0]), (true, sessions[1])] {
+        if is_previous && !config.previous {
+            continue;
+        }
+
+        let Some(levels) = SessionLevels::from_klines(
+            start,
+            end,
+            timeseries
+                .datapoints
+                .range(start..end)
+                .map(|(_, dp)| &dp.kline),
+        ) else {
+            continue;
+        };
+
+        let start_x = chart.interval_to_x(levels.start) - half_cell;
+        let (alpha, line_dash) = if is_previous {
+            (
+                0.45,
+                canvas::LineDash {
+                    segments: &[4.0, 3.0],
+                    offset: 0,
+                },
+            )
+        } else {
+            (0.8, canvas::LineDash::default())
+        };
+
+        let lines = [
+            (
+                config.open,
+                levels.open,
+                ["Open", "Prev open"],
+                palette.secondary.strong.color,
+            ),
+            (
+                config.high,
+                levels.high,
+                ["High", "Prev high"],
+                palette.success.base.color,
+            ),
+            (
+                config.low,
+                levels.low,
+                ["Low", "Prev low"],
+                palette.danger.base.color,
+            ),
+        ];
+
+        for (_, price, names, color) in lines.into_iter().filter(|(shown, ..)| *shown) {
+            let color = color.scale_alpha(alpha);
+            let y = chart.price_to_y(price);
+
+            frame.stroke(
+                &Path::line(Point::new(start_x, y), Point::new(end_x, y)),
+                Stroke::with_color(
+                    Stroke {
+                        width: 1.0,
+                        line_dash,
+                        ..Default::default()
+                    },
+                    color,
+                ),
+            );
+
+            let name = names[usize::from(is_previous)];
+            let content = format!("{name} {}", chart.format_price(price));
+            let text_width = content.len() as f32 * text_size * 0.65;
+            let label_x = if end_x >= start_x {
+                end_x - text_width - 4.0 / chart.scaling
+            } else {
+                end_x + 4.0 / chart.scaling
+            };
+
+            frame.fill_text(canvas::Text {
+                content,
+                position: Point::new(label_x, y - 2.0 / chart.scaling),
+                size: iced::Pixels(text_size),
+                color,
+                font: style::AZERET_MONO,
+                align_y: iced::alignment::Vertical::Bottom,
+                ..canvas::Text::default()
+            });
+        }
+    }
+}
+
+/// Buy plus sell volume of a candle valued in the display size unit, so bars stay comparable
+/// when the price moves a lot within the averaging window
+fn candle_volume_value(kline: &Kline, market: MarketKind, size_in_quote_ccy: bool) -> f32 {
+    market.qty_in_quote_value(
+        kline.volume.0 + kline.volume.1,
+        kline.close,
+        size_in_quote_ccy,
+    )
+}
+
 fn draw_candle_dp(
     frame: &mut canvas::Frame,
     price_to_y: impl Fn(Price) -> f32,
     candle_width: f32,
-    palette: &Extended,
+    wick_width: f32,
+    colors: &CandleColors,
+    hollow_up: bool,
     x_position: f32,
     kline: &Kline,
+    is_up: bool,
+    body_alpha: f32,
 ) {
     let y_open = price_to_y(kline.open);
     let y_high = price_to_y(kline.high);
     let y_low = price_to_y(kline.low);
     let y_close = price_to_y(kline.close);
 
-    let body_color = if kline.close >= kline.open {
-        palette.success.base.color
+    let (body_color, wick_color) = if is_up {
+        (colors.up_body, colors.up_wick)
     } else {
-        palette.danger.base.color
+        (colors.down_body, colors.down_wick)
     };
-    frame.fill_rectangle(
-        Point::new(x_position - (candle_width / 2.0), y_open.min(y_close)),
-        Size::new(candle_width, (y_open - y_close).abs()),
-        body_color,
-    );
+    let body_color = body_color.scale_alpha(body_alpha);
+
+    let body_top = y_open.min(y_close);
+    let body_height = (y_open - y_close).abs();
+    // the high sits below the low when the price axis is inverted
+    let (wick_top, wick_bottom) = (y_high.min(y_low), y_high.max(y_low));
+    let wick_x = x_position - (wick_width / 2.0);
+
+    // hollow candles come from close >= open, regardless of the coloring basis
+    if hollow_up && kline.close >= kline.open {
+        frame.fill_rectangle(
+            Point::new(wick_x, wick_top),
+            Size::new(wick_width, (body_top - wick_top).max(0.0)),
+            wick_color,
+        );
+        frame.fill_rectangle(
+            Point::new(wick_x, body_top + body_height),
+            Size::new(wick_width, (wick_bottom - body_top - body_height).max(0.0)),
+            wick_color,
+        );
+        frame.stroke(
+            &Path::rectangle(
+                Point::new(x_position - (candle_width / 2.0), body_top),
+                Size::new(candle_width, body_height),
+            ),
+            Stroke::with_color(
+                Stroke {
+                    width: 1.0,
+                    ..Default::default()
+                },
+                body_color,
+            ),
+        );
+        return;
+    }
 
-    let wick_color = if kline.close >= kline.open {
-        palette.success.base.color
-    } else {
-        palette.danger.base.color
-    };
     frame.fill_rectangle(
-        Point::new(x_position - (candle_width / 8.0), y_high),
-        Size::new(candle_width / 4.0, (y_high - y_low).abs()),
+        Point::new(wick_x, wick_top),
+        Size::new(wick_width, wick_bottom - wick_top),
         wick_color,
     );
+    frame.fill_rectangle(
+        Point::new(x_position - (candle_width / 2.0), body_top),
+        Size::new(candle_width, body_height),
+        body_color,
+    );
 }
 
 fn render_data_source<F>(
@@ -1115,25 +2237,34 @@ fn render_data_source<F>(
     earliest: u64,
     latest: u64,
     interval_to_x: impl Fn(u64) -> f32,
-    draw_fn: F,
+    mut draw_fn: F,
 ) where
-    F: Fn(&mut canvas::Frame, f32, &Kline, &KlineTrades),
+    F: FnMut(&mut canvas::Frame, f32, &Kline, &KlineTrades, Option<&Kline>),
 {
     match data_source {
         PlotData::TickBased(tick_aggr) => {
             let earliest = earliest as usize;
             let latest = latest as usize;
+            let datapoints = &tick_aggr.datapoints;
 
-            tick_aggr
-                .datapoints
+            datapoints
                 .iter()
                 .rev()
                 .enumerate()
                 .filter(|(index, _)| *index <= latest && *index >= earliest)
                 .for_each(|(index, tick_aggr)| {
                     let x_position = interval_to_x(index as u64);
+                    let prev = (datapoints.len() - 1 - index)
+                        .checked_sub(1)
+                        .map(|i| &datapoints[i].kline);
 
-                    draw_fn(frame, x_position, &tick_aggr.kline, &tick_aggr.footprint);
+                    draw_fn(
+                        frame,
+                        x_position,
+                        &tick_aggr.kline,
+                        &tick_aggr.footprint,
+                        prev,
+                    );
                 });
         }
         PlotData::TimeBased(timeseries) => {
@@ -1141,18 +2272,112 @@ fn render_data_source<F>(
                 return;
             }
 
-            timeseries
+            let mut prev = timeseries
                 .datapoints
-                .range(earliest..=latest)
-                .for_each(|(timestamp, dp)| {
-                    let x_position = interval_to_x(*timestamp);
+                .range(..earliest)
+                .next_back()
+                .map(|(_, dp)| &dp.kline);
 
-                    draw_fn(frame, x_position, &dp.kline, &dp.footprint);
-                });
+            for (timestamp, dp) in timeseries.datapoints.range(earliest..=latest) {
+                let x_position = interval_to_x(*timestamp);
+
+                draw_fn(frame, x_position, &dp.kline, &dp.footprint, prev);
+                prev = Some(&dp.kline);
+            }
         }
     }
 }
 
+/// High/low range of neighbouring candles merged into one band when zoomed out
+struct CandleBand {
+    key: i64,
+    open: (u64, Price),
+    close: (u64, Price),
+    high: Price,
+    low: Price,
+}
+
+impl CandleBand {
+    fn new(key: i64, kline: &Kline) -> Self {
+        Self {
+            key,
+            open: (kline.time, kline.open),
+            close: (kline.time, kline.close),
+            high: kline.high,
+            low: kline.low,
+        }
+    }
+
+    /// Candles arrive newest first on tick based charts, so open/close follow the kline times
+    fn add(&mut self, kline: &Kline) {
+        if kline.time < self.open.0 {
+            self.open = (kline.time, kline.open);
+        }
+        if kline.time >= self.close.0 {
+            self.close = (kline.time, kline.close);
+        }
+        self.high = self.high.max(kline.high);
+        self.low = self.low.min(kline.low);
+    }
+}
+
+/// Level-of-detail rendering for candles narrower than the configured threshold: candles that
+/// fall into the same `band_width` slice of the x axis are drawn as a single high/low bar,
+/// colored by the slice's net direction, instead of a body and wick each
+fn draw_candle_bands(
+    data_source: &PlotData<KlineDataPoint>,
+    frame: &mut canvas::Frame,
+    earliest: u64,
+    latest: u64,
+    interval_to_x: impl Fn(u64) -> f32,
+    price_to_y: impl Fn(Price) -> f32,
+    band_width: f32,
+    colors: &CandleColors,
+) {
+    let draw_band = |frame: &mut canvas::Frame, band: &CandleBand| {
+        let color = if band.close.1 >= band.open.1 {
+            colors.up_body
+        } else {
+            colors.down_body
+        };
+
+        let (y_high, y_low) = (price_to_y(band.high), price_to_y(band.low));
+        let x = band.key as f32 * band_width;
+
+        frame.fill_rectangle(
+            Point::new(x + band_width * 0.1, y_high.min(y_low)),
+            Size::new(band_width * 0.8, (y_high - y_low).abs()),
+            color,
+        );
+    };
+
+    let mut band: Option<CandleBand> = None;
+
+    render_data_source(
+        data_source,
+        frame,
+        earliest,
+        latest,
+        interval_to_x,
+        |frame, x_position, kline, _, _| {
+            let key = (x_position / band_width).floor() as i64;
+
+            match band.as_mut() {
+                Some(current) if current.key == key => current.add(kline),
+                _ => {
+                    if let Some(done) = band.replace(CandleBand::new(key, kline)) {
+                        draw_band(frame, &done);
+                    }
+                }
+            }
+        },
+    );
+
+    if let Some(last) = band {
+        draw_band(frame, &last);
+    }
+}
+
 fn draw_all_npocs(
     data_source: &PlotData<KlineDataPoint>,
     frame: &mut canvas::Frame,
@@ -1168,6 +2393,7 @@ fn draw_all_npocs(
     cluster_kind: ClusterKind,
     spacing: ContentGaps,
     imb_study_on: bool,
+    x_sign: f32,
 ) {
     let Some(lookback) = studies.iter().find_map(|study| {
         if let FootprintStudy::NPoC { lookback } = study {
@@ -1238,10 +2464,15 @@ fn draw_all_npocs(
         }
     };
 
-    let rightmost_cell_center_x = {
+    // with right-to-left time, lines run leftwards from the mirrored side of the candle
+    let mirrored = |cell_center_x: f32, x: f32| cell_center_x + (x - cell_center_x) * x_sign;
+    let start_x_for = |cell_center_x: f32| mirrored(cell_center_x, start_x_for(cell_center_x));
+    let end_x_for = |cell_center_x: f32| mirrored(cell_center_x, end_x_for(cell_center_x));
+
+    let newest_cell_center_x = {
         let earliest_x = interval_to_x(visible_earliest);
         let latest_x = interval_to_x(visible_latest);
-        if earliest_x > latest_x {
+        if (earliest_x - latest_x) * x_sign > 0.0 {
             earliest_x
         } else {
             latest_x
@@ -1253,7 +2484,7 @@ fn draw_all_npocs(
 
         let (line_width, color) = match poc.status {
             NPoc::Naked => {
-                let end_x = end_x_for(rightmost_cell_center_x);
+                let end_x = end_x_for(newest_cell_center_x);
                 let line_width = end_x - start_x;
                 if line_width.abs() <= cell_width {
                     return;
@@ -1339,6 +2570,212 @@ fn effective_cluster_qty(
     }
 }
 
+/// Delta shading settings resolved for the current ticker and size unit
+#[derive(Debug, Clone, Copy)]
+struct DeltaShading {
+    coloring: DeltaColoring,
+    market_type: MarketKind,
+    size_in_quote_ccy: bool,
+}
+
+/// Shades each price level of a footprint cell by its buy/sell delta, stronger for levels
+/// closer to the largest delta of the candle
+fn draw_delta_shading(
+    frame: &mut canvas::Frame,
+    price_to_y: impl Fn(Price) -> f32,
+    x_position: f32,
+    cell_width: f32,
+    cell_height: f32,
+    footprint: &KlineTrades,
+    shading: DeltaShading,
+    palette: &Extended,
+) {
+    let delta_value = |price: Price, delta: f32| {
+        shading
+            .market_type
+            .qty_in_quote_value(delta.abs(), price, shading.size_in_quote_ccy)
+    };
+
+    let max_delta = footprint
+        .trades
+        .iter()
+        .map(|(price, group)| delta_value(*price, group.delta_qty()))
+        .fold(0.0_f32, f32::max);
+
+    if max_delta <= f32::EPSILON {
+        return;
+    }
+
+    for (price, group) in &footprint.trades {
+        if !shading
+            .coloring
+            .exceeds_ratio(group.buy_qty, group.sell_qty)
+        {
+            continue;
+        }
+
+        let delta = group.delta_qty();
+        let strength = (delta_value(*price, delta) / max_delta).clamp(0.0, 1.0);
+
+        let color = if delta > 0.0 {
+            palette.success.base.color
+        } else {
+            palette.danger.base.color
+        };
+
+        frame.fill_rectangle(
+            Point::new(
+                x_position - (cell_width / 2.0),
+                price_to_y(*price) - (cell_height / 2.0),
+            ),
+            Size::new(cell_width, cell_height),
+            color.scale_alpha(0.1 + 0.5 * strength),
+        );
+    }
+}
+
+/// Outlines absorption levels and marks where an aggressive run through the levels ended
+fn draw_order_flow(
+    frame: &mut canvas::Frame,
+    price_to_y: impl Fn(Price) -> f32,
+    x_position: f32,
+    cell_width: f32,
+    cell_height: f32,
+    signals: &[OrderFlowSignal],
+    palette: &Extended,
+) {
+    for signal in signals {
+        let y = price_to_y(signal.price);
+        let side_color = if signal.is_buy {
+            palette.success.strong.color
+        } else {
+            palette.danger.strong.color
+        };
+
+        match signal.kind {
+            SignalKind::Absorption => {
+                frame.stroke(
+                    &Path::rectangle(
+                        Point::new(x_position - (cell_width / 2.0), y - (cell_height / 2.0)),
+                        Size::new(cell_width, cell_height),
+                    ),
+                    Stroke::with_color(
+                        Stroke {
+                            width: 2.0,
+                            ..Stroke::default()
+                        },
+                        palette.primary.strong.color,
+                    ),
+                );
+            }
+            SignalKind::Aggression => {
+                let radius = (cell_height / 2.0).clamp(2.0, 5.0);
+                frame.fill(
+                    &Path::circle(Point::new(x_position - (cell_width / 2.0), y), radius),
+                    side_color,
+                );
+            }
+        }
+    }
+}
+
+/// Small triangles above swing highs and below swing lows, with the structure label beyond them
+fn draw_swings(
+    frame: &mut canvas::Frame,
+    chart: &ViewState,
+    x_position: f32,
+    swings: &[Swing],
+    show_structure: bool,
+    palette: &Extended,
+) {
+    let size = 4.0 / chart.scaling;
+    let gap = 4.0 / chart.scaling;
+
+    for swing in swings {
+        let y = chart.price_to_y(swing.price);
+        // away from the candle: up for highs and down for lows, flipped on an inverted axis
+        let side = if swing.is_high { -1.0 } else { 1.0 };
+        let dir = side * chart.y_sign();
+        let color = if swing.is_high {
+            palette.danger.strong.color
+        } else {
+            palette.success.strong.color
+        };
+
+        let base_y = y + dir * gap;
+        let tip_y = base_y + dir * size * 1.5;
+        let marker = Path::new(|builder| {
+            builder.move_to(Point::new(x_position, base_y));
+            builder.line_to(Point::new(x_position - size, tip_y));
+            builder.line_to(Point::new(x_position + size, tip_y));
+            builder.close();
+        });
+        frame.fill(&marker, color);
+
+        if show_structure && let Some(structure) = swing.structure {
+            frame.fill_text(canvas::Text {
+                content: structure.to_string(),
+                position: Point::new(x_position, tip_y + dir * 2.0 / chart.scaling),
+                size: iced::Pixels(10.0 / chart.scaling),
+                color,
+                font: style::AZERET_MONO,
+                align_x: iced::widget::text::Alignment::Center,
+                align_y: if dir < 0.0 {
+                    iced::alignment::Vertical::Bottom
+                } else {
+                    iced::alignment::Vertical::Top
+                },
+                ..canvas::Text::default()
+            });
+        }
+    }
+}
+
+/// Color scale of the delta shading, kept in the top left corner of the chart
+fn draw_delta_legend(frame: &mut canvas::Frame, palette: &Extended, coloring: DeltaColoring) {
+    const STEPS: usize = 5;
+    const SWATCH: f32 = 8.0;
+
+    let text_color = palette.background.base.text;
+    let origin = Point::new(8.0, 8.0);
+
+    frame.fill_text(canvas::Text {
+        content: "Sell Δ".to_string(),
+        position: origin,
+        size: iced::Pixels(10.0),
+        color: text_color,
+        font: style::AZERET_MONO,
+        ..canvas::Text::default()
+    });
+
+    let swatches_left = origin.x + 40.0;
+    for i in 0..STEPS * 2 {
+        let (color, strength) = if i < STEPS {
+            (palette.danger.base.color, (STEPS - i) as f32 / STEPS as f32)
+        } else {
+            (
+                palette.success.base.color,
+                (i - STEPS + 1) as f32 / STEPS as f32,
+            )
+        };
+
+        frame.fill_rectangle(
+            Point::new(swatches_left + i as f32 * SWATCH, origin.y + 2.0),
+            Size::new(SWATCH, SWATCH),
+            color.scale_alpha(0.1 + 0.5 * strength),
+        );
+    }
+
+    frame.fill_text(canvas::Text {
+        content: format!("Buy Δ  ≥{:.1}×", coloring.min_ratio),
+        position: Point::new(swatches_left + (STEPS * 2) as f32 * SWATCH + 6.0, origin.y),
+        size: iced::Pixels(10.0),
+        color: text_color,
+        font: style::AZERET_MONO,
+        ..canvas::Text::default()
+    });
+}
+
 fn draw_clusters(
     frame: &mut canvas::Frame,
     price_to_y: impl Fn(Price) -> f32,
@@ -1705,41 +3142,159 @@ fn draw_cluster_text(
     });
 }
 
-fn draw_crosshair_tooltip(
-    data: &PlotData<KlineDataPoint>,
-    ticker_info: &TickerInfo,
-    frame: &mut canvas::Frame,
-    palette: &Extended,
-    at_interval: u64,
-) {
-    let kline_opt = match data {
-        PlotData::TimeBased(timeseries) => timeseries
-            .datapoints
-            .iter()
-            .find(|(time, _)| **time == at_interval)
-            .map(|(_, dp)| &dp.kline)
-            .or_else(|| {
-                if timeseries.datapoints.is_empty() {
-                    None
-                } else {
+/// Kline under the given interval along with the one before it, if any.
+///
+/// Past the latest time-based bar the last kline is returned, matching the forming candle.
+fn kline_at(data: &PlotData<KlineDataPoint>, at_interval: u64) -> Option<(&Kline, Option<&Kline>)> {
+    match data {
+        PlotData::TimeBased(timeseries) => {
+            let (time, dp) = match timeseries.datapoints.get_key_value(&at_interval) {
+                Some(entry) => entry,
+                None => {
                     let (last_time, dp) = timeseries.datapoints.last_key_value()?;
-                    if at_interval > *last_time {
-                        Some(&dp.kline)
-                    } else {
-                        None
+                    if at_interval <= *last_time {
+                        return None;
                     }
+                    (last_time, dp)
                 }
-            }),
+            };
+
+            let prev = timeseries
+                .datapoints
+                .range(..*time)
+                .next_back()
+                .map(|(_, dp)| &dp.kline);
+
+            Some((&dp.kline, prev))
+        }
         PlotData::TickBased(tick_aggr) => {
             let index = (at_interval / u64::from(tick_aggr.interval.0)) as usize;
-            if index < tick_aggr.datapoints.len() {
-                Some(&tick_aggr.datapoints[tick_aggr.datapoints.len() - 1 - index].kline)
-            } else {
-                None
+            let len = tick_aggr.datapoints.len();
+            if index >= len {
+                return None;
             }
+
+            let position = len - 1 - index;
+            let prev = position
+                .checked_sub(1)
+                .map(|i| &tick_aggr.datapoints[i].kline);
+
+            Some((&tick_aggr.datapoints[position].kline, prev))
         }
+    }
+}
+
+/// Compact box next to the cursor with OHLC, volume split and change from the previous close
+fn draw_bar_info_box(
+    data: &PlotData<KlineDataPoint>,
+    decimals: usize,
+    frame: &mut canvas::Frame,
+    palette: &Extended,
+    at_interval: u64,
+    cursor_position: Point,
+) {
+    let Some((kline, prev)) = kline_at(data, at_interval) else {
+        return;
     };
 
+    let close = kline.close.to_f32();
+    let reference = prev.map_or(kline.open, |k| k.close).to_f32();
+    let change_pct = if reference > 0.0 {
+        (close - reference) / reference * 100.0
+    } else {
+        0.0
+    };
+
+    let change_color = if change_pct >= 0.0 {
+        palette.success.base.color
+    } else {
+        palette.danger.base.color
+    };
+    let base_color = palette.background.base.text;
+
+    let (buy_volume, sell_volume) = kline.volume;
+    let fmt_price = |price: Price| format_price(price.to_f32(), decimals);
+
+    let lines = [
+        (format!("O {}", fmt_price(kline.open)), base_color),
+        (format!("H {}", fmt_price(kline.high)), base_color),
+        (format!("L {}", fmt_price(kline.low)), base_color),
+        (format!("C {}", fmt_price(kline.close)), base_color),
+        (
+            format!("V {}", format_volume(buy_volume + sell_volume)),
+            base_color,
+        ),
+        (
+            format!("B {}", format_volume(buy_volume)),
+            palette.success.base.color,
+        ),
+        (
+            format!("S {}", format_volume(sell_volume)),
+            palette.danger.base.color,
+        ),
+        (format!("Δ {change_pct:+.2}%"), change_color),
+    ];
+
+    let line_height = TEXT_SIZE + 3.0;
+    let rect_padding = 4.0;
+    let offset = 12.0;
+
+    let max_chars = lines
+        .iter()
+        .map(|(line, _)| line.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let box_size = Size::new(
+        max_chars as f32 * TEXT_SIZE * 0.6 + rect_padding * 2.0,
+        lines.len() as f32 * line_height + rect_padding * 2.0,
+    );
+
+    let bounds = frame.size();
+
+    // keep the box on the side of the cursor with room for it
+    let x = if cursor_position.x + offset + box_size.width > bounds.width {
+        cursor_position.x - offset - box_size.width
+    } else {
+        cursor_position.x + offset
+    };
+    let y = if cursor_position.y + offset + box_size.height > bounds.height {
+        cursor_position.y - offset - box_size.height
+    } else {
+        cursor_position.y + offset
+    };
+    let origin = Point::new(x.max(0.0), y.max(0.0));
+
+    frame.fill_rectangle(
+        origin,
+        box_size,
+        palette.background.weakest.color.scale_alpha(0.9),
+    );
+
+    for (i, (content, color)) in lines.into_iter().enumerate() {
+        frame.fill_text(canvas::Text {
+            content,
+            position: Point::new(
+                origin.x + rect_padding,
+                origin.y + rect_padding + i as f32 * line_height,
+            ),
+            size: iced::Pixels(TEXT_SIZE),
+            color,
+            font: style::AZERET_MONO,
+            ..canvas::Text::default()
+        });
+    }
+}
+
+fn draw_crosshair_tooltip(
+    data: &PlotData<KlineDataPoint>,
+    chart: &ViewState,
+    frame: &mut canvas::Frame,
+    palette: &Extended,
+    at_interval: u64,
+) {
+    let kline_opt = kline_at(data, at_interval).map(|(kline, _)| kline);
+
     if let Some(kline) = kline_opt {
         let change_pct = ((kline.close - kline.open).to_f32() / kline.open.to_f32()) * 100.0;
         let change_color = if change_pct >= 0.0 {
@@ -1749,17 +3304,16 @@ fn draw_crosshair_tooltip(
         };
 
         let base_color = palette.background.base.text;
-        let precision = ticker_info.min_ticksize;
 
         let segments = [
             ("O", base_color, false),
-            (&kline.open.to_string(precision), change_color, true),
+            (&chart.format_price(kline.open), change_color, true),
             ("H", base_color, false),
-            (&kline.high.to_string(precision), change_color, true),
+            (&chart.format_price(kline.high), change_color, true),
             ("L", base_color, false),
-            (&kline.low.to_string(precision), change_color, true),
+            (&chart.format_price(kline.low), change_color, true),
             ("C", base_color, false),
-            (&kline.close.to_string(precision), change_color, true),
+            (&chart.format_price(kline.close), change_color, true),
             (&format!("{change_pct:+.2}%"), change_color, true),
         ];
 
@@ -1799,6 +3353,100 @@ fn draw_crosshair_tooltip(
     }
 }
 
+/// ناحیه کمرنگ با لبه‌های خط‌چین روی بازه‌هایی که کندل آن‌ها از صرافی دریافت نشده است
+fn draw_data_gaps(
+    frame: &mut canvas::Frame,
+    chart: &ViewState,
+    gaps: &[(u64, u64)],
+    region: Rectangle,
+    palette: &Extended,
+) {
+    let half_cell = chart.cell_width / 2.0;
+    let color = palette.background.strong.color;
+
+    let edge = Stroke::with_color(
+        Stroke {
+            width: 1.0,
+            line_dash: canvas::LineDash {
+                segments: &[4.0, 4.0],
+                offset: 0,
+            },
+            ..Default::default()
+        },
+        color.scale_alpha(0.6),
+    );
+
+    for &(first_missing, last_missing) in gaps {
+        let (x1, x2) = (
+            chart.interval_to_x(first_missing),
+            chart.interval_to_x(last_missing),
+        );
+        let (left, right) = (x1.min(x2) - half_cell, x1.max(x2) + half_cell);
+
+        frame.fill_rectangle(
+            Point::new(left, region.y),
+            Size::new(right - left, region.height),
+            color.scale_alpha(0.15),
+        );
+
+        for x in [left, right] {
+            frame.stroke(
+                &Path::line(
+                    Point::new(x, region.y),
+                    Point::new(x, region.y + region.height),
+                ),
+                edge,
+            );
+        }
+    }
+}
+
+/// وقتی نشانگر روی یک شکاف داده قرار دارد، تعداد کندل‌های مفقود و شکاف‌های قابل مشاهده نمایش داده می‌شود
+fn draw_gap_tooltip(
+    frame: &mut canvas::Frame,
+    palette: &Extended,
+    gaps: &[(u64, u64)],
+    interval_ms: u64,
+    at_interval: u64,
+) {
+    let Some(&(first_missing, last_missing)) = gaps
+        .iter()
+        .find(|(first, last)| (*first..=*last).contains(&at_interval))
+    else {
+        return;
+    };
+
+    let missing = (last_missing - first_missing) / interval_ms.max(1) + 1;
+    let content = format!(
+        "No data: {missing} candle{} missing · {} gap{} in view",
+        if missing == 1 { "" } else { "s" },
+        gaps.len(),
+        if gaps.len() == 1 { "" } else { "s" },
+    );
+
+    let position = Point::new(8.0, 8.0);
+    let tooltip_rect = Rectangle {
+        x: position.x,
+        y: position.y,
+        width: content.chars().count() as f32 * (TEXT_SIZE * 0.65),
+        height: 16.0,
+    };
+
+    frame.fill_rectangle(
+        tooltip_rect.position(),
+        tooltip_rect.size(),
+        palette.background.weakest.color.scale_alpha(0.9),
+    );
+    frame.fill_text(canvas::Text {
+        content,
+        position,
+        size: iced::Pixels(TEXT_SIZE),
+        color: palette.background.base.text,
+        font: style::AZERET_MONO,
+        ..canvas::Text::default()
+    });
+}
+
 struct ProfileArea {
     imb_marker_left: f32,
     imb_marker_width: f32,
@@ -1,17 +1,72 @@
 pub mod linear;
+pub mod logarithmic;
 pub mod timeseries;
 
-use crate::{chart::TEXT_SIZE, style::AZERET_MONO};
+use crate::style::AZERET_MONO;
 
 use super::{Basis, Interaction, Message};
-use data::{chart::Autoscale, util::round_to_tick};
+use data::{
+    chart::{Autoscale, PriceScale},
+    util::round_to_tick,
+};
 use iced::{
     Alignment, Color, Event, Point, Rectangle, Renderer, Size, Theme, mouse,
     theme::palette::Extended,
     widget::canvas::{self, Cache, Frame, Geometry},
 };
 
-const REGULAR_LABEL_WIDTH: f32 = TEXT_SIZE * 6.0;
+/// Axis and crosshair label size, from the global label font setting
+pub fn label_text_size() -> f32 {
+    data::chart::label_font().size
+}
+
+/// Axis and crosshair label font, monospace unless turned off in the settings
+pub fn label_font() -> iced::Font {
+    if data::chart::label_font().monospace {
+        AZERET_MONO
+    } else {
+        iced::Font::DEFAULT
+    }
+}
+
+fn regular_label_width() -> f32 {
+    label_text_size() * 6.0
+}
+
+/// Time left until the current candle closes, counted from the latest candle's open
+/// so non-epoch-aligned candles (e.g. weekly) close at the right time.
+/// Sub-second intervals are shown with a decimal, e.g. "0.3s"
+fn candle_countdown(interval: u64, latest_x: u64, now: u64) -> Option<String> {
+    if interval == 0 {
+        return None;
+    }
+
+    let candle_close = if latest_x > 0 && latest_x <= now {
+        latest_x + ((now - latest_x) / interval + 1) * interval
+    } else {
+        (now / interval + 1) * interval
+    };
+    let remaining = (candle_close - now).min(interval);
+
+    if interval < 1_000 {
+        return Some(format!("{:.1}s", remaining as f32 / 1_000.0));
+    }
+
+    let remaining_seconds = remaining / 1_000;
+    if remaining_seconds == 0 {
+        return None;
+    }
+
+    let hours = remaining_seconds / 3600;
+    let minutes = (remaining_seconds % 3600) / 60;
+    let seconds = remaining_seconds % 60;
+
+    Some(if hours > 0 {
+        format!("{hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes:02}:{seconds:02}")
+    })
+}
 
 /// calculates `Rectangle` from given content, clamps it within bounds if needed
 pub fn calc_label_rect(
@@ -70,7 +125,8 @@ impl AxisLabel {
         is_crosshair: bool,
         palette: &Extended,
     ) -> Self {
-        let content_width = text_content.len() as f32 * (TEXT_SIZE / 2.6);
+        let text_size = label_text_size();
+        let content_width = text_content.len() as f32 * (text_size / 2.6);
 
         let rect = Rectangle {
             x: center_x_position - content_width,
@@ -91,7 +147,7 @@ impl AxisLabel {
             } else {
                 palette.background.base.text
             },
-            text_size: TEXT_SIZE,
+            text_size,
         };
 
         AxisLabel::X {
@@ -100,6 +156,14 @@ impl AxisLabel {
         }
     }
 
+    /// Mirrors the label across the axis, for charts drawn right-to-left or with an inverted price axis
+    fn mirror(&mut self, axis_size: Size) {
+        match self {
+            AxisLabel::X { bounds, .. } => bounds.x = axis_size.width - bounds.x - bounds.width,
+            AxisLabel::Y { bounds, .. } => bounds.y = axis_size.height - bounds.y - bounds.height,
+        }
+    }
+
     fn intersects(&self, other: &AxisLabel) -> bool {
         match (self, other) {
             (
@@ -157,7 +221,7 @@ impl AxisLabel {
                     color: label.text_color,
                     align_y: Alignment::Center.into(),
                     align_x: Alignment::Center.into(),
-                    font: AZERET_MONO,
+                    font: label_font(),
                     ..canvas::Text::default()
                 };
 
@@ -177,12 +241,13 @@ impl AxisLabel {
                 }
 
                 if let Some(timer_label) = timer_label {
+                    let timer_y = bounds.y + value_label.text_size + 3.0;
                     let value_label = canvas::Text {
                         content: value_label.content.clone(),
                         position: Point::new(bounds.x + 4.0, bounds.y + 2.0),
                         color: value_label.text_color,
                         size: value_label.text_size.into(),
-                        font: AZERET_MONO,
+                        font: label_font(),
                         ..canvas::Text::default()
                     };
 
@@ -190,10 +255,10 @@ impl AxisLabel {
 
                     let timer_label = canvas::Text {
                         content: timer_label.content.clone(),
-                        position: Point::new(bounds.x + 4.0, bounds.y + 15.0),
+                        position: Point::new(bounds.x + 4.0, timer_y),
                         color: timer_label.text_color,
                         size: timer_label.text_size.into(),
-                        font: AZERET_MONO,
+                        font: label_font(),
                         ..canvas::Text::default()
                     };
 
@@ -204,7 +269,7 @@ impl AxisLabel {
                         position: Point::new(bounds.x + 4.0, bounds.y + 4.0),
                         color: value_label.text_color,
                         size: value_label.text_size.into(),
-                        font: AZERET_MONO,
+                        font: label_font(),
                         ..canvas::Text::default()
                     };
 
@@ -227,9 +292,14 @@ pub struct AxisLabelsX<'a> {
     pub chart_bounds: Rectangle,
     pub interval_keys: Option<Vec<u64>>,
     pub autoscaling: Option<Autoscale>,
+    pub reverse_time: bool,
 }
 
 impl AxisLabelsX<'_> {
+    fn x_sign(&self) -> f32 {
+        if self.reverse_time { -1.0 } else { 1.0 }
+    }
+
     fn calc_crosshair_pos(&self, cursor_pos: Point, region: Rectangle) -> (f32, f32, i32) {
         let crosshair_ratio = f64::from(cursor_pos.x) / f64::from(self.chart_bounds.width);
         let chart_x_min = region.x;
@@ -266,7 +336,7 @@ impl AxisLabelsX<'_> {
                 }
 
                 let last_index = interval_keys.len() - 1;
-                let offset = i64::from(-cell_index) as usize;
+                let offset = (i64::from(-cell_index) * self.x_sign() as i64) as usize;
                 if offset > last_index {
                     return None;
                 }
@@ -342,6 +412,8 @@ impl AxisLabelsX<'_> {
     }
 
     fn x_to_interval(&self, x: f32) -> u64 {
+        let x = x * self.x_sign();
+
         match self.basis {
             Basis::Time(timeframe) => {
                 let interval = timeframe.to_milliseconds() as f64;
@@ -408,20 +480,14 @@ impl canvas::Program<Message> for AxisLabelsX<'_> {
                     }
                 }
                 mouse::Event::WheelScrolled { delta } => match delta {
-                    mouse::ScrollDelta::Lines { y, .. } | mouse::ScrollDelta::Pixels { y, .. } => {
-                        let message = Message::XScaling(
-                            *y,
-                            {
-                                if let Some(cursor_to_center) =
-                                    cursor.position_from(bounds.center())
-                                {
-                                    cursor_to_center.x
-                                } else {
-                                    0.0
-                                }
-                            },
-                            true,
-                        );
+                    mouse::ScrollDelta::Lines { x, y } | mouse::ScrollDelta::Pixels { x, y } => {
+                        let cursor_to_center_x = cursor
+                            .position_from(bounds.center())
+                            .map_or(0.0, |cursor_to_center| cursor_to_center.x);
+                        let delta = if *y != 0.0 { *y } else { *x };
+
+                        let message =
+                            super::wheel_time_scaling(delta, cursor_to_center_x, self.autoscaling);
 
                         return Some(canvas::Action::publish(message).and_capture());
                     }
@@ -446,7 +512,7 @@ impl canvas::Program<Message> for AxisLabelsX<'_> {
         let labels = self.labels_cache.draw(renderer, bounds.size(), |frame| {
             let region = self.visible_region(frame.size());
 
-            let target_spacing = REGULAR_LABEL_WIDTH * 2.0;
+            let target_spacing = regular_label_width() * 2.0;
             let target_count = (bounds.width / target_spacing).floor() as usize;
 
             let label_count = target_count.max(2);
@@ -460,7 +526,7 @@ impl canvas::Program<Message> for AxisLabelsX<'_> {
                         let mut last_x: Option<f32> = None;
                         for (i, timestamp) in interval_keys.iter().enumerate() {
                             let cell_index = -(last_idx as i32) + i as i32;
-                            let x_position = cell_index as f32 * self.cell_width;
+                            let x_position = cell_index as f32 * self.cell_width * self.x_sign();
 
                             let x_min_region = region.x;
                             let x_max_region = region.x + region.width;
@@ -485,19 +551,25 @@ impl canvas::Program<Message> for AxisLabelsX<'_> {
                     }
                 }
                 Basis::Time(timeframe) => {
-                    let x_min_region = self.x_to_interval(region.x);
-                    let x_max_region = self.x_to_interval(region.x + region.width);
+                    let left = self.x_to_interval(region.x);
+                    let right = self.x_to_interval(region.x + region.width);
 
-                    let generated_labels = timeseries::generate_time_labels(
+                    let mut generated_labels = timeseries::generate_time_labels(
                         timeframe,
                         self.timezone,
                         bounds,
-                        x_min_region,
-                        x_max_region,
+                        left.min(right),
+                        left.max(right),
                         label_count as i32,
                         palette,
                     );
 
+                    if self.reverse_time {
+                        generated_labels
+                            .iter_mut()
+                            .for_each(|label| label.mirror(bounds.size()));
+                    }
+
                     labels.extend(generated_labels);
                 }
             }
@@ -540,7 +612,10 @@ pub struct AxisLabelsY<'a> {
     pub decimals: usize,
     pub cell_height: f32,
     pub basis: Basis,
+    pub latest_x: u64,
     pub chart_bounds: Rectangle,
+    pub price_scale: PriceScale,
+    pub invert_price: bool,
 }
 
 impl AxisLabelsY<'_> {
@@ -556,8 +631,35 @@ impl AxisLabelsY<'_> {
         }
     }
 
+    fn y_sign(&self) -> f32 {
+        if self.invert_price { -1.0 } else { 1.0 }
+    }
+
     fn y_to_price(&self, y: f32) -> f32 {
-        self.min - (y / self.cell_height) * self.tick_size
+        let y = y * self.y_sign();
+
+        match self.price_scale {
+            PriceScale::Linear => self.min - (y / self.cell_height) * self.tick_size,
+            PriceScale::Logarithmic => {
+                logarithmic::y_to_price(y, self.min, self.tick_size, self.cell_height)
+            }
+        }
+    }
+
+    fn price_to_y(&self, price: f32) -> f32 {
+        let y = match self.price_scale {
+            PriceScale::Linear => (self.min - price) / self.tick_size * self.cell_height,
+            PriceScale::Logarithmic => {
+                logarithmic::price_to_y(price, self.min, self.tick_size, self.cell_height)
+            }
+        };
+
+        y * self.y_sign()
+    }
+
+    /// Y position of a price within the label canvas
+    fn price_to_label_y(&self, price: f32, region: Rectangle) -> f32 {
+        (self.price_to_y(price) - region.y) * self.scaling
     }
 }
 
@@ -634,49 +736,53 @@ impl canvas::Program<Message> for AxisLabelsY<'_> {
         bounds: Rectangle,
         cursor: mouse::Cursor,
     ) -> Vec<Geometry> {
-        let text_size = 12.0;
+        let text_size = label_text_size();
         let palette = theme.extended_palette();
 
         let labels = self.labels_cache.draw(renderer, bounds.size(), |frame| {
             let region = self.visible_region(frame.size());
 
-            let highest = self.y_to_price(region.y);
-            let lowest = self.y_to_price(region.y + region.height);
-
-            let range = highest - lowest;
+            let top = self.y_to_price(region.y);
+            let bottom = self.y_to_price(region.y + region.height);
+            let (highest, lowest) = (top.max(bottom), top.min(bottom));
 
-            let mut all_labels = linear::generate_labels(
-                bounds,
-                lowest,
-                highest,
-                text_size,
-                palette.background.base.text,
-                Some(self.decimals),
-            );
+            let mut all_labels = match self.price_scale {
+                PriceScale::Linear => {
+                    let mut labels = linear::generate_labels(
+                        bounds,
+                        lowest,
+                        highest,
+                        text_size,
+                        palette.background.base.text,
+                        Some(self.decimals),
+                    );
+                    // linear labels are laid out highest-first; logarithmic ones go through `price_to_y`
+                    if self.invert_price {
+                        labels
+                            .iter_mut()
+                            .for_each(|label| label.mirror(bounds.size()));
+                    }
+                    labels
+                }
+                PriceScale::Logarithmic => logarithmic::generate_labels(
+                    bounds,
+                    lowest,
+                    highest,
+                    text_size,
+                    palette.background.base.text,
+                    self.decimals,
+                    |price| self.price_to_label_y(price, region),
+                ),
+            };
 
             // Last price (priority 2)
             if let Some(label) = self.last_price {
                 let candle_close_label = match self.basis {
-                    Basis::Time(timeframe) => {
-                        let interval = timeframe.to_milliseconds();
-
+                    Basis::Time(timeframe) if data::chart::is_candle_countdown_enabled() => {
                         let current_time = chrono::Utc::now().timestamp_millis() as u64;
-                        let next_kline_open = (current_time / interval + 1) * interval;
-
-                        let remaining_seconds = (next_kline_open - current_time) / 1000;
-
-                        if remaining_seconds > 0 {
-                            let hours = remaining_seconds / 3600;
-                            let minutes = (remaining_seconds % 3600) / 60;
-                            let seconds = remaining_seconds % 60;
-
-                            let time_format = if hours > 0 {
-                                format!("{hours:02}:{minutes:02}:{seconds:02}")
-                            } else {
-                                format!("{minutes:02}:{seconds:02}")
-                            };
 
-                            Some(LabelContent {
+                        candle_countdown(timeframe.to_milliseconds(), self.latest_x, current_time)
+                            .map(|time_format| LabelContent {
                                 content: time_format,
                                 background_color: Some(palette.background.strong.color),
                                 text_color: if palette.is_dark {
@@ -684,20 +790,17 @@ impl canvas::Program<Message> for AxisLabelsY<'_> {
                                 } else {
                                     Color::WHITE.scale_alpha(0.8)
                                 },
-                                text_size: 11.0,
+                                text_size: text_size - 1.0,
                             })
-                        } else {
-                            None
-                        }
                     }
-                    Basis::Tick(_) => None,
+                    Basis::Time(_) | Basis::Tick(_) => None,
                 };
 
                 let (price, color) = label.get_with_color(palette);
                 let price = price.to_f32();
 
                 let price_label = LabelContent {
-                    content: format!("{:.*}", self.decimals, price),
+                    content: data::util::format_price(price, self.decimals),
                     background_color: Some(color),
                     text_color: {
                         if candle_close_label.is_some() {
@@ -710,10 +813,10 @@ impl canvas::Program<Message> for AxisLabelsY<'_> {
                             palette.primary.strong.text
                         }
                     },
-                    text_size: 12.0,
+                    text_size,
                 };
 
-                let y_pos = bounds.height - ((price - lowest) / range * bounds.height);
+                let y_pos = self.price_to_label_y(price, region);
                 let content_amt = if candle_close_label.is_some() { 2 } else { 1 };
 
                 all_labels.push(AxisLabel::Y {
@@ -726,16 +829,16 @@ impl canvas::Program<Message> for AxisLabelsY<'_> {
             // Crosshair price (priority 3)
             if let Some(crosshair_pos) = cursor.position_in(self.chart_bounds) {
                 let rounded_price = round_to_tick(
-                    lowest + (range * (bounds.height - crosshair_pos.y) / bounds.height),
+                    self.y_to_price(region.y + crosshair_pos.y / self.scaling),
                     self.tick_size,
                 );
-                let y_position = bounds.height - ((rounded_price - lowest) / range * bounds.height);
+                let y_position = self.price_to_label_y(rounded_price, region);
 
                 let label = LabelContent {
-                    content: format!("{:.*}", self.decimals, rounded_price),
+                    content: data::util::format_price(rounded_price, self.decimals),
                     background_color: Some(palette.secondary.base.color),
                     text_color: palette.secondary.base.text,
-                    text_size: 12.0,
+                    text_size,
                 };
 
                 all_labels.push(AxisLabel::Y {
@@ -1,5 +1,5 @@
 use super::{AxisLabel, LabelContent, calc_label_rect};
-use data::util::abbr_large_numbers;
+use data::util::{format_price, format_volume};
 use exchange::util::Price;
 
 const MAX_ITERATIONS: usize = 1000;
@@ -44,9 +44,9 @@ pub fn generate_labels(
     if labels_can_fit <= 1 {
         let label = LabelContent {
             content: if let Some(decimals) = decimals {
-                format!("{highest:.decimals$}")
+                format_price(highest, decimals)
             } else {
-                abbr_large_numbers(highest)
+                format_volume(highest)
             },
             background_color: None,
             text_color,
@@ -73,9 +73,9 @@ pub fn generate_labels(
     while value >= lowest && safety_counter < MAX_ITERATIONS {
         if value <= highest + step * 0.5 && value >= lowest - step * 0.5 {
             let content = if let Some(decimals) = decimals {
-                format!("{value:.decimals$}")
+                format_price(value, decimals)
             } else {
-                abbr_large_numbers(value)
+                format_volume(value)
             };
 
             let label = LabelContent {
@@ -120,6 +120,12 @@ impl PriceInfoLabel {
         }
     }
 
+    pub fn price(self) -> Price {
+        match self {
+            PriceInfoLabel::Up(p) | PriceInfoLabel::Down(p) | PriceInfoLabel::Neutral(p) => p,
+        }
+    }
+
     pub fn get_with_color(self, palette: &iced::theme::palette::Extended) -> (Price, iced::Color) {
         match self {
             PriceInfoLabel::Up(p) => (p, palette.success.base.color),
@@ -0,0 +1,81 @@
+use super::{AxisLabel, LabelContent, calc_label_rect};
+
+/// Pixels per unit of ln(price), chosen so that one `tick` near `base`
+/// spans `cell_height` just like on the linear scale
+fn scale_factor(base: f64, tick: f64, cell_height: f32) -> f64 {
+    f64::from(cell_height) * base / tick
+}
+
+/// Log-scale counterpart of the linear price to Y conversion; non-positive prices
+/// are clamped to one tick since they have no place in log space
+pub fn price_to_y(price: f32, base: f32, tick: f32, cell_height: f32) -> f32 {
+    let tick = f64::from(tick).max(f64::from(f32::MIN_POSITIVE));
+    let base = f64::from(base).max(tick);
+    let price = f64::from(price).max(tick);
+
+    (scale_factor(base, tick, cell_height) * (base.ln() - price.ln())) as f32
+}
+
+pub fn y_to_price(y: f32, base: f32, tick: f32, cell_height: f32) -> f32 {
+    let tick = f64::from(tick).max(f64::from(f32::MIN_POSITIVE));
+    let base = f64::from(base).max(tick);
+
+    (base * (-f64::from(y) / scale_factor(base, tick, cell_height)).exp()) as f32
+}
+
+/// Rounds to two significant digits so log-spaced labels land on readable prices
+fn round_to_nice(price: f32) -> f32 {
+    let magnitude = 10.0f32.powf(price.log10().floor() - 1.0);
+    (price / magnitude).round() * magnitude
+}
+
+/// Labels evenly spaced in log space, i.e. equal percentage steps between them
+pub fn generate_labels(
+    bounds: iced::Rectangle,
+    lowest: f32,
+    highest: f32,
+    text_size: f32,
+    text_color: iced::Color,
+    decimals: usize,
+    price_to_y: impl Fn(f32) -> f32,
+) -> Vec<AxisLabel> {
+    if !lowest.is_finite() || !highest.is_finite() || highest <= 0.0 {
+        return Vec::new();
+    }
+
+    let lowest = lowest.max(highest * f32::EPSILON);
+    let (ln_low, ln_high) = (lowest.ln(), highest.ln());
+
+    if ln_high <= ln_low {
+        return Vec::new();
+    }
+
+    let labels_can_fit = ((bounds.height / (text_size * 3.0)) as usize).max(1);
+    let mut labels = Vec::with_capacity(labels_can_fit);
+    let mut last_value = None;
+
+    for i in 0..labels_can_fit {
+        let ratio = (i as f32 + 0.5) / labels_can_fit as f32;
+        let value = round_to_nice((ln_low + ratio * (ln_high - ln_low)).exp());
+
+        if last_value == Some(value) || value < lowest || value > highest {
+            continue;
+        }
+        last_value = Some(value);
+
+        let label = LabelContent {
+            content: data::util::format_price(value, decimals),
+            background_color: None,
+            text_color,
+            text_size,
+        };
+
+        labels.push(AxisLabel::Y {
+            bounds: calc_label_rect(price_to_y(value), 1, text_size, bounds),
+            value_label: label,
+            timer_label: None,
+        });
+    }
+
+    labels
+}
@@ -1,6 +1,6 @@
-use crate::chart::scale::{AxisLabel, TEXT_SIZE};
+use crate::chart::scale::{AxisLabel, label_text_size};
 
-use chrono::{DateTime, Datelike, Months, Offset};
+use chrono::{DateTime, Datelike, Months};
 use data::{
     UserTimezone,
     util::{reset_to_start_of_month_utc, reset_to_start_of_year_utc},
@@ -118,7 +118,8 @@ fn calc_x_pos(time_millis: u64, min_millis: u64, max_millis: u64, width: f32) ->
 }
 
 fn is_drawable(x_pos: f64, width: f32) -> bool {
-    x_pos >= (-TEXT_SIZE * 5.0).into() && x_pos <= f64::from(width) + f64::from(TEXT_SIZE * 5.0)
+    let margin = f64::from(label_text_size() * 5.0);
+    x_pos >= -margin && x_pos <= f64::from(width) + margin
 }
 
 pub fn generate_time_labels(
@@ -397,16 +398,7 @@ fn to_user_fixed_offset<Tz: chrono::TimeZone>(
     dt: &chrono::DateTime<Tz>,
     tz: UserTimezone,
 ) -> chrono::DateTime<chrono::FixedOffset> {
-    match tz {
-        UserTimezone::Local => {
-            let offset = chrono::Local::now().offset().fix();
-            dt.with_timezone(&offset)
-        }
-        UserTimezone::Utc => {
-            let offset = chrono::FixedOffset::east_opt(0).unwrap();
-            dt.with_timezone(&offset)
-        }
-    }
+    tz.to_fixed_offset(dt)
 }
 
 fn with_user_timezone<Tz, F, R>(timezone: UserTimezone, f: F) -> impl Fn(&chrono::DateTime<Tz>) -> R
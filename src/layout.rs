@@ -1,9 +1,6 @@
 use crate::modal::layout_manager::LayoutManager;
 use crate::screen::dashboard::{Dashboard, pane};
-use data::{
-    UserTimezone,
-    layout::{WindowSpec, pane::Axis},
-};
+use data::layout::{WindowSpec, pane::Axis};
 
 use iced::widget::pane_grid::{self, Configuration};
 use std::vec;
@@ -31,8 +28,13 @@ pub struct SavedState {
     pub sidebar: data::Sidebar,             // وضعیت سایدبار
     pub theme: data::Theme,                 // تم انتخاب شده
     pub custom_theme: Option<data::Theme>,  // تم سفارشی (در صورت وجود)
+    pub custom_themes: Vec<data::Theme>,    // تم‌های سفارشی نام‌گذاری شده
     pub audio_cfg: data::AudioStream,       // تنظیمات صدا
     pub volume_size_unit: exchange::SizeUnit, // واحد نمایش حجم (Base یا Quote)
+    pub scanner: data::Scanner,               // تنظیمات اسکنر بازار
+    pub autosave_interval: data::AutosaveInterval, // فاصله ذخیره خودکار وضعیت
+    pub replay: data::replay::RecordingCfg,        // تنظیمات ضبط جلسه
+    pub network_errors: Vec<String>,               // خطاهای اعتبارسنجی تنظیمات شبکه
 }
 
 impl SavedState {
@@ -50,22 +52,6 @@ impl SavedState {
     }
 }
 
-impl Default for SavedState {
-    fn default() -> Self {
-        SavedState {
-            layout_manager: LayoutManager::new(),
-            main_window: None,
-            scale_factor: data::ScaleFactor::default(),
-            timezone: UserTimezone::default(),
-            sidebar: data::Sidebar::default(),
-            theme: data::Theme::default(),
-            custom_theme: None,
-            audio_cfg: data::AudioStream::default(),
-            volume_size_unit: exchange::SizeUnit::Base,
-        }
-    }
-}
-
 impl From<&Dashboard> for data::Dashboard {
     /// تبدیل داشبورد داخلی به ساختار داده‌ای قابل ذخیره (سریال‌سازی)
     fn from(dashboard: &Dashboard) -> Self {
@@ -166,6 +152,24 @@ impl From<&pane::State> for data::Pane {
                 settings: pane.settings.clone(),
                 link_group: pane.link_group,
             },
+            // منحنی تجمعی عمق
+            pane::Content::DepthCurve(_) => data::Pane::DepthCurve {
+                stream_type: streams,
+                settings: pane.settings.clone(),
+                link_group: pane.link_group,
+            },
+            // نرخ تأمین مالی و بیسیس
+            pane::Content::FundingChart(_) => data::Pane::FundingChart {
+                stream_type: streams,
+                settings: pane.settings.clone(),
+                link_group: pane.link_group,
+            },
+            // مانیتور اسپرد بین دو بازار
+            pane::Content::Spread(_) => data::Pane::SpreadChart {
+                stream_type: streams,
+                settings: pane.settings.clone(),
+                link_group: pane.link_group,
+            },
             // نمودار مقایسه‌ای
             pane::Content::Comparison(chart) => {
                 let settings = data::layout::pane::Settings {
@@ -289,6 +293,51 @@ pub fn configuration(pane: data::Pane) -> Configuration<pane::State> {
         } => {
             let content = pane::Content::Ladder(None);
 
+            Configuration::Pane(pane::State::from_config(
+                content,
+                stream_type,
+                settings,
+                link_group,
+            ))
+        }
+        // منحنی تجمعی عمق
+        data::Pane::DepthCurve {
+            stream_type,
+            settings,
+            link_group,
+        } => {
+            let content = pane::Content::DepthCurve(None);
+
+            Configuration::Pane(pane::State::from_config(
+                content,
+                stream_type,
+                settings,
+                link_group,
+            ))
+        }
+        // نرخ تأمین مالی و بیسیس
+        data::Pane::FundingChart {
+            stream_type,
+            settings,
+            link_group,
+        } => {
+            let content = pane::Content::FundingChart(None);
+
+            Configuration::Pane(pane::State::from_config(
+                content,
+                stream_type,
+                settings,
+                link_group,
+            ))
+        }
+        // مانیتور اسپرد بین دو بازار
+        data::Pane::SpreadChart {
+            stream_type,
+            settings,
+            link_group,
+        } => {
+            let content = pane::Content::Spread(None);
+
             Configuration::Pane(pane::State::from_config(
                 content,
                 stream_type,
@@ -301,81 +350,100 @@ pub fn configuration(pane: data::Pane) -> Configuration<pane::State> {
 
 /// بارگذاری وضعیت ذخیره شده از فایل روی دیسک
 pub fn load_saved_state() -> SavedState {
-    match data::read_from_file(data::SAVED_STATE_PATH) {
-        Ok(state) => {
-            let mut de_layouts = vec![];
+    let state = match data::read_from_file(data::SAVED_STATE_PATH) {
+        Ok(state) => state,
+        Err(e) => {
+            log::error!(
+                "Failed to load/find layout state: {}. Starting with the default layout.",
+                e
+            );
 
-            // بازسازی چیدمان‌ها از داده‌های سریال‌سازی شده
-            for layout in &state.layout_manager.layouts {
-                let mut popout_windows = Vec::new();
+            // در صورت بروز خطا یا نصب تازه، وضعیت پیش‌فرض با چیدمان پیش‌فرض استفاده می‌شود
+            data::State::fresh()
+        }
+    };
 
-                for (pane, window_spec) in &layout.dashboard.popout {
-                    let configuration = configuration(pane.clone());
-                    popout_windows.push((configuration, *window_spec));
-                }
+    let mut de_layouts = vec![];
 
-                let layout_id = Uuid::new_v4();
+    // بازسازی چیدمان‌ها از داده‌های سریال‌سازی شده
+    for layout in &state.layout_manager.layouts {
+        let mut popout_windows = Vec::new();
 
-                let dashboard = Dashboard::from_config(
-                    configuration(layout.dashboard.pane.clone()),
-                    popout_windows,
-                    layout_id,
-                );
+        for (pane, window_spec) in &layout.dashboard.popout {
+            let configuration = configuration(pane.clone());
+            popout_windows.push((configuration, *window_spec));
+        }
 
-                de_layouts.push((layout.name.clone(), layout_id, dashboard));
-            }
+        let layout_id = Uuid::new_v4();
 
-            // ایجاد مدیر چیدمان با داده‌های بارگذاری شده
-            let layout_manager = {
-                let mut layouts = Vec::with_capacity(de_layouts.len());
+        let dashboard = Dashboard::from_config(
+            configuration(layout.dashboard.pane.clone()),
+            popout_windows,
+            layout_id,
+        );
 
-                for (name, layout_id, dashboard) in de_layouts {
-                    let id = LayoutId {
-                        unique: layout_id,
-                        name,
-                    };
-                    layouts.push(Layout { id, dashboard });
-                }
+        de_layouts.push((layout.name.clone(), layout_id, dashboard));
+    }
 
-                let active_layout =
-                    state
-                        .layout_manager
-                        .active_layout
-                        .as_ref()
-                        .and_then(|target_name| {
-                            layouts
-                                .iter()
-                                .find(|layout| layout.id.name == *target_name)
-                                .map(|layout| layout.id.clone())
-                        });
-
-                LayoutManager::from_config(layouts, active_layout)
-            };
+    // ایجاد مدیر چیدمان با داده‌های بارگذاری شده
+    let layout_manager = {
+        let mut layouts = Vec::with_capacity(de_layouts.len());
 
-            // اعمال تنظیمات عمومی
-            exchange::fetcher::toggle_trade_fetch(state.trade_fetch_enabled);
-            exchange::set_preferred_currency(state.size_in_quote_ccy);
-
-            SavedState {
-                theme: state.selected_theme,
-                custom_theme: state.custom_theme,
-                layout_manager,
-                main_window: state.main_window,
-                timezone: state.timezone,
-                sidebar: state.sidebar,
-                scale_factor: state.scale_factor,
-                audio_cfg: state.audio_cfg,
-                volume_size_unit: state.size_in_quote_ccy,
-            }
+        for (name, layout_id, dashboard) in de_layouts {
+            let id = LayoutId {
+                unique: layout_id,
+                name,
+            };
+            layouts.push(Layout { id, dashboard });
         }
-        Err(e) => {
-            log::error!(
-                "Failed to load/find layout state: {}. Starting with a new layout.",
-                e
-            );
 
-            // در صورت بروز خطا، وضعیت پیش‌فرض برگردانده می‌شود
-            SavedState::default()
-        }
+        let active_layout = state
+            .layout_manager
+            .active_layout
+            .as_ref()
+            .and_then(|target_name| {
+                layouts
+                    .iter()
+                    .find(|layout| layout.id.name == *target_name)
+                    .map(|layout| layout.id.clone())
+            });
+
+        LayoutManager::from_config(layouts, active_layout)
+    };
+
+    // اعمال تنظیمات عمومی
+    exchange::fetcher::toggle_trade_fetch(state.trade_fetch_enabled);
+    exchange::set_preferred_currency(state.size_in_quote_ccy);
+    exchange::set_inverse_conversion(state.inverse_size_conversion);
+    data::util::set_number_format(state.number_format);
+    data::config::timezone::set_display_timezone(state.timezone);
+    data::tape_speed::set_tape_speed_window(state.tape_speed_window);
+    data::stale::set_stale_threshold(state.stale_threshold);
+    data::chart::set_candle_countdown(state.candle_countdown);
+    data::chart::set_label_font(state.label_font);
+    data::performance::set_performance_profile(state.performance_profile);
+    data::chart::indicator::set_default_indicators(state.default_indicators);
+    data::layout::dashboard::set_focus_follows_hover(state.focus_follows_hover);
+    data::chart::tick_preset::set_tick_presets(state.tick_presets);
+    let network_errors = exchange::network::set_network_config(state.network)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+    SavedState {
+        theme: state.selected_theme,
+        custom_theme: state.custom_theme,
+        custom_themes: state.custom_themes,
+        layout_manager,
+        main_window: state.main_window,
+        timezone: state.timezone,
+        sidebar: state.sidebar,
+        scale_factor: state.scale_factor,
+        audio_cfg: state.audio_cfg,
+        volume_size_unit: state.size_in_quote_ccy,
+        scanner: state.scanner,
+        autosave_interval: state.autosave_interval,
+        replay: state.replay,
+        network_errors,
     }
 }
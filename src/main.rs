@@ -12,23 +12,35 @@ mod style;   // تعریف استایل‌ها و تم‌های ظاهری
 mod widget;  // ویجت‌های سفارشی رابط کاربری
 mod window;  // مدیریت پنجره‌های سیستم‌عامل
 
+use audio::SoundType;
+use data::chart::indicator::{HeatmapIndicator, Indicator, KlineIndicator, UiIndicator};
 use data::config::theme::default_theme;
+use data::panel::spread::SpreadEvent;
+use data::walls::{WallEvent, WallSide};
 use data::{layout::WindowSpec, sidebar};
 use layout::{LayoutId, configuration};
-use modal::{LayoutManager, ThemeEditor, audio::AudioStream};
+use modal::{
+    LayoutManager, ThemeEditor,
+    audio::AudioStream,
+    command_palette::{self, CommandPalette},
+    connections::Connections,
+    measurements::Measurements,
+};
 use modal::{dashboard_modal, main_dialog_modal};
 use screen::dashboard::{self, Dashboard};
+use screen::scanner::{self, Scanner};
 use widget::{
     confirm_dialog_container,
     toast::{self, Toast},
     tooltip,
 };
 
+use iced::advanced::widget::operation::focusable;
 use iced::{
-    Alignment, Element, Subscription, Task, keyboard, padding,
+    Alignment, Element, Length, Subscription, Task, keyboard, padding,
     widget::{
-        button, column, container, pane_grid, pick_list, row, rule, scrollable, text,
-        tooltip::Position as TooltipPosition,
+        button, column, combo_box, container, pane_grid, pick_list, row, rule, scrollable, space,
+        stack, text, tooltip::Position as TooltipPosition,
     },
 };
 use std::{borrow::Cow, collections::HashMap, vec};
@@ -38,13 +50,37 @@ fn main() {
     // راه‌اندازی سیستم لاگ
     logger::setup(cfg!(debug_assertions)).expect("Failed to initialize logger");
 
+    // پرچم‌های اشکال‌زدایی همگام‌سازی دفتر سفارش
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--capture-depth" => {
+                exchange::capture::set_depth_capture_dir(Some(data::data_path(Some(
+                    "depth_captures",
+                ))));
+            }
+            "--replay-depth" => {
+                let Some(path) = args.next() else {
+                    eprintln!("--replay-depth requires a capture file path");
+                    std::process::exit(2);
+                };
+                std::process::exit(replay_depth_capture(std::path::Path::new(&path)));
+            }
+            _ => {}
+        }
+    }
+
     // اجرای ترد برای پاکسازی داده‌های قدیمی بازار در پس‌زمینه
     std::thread::spawn(data::cleanup_old_market_data);
 
+    // لبه‌های نرم فقط هنگام راه‌اندازی قابل تنظیم است، پس پروفایل کارایی پیش از اجرا خوانده می‌شود
+    let antialiasing = data::read_from_file(data::SAVED_STATE_PATH)
+        .map_or(true, |state| state.performance_profile.antialiasing());
+
     // اجرای برنامه اصلی با استفاده از کتابخانه Iced
     let _ = iced::daemon(Flowsurface::new, Flowsurface::update, Flowsurface::view)
         .settings(iced::Settings {
-            antialiasing: true, // فعال‌سازی لبه‌های نرم
+            antialiasing, // لبه‌های نرم بر اساس پروفایل کارایی
             fonts: vec![
                 Cow::Borrowed(style::AZERET_MONO_BYTES),
                 Cow::Borrowed(style::ICONS_BYTES),
@@ -59,6 +95,61 @@ fn main() {
         .run();
 }
 
+/// بازپخش یک فایل ضبط عمق و چاپ رویدادهای ناهمگامی؛ کد خروج غیر صفر یعنی ناهمگامی یا خطا
+fn replay_depth_capture(path: &std::path::Path) -> i32 {
+    match exchange::adapter::binance::replay_depth_capture(path) {
+        Ok(report) => {
+            println!(
+                "frames: {}, snapshots: {}, diffs applied: {}, stale: {}, last update id: {}",
+                report.frames,
+                report.snapshots,
+                report.diffs_applied,
+                report.stale,
+                report.last_update_id
+            );
+            for event in &report.resyncs {
+                println!("record {}: {}", event.record, event.reason);
+            }
+            i32::from(!report.resyncs.is_empty())
+        }
+        Err(e) => {
+            eprintln!("Failed to replay {}: {e}", path.display());
+            2
+        }
+    }
+}
+
+/// عنوان و راهنمای چک‌باکس دریافت معاملات، بر اساس صرافی‌هایی که منبع معاملات گذشته دارند
+static TRADE_FETCH_TEXT: std::sync::LazyLock<(String, String)> = std::sync::LazyLock::new(|| {
+    let mut sources = Vec::new();
+
+    for exchange in exchange::adapter::Exchange::ALL {
+        let venue = exchange::adapter::ExchangeInclusive::of(exchange);
+
+        if let Some(source) = exchange.trade_history_source()
+            && !sources.iter().any(|(v, _)| *v == venue)
+        {
+            sources.push((venue, source));
+        }
+    }
+
+    let venues = sources
+        .iter()
+        .map(|(venue, _)| venue.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let details = sources
+        .iter()
+        .map(|(venue, source)| format!("{venue}: {source}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    (
+        format!("Fetch trades ({venues})"),
+        format!("Try to fetch trades for footprint charts\n{details}"),
+    )
+});
+
 /// ساختار اصلی برنامه Flowsurface که وضعیت کل برنامه را نگه می‌دارد
 struct Flowsurface {
     main_window: window::Window,          // پنجره اصلی برنامه
@@ -66,12 +157,57 @@ struct Flowsurface {
     layout_manager: LayoutManager,        // مدیریت چیدمان‌ها
     theme_editor: ThemeEditor,            // ویرایشگر تم
     audio_stream: AudioStream,            // مدیریت پخش صدا
+    connections: Connections,             // وضعیت اتصال جریان‌های فعال
+    measurements: Measurements,           // تاریخچه اندازه‌گیری‌های خط‌کش
+    scanner: Scanner,                     // اسکنر حجم و نوسان بازار
+    replay: modal::replay::Replay,        // ضبط و بازپخش جلسات بازار
+    command_palette: CommandPalette,      // جستجو و اجرای سریع فرمان‌ها
     confirm_dialog: Option<screen::ConfirmDialog<Message>>, // دیالوگ تایید عملیات
     volume_size_unit: exchange::SizeUnit, // واحد نمایش حجم معاملات
     ui_scale_factor: data::ScaleFactor,   // ضریب بزرگنمایی رابط کاربری
     timezone: data::UserTimezone,         // منطقه زمانی کاربر
+    timezone_options: combo_box::State<data::UserTimezone>, // گزینه‌های قابل جستجوی منطقه زمانی
     theme: data::Theme,                   // تم فعلی برنامه
     notifications: Vec<Toast>,            // لیست اعلان‌ها (Toasts)
+    depth_replicas: exchange::depth::DepthReplicas, // نسخه‌های محلی دفتر سفارش هر جریان
+    autosave: Autosave,                   // وضعیت ذخیره خودکار
+    latency: LatencyProbe,                // نتایج آزمون تأخیر دامنه‌های صرافی‌ها
+}
+
+/// نتایج آخرین آزمون تأخیر دامنه‌های REST برای نمایش در تنظیمات
+#[derive(Default)]
+struct LatencyProbe {
+    reports: Vec<exchange::network::LatencyReport>, // تأخیر اندازه‌گیری شده هر دامنه
+    running: bool,                                  // آیا آزمونی در حال اجراست
+}
+
+/// وضعیت ذخیره خودکار دوره‌ای؛ فقط در صورت تغییر وضعیت از آخرین ذخیره، فایل نوشته می‌شود
+struct Autosave {
+    interval: data::AutosaveInterval, // فاصله بین ذخیره‌ها
+    dirty: bool,                      // آیا از آخرین ذخیره تغییری رخ داده است
+    in_flight: bool,                  // آیا عملیات نوشتن در حال انجام است
+    last_save: std::time::Instant,    // زمان آخرین ذخیره
+}
+
+impl Autosave {
+    fn new(interval: data::AutosaveInterval) -> Self {
+        Self {
+            interval,
+            dirty: false,
+            in_flight: false,
+            last_save: std::time::Instant::now(),
+        }
+    }
+
+    /// آیا زمان ذخیره خودکار بعدی فرا رسیده است
+    fn is_due(&self, now: std::time::Instant) -> bool {
+        self.dirty
+            && !self.in_flight
+            && self
+                .interval
+                .duration()
+                .is_some_and(|interval| now.duration_since(self.last_save) >= interval)
+    }
 }
 
 /// پیام‌های مختلف که در برنامه جابجا می‌شوند و باعث تغییر وضعیت می‌شوند
@@ -90,17 +226,40 @@ enum Message {
     RestartRequested(HashMap<window::Id, WindowSpec>), // درخواست راه‌اندازی مجدد
     GoBack,                               // بازگشت به مرحله قبل (مثلاً با دکمه Esc)
     DataFolderRequested,                  // درخواست باز کردن پوشه داده‌ها
+    ResetToDefaults,                      // پشتیبان‌گیری از وضعیت و شروع دوباره با تنظیمات پیش‌فرض
     ThemeSelected(data::Theme),           // انتخاب تم جدید
     ScaleFactorChanged(data::ScaleFactor),// تغییر ضریب بزرگنمایی
     SetTimezone(data::UserTimezone),      // تنظیم منطقه زمانی
     ToggleTradeFetch(bool),               // فعال/غیرفعال کردن دریافت معاملات
     ApplyVolumeSizeUnit(exchange::SizeUnit), // اعمال واحد حجم جدید
+    ApplyInverseConversion(bool),         // فعال/غیرفعال کردن تبدیل اندازه قراردادهای معکوس
+    SetNumberFormat(data::util::NumberFormat), // تغییر قالب‌بندی سراسری اعداد
+    SetTapeSpeedWindow(data::tape_speed::TapeSpeedWindow), // تغییر پنجره محاسبه سرعت نوار معاملات
+    SetStaleThreshold(data::stale::StaleThreshold), // تغییر آستانه کهنه شدن داده‌های پنل‌ها
+    SetCandleCountdown(bool),   // نمایش/عدم نمایش شمارش معکوس بسته شدن کندل
+    SetFocusFollowsHover(bool), // جابجایی فوکوس پنل‌ها با عبور ماوس به جای کلیک
+    SetLabelFont(data::chart::LabelFont), // اندازه و نوع قلم برچسب‌های محور و نشانگر
+    SetPerformanceProfile(data::performance::PerformanceProfile), // تغییر مصالحه کیفیت و نرخ فریم
+    SetLatencyProbe(bool),                // آزمون تأخیر دامنه‌ها هنگام راه‌اندازی
+    RunLatencyProbe,                      // اجرای آزمون تأخیر دامنه‌ها
+    LatencyProbed(Vec<exchange::network::LatencyReport>), // نتایج آزمون تأخیر
+    SetEndpointOverride(exchange::network::Endpoint, Option<String>), // انتخاب دستی دامنه
     RemoveNotification(usize),            // حذف یک اعلان
     // نمایش یا پنهان کردن دیالوگ تایید
     ToggleDialogModal(Option<screen::ConfirmDialog<Message>>),
     ThemeEditor(modal::theme_editor::Message), // پیام‌های ویرایشگر تم
     Layouts(modal::layout_manager::Message),   // پیام‌های مدیریت چیدمان
     AudioStream(modal::audio::Message),        // پیام‌های مربوط به صدا
+    Connections(modal::connections::Message),  // پیام‌های وضعیت اتصال
+    Measurements(modal::measurements::Message), // پیام‌های تاریخچه خط‌کش
+    Scanner(scanner::Message),                  // پیام‌های اسکنر بازار
+    Replay(modal::replay::Message),             // پیام‌های ضبط و بازپخش جلسه
+    ToggleCommandPalette,                       // باز یا بسته کردن جستجوی فرمان‌ها
+    Shortcut(Box<Message>),                     // اجرای میانبر در صورتی که فیلد متنی فوکوس نداشته باشد
+    CommandPalette(command_palette::Message),   // پیام‌های جستجوی فرمان‌ها
+    AutosaveIntervalChanged(data::AutosaveInterval), // تغییر فاصله ذخیره خودکار
+    AutosaveRequested(HashMap<window::Id, WindowSpec>), // ذخیره خودکار با مشخصات فعلی پنجره‌ها
+    AutosaveCompleted(Result<(), String>),      // نتیجه نوشتن ذخیره خودکار
 }
 
 impl Flowsurface {
@@ -128,15 +287,28 @@ impl Flowsurface {
         let mut state = Self {
             main_window: window::Window::new(main_window_id),
             layout_manager: saved_state.layout_manager,
-            theme_editor: ThemeEditor::new(saved_state.custom_theme),
+            theme_editor: ThemeEditor::new(saved_state.custom_theme, saved_state.custom_themes),
             audio_stream: AudioStream::new(saved_state.audio_cfg),
+            connections: Connections::default(),
+            measurements: Measurements::default(),
+            scanner: Scanner::new(saved_state.scanner),
+            replay: modal::replay::Replay::new(saved_state.replay),
+            command_palette: CommandPalette::default(),
             sidebar,
             confirm_dialog: None,
             timezone: saved_state.timezone,
+            timezone_options: combo_box::State::new(data::UserTimezone::options()),
             ui_scale_factor: saved_state.scale_factor,
             volume_size_unit: saved_state.volume_size_unit,
             theme: saved_state.theme,
-            notifications: vec![],
+            notifications: saved_state
+                .network_errors
+                .into_iter()
+                .map(Toast::error)
+                .collect(),
+            depth_replicas: exchange::depth::DepthReplicas::default(),
+            autosave: Autosave::new(saved_state.autosave_interval),
+            latency: LatencyProbe::default(),
         };
 
         // تعیین چیدمان فعال
@@ -150,12 +322,22 @@ impl Flowsurface {
         );
         let load_layout = state.load_layout(active_layout_id.unique, main_window_id);
 
+        // آزمون تأخیر اختیاری دامنه‌ها؛ تا پایان آن پیشنهاد ذخیره شده اجرای قبلی به کار می‌رود
+        let latency_probe = if exchange::network::network_config().latency_probe {
+            state.update(Message::RunLatencyProbe)
+        } else {
+            Task::none()
+        };
+
         (
             state,
-            open_main_window
-                .discard()
-                .chain(load_layout)
-                .chain(launch_sidebar.map(Message::Sidebar)),
+            Task::batch([
+                open_main_window
+                    .discard()
+                    .chain(load_layout)
+                    .chain(launch_sidebar.map(Message::Sidebar)),
+                latency_probe,
+            ]),
         )
     }
 
@@ -164,66 +346,121 @@ impl Flowsurface {
         match message {
             // رویدادهای وب‌ساکت بازار (قیمت‌ها، معاملات و غیره)
             Message::MarketWsEvent(event) => {
-                let main_window_id = self.main_window.id;
-                let dashboard = self.active_dashboard_mut();
-
-                match event {
-                    exchange::Event::Connected(exchange) => {
-                        log::info!("a stream connected to {exchange} WS");
-                    }
-                    exchange::Event::Disconnected(exchange, reason) => {
-                        log::info!("a stream disconnected from {exchange} WS: {reason:?}");
-                    }
-                    exchange::Event::DepthReceived(
-                        stream,
-                        depth_update_t,
-                        depth,
-                        trades_buffer,
-                    ) => {
-                        // به‌روزرسانی عمق بازار و معاملات
-                        let task = dashboard
-                            .update_depth_and_trades(
-                                &stream,
-                                depth_update_t,
-                                &depth,
-                                &trades_buffer,
-                                main_window_id,
-                            )
-                            .map(move |msg| Message::Dashboard {
-                                layout_id: None,
-                                event: msg,
-                            });
-
-                        // پخش صدا در صورت نیاز
-                        if let Err(err) = self.audio_stream.try_play_sound(&stream, &trades_buffer)
-                        {
-                            log::error!("Failed to play sound: {err}");
-                        }
+                // هنگام بازپخش یک جلسه ضبط شده، داده‌های زنده کنار گذاشته می‌شوند
+                if self.replay.is_playing_back() {
+                    return Task::none();
+                }
 
-                        return task;
+                // ثبت زمان آخرین پیام، نرخ پیام و تأخیر هر جریان برای نمایش وضعیت اتصال؛
+                // زمان شروع کندل برای تخمین تأخیر قابل استفاده نیست
+                match &event {
+                    exchange::Event::DepthReceived(stream, depth_update_t, ..) => {
+                        self.connections.received(
+                            stream.ticker_info().ticker,
+                            std::time::Instant::now(),
+                            Some(*depth_update_t),
+                        );
                     }
-                    exchange::Event::KlineReceived(stream, kline) => {
-                        // به‌روزرسانی کندل‌ها
-                        return dashboard
-                            .update_latest_klines(&stream, &kline, main_window_id)
-                            .map(move |msg| Message::Dashboard {
-                                layout_id: None,
-                                event: msg,
-                            });
+                    exchange::Event::KlineReceived(stream, _) => {
+                        self.connections.received(
+                            stream.ticker_info().ticker,
+                            std::time::Instant::now(),
+                            None,
+                        );
                     }
+                    _ => {}
                 }
+
+                return self.market_event(event);
             }
             // تیک زمان برای به‌روزرسانی‌های دوره‌ای
             Message::Tick(now) => {
                 let main_window_id = self.main_window.id;
 
-                return self
-                    .active_dashboard_mut()
-                    .tick(now, main_window_id)
-                    .map(move |msg| Message::Dashboard {
-                        layout_id: None,
-                        event: msg,
-                    });
+                // نسخه دفتر سفارش جریان‌هایی که اشتراکشان لغو شده حذف می‌شود؛
+                // هنگام بازپخش، جریان‌های ضبط شده ممکن است در داشبورد نباشند
+                if !self.replay.is_playing_back() {
+                    let streams = &self.active_dashboard().streams;
+                    let unsubscribed = self
+                        .depth_replicas
+                        .streams()
+                        .filter(|stream| !streams.contains(stream))
+                        .copied()
+                        .collect::<Vec<_>>();
+
+                    unsubscribed
+                        .iter()
+                        .for_each(|stream| self.depth_replicas.remove(stream));
+                }
+
+                let dashboard_tick =
+                    self.active_dashboard_mut()
+                        .tick(now, main_window_id)
+                        .map(move |msg| Message::Dashboard {
+                            layout_id: None,
+                            event: msg,
+                        });
+
+                // پیشروی بازپخش جلسه ضبط شده متناسب با زمان سپری شده و سرعت پخش
+                let replay_events = self.replay.tick(now);
+                let dashboard_tick = if replay_events.is_empty() {
+                    dashboard_tick
+                } else {
+                    Task::batch([dashboard_tick, self.replay_events(replay_events)])
+                };
+
+                // ذخیره خودکار دوره‌ای در صورت تغییر وضعیت
+                if self.take_state_changes() {
+                    self.autosave.dirty = true;
+                }
+                if self.autosave.is_due(now) {
+                    self.autosave.in_flight = true;
+
+                    let mut active_windows: Vec<window::Id> =
+                        self.active_dashboard().popout.keys().copied().collect();
+                    active_windows.push(main_window_id);
+
+                    return Task::batch([
+                        dashboard_tick,
+                        window::collect_window_specs(active_windows, Message::AutosaveRequested),
+                    ]);
+                }
+
+                return dashboard_tick;
+            }
+            // تغییر فاصله ذخیره خودکار
+            Message::AutosaveIntervalChanged(interval) => {
+                self.autosave.interval = interval;
+                self.autosave.dirty = true;
+            }
+            // نوشتن وضعیت در فایل خارج از رشته رابط کاربری
+            Message::AutosaveRequested(windows) => {
+                self.autosave.dirty = false;
+
+                let Some(json) = self.serialize_state(&windows) else {
+                    self.autosave.in_flight = false;
+                    return Task::none();
+                };
+
+                return Task::perform(
+                    async move {
+                        data::write_json_to_file(&json, data::SAVED_STATE_PATH)
+                            .map_err(|e| e.to_string())
+                    },
+                    Message::AutosaveCompleted,
+                );
+            }
+            Message::AutosaveCompleted(result) => {
+                self.autosave.in_flight = false;
+                self.autosave.last_save = std::time::Instant::now();
+
+                match result {
+                    Ok(()) => log::debug!("Auto-saved state to {}", data::SAVED_STATE_PATH),
+                    Err(e) => {
+                        log::error!("Failed to auto-save state: {e}");
+                        self.autosave.dirty = true;
+                    }
+                }
             }
             // رویدادهای پنجره (مانند درخواست بستن)
             Message::WindowEvent(event) => match event {
@@ -233,6 +470,7 @@ impl Flowsurface {
 
                     if window != main_window {
                         dashboard.popout.remove(&window);
+                        self.autosave.dirty = true;
                         return window::close(window);
                     }
 
@@ -245,6 +483,9 @@ impl Flowsurface {
 
                     return window::collect_window_specs(active_windows, Message::ExitRequested);
                 }
+                window::Event::Focused => self.audio_stream.set_window_focused(true),
+                window::Event::Unfocused => self.audio_stream.set_window_focused(false),
+                window::Event::Reshaped => self.autosave.dirty = true,
             },
             // خروج از برنامه و ذخیره وضعیت
             Message::ExitRequested(windows) => {
@@ -279,6 +520,7 @@ impl Flowsurface {
             // تغییر تم برنامه
             Message::ThemeSelected(theme) => {
                 self.theme = theme.clone();
+                self.autosave.dirty = true;
             }
             // رویدادهای مربوط به داشبورد (نمودارها و پنل‌ها)
             Message::Dashboard {
@@ -314,6 +556,11 @@ impl Flowsurface {
                             self.notifications.push(toast);
                             Task::none()
                         }
+                        // ثبت اندازه‌گیری کامل شده خط‌کش در تاریخچه
+                        Some(dashboard::Event::RulerMeasured(entry)) => {
+                            self.measurements.push(*entry);
+                            Task::none()
+                        }
                         // حل کردن استریم‌های ذخیره شده
                         Some(dashboard::Event::ResolveStreams { pane_id, streams }) => {
                             let tickers_info = self.sidebar.tickers_info();
@@ -375,10 +622,108 @@ impl Flowsurface {
             // تنظیم منطقه زمانی
             Message::SetTimezone(tz) => {
                 self.timezone = tz;
+                data::config::timezone::set_display_timezone(tz);
+                self.autosave.dirty = true;
+
+                // سطوح جلسه بر اساس روز محلی دوباره لنگر می‌شوند
+                let main_window = self.main_window.id;
+                self.active_dashboard_mut()
+                    .invalidate_all_panes(main_window);
+            }
+            // تغییر قالب‌بندی اعداد و بازسازی نمایش تمام پنل‌ها
+            Message::SetNumberFormat(format) => {
+                data::util::set_number_format(format);
+                self.autosave.dirty = true;
+
+                let main_window = self.main_window.id;
+                self.active_dashboard_mut()
+                    .invalidate_all_panes(main_window);
+            }
+            // تغییر طول پنجره سرعت نوار معاملات
+            Message::SetTapeSpeedWindow(window) => {
+                data::tape_speed::set_tape_speed_window(window);
+                self.autosave.dirty = true;
+            }
+            // تغییر آستانه کهنه شدن داده‌ها؛ در تیک بعدی روی پنل‌ها اعمال می‌شود
+            Message::SetStaleThreshold(threshold) => {
+                data::stale::set_stale_threshold(threshold);
+                self.autosave.dirty = true;
+            }
+            // نمایش یا پنهان کردن شمارش معکوس کندل روی محور قیمت
+            Message::SetCandleCountdown(enabled) => {
+                data::chart::set_candle_countdown(enabled);
+                self.autosave.dirty = true;
+
+                let main_window = self.main_window.id;
+                self.active_dashboard_mut()
+                    .invalidate_all_panes(main_window);
+            }
+            // انتخاب بین فوکوس با کلیک و فوکوس با عبور ماوس روی پنل‌ها
+            Message::SetFocusFollowsHover(enabled) => {
+                data::layout::dashboard::set_focus_follows_hover(enabled);
+                self.autosave.dirty = true;
+            }
+            // فعال کردن آزمون تأخیر؛ اگر هنوز نتیجه‌ای نیست همان لحظه اجرا می‌شود
+            Message::SetLatencyProbe(enabled) => {
+                exchange::network::set_latency_probe(enabled);
+                self.autosave.dirty = true;
+
+                if enabled && self.latency.reports.is_empty() {
+                    return self.update(Message::RunLatencyProbe);
+                }
+            }
+            // آزمون همزمان همه دامنه‌ها با مهلت محدود
+            Message::RunLatencyProbe => {
+                if !self.latency.running {
+                    self.latency.running = true;
+                    return Task::perform(
+                        exchange::network::probe_latencies(),
+                        Message::LatencyProbed,
+                    );
+                }
+            }
+            // ذخیره سریع‌ترین دامنه‌ها؛ فقط در صورت فعال بودن آزمون به کار گرفته می‌شوند
+            Message::LatencyProbed(reports) => {
+                self.latency.running = false;
+                exchange::network::apply_latency_reports(&reports);
+                self.autosave.dirty = true;
+                self.latency.reports = reports;
+            }
+            // دامنه انتخابی کاربر بر پیشنهاد آزمون مقدم است
+            Message::SetEndpointOverride(endpoint, value) => {
+                exchange::network::set_endpoint_override(endpoint, value);
+                self.autosave.dirty = true;
+            }
+            // تغییر قلم برچسب‌ها و بازسازی حافظه‌های رسم نمودارها
+            Message::SetLabelFont(font) => {
+                data::chart::set_label_font(font);
+                self.autosave.dirty = true;
+
+                let main_window = self.main_window.id;
+                self.active_dashboard_mut()
+                    .invalidate_all_panes(main_window);
+            }
+            // تغییر پروفایل کارایی؛ فاصله تیک و ساده‌سازی کندل‌ها فوراً و لبه‌های نرم پس از اجرای دوباره اعمال می‌شوند
+            Message::SetPerformanceProfile(profile) => {
+                let previous = data::performance::performance_profile();
+                data::performance::set_performance_profile(profile);
+                self.autosave.dirty = true;
+
+                if previous.antialiasing() != profile.antialiasing() {
+                    self.notifications
+                        .push(Toast::new(toast::Notification::Info(
+                            "Anti-aliasing change applies after relaunching the app".to_string(),
+                        )));
+                }
+
+                let main_window = self.main_window.id;
+                self.active_dashboard_mut()
+                    .invalidate_all_panes(main_window);
             }
             // تغییر ضریب بزرگنمایی رابط کاربری
             Message::ScaleFactorChanged(value) => {
                 self.ui_scale_factor = value;
+                self.autosave.dirty = true;
             }
             // فعال/غیرفعال کردن دریافت معاملات
             Message::ToggleTradeFetch(checked) => {
@@ -387,6 +732,7 @@ impl Flowsurface {
                     .for_each(|dashboard| {
                         dashboard.toggle_trade_fetch(checked, &self.main_window);
                     });
+                self.autosave.dirty = true;
 
                 if checked {
                     self.confirm_dialog = None;
@@ -470,11 +816,230 @@ impl Flowsurface {
                             manager.insert_layout(new_layout.clone(), dashboard);
                         }
                     }
+                    // خروجی گرفتن از یک چیدمان به صورت فایل JSON
+                    Some(modal::layout_manager::Action::Export(id)) => {
+                        let Some(layout) = self.layout_manager.get(id) else {
+                            return Task::none();
+                        };
+
+                        let ser_layout = data::Layout {
+                            name: layout.id.name.clone(),
+                            dashboard: data::Dashboard::from(&layout.dashboard),
+                        };
+
+                        match ser_layout.export() {
+                            Ok(exported) => {
+                                let path = exported.path.display();
+                                let body = if exported.renamed {
+                                    format!("A file with that name exists, exported to {path}")
+                                } else {
+                                    format!("Layout exported to {path}")
+                                };
+                                self.notifications
+                                    .push(Toast::new(toast::Notification::Info(body)));
+                            }
+                            Err(err) => self.notifications.push(Toast::error(err.to_string())),
+                        }
+                    }
+                    // ذخیره چیدمان به عنوان پیش‌فرض نصب تازه و بازنشانی
+                    Some(modal::layout_manager::Action::SetAsDefault(id)) => {
+                        let Some(layout) = self.layout_manager.get(id) else {
+                            return Task::none();
+                        };
+
+                        let ser_layout = data::Layout {
+                            name: layout.id.name.clone(),
+                            dashboard: data::Dashboard::from(&layout.dashboard),
+                        };
+
+                        match ser_layout.save_as_default() {
+                            Ok(_) => {
+                                let body = format!("\"{}\" set as default layout", ser_layout.name);
+                                self.notifications
+                                    .push(Toast::new(toast::Notification::Info(body)));
+                            }
+                            Err(err) => self.notifications.push(Toast::error(err.to_string())),
+                        }
+                    }
+                    // وارد کردن یک چیدمان از فایل؛ استریم‌ها هنگام بارگذاری از مسیر ResolveStreams حل می‌شوند
+                    Some(modal::layout_manager::Action::Import(path)) => {
+                        match data::Layout::import(&path) {
+                            Ok(ser_layout) => {
+                                let manager = &mut self.layout_manager;
+
+                                let name = match ser_layout.name.trim() {
+                                    "" => "Imported layout",
+                                    name => name,
+                                };
+
+                                let new_uid = uuid::Uuid::new_v4();
+                                let new_layout = LayoutId {
+                                    unique: new_uid,
+                                    name: manager.ensure_unique_name(name, new_uid),
+                                };
+
+                                let popout_windows = ser_layout
+                                    .dashboard
+                                    .popout
+                                    .iter()
+                                    .map(|(pane, spec)| (configuration(pane.clone()), *spec))
+                                    .collect();
+
+                                let dashboard = Dashboard::from_config(
+                                    configuration(ser_layout.dashboard.pane.clone()),
+                                    popout_windows,
+                                    new_uid,
+                                );
+
+                                manager.insert_layout(new_layout, dashboard);
+                            }
+                            Err(err) => self.notifications.push(Toast::error(err.to_string())),
+                        }
+                    }
                     None => {}
                 }
             }
             // به‌روزرسانی تنظیمات صدا
             Message::AudioStream(message) => self.audio_stream.update(message),
+            // درخواست اتصال مجدد یک جریان
+            Message::Connections(message) => {
+                if let Some(modal::connections::Action::ReconnectAll) =
+                    self.connections.update(message)
+                {
+                    let tickers = self
+                        .active_dashboard()
+                        .streamed_tickers()
+                        .into_iter()
+                        .map(|info| info.ticker)
+                        .collect::<Vec<_>>();
+
+                    let result = self
+                        .connections
+                        .reconnect_all(tickers.iter().copied(), std::time::Instant::now());
+
+                    // جریان‌های دوباره مشترک شده از یک دفتر خالی شروع می‌شوند
+                    if result.is_ok() {
+                        tickers
+                            .iter()
+                            .for_each(|ticker| self.depth_replicas.remove_ticker(ticker));
+                    }
+
+                    match result {
+                        Ok(0) => self
+                            .notifications
+                            .push(Toast::warn("No active streams to reconnect")),
+                        Ok(count) => {
+                            self.notifications
+                                .push(Toast::new(toast::Notification::Info(format!(
+                                    "Reconnecting streams of {count} ticker(s)"
+                                ))))
+                        }
+                        Err(wait) => self.notifications.push(Toast::warn(format!(
+                            "Streams were just refreshed, try again in {}s",
+                            wait.as_secs().max(1)
+                        ))),
+                    }
+                }
+            }
+            // پاک کردن یا خروجی گرفتن از تاریخچه خط‌کش
+            Message::Measurements(message) => {
+                if let Some(modal::measurements::Action::Exported(result)) =
+                    self.measurements.update(message)
+                {
+                    match result {
+                        Ok(path) => {
+                            let body = format!("Measurements exported to {}", path.display());
+                            self.notifications
+                                .push(Toast::new(toast::Notification::Info(body)));
+                        }
+                        Err(err) => self.notifications.push(Toast::error(format!(
+                            "Failed to export measurements: {err}"
+                        ))),
+                    }
+                }
+            }
+            // ضبط جریان‌های بازار و بازپخش جلسات ضبط شده
+            Message::Replay(message) => {
+                let main_window_id = self.main_window.id;
+
+                match self.replay.update(message) {
+                    Some(modal::replay::Action::Dispatch(events)) => {
+                        return self.replay_events(events);
+                    }
+                    // پنل‌ها خالی می‌شوند تا بازپخش از آخرین تصویر کامل عمق پیش از نقطه جدید ساخته شود؛
+                    // تاریخچه زنده کندل‌ها دریافت نمی‌شود تا با رویدادهای ضبط شده مخلوط نشود
+                    Some(modal::replay::Action::Restart(events)) => {
+                        self.depth_replicas.clear();
+                        let dashboard = self.active_dashboard_mut();
+                        dashboard.set_playback(true);
+                        dashboard.reset_pane_contents(main_window_id);
+
+                        return self.replay_events(events);
+                    }
+                    // پس از بستن بازپخش، جریان‌های زنده روی پنل‌های تازه ادامه می‌یابند
+                    Some(modal::replay::Action::Closed) => {
+                        self.depth_replicas.clear();
+                        let dashboard = self.active_dashboard_mut();
+                        dashboard.set_playback(false);
+                        dashboard.reset_pane_contents(main_window_id);
+                    }
+                    Some(modal::replay::Action::Info(body)) => {
+                        self.notifications
+                            .push(Toast::new(toast::Notification::Info(body)));
+                    }
+                    Some(modal::replay::Action::Error(err)) => {
+                        self.notifications.push(Toast::error(err));
+                    }
+                    None => {}
+                }
+            }
+            // اسکن دوره‌ای بازار و انتخاب نماد از نتایج اسکنر
+            Message::Scanner(message) => {
+                let (task, action) = self.scanner.update(message, &self.sidebar.tickers_table);
+
+                if let Some(scanner::Action::TickerSelected(ticker_info)) = action {
+                    let main_window_id = self.main_window.id;
+
+                    return self
+                        .active_dashboard_mut()
+                        .switch_tickers_in_group(main_window_id, ticker_info)
+                        .map(move |msg| Message::Dashboard {
+                            layout_id: None,
+                            event: msg,
+                        });
+                }
+
+                return task.map(Message::Scanner);
+            }
+            // باز کردن جستجوی فرمان‌ها با Ctrl+K و اجرای فرمان انتخاب شده
+            Message::ToggleCommandPalette => {
+                if self.sidebar.is_menu_active(sidebar::Menu::CommandPalette) {
+                    self.sidebar.set_menu(None);
+                } else {
+                    self.sidebar.set_menu(Some(sidebar::Menu::CommandPalette));
+                    return self.command_palette.open();
+                }
+            }
+            // فیلدهای متنی میانبرهای Ctrl را مصرف نمی‌کنند، پس فوکوس آن‌ها جداگانه بررسی می‌شود
+            Message::Shortcut(message) => {
+                return iced::advanced::widget::operate(focusable::count()).then(move |count| {
+                    if count.focused.is_none() {
+                        Task::done(*message.clone())
+                    } else {
+                        Task::none()
+                    }
+                });
+            }
+            Message::CommandPalette(message) => {
+                let entries = self.command_palette_entries();
+
+                if let Some(command_palette::Action::Run(command)) =
+                    self.command_palette.update(message, &entries)
+                {
+                    self.sidebar.set_menu(None);
+                    return self.run_command(command);
+                }
+            }
             // باز کردن پوشه داده‌ها
             Message::DataFolderRequested => {
                 if let Err(err) = data::open_data_folder() {
@@ -482,6 +1047,24 @@ impl Flowsurface {
                         .push(Toast::error(format!("Failed to open data folder: {err}")));
                 }
             }
+            // وضعیت فعلی ذخیره نمی‌شود؛ فایل آن کنار گذاشته و برنامه با چیدمان پیش‌فرض از نو ساخته می‌شود
+            Message::ResetToDefaults => {
+                self.confirm_dialog = None;
+
+                match data::backup_and_clear_state(data::SAVED_STATE_PATH) {
+                    Ok(backup) => {
+                        let task = self.restart();
+
+                        if let Some(path) = backup {
+                            let body = format!("Previous state backed up to {}", path.display());
+                            self.notifications
+                                .push(Toast::new(toast::Notification::Info(body)));
+                        }
+                        return task;
+                    }
+                    Err(err) => self.notifications.push(Toast::error(err.to_string())),
+                }
+            }
             // پیام‌های مربوط به ویرایشگر تم
             Message::ThemeEditor(msg) => {
                 let action = self.theme_editor.update(msg, &self.theme.clone().into());
@@ -492,6 +1075,40 @@ impl Flowsurface {
                     }
                     Some(modal::theme_editor::Action::UpdateTheme(theme)) => {
                         self.theme = data::Theme(theme);
+                        self.autosave.dirty = true;
+
+                        let main_window = self.main_window.id;
+
+                        self.active_dashboard_mut()
+                            .invalidate_all_panes(main_window);
+                    }
+                    Some(modal::theme_editor::Action::Exported(result)) => match result {
+                        Ok(path) => {
+                            self.notifications.push(Toast::new(toast::Notification::Info(
+                                format!("Theme exported to {}", path.display()),
+                            )));
+                        }
+                        Err(err) => {
+                            self.notifications
+                                .push(Toast::error(format!("Failed to export theme: {err}")));
+                        }
+                    },
+                    Some(modal::theme_editor::Action::Imported(result)) => {
+                        // در صورت خطا در خواندن فایل، به تم پیش‌فرض بازگردانده می‌شود
+                        match result {
+                            Ok(theme) => {
+                                self.notifications.push(Toast::new(
+                                    toast::Notification::Info(format!("Imported theme {theme}")),
+                                ));
+                                self.theme = data::Theme(theme);
+                            }
+                            Err(err) => {
+                                self.notifications
+                                    .push(Toast::error(format!("Failed to import theme: {err}")));
+                                self.theme = data::Theme::default();
+                            }
+                        }
+                        self.autosave.dirty = true;
 
                         let main_window = self.main_window.id;
 
@@ -503,6 +1120,16 @@ impl Flowsurface {
             }
             // پیام‌های مربوط به سایدبار
             Message::Sidebar(message) => {
+                // قیمت مارک آمار دریافت شده به نمودارهای کندلی همان نماد داده می‌شود
+                if let dashboard::sidebar::Message::TickersTable(
+                    dashboard::tickers_table::Message::UpdateTickerStats(_, stats),
+                ) = &message
+                {
+                    let main_window = self.main_window.id;
+                    self.active_dashboard_mut()
+                        .update_mark_prices(main_window, stats);
+                }
+
                 let (task, action) = self.sidebar.update(message);
 
                 match action {
@@ -546,6 +1173,17 @@ impl Flowsurface {
                     self.active_dashboard().popout.keys().copied().collect();
                 active_windows.push(self.main_window.id);
 
+                return window::collect_window_specs(active_windows, Message::RestartRequested);
+            }
+            // تبدیل اندازه قراردادهای معکوس نیز نیازمند راه‌اندازی مجدد جریان‌هاست
+            Message::ApplyInverseConversion(enabled) => {
+                exchange::set_inverse_conversion(enabled);
+                self.confirm_dialog = None;
+
+                let mut active_windows: Vec<window::Id> =
+                    self.active_dashboard().popout.keys().copied().collect();
+                active_windows.push(self.main_window.id);
+
                 return window::collect_window_specs(active_windows, Message::RestartRequested);
             }
         }
@@ -599,15 +1237,37 @@ impl Flowsurface {
             };
 
             // چیدمان کلی صفحه اصلی (سایدبار + داشبورد)
-            let base = column![
-                header_title,
+            let main_row: Element<'_, Message> = if self.sidebar.is_pinned() {
                 match sidebar_pos {
                     sidebar::Position::Left => row![sidebar_view, dashboard_view,],
                     sidebar::Position::Right => row![dashboard_view, sidebar_view],
                 }
                 .spacing(4)
-                .padding(8),
-            ];
+                .into()
+            } else {
+                // سایدبار غیرثابت روی نمودارها می‌لغزد؛ فقط نوار باریک آن فضا اشغال می‌کند
+                let handle_space =
+                    space::horizontal().width(dashboard::sidebar::COLLAPSED_WIDTH);
+                let (dashboard_row, align_x) = match sidebar_pos {
+                    sidebar::Position::Left => {
+                        (row![handle_space, dashboard_view], Alignment::Start)
+                    }
+                    sidebar::Position::Right => {
+                        (row![dashboard_view, handle_space], Alignment::End)
+                    }
+                };
+
+                stack![
+                    dashboard_row.spacing(4),
+                    container(sidebar_view)
+                        .width(Length::Fill)
+                        .height(Length::Fill)
+                        .align_x(align_x),
+                ]
+                .into()
+            };
+
+            let base = column![header_title, container(main_row).padding(8)];
 
             // اگر منویی در سایدبار باز است، آن را به صورت مودال نمایش بده
             if let Some(menu) = self.sidebar.active_menu() {
@@ -668,32 +1328,103 @@ impl Flowsurface {
         // رویدادهای سایدبار
         let sidebar = self.sidebar.subscription().map(Message::Sidebar);
 
-        // استریم‌های داده‌های بازار
-        let exchange_streams = self
-            .active_dashboard()
-            .market_subscriptions()
-            .map(Message::MarketWsEvent);
+        // استریم‌های داده‌های بازار؛ هنگام بازپخش جلسه ضبط شده اتصال زنده قطع می‌شود
+        let exchange_streams = if self.replay.is_playing_back() {
+            Subscription::none()
+        } else {
+            self.active_dashboard()
+                .market_subscriptions(self.connections.reconnects())
+                .map(Message::MarketWsEvent)
+        };
 
-        // تیک زمان هر ۱۰۰ میلی‌ثانیه
-        let tick = iced::time::every(std::time::Duration::from_millis(100)).map(Message::Tick);
+        // تیک زمان با فاصله پروفایل کارایی (۱۰۰ میلی‌ثانیه در بالاترین کیفیت)
+        let tick = iced::time::every(data::performance::performance_profile().tick_interval())
+            .map(Message::Tick);
 
-        // گوش دادن به کلیدهای کیبورد (مثلاً Esc برای بازگشت)
+        // گوش دادن به کلیدهای کیبورد (Esc برای بازگشت و Ctrl+Z برای بازگردانی پنل)؛
+        // کلیدهایی که ویجت فوکوس‌دار مصرف کرده باشد به اینجا نمی‌رسند
         let hotkeys = keyboard::listen().filter_map(|event| {
-            let keyboard::Event::KeyPressed { key, .. } = event else {
+            let keyboard::Event::KeyPressed { key, modifiers, .. } = event else {
                 return None;
             };
-            match key {
+            let shortcut = |message| Some(Message::Shortcut(Box::new(message)));
+
+            match key.as_ref() {
                 keyboard::Key::Named(keyboard::key::Named::Escape) => Some(Message::GoBack),
+                // اتصال مجدد همه جریان‌ها با Ctrl+R (مثلاً پس از بیدار شدن سیستم از حالت خواب)
+                keyboard::Key::Character("r") if modifiers.command() => shortcut(
+                    Message::Connections(modal::connections::Message::ReconnectAll),
+                ),
+                // بازگرداندن آخرین پنل بسته یا بازنشانی شده با Ctrl+Z
+                keyboard::Key::Character("z") if modifiers.command() => {
+                    shortcut(Message::Dashboard {
+                        layout_id: None,
+                        event: dashboard::Message::Undo,
+                    })
+                }
+                // بی‌صدا کردن یا فعال‌سازی مجدد همه هشدارهای صوتی با Ctrl+M
+                keyboard::Key::Character("m") if modifiers.command() => {
+                    shortcut(Message::AudioStream(modal::audio::Message::ToggleMute))
+                }
+                // جستجو و اجرای فرمان‌ها با Ctrl+K؛ فیلد جستجوی خود پالت فوکوس دارد،
+                // پس این میانبر بدون بررسی فوکوس اجرا می‌شود تا بتواند پالت را ببندد
+                keyboard::Key::Character("k") if modifiers.command() => {
+                    Some(Message::ToggleCommandPalette)
+                }
                 _ => None,
             }
         });
 
+        // پیمایش نتایج جستجوی فرمان‌ها با کلیدهای جهت‌نما
+        let command_palette = if self.sidebar.is_menu_active(sidebar::Menu::CommandPalette) {
+            keyboard::listen().filter_map(|event| {
+                let keyboard::Event::KeyPressed { key, .. } = event else {
+                    return None;
+                };
+                let delta = match key.as_ref() {
+                    keyboard::Key::Named(keyboard::key::Named::ArrowUp) => -1,
+                    keyboard::Key::Named(keyboard::key::Named::ArrowDown) => 1,
+                    _ => return None,
+                };
+                Some(Message::CommandPalette(
+                    command_palette::Message::MoveSelection(delta),
+                ))
+            })
+        } else {
+            Subscription::none()
+        };
+
+        // دکمه‌های ماوس در حالت فوکوس با عبور ماوس، تا کشیدن یا جابجایی نمودار فوکوس را جابجا نکند
+        let pointer = if data::layout::dashboard::is_focus_follows_hover() {
+            iced::event::listen_with(|event, _status, _window| match event {
+                iced::Event::Mouse(iced::mouse::Event::ButtonPressed(_)) => Some(true),
+                iced::Event::Mouse(iced::mouse::Event::ButtonReleased(_)) => Some(false),
+                _ => None,
+            })
+            .map(|held| Message::Dashboard {
+                layout_id: None,
+                event: dashboard::Message::PointerHeld(held),
+            })
+        } else {
+            Subscription::none()
+        };
+
+        // اسکن دوره‌ای فقط زمانی که منوی اسکنر باز است
+        let scanner = if self.sidebar.is_menu_active(sidebar::Menu::Scanner) {
+            self.scanner.subscription().map(Message::Scanner)
+        } else {
+            Subscription::none()
+        };
+
         Subscription::batch(vec![
             exchange_streams,
             sidebar,
             window_events,
             tick,
             hotkeys,
+            scanner,
+            command_palette,
+            pointer,
         ])
     }
 
@@ -709,22 +1440,143 @@ impl Flowsurface {
             .expect("No active dashboard")
     }
 
+    /// اعمال رویدادهای یک جلسه ضبط شده به ترتیب زمانی
+    fn replay_events(&mut self, events: Vec<exchange::Event>) -> Task<Message> {
+        Task::batch(events.into_iter().map(|event| self.market_event(event)))
+    }
+
+    /// اعمال یک رویداد بازار روی داشبورد فعال؛ رویدادهای زنده و رویدادهای بازپخش شده
+    /// از یک جلسه ضبط شده هر دو از این مسیر عبور می‌کنند
+    fn market_event(&mut self, event: exchange::Event) -> Task<Message> {
+        let main_window_id = self.main_window.id;
+
+        match event {
+            exchange::Event::Connected(exchange) => {
+                log::info!("a stream connected to {exchange} WS");
+                self.connections.connected(exchange);
+                Task::none()
+            }
+            exchange::Event::Disconnected(exchange, reason) => {
+                log::info!("a stream disconnected from {exchange} WS: {reason:?}");
+                self.connections
+                    .disconnected(exchange, &reason, std::time::Instant::now());
+                Task::none()
+            }
+            exchange::Event::DepthReceived(stream, depth_update_t, depth_change, trades_buffer) => {
+                // اعمال تغییرات روی نسخه محلی دفتر سفارش، بدون کپی کامل در هر بروزرسانی
+                let depth = self.depth_replicas.apply(&stream, &depth_change);
+
+                // ضبط رویداد برای بازپخش؛ با رسیدن به سقف حجم، ضبط متوقف می‌شود
+                if let Some(body) = self.replay.record_depth(
+                    &stream,
+                    depth_update_t,
+                    &depth_change,
+                    depth,
+                    &trades_buffer,
+                ) {
+                    self.notifications
+                        .push(Toast::new(toast::Notification::Info(body)));
+                }
+
+                // به‌روزرسانی عمق بازار و معاملات
+                let (task, wall_events) = self
+                    .layout_manager
+                    .active_dashboard_mut()
+                    .expect("No active dashboard")
+                    .update_depth_and_trades(
+                        &stream,
+                        depth_update_t,
+                        depth,
+                        &trades_buffer,
+                        main_window_id,
+                    );
+                let task = task.map(move |msg| Message::Dashboard {
+                    layout_id: None,
+                    event: msg,
+                });
+
+                // صدای هشدار ظاهر یا برداشته شدن دیوارهای عمق
+                for event in wall_events {
+                    let sound = match event {
+                        WallEvent::Appeared { side, .. } => match side {
+                            WallSide::Bid => SoundType::HardBuy,
+                            WallSide::Ask => SoundType::HardSell,
+                        },
+                        WallEvent::Pulled { side, .. } => match side {
+                            WallSide::Bid => SoundType::Buy,
+                            WallSide::Ask => SoundType::Sell,
+                        },
+                    };
+                    if let Err(err) = self.audio_stream.play(sound) {
+                        log::error!("Failed to play wall alert sound: {err}");
+                    }
+                }
+
+                // پخش صدا در صورت نیاز
+                if let Err(err) = self.audio_stream.try_play_sound(&stream, &trades_buffer) {
+                    log::error!("Failed to play sound: {err}");
+                }
+
+                task
+            }
+            exchange::Event::KlineReceived(stream, kline) => {
+                if let Some(body) = self.replay.record_kline(&stream, &kline) {
+                    self.notifications
+                        .push(Toast::new(toast::Notification::Info(body)));
+                }
+
+                // به‌روزرسانی کندل‌ها
+                let (task, spread_events) = self.active_dashboard_mut().update_latest_klines(
+                    &stream,
+                    &kline,
+                    main_window_id,
+                );
+
+                // صدای هشدار عبور اسپرد از آستانه‌ها
+                for event in spread_events {
+                    let sound = match event {
+                        SpreadEvent::Above(_) => SoundType::HardBuy,
+                        SpreadEvent::Below(_) => SoundType::HardSell,
+                    };
+                    if let Err(err) = self.audio_stream.play(sound) {
+                        log::error!("Failed to play spread alert sound: {err}");
+                    }
+                }
+
+                task.map(move |msg| Message::Dashboard {
+                    layout_id: None,
+                    event: msg,
+                })
+            }
+        }
+    }
+
+    /// آیا چیدمان‌ها، پنل‌ها یا تنظیمات بخش‌های دیگر از آخرین بررسی تغییر کرده‌اند؛ همه پرچم‌ها پاک می‌شوند
+    fn take_state_changes(&mut self) -> bool {
+        let main_window = self.main_window.id;
+
+        self.layout_manager.take_changed(main_window)
+            | self.sidebar.take_changed()
+            | self.scanner.take_changed()
+            | self.audio_stream.take_changed()
+            | self.theme_editor.take_changed()
+            | self.replay.take_changed()
+    }
+
     /// دریافت مرجع قابل تغییر به داشبورد فعال
     fn active_dashboard_mut(&mut self) -> &mut Dashboard {
-        let active_layout = self
-            .layout_manager
-            .active_layout_id()
-            .expect("No active layout");
         self.layout_manager
-            .get_mut(active_layout.unique)
-            .map(|layout| &mut layout.dashboard)
+            .active_dashboard_mut()
             .expect("No active dashboard")
     }
 
     /// بارگذاری یک چیدمان خاص
     fn load_layout(&mut self, layout_uid: uuid::Uuid, main_window: window::Id) -> Task<Message> {
+        let playing_back = self.replay.is_playing_back();
+
         match self.layout_manager.set_active_layout(layout_uid) {
             Ok(layout) => {
+                layout.dashboard.set_playback(playing_back);
                 layout
                     .dashboard
                     .load_layout(main_window)
@@ -740,6 +1592,241 @@ impl Flowsurface {
         }
     }
 
+    /// فرمان‌های قابل جستجو: منوها و تنظیمات، چیدمان‌ها، اندیکاتورهای پنل فعال و نمادها
+    fn command_palette_entries(&self) -> Vec<command_palette::Entry> {
+        use command_palette::{Command, Entry};
+
+        let mut entries = vec![
+            Entry::new("Menu", "Open settings", Command::OpenMenu(sidebar::Menu::Settings)),
+            Entry::new("Menu", "Manage layouts", Command::OpenMenu(sidebar::Menu::Layout)),
+            Entry::new("Menu", "Audio alerts", Command::OpenMenu(sidebar::Menu::Audio)),
+            Entry::new(
+                "Menu",
+                "Connections",
+                Command::OpenMenu(sidebar::Menu::Connections),
+            ),
+            Entry::new(
+                "Menu",
+                "Measurements",
+                Command::OpenMenu(sidebar::Menu::Measurements),
+            ),
+            Entry::new("Menu", "Market scanner", Command::OpenMenu(sidebar::Menu::Scanner)),
+            Entry::new("Menu", "Session replay", Command::OpenMenu(sidebar::Menu::Replay)),
+            Entry::new(
+                "Menu",
+                "Theme editor",
+                Command::OpenMenu(sidebar::Menu::ThemeEditor),
+            ),
+            Entry::new(
+                "Settings",
+                if exchange::fetcher::is_trade_fetch_enabled() {
+                    "Disable trade fetching"
+                } else {
+                    "Enable trade fetching"
+                },
+                Command::ToggleTradeFetch,
+            ),
+            Entry::new("Audio", "Toggle mute", Command::ToggleMute),
+            Entry::new("Streams", "Reconnect all streams", Command::ReconnectAll),
+            Entry::new("Pane", "Undo closing pane", Command::Undo),
+            Entry::new("App", "Open data folder", Command::OpenDataFolder),
+        ];
+
+        entries.extend(self.layout_manager.layouts.iter().map(|layout| {
+            Entry::new(
+                "Layout",
+                format!("Switch to layout {}", layout.id.name),
+                Command::SwitchLayout(layout.id.unique),
+            )
+        }));
+
+        let main_window = self.main_window.id;
+        let dashboard = self.active_dashboard();
+        if let Some((window, pane)) = dashboard.focus
+            && let Some(state) = dashboard.get_pane(main_window, window, pane)
+            && let Some(ticker_info) = state.stream_pair()
+        {
+            let market = ticker_info.market_type();
+            let toggles: Vec<(UiIndicator, String, bool)> = match &state.content {
+                dashboard::pane::Content::Kline { indicators, .. } => {
+                    KlineIndicator::for_market(market)
+                        .iter()
+                        .map(|ind| ((*ind).into(), ind.to_string(), indicators.contains(ind)))
+                        .collect()
+                }
+                dashboard::pane::Content::Heatmap { indicators, .. } => {
+                    HeatmapIndicator::for_market(market)
+                        .iter()
+                        .map(|ind| ((*ind).into(), ind.to_string(), indicators.contains(ind)))
+                        .collect()
+                }
+                _ => vec![],
+            };
+
+            entries.extend(toggles.into_iter().map(|(indicator, name, enabled)| {
+                let verb = if enabled { "Remove" } else { "Add" };
+                Entry::new(
+                    "Indicator",
+                    format!("{verb} {name} indicator"),
+                    Command::ToggleIndicator(window, pane, indicator),
+                )
+            }));
+
+            // پرش نمودار به زمان یا قیمت تایپ شده؛ تاریخ در منطقه زمانی کاربر یا فاصله نسبی مانند -3d
+            let query = self.command_palette.query();
+            let time_basis = match &state.content {
+                dashboard::pane::Content::Kline { chart: Some(c), .. } => Some(c.basis().is_time()),
+                dashboard::pane::Content::Heatmap { chart: Some(_), .. } => Some(true),
+                _ => None,
+            };
+
+            if let Some(time_basis) = time_basis
+                && !query.is_empty()
+            {
+                let now = chrono::Utc::now().timestamp_millis() as u64;
+
+                if time_basis && let Some(time) = self.timezone.parse_time_input(query, now) {
+                    let label = self.timezone.format_datetime(time as i64);
+                    entries.push(
+                        Entry::new(
+                            "Navigate",
+                            format!("Go to {query} ({label})"),
+                            Command::GoToTime(window, pane, time),
+                        )
+                        .dynamic(),
+                    );
+                }
+
+                if let Ok(price) = query.parse::<f32>()
+                    && price.is_finite()
+                    && price > 0.0
+                {
+                    entries.push(
+                        Entry::new(
+                            "Navigate",
+                            format!("Go to price {query}"),
+                            Command::GoToPrice(
+                                window,
+                                pane,
+                                exchange::util::Price::from_f32(price),
+                            ),
+                        )
+                        .dynamic(),
+                    );
+                }
+            }
+        }
+
+        entries.extend(data::UserTimezone::options().into_iter().map(|timezone| {
+            Entry::new(
+                "Timezone",
+                format!("Timezone {timezone}"),
+                Command::SetTimezone(timezone),
+            )
+            .dynamic()
+        }));
+
+        entries.extend(
+            self.sidebar
+                .tickers_table
+                .tickers_info
+                .values()
+                .flatten()
+                .map(|info| {
+                    let (symbol, market) = info.ticker.display_symbol_and_type();
+                    Entry::new(
+                        "Ticker",
+                        format!("{symbol} {market} {}", info.exchange()),
+                        Command::SelectTicker(*info),
+                    )
+                    .dynamic()
+                }),
+        );
+
+        entries
+    }
+
+    /// اجرای فرمان انتخاب شده از طریق همان پیام‌هایی که منوها و میانبرها ارسال می‌کنند
+    fn run_command(&mut self, command: command_palette::Command) -> Task<Message> {
+        use command_palette::Command;
+
+        match command {
+            Command::OpenMenu(menu) => {
+                self.sidebar.set_menu(Some(menu));
+                Task::none()
+            }
+            Command::SwitchLayout(id) => {
+                self.update(Message::Layouts(modal::layout_manager::Message::SelectActive(id)))
+            }
+            Command::SetTimezone(timezone) => self.update(Message::SetTimezone(timezone)),
+            Command::ToggleTradeFetch => {
+                if exchange::fetcher::is_trade_fetch_enabled() {
+                    self.update(Message::ToggleTradeFetch(false))
+                } else {
+                    // فعال‌سازی مانند تنظیمات نیاز به تایید دارد
+                    self.sidebar.set_menu(Some(sidebar::Menu::Settings));
+                    self.confirm_dialog = Some(screen::ConfirmDialog::new(
+                        "This might be unreliable and take some time to complete. Proceed?"
+                            .to_string(),
+                        Box::new(Message::ToggleTradeFetch(true)),
+                    ));
+                    Task::none()
+                }
+            }
+            Command::ToggleIndicator(window, pane, indicator) => self.update(Message::Dashboard {
+                layout_id: None,
+                event: dashboard::Message::Pane(
+                    window,
+                    dashboard::pane::Message::PaneEvent(
+                        pane,
+                        dashboard::pane::Event::ToggleIndicator(indicator),
+                    ),
+                ),
+            }),
+            Command::GoToTime(window, pane, time) => self.update(Message::Dashboard {
+                layout_id: None,
+                event: dashboard::Message::Pane(
+                    window,
+                    dashboard::pane::Message::PaneEvent(
+                        pane,
+                        dashboard::pane::Event::ChartInteraction(chart::Message::GoToTime(time)),
+                    ),
+                ),
+            }),
+            Command::GoToPrice(window, pane, price) => self.update(Message::Dashboard {
+                layout_id: None,
+                event: dashboard::Message::Pane(
+                    window,
+                    dashboard::pane::Message::PaneEvent(
+                        pane,
+                        dashboard::pane::Event::ChartInteraction(chart::Message::GoToPrice(price)),
+                    ),
+                ),
+            }),
+            Command::SelectTicker(ticker_info) => {
+                let main_window_id = self.main_window.id;
+
+                self.active_dashboard_mut()
+                    .switch_tickers_in_group(main_window_id, ticker_info)
+                    .map(move |msg| Message::Dashboard {
+                        layout_id: None,
+                        event: msg,
+                    })
+            }
+            Command::ReconnectAll => self.update(Message::Connections(
+                modal::connections::Message::ReconnectAll,
+            )),
+            Command::ToggleMute => {
+                self.update(Message::AudioStream(modal::audio::Message::ToggleMute))
+            }
+            Command::Undo => self.update(Message::Dashboard {
+                layout_id: None,
+                event: dashboard::Message::Undo,
+            }),
+            Command::OpenDataFolder => self.update(Message::DataFolderRequested),
+        }
+    }
+
     /// نمایش محتوا به همراه یک پنجره مودال (تنظیمات، چیدمان، صدا و غیره)
     fn view_with_modal<'a>(
         &'a self,
@@ -760,9 +1847,7 @@ impl Flowsurface {
                         let default_theme = iced_core::Theme::Custom(default_theme().into());
                         themes.push(default_theme);
 
-                        if let Some(custom_theme) = &self.theme_editor.custom_theme {
-                            themes.push(custom_theme.clone());
-                        }
+                        themes.extend(self.theme_editor.themes());
 
                         pick_list(themes, Some(self.theme.0.clone()), |theme| {
                             Message::ThemeSelected(data::Theme(theme))
@@ -776,12 +1861,14 @@ impl Flowsurface {
                         ))),
                     );
 
-                    // لیست انتخاب منطقه زمانی
-                    let timezone_picklist = pick_list(
-                        [data::UserTimezone::Utc, data::UserTimezone::Local],
-                        Some(self.timezone),
+                    // لیست قابل جستجوی منطقه زمانی (نام IANA یا اختلاف ثابت با UTC)
+                    let timezone_picklist = combo_box(
+                        &self.timezone_options,
+                        "Search time zone...",
+                        Some(&self.timezone),
                         Message::SetTimezone,
-                    );
+                    )
+                    .menu_height(240);
 
                     // چک‌باکس نمایش حجم بر اساس ارز کوت (مثلاً USD)
                     let size_in_quote_currency_checkbox = {
@@ -812,12 +1899,147 @@ impl Flowsurface {
                         tooltip(
                             checkbox,
                             Some(
-                                "Display sizes/volumes in quote currency (USD)\nHas no effect on inverse perps or open interest",
+                                "Display sizes/volumes in quote currency (USD)\nApplies to inverse perps only when their conversion is enabled\nHas no effect on open interest",
                             ),
                             TooltipPosition::Top,
                         )
                     };
 
+                    // چک‌باکس تبدیل اندازه قراردادهای معکوس بر اساس واحد انتخابی
+                    let inverse_conversion_checkbox = {
+                        let is_enabled = exchange::is_inverse_conversion_enabled();
+
+                        let checkbox = iced::widget::checkbox(is_enabled)
+                            .label("Convert inverse perp sizes")
+                            .on_toggle(|checked| {
+                                let confirm_dialog = screen::ConfirmDialog::new(
+                                    "Changing inverse perp sizes requires application restart"
+                                        .to_string(),
+                                    Box::new(Message::ApplyInverseConversion(checked)),
+                                )
+                                .with_confirm_btn_text("Restart now".to_string());
+
+                                Message::ToggleDialogModal(Some(confirm_dialog))
+                            });
+
+                        tooltip(
+                            checkbox,
+                            Some(
+                                "Convert inverse perp contracts using their contract size and price\nQuote shows USD notional, base shows the coin amount\nWhen off, sizes keep each exchange's contract display",
+                            ),
+                            TooltipPosition::Top,
+                        )
+                    };
+
+                    // قالب‌بندی اعداد: اختصار حجم‌ها و جداکننده هزارگان
+                    let number_format_checkboxes = {
+                        let format = data::util::number_format();
+
+                        let abbreviate = iced::widget::checkbox(format.abbreviate)
+                            .label("Abbreviate large volumes")
+                            .on_toggle(move |abbreviate| {
+                                Message::SetNumberFormat(data::util::NumberFormat {
+                                    abbreviate,
+                                    ..format
+                                })
+                            });
+
+                        let separator = iced::widget::checkbox(format.thousands_separator)
+                            .label("Thousands separators")
+                            .on_toggle(move |thousands_separator| {
+                                Message::SetNumberFormat(data::util::NumberFormat {
+                                    thousands_separator,
+                                    ..format
+                                })
+                            });
+
+                        column![
+                            tooltip(
+                                abbreviate,
+                                Some("Show volumes as 1.23m / 4.50b instead of full numbers"),
+                                TooltipPosition::Top,
+                            ),
+                            tooltip(
+                                separator,
+                                Some("Group digits of prices and full volumes, e.g. 67,123.5"),
+                                TooltipPosition::Top,
+                            ),
+                        ]
+                        .spacing(8)
+                    };
+
+                    // شمارش معکوس بسته شدن کندل کنار برچسب آخرین قیمت
+                    let candle_countdown_checkbox = tooltip(
+                        iced::widget::checkbox(data::chart::is_candle_countdown_enabled())
+                            .label("Candle close countdown")
+                            .on_toggle(Message::SetCandleCountdown),
+                        Some("Show the time left until the current candle closes\nunder the last price label on time-based charts"),
+                        TooltipPosition::Top,
+                    );
+
+                    // فوکوس پنل با کلیک (پیش‌فرض) یا با عبور نشانگر ماوس
+                    let focus_follows_hover_checkbox = tooltip(
+                        iced::widget::checkbox(data::layout::dashboard::is_focus_follows_hover())
+                            .label("Focus follows hover")
+                            .on_toggle(Message::SetFocusFollowsHover),
+                        Some("Focus the pane under the cursor\nPanning or dragging keeps focus"),
+                        TooltipPosition::Top,
+                    );
+
+                    // اندازه و نوع قلم برچسب‌های محور و نشانگر نمودارها
+                    let label_font_controls = {
+                        let font = data::chart::label_font();
+                        let (min_size, max_size) = (
+                            *data::chart::LabelFont::SIZE_RANGE.start(),
+                            *data::chart::LabelFont::SIZE_RANGE.end(),
+                        );
+
+                        let decrease_btn = if font.size > min_size {
+                            button(text("-")).on_press(Message::SetLabelFont(
+                                data::chart::LabelFont {
+                                    size: font.size - 1.0,
+                                    ..font
+                                },
+                            ))
+                        } else {
+                            button(text("-"))
+                        };
+
+                        let increase_btn = if font.size < max_size {
+                            button(text("+")).on_press(Message::SetLabelFont(
+                                data::chart::LabelFont {
+                                    size: font.size + 1.0,
+                                    ..font
+                                },
+                            ))
+                        } else {
+                            button(text("+"))
+                        };
+
+                        column![
+                            container(
+                                row![
+                                    decrease_btn,
+                                    text(format!("{:.0}px", font.size)).size(14),
+                                    increase_btn,
+                                ]
+                                .align_y(Alignment::Center)
+                                .spacing(8)
+                                .padding(4),
+                            )
+                            .style(style::modal_container),
+                            iced::widget::checkbox(font.monospace)
+                                .label("Monospace font")
+                                .on_toggle(move |monospace| {
+                                    Message::SetLabelFont(data::chart::LabelFont {
+                                        monospace,
+                                        ..font
+                                    })
+                                }),
+                        ]
+                        .spacing(8)
+                    };
+
                     // انتخاب موقعیت سایدبار (چپ یا راست)
                     let sidebar_pos = pick_list(
                         [sidebar::Position::Left, sidebar::Position::Right],
@@ -827,6 +2049,23 @@ impl Flowsurface {
                         },
                     );
 
+                    // جمع شدن خودکار و ردیف‌های فشرده جدول نمادها
+                    let sidebar_toggles = column![
+                        iced::widget::checkbox(!self.sidebar.is_pinned())
+                            .label("Auto-hide when not hovered")
+                            .on_toggle(|auto_hide| {
+                                Message::Sidebar(dashboard::sidebar::Message::SetPinned(
+                                    !auto_hide,
+                                ))
+                            }),
+                        iced::widget::checkbox(self.sidebar.state.compact)
+                            .label("Compact ticker rows")
+                            .on_toggle(|compact| {
+                                Message::Sidebar(dashboard::sidebar::Message::SetCompact(compact))
+                            }),
+                    ]
+                    .spacing(8);
+
                     // تنظیم ضریب بزرگنمایی رابط کاربری
                     let scale_factor = {
                         let current_value: f32 = self.ui_scale_factor.into();
@@ -862,8 +2101,10 @@ impl Flowsurface {
                     let trade_fetch_checkbox = {
                         let is_active = exchange::fetcher::is_trade_fetch_enabled();
 
+                        let (label, details) = &*TRADE_FETCH_TEXT;
+
                         let checkbox = iced::widget::checkbox(is_active)
-                            .label("Fetch trades (Binance)")
+                            .label(label.as_str())
                             .on_toggle(|checked| {
                                 if checked {
                                     let confirm_dialog = screen::ConfirmDialog::new(
@@ -879,11 +2120,139 @@ impl Flowsurface {
 
                         tooltip(
                             checkbox,
-                            Some("Try to fetch trades for footprint charts"),
+                            Some(details.as_str()),
                             TooltipPosition::Top,
                         )
                     };
 
+                    // فاصله ذخیره خودکار وضعیت
+                    let autosave_picklist = pick_list(
+                        data::AutosaveInterval::ALL,
+                        Some(self.autosave.interval),
+                        Message::AutosaveIntervalChanged,
+                    );
+
+                    // پنجره زمانی نمایش سرعت نوار معاملات در پنل‌ها
+                    let tape_speed_picklist = tooltip(
+                        pick_list(
+                            data::tape_speed::TapeSpeedWindow::ALL,
+                            Some(data::tape_speed::tape_speed_window()),
+                            Message::SetTapeSpeedWindow,
+                        ),
+                        Some("Rolling window of the trades/s and volume/s readout on trade panes"),
+                        TooltipPosition::Top,
+                    );
+
+                    // مدت زمان بدون داده که پس از آن پنل کهنه نمایش داده می‌شود
+                    let stale_threshold_picklist = tooltip(
+                        pick_list(
+                            data::stale::StaleThreshold::ALL,
+                            Some(data::stale::stale_threshold()),
+                            Message::SetStaleThreshold,
+                        ),
+                        Some(
+                            "Dim panes whose feed has been silent this long\n\
+                            Auto scales with the pane's timeframe",
+                        ),
+                        TooltipPosition::Top,
+                    );
+
+                    // مصالحه بین کیفیت نمایش و نرخ فریم
+                    let performance_picklist = tooltip(
+                        pick_list(
+                            data::performance::PerformanceProfile::ALL,
+                            Some(data::performance::performance_profile()),
+                            Message::SetPerformanceProfile,
+                        ),
+                        Some(
+                            "Trade visual smoothness for frame rate on slower machines
+                            Balanced and Performance redraw less often and merge thin candles
+                            Performance also turns off anti-aliasing after a relaunch",
+                        ),
+                        TooltipPosition::Top,
+                    );
+
+                    // تأخیر دامنه‌های REST؛ کلیک روی یک دامنه آن را به صورت دستی انتخاب می‌کند
+                    let latency_controls = {
+                        let network = exchange::network::network_config();
+
+                        let probe_checkbox = tooltip(
+                            iced::widget::checkbox(network.latency_probe)
+                                .label("Pick fastest on startup")
+                                .on_toggle(Message::SetLatencyProbe),
+                            Some("Ping REST domains on launch\nand use the fastest one"),
+                            TooltipPosition::Top,
+                        );
+
+                        let run_button = if self.latency.running {
+                            button(text("Testing..."))
+                        } else {
+                            button(text("Test latency")).on_press(Message::RunLatencyProbe)
+                        };
+
+                        let reports = self.latency.reports.iter().fold(
+                            column![].spacing(8),
+                            |reports, report| {
+                                let endpoint = report.endpoint;
+                                let active = endpoint.resolve();
+                                let selectable = report.candidates.len() > 1;
+
+                                let mut header = row![text(endpoint.to_string()).size(13)]
+                                    .align_y(Alignment::Center);
+                                if network.endpoints.contains_key(&endpoint) {
+                                    header = header.push(space::horizontal()).push(
+                                        button(text("Auto").size(11))
+                                            .padding([1, 4])
+                                            .on_press(Message::SetEndpointOverride(endpoint, None)),
+                                    );
+                                }
+
+                                let candidates = report.candidates.iter().fold(
+                                    column![].spacing(2),
+                                    |rows, candidate| {
+                                        let latency = candidate.latency.map_or_else(
+                                            || "timeout".to_string(),
+                                            |latency| format!("{} ms", latency.as_millis()),
+                                        );
+                                        let host = candidate
+                                            .domain
+                                            .trim_start_matches("https://")
+                                            .trim_start_matches("http://");
+                                        let label = row![
+                                            text(host.to_string()).size(12),
+                                            space::horizontal(),
+                                            text(latency).size(12),
+                                        ];
+
+                                        if !selectable {
+                                            return rows.push(container(label).padding([2, 4]));
+                                        }
+
+                                        let is_active = candidate.domain == active;
+                                        rows.push(
+                                            button(label)
+                                                .width(Length::Fill)
+                                                .padding([2, 4])
+                                                .on_press(Message::SetEndpointOverride(
+                                                    endpoint,
+                                                    Some(candidate.domain.clone()),
+                                                ))
+                                                .style(move |theme, status| {
+                                                    style::button::transparent(
+                                                        theme, status, is_active,
+                                                    )
+                                                }),
+                                        )
+                                    },
+                                );
+
+                                reports.push(column![header, candidates].spacing(4))
+                            },
+                        );
+
+                        column![probe_checkbox, run_button, reports].spacing(8)
+                    };
+
                     // دکمه باز کردن پوشه داده‌ها
                     let open_data_folder = {
                         let button =
@@ -896,14 +2265,57 @@ impl Flowsurface {
                         )
                     };
 
+                    // بازنشانی همه تنظیمات و چیدمان‌ها به پیش‌فرض
+                    let reset_to_defaults = {
+                        let button = button(text("Reset to defaults")).on_press(
+                            Message::ToggleDialogModal(Some(
+                                screen::ConfirmDialog::new(
+                                    "Reset all settings and layouts to defaults?\n\
+                                    The current state file will be backed up first"
+                                        .to_string(),
+                                    Box::new(Message::ResetToDefaults),
+                                )
+                                .with_confirm_btn_text("Reset".to_string()),
+                            )),
+                        );
+
+                        tooltip(
+                            button,
+                            Some(
+                                "Start over with the default layout\nSet it from the layout manager",
+                            ),
+                            TooltipPosition::Top,
+                        )
+                    };
+
                     // چیدمان ستونی محتوای تنظیمات
                     let column_content = split_column![
-                        column![open_data_folder,].spacing(8),
-                        column![text("Sidebar position").size(14), sidebar_pos,].spacing(12),
+                        column![open_data_folder, reset_to_defaults,].spacing(8),
+                        column![text("Sidebar").size(14), sidebar_pos, sidebar_toggles,].spacing(12),
                         column![text("Time zone").size(14), timezone_picklist,].spacing(12),
-                        column![text("Market data").size(14), size_in_quote_currency_checkbox,].spacing(12),
+                        column![
+                            text("Market data").size(14),
+                            column![size_in_quote_currency_checkbox, inverse_conversion_checkbox,]
+                                .spacing(8),
+                        ]
+                        .spacing(12),
+                        column![text("Number format").size(14), number_format_checkboxes,]
+                            .spacing(12),
+                        column![text("Price axis").size(14), candle_countdown_checkbox,]
+                            .spacing(12),
+                        column![text("Pane focus").size(14), focus_follows_hover_checkbox,]
+                            .spacing(12),
+                        column![text("Chart labels").size(14), label_font_controls,]
+                            .spacing(12),
+                        column![text("Tape speed window").size(14), tape_speed_picklist,]
+                            .spacing(12),
+                        column![text("Stale data").size(14), stale_threshold_picklist,]
+                            .spacing(12),
                         column![text("Theme").size(14), theme_picklist,].spacing(12),
                         column![text("Interface scale").size(14), scale_factor,].spacing(12),
+                        column![text("Performance").size(14), performance_picklist,].spacing(12),
+                        column![text("Auto-save").size(14), autosave_picklist,].spacing(12),
+                        column![text("Network latency").size(14), latency_controls,].spacing(12),
                         column![
                             text("Experimental").size(14),
                             column![trade_fetch_checkbox, toggle_theme_editor,].spacing(8),
@@ -1090,6 +2502,91 @@ impl Flowsurface {
                     align_x,
                 )
             }
+            // منوی وضعیت اتصال جریان‌ها
+            sidebar::Menu::Connections => {
+                let (align_x, padding) = match sidebar_pos {
+                    sidebar::Position::Left => (Alignment::Start, padding::left(44).top(116)),
+                    sidebar::Position::Right => (Alignment::End, padding::right(44).top(116)),
+                };
+
+                let active_tickers = dashboard.streamed_tickers();
+
+                dashboard_modal(
+                    base,
+                    self.connections
+                        .view(active_tickers)
+                        .map(Message::Connections),
+                    Message::Sidebar(dashboard::sidebar::Message::ToggleSidebarMenu(None)),
+                    padding,
+                    Alignment::Start,
+                    align_x,
+                )
+            }
+            // منوی تاریخچه اندازه‌گیری‌های خط‌کش
+            sidebar::Menu::Measurements => {
+                let (align_x, padding) = match sidebar_pos {
+                    sidebar::Position::Left => (Alignment::Start, padding::left(44).top(156)),
+                    sidebar::Position::Right => (Alignment::End, padding::right(44).top(156)),
+                };
+
+                dashboard_modal(
+                    base,
+                    self.measurements
+                        .view(self.timezone)
+                        .map(Message::Measurements),
+                    Message::Sidebar(dashboard::sidebar::Message::ToggleSidebarMenu(None)),
+                    padding,
+                    Alignment::Start,
+                    align_x,
+                )
+            }
+            // منوی اسکنر بازار
+            sidebar::Menu::Scanner => {
+                let (align_x, padding) = match sidebar_pos {
+                    sidebar::Position::Left => (Alignment::Start, padding::left(44).top(196)),
+                    sidebar::Position::Right => (Alignment::End, padding::right(44).top(196)),
+                };
+
+                dashboard_modal(
+                    base,
+                    self.scanner.view().map(Message::Scanner),
+                    Message::Sidebar(dashboard::sidebar::Message::ToggleSidebarMenu(None)),
+                    padding,
+                    Alignment::Start,
+                    align_x,
+                )
+            }
+            // منوی ضبط و بازپخش جلسه
+            sidebar::Menu::Replay => {
+                let (align_x, padding) = match sidebar_pos {
+                    sidebar::Position::Left => (Alignment::Start, padding::left(44).top(236)),
+                    sidebar::Position::Right => (Alignment::End, padding::right(44).top(236)),
+                };
+
+                dashboard_modal(
+                    base,
+                    self.replay.view(self.timezone).map(Message::Replay),
+                    Message::Sidebar(dashboard::sidebar::Message::ToggleSidebarMenu(None)),
+                    padding,
+                    Alignment::Start,
+                    align_x,
+                )
+            }
+            // جستجوی فرمان‌ها در بالای صفحه
+            sidebar::Menu::CommandPalette => {
+                let entries = self.command_palette_entries();
+
+                dashboard_modal(
+                    base,
+                    self.command_palette
+                        .view(&entries)
+                        .map(Message::CommandPalette),
+                    Message::Sidebar(dashboard::sidebar::Message::ToggleSidebarMenu(None)),
+                    padding::top(80),
+                    Alignment::Start,
+                    Alignment::Center,
+                )
+            }
             // منوی ویرایشگر تم
             sidebar::Menu::ThemeEditor => {
                 let (align_x, padding) = match sidebar_pos {
@@ -1113,6 +2610,21 @@ impl Flowsurface {
 
     /// ذخیره وضعیت فعلی برنامه روی دیسک
     fn save_state_to_disk(&mut self, windows: &HashMap<window::Id, WindowSpec>) {
+        let Some(layout_str) = self.serialize_state(windows) else {
+            return;
+        };
+
+        // نوشتن وضعیت در فایل
+        let file_name = data::SAVED_STATE_PATH;
+        if let Err(e) = data::write_json_to_file(&layout_str, file_name) {
+            log::error!("Failed to write layout state to file: {}", e);
+        } else {
+            log::info!("Persisted state to {file_name}");
+        }
+    }
+
+    /// سریال‌سازی وضعیت فعلی برنامه به JSON برای ذخیره در فایل
+    fn serialize_state(&mut self, windows: &HashMap<window::Id, WindowSpec>) -> Option<String> {
         // به‌روزرسانی مشخصات پنجره‌های پاپ‌اوت
         self.active_dashboard_mut()
             .popout
@@ -1161,26 +2673,26 @@ impl Flowsurface {
             layouts,
             self.theme.clone(),
             self.theme_editor.custom_theme.clone().map(data::Theme),
+            self.theme_editor
+                .custom_themes
+                .iter()
+                .cloned()
+                .map(data::Theme)
+                .collect(),
             main_window_spec,
             self.timezone,
             self.sidebar.state.clone(),
             self.ui_scale_factor,
             audio_cfg,
             self.volume_size_unit,
+            self.scanner.settings(),
+            self.autosave.interval,
+            self.replay.settings(),
         );
 
-        // نوشتن وضعیت در فایل
-        match serde_json::to_string(&state) {
-            Ok(layout_str) => {
-                let file_name = data::SAVED_STATE_PATH;
-                if let Err(e) = data::write_json_to_file(&layout_str, file_name) {
-                    log::error!("Failed to write layout state to file: {}", e);
-                } else {
-                    log::info!("Persisted state to {file_name}");
-                }
-            }
-            Err(e) => log::error!("Failed to serialize layout: {}", e),
-        }
+        serde_json::to_string(&state)
+            .map_err(|e| log::error!("Failed to serialize layout: {}", e))
+            .ok()
     }
 
     /// راه‌اندازی مجدد برنامه (بستن تمام پنجره‌ها و ایجاد نمونه جدید)
@@ -1,7 +1,11 @@
 // ماژول‌های مربوط به انواع مختلف مودال‌ها (پنجره‌های شناور)
 pub mod audio;
+pub mod command_palette;
+pub mod connections;
 pub mod layout_manager;
+pub mod measurements;
 pub mod pane;
+pub mod replay;
 pub mod theme_editor;
 
 use iced::widget::{center, container, mouse_area, opaque, stack};
@@ -2,15 +2,18 @@ use crate::TooltipPosition;
 use crate::audio::{SoundCache, SoundType};
 use crate::style::{self, icon_text};
 use crate::widget::{labeled_slider, tooltip};
-use data::audio::StreamCfg;
+use data::audio::{
+    ImbalanceCfg, Pressure, SampleCfg, SizeBucket, SoundTheme, StreamCfg, TradeImbalance,
+};
 use exchange::adapter::{Exchange, StreamKind, StreamTicksize};
 
 use exchange::{PushFrequency, Trade};
-use iced::widget::{button, column, container, row, text};
+use iced::widget::{button, column, container, pick_list, row, text};
 use iced::widget::{checkbox, slider, space};
 use iced::{Element, padding};
 use rustc_hash::FxHashMap;
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
 const HARD_THRESHOLD: usize = 4;
 
@@ -20,12 +23,28 @@ pub enum Message {
     ToggleStream(bool, (Exchange, exchange::Ticker)),
     ToggleCard(Exchange, exchange::Ticker),
     SetThreshold(Exchange, exchange::Ticker, data::audio::Threshold),
+    ThemeChanged(SoundTheme),
+    PreviewSample(SampleCfg),
+    CooldownChanged(u64),
+    ToggleMuteOnFocusLoss(bool),
+    ToggleMute,
+    ImbalanceChanged(ImbalanceCfg),
 }
 
 pub struct AudioStream {
     cache: SoundCache,
     streams: HashMap<Exchange, HashMap<exchange::Ticker, StreamCfg>>,
     expanded_card: Option<(Exchange, exchange::Ticker)>,
+    theme: SoundTheme,
+    last_themed_sound: FxHashMap<exchange::Ticker, Instant>,
+    cooldown_ms: u64,
+    last_sound: FxHashMap<exchange::Ticker, Instant>,
+    mute_on_focus_loss: bool,
+    muted: bool,
+    window_focused: bool,
+    imbalance_cfg: ImbalanceCfg,
+    imbalance: FxHashMap<exchange::Ticker, TradeImbalance>,
+    changed: bool,
 }
 
 impl AudioStream {
@@ -47,13 +66,29 @@ impl AudioStream {
                 .expect("Failed to create sound cache"),
             streams,
             expanded_card: None,
+            theme: cfg.theme,
+            last_themed_sound: FxHashMap::default(),
+            cooldown_ms: cfg.cooldown_ms.min(data::AudioStream::MAX_COOLDOWN_MS),
+            last_sound: FxHashMap::default(),
+            mute_on_focus_loss: cfg.mute_on_focus_loss,
+            muted: cfg.muted,
+            window_focused: true,
+            imbalance_cfg: cfg.imbalance,
+            imbalance: FxHashMap::default(),
+            changed: false,
         }
     }
 
+    /// Whether the saved audio settings changed since the last call
+    pub fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+
     pub fn update(&mut self, message: Message) {
         match message {
             Message::SoundLevelChanged(value) => {
                 self.cache.set_volume(value);
+                self.changed = true;
             }
             Message::ToggleStream(is_checked, (exchange, ticker)) => {
                 if is_checked {
@@ -79,6 +114,7 @@ impl AudioStream {
                         .or_default()
                         .insert(ticker, StreamCfg::default());
                 }
+                self.changed = true;
             }
             Message::ToggleCard(exchange, ticker) => {
                 self.expanded_card = match self.expanded_card {
@@ -91,11 +127,51 @@ impl AudioStream {
                     && let Some(cfg) = streams.get_mut(&ticker)
                 {
                     cfg.threshold = threshold;
+                    self.changed = true;
+                }
+            }
+            Message::ThemeChanged(theme) => {
+                self.theme = theme;
+                self.changed = true;
+            }
+            Message::PreviewSample(sample) => {
+                if let Err(err) = self
+                    .cache
+                    .play_scaled(sample.sample.into(), sample.volume / 100.0)
+                {
+                    log::error!("Failed to preview sound: {err}");
                 }
             }
+            Message::CooldownChanged(ms) => {
+                self.cooldown_ms = ms.min(data::AudioStream::MAX_COOLDOWN_MS);
+                self.changed = true;
+            }
+            Message::ToggleMuteOnFocusLoss(enabled) => {
+                self.mute_on_focus_loss = enabled;
+                self.changed = true;
+            }
+            Message::ToggleMute => {
+                self.muted = !self.muted;
+                self.changed = true;
+            }
+            Message::ImbalanceChanged(cfg) => {
+                self.imbalance_cfg = cfg;
+                self.imbalance.clear();
+                self.changed = true;
+            }
         }
     }
 
+    /// Tracks whether any of the app's windows has focus, for muting on focus loss
+    pub fn set_window_focused(&mut self, focused: bool) {
+        self.window_focused = focused;
+    }
+
+    /// Master mute, the focus-loss option or a zero volume silence every alert
+    fn is_silenced(&self) -> bool {
+        self.muted || self.cache.is_muted() || (self.mute_on_focus_loss && !self.window_focused)
+    }
+
     pub fn view(
         &self,
         active_streams: Vec<(exchange::TickerInfo, StreamTicksize, PushFrequency)>,
@@ -114,9 +190,46 @@ impl AudioStream {
                 )
             };
 
-            column![text("Sound").size(14), volume_slider,].spacing(8)
+            let cooldown_slider = labeled_slider(
+                "Cooldown",
+                0.0..=data::AudioStream::MAX_COOLDOWN_MS as f32,
+                self.cooldown_ms as f32,
+                |value| Message::CooldownChanged(value as u64),
+                |value| {
+                    if *value == 0.0 {
+                        "Off".to_string()
+                    } else {
+                        format!("{value:.0}ms")
+                    }
+                },
+                Some(50.0),
+            );
+
+            let mute_checkbox = checkbox(self.muted)
+                .label("Mute all alerts (Ctrl+M)")
+                .on_toggle(|_| Message::ToggleMute);
+
+            let focus_checkbox = checkbox(self.mute_on_focus_loss)
+                .label("Mute when the app is in the background")
+                .on_toggle(Message::ToggleMuteOnFocusLoss);
+
+            column![
+                text("Sound").size(14),
+                volume_slider,
+                tooltip(
+                    cooldown_slider,
+                    Some("Minimum time between sounds of the same stream"),
+                    TooltipPosition::Top,
+                ),
+                mute_checkbox,
+                focus_checkbox,
+            ]
+            .spacing(8)
         };
 
+        let theme_container = self.theme_view();
+        let imbalance_container = self.imbalance_view();
+
         let audio_contents = {
             let mut available_streams = column![].spacing(4);
 
@@ -222,18 +335,249 @@ impl AudioStream {
             column![text("Audio streams").size(14), available_streams,].spacing(8)
         };
 
-        container(column![volume_container, audio_contents,].spacing(20))
-            .max_width(320)
-            .padding(24)
-            .style(style::dashboard_modal)
+        container(
+            column![
+                volume_container,
+                theme_container,
+                imbalance_container,
+                audio_contents,
+            ]
+            .spacing(20),
+        )
+        .max_width(360)
+        .padding(24)
+        .style(style::dashboard_modal)
+        .into()
+    }
+
+    fn theme_view(&self) -> Element<'_, Message> {
+        let theme = self.theme;
+
+        let toggle = checkbox(theme.enabled)
+            .label("Pick sounds by side and trade size")
+            .on_toggle(move |enabled| Message::ThemeChanged(SoundTheme { enabled, ..theme }));
+
+        if !theme.enabled {
+            return column![text("Sound theme").size(14), toggle]
+                .spacing(8)
+                .into();
+        }
+
+        let unit = match exchange::volume_size_unit() {
+            exchange::SizeUnit::Base => "base",
+            exchange::SizeUnit::Quote => "quote",
+        };
+
+        let thresholds = column![
+            labeled_slider(
+                "Medium",
+                100.0..=1_000_000.0,
+                theme.medium_size,
+                move |value| Message::ThemeChanged(SoundTheme {
+                    medium_size: value,
+                    large_size: theme.large_size.max(value),
+                    ..theme
+                }),
+                move |value| format!("≥ {} {unit}", data::util::abbr_large_numbers(*value)),
+                Some(100.0),
+            ),
+            labeled_slider(
+                "Large",
+                100.0..=1_000_000.0,
+                theme.large_size,
+                move |value| Message::ThemeChanged(SoundTheme {
+                    large_size: value,
+                    medium_size: theme.medium_size.min(value),
+                    ..theme
+                }),
+                move |value| format!("≥ {} {unit}", data::util::abbr_large_numbers(*value)),
+                Some(100.0),
+            ),
+            labeled_slider(
+                "Gap",
+                0.0..=1000.0,
+                theme.min_interval_ms as f32,
+                move |value| Message::ThemeChanged(SoundTheme {
+                    min_interval_ms: value as u64,
+                    ..theme
+                }),
+                |value| format!("{value:.0}ms"),
+                Some(10.0),
+            ),
+        ]
+        .spacing(4);
+
+        let mut samples = column![].spacing(4);
+
+        for is_sell in [false, true] {
+            for bucket in SizeBucket::ALL {
+                let cfg = theme.sample(is_sell, bucket);
+                let side = if is_sell { "Sell" } else { "Buy" };
+
+                let picker = pick_list(data::audio::Sample::ALL, Some(cfg.sample), move |sample| {
+                    Message::ThemeChanged(theme.with_sample(
+                        is_sell,
+                        bucket,
+                        SampleCfg { sample, ..cfg },
+                    ))
+                })
+                .text_size(12);
+
+                let volume = slider(0.0..=100.0, cfg.volume, move |volume| {
+                    Message::ThemeChanged(theme.with_sample(
+                        is_sell,
+                        bucket,
+                        SampleCfg { volume, ..cfg },
+                    ))
+                })
+                .width(60);
+
+                let preview = tooltip(
+                    button(icon_text(style::Icon::SpeakerHigh, 12))
+                        .on_press(Message::PreviewSample(cfg))
+                        .style(move |theme, status| {
+                            style::button::transparent(theme, status, false)
+                        }),
+                    Some("Preview"),
+                    TooltipPosition::Top,
+                );
+
+                samples = samples.push(
+                    row![
+                        text(format!("{side} {bucket}")).size(12).width(84),
+                        picker,
+                        volume,
+                        preview,
+                    ]
+                    .align_y(iced::Alignment::Center)
+                    .spacing(4),
+                );
+            }
+        }
+
+        column![text("Sound theme").size(14), toggle, thresholds, samples]
+            .spacing(8)
             .into()
     }
 
+    fn imbalance_view(&self) -> Element<'_, Message> {
+        let cfg = self.imbalance_cfg;
+
+        let toggle = checkbox(cfg.enabled)
+            .label("Sound on buy/sell pressure")
+            .on_toggle(move |enabled| Message::ImbalanceChanged(ImbalanceCfg { enabled, ..cfg }));
+
+        if !cfg.enabled {
+            return column![
+                text("Order flow pressure").size(14),
+                tooltip(
+                    toggle,
+                    Some("Plays a sound when one side dominates the volume of the last trades"),
+                    TooltipPosition::Top,
+                ),
+            ]
+            .spacing(8)
+            .into();
+        }
+
+        let sliders = column![
+            labeled_slider(
+                "Window",
+                ImbalanceCfg::WINDOW_RANGE,
+                cfg.window as f32,
+                move |value| Message::ImbalanceChanged(ImbalanceCfg {
+                    window: value as usize,
+                    ..cfg
+                }),
+                |value| format!("{value:.0} trades"),
+                Some(10.0),
+            ),
+            labeled_slider(
+                "Threshold",
+                ImbalanceCfg::THRESHOLD_RANGE,
+                cfg.threshold,
+                move |value| Message::ImbalanceChanged(ImbalanceCfg {
+                    threshold: value,
+                    ..cfg
+                }),
+                |value| format!("≥ {value:.0}% of volume"),
+                Some(1.0),
+            ),
+        ]
+        .spacing(4);
+
+        let mut samples = column![].spacing(4);
+
+        for pressure in [Pressure::Buy, Pressure::Sell] {
+            let sample_cfg = cfg.sample(pressure);
+            let with_sample = move |sample_cfg: SampleCfg| match pressure {
+                Pressure::Buy => ImbalanceCfg {
+                    buy: sample_cfg,
+                    ..cfg
+                },
+                Pressure::Sell => ImbalanceCfg {
+                    sell: sample_cfg,
+                    ..cfg
+                },
+            };
+            let label = match pressure {
+                Pressure::Buy => "Buy pressure",
+                Pressure::Sell => "Sell pressure",
+            };
+
+            let picker = pick_list(
+                data::audio::Sample::ALL,
+                Some(sample_cfg.sample),
+                move |sample| {
+                    Message::ImbalanceChanged(with_sample(SampleCfg {
+                        sample,
+                        ..sample_cfg
+                    }))
+                },
+            )
+            .text_size(12);
+
+            let volume = slider(0.0..=100.0, sample_cfg.volume, move |volume| {
+                Message::ImbalanceChanged(with_sample(SampleCfg {
+                    volume,
+                    ..sample_cfg
+                }))
+            })
+            .width(60);
+
+            let preview = tooltip(
+                button(icon_text(style::Icon::SpeakerHigh, 12))
+                    .on_press(Message::PreviewSample(sample_cfg))
+                    .style(move |theme, status| style::button::transparent(theme, status, false)),
+                Some("Preview"),
+                TooltipPosition::Top,
+            );
+
+            samples = samples.push(
+                row![text(label).size(12).width(84), picker, volume, preview,]
+                    .align_y(iced::Alignment::Center)
+                    .spacing(4),
+            );
+        }
+
+        column![
+            text("Order flow pressure").size(14),
+            toggle,
+            sliders,
+            samples
+        ]
+        .spacing(8)
+        .into()
+    }
+
     pub fn volume(&self) -> Option<f32> {
         self.cache.get_volume()
     }
 
     pub fn play(&mut self, sound: SoundType) -> Result<(), String> {
+        if self.is_silenced() {
+            return Ok(());
+        }
         self.cache.play(sound)
     }
 
@@ -249,7 +593,7 @@ impl AudioStream {
     }
 
     pub fn should_play_sound(&self, stream: &StreamKind) -> Option<StreamCfg> {
-        if self.cache.is_muted() {
+        if self.is_silenced() {
             return None;
         }
 
@@ -276,6 +620,21 @@ impl AudioStream {
             return Ok(());
         };
 
+        let StreamKind::DepthAndTrades { ticker_info, .. } = stream else {
+            return Ok(());
+        };
+
+        if let Some(pressure) = self.imbalance_crossed(ticker_info.ticker, trades_buffer) {
+            if !self.cooldown_elapsed(ticker_info.ticker) {
+                return Ok(());
+            }
+
+            let sample = self.imbalance_cfg.sample(pressure);
+            return self
+                .cache
+                .play_scaled(sample.sample.into(), sample.volume / 100.0);
+        }
+
         match cfg.threshold {
             data::audio::Threshold::Count(v) => {
                 let (buy_count, sell_count) =
@@ -291,6 +650,14 @@ impl AudioStream {
                     return Ok(());
                 }
 
+                if !self.cooldown_elapsed(ticker_info.ticker) {
+                    return Ok(());
+                }
+
+                if self.theme.enabled {
+                    return self.play_themed(ticker_info.ticker, trades_buffer);
+                }
+
                 let sound = |count: usize, is_sell: bool| {
                     if count > (v * HARD_THRESHOLD) {
                         if is_sell {
@@ -325,6 +692,74 @@ impl AudioStream {
 
         Ok(())
     }
+
+    /// Feeds the stream's rolling trade window, returning the side that just crossed
+    /// the configured share of volume
+    fn imbalance_crossed(
+        &mut self,
+        ticker: exchange::Ticker,
+        trades_buffer: &[Trade],
+    ) -> Option<Pressure> {
+        if !self.imbalance_cfg.enabled {
+            return None;
+        }
+
+        self.imbalance
+            .entry(ticker)
+            .or_default()
+            .push(&self.imbalance_cfg, trades_buffer)
+    }
+
+    /// Per-stream cooldown, so one busy symbol can't drown out alerts of the others
+    fn cooldown_elapsed(&mut self, ticker: exchange::Ticker) -> bool {
+        if self.cooldown_ms == 0 {
+            return true;
+        }
+
+        let now = Instant::now();
+        let cooldown = Duration::from_millis(self.cooldown_ms);
+
+        if self
+            .last_sound
+            .get(&ticker)
+            .is_some_and(|last| now.duration_since(*last) < cooldown)
+        {
+            return false;
+        }
+        self.last_sound.insert(ticker, now);
+        true
+    }
+
+    /// Plays the theme sample matching the side and size of the largest trade in the buffer,
+    /// skipping it if the same ticker played one within the configured interval
+    fn play_themed(
+        &mut self,
+        ticker: exchange::Ticker,
+        trades_buffer: &[Trade],
+    ) -> Result<(), String> {
+        let Some(largest) = trades_buffer.iter().max_by(|a, b| a.qty.total_cmp(&b.qty)) else {
+            return Ok(());
+        };
+
+        let now = Instant::now();
+        let min_interval = Duration::from_millis(self.theme.min_interval_ms);
+
+        if self
+            .last_themed_sound
+            .get(&ticker)
+            .is_some_and(|last| now.duration_since(*last) < min_interval)
+        {
+            return Ok(());
+        }
+        self.last_themed_sound.insert(ticker, now);
+
+        let sample = self
+            .theme
+            .sample(largest.is_sell, self.theme.bucket(largest.qty));
+
+        self.cache
+            .play_scaled(sample.sample.into(), sample.volume / 100.0)
+    }
 }
 
 impl From<&AudioStream> for data::AudioStream {
@@ -341,6 +776,11 @@ impl From<&AudioStream> for data::AudioStream {
         data::AudioStream {
             volume: audio_stream.cache.get_volume(),
             streams,
+            theme: audio_stream.theme,
+            cooldown_ms: audio_stream.cooldown_ms,
+            mute_on_focus_loss: audio_stream.mute_on_focus_loss,
+            muted: audio_stream.muted,
+            imbalance: audio_stream.imbalance_cfg,
         }
     }
 }
@@ -0,0 +1,225 @@
+use crate::style;
+
+use data::chart::indicator::UiIndicator;
+use data::{UserTimezone, sidebar};
+use exchange::TickerInfo;
+use exchange::util::Price;
+
+use iced::widget::{button, column, container, row, space, text, text_input};
+use iced::{Alignment, Element, Length, Task, Theme, window};
+use rustc_hash::FxHashMap;
+use std::time::Instant;
+
+const MAX_RESULTS: usize = 12;
+const INPUT_ID: &str = "command_palette_input";
+
+/// Something the palette can run; the app maps each to its existing message
+#[derive(Debug, Clone)]
+pub enum Command {
+    OpenMenu(sidebar::Menu),
+    SwitchLayout(uuid::Uuid),
+    SetTimezone(UserTimezone),
+    ToggleTradeFetch,
+    ToggleIndicator(window::Id, iced::widget::pane_grid::Pane, UiIndicator),
+    GoToTime(window::Id, iced::widget::pane_grid::Pane, u64),
+    GoToPrice(window::Id, iced::widget::pane_grid::Pane, Price),
+    SelectTicker(TickerInfo),
+    ReconnectAll,
+    ToggleMute,
+    Undo,
+    OpenDataFolder,
+}
+
+/// A searchable palette row; `dynamic` entries (tickers) only show up once something is typed
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub command: Command,
+    pub label: String,
+    pub category: &'static str,
+    pub dynamic: bool,
+}
+
+impl Entry {
+    pub fn new(category: &'static str, label: impl Into<String>, command: Command) -> Self {
+        Self {
+            command,
+            label: label.into(),
+            category,
+            dynamic: false,
+        }
+    }
+
+    pub fn dynamic(mut self) -> Self {
+        self.dynamic = true;
+        self
+    }
+
+    fn usage_key(&self) -> String {
+        format!("{}/{}", self.category, self.label)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    QueryChanged(String),
+    MoveSelection(isize),
+    Submit,
+    Invoke(usize),
+}
+
+pub enum Action {
+    Run(Command),
+}
+
+struct Usage {
+    count: u32,
+    last_used: Instant,
+}
+
+impl Usage {
+    /// Frequently and recently run commands float up, fading after about half an hour
+    fn bonus(&self, now: Instant) -> i32 {
+        let frequency = self.count.min(10) as i32 * 3;
+        let minutes = now.saturating_duration_since(self.last_used).as_secs() / 60;
+        let recency = 30_i32.saturating_sub(minutes.min(30) as i32);
+
+        frequency + recency
+    }
+}
+
+/// Fuzzy launcher over app commands, layouts and tickers, opened with Ctrl+K
+#[derive(Default)]
+pub struct CommandPalette {
+    query: String,
+    selected: usize,
+    usage: FxHashMap<String, Usage>,
+}
+
+impl CommandPalette {
+    /// Clears the last search and focuses the input
+    pub fn open<M: 'static>(&mut self) -> Task<M> {
+        self.query.clear();
+        self.selected = 0;
+
+        iced::widget::operation::focus(INPUT_ID)
+    }
+
+    /// The text typed so far, for entries built from the query itself (e.g. go to time)
+    pub fn query(&self) -> &str {
+        self.query.trim()
+    }
+
+    pub fn update(&mut self, message: Message, entries: &[Entry]) -> Option<Action> {
+        match message {
+            Message::QueryChanged(query) => {
+                self.query = query;
+                self.selected = 0;
+            }
+            Message::MoveSelection(delta) => {
+                let len = self.ranked(entries).len();
+                if len > 0 {
+                    self.selected = self.selected.saturating_add_signed(delta).min(len - 1);
+                }
+            }
+            Message::Submit => return self.invoke(self.selected, entries),
+            Message::Invoke(idx) => return self.invoke(idx, entries),
+        }
+
+        None
+    }
+
+    fn invoke(&mut self, idx: usize, entries: &[Entry]) -> Option<Action> {
+        let entry = &entries[*self.ranked(entries).get(idx)?];
+
+        let usage = self.usage.entry(entry.usage_key()).or_insert(Usage {
+            count: 0,
+            last_used: Instant::now(),
+        });
+        usage.count += 1;
+        usage.last_used = Instant::now();
+
+        Some(Action::Run(entry.command.clone()))
+    }
+
+    /// Indices of the best matching entries, by fuzzy score plus a boost for commands run
+    /// often or lately; an empty query lists the static commands by that boost alone
+    fn ranked(&self, entries: &[Entry]) -> Vec<usize> {
+        let now = Instant::now();
+        let query = self.query.trim();
+
+        let mut scored = entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| !(query.is_empty() && entry.dynamic))
+            .filter_map(|(idx, entry)| {
+                let score = data::util::fuzzy_score(query, &entry.label)?;
+                let bonus = self
+                    .usage
+                    .get(&entry.usage_key())
+                    .map_or(0, |usage| usage.bonus(now));
+
+                Some((idx, score + bonus))
+            })
+            .collect::<Vec<_>>();
+
+        scored.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        scored.truncate(MAX_RESULTS);
+        scored.into_iter().map(|(idx, _)| idx).collect()
+    }
+
+    pub fn view(&self, entries: &[Entry]) -> Element<'_, Message> {
+        let input = text_input("Type a command, layout or ticker...", &self.query)
+            .id(INPUT_ID)
+            .on_input(Message::QueryChanged)
+            .on_submit(Message::Submit)
+            .style(|theme, status| style::validated_text_input(theme, status, true))
+            .padding(6);
+
+        let ranked = self.ranked(entries);
+        let mut results = column![].spacing(2);
+
+        if ranked.is_empty() {
+            results = results.push(text("No matches").size(11));
+        }
+
+        for (pos, &idx) in ranked.iter().enumerate() {
+            let entry = &entries[idx];
+            let is_selected = pos == self.selected;
+
+            let content = row![
+                text(entry.label.clone()),
+                space::horizontal(),
+                text(entry.category)
+                    .size(11)
+                    .style(|theme: &Theme| text::Style {
+                        color: Some(theme.extended_palette().background.strong.color),
+                    }),
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center);
+
+            results = results.push(
+                button(content)
+                    .width(Length::Fill)
+                    .padding(6)
+                    .on_press(Message::Invoke(pos))
+                    .style(move |theme, status| {
+                        style::button::transparent(theme, status, is_selected)
+                    }),
+            );
+        }
+
+        container(
+            column![
+                input,
+                results,
+                text("↑↓ to select, Enter to run, Esc to close").size(11),
+            ]
+            .spacing(8),
+        )
+        .width(420)
+        .padding(16)
+        .style(style::dashboard_modal)
+        .into()
+    }
+}
@@ -0,0 +1,319 @@
+use crate::style;
+use crate::widget::tooltip;
+use exchange::adapter::Exchange;
+use exchange::{Ticker, TickerInfo};
+
+use iced::widget::{
+    button, column, container, row, space, text, tooltip::Position as TooltipPosition,
+};
+use iced::{Alignment, Color, Element, Theme};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::time::{Duration, Instant};
+
+/// A connected stream that stays quiet for longer than this is shown as stalled
+const STALE_AFTER: Duration = Duration::from_secs(10);
+/// Minimum time between two "reconnect all" refreshes, so repeated presses don't
+/// hammer the venues with new connections and depth snapshot requests
+const RECONNECT_ALL_COOLDOWN: Duration = Duration::from_secs(5);
+/// Message rate is sampled over windows of this length
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+/// Weight of the newest sample in the rolling averages
+const SMOOTHING: f32 = 0.2;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Message {
+    Reconnect(Ticker),
+    ReconnectAll,
+}
+
+pub enum Action {
+    ReconnectAll,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Health {
+    Live,
+    Stalled,
+    Down,
+}
+
+impl Health {
+    fn color(self, theme: &Theme) -> Color {
+        let palette = theme.extended_palette();
+        match self {
+            Health::Live => palette.success.base.color,
+            Health::Stalled => palette.warning.base.color,
+            Health::Down => palette.danger.base.color,
+        }
+    }
+}
+
+/// Rolling message rate and exchange-to-client latency of a stream
+#[derive(Debug, Default)]
+struct StreamMetrics {
+    window_start: Option<Instant>,
+    window_count: u32,
+    /// Smoothed messages per second, available after the first full window
+    rate: Option<f32>,
+    /// Smoothed local receive time minus the payload's exchange timestamp, in ms.
+    /// Kept signed, clock skew between the venue and this machine can make it negative
+    latency_ms: Option<f32>,
+}
+
+impl StreamMetrics {
+    fn record(&mut self, now: Instant, latency_ms: Option<i64>) {
+        let window_start = *self.window_start.get_or_insert(now);
+        self.window_count += 1;
+
+        let elapsed = now.saturating_duration_since(window_start);
+        if elapsed >= RATE_WINDOW {
+            let sample = self.window_count as f32 / elapsed.as_secs_f32();
+            self.rate = Some(smooth(self.rate, sample));
+            self.window_start = Some(now);
+            self.window_count = 0;
+        }
+
+        if let Some(latency) = latency_ms {
+            self.latency_ms = Some(smooth(self.latency_ms, latency as f32));
+        }
+    }
+
+    fn summary(&self) -> Option<String> {
+        let rate = self.rate.map(|rate| format!("{rate:.1} msg/s"));
+
+        let latency = self.latency_ms.map(|latency| {
+            if latency < 0.0 {
+                "~0ms (clock skew)".to_string()
+            } else {
+                format!("~{latency:.0}ms")
+            }
+        });
+
+        match (rate, latency) {
+            (Some(rate), Some(latency)) => Some(format!("{rate} · {latency}")),
+            (Some(value), None) | (None, Some(value)) => Some(value),
+            (None, None) => None,
+        }
+    }
+}
+
+fn smooth(prev: Option<f32>, sample: f32) -> f32 {
+    match prev {
+        Some(prev) => prev + SMOOTHING * (sample - prev),
+        None => sample,
+    }
+}
+
+#[derive(Debug, Default)]
+struct StreamStatus {
+    connected: bool,
+    last_message: Option<Instant>,
+    last_disconnect: Option<(Instant, String)>,
+    metrics: StreamMetrics,
+}
+
+impl StreamStatus {
+    fn health(&self, now: Instant) -> Health {
+        if !self.connected {
+            return Health::Down;
+        }
+        match self.last_message {
+            Some(t) if now.saturating_duration_since(t) <= STALE_AFTER => Health::Live,
+            _ => Health::Stalled,
+        }
+    }
+}
+
+/// Tracks the connection state of every ticker with an active stream.
+///
+/// `Connected`/`Disconnected` events only name the exchange, so they are applied to all
+/// tracked tickers of that exchange; a ticker that keeps receiving messages is considered
+/// connected again right away.
+#[derive(Default)]
+pub struct Connections {
+    streams: FxHashMap<Ticker, StreamStatus>,
+    reconnects: FxHashMap<Ticker, u32>,
+    last_reconnect_all: Option<Instant>,
+}
+
+impl Connections {
+    pub fn update(&mut self, message: Message) -> Option<Action> {
+        match message {
+            Message::Reconnect(ticker) => {
+                log::info!("forcing reconnect for {ticker}");
+                self.force_reconnect(ticker);
+                None
+            }
+            Message::ReconnectAll => Some(Action::ReconnectAll),
+        }
+    }
+
+    /// Restarts the streams of every given ticker, which also re-fetches their depth snapshots.
+    ///
+    /// Returns the number of restarted tickers, or the remaining cooldown when the last
+    /// refresh was too recent.
+    pub fn reconnect_all(
+        &mut self,
+        tickers: impl IntoIterator<Item = Ticker>,
+        now: Instant,
+    ) -> Result<usize, Duration> {
+        if let Some(last) = self.last_reconnect_all {
+            let elapsed = now.saturating_duration_since(last);
+            if elapsed < RECONNECT_ALL_COOLDOWN {
+                return Err(RECONNECT_ALL_COOLDOWN - elapsed);
+            }
+        }
+        self.last_reconnect_all = Some(now);
+
+        let tickers = tickers.into_iter().collect::<FxHashSet<_>>();
+
+        log::info!(
+            "forcing reconnect for all {} streamed tickers",
+            tickers.len()
+        );
+
+        for ticker in &tickers {
+            self.force_reconnect(*ticker);
+        }
+        Ok(tickers.len())
+    }
+
+    fn force_reconnect(&mut self, ticker: Ticker) {
+        *self.reconnects.entry(ticker).or_default() += 1;
+
+        let status = self.streams.entry(ticker).or_default();
+        status.connected = false;
+        status.last_message = None;
+        status.metrics = StreamMetrics::default();
+    }
+
+    pub fn connected(&mut self, exchange: Exchange) {
+        self.streams
+            .iter_mut()
+            .filter(|(ticker, _)| ticker.exchange == exchange)
+            .for_each(|(_, status)| status.connected = true);
+    }
+
+    pub fn disconnected(&mut self, exchange: Exchange, reason: &str, now: Instant) {
+        self.streams
+            .iter_mut()
+            .filter(|(ticker, _)| ticker.exchange == exchange)
+            .for_each(|(_, status)| {
+                status.connected = false;
+                status.last_disconnect = Some((now, reason.to_string()));
+            });
+    }
+
+    /// Records a message of the ticker's stream; `exchange_time` is the payload's venue
+    /// timestamp in ms, when it carries one, compared against the local clock for latency
+    pub fn received(&mut self, ticker: Ticker, now: Instant, exchange_time: Option<u64>) {
+        let latency_ms = exchange_time
+            .filter(|t| *t > 0)
+            .map(|t| chrono::Utc::now().timestamp_millis() - t as i64);
+
+        let status = self.streams.entry(ticker).or_default();
+        status.connected = true;
+        status.last_message = Some(now);
+        status.metrics.record(now, latency_ms);
+    }
+
+    /// Bumped on every forced reconnect, used as part of the stream subscription identity
+    pub fn reconnects(&self) -> &FxHashMap<Ticker, u32> {
+        &self.reconnects
+    }
+
+    pub fn view(&self, mut active_tickers: Vec<TickerInfo>) -> Element<'_, Message> {
+        let now = Instant::now();
+
+        active_tickers.sort_by_key(|info| (info.exchange().to_string(), info.ticker.to_string()));
+        active_tickers.dedup_by_key(|info| info.ticker);
+
+        let mut rows = column![].spacing(4);
+
+        if active_tickers.is_empty() {
+            rows = rows.push(text("No active streams"));
+        }
+
+        for info in active_tickers {
+            let ticker = info.ticker;
+            let status = self.streams.get(&ticker);
+            let health = status.map_or(Health::Down, |s| s.health(now));
+
+            let last_message = match status.and_then(|s| s.last_message) {
+                Some(t) => format!("{}s ago", now.saturating_duration_since(t).as_secs()),
+                None => "no data yet".to_string(),
+            };
+
+            let dot = text("●").size(12).style(move |theme: &Theme| text::Style {
+                color: Some(health.color(theme)),
+            });
+
+            let mut info_col = column![
+                row![
+                    dot,
+                    text(format!("{} - {ticker}", info.exchange())),
+                    space::horizontal(),
+                    text(last_message).size(11),
+                ]
+                .spacing(6)
+                .align_y(Alignment::Center),
+            ]
+            .spacing(2);
+
+            if health != Health::Down
+                && let Some(summary) = status.and_then(|s| s.metrics.summary())
+            {
+                info_col = info_col.push(text(summary).size(11));
+            }
+
+            if let Some((at, reason)) = status.and_then(|s| s.last_disconnect.as_ref()) {
+                info_col = info_col.push(
+                    text(format!(
+                        "Disconnected {}s ago: {reason}",
+                        now.saturating_duration_since(*at).as_secs()
+                    ))
+                    .size(11)
+                    .style(|theme: &Theme| text::Style {
+                        color: Some(theme.extended_palette().background.strong.color),
+                    }),
+                );
+            }
+
+            let can_reconnect = health != Health::Live;
+
+            let stream_btn = button(info_col)
+                .width(iced::Length::Fill)
+                .padding(6)
+                .on_press_maybe(can_reconnect.then_some(Message::Reconnect(ticker)))
+                .style(move |theme, status| style::button::transparent(theme, status, false));
+
+            rows = rows.push(container(stream_btn).style(style::modal_container));
+        }
+
+        let reconnect_all_btn = button(text("Reconnect all").size(12))
+            .on_press(Message::ReconnectAll)
+            .style(|theme, status| style::button::transparent(theme, status, false));
+
+        container(
+            column![
+                row![
+                    text("Connections").size(14),
+                    space::horizontal(),
+                    tooltip(
+                        reconnect_all_btn,
+                        Some("Restart every stream and re-fetch order books (Ctrl+R)"),
+                        TooltipPosition::Left,
+                    ),
+                ]
+                .align_y(Alignment::Center),
+                text("Click a stalled or disconnected stream to reconnect").size(11),
+                rows,
+            ]
+            .spacing(8),
+        )
+        .width(320)
+        .padding(24)
+        .style(style::dashboard_modal)
+        .into()
+    }
+}
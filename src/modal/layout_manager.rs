@@ -10,6 +10,7 @@ use iced::widget::{
     tooltip::Position as TooltipPosition,
 };
 use iced::{Element, Theme, padding};
+use std::path::PathBuf;
 use std::vec;
 use uuid::Uuid;
 
@@ -17,6 +18,7 @@ use uuid::Uuid;
 pub enum Editing {
     ConfirmingDelete(Uuid),
     Renaming(Uuid, String),
+    Importing(Vec<PathBuf>),
     Preview,
     None,
 }
@@ -30,42 +32,36 @@ pub enum Message {
     RemoveLayout(Uuid),
     ToggleEditMode(Editing),
     CloneLayout(Uuid),
+    ExportLayout(Uuid),
+    SetAsDefault(Uuid),
+    ShowImports,
+    ImportLayout(PathBuf),
     Reorder(DragEvent),
 }
 
 pub enum Action {
     Select(Uuid),
     Clone(Uuid),
+    Export(Uuid),
+    SetAsDefault(Uuid),
+    Import(PathBuf),
 }
 
 pub struct LayoutManager {
     pub layouts: Vec<Layout>,
     active_layout_id: Option<Uuid>,
     pub edit_mode: Editing,
+    /// Layouts were added, removed, renamed, reordered or switched since the last check
+    changed: bool,
 }
 
 impl LayoutManager {
-    pub fn new() -> Self {
-        let default_layout = LayoutId {
-            unique: Uuid::new_v4(),
-            name: "Layout 1".into(),
-        };
-
-        Self {
-            layouts: vec![Layout {
-                id: default_layout.clone(),
-                dashboard: Dashboard::default(),
-            }],
-            active_layout_id: Some(default_layout.unique),
-            edit_mode: Editing::None,
-        }
-    }
-
     pub fn from_config(layouts: Vec<Layout>, active_layout: Option<LayoutId>) -> Self {
         Self {
             layouts,
             active_layout_id: active_layout.map(|l| l.unique),
             edit_mode: Editing::None,
+            changed: false,
         }
     }
 
@@ -87,6 +83,18 @@ impl LayoutManager {
 
     pub fn insert_layout(&mut self, id: LayoutId, dashboard: Dashboard) {
         self.layouts.push(Layout { id, dashboard });
+        self.changed = true;
+    }
+
+    /// Whether the layout list or any of its dashboards changed since the last call
+    pub fn take_changed(&mut self, main_window: iced::window::Id) -> bool {
+        let dashboards = self
+            .iter_dashboards_mut()
+            .fold(false, |changed, dashboard| {
+                dashboard.take_changed(main_window) | changed
+            });
+
+        std::mem::take(&mut self.changed) | dashboards
     }
 
     pub fn generate_unique_layout_name(&self) -> String {
@@ -126,6 +134,10 @@ impl LayoutManager {
         self.get_mut(id).map(|e| &mut e.dashboard)
     }
 
+    pub fn active_dashboard_mut(&mut self) -> Option<&mut Dashboard> {
+        self.mut_dashboard(self.active_layout_id?)
+    }
+
     pub fn set_active_layout(&mut self, layout_id: Uuid) -> Result<&mut Layout, String> {
         self.active_layout_id = Some(layout_id);
 
@@ -137,6 +149,7 @@ impl LayoutManager {
         match message {
             Message::SelectActive(id) => {
                 self.active_layout_id = Some(id);
+                self.changed = true;
                 return Some(Action::Select(id));
             }
             Message::ToggleEditMode(new_mode) => match (&new_mode, &self.edit_mode) {
@@ -168,12 +181,14 @@ impl LayoutManager {
                 }
                 self.layouts.retain(|layout| layout.id.unique != id);
                 self.edit_mode = Editing::Preview;
+                self.changed = true;
             }
             Message::SetLayoutName(id, new_name) => {
                 let unique_name = self.ensure_unique_name(&new_name, id);
 
                 if let Some(layout) = self.get_mut(id) {
                     layout.id.name = unique_name;
+                    self.changed = true;
                 }
 
                 self.edit_mode = Editing::Preview;
@@ -190,7 +205,26 @@ impl LayoutManager {
             Message::CloneLayout(id) => {
                 return Some(Action::Clone(id));
             }
-            Message::Reorder(event) => column_drag::reorder_vec(&mut self.layouts, &event),
+            Message::ExportLayout(id) => {
+                return Some(Action::Export(id));
+            }
+            Message::SetAsDefault(id) => {
+                return Some(Action::SetAsDefault(id));
+            }
+            Message::ShowImports => {
+                self.edit_mode = match self.edit_mode {
+                    Editing::Importing(_) => Editing::Preview,
+                    _ => Editing::Importing(data::layout::exported_layouts()),
+                };
+            }
+            Message::ImportLayout(path) => {
+                self.edit_mode = Editing::Preview;
+                return Some(Action::Import(path));
+            }
+            Message::Reorder(event) => {
+                column_drag::reorder_vec(&mut self.layouts, &event);
+                self.changed |= matches!(event, DragEvent::Dropped { .. });
+            }
         }
 
         None
@@ -261,9 +295,11 @@ impl LayoutManager {
                         layout_row = layout_row.push(create_layout_button(layout_id, None));
                     }
                 }
-                Editing::Preview => {
+                Editing::Preview | Editing::Importing(_) => {
                     layout_row = layout_row
                         .push(create_layout_button(layout_id, None))
+                        .push(create_default_button(layout_id))
+                        .push(create_export_button(layout_id))
                         .push(create_clone_button(layout_id))
                         .push(create_rename_button(layout_id));
 
@@ -327,6 +363,17 @@ impl LayoutManager {
                     .width(iced::Length::Fill)
                     .on_press(Message::AddLayout),
             );
+
+            content = content.push(
+                button(text("Import layout"))
+                    .style(move |t, s| style::button::transparent(t, s, true))
+                    .width(iced::Length::Fill)
+                    .on_press(Message::ShowImports),
+            );
+
+            if let Editing::Importing(paths) = &self.edit_mode {
+                content = content.push(imports_list(paths));
+            }
         };
 
         scrollable::Scrollable::with_direction(
@@ -376,6 +423,64 @@ fn create_clone_button<'a>(layout: &LayoutId) -> Element<'a, Message> {
     )
 }
 
+fn create_export_button<'a>(layout: &LayoutId) -> Element<'a, Message> {
+    tooltip(
+        create_icon_button(
+            style::Icon::ExternalLink,
+            12,
+            |theme, status| style::button::layout_name(theme, *status),
+            Some(Message::ExportLayout(layout.unique)),
+        ),
+        Some("Export layout"),
+        TooltipPosition::Top,
+    )
+}
+
+fn create_default_button<'a>(layout: &LayoutId) -> Element<'a, Message> {
+    tooltip(
+        create_icon_button(
+            style::Icon::Star,
+            12,
+            |theme, status| style::button::layout_name(theme, *status),
+            Some(Message::SetAsDefault(layout.unique)),
+        ),
+        Some("Use as default layout on fresh start or reset"),
+        TooltipPosition::Top,
+    )
+}
+
+fn imports_list<'a>(paths: &[PathBuf]) -> Element<'a, Message> {
+    if paths.is_empty() {
+        return container(
+            text(format!(
+                "No layout files found in the \"{}\" data folder",
+                data::layout::LAYOUT_EXPORT_DIR
+            ))
+            .size(12),
+        )
+        .padding(4)
+        .into();
+    }
+
+    let mut list = column![].spacing(2);
+
+    for path in paths {
+        let name = path.file_stem().map_or_else(
+            || path.display().to_string(),
+            |s| s.to_string_lossy().into(),
+        );
+
+        list = list.push(
+            button(text(name).size(12))
+                .style(style::button::layout_name)
+                .width(iced::Length::Fill)
+                .on_press(Message::ImportLayout(path.clone())),
+        );
+    }
+
+    list.into()
+}
+
 fn create_confirm_delete_buttons<'a>(
     layout: &LayoutId,
 ) -> (button::Button<'a, Message>, button::Button<'a, Message>) {
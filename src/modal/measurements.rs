@@ -0,0 +1,189 @@
+use crate::chart::{RulerMeasurement, RulerSpan};
+use crate::style;
+use data::UserTimezone;
+use exchange::Ticker;
+
+use chrono::{DateTime, Utc};
+use iced::widget::{button, column, container, row, scrollable, space, text};
+use iced::{Alignment, Element, Theme};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+
+/// Oldest entries are dropped once the log grows past this
+const MAX_ENTRIES: usize = 200;
+
+const EXPORT_DIR: &str = "measurements";
+
+#[derive(Debug, Clone, Copy)]
+pub enum Message {
+    Clear,
+    Export,
+}
+
+pub enum Action {
+    Exported(Result<PathBuf, String>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub measured_at: DateTime<Utc>,
+    pub ticker: Ticker,
+    pub ruler: RulerMeasurement,
+}
+
+impl Entry {
+    pub fn new(ticker: Ticker, ruler: RulerMeasurement) -> Self {
+        Self {
+            measured_at: Utc::now(),
+            ticker,
+            ruler,
+        }
+    }
+}
+
+/// In-memory log of completed ruler measurements, newest first
+#[derive(Default)]
+pub struct Measurements {
+    entries: VecDeque<Entry>,
+}
+
+impl Measurements {
+    pub fn push(&mut self, entry: Entry) {
+        self.entries.push_front(entry);
+        self.entries.truncate(MAX_ENTRIES);
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Action> {
+        match message {
+            Message::Clear => {
+                self.entries.clear();
+                None
+            }
+            Message::Export => Some(Action::Exported(self.export())),
+        }
+    }
+
+    /// Writes the log as CSV into the data folder, oldest entry first
+    fn export(&self) -> Result<PathBuf, String> {
+        let mut csv = String::from(
+            "measured_at,exchange,symbol,timeframe,start_price,end_price,pct_change,span,bars\n",
+        );
+
+        for entry in self.entries.iter().rev() {
+            let span = match entry.ruler.span {
+                RulerSpan::Duration(ms) => format!("{}s", ms / 1000),
+                RulerSpan::Ticks(ticks) => format!("{ticks}t"),
+            };
+
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{:.4},{},{}\n",
+                entry.measured_at.to_rfc3339(),
+                entry.ticker.exchange,
+                entry.ticker,
+                entry.ruler.basis,
+                entry.ruler.start_price,
+                entry.ruler.end_price,
+                entry.ruler.pct_change,
+                span,
+                entry.ruler.bars,
+            ));
+        }
+
+        let file_name = format!(
+            "{EXPORT_DIR}/ruler_{}.csv",
+            Utc::now().format("%Y%m%d_%H%M%S")
+        );
+        let path = data::data_path(Some(&file_name));
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        std::fs::write(&path, csv).map_err(|e| e.to_string())?;
+
+        Ok(path)
+    }
+
+    pub fn view(&self, timezone: UserTimezone) -> Element<'_, Message> {
+        let mut rows = column![].spacing(4);
+
+        if self.entries.is_empty() {
+            rows = rows.push(text("No measurements yet").size(12));
+        }
+
+        for entry in &self.entries {
+            let measured_at = timezone
+                .to_fixed_offset(&entry.measured_at)
+                .format("%b %-d %H:%M:%S")
+                .to_string();
+
+            let pct = entry.ruler.pct_change;
+
+            let info = column![
+                row![
+                    text(format!("{} · {}", entry.ticker, entry.ruler.basis)).size(12),
+                    space::horizontal(),
+                    text(measured_at).size(11),
+                ]
+                .align_y(Alignment::Center),
+                row![
+                    text(format!(
+                        "{} → {}",
+                        entry.ruler.start_price, entry.ruler.end_price
+                    ))
+                    .size(11),
+                    space::horizontal(),
+                    text(format!("{pct:+.2}%"))
+                        .size(11)
+                        .style(move |theme: &Theme| {
+                            let palette = theme.extended_palette();
+                            text::Style {
+                                color: Some(if pct >= 0.0 {
+                                    palette.success.base.color
+                                } else {
+                                    palette.danger.base.color
+                                }),
+                            }
+                        }),
+                ]
+                .spacing(6),
+                text(format!("{} bars, {}", entry.ruler.bars, entry.ruler.span))
+                    .size(11)
+                    .style(|theme: &Theme| text::Style {
+                        color: Some(theme.extended_palette().background.strong.color),
+                    }),
+            ]
+            .spacing(2);
+
+            rows = rows.push(
+                container(info)
+                    .width(iced::Length::Fill)
+                    .padding(6)
+                    .style(style::modal_container),
+            );
+        }
+
+        let has_entries = !self.entries.is_empty();
+
+        let controls = row![
+            button(text("Export CSV").size(12))
+                .on_press_maybe(has_entries.then_some(Message::Export)),
+            button(text("Clear").size(12)).on_press_maybe(has_entries.then_some(Message::Clear)),
+        ]
+        .spacing(6);
+
+        container(
+            column![
+                text("Ruler measurements").size(14),
+                text("Hold Shift and drag on a chart to measure").size(11),
+                scrollable(rows).height(iced::Length::Shrink),
+                controls,
+            ]
+            .spacing(8),
+        )
+        .width(320)
+        .max_height(480)
+        .padding(24)
+        .style(style::dashboard_modal)
+        .into()
+    }
+}
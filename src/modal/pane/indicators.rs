@@ -1,10 +1,13 @@
 use crate::screen::dashboard::pane::{self, Message};
 use crate::style::{self, Icon, icon_text};
-use crate::widget::{column_drag, dragger_row};
+use crate::widget::color_picker::color_picker;
+use crate::widget::{column_drag, dragger_row, labeled_slider, tooltip};
 
-use data::chart::indicator::{Indicator, UiIndicator};
+use data::chart::indicator::{Indicator, IndicatorStyle, KlineIndicator, UiIndicator};
+use data::chart::kline;
+use data::layout::pane::VisualConfig;
 use iced::{
-    Element, Length, padding,
+    Alignment, Element, Length, padding,
     widget::{button, column, container, pane_grid, row, space, text},
 };
 
@@ -13,6 +16,7 @@ pub fn view<'a, I>(
     state: &'a pane::State,
     selected: &[I],
     market_type: Option<exchange::adapter::MarketKind>,
+    colors: Option<Element<'a, Message>>,
 ) -> Element<'a, Message>
 where
     I: Indicator + Copy + Into<UiIndicator>,
@@ -24,7 +28,7 @@ where
         column![].spacing(4).into()
     };
 
-    container(content_row)
+    container(column![content_row].push(colors).spacing(12))
         .max_width(200)
         .padding(16)
         .style(style::chart_modal)
@@ -35,19 +39,29 @@ fn build_indicator_row<'a, I>(
     pane: pane_grid::Pane,
     indicator: &I,
     is_selected: bool,
+    is_available: bool,
 ) -> Element<'a, Message>
 where
     I: Indicator + Copy + Into<UiIndicator>,
 {
+    // kept in the list so its position survives, but it has no panel on this market
+    let label = if is_available {
+        text(indicator.to_string())
+    } else {
+        text(format!("{indicator} (n/a)")).style(|theme: &iced::Theme| text::Style {
+            color: Some(theme.extended_palette().background.strong.color),
+        })
+    };
+
     let content = if is_selected {
         row![
-            text(indicator.to_string()),
+            label,
             space::horizontal(),
             container(icon_text(Icon::Checkmark, 12)),
         ]
         .width(Length::Fill)
     } else {
-        row![text(indicator.to_string())].width(Length::Fill)
+        row![label].width(Length::Fill)
     };
 
     button(content)
@@ -63,15 +77,18 @@ where
 fn selected_list<'a, I>(
     pane: pane_grid::Pane,
     selected: &[I],
+    market: exchange::adapter::MarketKind,
     reorderable: bool,
 ) -> Element<'a, Message>
 where
     I: Indicator + Copy + Into<UiIndicator>,
 {
+    let available = I::for_market(market);
+
     let elements: Vec<Element<_>> = selected
         .iter()
         .map(|indicator| {
-            let base = build_indicator_row(pane, indicator, true);
+            let base = build_indicator_row(pane, indicator, true, available.contains(indicator));
             dragger_row(base, reorderable)
         })
         .collect();
@@ -98,7 +115,7 @@ where
     let elements: Vec<Element<_>> = available
         .iter()
         .map(|indicator| {
-            let base = build_indicator_row(pane, indicator, false);
+            let base = build_indicator_row(pane, indicator, false, true);
             dragger_row(base, false)
         })
         .collect();
@@ -120,7 +137,7 @@ where
     let reorderable = allows_drag && selected.len() >= 2;
 
     let selected_list = if !selected.is_empty() {
-        Some(selected_list(pane, selected, reorderable))
+        Some(selected_list(pane, selected, market, reorderable))
     } else {
         None
     };
@@ -144,10 +161,112 @@ where
         col = col.push(avail);
     }
 
+    let save_as_default = tooltip(
+        button(text("Save as default").size(12))
+            .on_press(Message::PaneEvent(
+                pane,
+                pane::Event::SaveIndicatorsAsDefault,
+            ))
+            .width(Length::Fill)
+            .style(|theme, status| style::button::transparent(theme, status, false)),
+        Some("New panes of this chart type start with these indicators"),
+        iced::widget::tooltip::Position::Top,
+    );
+
     column![
         container(text("Indicators").size(14)).padding(padding::bottom(8)),
-        col.spacing(4)
+        col.spacing(4),
+        container(save_as_default).padding(padding::top(8)),
     ]
     .spacing(4)
     .into()
 }
+
+/// Color and opacity overrides of the enabled kline indicators; without one they follow the theme
+pub fn kline_colors_view<'a>(
+    pane: pane_grid::Pane,
+    selected: &[KlineIndicator],
+    cfg: kline::Config,
+    editing: Option<KlineIndicator>,
+) -> Element<'a, Message> {
+    if selected.is_empty() {
+        return column![].into();
+    }
+
+    let set_style = move |indicator: KlineIndicator, style: Option<IndicatorStyle>| {
+        let cfg = kline::Config {
+            indicator_colors: cfg.indicator_colors.with(indicator, style),
+            ..cfg
+        };
+        Message::VisualConfigChanged(pane, VisualConfig::Kline(cfg), false)
+    };
+
+    let mut col = column![text("Colors").size(14)].spacing(4);
+
+    for &indicator in selected {
+        let custom = cfg.indicator_colors.get(indicator);
+        let is_open = editing == Some(indicator);
+
+        let swatch = container("")
+            .width(14)
+            .height(14)
+            .style(move |theme: &iced::Theme| {
+                let color = custom
+                    .map_or(theme.extended_palette().secondary.strong.color, |style| {
+                        style.color()
+                    });
+                style::colored_circle_container(theme, color)
+            });
+
+        let header = button(
+            row![swatch, text(indicator.to_string()).size(13)]
+                .spacing(8)
+                .align_y(Alignment::Center),
+        )
+        .on_press(Message::PaneEvent(
+            pane,
+            pane::Event::IndicatorColorEditing((!is_open).then_some(indicator)),
+        ))
+        .style(move |theme, status| style::button::transparent(theme, status, is_open))
+        .width(Length::Fill);
+
+        let reset = custom.is_some().then(|| {
+            tooltip(
+                button(icon_text(Icon::Return, 11))
+                    .on_press(set_style(indicator, None))
+                    .style(|theme, status| style::button::transparent(theme, status, false)),
+                Some("Reset to theme colors"),
+                iced::widget::tooltip::Position::Top,
+            )
+        });
+
+        col = col.push(row![header].push(reset).align_y(Alignment::Center));
+
+        if is_open {
+            let style = custom.unwrap_or_default();
+
+            col = col.push(color_picker(
+                data::config::theme::to_hsva(style.color),
+                move |hsva| {
+                    set_style(
+                        indicator,
+                        Some(IndicatorStyle {
+                            color: data::config::theme::from_hsva(hsva),
+                            ..style
+                        }),
+                    )
+                },
+            ));
+            col = col.push(labeled_slider(
+                "Opacity",
+                IndicatorStyle::OPACITY_RANGE,
+                style.opacity,
+                move |opacity| set_style(indicator, Some(IndicatorStyle { opacity, ..style })),
+                |value| format!("{:.0}%", value * 100.0),
+                Some(0.05),
+            ));
+        }
+    }
+
+    col.into()
+}
@@ -1,21 +1,36 @@
-use crate::chart::comparison::ComparisonChart;
+use crate::chart::{self, comparison::ComparisonChart};
 use crate::screen::dashboard::pane::{Event, Message};
 use crate::screen::dashboard::panel::timeandsales;
 use crate::split_column;
+use crate::widget::color_picker::color_picker;
 use crate::widget::{classic_slider_row, labeled_slider};
 use crate::{style, tooltip, widget::scrollable_content};
 
+use data::aggr::PrintAggregation;
+use data::book_spread::BookSpreadConfig;
 use data::chart::heatmap::HeatmapStudy;
 use data::chart::kline::FootprintStudy;
 use data::chart::{
-    KlineChartKind,
-    heatmap::{self, CoalesceKind},
-    kline::ClusterKind,
+    AutoscalePadding, KlineChartKind, MAX_PRICE_DECIMALS, PriceAnchor, PriceLines, PriceScale,
+    PriceSnap, TradeRetention, ViewConfig,
+    heatmap::{self, CellVolumeConfig, CoalesceKind},
+    kline::{
+        self, CandleColors, ClusterKind, DeltaColoring, HigherTimeframe, HigherTimeframeStyle,
+        OpenInterestDisplay, TradeFetchConfig, VolumeOpacity,
+    },
+    session::SessionLevelsConfig,
+    swings::SwingConfig,
 };
 use data::layout::pane::VisualConfig;
-use data::panel::ladder;
-use data::panel::timeandsales::{StackedBar, StackedBarRatio};
-use data::util::format_with_commas;
+use data::orderflow::OrderFlowConfig;
+use data::panel::timeandsales::{SizeScaling, StackedBar, StackedBarRatio};
+use data::panel::{depth_curve, ladder, spread};
+use data::util::{abbr_large_numbers, format_bytes, format_with_commas};
+use data::depth_totals::DepthTotalsConfig;
+use exchange::adapter::{Exchange, KlinePriceSource};
+use data::walls::WallConfig;
+use exchange::fetcher::TradeSource;
+use exchange::{PushFrequency, TickMultiplier, Timeframe};
 
 use iced::widget::{checkbox, space};
 use iced::{
@@ -45,6 +60,13 @@ pub fn heatmap_cfg_view<'a>(
     study_config: &'a study::Configurator<HeatmapStudy>,
     studies: &'a [HeatmapStudy],
     basis: data::chart::Basis,
+    memory_usage: (usize, usize),
+    price_decimals: Option<u8>,
+    price_lines: PriceLines,
+    invert_price: bool,
+    watermark: bool,
+    exchange: Option<Exchange>,
+    depth_push_freq: Option<Timeframe>,
 ) -> Element<'a, Message> {
     let trade_size_slider = {
         let filter = cfg.trade_size_filter;
@@ -228,6 +250,19 @@ pub fn heatmap_cfg_view<'a>(
     };
 
     let trade_viz_column = {
+        let show_checkbox = checkbox(cfg.show_trades)
+            .label("Show trade prints")
+            .on_toggle(move |value| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Heatmap(heatmap::Config {
+                        show_trades: value,
+                        ..cfg
+                    }),
+                    false,
+                )
+            });
+
         let dyn_checkbox = checkbox(cfg.trade_size_scale.is_some())
             .label("Dynamic circle radius")
             .on_toggle(move |value| {
@@ -241,13 +276,185 @@ pub fn heatmap_cfg_view<'a>(
                 )
             });
 
-        let mut col = column![text("Trade visualization").size(14), dyn_checkbox].spacing(8);
-        if let Some(slider) = circle_scaling_slider {
-            col = col.push(slider);
+        let mut col = column![text("Trade visualization").size(14), show_checkbox].spacing(8);
+        if cfg.show_trades {
+            col = col.push(dyn_checkbox);
+            if let Some(slider) = circle_scaling_slider {
+                col = col.push(slider);
+            }
         }
         col
     };
 
+    let grid_column = {
+        let stream_interval = match basis {
+            data::chart::Basis::Time(interval) => Some(interval),
+            data::chart::Basis::Tick(_) => None,
+        };
+
+        // picking the stream's own resolution resets the bucket back to it
+        let time_bucket = stream_interval.map(|stream_interval| {
+            let options = Timeframe::HEATMAP
+                .into_iter()
+                .filter(|tf| u64::from(*tf) >= u64::from(stream_interval))
+                .collect::<Vec<_>>();
+
+            row![
+                text("Time bucket"),
+                space::horizontal(),
+                pick_list(
+                    options,
+                    Some(cfg.time_bucket.unwrap_or(stream_interval)),
+                    move |tf| {
+                        Message::VisualConfigChanged(
+                            pane,
+                            VisualConfig::Heatmap(heatmap::Config {
+                                time_bucket: (tf != stream_interval).then_some(tf),
+                                ..cfg
+                            }),
+                            false,
+                        )
+                    },
+                ),
+            ]
+            .align_y(Alignment::Center)
+        });
+
+        let price_bucket = row![
+            text("Price bucket"),
+            space::horizontal(),
+            pick_list(
+                &TickMultiplier::ALL[..6],
+                Some(cfg.price_bucket.unwrap_or(TickMultiplier(1))),
+                move |multiplier| {
+                    Message::VisualConfigChanged(
+                        pane,
+                        VisualConfig::Heatmap(heatmap::Config {
+                            price_bucket: (multiplier.0 > 1).then_some(multiplier),
+                            ..cfg
+                        }),
+                        false,
+                    )
+                },
+            ),
+        ]
+        .align_y(Alignment::Center);
+
+        let levels_checkbox = checkbox(cfg.render_levels.is_some())
+            .label("Limit rendered levels")
+            .on_toggle(move |value| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Heatmap(heatmap::Config {
+                        render_levels: value.then_some(heatmap::DEFAULT_RENDER_LEVELS),
+                        ..cfg
+                    }),
+                    false,
+                )
+            });
+
+        let levels_slider = cfg.render_levels.map(|levels| {
+            labeled_slider(
+                "Levels per side",
+                heatmap::RENDER_LEVELS_RANGE,
+                levels,
+                move |value| {
+                    Message::VisualConfigChanged(
+                        pane,
+                        VisualConfig::Heatmap(heatmap::Config {
+                            render_levels: Some(value),
+                            ..cfg
+                        }),
+                        false,
+                    )
+                },
+                u16::to_string,
+                Some(10),
+            )
+        });
+
+        column![text("Grid").size(14)]
+            .push(time_bucket)
+            .push(price_bucket)
+            .push(text("Cells sum resting size across the book levels they cover").size(12))
+            .push(levels_checkbox)
+            .push(levels_slider)
+            .spacing(8)
+    };
+
+    let history_column = {
+        let (snapshots, bytes) = memory_usage;
+
+        let cap_slider = labeled_slider(
+            "Snapshots",
+            heatmap::MIN_SNAPSHOTS as f32..=heatmap::MAX_SNAPSHOTS as f32,
+            cfg.max_snapshots as f32,
+            move |value| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Heatmap(heatmap::Config {
+                        max_snapshots: value as usize,
+                        ..cfg
+                    }),
+                    false,
+                )
+            },
+            |value| format!("≤ {}", abbr_large_numbers(*value)),
+            Some(heatmap::MIN_SNAPSHOTS as f32),
+        );
+
+        let memory_slider = labeled_slider(
+            "Memory",
+            heatmap::MIN_MEMORY_MB as f32..=heatmap::MAX_MEMORY_MB as f32,
+            cfg.max_memory_mb as f32,
+            move |value| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Heatmap(heatmap::Config {
+                        max_memory_mb: value as usize,
+                        ..cfg
+                    }),
+                    false,
+                )
+            },
+            |value| format!("≤ {}", format_bytes(*value as usize * 1024 * 1024)),
+            Some(heatmap::MIN_MEMORY_MB as f32),
+        );
+
+        let window = row![
+            text("Time window"),
+            space::horizontal(),
+            pick_list(
+                heatmap::HistoryWindow::ALL,
+                Some(cfg.history_window),
+                move |history_window| {
+                    Message::VisualConfigChanged(
+                        pane,
+                        VisualConfig::Heatmap(heatmap::Config {
+                            history_window,
+                            ..cfg
+                        }),
+                        false,
+                    )
+                },
+            ),
+        ]
+        .align_y(Alignment::Center);
+
+        column![
+            text("History").size(14),
+            window,
+            cap_slider,
+            memory_slider,
+            text(format!(
+                "Holding {snapshots} snapshots ≈ {}",
+                format_bytes(bytes)
+            ))
+            .size(12),
+        ]
+        .spacing(8)
+    };
+
     let study_cfg = study_config.view(studies, basis).map(move |msg| {
         Message::PaneEvent(
             pane,
@@ -259,6 +466,50 @@ pub fn heatmap_cfg_view<'a>(
         size_filters_column,
         noise_filters_column,
         trade_viz_column,
+        grid_column,
+        history_column,
+        walls_view(cfg.walls, move |walls| {
+            Message::VisualConfigChanged(
+                pane,
+                VisualConfig::Heatmap(heatmap::Config { walls, ..cfg }),
+                false,
+            )
+        }),
+        cell_volume_view(cfg.cell_volume, move |cell_volume| {
+            Message::VisualConfigChanged(
+                pane,
+                VisualConfig::Heatmap(heatmap::Config {
+                    cell_volume,
+                    ..cfg
+                }),
+                false,
+            )
+        }),
+        depth_totals_view(cfg.depth_totals, move |depth_totals| {
+            Message::VisualConfigChanged(
+                pane,
+                VisualConfig::Heatmap(heatmap::Config {
+                    depth_totals,
+                    ..cfg
+                }),
+                false,
+            )
+        }),
+        spread_history_view(cfg.spread_history, move |spread_history| {
+            Message::VisualConfigChanged(
+                pane,
+                VisualConfig::Heatmap(heatmap::Config {
+                    spread_history,
+                    ..cfg
+                }),
+                false,
+            )
+        }),
+        price_decimals_view(pane, price_decimals),
+        axis_direction_view(pane, None, invert_price),
+        price_lines_view(pane, price_lines, false),
+        watermark_view(pane, watermark),
+        push_freq_view(pane, exchange, depth_push_freq),
         column![text("Studies").size(14), study_cfg].spacing(8),
         row![
             space::horizontal(),
@@ -297,6 +548,65 @@ pub fn timesales_cfg_view<'a>(
         column![text("Size filter").size(14), slider].spacing(8)
     };
 
+    let size_display_column = {
+        let scaling_picklist = pick_list(SizeScaling::ALL, Some(cfg.size_scaling), move |value| {
+            Message::VisualConfigChanged(
+                pane,
+                VisualConfig::TimeAndSales(timeandsales::Config {
+                    size_scaling: value,
+                    ..cfg
+                }),
+                false,
+            )
+        });
+
+        let block_checkbox = checkbox(cfg.block_trade_filter.is_some())
+            .label("Highlight block trades")
+            .on_toggle(move |value| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::TimeAndSales(timeandsales::Config {
+                        block_trade_filter: value.then_some(100_000.0),
+                        ..cfg
+                    }),
+                    false,
+                )
+            });
+
+        let mut column = column![
+            text("Size display").size(14),
+            scaling_picklist,
+            tooltip(
+                block_checkbox,
+                Some("Trades above the threshold are filled solid and flash when printed"),
+                TooltipPosition::Top,
+            ),
+        ]
+        .spacing(8);
+
+        if let Some(threshold) = cfg.block_trade_filter {
+            column = column.push(labeled_slider(
+                "Block",
+                5000.0..=2_000_000.0,
+                threshold,
+                move |value| {
+                    Message::VisualConfigChanged(
+                        pane,
+                        VisualConfig::TimeAndSales(timeandsales::Config {
+                            block_trade_filter: Some(value),
+                            ..cfg
+                        }),
+                        false,
+                    )
+                },
+                |value| format!(">${}", format_with_commas(*value)),
+                Some(5000.0),
+            ));
+        }
+
+        column
+    };
+
     let retention_minutes = (cfg.trade_retention.as_secs_f32() / 60.0).max(1.0);
     let retention_slider = {
         let slider_ui = slider(1.0..=60.0, retention_minutes, move |new_minutes| {
@@ -428,8 +738,21 @@ pub fn timesales_cfg_view<'a>(
             .into()
     };
 
+    let print_aggregation = print_aggregation_view(cfg.print_aggregation, move |value| {
+        Message::VisualConfigChanged(
+            pane,
+            VisualConfig::TimeAndSales(timeandsales::Config {
+                print_aggregation: value,
+                ..cfg
+            }),
+            false,
+        )
+    });
+
     let content = split_column![
         trade_size_column,
+        size_display_column,
+        print_aggregation,
         history_column,
         stacked_bar,
         row![space::horizontal(), sync_all_button(pane, VisualConfig::TimeAndSales(cfg))],
@@ -456,95 +779,1660 @@ pub fn comparison_cfg_view<'a>(
     cfg_view_container(320, content)
 }
 
-pub fn kline_cfg_view<'a>(
-    study_config: &'a study::Configurator<FootprintStudy>,
-    cfg: data::chart::kline::Config,
-    kind: &'a KlineChartKind,
-    pane: pane_grid::Pane,
-    basis: data::chart::Basis,
-) -> Element<'a, Message> {
-    let content = match kind {
-        KlineChartKind::Candles => column![text(
-            "This chart type doesn't have any configurations, WIP..."
-        )],
-        KlineChartKind::Footprint {
-            clusters,
-            scaling,
-            studies,
-        } => {
-            let cluster_picklist =
-                pick_list(ClusterKind::ALL, Some(clusters), move |new_cluster_kind| {
-                    Message::PaneEvent(pane, Event::ClusterKindSelected(new_cluster_kind))
-                });
+/// Which of the custom candle colors is open in the color picker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CandleColorTarget {
+    UpBody,
+    DownBody,
+    UpWick,
+    DownWick,
+}
 
-            let scaling = {
-                let picklist = pick_list(
-                    data::chart::kline::ClusterScaling::ALL,
-                    Some(scaling),
-                    move |new_scaling| {
-                        Message::PaneEvent(pane, Event::ClusterScalingSelected(new_scaling))
-                    },
-                );
+impl CandleColorTarget {
+    const ALL: [CandleColorTarget; 4] = [
+        CandleColorTarget::UpBody,
+        CandleColorTarget::UpWick,
+        CandleColorTarget::DownBody,
+        CandleColorTarget::DownWick,
+    ];
 
-                if let data::chart::kline::ClusterScaling::Hybrid { weight } = scaling {
-                    let hybrid_slider = slider(0.0..=1.0, *weight, move |new_weight| {
-                        Message::PaneEvent(
-                            pane,
-                            Event::ClusterScalingSelected(
-                                data::chart::kline::ClusterScaling::Hybrid { weight: new_weight },
-                            ),
-                        )
-                    })
-                    .step(0.05);
+    fn label(self) -> &'static str {
+        match self {
+            CandleColorTarget::UpBody => "Up body",
+            CandleColorTarget::DownBody => "Down body",
+            CandleColorTarget::UpWick => "Up wick",
+            CandleColorTarget::DownWick => "Down wick",
+        }
+    }
 
-                    column![
-                        picklist,
-                        hybrid_slider,
-                        text("Blend visible-range and per-candle scaling"),
-                    ]
-                    .spacing(8)
-                } else {
-                    column![picklist].spacing(8)
-                }
-            };
+    fn get(self, colors: &CandleColors) -> iced::Color {
+        match self {
+            CandleColorTarget::UpBody => colors.up_body,
+            CandleColorTarget::DownBody => colors.down_body,
+            CandleColorTarget::UpWick => colors.up_wick,
+            CandleColorTarget::DownWick => colors.down_wick,
+        }
+    }
 
-            let study_cfg = study_config.view(studies, basis).map(move |msg| {
-                Message::PaneEvent(
-                    pane,
-                    Event::StudyConfigurator(study::StudyMessage::Footprint(msg)),
-                )
-            });
+    fn with(self, colors: CandleColors, color: iced::Color) -> CandleColors {
+        match self {
+            CandleColorTarget::UpBody => CandleColors {
+                up_body: color,
+                ..colors
+            },
+            CandleColorTarget::DownBody => CandleColors {
+                down_body: color,
+                ..colors
+            },
+            CandleColorTarget::UpWick => CandleColors {
+                up_wick: color,
+                ..colors
+            },
+            CandleColorTarget::DownWick => CandleColors {
+                down_wick: color,
+                ..colors
+            },
+        }
+    }
+}
 
-            split_column![
-                column![text("Cluster type").size(14), cluster_picklist].spacing(8),
-                column![text("Cluster scaling").size(14), scaling].spacing(8),
-                column![text("Studies").size(14), study_cfg].spacing(8),
-                row![
-                    space::horizontal(),
-                    sync_all_button(pane, VisualConfig::Kline(cfg))
-                ],
-                ; spacing = 12, align_x = Alignment::Start
-            ]
+/// Price precision shown on the axis and crosshair, either derived from the tick size or fixed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PriceDecimals {
+    Auto,
+    Fixed(u8),
+}
+
+impl std::fmt::Display for PriceDecimals {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PriceDecimals::Auto => write!(f, "Auto"),
+            PriceDecimals::Fixed(decimals) => write!(f, "{decimals}"),
         }
-    };
+    }
+}
 
-    cfg_view_container(360, content)
+/// Depth stream push rate, either following the heatmap timeframe or fixed by the user
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PushFreqChoice {
+    Auto,
+    Fixed(Timeframe),
 }
 
-pub fn ladder_cfg_view<'a>(cfg: ladder::Config, pane: pane_grid::Pane) -> Element<'a, Message> {
-    let display_options = {
-        let spread = checkbox(cfg.show_spread)
-            .label("Show Spread")
-            .on_toggle(move |value| {
-                Message::VisualConfigChanged(
-                    pane,
-                    VisualConfig::Ladder(ladder::Config {
-                        show_spread: value,
-                        ..cfg
-                    }),
-                    false,
-                )
-            });
+impl std::fmt::Display for PushFreqChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PushFreqChoice::Auto => write!(f, "Auto"),
+            PushFreqChoice::Fixed(timeframe) => write!(f, "{timeframe}"),
+        }
+    }
+}
+
+fn push_freq_view<'a>(
+    pane: pane_grid::Pane,
+    exchange: Option<Exchange>,
+    current: Option<Timeframe>,
+) -> Element<'a, Message> {
+    let header = |hint: &'static str| {
+        row![
+            text("Depth push frequency").size(14),
+            tooltip(
+                button("i").style(style::button::info),
+                Some(hint),
+                TooltipPosition::Top,
+            ),
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center)
+    };
+
+    let Some(exchange) = exchange.filter(Exchange::is_custom_push_freq) else {
+        let hint = if exchange.is_some() {
+            "This exchange pushes depth updates at a fixed rate"
+        } else {
+            "Select a ticker first"
+        };
+        return column![header(hint), button(text("Server default").size(12))]
+            .spacing(8)
+            .into();
+    };
+
+    let options = std::iter::once(PushFreqChoice::Auto)
+        .chain(Timeframe::HEATMAP.map(PushFreqChoice::Fixed))
+        .collect::<Vec<_>>();
+
+    let selected = current.map_or(PushFreqChoice::Auto, PushFreqChoice::Fixed);
+
+    let picker = pick_list(options, Some(selected), move |choice| {
+        let push_freq = match choice {
+            PushFreqChoice::Auto => None,
+            PushFreqChoice::Fixed(timeframe) => Some(timeframe),
+        };
+        Message::PaneEvent(pane, Event::DepthPushFreqSelected(push_freq))
+    });
+
+    let mut content = column![
+        header("Faster updates cost more bandwidth and CPU; Auto keeps the pane's default"),
+        picker,
+    ]
+    .spacing(8);
+
+    if let Some(timeframe) = current
+        && let PushFrequency::Custom(actual) = exchange.nearest_push_freq(timeframe)
+        && actual != timeframe
+    {
+        content = content.push(text(format!("{exchange} streams every {actual}")).size(11));
+    }
+
+    content.into()
+}
+
+fn price_scale_view<'a>(pane: pane_grid::Pane, current: PriceScale) -> Element<'a, Message> {
+    let picker = pick_list(PriceScale::ALL, Some(current), move |price_scale| {
+        Message::PaneEvent(
+            pane,
+            Event::ChartInteraction(chart::Message::PriceScaleChanged(price_scale)),
+        )
+    });
+
+    column![
+        row![
+            text("Price scale").size(14),
+            tooltip(
+                button("i").style(style::button::info),
+                Some("Logarithmic spaces equal percentage moves equally"),
+                TooltipPosition::Top,
+            ),
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center),
+        picker,
+    ]
+    .spacing(8)
+    .into()
+}
+
+/// Axis direction toggles; `reverse_time` is `None` for charts that can't run right-to-left
+fn axis_direction_view<'a>(
+    pane: pane_grid::Pane,
+    reverse_time: Option<bool>,
+    invert_price: bool,
+) -> Element<'a, Message> {
+    let on_change =
+        move |message: chart::Message| Message::PaneEvent(pane, Event::ChartInteraction(message));
+
+    let invert = checkbox(invert_price)
+        .label("Invert price axis")
+        .on_toggle(move |invert| on_change(chart::Message::InvertPriceToggled(invert)));
+
+    column![
+        row![
+            text("Axis direction").size(14),
+            tooltip(
+                button("i").style(style::button::info),
+                Some("Inverting puts higher prices at the bottom, flipping bids and asks"),
+                TooltipPosition::Top,
+            ),
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center),
+        invert,
+    ]
+    .push(reverse_time.map(|reverse_time| {
+        checkbox(reverse_time)
+            .label("Right-to-left time")
+            .on_toggle(move |reverse| on_change(chart::Message::ReverseTimeToggled(reverse)))
+    }))
+    .spacing(8)
+    .into()
+}
+
+fn watermark_view<'a>(pane: pane_grid::Pane, watermark: bool) -> Element<'a, Message> {
+    checkbox(watermark)
+        .label("Show symbol watermark")
+        .on_toggle(move |watermark| {
+            Message::PaneEvent(
+                pane,
+                Event::ChartInteraction(chart::Message::WatermarkToggled(watermark)),
+            )
+        })
+        .into()
+}
+
+fn autoscale_padding_view<'a>(
+    pane: pane_grid::Pane,
+    padding: AutoscalePadding,
+) -> Element<'a, Message> {
+    let header = row![
+        text("Autoscale padding").size(14),
+        tooltip(
+            button("i").style(style::button::info),
+            Some("Empty space kept above and below the candles when the chart autoscales"),
+            TooltipPosition::Top,
+        ),
+    ]
+    .align_y(Alignment::Center)
+    .spacing(4);
+
+    let slider = labeled_slider(
+        "Padding",
+        AutoscalePadding::RANGE,
+        padding.0,
+        move |value| {
+            let padding = AutoscalePadding(value);
+            Message::PaneEvent(
+                pane,
+                Event::ChartInteraction(chart::Message::AutoscalePaddingChanged(padding)),
+            )
+        },
+        |value| format!("{value:.0}%"),
+        Some(1.0),
+    );
+
+    column![header, slider].spacing(8).into()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PriceSnapChoice(PriceSnap);
+
+impl std::fmt::Display for PriceSnapChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            PriceSnap::Tick => write!(f, "Chart tick"),
+            PriceSnap::Ticks(multiple) => write!(f, "{multiple} ticks"),
+            PriceSnap::Increment(step) => write!(f, "Every {step}"),
+        }
+    }
+}
+
+/// Price levels that click-placed drawings snap to
+fn price_snap_view<'a>(
+    pane: pane_grid::Pane,
+    current: PriceSnap,
+    tick_size: f32,
+) -> Element<'a, Message> {
+    let mut options = std::iter::once(PriceSnap::Tick)
+        .chain(PriceSnap::TICK_MULTIPLES.map(PriceSnap::Ticks))
+        .chain(
+            PriceSnap::increments(tick_size)
+                .into_iter()
+                .map(PriceSnap::Increment),
+        )
+        .map(PriceSnapChoice)
+        .collect::<Vec<_>>();
+    // a saved increment stays selectable after switching to a ticker with another tick size
+    if !options.contains(&PriceSnapChoice(current)) {
+        options.push(PriceSnapChoice(current));
+    }
+
+    let picker = pick_list(options, Some(PriceSnapChoice(current)), move |choice| {
+        Message::PaneEvent(
+            pane,
+            Event::ChartInteraction(chart::Message::PriceSnapChanged(choice.0)),
+        )
+    });
+
+    let header = row![
+        text("Drawing snap").size(14),
+        tooltip(
+            button("i").style(style::button::info),
+            Some("Price levels that click-placed drawings snap to; hold Alt to place them freely"),
+            TooltipPosition::Top,
+        ),
+    ]
+    .align_y(Alignment::Center)
+    .spacing(4);
+
+    column![header, picker].spacing(8).into()
+}
+
+fn price_decimals_view<'a>(pane: pane_grid::Pane, current: Option<u8>) -> Element<'a, Message> {
+    let options = std::iter::once(PriceDecimals::Auto)
+        .chain((0..=MAX_PRICE_DECIMALS).map(PriceDecimals::Fixed))
+        .collect::<Vec<_>>();
+
+    let selected = current.map_or(PriceDecimals::Auto, PriceDecimals::Fixed);
+
+    let picker = pick_list(options, Some(selected), move |choice| {
+        let decimals = match choice {
+            PriceDecimals::Auto => None,
+            PriceDecimals::Fixed(decimals) => Some(decimals),
+        };
+        Message::PaneEvent(
+            pane,
+            Event::ChartInteraction(chart::Message::PriceDecimalsChanged(decimals)),
+        )
+    });
+
+    column![
+        row![
+            text("Price decimals").size(14),
+            tooltip(
+                button("i").style(style::button::info),
+                Some("Display only, prices still follow the ticker's tick size"),
+                TooltipPosition::Top,
+            ),
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center),
+        picker,
+    ]
+    .spacing(8)
+    .into()
+}
+
+fn print_aggregation_view<'a>(
+    cfg: PrintAggregation,
+    on_change: impl Fn(PrintAggregation) -> Message + Copy + 'a,
+) -> Element<'a, Message> {
+    let enabled = checkbox(cfg.enabled)
+        .label("Merge same-price prints")
+        .on_toggle(move |enabled| on_change(PrintAggregation { enabled, ..cfg }));
+
+    let mut col = column![
+        row![
+            text("Print aggregation").size(14),
+            tooltip(
+                button("i").style(style::button::info),
+                Some("Same-side trades at one price within the window show as one print"),
+                TooltipPosition::Top,
+            )
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center),
+        enabled,
+    ]
+    .spacing(8);
+
+    if cfg.enabled {
+        col = col.push(labeled_slider(
+            "Window",
+            PrintAggregation::WINDOW_RANGE,
+            cfg.window_ms as f32,
+            move |value| {
+                on_change(PrintAggregation {
+                    window_ms: value as u64,
+                    ..cfg
+                })
+            },
+            |value| format!("{value:.0}ms"),
+            Some(1.0),
+        ));
+    }
+
+    col.into()
+}
+
+/// Last price line style and the order book lines (best bid/ask, microprice); heatmaps only draw
+/// the order book lines
+fn price_lines_view<'a>(
+    pane: pane_grid::Pane,
+    cfg: PriceLines,
+    has_last_price: bool,
+) -> Element<'a, Message> {
+    let on_change = move |price_lines: PriceLines| {
+        Message::PaneEvent(
+            pane,
+            Event::ChartInteraction(chart::Message::PriceLinesChanged(price_lines)),
+        )
+    };
+
+    let bid_ask = checkbox(cfg.bid_ask)
+        .label("Best bid/ask lines")
+        .on_toggle(move |bid_ask| on_change(PriceLines { bid_ask, ..cfg }));
+
+    let microprice = checkbox(cfg.microprice)
+        .label("Microprice line")
+        .on_toggle(move |microprice| on_change(PriceLines { microprice, ..cfg }));
+
+    let show_label = checkbox(cfg.show_label)
+        .label("Price labels")
+        .on_toggle(move |show_label| on_change(PriceLines { show_label, ..cfg }));
+
+    let width = labeled_slider(
+        "Width",
+        PriceLines::WIDTH_RANGE,
+        cfg.width,
+        move |width| on_change(PriceLines { width, ..cfg }),
+        |value| format!("{value:.1}px"),
+        Some(0.5),
+    );
+
+    let mut col = column![text("Price lines").size(14)].spacing(8);
+
+    if has_last_price {
+        let anchor = row![
+            text("Last price line at"),
+            pick_list(PriceAnchor::ALL, Some(cfg.anchor), move |anchor| {
+                on_change(PriceLines { anchor, ..cfg })
+            }),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center);
+
+        col = col.push(
+            checkbox(cfg.dashed)
+                .label("Dashed last price line")
+                .on_toggle(move |dashed| on_change(PriceLines { dashed, ..cfg })),
+        );
+        col = col.push(anchor);
+    }
+
+    let microprice = row![
+        microprice,
+        tooltip(
+            button("i").style(style::button::info),
+            Some("Best bid/ask weighted by the opposite side's size,\nleans towards where price is likely to tick next"),
+            TooltipPosition::Top,
+        ),
+    ]
+    .spacing(4)
+    .align_y(Alignment::Center);
+
+    col.push(bid_ask)
+        .push(microprice)
+        .push(show_label)
+        .push(width)
+        .into()
+}
+
+/// Timeframe fetched trades are grouped into, either the chart's own or a finer one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FetchBucket {
+    Chart,
+    Fixed(Timeframe),
+}
+
+impl std::fmt::Display for FetchBucket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FetchBucket::Chart => write!(f, "Chart timeframe"),
+            FetchBucket::Fixed(tf) => write!(f, "{tf}"),
+        }
+    }
+}
+
+fn trade_fetch_view<'a>(
+    cfg: kline::Config,
+    pane: pane_grid::Pane,
+    basis: data::chart::Basis,
+) -> Element<'a, Message> {
+    let fetch = cfg.trade_fetch;
+
+    let set_fetch = move |trade_fetch: TradeFetchConfig| {
+        Message::VisualConfigChanged(
+            pane,
+            VisualConfig::Kline(kline::Config { trade_fetch, ..cfg }),
+            false,
+        )
+    };
+
+    let source = pick_list(TradeSource::ALL, Some(fetch.source), move |source| {
+        set_fetch(TradeFetchConfig { source, ..fetch })
+    });
+
+    let bucket = match basis {
+        data::chart::Basis::Time(chart_tf) => {
+            let buckets = std::iter::once(FetchBucket::Chart)
+                .chain(
+                    Timeframe::KLINE
+                        .into_iter()
+                        .filter(|tf| *tf < chart_tf && TradeFetchConfig::divides(*tf, chart_tf))
+                        .map(FetchBucket::Fixed),
+                )
+                .collect::<Vec<_>>();
+
+            let selected = match fetch.bucket_timeframe(chart_tf) {
+                tf if tf == chart_tf => FetchBucket::Chart,
+                tf => FetchBucket::Fixed(tf),
+            };
+
+            let picker = pick_list(buckets, Some(selected), move |bucket| {
+                set_fetch(TradeFetchConfig {
+                    base_timeframe: match bucket {
+                        FetchBucket::Chart => None,
+                        FetchBucket::Fixed(tf) => Some(tf),
+                    },
+                    ..fetch
+                })
+            });
+
+            Some(
+                row![text("Candles").size(13), space::horizontal(), picker]
+                    .spacing(8)
+                    .align_y(Alignment::Center),
+            )
+        }
+        data::chart::Basis::Tick(_) => None,
+    };
+
+    column![
+        row![
+            text("Trade fetch").size(14),
+            tooltip(
+                button("i").style(style::button::info),
+                Some(
+                    "Used by \"Fetch trades\" to rebuild footprints.\n\
+                    Trades are grouped into candles of the chosen timeframe,\n\
+                    which also rebuild candles missing from the chart"
+                ),
+                TooltipPosition::Top,
+            ),
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center),
+        row![text("Source").size(13), space::horizontal(), source]
+            .spacing(8)
+            .align_y(Alignment::Center),
+    ]
+    .push(bucket)
+    .spacing(8)
+    .into()
+}
+
+fn walls_view<'a>(
+    cfg: WallConfig,
+    on_change: impl Fn(WallConfig) -> Message + Copy + 'a,
+) -> Element<'a, Message> {
+    let enable = checkbox(cfg.enabled)
+        .label("Highlight walls")
+        .on_toggle(move |enabled| on_change(WallConfig { enabled, ..cfg }));
+
+    let header = row![
+        text("Depth walls").size(14),
+        tooltip(
+            button("i").style(style::button::info),
+            Some("Levels resting well above the median size of nearby levels.\nA wall must hold for a couple of seconds before it is shown"),
+            TooltipPosition::Top,
+        ),
+    ]
+    .spacing(4)
+    .align_y(Alignment::Center);
+
+    if !cfg.enabled {
+        return column![header, enable].spacing(8).into();
+    }
+
+    let multiple_slider = labeled_slider(
+        "Multiple",
+        WallConfig::MULTIPLE_RANGE,
+        cfg.multiple,
+        move |multiple| on_change(WallConfig { multiple, ..cfg }),
+        |value| format!("≥ {value:.1}× median"),
+        Some(0.5),
+    );
+
+    let notional_slider = labeled_slider(
+        "Notional",
+        WallConfig::MIN_NOTIONAL_RANGE,
+        cfg.min_notional,
+        move |min_notional| {
+            on_change(WallConfig {
+                min_notional,
+                ..cfg
+            })
+        },
+        |value| format!("≥ ${}", abbr_large_numbers(*value)),
+        Some(10_000.0),
+    );
+
+    let notify = checkbox(cfg.notify)
+        .label("Alert when walls appear or get pulled")
+        .on_toggle(move |notify| on_change(WallConfig { notify, ..cfg }));
+
+    column![header, enable, multiple_slider, notional_slider, notify]
+        .spacing(8)
+        .into()
+}
+
+/// Bid/ask liquidity within a band around mid, shown in the pane title bar
+fn depth_totals_view<'a>(
+    cfg: DepthTotalsConfig,
+    on_change: impl Fn(DepthTotalsConfig) -> Message + Copy + 'a,
+) -> Element<'a, Message> {
+    let enable = checkbox(cfg.enabled)
+        .label("Show depth totals")
+        .on_toggle(move |enabled| on_change(DepthTotalsConfig { enabled, ..cfg }));
+
+    let header = row![
+        text("Depth totals").size(14),
+        tooltip(
+            button("i").style(style::button::info),
+            Some("Sum of resting bids and asks within the band around mid,
+with the imbalance between the two sides"),
+            TooltipPosition::Top,
+        ),
+    ]
+    .spacing(4)
+    .align_y(Alignment::Center);
+
+    if !cfg.enabled {
+        return column![header, enable].spacing(8).into();
+    }
+
+    let band_slider = labeled_slider(
+        "Band",
+        DepthTotalsConfig::BAND_RANGE,
+        cfg.band_pct,
+        move |band_pct| on_change(DepthTotalsConfig { band_pct, ..cfg }),
+        |value| format!("±{value:.1}%"),
+        Some(0.1),
+    );
+
+    column![header, enable, band_slider].spacing(8).into()
+}
+
+/// Current bid-ask spread and a sparkline of its recent history, shown in the pane corner
+fn spread_history_view<'a>(
+    cfg: BookSpreadConfig,
+    on_change: impl Fn(BookSpreadConfig) -> Message + Copy + 'a,
+) -> Element<'a, Message> {
+    let enable = checkbox(cfg.enabled)
+        .label("Show spread widget")
+        .on_toggle(move |enabled| on_change(BookSpreadConfig { enabled, ..cfg }));
+
+    let header = row![
+        text("Spread").size(14),
+        tooltip(
+            button("i").style(style::button::info),
+            Some(
+                "Best ask minus best bid, in price and basis points,
+with its history over the window; widening spreads signal thin liquidity"
+            ),
+            TooltipPosition::Top,
+        ),
+    ]
+    .spacing(4)
+    .align_y(Alignment::Center);
+
+    if !cfg.enabled {
+        return column![header, enable].spacing(8).into();
+    }
+
+    let window_slider = labeled_slider(
+        "History",
+        *BookSpreadConfig::WINDOW_RANGE.start() as f32
+            ..=*BookSpreadConfig::WINDOW_RANGE.end() as f32,
+        cfg.window_mins as f32,
+        move |mins| {
+            on_change(BookSpreadConfig {
+                window_mins: mins as u32,
+                ..cfg
+            })
+        },
+        |value| format!("{value:.0} min"),
+        Some(1.0),
+    );
+
+    column![header, enable, window_slider].spacing(8).into()
+}
+
+/// Traded volume of each price cell, drawn as a buy/sell bar across its time column
+fn cell_volume_view<'a>(
+    cfg: CellVolumeConfig,
+    on_change: impl Fn(CellVolumeConfig) -> Message + Copy + 'a,
+) -> Element<'a, Message> {
+    let enable = checkbox(cfg.enabled)
+        .label("Show cell volume bars")
+        .on_toggle(move |enabled| on_change(CellVolumeConfig { enabled, ..cfg }));
+
+    let header = row![
+        text("Cell volume").size(14),
+        tooltip(
+            button("i").style(style::button::info),
+            Some(
+                "Buy/sell volume traded in each cell, in the selected size unit;
+bars scale to the largest cell over the recent columns"
+            ),
+            TooltipPosition::Top,
+        ),
+    ]
+    .spacing(4)
+    .align_y(Alignment::Center);
+
+    if !cfg.enabled {
+        return column![header, enable].spacing(8).into();
+    }
+
+    let rolling_slider = labeled_slider(
+        "Scale over",
+        *CellVolumeConfig::ROLLING_RANGE.start() as f32
+            ..=*CellVolumeConfig::ROLLING_RANGE.end() as f32,
+        cfg.rolling_columns as f32,
+        move |columns| {
+            on_change(CellVolumeConfig {
+                rolling_columns: columns as u32,
+                ..cfg
+            })
+        },
+        |value| format!("{value:.0} columns"),
+        Some(5.0),
+    );
+
+    column![header, enable, rolling_slider].spacing(8).into()
+}
+
+/// Which price the candles follow; only shown on time-based charts of markets offering more
+/// than the last traded price
+fn price_source_view<'a>(
+    cfg: kline::Config,
+    pane: pane_grid::Pane,
+    basis: data::chart::Basis,
+    available: &'static [KlinePriceSource],
+) -> Option<Element<'a, Message>> {
+    if available.len() < 2 || !matches!(basis, data::chart::Basis::Time(_)) {
+        return None;
+    }
+
+    let selected = if available.contains(&cfg.price_source) {
+        cfg.price_source
+    } else {
+        KlinePriceSource::Trade
+    };
+
+    let picker = pick_list(available, Some(selected), move |price_source| {
+        Message::VisualConfigChanged(
+            pane,
+            VisualConfig::Kline(kline::Config {
+                price_source,
+                ..cfg
+            }),
+            false,
+        )
+    });
+
+    let header = row![
+        text("Price source").size(14),
+        tooltip(
+            button("i").style(style::button::info),
+            Some("Mark and index candles have no volume,
+so volume and trade count panels are hidden"),
+            TooltipPosition::Top,
+        ),
+    ]
+    .spacing(4)
+    .align_y(Alignment::Center);
+
+    Some(column![header, picker].spacing(8).into())
+}
+
+/// Footprint cell shading by per-level buy/sell delta
+fn delta_coloring_view<'a>(cfg: kline::Config, pane: pane_grid::Pane) -> Element<'a, Message> {
+    let coloring = cfg.delta_coloring;
+
+    let on_change = move |delta_coloring: DeltaColoring| {
+        Message::VisualConfigChanged(
+            pane,
+            VisualConfig::Kline(kline::Config {
+                delta_coloring,
+                ..cfg
+            }),
+            false,
+        )
+    };
+
+    let enable = checkbox(coloring.enabled)
+        .label("Color cells by delta")
+        .on_toggle(move |enabled| {
+            on_change(DeltaColoring {
+                enabled,
+                ..coloring
+            })
+        });
+
+    let header = row![
+        text("Delta coloring").size(14),
+        tooltip(
+            button("i").style(style::button::info),
+            Some(
+                "Greener for buy dominance, redder for sell dominance,\n\
+                stronger the larger the level's delta within its candle"
+            ),
+            TooltipPosition::Top,
+        ),
+    ]
+    .spacing(4)
+    .align_y(Alignment::Center);
+
+    let mut col = column![header, enable].spacing(8);
+
+    if coloring.enabled {
+        col = col.push(labeled_slider(
+            "Imbalance",
+            DeltaColoring::RATIO_RANGE,
+            coloring.min_ratio,
+            move |min_ratio| {
+                on_change(DeltaColoring {
+                    min_ratio,
+                    ..coloring
+                })
+            },
+            |value| format!("≥ {value:.1}×"),
+            Some(0.1),
+        ));
+    }
+
+    col.into()
+}
+
+/// Absorption/aggression highlighting on the footprint
+fn order_flow_view<'a>(cfg: kline::Config, pane: pane_grid::Pane) -> Element<'a, Message> {
+    let order_flow = cfg.order_flow;
+
+    let on_change = move |order_flow: OrderFlowConfig| {
+        Message::VisualConfigChanged(
+            pane,
+            VisualConfig::Kline(kline::Config { order_flow, ..cfg }),
+            false,
+        )
+    };
+
+    let enable = checkbox(order_flow.enabled)
+        .label("Highlight absorption and aggression")
+        .on_toggle(move |enabled| {
+            on_change(OrderFlowConfig {
+                enabled,
+                ..order_flow
+            })
+        });
+
+    let header = row![
+        text("Order flow").size(14),
+        tooltip(
+            button("i").style(style::button::info),
+            Some(
+                "Absorption: a level trading well above the candle's average\n\
+                while the candle barely moves from its open (outlined).\n\
+                Aggression: several new levels traded within a second (dot)"
+            ),
+            TooltipPosition::Top,
+        ),
+    ]
+    .spacing(4)
+    .align_y(Alignment::Center);
+
+    if !order_flow.enabled {
+        return column![header, enable].spacing(8).into();
+    }
+
+    let multiple_slider = labeled_slider(
+        "Volume",
+        OrderFlowConfig::MULTIPLE_RANGE,
+        order_flow.volume_multiple,
+        move |volume_multiple| {
+            on_change(OrderFlowConfig {
+                volume_multiple,
+                ..order_flow
+            })
+        },
+        |value| format!("≥ {value:.1}× avg"),
+        Some(0.5),
+    );
+
+    let displacement_slider = labeled_slider(
+        "Progress",
+        OrderFlowConfig::DISPLACEMENT_RANGE,
+        order_flow.max_displacement,
+        move |max_displacement| {
+            on_change(OrderFlowConfig {
+                max_displacement,
+                ..order_flow
+            })
+        },
+        |value| format!("≤ {value} ticks"),
+        Some(1),
+    );
+
+    let sweep_slider = labeled_slider(
+        "Sweep",
+        OrderFlowConfig::SWEEP_RANGE,
+        order_flow.sweep_levels,
+        move |sweep_levels| {
+            on_change(OrderFlowConfig {
+                sweep_levels,
+                ..order_flow
+            })
+        },
+        |value| format!("≥ {value} levels/s"),
+        Some(1),
+    );
+
+    let notify = checkbox(order_flow.notify)
+        .label("Alert on the forming candle")
+        .on_toggle(move |notify| on_change(OrderFlowConfig { notify, ..order_flow }));
+
+    column![
+        header,
+        enable,
+        multiple_slider,
+        displacement_slider,
+        sweep_slider,
+        notify
+    ]
+    .spacing(8)
+    .into()
+}
+
+/// How far back "Backfill" pages klines; tick based charts build their candles from live trades
+fn backfill_view<'a>(
+    cfg: kline::Config,
+    pane: pane_grid::Pane,
+    basis: data::chart::Basis,
+) -> Option<Element<'a, Message>> {
+    if !matches!(basis, data::chart::Basis::Time(_)) {
+        return None;
+    }
+
+    let max_history = pick_list(
+        kline::MaxHistory::ALL,
+        Some(cfg.max_history),
+        move |max_history| {
+            Message::VisualConfigChanged(
+                pane,
+                VisualConfig::Kline(kline::Config { max_history, ..cfg }),
+                false,
+            )
+        },
+    );
+
+    let backfill =
+        button(text("Backfill").size(13)).on_press(Message::PaneEvent(pane, Event::StartBackfill));
+
+    Some(
+        column![
+            row![
+                text("History").size(14),
+                tooltip(
+                    button("i").style(style::button::info),
+                    Some(
+                        "Loads candles back to the selected age in pages,\n\
+                        older than the ones already on the chart"
+                    ),
+                    TooltipPosition::Top,
+                ),
+            ]
+            .spacing(4)
+            .align_y(Alignment::Center),
+            row![
+                text("Max history").size(13),
+                space::horizontal(),
+                max_history,
+                backfill
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center),
+        ]
+        .spacing(8)
+        .into(),
+    )
+}
+
+/// Overlay of a higher timeframe's candles; only offered for time based charts that have a
+/// larger timeframe to pick from
+fn higher_timeframe_view<'a>(
+    cfg: kline::Config,
+    pane: pane_grid::Pane,
+    basis: data::chart::Basis,
+) -> Option<Element<'a, Message>> {
+    let data::chart::Basis::Time(timeframe) = basis else {
+        return None;
+    };
+
+    let options = Timeframe::KLINE
+        .into_iter()
+        .filter(|tf| *tf > timeframe)
+        .collect::<Vec<_>>();
+    let default_tf = options
+        .iter()
+        .copied()
+        .find(|tf| tf.to_milliseconds() >= timeframe.to_milliseconds() * 4)
+        .or_else(|| options.last().copied())?;
+
+    let on_change = move |higher_timeframe| {
+        Message::VisualConfigChanged(
+            pane,
+            VisualConfig::Kline(kline::Config {
+                higher_timeframe,
+                ..cfg
+            }),
+            false,
+        )
+    };
+
+    let effective = cfg.effective_higher_timeframe(basis);
+    let style = cfg
+        .higher_timeframe
+        .map(|htf| htf.style)
+        .unwrap_or_default();
+
+    let enable = checkbox(effective.is_some())
+        .label("Show higher timeframe")
+        .on_toggle(move |enabled| {
+            on_change(enabled.then_some(HigherTimeframe {
+                timeframe: default_tf,
+                style,
+            }))
+        });
+
+    let mut col = column![text("Higher timeframe").size(14), enable].spacing(8);
+
+    if let Some(htf) = effective {
+        let timeframe_picker = pick_list(options, Some(htf.timeframe), move |timeframe| {
+            on_change(Some(HigherTimeframe { timeframe, ..htf }))
+        });
+        let style_picker = pick_list(HigherTimeframeStyle::ALL, Some(htf.style), move |style| {
+            on_change(Some(HigherTimeframe { style, ..htf }))
+        });
+
+        col = col.push(
+            row![timeframe_picker, style_picker]
+                .spacing(8)
+                .align_y(Alignment::Center),
+        );
+    }
+
+    Some(col.into())
+}
+
+/// Session open/high/low lines, for intraday time-based charts only
+fn session_levels_view<'a>(
+    cfg: kline::Config,
+    pane: pane_grid::Pane,
+    basis: data::chart::Basis,
+) -> Option<Element<'a, Message>> {
+    let data::chart::Basis::Time(timeframe) = basis else {
+        return None;
+    };
+    if timeframe >= Timeframe::D1 {
+        return None;
+    }
+
+    let levels = cfg.session_levels;
+    let on_change = move |session_levels| {
+        Message::VisualConfigChanged(
+            pane,
+            VisualConfig::Kline(kline::Config {
+                session_levels,
+                ..cfg
+            }),
+            false,
+        )
+    };
+
+    let enable = tooltip(
+        checkbox(levels.enabled)
+            .label("Show session levels")
+            .on_toggle(move |enabled| on_change(SessionLevelsConfig { enabled, ..levels })),
+        Some("Open, high and low of the current day in your time zone"),
+        TooltipPosition::Top,
+    );
+
+    let mut col = column![text("Session").size(14), enable].spacing(8);
+
+    if levels.enabled {
+        let open = checkbox(levels.open)
+            .label("Open")
+            .on_toggle(move |open| on_change(SessionLevelsConfig { open, ..levels }));
+        let high = checkbox(levels.high)
+            .label("High")
+            .on_toggle(move |high| on_change(SessionLevelsConfig { high, ..levels }));
+        let low = checkbox(levels.low)
+            .label("Low")
+            .on_toggle(move |low| on_change(SessionLevelsConfig { low, ..levels }));
+        let previous = checkbox(levels.previous)
+            .label("Previous session")
+            .on_toggle(move |previous| on_change(SessionLevelsConfig { previous, ..levels }));
+
+        col = col.push(row![open, high, low].spacing(12)).push(previous);
+    }
+
+    Some(col.into())
+}
+
+fn swings_view<'a>(cfg: kline::Config, pane: pane_grid::Pane) -> Element<'a, Message> {
+    let swings = cfg.swings;
+    let on_change = move |swings| {
+        Message::VisualConfigChanged(
+            pane,
+            VisualConfig::Kline(kline::Config { swings, ..cfg }),
+            false,
+        )
+    };
+
+    let enable = tooltip(
+        checkbox(swings.enabled)
+            .label("Show swing points")
+            .on_toggle(move |enabled| on_change(SwingConfig { enabled, ..swings })),
+        Some("Marks a candle whose high or low is beyond the candles on both sides"),
+        TooltipPosition::Top,
+    );
+
+    let mut col = column![text("Market structure").size(14), enable].spacing(8);
+
+    if swings.enabled {
+        let lookback = labeled_slider(
+            "Lookback",
+            *SwingConfig::LOOKBACK_RANGE.start() as f32..=*SwingConfig::LOOKBACK_RANGE.end() as f32,
+            swings.lookback as f32,
+            move |value| {
+                on_change(SwingConfig {
+                    lookback: value as u32,
+                    ..swings
+                })
+            },
+            |value| format!("{value:.0} bars"),
+            Some(1.0),
+        );
+        let structure = checkbox(swings.structure)
+            .label("Label higher/lower highs and lows")
+            .on_toggle(move |structure| {
+                on_change(SwingConfig {
+                    structure,
+                    ..swings
+                })
+            });
+
+        col = col.push(lookback).push(structure);
+    }
+
+    col.into()
+}
+
+fn volume_opacity_view<'a>(cfg: kline::Config, pane: pane_grid::Pane) -> Element<'a, Message> {
+    let volume_opacity = cfg.volume_opacity;
+    let on_change = move |volume_opacity| {
+        Message::VisualConfigChanged(
+            pane,
+            VisualConfig::Kline(kline::Config {
+                volume_opacity,
+                ..cfg
+            }),
+            false,
+        )
+    };
+
+    let enable = tooltip(
+        checkbox(volume_opacity.enabled)
+            .label("Fade low volume candles")
+            .on_toggle(move |enabled| {
+                on_change(VolumeOpacity {
+                    enabled,
+                    ..volume_opacity
+                })
+            }),
+        Some("Bodies fade as volume falls\nbelow the rolling average"),
+        TooltipPosition::Top,
+    );
+
+    let mut col = column![text("Volume opacity").size(14), enable].spacing(8);
+
+    if volume_opacity.enabled {
+        let strength = labeled_slider(
+            "Strength",
+            VolumeOpacity::STRENGTH_RANGE,
+            volume_opacity.strength,
+            move |strength| {
+                on_change(VolumeOpacity {
+                    strength,
+                    ..volume_opacity
+                })
+            },
+            |value| format!("{:.0}%", value * 100.0),
+            Some(0.05),
+        );
+        let period = labeled_slider(
+            "Average",
+            *VolumeOpacity::PERIOD_RANGE.start() as f32..=*VolumeOpacity::PERIOD_RANGE.end() as f32,
+            volume_opacity.period as f32,
+            move |value| {
+                on_change(VolumeOpacity {
+                    period: value as u32,
+                    ..volume_opacity
+                })
+            },
+            |value| format!("{value:.0} bars"),
+            Some(1.0),
+        );
+
+        col = col.push(strength).push(period);
+    }
+
+    col.into()
+}
+
+fn open_interest_view<'a>(cfg: kline::Config, pane: pane_grid::Pane) -> Element<'a, Message> {
+    let picker = pick_list(
+        OpenInterestDisplay::ALL,
+        Some(cfg.open_interest),
+        move |open_interest| {
+            Message::VisualConfigChanged(
+                pane,
+                VisualConfig::Kline(kline::Config {
+                    open_interest,
+                    ..cfg
+                }),
+                false,
+            )
+        },
+    );
+
+    column![
+        row![
+            text("Open interest").size(14),
+            tooltip(
+                button("i").style(style::button::info),
+                Some("Change plots period-over-period OI as a histogram:\nrising (build-up) green, falling (unwind) red"),
+                TooltipPosition::Top,
+            ),
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center),
+        picker,
+    ]
+    .spacing(8)
+    .into()
+}
+
+fn trade_retention_view<'a>(
+    cfg: kline::Config,
+    pane: pane_grid::Pane,
+    retained_trades: usize,
+) -> Element<'a, Message> {
+    let retention = cfg.trade_retention;
+
+    let set_retention = move |trade_retention: TradeRetention| {
+        Message::VisualConfigChanged(
+            pane,
+            VisualConfig::Kline(kline::Config {
+                trade_retention,
+                ..cfg
+            }),
+            false,
+        )
+    };
+
+    let max_trades = labeled_slider(
+        "Trades",
+        100_000.0..=5_000_000.0,
+        retention.max_trades as f32,
+        move |value| {
+            set_retention(TradeRetention {
+                max_trades: value as usize,
+                ..retention
+            })
+        },
+        |value| format!("≤ {}", abbr_large_numbers(*value)),
+        Some(100_000.0),
+    );
+
+    let max_age = labeled_slider(
+        "Age",
+        1.0..=72.0,
+        retention.max_age.as_secs_f32() / 3600.0,
+        move |hours| {
+            set_retention(TradeRetention {
+                max_age: Duration::from_secs(hours as u64 * 3600),
+                ..retention
+            })
+        },
+        |hours| format!("≤ {hours:.0}h"),
+        Some(1.0),
+    );
+
+    column![
+        row![
+            text("Trade history").size(14),
+            tooltip(
+                button("i").style(style::button::info),
+                Some("Raw trades are kept to rebuild footprints when the tick size changes"),
+                TooltipPosition::Top,
+            )
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center),
+        max_trades,
+        max_age,
+        text(format!(
+            "Holding {} trades ≈ {}",
+            abbr_large_numbers(retained_trades as f32),
+            format_bytes(data::chart::trades_memory_usage(retained_trades))
+        ))
+        .size(12),
+    ]
+    .spacing(8)
+    .into()
+}
+
+fn candle_style_view<'a>(
+    cfg: kline::Config,
+    editing: Option<CandleColorTarget>,
+    pane: pane_grid::Pane,
+) -> Element<'a, Message> {
+    let set_cfg = move |cfg: kline::Config| {
+        Message::VisualConfigChanged(pane, VisualConfig::Kline(cfg), false)
+    };
+
+    let coloring = pick_list(
+        kline::CandleColoring::ALL,
+        Some(cfg.coloring),
+        move |coloring| set_cfg(kline::Config { coloring, ..cfg }),
+    );
+
+    let hollow_up = checkbox(cfg.hollow_up)
+        .label("Hollow up candles")
+        .on_toggle(move |hollow_up| set_cfg(kline::Config { hollow_up, ..cfg }));
+
+    let simplify_below = tooltip(
+        labeled_slider(
+            "Simplify below",
+            kline::SimplifyBelow::RANGE,
+            cfg.simplify_below.0,
+            move |value| {
+                set_cfg(kline::Config {
+                    simplify_below: kline::SimplifyBelow(value),
+                    ..cfg
+                })
+            },
+            |value| {
+                if *value <= 0.0 {
+                    "Off".to_string()
+                } else {
+                    format!("{value:.1}px")
+                }
+            },
+            Some(0.5),
+        ),
+        Some(
+            "Zoomed out past this candle width, neighbouring\n\
+            candles are drawn as a single high/low band",
+        ),
+        TooltipPosition::Top,
+    );
+
+    let geometry = cfg.candle_geometry;
+    let set_geometry = move |candle_geometry: kline::CandleGeometry| {
+        set_cfg(kline::Config {
+            candle_geometry,
+            ..cfg
+        })
+    };
+    let percent = |value: &f32| format!("{:.0}%", value * 100.0);
+
+    let candle_spacing = labeled_slider(
+        "Spacing",
+        kline::CandleGeometry::SPACING_RANGE,
+        geometry.spacing,
+        move |spacing| {
+            set_geometry(kline::CandleGeometry {
+                spacing,
+                // keeps the body within what the new spacing leaves free
+                body_width: geometry.body_width.min(1.0 - spacing),
+                ..geometry
+            })
+        },
+        percent,
+        Some(0.05),
+    );
+    let body_width = labeled_slider(
+        "Body width",
+        kline::CandleGeometry::BODY_RANGE,
+        geometry.body_width,
+        move |body_width| {
+            set_geometry(kline::CandleGeometry {
+                body_width: body_width.min(1.0 - geometry.spacing),
+                ..geometry
+            })
+        },
+        percent,
+        Some(0.05),
+    );
+    let wick_width = tooltip(
+        labeled_slider(
+            "Wick width",
+            kline::CandleGeometry::WICK_RANGE,
+            geometry.wick_width,
+            move |wick_width| {
+                set_geometry(kline::CandleGeometry {
+                    wick_width,
+                    ..geometry
+                })
+            },
+            percent,
+            Some(0.05),
+        ),
+        Some("Relative to the body width"),
+        TooltipPosition::Top,
+    );
+
+    let show_info_box = checkbox(cfg.show_info_box)
+        .label("Show bar info at cursor")
+        .on_toggle(move |show_info_box| {
+            set_cfg(kline::Config {
+                show_info_box,
+                ..cfg
+            })
+        });
+
+    let custom_colors = checkbox(cfg.candle_colors.is_some())
+        .label("Custom colors")
+        .on_toggle(move |enabled| {
+            set_cfg(kline::Config {
+                candle_colors: enabled.then(CandleColors::default),
+                ..cfg
+            })
+        });
+
+    let mut colors_col = column![custom_colors].spacing(8);
+
+    if let Some(colors) = cfg.candle_colors {
+        for target in CandleColorTarget::ALL {
+            let color = target.get(&colors);
+            let is_open = editing == Some(target);
+
+            let header = button(
+                row![
+                    container("")
+                        .width(14)
+                        .height(14)
+                        .style(move |theme| style::colored_circle_container(theme, color)),
+                    text(target.label()).size(13),
+                ]
+                .spacing(8)
+                .align_y(Alignment::Center),
+            )
+            .on_press(Message::PaneEvent(
+                pane,
+                Event::CandleColorEditing((!is_open).then_some(target)),
+            ))
+            .style(move |theme, status| style::button::transparent(theme, status, is_open))
+            .width(Length::Fill);
+
+            colors_col = colors_col.push(header);
+
+            if is_open {
+                colors_col = colors_col.push(color_picker(
+                    data::config::theme::to_hsva(color),
+                    move |hsva| {
+                        set_cfg(kline::Config {
+                            candle_colors: Some(
+                                target.with(colors, data::config::theme::from_hsva(hsva)),
+                            ),
+                            ..cfg
+                        })
+                    },
+                ));
+            }
+        }
+    }
+
+    split_column![
+        column![text("Candle coloring").size(14), coloring].spacing(8),
+        column![
+            text("Candle style").size(14),
+            hollow_up,
+            simplify_below,
+            colors_col
+        ]
+        .spacing(8),
+        column![
+            text("Candle geometry").size(14),
+            candle_spacing,
+            body_width,
+            wick_width,
+        ]
+        .spacing(8),
+        column![text("Crosshair").size(14), show_info_box].spacing(8),
+        row![
+            space::horizontal(),
+            sync_all_button(pane, VisualConfig::Kline(cfg))
+        ],
+        ; spacing = 12, align_x = Alignment::Start
+    ]
+    .into()
+}
+
+pub fn kline_cfg_view<'a>(
+    study_config: &'a study::Configurator<FootprintStudy>,
+    cfg: kline::Config,
+    candle_color_editing: Option<CandleColorTarget>,
+    kind: &'a KlineChartKind,
+    pane: pane_grid::Pane,
+    basis: data::chart::Basis,
+    retained_trades: usize,
+    layout: &ViewConfig,
+    tick_size: f32,
+    has_open_interest: bool,
+    price_sources: &'static [KlinePriceSource],
+) -> Element<'a, Message> {
+    let content = match kind {
+        KlineChartKind::Candles => column![
+            price_source_view(cfg, pane, basis, price_sources),
+            candle_style_view(cfg, candle_color_editing, pane),
+            volume_opacity_view(cfg, pane),
+            price_scale_view(pane, layout.price_scale),
+            autoscale_padding_view(pane, layout.autoscale_padding),
+            axis_direction_view(pane, Some(layout.reverse_time), layout.invert_price),
+            price_decimals_view(pane, layout.price_decimals),
+            price_lines_view(pane, layout.price_lines, true),
+            watermark_view(pane, layout.watermark),
+            price_snap_view(pane, layout.price_snap, tick_size),
+        ]
+        .push(higher_timeframe_view(cfg, pane, basis))
+        .push(session_levels_view(cfg, pane, basis))
+        .push(swings_view(cfg, pane))
+        .push(has_open_interest.then(|| open_interest_view(cfg, pane)))
+        .push(backfill_view(cfg, pane, basis))
+        .push(trade_retention_view(cfg, pane, retained_trades))
+        .spacing(12),
+        KlineChartKind::Footprint {
+            clusters,
+            scaling,
+            studies,
+        } => {
+            let cluster_picklist =
+                pick_list(ClusterKind::ALL, Some(clusters), move |new_cluster_kind| {
+                    Message::PaneEvent(pane, Event::ClusterKindSelected(new_cluster_kind))
+                });
+
+            let scaling = {
+                let picklist = pick_list(
+                    data::chart::kline::ClusterScaling::ALL,
+                    Some(scaling),
+                    move |new_scaling| {
+                        Message::PaneEvent(pane, Event::ClusterScalingSelected(new_scaling))
+                    },
+                );
+
+                if let data::chart::kline::ClusterScaling::Hybrid { weight } = scaling {
+                    let hybrid_slider = slider(0.0..=1.0, *weight, move |new_weight| {
+                        Message::PaneEvent(
+                            pane,
+                            Event::ClusterScalingSelected(
+                                data::chart::kline::ClusterScaling::Hybrid { weight: new_weight },
+                            ),
+                        )
+                    })
+                    .step(0.05);
+
+                    column![
+                        picklist,
+                        hybrid_slider,
+                        text("Blend visible-range and per-candle scaling"),
+                    ]
+                    .spacing(8)
+                } else {
+                    column![picklist].spacing(8)
+                }
+            };
+
+            let study_cfg = study_config.view(studies, basis).map(move |msg| {
+                Message::PaneEvent(
+                    pane,
+                    Event::StudyConfigurator(study::StudyMessage::Footprint(msg)),
+                )
+            });
+
+            split_column![
+                column![text("Cluster type").size(14), cluster_picklist].spacing(8),
+                column![text("Cluster scaling").size(14), scaling].spacing(8),
+                column![text("Studies").size(14), study_cfg].spacing(8),
+                delta_coloring_view(cfg, pane),
+                order_flow_view(cfg, pane),
+                print_aggregation_view(cfg.print_aggregation, move |print_aggregation| {
+                    Message::VisualConfigChanged(
+                        pane,
+                        VisualConfig::Kline(kline::Config {
+                            print_aggregation,
+                            ..cfg
+                        }),
+                        false,
+                    )
+                }),
+                price_decimals_view(pane, layout.price_decimals),
+                autoscale_padding_view(pane, layout.autoscale_padding),
+                axis_direction_view(pane, Some(layout.reverse_time), layout.invert_price),
+                watermark_view(pane, layout.watermark),
+                price_snap_view(pane, layout.price_snap, tick_size),
+                column![price_lines_view(pane, layout.price_lines, true)]
+                    .push(higher_timeframe_view(cfg, pane, basis))
+                    .push(session_levels_view(cfg, pane, basis))
+                    .push(swings_view(cfg, pane))
+                    .push(has_open_interest.then(|| open_interest_view(cfg, pane)))
+                    .push(backfill_view(cfg, pane, basis))
+                    .spacing(12),
+                trade_fetch_view(cfg, pane, basis),
+                trade_retention_view(cfg, pane, retained_trades),
+                row![
+                    space::horizontal(),
+                    sync_all_button(pane, VisualConfig::Kline(cfg))
+                ],
+                ; spacing = 12, align_x = Alignment::Start
+            ]
+        }
+    };
+
+    cfg_view_container(360, content)
+}
+
+pub fn ladder_cfg_view<'a>(
+    cfg: ladder::Config,
+    pane: pane_grid::Pane,
+    exchange: Option<Exchange>,
+    depth_push_freq: Option<Timeframe>,
+) -> Element<'a, Message> {
+    let display_options = {
+        let spread = checkbox(cfg.show_spread)
+            .label("Show Spread")
+            .on_toggle(move |value| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::Ladder(ladder::Config {
+                        show_spread: value,
+                        ..cfg
+                    }),
+                    false,
+                )
+            });
 
         let chase_tracker = checkbox(cfg.show_chase_tracker)
             .label("Show Chase Tracker")
@@ -607,6 +2495,27 @@ pub fn ladder_cfg_view<'a>(cfg: ladder::Config, pane: pane_grid::Pane) -> Elemen
     let content = split_column![
         display_options,
         history_column,
+        depth_totals_view(cfg.depth_totals, move |depth_totals| {
+            Message::VisualConfigChanged(
+                pane,
+                VisualConfig::Ladder(ladder::Config {
+                    depth_totals,
+                    ..cfg
+                }),
+                false,
+            )
+        }),
+        spread_history_view(cfg.spread_history, move |spread_history| {
+            Message::VisualConfigChanged(
+                pane,
+                VisualConfig::Ladder(ladder::Config {
+                    spread_history,
+                    ..cfg
+                }),
+                false,
+            )
+        }),
+        push_freq_view(pane, exchange, depth_push_freq),
         row![
             space::horizontal(),
             sync_all_button(pane, VisualConfig::Ladder(cfg))
@@ -617,6 +2526,161 @@ pub fn ladder_cfg_view<'a>(cfg: ladder::Config, pane: pane_grid::Pane) -> Elemen
     cfg_view_container(320, content)
 }
 
+pub fn depth_curve_cfg_view<'a>(
+    cfg: depth_curve::Config,
+    pane: pane_grid::Pane,
+) -> Element<'a, Message> {
+    let range_column = {
+        let slider_ui = slider(
+            depth_curve::MIN_RANGE_PCT..=depth_curve::MAX_RANGE_PCT,
+            cfg.range_pct,
+            move |value| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::DepthCurve(depth_curve::Config {
+                        range_pct: value,
+                        ..cfg
+                    }),
+                    false,
+                )
+            },
+        )
+        .step(0.1);
+
+        column![
+            text("Price range").size(14),
+            classic_slider_row(
+                text("Around mid"),
+                slider_ui.into(),
+                Some(text(format!("±{:.1}%", cfg.range_pct)).size(13)),
+            )
+        ]
+        .spacing(8)
+    };
+
+    let display_options = {
+        let spread = checkbox(cfg.show_spread)
+            .label("Show Spread")
+            .on_toggle(move |value| {
+                Message::VisualConfigChanged(
+                    pane,
+                    VisualConfig::DepthCurve(depth_curve::Config {
+                        show_spread: value,
+                        ..cfg
+                    }),
+                    false,
+                )
+            });
+
+        column![text("Display Options").size(14), spread].spacing(8)
+    };
+
+    let content = split_column![
+        range_column,
+        display_options,
+        walls_view(cfg.walls, move |walls| {
+            Message::VisualConfigChanged(
+                pane,
+                VisualConfig::DepthCurve(depth_curve::Config { walls, ..cfg }),
+                false,
+            )
+        }),
+        depth_totals_view(cfg.depth_totals, move |depth_totals| {
+            Message::VisualConfigChanged(
+                pane,
+                VisualConfig::DepthCurve(depth_curve::Config {
+                    depth_totals,
+                    ..cfg
+                }),
+                false,
+            )
+        }),
+        spread_history_view(cfg.spread_history, move |spread_history| {
+            Message::VisualConfigChanged(
+                pane,
+                VisualConfig::DepthCurve(depth_curve::Config {
+                    spread_history,
+                    ..cfg
+                }),
+                false,
+            )
+        }),
+        row![
+            space::horizontal(),
+            sync_all_button(pane, VisualConfig::DepthCurve(cfg))
+        ],
+        ; spacing = 12, align_x = Alignment::Start
+    ];
+
+    cfg_view_container(320, content)
+}
+
+pub fn spread_cfg_view<'a>(cfg: spread::Config, pane: pane_grid::Pane) -> Element<'a, Message> {
+    let on_change = move |cfg: spread::Config| {
+        Message::VisualConfigChanged(pane, VisualConfig::Spread(cfg), false)
+    };
+
+    let unit_column = {
+        let picker = pick_list(spread::SpreadUnit::ALL, Some(cfg.unit), move |unit| {
+            on_change(spread::Config { unit, ..cfg })
+        });
+
+        column![text("Plot spread in").size(14), picker].spacing(8)
+    };
+
+    let alerts_column = {
+        let header = row![
+            text("Alerts").size(14),
+            tooltip(
+                button("i").style(style::button::info),
+                Some("Spread is the first ticker minus the second, compared on candle mids.\nEach threshold alerts once until the spread moves back inside it"),
+                TooltipPosition::Top,
+            ),
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center);
+
+        let enable = checkbox(cfg.alert)
+            .label("Alert when spread crosses thresholds")
+            .on_toggle(move |alert| on_change(spread::Config { alert, ..cfg }));
+
+        if cfg.alert {
+            let upper = labeled_slider(
+                "Upper",
+                spread::Config::THRESHOLD_RANGE,
+                cfg.upper_bps,
+                move |upper_bps| on_change(spread::Config { upper_bps, ..cfg }),
+                |value| format!("≥ {value:+.0} bps"),
+                Some(1.0),
+            );
+            let lower = labeled_slider(
+                "Lower",
+                spread::Config::THRESHOLD_RANGE,
+                cfg.lower_bps,
+                move |lower_bps| on_change(spread::Config { lower_bps, ..cfg }),
+                |value| format!("≤ {value:+.0} bps"),
+                Some(1.0),
+            );
+
+            column![header, enable, upper, lower].spacing(8)
+        } else {
+            column![header, enable].spacing(8)
+        }
+    };
+
+    let content = split_column![
+        unit_column,
+        alerts_column,
+        row![
+            space::horizontal(),
+            sync_all_button(pane, VisualConfig::Spread(cfg))
+        ],
+        ; spacing = 12, align_x = Alignment::Start
+    ];
+
+    cfg_view_container(320, content)
+}
+
 fn sync_all_button<'a>(pane: pane_grid::Pane, config: VisualConfig) -> Element<'a, Message> {
     tooltip(
         button("Sync all").on_press(Message::VisualConfigChanged(pane, config, true)),
@@ -1,18 +1,23 @@
 use crate::{
     style::{self, icon_text},
-    widget::numeric_input_box,
+    widget::{numeric_input_box, tooltip},
 };
 
 use data::chart::Basis;
 use exchange::{
     TickMultiplier, TickerInfo, Timeframe,
-    adapter::{Exchange, hyperliquid::allowed_multipliers_for_base_tick},
+    adapter::{
+        Exchange,
+        hyperliquid::{
+            allowed_multipliers_for_base_tick, depth_fallback, effective_depth_multiplier,
+        },
+    },
 };
 use iced::{
     Element, Length,
     alignment::Horizontal,
     padding,
-    widget::{button, column, container, row, rule, scrollable, text},
+    widget::{button, checkbox, column, container, row, rule, scrollable, text},
 };
 use serde::{Deserialize, Serialize};
 
@@ -127,7 +132,9 @@ pub enum SelectedTab {
 pub enum Action {
     BasisSelected(Basis),
     TicksizeSelected(TickMultiplier),
+    RoundStepsToggled(bool),
     TabSelected(SelectedTab),
+    SaveTicksizeAsDefault,
 }
 
 #[derive(Debug, Clone)]
@@ -136,7 +143,9 @@ pub enum Message {
     TabSelected(SelectedTab),
     TicksizeInputChanged(String),
     TicksizeSelected(TickMultiplier),
+    RoundStepsToggled(bool),
     TickCountInputChanged(String),
+    SaveTicksizeAsDefault,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
@@ -146,6 +155,7 @@ pub struct Modifier {
     kind: ModifierKind,
     base_ticksize: Option<f32>,
     exchange: Option<Exchange>,
+    round_steps: bool,
 }
 
 impl Modifier {
@@ -158,6 +168,7 @@ impl Modifier {
             view_mode: ViewMode::BasisSelection,
             base_ticksize: None,
             exchange: None,
+            round_steps: false,
         }
     }
 
@@ -171,6 +182,7 @@ impl Modifier {
         base_ticksize: f32,
         multiplier: TickMultiplier,
         exchange: Option<Exchange>,
+        round_steps: bool,
     ) -> Self {
         self.view_mode = ViewMode::TicksizeSelection {
             raw_input_buf: if multiplier.is_custom() {
@@ -187,6 +199,7 @@ impl Modifier {
         };
         self.base_ticksize = Some(base_ticksize);
         self.exchange = exchange;
+        self.round_steps = round_steps;
         self
     }
 
@@ -264,6 +277,11 @@ impl Modifier {
                 }
                 Some(Action::TicksizeSelected(new_ticksize))
             }
+            Message::RoundStepsToggled(round_steps) => {
+                self.round_steps = round_steps;
+                Some(Action::RoundStepsToggled(round_steps))
+            }
+            Message::SaveTicksizeAsDefault => Some(Action::SaveTicksizeAsDefault),
             Message::TicksizeInputChanged(value_str) => {
                 if let ViewMode::TicksizeSelection {
                     ref mut raw_input_buf,
@@ -596,26 +614,103 @@ impl Modifier {
                         3,
                     );
 
-                    if allows_custom_tsizes {
-                        let custom_input = {
-                            let tick_multiplier_to_submit = parsed_input.filter(|tm| {
-                                tm.0 >= TICK_MULTIPLIER_MIN && tm.0 <= TICK_MULTIPLIER_MAX
-                            });
+                    let tick_multiplier_to_submit = parsed_input
+                        .filter(|tm| tm.0 >= TICK_MULTIPLIER_MIN && tm.0 <= TICK_MULTIPLIER_MAX);
+
+                    // Server-side aggregation only serves 1-2-5 steps, so custom values
+                    // are snapped to the multiplier the feed will actually deliver
+                    let server_side = (!allows_custom_tsizes)
+                        .then_some(self.base_ticksize)
+                        .flatten()
+                        .map(|base| {
+                            let tm = tick_multiplier_to_submit.unwrap_or(ticksize);
+                            let (effective, cfg) = effective_depth_multiplier(base, tm.0);
+                            (tm, exchange::TickMultiplier(effective), cfg)
+                        });
+
+                    let on_submit = match server_side {
+                        Some((_, effective, _)) => tick_multiplier_to_submit.map(|_| effective),
+                        None => tick_multiplier_to_submit,
+                    };
 
-                            numeric_input_box::<_, Message>(
-                                "Custom: ",
-                                &format!("{}-{}", TICK_MULTIPLIER_MIN, TICK_MULTIPLIER_MAX),
-                                &raw_input_buf.to_display_string(),
-                                is_input_valid,
-                                Message::TicksizeInputChanged,
-                                tick_multiplier_to_submit.map(Message::TicksizeSelected),
-                            )
-                        };
+                    ticksizes_column = ticksizes_column.push(numeric_input_box::<_, Message>(
+                        "Custom: ",
+                        &format!("{}-{}", TICK_MULTIPLIER_MIN, TICK_MULTIPLIER_MAX),
+                        &raw_input_buf.to_display_string(),
+                        is_input_valid,
+                        Message::TicksizeInputChanged,
+                        on_submit.map(Message::TicksizeSelected),
+                    ));
+
+                    if let Some((requested, effective, cfg)) = server_side {
+                        ticksizes_column = ticksizes_column.push(
+                            text(format!("{requested} → {effective} · {cfg}"))
+                                .size(11)
+                                .style(|theme: &iced::Theme| iced::widget::text::Style {
+                                    color: Some(
+                                        theme.extended_palette().background.strongest.color,
+                                    ),
+                                }),
+                        );
 
-                        ticksizes_column = ticksizes_column.push(custom_input);
+                        // the running stream may have fallen back to full precision after a null book
+                        if let Some(rejected) =
+                            ticker_info.and_then(|info| depth_fallback(info.ticker, ticksize.0))
+                        {
+                            ticksizes_column = ticksizes_column.push(
+                                text(format!("Live feed: full precision, no book for {rejected}"))
+                                    .size(11)
+                                    .style(|theme: &iced::Theme| iced::widget::text::Style {
+                                        color: Some(theme.extended_palette().danger.base.color),
+                                    }),
+                            );
+                        }
+                    } else if let (Some(tm), Some(base)) =
+                        (tick_multiplier_to_submit, self.base_ticksize)
+                    {
+                        let decimals = (-base.log10()).ceil().max(0.0) as usize;
+                        let tm = if self.round_steps { tm.rounded() } else { tm };
+
+                        ticksizes_column = ticksizes_column.push(
+                            text(format!("Step: {:.decimals$}", base * f32::from(tm.0)))
+                                .size(11)
+                                .style(|theme: &iced::Theme| iced::widget::text::Style {
+                                    color: Some(
+                                        theme.extended_palette().background.strongest.color,
+                                    ),
+                                }),
+                        );
                     }
+
                     ticksizes_column = ticksizes_column.push(tick_multiplier_grid);
 
+                    // server-side steps are already 1-2-5, so only custom grouping can be uneven
+                    if allows_custom_tsizes {
+                        ticksizes_column = ticksizes_column.push(
+                            checkbox(self.round_steps)
+                                .label("Snap to round steps")
+                                .on_toggle(Message::RoundStepsToggled),
+                        );
+                    }
+
+                    // presets only seed new heatmap and footprint panes
+                    if matches!(
+                        kind,
+                        ModifierKind::Heatmap(..) | ModifierKind::Footprint(..)
+                    ) && ticker_info.is_some()
+                    {
+                        ticksizes_column = ticksizes_column.push(tooltip(
+                            button(text("Save as default").size(12))
+                                .on_press(Message::SaveTicksizeAsDefault)
+                                .width(Length::Fill)
+                                .style(|theme, status| {
+                                    style::button::transparent(theme, status, false)
+                                }),
+                            Some("New panes of this ticker and chart type\nstart with this tick size"),
+                            iced::widget::tooltip::Position::Top,
+                        ));
+                    }
+
                     if let Some(base_ticksize) = self.base_ticksize {
                         ticksizes_column = ticksizes_column.push(
                             row![
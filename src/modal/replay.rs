@@ -0,0 +1,399 @@
+use crate::style;
+use crate::widget::labeled_slider;
+use data::UserTimezone;
+use data::replay::{Playback, RecordError, Recorded, Recorder, RecordingCfg, Seek};
+use exchange::adapter::StreamKind;
+use exchange::depth::{Depth, DepthChange};
+use exchange::{Kline, Trade};
+
+use iced::widget::{button, checkbox, column, container, pick_list, row, scrollable, slider, text};
+use iced::{Alignment, Element, Length};
+use std::path::PathBuf;
+use std::time::Instant;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ToggleRecording(bool),
+    BudgetChanged(u64),
+    RefreshRecordings,
+    Open(PathBuf),
+    TogglePlay,
+    Step,
+    Scrub(u64),
+    ScrubReleased,
+    SpeedChanged(Speed),
+    Close,
+}
+
+pub enum Action {
+    /// Events to feed through the live update path
+    Dispatch(Vec<exchange::Event>),
+    /// Panes must be rebuilt empty before feeding the events, which start at a depth keyframe
+    Restart(Vec<exchange::Event>),
+    /// Playback was closed, live streams take over again on fresh panes
+    Closed,
+    Info(String),
+    Error(String),
+}
+
+/// Playback rate, shown as a multiplier
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Speed(f32);
+
+impl std::fmt::Display for Speed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x", self.0)
+    }
+}
+
+struct ActivePlayback {
+    path: PathBuf,
+    playback: Playback,
+    last_tick: Option<Instant>,
+    scrub: Option<u64>,
+}
+
+/// Session recorder and the playback of recorded sessions
+pub struct Replay {
+    cfg: RecordingCfg,
+    recorder: Option<Recorder>,
+    recordings: Vec<PathBuf>,
+    active: Option<ActivePlayback>,
+    changed: bool,
+}
+
+impl Replay {
+    pub fn new(cfg: RecordingCfg) -> Self {
+        Self {
+            cfg,
+            recorder: None,
+            recordings: data::replay::list_recordings(),
+            active: None,
+            changed: false,
+        }
+    }
+
+    pub fn settings(&self) -> RecordingCfg {
+        self.cfg
+    }
+
+    /// Whether the saved recording settings changed since the last call
+    pub fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+
+    /// While a recording is played back, live market events are ignored
+    pub fn is_playing_back(&self) -> bool {
+        self.active.is_some()
+    }
+
+    /// Records a depth update if recording is on, returning a message when it had to stop
+    pub fn record_depth(
+        &mut self,
+        stream: &StreamKind,
+        update_t: u64,
+        change: &DepthChange,
+        book: &Depth,
+        trades: &[Trade],
+    ) -> Option<String> {
+        let recorder = self.recorder.as_mut()?;
+        let result = recorder.record_depth(now_millis(), stream, update_t, change, book, trades);
+
+        self.stop_on_error(result)
+    }
+
+    /// Records a kline update if recording is on, returning a message when it had to stop
+    pub fn record_kline(&mut self, stream: &StreamKind, kline: &Kline) -> Option<String> {
+        let recorder = self.recorder.as_mut()?;
+        let result = recorder.record_kline(now_millis(), stream, kline);
+
+        self.stop_on_error(result)
+    }
+
+    fn stop_on_error(&mut self, result: Result<(), RecordError>) -> Option<String> {
+        let err = result.err()?;
+        let path = self.recorder.take().map(Recorder::finish);
+        self.recordings = data::replay::list_recordings();
+
+        Some(match path {
+            Some(Ok(path)) => format!("Recording stopped, {err}: {}", path.display()),
+            _ => format!("Recording stopped: {err}"),
+        })
+    }
+
+    /// Advances a running playback by the time since the last tick
+    pub fn tick(&mut self, now: Instant) -> Vec<exchange::Event> {
+        let Some(active) = self.active.as_mut() else {
+            return vec![];
+        };
+
+        let elapsed = active
+            .last_tick
+            .map(|last| now.saturating_duration_since(last))
+            .unwrap_or_default();
+        active.last_tick = Some(now);
+
+        active
+            .playback
+            .advance(elapsed)
+            .iter()
+            .map(|event| event.to_event())
+            .collect()
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Action> {
+        match message {
+            Message::ToggleRecording(true) => {
+                if self.recorder.is_some() || self.active.is_some() {
+                    return None;
+                }
+
+                match Recorder::create(self.cfg) {
+                    Ok(recorder) => self.recorder = Some(recorder),
+                    Err(err) => {
+                        return Some(Action::Error(format!("Failed to start recording: {err}")));
+                    }
+                }
+            }
+            Message::ToggleRecording(false) => {
+                let recorder = self.recorder.take()?;
+                self.recordings = data::replay::list_recordings();
+
+                return Some(match recorder.finish() {
+                    Ok(path) => Action::Info(format!("Recording saved to {}", path.display())),
+                    Err(err) => Action::Error(err.to_string()),
+                });
+            }
+            Message::BudgetChanged(budget_mb) => {
+                self.cfg.budget_mb = budget_mb;
+                self.changed = true;
+            }
+            Message::RefreshRecordings => {
+                self.recordings = data::replay::list_recordings();
+            }
+            Message::Open(path) => {
+                if self.recorder.is_some() {
+                    return None;
+                }
+
+                let playback = match Playback::open(&path) {
+                    Ok(Some(playback)) => playback,
+                    Ok(None) => return Some(Action::Error("The recording is empty".to_string())),
+                    Err(err) => {
+                        return Some(Action::Error(format!("Failed to open recording: {err}")));
+                    }
+                };
+
+                self.active = Some(ActivePlayback {
+                    path,
+                    playback,
+                    last_tick: None,
+                    scrub: None,
+                });
+                return Some(Action::Restart(vec![]));
+            }
+            Message::TogglePlay => {
+                let active = self.active.as_mut()?;
+                active.last_tick = None;
+
+                if active.playback.is_finished() {
+                    let start = active.playback.start();
+                    let action = seek_action(&mut active.playback, start);
+                    active.playback.playing = true;
+                    return Some(action);
+                }
+                active.playback.playing = !active.playback.playing;
+            }
+            Message::Step => {
+                let active = self.active.as_mut()?;
+                return Some(Action::Dispatch(
+                    active
+                        .playback
+                        .step()
+                        .iter()
+                        .map(|event| event.to_event())
+                        .collect(),
+                ));
+            }
+            Message::Scrub(position) => {
+                if let Some(active) = self.active.as_mut() {
+                    active.scrub = Some(position);
+                }
+            }
+            Message::ScrubReleased => {
+                let active = self.active.as_mut()?;
+                let position = active.scrub.take()?;
+                active.last_tick = None;
+
+                return Some(seek_action(&mut active.playback, position));
+            }
+            Message::SpeedChanged(Speed(speed)) => {
+                if let Some(active) = self.active.as_mut() {
+                    active.playback.speed = speed;
+                }
+            }
+            Message::Close => {
+                self.active.take()?;
+                return Some(Action::Closed);
+            }
+        }
+
+        None
+    }
+
+    pub fn view(&self, timezone: UserTimezone) -> Element<'_, Message> {
+        let recording = {
+            let is_recording = self.recorder.is_some();
+
+            let toggle = checkbox(is_recording)
+                .label("Record market events")
+                .on_toggle_maybe(
+                    self.active
+                        .is_none()
+                        .then_some(Message::ToggleRecording as fn(bool) -> Message),
+                );
+
+            let budget = labeled_slider(
+                "Size limit",
+                *RecordingCfg::BUDGET_RANGE.start() as f32
+                    ..=*RecordingCfg::BUDGET_RANGE.end() as f32,
+                self.cfg.budget_mb as f32,
+                |value| Message::BudgetChanged(value as u64),
+                |value| format!("{value:.0} MB"),
+                Some(10.0),
+            );
+
+            let status = match &self.recorder {
+                Some(recorder) => format!(
+                    "Recording {:.1} of {} MB",
+                    recorder.written() as f32 / (1024.0 * 1024.0),
+                    self.cfg.budget_mb
+                ),
+                None => {
+                    "Records the depth, trade and kline streams of the active layout".to_string()
+                }
+            };
+
+            column![
+                text("Session recording").size(14),
+                toggle,
+                budget,
+                text(status).size(11),
+            ]
+            .spacing(8)
+        };
+
+        let playback: Element<'_, Message> = match &self.active {
+            Some(active) => self.playback_view(active, timezone),
+            None => self.recordings_view(),
+        };
+
+        container(column![recording, playback].spacing(20))
+            .width(320)
+            .max_height(560)
+            .padding(24)
+            .style(style::dashboard_modal)
+            .into()
+    }
+
+    fn recordings_view(&self) -> Element<'_, Message> {
+        let mut list = column![].spacing(2);
+
+        if self.recordings.is_empty() {
+            list = list.push(text("No recordings yet").size(12));
+        }
+
+        for path in &self.recordings {
+            let name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+
+            list = list.push(
+                button(text(name).size(12))
+                    .width(Length::Fill)
+                    .on_press_maybe(self.recorder.is_none().then(|| Message::Open(path.clone())))
+                    .style(|theme, status| style::button::transparent(theme, status, false)),
+            );
+        }
+
+        column![
+            row![
+                text("Playback").size(14),
+                iced::widget::space::horizontal(),
+                button(text("Refresh").size(12)).on_press(Message::RefreshRecordings),
+            ]
+            .align_y(Alignment::Center),
+            scrollable(list).height(Length::Shrink),
+        ]
+        .spacing(8)
+        .into()
+    }
+
+    fn playback_view<'a>(
+        &'a self,
+        active: &'a ActivePlayback,
+        timezone: UserTimezone,
+    ) -> Element<'a, Message> {
+        let playback = &active.playback;
+        let position = active.scrub.unwrap_or(playback.position());
+
+        let name = active
+            .path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let timeline = slider(
+            playback.start() as f64..=playback.end().max(playback.start() + 1) as f64,
+            position as f64,
+            |value| Message::Scrub(value as u64),
+        )
+        .on_release(Message::ScrubReleased);
+
+        let controls = row![
+            button(text(if playback.playing { "Pause" } else { "Play" }).size(12))
+                .on_press(Message::TogglePlay),
+            button(text("Step").size(12))
+                .on_press_maybe((!playback.is_finished()).then_some(Message::Step)),
+            pick_list(
+                Playback::SPEEDS.map(Speed),
+                Some(Speed(playback.speed)),
+                Message::SpeedChanged,
+            )
+            .text_size(12),
+            iced::widget::space::horizontal(),
+            button(text("Close").size(12)).on_press(Message::Close),
+        ]
+        .align_y(Alignment::Center)
+        .spacing(4);
+
+        column![
+            text("Playback").size(14),
+            text(name).size(12),
+            timeline,
+            row![
+                text(timezone.format_datetime(position as i64)).size(11),
+                iced::widget::space::horizontal(),
+                text(format!("{} events", playback.len())).size(11),
+            ],
+            controls,
+            text("Live streams are paused until playback is closed").size(11),
+        ]
+        .spacing(8)
+        .into()
+    }
+}
+
+fn seek_action(playback: &mut Playback, position: u64) -> Action {
+    let to_events = |events: Vec<Recorded>| events.iter().map(|event| event.to_event()).collect();
+
+    match playback.seek(position) {
+        Seek::Forward(events) => Action::Dispatch(to_events(events)),
+        Seek::Restart(events) => Action::Restart(to_events(events)),
+    }
+}
+
+fn now_millis() -> u64 {
+    chrono::Utc::now().timestamp_millis().max(0) as u64
+}
@@ -1,7 +1,10 @@
 use iced::{
     Alignment, Element,
-    widget::{button, column, container, pick_list, row, space, text_input::default},
+    widget::{
+        button, column, container, pick_list, row, space, text, text_input, text_input::default,
+    },
 };
+use std::path::PathBuf;
 
 use crate::{
     style::{self, Icon, icon_text},
@@ -42,31 +45,49 @@ pub enum Message {
     CloseRequested,
     Color(Hsva),
     HexInput(String),
+    NameInput(String),
+    SaveTheme,
+    DeleteTheme(String),
+    ExportTheme,
+    ImportTheme,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Action {
     UpdateTheme(iced_core::Theme),
+    Exported(Result<PathBuf, String>),
+    Imported(Result<iced_core::Theme, String>),
     Exit,
 }
 
 pub struct ThemeEditor {
     pub custom_theme: Option<iced_core::Theme>,
+    pub custom_themes: Vec<iced_core::Theme>,
+    name_input: String,
     component: Component,
     hex_input: Option<String>,
     editing: Option<Hsva>,
+    changed: bool,
 }
 
 impl ThemeEditor {
-    pub fn new(custom_theme: Option<data::Theme>) -> Self {
+    pub fn new(custom_theme: Option<data::Theme>, custom_themes: Vec<data::Theme>) -> Self {
         Self {
             custom_theme: custom_theme.map(|theme| theme.0),
+            custom_themes: custom_themes.into_iter().map(|theme| theme.0).collect(),
+            name_input: String::new(),
             component: Component::Background,
             hex_input: None,
             editing: None,
+            changed: false,
         }
     }
 
+    /// Whether the saved custom themes changed since the last call
+    pub fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+
     fn focused_color(&self, theme: &iced_core::Theme) -> iced_core::Color {
         let palette = theme.palette();
         match self.component {
@@ -79,6 +100,32 @@ impl ThemeEditor {
         }
     }
 
+    /// Themes to list alongside the built-in ones: saved named themes, then the
+    /// unsaved draft if it doesn't shadow one of them.
+    pub fn themes(&self) -> Vec<iced_core::Theme> {
+        let mut themes = self.custom_themes.clone();
+
+        if let Some(draft) = &self.custom_theme
+            && !themes.iter().any(|t| t.to_string() == draft.to_string())
+        {
+            themes.push(draft.clone());
+        }
+
+        themes
+    }
+
+    fn store_theme(&mut self, theme: iced_core::Theme) {
+        let name = theme.to_string();
+        match self
+            .custom_themes
+            .iter_mut()
+            .find(|t| t.to_string() == name)
+        {
+            Some(existing) => *existing = theme,
+            None => self.custom_themes.push(theme),
+        }
+    }
+
     pub fn update(&mut self, message: Message, theme: &iced_core::Theme) -> Option<Action> {
         match message {
             Message::Color(hsva) => {
@@ -97,8 +144,9 @@ impl ThemeEditor {
                     Component::Warning => new_palette.warning = color,
                 }
 
-                let new_theme = iced_core::Theme::custom("Custom".to_string(), new_palette);
+                let new_theme = iced_core::Theme::custom(draft_name(theme), new_palette);
                 self.custom_theme = Some(new_theme.clone());
+                self.changed = true;
 
                 Some(Action::UpdateTheme(new_theme))
             }
@@ -125,8 +173,9 @@ impl ThemeEditor {
 
                     self.editing = Some(data::config::theme::to_hsva(color));
 
-                    let new_theme = iced_core::Theme::custom("Custom".to_string(), new_palette);
+                    let new_theme = iced_core::Theme::custom(draft_name(theme), new_palette);
                     self.custom_theme = Some(new_theme.clone());
+                    self.changed = true;
 
                     action = Some(Action::UpdateTheme(new_theme));
                 }
@@ -134,6 +183,51 @@ impl ThemeEditor {
                 self.hex_input = Some(input);
                 action
             }
+            Message::NameInput(input) => {
+                self.name_input = input;
+                None
+            }
+            Message::SaveTheme => {
+                let name = self.name_input.trim();
+                if name.is_empty() || name == "Flowsurface" {
+                    return None;
+                }
+
+                let new_theme = iced_core::Theme::custom(name.to_string(), theme.palette());
+                self.store_theme(new_theme.clone());
+                self.custom_theme = Some(new_theme.clone());
+                self.changed = true;
+                self.name_input.clear();
+
+                Some(Action::UpdateTheme(new_theme))
+            }
+            Message::DeleteTheme(name) => {
+                self.custom_themes.retain(|t| t.to_string() != name);
+                self.changed = true;
+                None
+            }
+            Message::ExportTheme => Some(Action::Exported(data::config::theme::export_theme(
+                &data::Theme(theme.clone()),
+            ))),
+            Message::ImportTheme => {
+                let name = self.name_input.trim();
+                if name.is_empty() {
+                    return None;
+                }
+
+                let path = data::config::theme::theme_file_path(name);
+                let result = data::config::theme::import_theme(&path)
+                    .map(|imported| imported.0)
+                    .map_err(|err| format!("{}: {err}", path.display()));
+
+                if let Ok(imported) = &result {
+                    self.store_theme(imported.clone());
+                    self.changed = true;
+                    self.name_input.clear();
+                }
+
+                Some(Action::Imported(result))
+            }
             Message::CloseRequested => Some(Action::Exit),
         }
     }
@@ -193,6 +287,7 @@ impl ThemeEditor {
             .spacing(8)
             .align_y(Alignment::Center),
             color_picker(hsva_in, Message::Color),
+            self.named_themes_view(theme),
         ]
         .spacing(10);
 
@@ -202,4 +297,49 @@ impl ThemeEditor {
             .style(style::dashboard_modal)
             .into()
     }
+
+    fn named_themes_view(&self, theme: &iced_core::Theme) -> Element<'_, Message> {
+        let has_name = !self.name_input.trim().is_empty();
+        let is_custom = matches!(theme, iced_core::Theme::Custom(_));
+
+        let name_input = text_input("Theme name", &self.name_input)
+            .on_input(Message::NameInput)
+            .on_submit(Message::SaveTheme);
+
+        let controls = row![
+            name_input,
+            button(text("Save")).on_press_maybe(has_name.then_some(Message::SaveTheme)),
+            button(text("Import")).on_press_maybe(has_name.then_some(Message::ImportTheme)),
+            button(text("Export")).on_press_maybe(is_custom.then_some(Message::ExportTheme)),
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center);
+
+        let saved = self.custom_themes.iter().map(|saved| {
+            let name = saved.to_string();
+            row![
+                text(name.clone()),
+                space::horizontal(),
+                button(icon_text(Icon::TrashBin, 11))
+                    .on_press(Message::DeleteTheme(name))
+                    .style(move |theme, status| style::button::transparent(theme, status, false)),
+            ]
+            .align_y(Alignment::Center)
+            .into()
+        });
+
+        column![controls, column(saved).spacing(2)]
+            .spacing(8)
+            .into()
+    }
+}
+
+/// Name for an edited palette: keeps the name of the custom theme being edited.
+fn draft_name(theme: &iced_core::Theme) -> String {
+    match theme {
+        iced_core::Theme::Custom(custom) if custom.to_string() != "Flowsurface" => {
+            custom.to_string()
+        }
+        _ => "Custom".to_string(),
+    }
 }
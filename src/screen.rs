@@ -1,4 +1,5 @@
 pub mod dashboard;
+pub mod scanner;
 
 /// خطاهای مربوط به داشبورد
 #[derive(thiserror::Error, Debug, Clone)]
@@ -10,21 +10,29 @@ use crate::{
     chart,
     screen::dashboard::tickers_table::TickersTable,
     style,
-    widget::toast::Toast,
+    widget::toast::{self, Toast},
     window::{self, Window},
 };
 use data::{
     UserTimezone,
-    layout::{WindowSpec, pane::ContentKind},
+    chart::Basis,
+    layout::{
+        WindowSpec,
+        pane::{ContentKind, LinkGroup},
+    },
+    orderflow::OrderFlowEvent,
+    panel::spread::SpreadEvent,
+    walls::WallEvent,
 };
 use exchange::{
-    Kline, PushFrequency, StreamPairKind, TickMultiplier, TickerInfo, Timeframe, Trade,
+    Kline, PushFrequency, StreamPairKind, TickMultiplier, Ticker, TickerInfo, TickerStats,
+    Timeframe, Trade,
     adapter::{
-        self, AdapterError, Exchange, PersistStreamKind, ResolvedStream, StreamConfig, StreamKind,
-        StreamTicksize, UniqueStreams, binance, bybit, hyperliquid, okex,
+        self, AdapterError, Exchange, KlinePriceSource, PersistStreamKind, ResolvedStream,
+        StreamConfig, StreamKind, StreamTicksize, UniqueStreams, binance, bitget, bybit, coinbase, gateio, hyperliquid, okex,
     },
     depth::Depth,
-    fetcher::{FetchRange, FetchedData},
+    fetcher::{FetchRange, FetchedData, TradeSource},
 };
 
 use iced::{
@@ -36,7 +44,13 @@ use iced::{
     },
 };
 use iced_futures::futures::TryFutureExt;
-use std::{collections::HashMap, path::PathBuf, time::Instant, vec};
+use rustc_hash::FxHashMap;
+use std::{
+    collections::{HashMap, VecDeque},
+    path::PathBuf,
+    time::Instant,
+    vec,
+};
 
 #[derive(Debug, Clone)]
 pub enum Message {
@@ -52,6 +66,29 @@ pub enum Message {
         data: FetchedData,
     },
     ResolveStreams(uuid::Uuid, Vec<PersistStreamKind>),
+    Undo,
+    /// Any mouse button went down (`true`) or up (`false`), only tracked with focus-follows-hover
+    PointerHeld(bool),
+}
+
+const UNDO_STACK_DEPTH: usize = 10;
+
+/// Serialized copy of a pane taken right before it was closed or reset
+struct PaneSnapshot {
+    pane: data::Pane,
+    origin: SnapshotOrigin,
+}
+
+enum SnapshotOrigin {
+    Closed {
+        sibling: pane_grid::Pane,
+        axis: pane_grid::Axis,
+        /// `None` when the sibling was itself a split, so `sibling` is only one of its panes
+        /// and the saved ratio doesn't describe a split around it
+        ratio: Option<f32>,
+        was_first: bool,
+    },
+    Replaced(pane_grid::Pane),
 }
 
 pub struct Dashboard {
@@ -60,6 +97,14 @@ pub struct Dashboard {
     pub popout: HashMap<window::Id, (pane_grid::State<pane::State>, WindowSpec)>,
     pub streams: UniqueStreams,
     layout_id: uuid::Uuid,
+    undo_stack: VecDeque<PaneSnapshot>,
+    pointer_held: bool,
+    /// Pane entered while a button was held; it takes focus once the drag or pan ends
+    deferred_hover: Option<(window::Id, pane_grid::Pane)>,
+    /// While a recording plays back, charts are fed only by recorded events
+    playback: bool,
+    /// The grid, popouts or a pane's streams changed since the last autosave check
+    changed: bool,
 }
 
 impl Default for Dashboard {
@@ -70,6 +115,11 @@ impl Default for Dashboard {
             streams: UniqueStreams::default(),
             popout: HashMap::new(),
             layout_id: uuid::Uuid::new_v4(),
+            undo_stack: VecDeque::new(),
+            pointer_held: false,
+            deferred_hover: None,
+            playback: false,
+            changed: false,
         }
     }
 }
@@ -87,6 +137,7 @@ pub enum Event {
         pane_id: uuid::Uuid,
         streams: Vec<PersistStreamKind>,
     },
+    RulerMeasured(Box<crate::modal::measurements::Entry>),
 }
 
 impl Dashboard {
@@ -136,6 +187,11 @@ impl Dashboard {
             streams: UniqueStreams::default(),
             popout,
             layout_id,
+            undo_stack: VecDeque::new(),
+            pointer_held: false,
+            deferred_hover: None,
+            playback: false,
+            changed: false,
         }
     }
 
@@ -183,6 +239,7 @@ impl Dashboard {
                 for (window_id, new_spec) in specs {
                     if let Some((_, spec)) = self.popout.get_mut(&window_id) {
                         *spec = new_spec;
+                        self.changed = true;
                     }
                 }
             }
@@ -200,16 +257,36 @@ impl Dashboard {
                     );
                 }
             },
+            Message::PointerHeld(held) => {
+                self.pointer_held = held;
+
+                let deferred = self.deferred_hover.take();
+                if !held && let Some(hovered) = deferred {
+                    self.focus = Some(hovered);
+                }
+            }
             Message::Pane(window, message) => match message {
                 pane::Message::PaneClicked(pane) => {
                     self.focus = Some((window, pane));
                 }
+                pane::Message::PaneHovered(pane) => {
+                    if data::layout::dashboard::is_focus_follows_hover() {
+                        // don't pull focus away from a pane that's being panned or dragged
+                        if self.pointer_held {
+                            self.deferred_hover = Some((window, pane));
+                        } else {
+                            self.focus = Some((window, pane));
+                        }
+                    }
+                }
                 pane::Message::PaneResized(pane_grid::ResizeEvent { split, ratio }) => {
                     self.panes.resize(split, ratio);
+                    self.changed = true;
                 }
                 pane::Message::PaneDragged(event) => {
                     if let pane_grid::DragEvent::Dropped { pane, target } = event {
                         self.panes.drop(pane, target);
+                        self.changed = true;
                     }
                 }
                 pane::Message::SplitPane(axis, pane) => {
@@ -223,11 +300,32 @@ impl Dashboard {
 
                     if Some(focus_pane).is_some() {
                         self.focus = Some((window, focus_pane.unwrap()));
+                        self.changed = true;
                     }
                 }
                 pane::Message::ClosePane(pane) => {
+                    let snapshot = self
+                        .panes
+                        .get(pane)
+                        .map(data::Pane::from)
+                        .zip(parent_split(self.panes.layout(), pane));
+
                     if let Some((_, sibling)) = self.panes.close(pane) {
                         self.focus = Some((window, sibling));
+                        self.changed = true;
+
+                        if let Some((ser_pane, (axis, ratio, was_first, direct_sibling))) = snapshot
+                        {
+                            self.push_undo(
+                                ser_pane,
+                                SnapshotOrigin::Closed {
+                                    sibling: direct_sibling.unwrap_or(sibling),
+                                    axis,
+                                    ratio: direct_sibling.map(|_| ratio),
+                                    was_first,
+                                },
+                            );
+                        }
                     }
                 }
                 pane::Message::MaximizePane(pane) => {
@@ -237,13 +335,19 @@ impl Dashboard {
                     self.panes.restore();
                 }
                 pane::Message::ReplacePane(pane) => {
-                    if let Some(pane) = self.panes.get_mut(pane) {
-                        *pane = pane::State::new();
+                    if let Some(state) = self.panes.get_mut(pane) {
+                        let ser_pane = data::Pane::from(&*state);
+                        *state = pane::State::new();
+
+                        self.push_undo(ser_pane, SnapshotOrigin::Replaced(pane));
+                        self.changed = true;
                     }
 
                     return (self.refresh_streams(main_window.id), None);
                 }
                 pane::Message::VisualConfigChanged(pane, cfg, to_sync) => {
+                    self.changed = true;
+
                     if to_sync {
                         if let Some(state) = self.get_pane(main_window.id, window, pane) {
                             let studies_cfg = state.content.studies();
@@ -276,6 +380,12 @@ impl Dashboard {
                                             ) | (
                                                 data::layout::pane::VisualConfig::Comparison(_),
                                                 pane::Content::Comparison(_)
+                                            ) | (
+                                                data::layout::pane::VisualConfig::DepthCurve(_),
+                                                pane::Content::DepthCurve(_)
+                                            ) | (
+                                                data::layout::pane::VisualConfig::Spread(_),
+                                                pane::Content::Spread(_)
                                             )
                                         ),
                                     };
@@ -302,8 +412,32 @@ impl Dashboard {
                         state.settings.visual_config = Some(cfg.clone());
                         state.content.change_visual_config(cfg);
                     }
+
+                    let mut streams_changed = false;
+                    let mut refetches = vec![];
+                    self.iter_all_panes_mut(main_window.id)
+                        .for_each(|(_, _, state)| {
+                            if let Some(stream) = state.sync_price_source() {
+                                refetches.push(kline_fetch_task(
+                                    *layout_id,
+                                    state.unique_id(),
+                                    stream,
+                                    None,
+                                    None,
+                                ));
+                                streams_changed = true;
+                            }
+                            streams_changed |= state.sync_higher_tf_stream();
+                        });
+
+                    if streams_changed {
+                        refetches.push(self.refresh_streams(main_window.id));
+                        return (Task::batch(refetches), None);
+                    }
                 }
                 pane::Message::SwitchLinkGroup(pane, group) => {
+                    self.changed = true;
+
                     if group.is_none() {
                         if let Some(state) = self.get_mut_pane(main_window.id, window, pane) {
                             state.link_group = None;
@@ -327,6 +461,11 @@ impl Dashboard {
                         state.modal = None;
 
                         if let Some(ticker_info) = maybe_ticker_info
+                            && !state.settings.pin_ticker
+                            && state
+                                .content
+                                .kind()
+                                .follows_group_ticker(ticker_info.market_type())
                             && state.stream_pair() != Some(ticker_info)
                         {
                             let pane_id = state.unique_id();
@@ -354,6 +493,8 @@ impl Dashboard {
                     return (self.merge_pane(main_window), None);
                 }
                 pane::Message::PaneEvent(pane, local) => {
+                    let playback = self.playback;
+
                     if let Some(state) = self.get_mut_pane(main_window.id, window, pane) {
                         let Some(effect) = state.update(local) else {
                             return (Task::none(), None);
@@ -364,6 +505,7 @@ impl Dashboard {
                             pane::Effect::RequestFetch(reqs) => request_fetch_many(
                                 state,
                                 *layout_id,
+                                playback,
                                 reqs.into_iter().map(|r| (r.req_id, r.fetch, r.stream)),
                             )
                             .chain(self.refresh_streams(main_window.id)),
@@ -373,6 +515,16 @@ impl Dashboard {
                             pane::Effect::FocusWidget(id) => {
                                 return (iced::widget::operation::focus(id), None);
                             }
+                            pane::Effect::SyncCrosshair(group, time) => {
+                                self.sync_crosshair(main_window.id, (window, pane), group, time);
+                                Task::none()
+                            }
+                            pane::Effect::RulerMeasured(entry) => {
+                                return (Task::none(), Some(Event::RulerMeasured(entry)));
+                            }
+                            pane::Effect::CopyToClipboard(contents) => {
+                                iced::clipboard::write(contents)
+                            }
                         };
                         return (task, None);
                     }
@@ -408,11 +560,92 @@ impl Dashboard {
             Message::Notification(toast) => {
                 return (Task::none(), Some(Event::Notification(toast)));
             }
+            Message::Undo => {
+                return (self.undo(main_window.id), None);
+            }
         }
 
         (Task::none(), None)
     }
 
+    fn push_undo(&mut self, pane: data::Pane, origin: SnapshotOrigin) {
+        if matches!(pane, data::Pane::Starter { .. }) {
+            return;
+        }
+
+        if self.undo_stack.len() == UNDO_STACK_DEPTH {
+            self.undo_stack.pop_front();
+        }
+        self.undo_stack.push_back(PaneSnapshot { pane, origin });
+    }
+
+    /// Restores the most recently closed or reset pane, back into its
+    /// original grid position if the surrounding layout still allows it
+    fn undo(&mut self, main_window: window::Id) -> Task<Message> {
+        let Some(snapshot) = self.undo_stack.pop_back() else {
+            return Task::none();
+        };
+
+        let Configuration::Pane(state) = crate::layout::configuration(snapshot.pane) else {
+            return Task::none();
+        };
+        self.changed = true;
+
+        let restored = match snapshot.origin {
+            SnapshotOrigin::Replaced(pane) if self.panes.get(pane).is_some() => {
+                if let Some(current) = self.panes.get_mut(pane) {
+                    *current = state;
+                }
+                Some(pane)
+            }
+            SnapshotOrigin::Replaced(_) => {
+                self.restore_split(None, pane_grid::Axis::Vertical, None, false, state)
+            }
+            SnapshotOrigin::Closed {
+                sibling,
+                axis,
+                ratio,
+                was_first,
+            } => self.restore_split(Some(sibling), axis, ratio, was_first, state),
+        };
+
+        if let Some(pane) = restored {
+            self.focus = Some((main_window, pane));
+        }
+
+        self.refresh_streams(main_window)
+    }
+
+    fn restore_split(
+        &mut self,
+        sibling: Option<pane_grid::Pane>,
+        axis: pane_grid::Axis,
+        ratio: Option<f32>,
+        was_first: bool,
+        state: pane::State,
+    ) -> Option<pane_grid::Pane> {
+        let exact = sibling.filter(|pane| self.panes.get(*pane).is_some());
+
+        let Some(target) = exact.or_else(|| self.panes.iter().last().map(|(pane, _)| *pane)) else {
+            let (panes, pane) = pane_grid::State::new(state);
+            self.panes = panes;
+            return Some(pane);
+        };
+
+        let (pane, split) = self.panes.split(axis, target, state)?;
+
+        if exact.is_some() {
+            if was_first {
+                self.panes.swap(pane, target);
+            }
+            if let Some(ratio) = ratio {
+                self.panes.resize(split, ratio);
+            }
+        }
+
+        Some(pane)
+    }
+
     fn new_pane(
         &mut self,
         axis: pane_grid::Axis,
@@ -485,6 +718,7 @@ impl Dashboard {
 
             let (state, id) = pane_grid::State::new(pane);
             self.popout.insert(window, (state, WindowSpec::default()));
+            self.changed = true;
 
             return task.then(move |window| {
                 Task::done(Message::Pane(window, pane::Message::PaneClicked(id)))
@@ -502,6 +736,7 @@ impl Dashboard {
                 .and_then(|(mut panes, _)| panes.panes.remove(&pane))
         {
             let task = self.new_pane(pane_grid::Axis::Horizontal, main_window, Some(pane_state));
+            self.changed = true;
 
             return Task::batch(vec![window::close(window), task]);
         }
@@ -687,6 +922,7 @@ impl Dashboard {
 
             let streams = state.set_content_and_streams(vec![ticker_info], content_kind);
             self.streams.extend(streams.iter());
+            self.changed = true;
 
             for stream in &streams {
                 if let StreamKind::Kline { .. } = stream {
@@ -715,7 +951,10 @@ impl Dashboard {
             && let Some(state) = self.get_mut_pane(main_window, window, selected_pane)
         {
             let previous_ticker = state.stream_pair();
-            if previous_ticker.is_some() && previous_ticker != Some(ticker_info) {
+            if previous_ticker.is_some()
+                && previous_ticker != Some(ticker_info)
+                && !state.settings.pin_ticker
+            {
                 state.link_group = None;
             }
 
@@ -723,6 +962,7 @@ impl Dashboard {
 
             let pane_id = state.unique_id();
             self.streams.extend(streams.iter());
+            self.changed = true;
 
             for stream in &streams {
                 if let StreamKind::Kline { .. } = stream {
@@ -737,6 +977,23 @@ impl Dashboard {
         )))
     }
 
+    /// Draws the hovered time of one pane as a vertical line on the other panes of its group
+    fn sync_crosshair(
+        &mut self,
+        main_window: window::Id,
+        origin: (window::Id, pane_grid::Pane),
+        group: LinkGroup,
+        time: Option<u64>,
+    ) {
+        self.iter_all_panes_mut(main_window)
+            .filter(|(window, pane, state)| {
+                state.link_group == Some(group) && (*window, *pane) != origin
+            })
+            .for_each(|(_, _, state)| {
+                state.content.sync_crosshair(time.map(|time| (time, group)));
+            });
+    }
+
     pub fn switch_tickers_in_group(
         &mut self,
         main_window: window::Id,
@@ -749,30 +1006,59 @@ impl Dashboard {
             self.focus = Some((main_window, *pane_id));
         }
 
+        // a pinned pane switches on its own and stays in its group
         let link_group = self.focus.and_then(|(window, pane)| {
             self.get_pane(main_window, window, pane)
+                .filter(|state| !state.settings.pin_ticker)
                 .and_then(|state| state.link_group)
         });
 
         if let Some(group) = link_group {
+            let market = ticker_info.market_type();
+            let focus = self.focus;
+
+            let mut kept = vec![];
             let pane_infos: Vec<(window::Id, pane_grid::Pane, ContentKind)> = self
                 .iter_all_panes_mut(main_window)
+                .filter(|(_, _, state)| state.link_group == Some(group))
                 .filter_map(|(window, pane, state)| {
-                    if state.link_group == Some(group) {
-                        Some((window, pane, state.content.kind()))
-                    } else {
-                        None
+                    let content_kind = state.content.kind();
+
+                    if Some((window, pane)) == focus {
+                        return Some((window, pane, content_kind));
+                    }
+                    if state.settings.pin_ticker {
+                        return None;
+                    }
+                    if !content_kind.follows_group_ticker(market) {
+                        if content_kind != ContentKind::Starter {
+                            kept.push(content_kind);
+                        }
+                        return None;
                     }
+                    Some((window, pane, content_kind))
                 })
                 .collect();
 
-            let tasks: Vec<Task<Message>> = pane_infos
+            let mut tasks: Vec<Task<Message>> = pane_infos
                 .iter()
                 .map(|(window, pane, content_kind)| {
                     self.init_pane(main_window, *window, *pane, ticker_info, *content_kind)
                 })
                 .collect();
 
+            if !kept.is_empty() {
+                let kinds = kept
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                tasks.push(Task::done(Message::Notification(Toast::warn(format!(
+                    "Kept the ticker of linked panes that can't show {}: {kinds}",
+                    ticker_info.ticker
+                )))));
+            }
+
             Task::batch(tasks)
         } else if let Some((window, pane)) = self.focus {
             if let Some(state) = self.get_mut_pane(main_window, window, pane) {
@@ -795,7 +1081,12 @@ impl Dashboard {
 
         self.iter_all_panes_mut(main_window.id)
             .for_each(|(_, _, state)| {
-                if let pane::Content::Kline { chart, kind, .. } = &mut state.content
+                let supports_fetch = state
+                    .stream_pair()
+                    .is_some_and(|info| info.exchange().supports_trade_fetch());
+
+                if supports_fetch
+                    && let pane::Content::Kline { chart, kind, .. } = &mut state.content
                     && matches!(kind, data::chart::KlineChartKind::Footprint { .. })
                     && let Some(c) = chart
                 {
@@ -815,6 +1106,15 @@ impl Dashboard {
         data: FetchedData,
         stream_type: StreamKind,
     ) -> Task<Message> {
+        let is_current = self
+            .get_mut_pane_state_by_uuid(main_window, pane_id)
+            .is_some_and(|state| is_fetch_current(state, &stream_type));
+
+        if !is_current {
+            log::debug!("Discarding fetched data for superseded stream {stream_type:?}");
+            return Task::none();
+        }
+
         match data {
             FetchedData::Trades { batch, until_time } => {
                 let last_trade_time = batch.last().map_or(0, |trade| trade.time);
@@ -839,6 +1139,35 @@ impl Dashboard {
                     }
                 }
             }
+            FetchedData::KlineBackfill { batch, outcome } => {
+                if let Some(pane_state) = self.get_mut_pane_state_by_uuid(main_window, pane_id)
+                    && let pane::Content::Kline { chart: Some(c), .. } = &mut pane_state.content
+                {
+                    // pages already queued when the backfill got cancelled
+                    if !c.is_backfilling() {
+                        return Task::none();
+                    }
+
+                    c.insert_backfill_klines(&batch, outcome.is_some());
+
+                    match outcome {
+                        None => {
+                            let (count, progress) = c.backfill_progress();
+                            pane_state.status = pane::Status::Loading(
+                                exchange::fetcher::InfoKind::BackfillingKlines(count, progress),
+                            );
+                        }
+                        Some(Ok(())) => pane_state.status = pane::Status::Ready,
+                        Some(Err(err)) => {
+                            return self.handle_error(
+                                Some(pane_id),
+                                &DashboardError::Fetch(err),
+                                main_window,
+                            );
+                        }
+                    }
+                }
+            }
             FetchedData::Klines { data, req_id } => {
                 if let Some(pane_state) = self.get_mut_pane_state_by_uuid(main_window, pane_id) {
                     pane_state.status = pane::Status::Ready;
@@ -846,9 +1175,16 @@ impl Dashboard {
                     if let StreamKind::Kline {
                         timeframe,
                         ticker_info,
+                        source,
                     } = stream_type
                     {
-                        pane_state.insert_hist_klines(req_id, timeframe, ticker_info, &data);
+                        pane_state.insert_hist_klines(
+                            req_id,
+                            timeframe,
+                            ticker_info,
+                            source,
+                            &data,
+                        );
                     }
                 }
             }
@@ -861,6 +1197,12 @@ impl Dashboard {
                     }
                 }
             }
+            FetchedData::Funding { data, req_id } => {
+                if let Some(pane_state) = self.get_mut_pane_state_by_uuid(main_window, pane_id) {
+                    pane_state.status = pane::Status::Ready;
+                    pane_state.insert_hist_funding(req_id, &data);
+                }
+            }
         }
 
         Task::none()
@@ -881,22 +1223,27 @@ impl Dashboard {
                 )
             })?;
 
-        match &mut pane_state.status {
-            pane::Status::Loading(exchange::fetcher::InfoKind::FetchingTrades(count)) => {
-                *count += trades.len();
-            }
-            _ => {
-                pane_state.status = pane::Status::Loading(
-                    exchange::fetcher::InfoKind::FetchingTrades(trades.len()),
-                );
-            }
-        }
+        let fetched = match pane_state.status {
+            pane::Status::Loading(exchange::fetcher::InfoKind::FetchingTrades(count, _)) => count,
+            _ => 0,
+        };
 
         match &mut pane_state.content {
             pane::Content::Kline { chart, .. } => {
                 if let Some(c) = chart {
+                    // batches already queued when the fetch got cancelled
+                    if !c.is_fetching_trades() {
+                        return Ok(());
+                    }
+
                     c.insert_raw_trades(trades.to_owned(), is_batches_done);
 
+                    pane_state.status =
+                        pane::Status::Loading(exchange::fetcher::InfoKind::FetchingTrades(
+                            fetched + trades.len(),
+                            c.trade_fetch_progress(),
+                        ));
+
                     if is_batches_done {
                         pane_state.status = pane::Status::Ready;
                     }
@@ -918,19 +1265,39 @@ impl Dashboard {
         stream: &StreamKind,
         kline: &Kline,
         main_window: window::Id,
-    ) -> Task<Message> {
+    ) -> (Task<Message>, Vec<SpreadEvent>) {
         let mut found_match = false;
+        let mut spread_alerts = vec![];
+        let now = Instant::now();
 
         self.iter_all_panes_mut(main_window)
             .for_each(|(_, _, pane_state)| {
                 if pane_state.matches_stream(stream) {
+                    pane_state.mark_data_received(now);
+
                     match &mut pane_state.content {
                         pane::Content::Kline { chart: Some(c), .. } => {
-                            c.update_latest_kline(kline);
+                            if c.higher_tf_stream().as_ref() == Some(stream) {
+                                c.update_higher_tf_kline(kline);
+                            } else {
+                                c.update_latest_kline(kline);
+                            }
                         }
                         pane::Content::Comparison(Some(c)) => {
                             c.update_latest_kline(&stream.ticker_info(), kline);
                         }
+                        pane::Content::FundingChart(Some(p)) => {
+                            p.update_latest_kline(stream, kline);
+                        }
+                        pane::Content::Spread(Some(p)) => {
+                            if let Some(event) = p.update_latest_kline(&stream.ticker_info(), kline)
+                            {
+                                pane_state.notifications.push(Toast::new(
+                                    toast::Notification::Info(p.alert_text(&event)),
+                                ));
+                                spread_alerts.push(event);
+                            }
+                        }
                         _ => {}
                     }
                     found_match = true;
@@ -938,13 +1305,33 @@ impl Dashboard {
             });
 
         if found_match {
-            Task::none()
+            (Task::none(), spread_alerts)
         } else {
             log::debug!("{stream:?} stream had no matching panes - dropping");
-            self.refresh_streams(main_window)
+            (self.refresh_streams(main_window), spread_alerts)
         }
     }
 
+    /// Forwards mark prices from the tickers table stats to kline charts of matching tickers
+    pub fn update_mark_prices(
+        &mut self,
+        main_window: window::Id,
+        stats: &HashMap<Ticker, TickerStats>,
+    ) {
+        self.iter_all_panes_mut(main_window)
+            .for_each(|(_, _, pane_state)| {
+                let Some(ticker_info) = pane_state.stream_pair() else {
+                    return;
+                };
+
+                if let pane::Content::Kline { chart: Some(c), .. } = &mut pane_state.content
+                    && let Some(ticker_stats) = stats.get(&ticker_info.ticker)
+                {
+                    c.set_mark_price(ticker_stats.mark_price);
+                }
+            });
+    }
+
     pub fn update_depth_and_trades(
         &mut self,
         stream: &StreamKind,
@@ -952,21 +1339,41 @@ impl Dashboard {
         depth: &Depth,
         trades_buffer: &[Trade],
         main_window: window::Id,
-    ) -> Task<Message> {
+    ) -> (Task<Message>, Vec<WallEvent>) {
         let mut found_match = false;
+        let mut wall_alerts = vec![];
+        let received_at = chrono::Utc::now().timestamp_millis() as u64;
+        let now = Instant::now();
 
         self.iter_all_panes_mut(main_window)
             .for_each(|(_, _, pane_state)| {
                 if pane_state.matches_stream(stream) {
+                    pane_state.mark_data_received(now);
+
+                    let mut wall_events = vec![];
+                    let mut notify_walls = false;
+                    let mut order_flow_events = vec![];
+
+                    pane_state.tape_speed.push(trades_buffer, received_at);
+                    pane_state.update_depth_totals(depth);
+                    pane_state.update_spread_history(depth, received_at);
+
                     match &mut pane_state.content {
                         pane::Content::Heatmap { chart, .. } => {
                             if let Some(c) = chart {
-                                c.insert_datapoint(trades_buffer, depth_update_t, depth);
+                                wall_events =
+                                    c.insert_datapoint(trades_buffer, depth_update_t, depth);
+                                notify_walls = c.visual_config().walls.notify;
                             }
                         }
                         pane::Content::Kline { chart, .. } => {
                             if let Some(c) = chart {
-                                c.insert_trades_buffer(trades_buffer);
+                                order_flow_events = c.insert_trades_buffer(trades_buffer);
+                                if !c.visual_config().order_flow.notify {
+                                    order_flow_events.clear();
+                                }
+                                c.apply_trade_retention(depth_update_t);
+                                c.set_book_top(depth);
                             }
                         }
                         pane::Content::TimeAndSales(panel) => {
@@ -979,20 +1386,56 @@ impl Dashboard {
                                 panel.insert_buffers(depth_update_t, depth, trades_buffer);
                             }
                         }
+                        pane::Content::DepthCurve(panel) => {
+                            if let Some(panel) = panel {
+                                wall_events = panel.insert_depth(depth, depth_update_t);
+                                notify_walls = panel.config.walls.notify;
+                            }
+                        }
                         _ => {
                             log::error!("No chart found for the stream: {stream:?}");
                         }
                     }
+
+                    if notify_walls && !wall_events.is_empty() {
+                        let ticker_info = stream.ticker_info();
+
+                        for event in &wall_events {
+                            pane_state
+                                .notifications
+                                .push(Toast::new(toast::Notification::Info(wall_alert_text(
+                                    event,
+                                    &ticker_info,
+                                ))));
+                        }
+                        wall_alerts.extend(wall_events);
+                    }
+
+                    if !order_flow_events.is_empty() {
+                        let ticker_info = stream.ticker_info();
+
+                        for event in &order_flow_events {
+                            pane_state
+                                .notifications
+                                .push(Toast::new(toast::Notification::Info(order_flow_text(
+                                    event,
+                                    &ticker_info,
+                                ))));
+                        }
+                    }
+
                     found_match = true;
                 }
             });
 
-        if found_match {
+        let task = if found_match {
             Task::none()
         } else {
             log::debug!("No matching pane found for the stream: {stream:?}");
             self.refresh_streams(main_window)
-        }
+        };
+
+        (task, wall_alerts)
     }
 
     pub fn invalidate_all_panes(&mut self, main_window: window::Id) {
@@ -1002,9 +1445,43 @@ impl Dashboard {
             });
     }
 
+    /// Whether anything saved with the layout changed since the last call, including the
+    /// settings of its panes
+    pub fn take_changed(&mut self, main_window: window::Id) -> bool {
+        let panes = self
+            .iter_all_panes_mut(main_window)
+            .fold(false, |changed, (_, _, state)| {
+                state.take_changed() | changed
+            });
+
+        std::mem::take(&mut self.changed) | panes
+    }
+
+    /// Marks whether a recording is being played back
+    pub fn set_playback(&mut self, active: bool) {
+        self.playback = active;
+    }
+
+    /// Rebuilds every stream-bound pane with empty content, e.g. before a recorded session
+    /// is played back over it; outside playback candle charts refetch their history on the
+    /// next tick
+    pub fn reset_pane_contents(&mut self, main_window: window::Id) {
+        self.iter_all_panes_mut(main_window)
+            .for_each(|(_, _, state)| match state.stream_pair_kind() {
+                Some(StreamPairKind::MultiSource(tickers)) => {
+                    state.set_content_and_streams(tickers, state.content.kind());
+                }
+                Some(StreamPairKind::SingleSource(ticker)) => {
+                    state.set_content_and_streams(vec![ticker], state.content.kind());
+                }
+                None => {}
+            });
+    }
+
     pub fn tick(&mut self, now: Instant, main_window: window::Id) -> Task<Message> {
         let mut tasks = vec![];
         let layout_id = self.layout_id;
+        let playback = self.playback;
 
         self.iter_all_panes_mut(main_window)
             .for_each(|(_window_id, _pane, state)| match state.tick(now) {
@@ -1017,11 +1494,21 @@ impl Dashboard {
                         tasks.push(request_fetch_many(
                             state,
                             layout_id,
+                            playback,
+                            reqs.into_iter().map(|r| (r.req_id, r.fetch, r.stream)),
+                        ));
+                    }
+                },
+                Some(pane::Action::Panel(action)) => match action {
+                    panel::Action::RequestFetch(reqs) => {
+                        tasks.push(request_fetch_many(
+                            state,
+                            layout_id,
+                            playback,
                             reqs.into_iter().map(|r| (r.req_id, r.fetch, r.stream)),
                         ));
                     }
                 },
-                Some(pane::Action::Panel(_action)) => {}
                 Some(pane::Action::ResolveStreams(streams)) => {
                     tasks.push(Task::done(Message::ResolveStreams(
                         state.unique_id(),
@@ -1051,11 +1538,59 @@ impl Dashboard {
     ) -> Task<Message> {
         if let Some(state) = self.get_mut_pane_state_by_uuid(main_window, pane_id) {
             state.streams = ResolvedStream::Ready(streams.clone());
+            state.sync_higher_tf_stream();
         }
         self.refresh_streams(main_window)
     }
 
-    pub fn market_subscriptions(&self) -> Subscription<exchange::Event> {
+    /// Finest tick multiplier among the panes using each ticker's depth stream, so a shared
+    /// stream still fetches enough levels for its most detailed view
+    fn finest_depth_multipliers(&self) -> FxHashMap<exchange::Ticker, TickMultiplier> {
+        let mut finest: FxHashMap<exchange::Ticker, TickMultiplier> = FxHashMap::default();
+
+        let states = self.panes.iter().map(|(_, state)| state).chain(
+            self.popout
+                .values()
+                .flat_map(|(panes, _)| panes.iter().map(|(_, state)| state)),
+        );
+
+        for state in states {
+            let multiplier = state.settings.tick_multiply.unwrap_or(TickMultiplier(1));
+
+            for stream in state.streams.ready_iter().into_iter().flatten() {
+                if let StreamKind::DepthAndTrades { ticker_info, .. } = stream {
+                    finest
+                        .entry(ticker_info.ticker)
+                        .and_modify(|m| m.0 = m.0.min(multiplier.0))
+                        .or_insert(multiplier);
+                }
+            }
+        }
+
+        finest
+    }
+
+    /// Tickers of every depth and kline stream currently in use, may contain duplicates
+    pub fn streamed_tickers(&self) -> Vec<TickerInfo> {
+        self.streams
+            .combined_used()
+            .flat_map(|(_, specs)| {
+                specs
+                    .depth
+                    .iter()
+                    .map(|(info, _, _)| *info)
+                    .chain(specs.kline.iter().map(|(info, _)| *info))
+                    .chain(specs.price_kline.iter().map(|(info, _, _)| *info))
+            })
+            .collect()
+    }
+
+    /// `reconnects` counts forced reconnects per ticker; changing it restarts the streams
+    /// carrying that ticker
+    pub fn market_subscriptions(
+        &self,
+        reconnects: &FxHashMap<exchange::Ticker, u32>,
+    ) -> Subscription<exchange::Event> {
         let unique_streams = self
             .streams
             .combined_used()
@@ -1071,7 +1606,11 @@ impl Dashboard {
                                 StreamTicksize::Client => None,
                                 StreamTicksize::ServerSide(tick_mltp) => Some(*tick_mltp),
                             };
+                            let epoch = reconnects.get(&ticker.ticker).copied().unwrap_or(0);
+
                             depth_subscription(*ticker, tick_mltp, *push_freq)
+                                .with(epoch)
+                                .map(|(_, event)| event)
                         })
                         .collect::<Vec<_>>();
 
@@ -1087,7 +1626,30 @@ impl Dashboard {
                     .collect::<Vec<_>>();
 
                 if !kline_params.is_empty() {
-                    subs.push(kline_subscription(exchange, kline_params));
+                    let epoch = kline_params
+                        .iter()
+                        .map(|(ticker, _)| reconnects.get(&ticker.ticker).copied().unwrap_or(0))
+                        .sum::<u32>();
+
+                    subs.push(
+                        kline_subscription(exchange, kline_params)
+                            .with(epoch)
+                            .map(|(_, event)| event),
+                    );
+                }
+
+                if !specs.price_kline.is_empty() {
+                    let epoch = specs
+                        .price_kline
+                        .iter()
+                        .map(|(ticker, _, _)| reconnects.get(&ticker.ticker).copied().unwrap_or(0))
+                        .sum::<u32>();
+
+                    subs.push(
+                        price_kline_subscription(exchange, specs.price_kline.clone())
+                            .with(epoch)
+                            .map(|(_, event)| event),
+                    );
                 }
 
                 subs
@@ -1103,10 +1665,46 @@ impl Dashboard {
             .flat_map(|(_, _, pane_state)| pane_state.streams.ready_iter().into_iter().flatten());
         self.streams = UniqueStreams::from(all_pane_streams);
 
+        // Binance reads the finest multiplier whenever it fetches a depth snapshot; keeping it
+        // out of the stream config means changing it doesn't restart the websocket
+        binance::set_depth_multipliers(
+            self.finest_depth_multipliers()
+                .into_iter()
+                .filter(|(ticker, _)| ticker.exchange.is_depth_limit_adaptive())
+                .collect(),
+        );
+
         Task::none()
     }
 }
 
+/// Finds the split directly holding `pane`, returning its axis, ratio, whether `pane`
+/// is the first child and the sibling pane when the other side is a single pane
+fn parent_split(
+    node: &pane_grid::Node,
+    pane: pane_grid::Pane,
+) -> Option<(pane_grid::Axis, f32, bool, Option<pane_grid::Pane>)> {
+    let pane_grid::Node::Split {
+        axis, ratio, a, b, ..
+    } = node
+    else {
+        return None;
+    };
+
+    let as_pane = |node: &pane_grid::Node| match node {
+        pane_grid::Node::Pane(p) => Some(*p),
+        pane_grid::Node::Split { .. } => None,
+    };
+
+    if as_pane(a) == Some(pane) {
+        Some((*axis, *ratio, true, as_pane(b)))
+    } else if as_pane(b) == Some(pane) {
+        Some((*axis, *ratio, false, as_pane(a)))
+    } else {
+        parent_split(a, pane).or_else(|| parent_split(b, pane))
+    }
+}
+
 fn request_fetch(
     state: &mut pane::State,
     layout_id: uuid::Uuid,
@@ -1161,6 +1759,64 @@ fn request_fetch(
                 return oi_fetch_task(layout_id, pane_uid, stream, Some(req_id), Some((from, to)));
             }
         }
+        FetchRange::Funding(from, to) => {
+            let kline_stream = state.streams.find_ready_map(|stream| {
+                if let StreamKind::Kline { .. } = stream {
+                    Some(*stream)
+                } else {
+                    None
+                }
+            });
+
+            if let Some(stream) = kline_stream {
+                return funding_fetch_task(layout_id, pane_id, stream, Some(req_id), (from, to));
+            }
+        }
+        FetchRange::KlineBackfill(from, to) => {
+            let kline_stream = state.streams.find_ready_map(|stream| {
+                if let StreamKind::Kline {
+                    ticker_info,
+                    timeframe,
+                    source,
+                } = stream
+                {
+                    Some((*ticker_info, *timeframe, *source, *stream))
+                } else {
+                    None
+                }
+            });
+
+            if let Some((ticker_info, timeframe, source, stream)) = kline_stream {
+                let (task, handle) = Task::sip(
+                    fetch_klines_backfill(ticker_info, timeframe, source, (from, to)),
+                    move |batch| Message::DistributeFetchedData {
+                        layout_id,
+                        pane_id,
+                        data: FetchedData::KlineBackfill {
+                            batch,
+                            outcome: None,
+                        },
+                        stream,
+                    },
+                    move |result| Message::DistributeFetchedData {
+                        layout_id,
+                        pane_id,
+                        data: FetchedData::KlineBackfill {
+                            batch: vec![],
+                            outcome: Some(result.map_err(|err| err.to_user_message().to_string())),
+                        },
+                        stream,
+                    },
+                )
+                .abortable();
+
+                if let pane::Content::Kline { chart: Some(c), .. } = &mut state.content {
+                    c.set_backfill_handle(handle.abort_on_drop());
+                }
+
+                return task;
+            }
+        }
         FetchRange::Trades(from_time, to_time) => {
             let trade_info = state.streams.find_ready_map(|stream| {
                 if let StreamKind::DepthAndTrades { ticker_info, .. } = stream {
@@ -1171,16 +1827,31 @@ fn request_fetch(
             });
 
             if let Some((ticker_info, pane_id, stream)) = trade_info {
-                let is_binance = matches!(
-                    ticker_info.exchange(),
-                    Exchange::BinanceSpot | Exchange::BinanceLinear | Exchange::BinanceInverse
-                );
+                let fetch_cfg = match &state.content {
+                    pane::Content::Kline { chart: Some(c), .. } => {
+                        Some((c.trade_fetch_config(), c.basis()))
+                    }
+                    _ => None,
+                };
 
-                if is_binance {
-                    let data_path = data::data_path(Some("market_data/binance/"));
+                if ticker_info.exchange().supports_trade_fetch()
+                    && let Some((fetch_cfg, basis)) = fetch_cfg
+                {
+                    let data_path = data::data_path(Some("market_data/"));
+
+                    let step_ms = match basis {
+                        Basis::Time(tf) => fetch_cfg.bucket_timeframe(tf).to_milliseconds(),
+                        Basis::Tick(_) => to_time.saturating_sub(from_time),
+                    };
 
                     let (task, handle) = Task::sip(
-                        fetch_trades_batched(ticker_info, from_time, to_time, data_path),
+                        fetch_trades_batched(
+                            ticker_info,
+                            (from_time, to_time),
+                            data_path,
+                            fetch_cfg.source,
+                            step_ms,
+                        ),
                         move |batch| {
                             let data = FetchedData::Trades {
                                 batch,
@@ -1218,13 +1889,85 @@ fn request_fetch(
     Task::none()
 }
 
+/// Whether a fetch issued for `stream` still targets what the pane is showing; results for a
+/// ticker or timeframe the pane has switched away from in the meantime are stale
+fn is_fetch_current(state: &pane::State, stream: &StreamKind) -> bool {
+    state.streams.ready_iter().is_some_and(|mut streams| {
+        streams.any(|current| match (current, stream) {
+            (
+                StreamKind::Kline {
+                    ticker_info,
+                    timeframe,
+                    source,
+                },
+                StreamKind::Kline {
+                    ticker_info: fetched_ticker,
+                    timeframe: fetched_timeframe,
+                    source: fetched_source,
+                },
+            ) => {
+                ticker_info == fetched_ticker
+                    && timeframe == fetched_timeframe
+                    && source == fetched_source
+            }
+            (
+                StreamKind::DepthAndTrades { ticker_info, .. },
+                StreamKind::DepthAndTrades {
+                    ticker_info: fetched_ticker,
+                    ..
+                },
+            ) => ticker_info == fetched_ticker,
+            _ => false,
+        })
+    })
+}
+
+fn wall_alert_text(event: &WallEvent, ticker_info: &TickerInfo) -> String {
+    let (action, side, price, qty) = match *event {
+        WallEvent::Appeared { side, price, qty } => ("appeared", side, price, qty),
+        WallEvent::Pulled { side, price, qty } => ("pulled", side, price, qty),
+    };
+
+    format!(
+        "{} {side} wall {action} at {} ({})",
+        ticker_info.ticker.display_symbol_and_type().0,
+        price.to_string(ticker_info.min_ticksize),
+        data::util::format_volume(qty),
+    )
+}
+
+fn order_flow_text(event: &OrderFlowEvent, ticker_info: &TickerInfo) -> String {
+    let signal = event.signal;
+    let side = if signal.is_buy { "buy" } else { "sell" };
+
+    format!(
+        "{} {} ({side}) at {} ({})",
+        ticker_info.ticker.display_symbol_and_type().0,
+        signal.kind,
+        signal.price.to_string(ticker_info.min_ticksize),
+        data::util::format_volume(signal.qty),
+    )
+}
+
+/// During playback, candle and open interest history isn't fetched so live data can't mix
+/// with the recorded klines
 fn request_fetch_many(
     state: &mut pane::State,
     layout_id: uuid::Uuid,
+    playback: bool,
     reqs: impl IntoIterator<Item = (uuid::Uuid, FetchRange, Option<StreamKind>)>,
 ) -> Task<Message> {
     let tasks = reqs
         .into_iter()
+        .filter(|(_, fetch, _)| {
+            !playback
+                || !matches!(
+                    fetch,
+                    FetchRange::Kline(..)
+                        | FetchRange::KlineBackfill(..)
+                        | FetchRange::OpenInterest(..)
+                )
+        })
         .map(|(req_id, fetch, stream)| request_fetch(state, layout_id, req_id, fetch, stream))
         .collect::<Vec<_>>();
     Task::batch(tasks)
@@ -1246,6 +1989,7 @@ fn oi_fetch_task(
         StreamKind::Kline {
             ticker_info,
             timeframe,
+            ..
         } => Task::perform(
             adapter::fetch_open_interest(ticker_info.ticker, timeframe, range)
                 .map_err(|err| format!("{err}")),
@@ -1268,6 +2012,41 @@ fn oi_fetch_task(
     update_status.chain(fetch_task)
 }
 
+fn funding_fetch_task(
+    layout_id: uuid::Uuid,
+    pane_id: uuid::Uuid,
+    stream: StreamKind,
+    req_id: Option<uuid::Uuid>,
+    range: (u64, u64),
+) -> Task<Message> {
+    let update_status = Task::done(Message::ChangePaneStatus(
+        pane_id,
+        pane::Status::Loading(exchange::fetcher::InfoKind::FetchingFunding),
+    ));
+
+    let fetch_task = Task::perform(
+        adapter::fetch_funding_history(stream.ticker_info().ticker, Some(range))
+            .map_err(|err| format!("{err}")),
+        move |result| match result {
+            Ok(rates) => {
+                let data = FetchedData::Funding {
+                    data: rates,
+                    req_id,
+                };
+                Message::DistributeFetchedData {
+                    layout_id,
+                    pane_id,
+                    data,
+                    stream,
+                }
+            }
+            Err(err) => Message::ErrorOccurred(Some(pane_id), DashboardError::Fetch(err)),
+        },
+    );
+
+    update_status.chain(fetch_task)
+}
+
 fn kline_fetch_task(
     layout_id: uuid::Uuid,
     pane_id: uuid::Uuid,
@@ -1284,8 +2063,9 @@ fn kline_fetch_task(
         StreamKind::Kline {
             ticker_info,
             timeframe,
+            source,
         } => Task::perform(
-            adapter::fetch_klines(ticker_info, timeframe, range)
+            adapter::fetch_klines(ticker_info, timeframe, source, range)
                 .map_err(|err| err.to_user_message()),
             move |result| match result {
                 Ok(klines) => {
@@ -1311,27 +2091,66 @@ fn kline_fetch_task(
     update_status.chain(fetch_task)
 }
 
+/// Fetches trades for the range and hands them over one `step_ms` window at a time,
+/// so footprints fill in window by window while the download is running
 pub fn fetch_trades_batched(
     ticker_info: TickerInfo,
-    from_time: u64,
-    to_time: u64,
+    (from_time, to_time): (u64, u64),
     data_path: PathBuf,
+    source: TradeSource,
+    step_ms: u64,
 ) -> impl Straw<(), Vec<Trade>, AdapterError> {
     sipper(async move |mut progress| {
+        let step_ms = step_ms.max(1);
+
         let mut latest_trade_t = from_time;
+        let mut window_end = (from_time / step_ms + 1) * step_ms;
+        let mut pending: Vec<Trade> = vec![];
 
         while latest_trade_t < to_time {
-            match binance::fetch_trades(ticker_info, latest_trade_t, data_path.clone()).await {
-                Ok(batch) => {
-                    if batch.is_empty() {
-                        break;
-                    }
+            let batch =
+                adapter::fetch_trades(ticker_info, latest_trade_t, data_path.clone(), source)
+                    .await?;
 
-                    latest_trade_t = batch.last().map_or(latest_trade_t, |trade| trade.time);
+            let Some(last_trade_t) = batch.last().map(|trade| trade.time) else {
+                break;
+            };
 
-                    let () = progress.send(batch).await;
-                }
-                Err(err) => return Err(err),
+            latest_trade_t = last_trade_t;
+            pending.extend(batch);
+
+            if latest_trade_t >= window_end.min(to_time) {
+                window_end = (latest_trade_t / step_ms + 1) * step_ms;
+
+                let () = progress.send(std::mem::take(&mut pending)).await;
+            }
+        }
+
+        if !pending.is_empty() {
+            let () = progress.send(pending).await;
+        }
+
+        Ok(())
+    })
+}
+
+/// Pages through `fetch_klines` from the end of the range backwards, handing over each page
+/// sorted and without candles that an earlier page already delivered
+pub fn fetch_klines_backfill(
+    ticker_info: TickerInfo,
+    timeframe: Timeframe,
+    source: KlinePriceSource,
+    range: (u64, u64),
+) -> impl Straw<(), Vec<Kline>, AdapterError> {
+    sipper(async move |mut progress| {
+        let mut backfill = exchange::fetcher::KlineBackfill::new(range, timeframe);
+
+        while let Some(page) = backfill.next_page() {
+            let klines = adapter::fetch_klines(ticker_info, timeframe, source, Some(page)).await?;
+            let batch = backfill.accept(page, klines);
+
+            if !batch.is_empty() {
+                let () = progress.send(batch).await;
             }
         }
 
@@ -1372,6 +2191,24 @@ pub fn depth_subscription(
                 |cfg: &StreamConfig<TickerInfo>| okex::connect_market_stream(cfg.id, cfg.push_freq);
             Subscription::run_with(config, builder)
         }
+        Exchange::GateioLinear | Exchange::GateioSpot => {
+            let builder = |cfg: &StreamConfig<TickerInfo>| {
+                gateio::connect_market_stream(cfg.id, cfg.push_freq)
+            };
+            Subscription::run_with(config, builder)
+        }
+        Exchange::CoinbaseSpot => {
+            let builder = |cfg: &StreamConfig<TickerInfo>| {
+                coinbase::connect_market_stream(cfg.id, cfg.push_freq)
+            };
+            Subscription::run_with(config, builder)
+        }
+        Exchange::BitgetLinear | Exchange::BitgetSpot => {
+            let builder = |cfg: &StreamConfig<TickerInfo>| {
+                bitget::connect_market_stream(cfg.id, cfg.push_freq)
+            };
+            Subscription::run_with(config, builder)
+        }
     }
 }
 
@@ -1405,5 +2242,41 @@ pub fn kline_subscription(
             };
             Subscription::run_with(config, builder)
         }
+        Exchange::GateioLinear | Exchange::GateioSpot => {
+            let builder = |cfg: &StreamConfig<Vec<(TickerInfo, Timeframe)>>| {
+                gateio::connect_kline_stream(cfg.id.clone(), cfg.market_type)
+            };
+            Subscription::run_with(config, builder)
+        }
+        Exchange::CoinbaseSpot => {
+            let builder = |cfg: &StreamConfig<Vec<(TickerInfo, Timeframe)>>| {
+                coinbase::connect_kline_stream(cfg.id.clone(), cfg.market_type)
+            };
+            Subscription::run_with(config, builder)
+        }
+        Exchange::BitgetLinear | Exchange::BitgetSpot => {
+            let builder = |cfg: &StreamConfig<Vec<(TickerInfo, Timeframe)>>| {
+                bitget::connect_kline_stream(cfg.id.clone(), cfg.market_type)
+            };
+            Subscription::run_with(config, builder)
+        }
     }
 }
+
+/// Mark and index price candles, polled from the same endpoints as their history
+pub fn price_kline_subscription(
+    exchange: Exchange,
+    streams: Vec<(TickerInfo, Timeframe, KlinePriceSource)>,
+) -> Subscription<exchange::Event> {
+    let config = StreamConfig::new(streams, exchange, None, PushFrequency::ServerDefault);
+
+    Subscription::run_with(
+        config,
+        |cfg: &StreamConfig<Vec<(TickerInfo, Timeframe, KlinePriceSource)>>| {
+            let exchange = cfg.id.first().map_or(Exchange::BinanceLinear, |(info, _, _)| {
+                info.exchange()
+            });
+            adapter::connect_price_kline_stream(cfg.id.clone(), exchange)
+        },
+    )
+}
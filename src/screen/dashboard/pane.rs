@@ -10,31 +10,47 @@ use crate::{
         },
     },
     screen::dashboard::{
-        panel::{self, ladder::Ladder, timeandsales::TimeAndSales},
+        panel::{
+            self, depth_curve::DepthCurve, funding::FundingChart, ladder::Ladder,
+            spread::SpreadChart, timeandsales::TimeAndSales,
+        },
         tickers_table::TickersTable,
     },
     style::{self, Icon, icon_text},
-    widget::{self, button_with_tooltip, column_drag, link_group_button, toast::Toast},
+    widget::{
+        self, button_with_tooltip, column_drag, link_group_button,
+        toast::{Notification, Toast},
+    },
     window::{self, Window},
 };
 use data::{
     UserTimezone,
+    book_spread::{BookSpread, BookSpreadConfig, SpreadHistory},
     chart::{
-        Basis, ViewConfig,
-        indicator::{HeatmapIndicator, Indicator, KlineIndicator, UiIndicator},
+        AutoscalePadding, Basis, PriceSnap, QuickRange, ViewConfig,
+        indicator::{HeatmapIndicator, KlineIndicator, UiIndicator},
     },
     layout::pane::{ContentKind, LinkGroup, PaneSetup, Settings, VisualConfig},
+    depth_totals::{DepthTotals, DepthTotalsConfig},
+    tape_speed::TapeSpeed,
 };
 use exchange::{
-    Kline, OpenInterest, StreamPairKind, TickMultiplier, TickerInfo, Timeframe,
-    adapter::{MarketKind, PersistStreamKind, ResolvedStream, StreamKind, StreamTicksize},
+    FundingRate, Kline, OpenInterest, StreamPairKind, TickMultiplier, TickerInfo, Timeframe,
+    adapter::{
+        KlinePriceSource, MarketKind, PersistStreamKind, ResolvedStream, StreamKind,
+        StreamTicksize,
+    },
+    depth::Depth,
     fetcher::FetchRequests,
 };
 use iced::{
     Alignment, Element, Length, Renderer, Theme,
     alignment::Vertical,
     padding,
-    widget::{button, center, column, container, pane_grid, pick_list, row, text, tooltip},
+    widget::{
+        button, center, checkbox, column, container, mouse_area, pane_grid, pick_list, row, stack,
+        text, tooltip,
+    },
 };
 use std::time::Instant;
 
@@ -44,6 +60,9 @@ pub enum Effect {
     RequestFetch(FetchRequests),
     SwitchTickersInGroup(TickerInfo),
     FocusWidget(iced::widget::Id),
+    SyncCrosshair(LinkGroup, Option<u64>),
+    RulerMeasured(Box<modal::measurements::Entry>),
+    CopyToClipboard(String),
 }
 
 #[derive(Debug, Default, Clone, PartialEq)]
@@ -64,6 +83,7 @@ pub enum Action {
 #[derive(Debug, Clone)]
 pub enum Message {
     PaneClicked(pane_grid::Pane),
+    PaneHovered(pane_grid::Pane),
     PaneResized(pane_grid::ResizeEvent),
     PaneDragged(pane_grid::DragEvent),
     ClosePane(pane_grid::Pane),
@@ -86,14 +106,22 @@ pub enum Event {
     ChartInteraction(super::chart::Message),
     PanelInteraction(super::panel::Message),
     ToggleIndicator(UiIndicator),
+    SaveIndicatorsAsDefault,
     DeleteNotification(usize),
     ReorderIndicator(column_drag::DragEvent),
     ClusterKindSelected(data::chart::kline::ClusterKind),
     ClusterScalingSelected(data::chart::kline::ClusterScaling),
+    CandleColorEditing(Option<modal::pane::settings::CandleColorTarget>),
+    IndicatorColorEditing(Option<KlineIndicator>),
     StudyConfigurator(modal::pane::settings::study::StudyMessage),
     StreamModifierChanged(modal::stream::Message),
     ComparisonChartInteraction(super::chart::comparison::Message),
     MiniTickersListInteraction(modal::pane::mini_tickers_list::Message),
+    CancelTradeFetch,
+    StartBackfill,
+    CancelBackfill,
+    DepthPushFreqSelected(Option<Timeframe>),
+    PinTickerToggled(bool),
 }
 
 pub struct State {
@@ -105,6 +133,13 @@ pub struct State {
     pub streams: ResolvedStream,
     pub status: Status,
     pub link_group: Option<LinkGroup>,
+    pub tape_speed: TapeSpeed,
+    pub depth_totals: Option<DepthTotals>,
+    pub spread_history: SpreadHistory,
+    last_data: Option<Instant>,
+    is_stale: bool,
+    /// Something saved with the layout changed since the dashboard last checked
+    changed: bool,
 }
 
 impl State {
@@ -134,6 +169,36 @@ impl State {
         })
     }
 
+    /// Smallest price step of the pane's ticker, before the multiplier is applied
+    fn base_ticksize(&self, multiplier: TickMultiplier, tick_size: f32) -> f32 {
+        match self.stream_pair() {
+            Some(ticker_info) => ticker_info.min_ticksize.into(),
+            None => multiplier.base(tick_size),
+        }
+    }
+
+    /// Regroups price levels of the content with the pane's current tick multiplier
+    fn apply_tick_multiplier(&mut self, tm: TickMultiplier) {
+        let Some(ticker) = self.stream_pair() else {
+            return;
+        };
+        let tick_size = self.settings.tick_size(tm, ticker);
+
+        match &mut self.content {
+            Content::Kline { chart: Some(c), .. } => {
+                c.change_tick_size(tick_size);
+                c.reset_request_handler();
+            }
+            Content::Heatmap { chart: Some(c), .. } => {
+                c.change_tick_size(tick_size);
+            }
+            Content::Ladder(Some(p)) => {
+                p.set_tick_size(tick_size);
+            }
+            _ => {}
+        }
+    }
+
     pub fn stream_pair_kind(&self) -> Option<StreamPairKind> {
         let ready_streams = self.streams.ready_iter()?;
         let mut unique = vec![];
@@ -165,21 +230,30 @@ impl State {
         let base_ticker = tickers[0];
         let prev_base_ticker = self.stream_pair();
 
+        // a pane getting its first ticker starts from the user's default indicator set
+        if prev_base_ticker.is_none() {
+            self.content
+                .inherit_default_indicators(base_ticker.market_type());
+        }
+
         let derived_plan = PaneSetup::new(
             kind,
             base_ticker,
             prev_base_ticker,
             self.settings.selected_basis,
             self.settings.tick_multiply,
+            self.settings.round_steps,
+            self.settings.depth_push_freq,
         );
 
         self.settings.selected_basis = derived_plan.basis;
         self.settings.tick_multiply = derived_plan.tick_multiplier;
 
-        let (content, streams) = {
+        let (content, mut streams) = {
             let kline_stream = |ti: TickerInfo, tf: Timeframe| StreamKind::Kline {
                 ticker_info: ti,
                 timeframe: tf,
+                source: KlinePriceSource::Trade,
             };
             let depth_stream = |derived_plan: &PaneSetup| StreamKind::DepthAndTrades {
                 ticker_info: derived_plan.ticker_info,
@@ -235,10 +309,21 @@ impl State {
                         )
                     };
 
+                    let source = match &content {
+                        Content::Kline { chart: Some(c), .. } => c.price_source(),
+                        _ => KlinePriceSource::Trade,
+                    };
+
                     let streams = by_basis_default(
                         derived_plan.basis,
                         Timeframe::M15,
-                        |tf| vec![kline_stream(derived_plan.ticker_info, tf)],
+                        |tf| {
+                            vec![StreamKind::Kline {
+                                ticker_info: derived_plan.ticker_info,
+                                timeframe: tf,
+                                source,
+                            }]
+                        },
                         || {
                             let depth_aggr = derived_plan
                                 .ticker_info
@@ -286,6 +371,40 @@ impl State {
 
                     (content, vec![depth_stream(&derived_plan)])
                 }
+                ContentKind::DepthCurve => {
+                    let config = self
+                        .settings
+                        .visual_config
+                        .clone()
+                        .and_then(|cfg| cfg.depth_curve());
+                    let content = Content::DepthCurve(Some(DepthCurve::new(
+                        config,
+                        derived_plan.ticker_info,
+                    )));
+
+                    (content, vec![depth_stream(&derived_plan)])
+                }
+                ContentKind::FundingChart => {
+                    let content =
+                        Content::FundingChart(Some(FundingChart::new(derived_plan.ticker_info)));
+
+                    (content, FundingChart::streams(derived_plan.ticker_info))
+                }
+                ContentKind::SpreadChart => {
+                    let config = self
+                        .settings
+                        .visual_config
+                        .clone()
+                        .and_then(|cfg| cfg.spread());
+                    let timeframe = match derived_plan.basis {
+                        Some(Basis::Time(tf)) => tf,
+                        _ => Timeframe::M1,
+                    };
+                    let chart = SpreadChart::new(&tickers, timeframe, config);
+                    let streams = chart.streams();
+
+                    (Content::Spread(Some(chart)), streams)
+                }
                 ContentKind::ComparisonChart => {
                     let config = self
                         .settings
@@ -315,12 +434,62 @@ impl State {
             }
         };
 
+        sync_higher_tf_stream(&content, &mut streams);
+
         self.content = content;
         self.streams = ResolvedStream::Ready(streams.clone());
+        self.tape_speed.clear();
+        self.depth_totals = None;
+        self.spread_history.clear();
 
         streams
     }
 
+    /// Rebuilds a candlestick pane whose configured price source differs from the one its kline
+    /// stream carries, since mark or index candles replace the whole series; returns the new
+    /// kline stream so its history can be fetched
+    pub fn sync_price_source(&mut self) -> Option<StreamKind> {
+        let Content::Kline { chart: Some(c), .. } = &self.content else {
+            return None;
+        };
+        let wanted = c.price_source();
+
+        let current = self.streams.find_ready_map(|stream| match stream {
+            StreamKind::Kline {
+                timeframe, source, ..
+            } if c.basis() == Basis::Time(*timeframe) => Some(*source),
+            _ => None,
+        })?;
+        if current == wanted {
+            return None;
+        }
+
+        let ticker_info = self.stream_pair()?;
+        let kind = self.content.kind();
+
+        self.set_content_and_streams(vec![ticker_info], kind)
+            .into_iter()
+            .find(|stream| matches!(stream, StreamKind::Kline { source, .. } if *source == wanted))
+    }
+
+    /// Keeps the extra kline stream of a kline pane in line with its higher timeframe overlay;
+    /// returns whether the streams changed
+    pub fn sync_higher_tf_stream(&mut self) -> bool {
+        match &mut self.streams {
+            ResolvedStream::Ready(streams) => sync_higher_tf_stream(&self.content, streams),
+            ResolvedStream::Waiting(_) => false,
+        }
+    }
+
+    pub fn insert_hist_funding(&mut self, req_id: Option<uuid::Uuid>, rates: &[FundingRate]) {
+        match &mut self.content {
+            Content::FundingChart(Some(panel)) => panel.insert_funding(req_id, rates),
+            _ => {
+                log::error!("pane content not funding chart");
+            }
+        }
+    }
+
     pub fn insert_hist_oi(&mut self, req_id: Option<uuid::Uuid>, oi: &[OpenInterest]) {
         match &mut self.content {
             Content::Kline { chart, .. } => {
@@ -340,6 +509,7 @@ impl State {
         req_id: Option<uuid::Uuid>,
         timeframe: Timeframe,
         ticker_info: TickerInfo,
+        source: KlinePriceSource,
         klines: &[Kline],
     ) {
         match &mut self.content {
@@ -351,6 +521,12 @@ impl State {
                 };
 
                 if let Some(id) = req_id {
+                    if chart.higher_tf_stream().is_some_and(|stream| {
+                        matches!(stream, StreamKind::Kline { timeframe: tf, .. } if tf == timeframe)
+                    }) {
+                        chart.insert_higher_tf_klines(id, klines);
+                        return;
+                    }
                     if chart.basis() != Basis::Time(timeframe) {
                         log::warn!(
                             "Ignoring stale kline fetch for timeframe {:?}; chart basis = {:?}",
@@ -373,6 +549,7 @@ impl State {
                         indicators,
                         ticker_info,
                         chart.kind(),
+                        Some(chart.visual_config()),
                     );
                 }
             }
@@ -399,6 +576,18 @@ impl State {
                     );
                 }
             }
+            Content::FundingChart(panel) => {
+                let Some(panel) = panel else {
+                    panic!("Funding chart wasn't initialized when inserting klines");
+                };
+                panel.insert_klines(req_id, source, klines);
+            }
+            Content::Spread(panel) => {
+                let Some(panel) = panel else {
+                    panic!("Spread chart wasn't initialized when inserting klines");
+                };
+                panel.insert_klines(req_id, ticker_info, timeframe, klines);
+            }
             _ => {
                 log::error!("pane content not candlestick or footprint");
             }
@@ -637,7 +826,7 @@ impl State {
 
                     let kind = ModifierKind::Orderbook(basis, tick_multiply);
 
-                    let base_ticksize = tick_multiply.base(panel.tick_size());
+                    let base_ticksize = self.base_ticksize(tick_multiply, panel.tick_size());
                     let exchange = self.stream_pair().map(|ti| ti.ticker.exchange);
 
                     let modifiers = ticksize_modifier(
@@ -647,6 +836,7 @@ impl State {
                         modifier,
                         kind,
                         exchange,
+                        self.settings.round_steps,
                     );
 
                     stream_info_element = stream_info_element.push(modifiers);
@@ -654,9 +844,16 @@ impl State {
                     let base = panel::view(panel, timezone).map(move |message| {
                         Message::PaneEvent(id, Event::PanelInteraction(message))
                     });
+                    let base = self.with_spread_overlay(base);
 
-                    let settings_modal =
-                        || modal::pane::settings::ladder_cfg_view(panel.config, id);
+                    let settings_modal = || {
+                        modal::pane::settings::ladder_cfg_view(
+                            panel.config,
+                            id,
+                            self.stream_pair().map(|ti| ti.exchange()),
+                            self.settings.depth_push_freq,
+                        )
+                    };
 
                     self.compose_stack_view(
                         base,
@@ -680,6 +877,96 @@ impl State {
                     )
                 }
             }
+            Content::DepthCurve(panel) => {
+                if let Some(panel) = panel {
+                    let base = panel::view(panel, timezone).map(move |message| {
+                        Message::PaneEvent(id, Event::PanelInteraction(message))
+                    });
+                    let base = self.with_spread_overlay(base);
+
+                    let settings_modal =
+                        || modal::pane::settings::depth_curve_cfg_view(panel.config, id);
+
+                    self.compose_stack_view(
+                        base,
+                        id,
+                        None,
+                        compact_controls,
+                        settings_modal,
+                        None,
+                        tickers_table,
+                    )
+                } else {
+                    let base = uninitialized_base(ContentKind::DepthCurve);
+                    self.compose_stack_view(
+                        base,
+                        id,
+                        None,
+                        compact_controls,
+                        || column![].into(),
+                        None,
+                        tickers_table,
+                    )
+                }
+            }
+            Content::FundingChart(panel) => {
+                let base = if let Some(panel) = panel {
+                    panel::view(panel, timezone).map(move |message| {
+                        Message::PaneEvent(id, Event::PanelInteraction(message))
+                    })
+                } else {
+                    uninitialized_base(ContentKind::FundingChart)
+                };
+
+                self.compose_stack_view(
+                    base,
+                    id,
+                    None,
+                    compact_controls,
+                    || column![].into(),
+                    None,
+                    tickers_table,
+                )
+            }
+            Content::Spread(panel) => {
+                if let Some(panel) = panel {
+                    let selected_basis = Basis::Time(panel.timeframe());
+                    let kind = ModifierKind::Comparison(selected_basis);
+
+                    let modifiers =
+                        row![basis_modifier(id, selected_basis, modifier, kind),].spacing(4);
+
+                    stream_info_element = stream_info_element.push(modifiers);
+
+                    let base = panel::view(panel, timezone).map(move |message| {
+                        Message::PaneEvent(id, Event::PanelInteraction(message))
+                    });
+
+                    let settings_modal =
+                        || modal::pane::settings::spread_cfg_view(panel.config, id);
+
+                    self.compose_stack_view(
+                        base,
+                        id,
+                        None,
+                        compact_controls,
+                        settings_modal,
+                        Some(panel.selected_tickers()),
+                        tickers_table,
+                    )
+                } else {
+                    let base = uninitialized_base(ContentKind::SpreadChart);
+                    self.compose_stack_view(
+                        base,
+                        id,
+                        None,
+                        compact_controls,
+                        || column![].into(),
+                        None,
+                        tickers_table,
+                    )
+                }
+            }
             Content::Heatmap {
                 chart, indicators, ..
             } => {
@@ -694,7 +981,7 @@ impl State {
                     let tick_multiply = self.settings.tick_multiply.unwrap_or(TickMultiplier(5));
 
                     let kind = ModifierKind::Heatmap(basis, tick_multiply);
-                    let base_ticksize = tick_multiply.base(chart.tick_size());
+                    let base_ticksize = self.base_ticksize(tick_multiply, chart.tick_size());
 
                     let modifiers = row![
                         basis_modifier(id, basis, modifier, kind),
@@ -704,7 +991,8 @@ impl State {
                             tick_multiply,
                             modifier,
                             kind,
-                            exchange
+                            exchange,
+                            self.settings.round_steps,
                         ),
                     ]
                     .spacing(4);
@@ -714,6 +1002,7 @@ impl State {
                     let base = chart::view(chart, indicators, timezone).map(move |message| {
                         Message::PaneEvent(id, Event::ChartInteraction(message))
                     });
+                    let base = self.with_spread_overlay(base);
                     let settings_modal = || {
                         heatmap_cfg_view(
                             chart.visual_config(),
@@ -721,6 +1010,13 @@ impl State {
                             chart.study_configurator(),
                             &chart.studies,
                             basis,
+                            chart.memory_usage(),
+                            chart.chart_layout().price_decimals,
+                            chart.chart_layout().price_lines,
+                            chart.chart_layout().invert_price,
+                            chart.chart_layout().watermark,
+                            self.stream_pair().map(|ti| ti.exchange()),
+                            self.settings.depth_push_freq,
                         )
                     };
 
@@ -730,6 +1026,7 @@ impl State {
                             self,
                             indicators,
                             self.stream_pair().map(|i| i.ticker.market_type()),
+                            None,
                         ))
                     } else {
                         None
@@ -772,7 +1069,8 @@ impl State {
                                 self.settings.tick_multiply.unwrap_or(TickMultiplier(10));
 
                             let kind = ModifierKind::Footprint(basis, tick_multiply);
-                            let base_ticksize = tick_multiply.base(chart.tick_size());
+                            let base_ticksize =
+                                self.base_ticksize(tick_multiply, chart.tick_size());
 
                             let exchange =
                                 self.stream_pair().as_ref().map(|info| info.ticker.exchange);
@@ -785,7 +1083,8 @@ impl State {
                                     tick_multiply,
                                     modifier,
                                     kind,
-                                    exchange
+                                    exchange,
+                                    self.settings.round_steps,
                                 ),
                             ]
                             .spacing(4);
@@ -813,10 +1112,18 @@ impl State {
                     let settings_modal = || {
                         kline_cfg_view(
                             chart.study_configurator(),
-                            data::chart::kline::Config {},
+                            chart.visual_config(),
+                            chart.candle_color_editing,
                             chart_kind,
                             id,
                             chart.basis(),
+                            chart.retained_trades(),
+                            &chart.chart_layout(),
+                            chart.tick_size(),
+                            chart.has_open_interest(),
+                            self.stream_pair().map_or(&[KlinePriceSource::Trade], |info| {
+                                KlinePriceSource::available(info.exchange())
+                            }),
                         )
                     };
 
@@ -826,6 +1133,12 @@ impl State {
                             self,
                             indicators,
                             self.stream_pair().map(|i| i.ticker.market_type()),
+                            Some(modal::indicators::kline_colors_view(
+                                id,
+                                indicators,
+                                chart.visual_config(),
+                                chart.indicator_color_editing,
+                            )),
                         ))
                     } else {
                         None
@@ -865,19 +1178,72 @@ impl State {
             Status::Loading(exchange::fetcher::InfoKind::FetchingKlines) => {
                 stream_info_element = stream_info_element.push(text("Fetching Klines..."));
             }
-            Status::Loading(exchange::fetcher::InfoKind::FetchingTrades(count)) => {
-                stream_info_element =
-                    stream_info_element.push(text(format!("Fetching Trades... {count} fetched")));
+            Status::Loading(exchange::fetcher::InfoKind::FetchingTrades(count, progress)) => {
+                let cancel_btn = button(text("Cancel").size(11))
+                    .padding([1, 4])
+                    .on_press(Message::PaneEvent(id, Event::CancelTradeFetch))
+                    .style(|theme, status| style::button::transparent(theme, status, false));
+
+                stream_info_element = stream_info_element
+                    .push(text(format!(
+                        "Fetching Trades... {count} fetched ({progress}%)"
+                    )))
+                    .push(cancel_btn);
+            }
+            Status::Loading(exchange::fetcher::InfoKind::BackfillingKlines(count, progress)) => {
+                let cancel_btn = button(text("Cancel").size(11))
+                    .padding([1, 4])
+                    .on_press(Message::PaneEvent(id, Event::CancelBackfill))
+                    .style(|theme, status| style::button::transparent(theme, status, false));
+
+                stream_info_element = stream_info_element
+                    .push(text(format!(
+                        "Backfilling Klines... {count} fetched ({progress}%)"
+                    )))
+                    .push(cancel_btn);
             }
             Status::Loading(exchange::fetcher::InfoKind::FetchingOI) => {
                 stream_info_element = stream_info_element.push(text("Fetching Open Interest..."));
             }
+            Status::Loading(exchange::fetcher::InfoKind::FetchingFunding) => {
+                stream_info_element = stream_info_element.push(text("Fetching Funding..."));
+            }
             Status::Stale(msg) => {
                 stream_info_element = stream_info_element.push(text(msg));
             }
-            Status::Ready => {}
+            Status::Ready => {
+                if let Some(readout) = self.tape_speed_readout() {
+                    stream_info_element = stream_info_element.push(readout);
+                }
+                if let Some(readout) = self.depth_totals_readout() {
+                    stream_info_element = stream_info_element.push(readout);
+                }
+            }
         }
 
+        if self.is_stale {
+            stream_info_element = stream_info_element.push(
+                container(text("Stale").size(11))
+                    .padding([1, 4])
+                    .style(style::stale_badge),
+            );
+        }
+
+        let body: Element<'a, Message> = if self.is_stale {
+            stack![
+                body,
+                container(iced::widget::space())
+                    .width(Length::Fill)
+                    .height(Length::Fill)
+                    .style(style::stale_overlay),
+            ]
+            .into()
+        } else {
+            body
+        };
+
+        let body = mouse_area(body).on_enter(Message::PaneHovered(id));
+
         let content = pane_grid::Content::new(body)
             .style(move |theme| style::pane_background(theme, is_focused));
 
@@ -925,6 +1291,11 @@ impl State {
         })
     }
 
+    /// Whether the pane's saved configuration changed since the last call
+    pub fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+
     pub fn update(&mut self, msg: Event) -> Option<Effect> {
         match msg {
             Event::ShowModal(requested_modal) => {
@@ -935,6 +1306,7 @@ impl State {
             }
             Event::ContentSelected(kind) => {
                 self.content = Content::placeholder(kind);
+                self.changed = true;
 
                 if !matches!(kind, ContentKind::Starter) {
                     self.streams = ResolvedStream::Waiting(vec![]);
@@ -945,22 +1317,99 @@ impl State {
                     }
                 }
             }
-            Event::ChartInteraction(msg) => match &mut self.content {
-                Content::Heatmap { chart: Some(c), .. } => {
-                    super::chart::update(c, &msg);
+            Event::ChartInteraction(msg) => {
+                let (hover_changed, ruler, copied) = match &mut self.content {
+                    Content::Heatmap { chart: Some(c), .. } => {
+                        let copied = chart::clipboard_text(c, &msg);
+                        self.changed |= super::chart::update(c, &msg);
+                        (
+                            chart::track_hovered_time(c, &msg),
+                            chart::completed_ruler(c, &msg),
+                            copied,
+                        )
+                    }
+                    Content::Kline { chart: Some(c), .. } => {
+                        let copied = chart::clipboard_text(c, &msg);
+                        self.changed |= super::chart::update(c, &msg);
+                        (
+                            chart::track_hovered_time(c, &msg),
+                            chart::completed_ruler(c, &msg),
+                            copied,
+                        )
+                    }
+                    _ => (false, None, None),
+                };
+
+                if let chart::Message::CopyRequested(target) = msg {
+                    let Some(copied) = copied else {
+                        self.notifications
+                            .push(Toast::warn(format!("Nothing to copy for \"{target}\"")));
+                        return None;
+                    };
+                    self.notifications
+                        .push(Toast::new(Notification::Info(format!("Copied: {copied}"))));
+                    return Some(Effect::CopyToClipboard(copied));
                 }
-                Content::Kline { chart: Some(c), .. } => {
-                    super::chart::update(c, &msg);
+
+                if let Some(measurement) = ruler
+                    && let Some(ticker_info) = self.stream_pair()
+                {
+                    return Some(Effect::RulerMeasured(Box::new(
+                        modal::measurements::Entry::new(ticker_info.ticker, measurement),
+                    )));
                 }
-                _ => {}
-            },
+
+                // "Max" pages in as much history as the backfill reaches
+                if let chart::Message::QuickRangeSelected(QuickRange::Max) = msg
+                    && let Content::Kline { chart: Some(c), .. } = &mut self.content
+                    && let Some(chart::Action::RequestFetch(fetch)) = c.start_backfill()
+                {
+                    self.status =
+                        Status::Loading(exchange::fetcher::InfoKind::BackfillingKlines(0, 0));
+                    return Some(Effect::RequestFetch(fetch));
+                }
+
+                // jumping before the loaded history fetches it right away instead of on the next tick
+                if let chart::Message::GoToTime(_) | chart::Message::QuickRangeSelected(_) = msg
+                    && let Content::Kline { chart: Some(c), .. } = &mut self.content
+                    && let Some(chart::Action::RequestFetch(fetch)) =
+                        c.invalidate(Some(Instant::now()))
+                {
+                    return Some(Effect::RequestFetch(fetch));
+                }
+
+                if hover_changed
+                    && let Some(group) = self.link_group
+                    && let chart::Message::CrosshairMoved(time) = msg
+                {
+                    return Some(Effect::SyncCrosshair(group, time));
+                }
+            }
             Event::PanelInteraction(msg) => match &mut self.content {
                 Content::Ladder(Some(p)) => super::panel::update(p, msg),
                 Content::TimeAndSales(Some(p)) => super::panel::update(p, msg),
+                Content::DepthCurve(Some(p)) => super::panel::update(p, msg),
+                Content::FundingChart(Some(p)) => super::panel::update(p, msg),
+                Content::Spread(Some(p)) => super::panel::update(p, msg),
                 _ => {}
             },
             Event::ToggleIndicator(ind) => {
                 self.content.toggle_indicator(ind);
+                self.changed = true;
+            }
+            Event::SaveIndicatorsAsDefault => {
+                let mut defaults = data::chart::indicator::default_indicators();
+                match &self.content {
+                    Content::Kline { indicators, .. } => defaults.kline = indicators.clone(),
+                    Content::Heatmap { indicators, .. } => defaults.heatmap = indicators.clone(),
+                    _ => return None,
+                }
+                data::chart::indicator::set_default_indicators(defaults);
+                self.changed = true;
+
+                self.notifications.push(Toast::new(Notification::Info(
+                    "Saved as the default indicators for new panes".to_string(),
+                )));
             }
             Event::DeleteNotification(idx) => {
                 if idx < self.notifications.len() {
@@ -969,6 +1418,7 @@ impl State {
             }
             Event::ReorderIndicator(e) => {
                 self.content.reorder_indicators(&e);
+                self.changed |= matches!(e, column_drag::DragEvent::Dropped { .. });
             }
             Event::ClusterKindSelected(kind) => {
                 if let Content::Kline {
@@ -978,6 +1428,74 @@ impl State {
                 {
                     c.set_cluster_kind(kind);
                     *cur = c.kind.clone();
+                    self.changed = true;
+                }
+            }
+            Event::CancelTradeFetch => {
+                if let Content::Kline { chart: Some(c), .. } = &mut self.content {
+                    c.cancel_trade_fetch();
+                    self.status = Status::Ready;
+                }
+            }
+            Event::StartBackfill => {
+                if let Content::Kline { chart: Some(c), .. } = &mut self.content {
+                    if let Some(chart::Action::RequestFetch(fetch)) = c.start_backfill() {
+                        self.status =
+                            Status::Loading(exchange::fetcher::InfoKind::BackfillingKlines(0, 0));
+                        return Some(Effect::RequestFetch(fetch));
+                    }
+                    self.notifications.push(Toast::warn(
+                        "Nothing to backfill, history already covers the selected range",
+                    ));
+                }
+            }
+            Event::CancelBackfill => {
+                if let Content::Kline { chart: Some(c), .. } = &mut self.content {
+                    c.cancel_backfill();
+                    self.status = Status::Ready;
+                }
+            }
+            Event::PinTickerToggled(pinned) => {
+                self.settings.pin_ticker = pinned;
+                self.changed = true;
+            }
+            Event::DepthPushFreqSelected(selected) => {
+                self.settings.depth_push_freq = selected;
+                self.changed = true;
+
+                let content_kind = self.content.kind();
+                let basis = self.settings.selected_basis;
+
+                if let Some(StreamKind::DepthAndTrades {
+                    ticker_info,
+                    push_freq,
+                    ..
+                }) = self
+                    .streams
+                    .ready_iter_mut()
+                    .and_then(|mut it| it.find(|s| matches!(s, StreamKind::DepthAndTrades { .. })))
+                {
+                    let new_freq = data::layout::pane::depth_push_freq(
+                        content_kind,
+                        ticker_info.exchange(),
+                        basis,
+                        selected,
+                    );
+
+                    if *push_freq != new_freq {
+                        *push_freq = new_freq;
+                        return Some(Effect::RefreshStreams);
+                    }
+                }
+            }
+            Event::CandleColorEditing(target) => {
+                if let Content::Kline { chart: Some(c), .. } = &mut self.content {
+                    c.candle_color_editing = target;
+                }
+            }
+            Event::IndicatorColorEditing(indicator) => {
+                if let Content::Kline { chart: Some(c), .. } = &mut self.content {
+                    c.indicator_color_editing = indicator;
                 }
             }
             Event::ClusterScalingSelected(scaling) => {
@@ -986,6 +1504,7 @@ impl State {
                 {
                     c.set_cluster_scaling(scaling);
                     *kind = c.kind.clone();
+                    self.changed = true;
                 }
             }
             Event::StudyConfigurator(study_msg) => match study_msg {
@@ -995,6 +1514,7 @@ impl State {
                     {
                         c.update_study_configurator(m);
                         *kind = c.kind.clone();
+                        self.changed = true;
                     }
                 }
                 modal::pane::settings::study::StudyMessage::Heatmap(m) => {
@@ -1003,6 +1523,7 @@ impl State {
                     {
                         c.update_study_configurator(m);
                         *studies = c.studies.clone();
+                        self.changed = true;
                     }
                 }
             },
@@ -1018,26 +1539,8 @@ impl State {
                             modal::stream::Action::TicksizeSelected(tm) => {
                                 modifier.update_kind_with_multiplier(tm);
                                 self.settings.tick_multiply = Some(tm);
-
-                                if let Some(ticker) = self.stream_pair() {
-                                    match &mut self.content {
-                                        Content::Kline { chart: Some(c), .. } => {
-                                            c.change_tick_size(
-                                                tm.multiply_with_min_tick_size(ticker),
-                                            );
-                                            c.reset_request_handler();
-                                        }
-                                        Content::Heatmap { chart: Some(c), .. } => {
-                                            c.change_tick_size(
-                                                tm.multiply_with_min_tick_size(ticker),
-                                            );
-                                        }
-                                        Content::Ladder(Some(p)) => {
-                                            p.set_tick_size(tm.multiply_with_min_tick_size(ticker));
-                                        }
-                                        _ => {}
-                                    }
-                                }
+                                self.changed = true;
+                                self.apply_tick_multiplier(tm);
 
                                 let is_client = self
                                     .stream_pair()
@@ -1055,13 +1558,43 @@ impl State {
                                         }
                                     }
                                 }
-                                if !is_client {
-                                    effect = Some(Effect::RefreshStreams);
+                                // client-side aggregation keeps the same streams, but the
+                                // dashboard still picks up the multiplier for depth snapshots
+                                effect = Some(Effect::RefreshStreams);
+                            }
+                            modal::stream::Action::SaveTicksizeAsDefault => {
+                                let kind = self.content.kind();
+
+                                if let (Some(tm), Some(ticker_info)) =
+                                    (self.settings.tick_multiply, self.stream_pair())
+                                {
+                                    let mut presets = data::chart::tick_preset::tick_presets();
+
+                                    if presets.save_for_ticker(kind, ticker_info, tm) {
+                                        data::chart::tick_preset::set_tick_presets(presets);
+                                        self.changed = true;
+
+                                        self.notifications.push(Toast::new(Notification::Info(
+                                            format!(
+                                                "New {kind} panes of {} will start at {tm}",
+                                                ticker_info.ticker
+                                            ),
+                                        )));
+                                    }
+                                }
+                            }
+                            modal::stream::Action::RoundStepsToggled(round_steps) => {
+                                self.settings.round_steps = round_steps;
+                                self.changed = true;
+
+                                if let Some(tm) = self.settings.tick_multiply {
+                                    self.apply_tick_multiplier(tm);
                                 }
                             }
                             modal::stream::Action::BasisSelected(new_basis) => {
                                 modifier.update_kind_with_basis(new_basis);
                                 self.settings.selected_basis = Some(new_basis);
+                                self.changed = true;
 
                                 let base_ticker = self.stream_pair();
 
@@ -1080,17 +1613,13 @@ impl State {
                                                 ticker_info,
                                                 ..
                                             } = stream_type
-                                            && ticker_info.exchange().is_custom_push_freq()
                                         {
-                                            match new_basis {
-                                                Basis::Time(tf) => {
-                                                    *push_freq = exchange::PushFrequency::Custom(tf)
-                                                }
-                                                Basis::Tick(_) => {
-                                                    *push_freq =
-                                                        exchange::PushFrequency::ServerDefault
-                                                }
-                                            }
+                                            *push_freq = data::layout::pane::depth_push_freq(
+                                                ContentKind::HeatmapChart,
+                                                ticker_info.exchange(),
+                                                Some(new_basis),
+                                                self.settings.depth_push_freq,
+                                            );
                                         }
 
                                         effect = Some(Effect::RefreshStreams);
@@ -1102,6 +1631,7 @@ impl State {
                                                     let kline_stream = StreamKind::Kline {
                                                         ticker_info: base_ticker,
                                                         timeframe: tf,
+                                                        source: c.price_source(),
                                                     };
                                                     let mut streams = vec![kline_stream];
 
@@ -1128,8 +1658,9 @@ impl State {
                                                         });
                                                     }
 
-                                                    self.streams = ResolvedStream::Ready(streams);
                                                     let action = c.set_basis(new_basis);
+                                                    streams.extend(c.higher_tf_stream());
+                                                    self.streams = ResolvedStream::Ready(streams);
 
                                                     if let Some(chart::Action::RequestFetch(
                                                         fetch,
@@ -1174,6 +1705,7 @@ impl State {
                                                 .map(|ti| StreamKind::Kline {
                                                     ticker_info: ti,
                                                     timeframe: tf,
+                                                    source: KlinePriceSource::Trade,
                                                 })
                                                 .collect();
 
@@ -1186,6 +1718,19 @@ impl State {
                                             }
                                         }
                                     }
+                                    Content::Spread(Some(p)) => {
+                                        if let Basis::Time(tf) = new_basis {
+                                            let action = p.set_timeframe(tf);
+                                            self.streams = ResolvedStream::Ready(p.streams());
+
+                                            effect = Some(match action {
+                                                Some(panel::Action::RequestFetch(fetch)) => {
+                                                    Effect::RequestFetch(fetch)
+                                                }
+                                                None => Effect::RefreshStreams,
+                                            });
+                                        }
+                                    }
                                     _ => {}
                                 }
                             }
@@ -1207,9 +1752,11 @@ impl State {
                     match action {
                         super::chart::comparison::Action::SeriesColorChanged(t, color) => {
                             chart.set_series_color(t, color);
+                            self.changed = true;
                         }
                         super::chart::comparison::Action::SeriesNameChanged(t, name) => {
                             chart.set_series_name(t, name);
+                            self.changed = true;
                         }
                         super::chart::comparison::Action::OpenSeriesEditor => {
                             self.modal = Some(Modal::Settings);
@@ -1217,6 +1764,7 @@ impl State {
                         super::chart::comparison::Action::RemoveSeries(ti) => {
                             let rebuilt = chart.remove_ticker(&ti);
                             self.streams = ResolvedStream::Ready(rebuilt);
+                            self.changed = true;
 
                             return Some(Effect::RefreshStreams);
                         }
@@ -1232,20 +1780,26 @@ impl State {
                     let crate::modal::pane::mini_tickers_list::Action::RowSelected(sel) = action;
                     match sel {
                         crate::modal::pane::mini_tickers_list::RowSelection::Add(ti) => {
-                            if let Content::Comparison(chart) = &mut self.content
-                                && let Some(c) = chart
-                            {
-                                let rebuilt = c.add_ticker(&ti);
+                            let rebuilt = match &mut self.content {
+                                Content::Comparison(Some(c)) => Some(c.add_ticker(&ti)),
+                                Content::Spread(Some(p)) => Some(p.add_ticker(&ti)),
+                                _ => None,
+                            };
+                            if let Some(rebuilt) = rebuilt {
                                 self.streams = ResolvedStream::Ready(rebuilt);
+                                self.changed = true;
                                 return Some(Effect::RefreshStreams);
                             }
                         }
                         crate::modal::pane::mini_tickers_list::RowSelection::Remove(ti) => {
-                            if let Content::Comparison(chart) = &mut self.content
-                                && let Some(c) = chart
-                            {
-                                let rebuilt = c.remove_ticker(&ti);
+                            let rebuilt = match &mut self.content {
+                                Content::Comparison(Some(c)) => Some(c.remove_ticker(&ti)),
+                                Content::Spread(Some(p)) => Some(p.remove_ticker(&ti)),
+                                _ => None,
+                            };
+                            if let Some(rebuilt) = rebuilt {
                                 self.streams = ResolvedStream::Ready(rebuilt);
+                                self.changed = true;
                                 return Some(Effect::RefreshStreams);
                             }
                         }
@@ -1383,7 +1937,7 @@ impl State {
 
         match &self.modal {
             Some(Modal::LinkGroup) => {
-                let content = link_group_modal(pane, self.link_group);
+                let content = link_group_modal(pane, self.link_group, self.settings.pin_ticker);
 
                 stack_modal(
                     base,
@@ -1452,6 +2006,147 @@ impl State {
         }
     }
 
+    /// Trades and volume per second over the configured window, for panes fed by trades
+    fn tape_speed_readout<'a>(&self) -> Option<Element<'a, Message>> {
+        let has_trades = self
+            .streams
+            .ready_iter()?
+            .any(|stream| matches!(stream, StreamKind::DepthAndTrades { .. }));
+        if !has_trades {
+            return None;
+        }
+
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+        let rate = self.tape_speed.rate(now);
+
+        Some(
+            text(format!(
+                "{:.1} t/s · {}/s",
+                rate.trades_per_sec,
+                data::util::format_volume(rate.volume_per_sec)
+            ))
+            .size(11)
+            .style(|theme: &Theme| text::Style {
+                color: Some(theme.extended_palette().background.strong.text),
+            })
+            .into(),
+        )
+    }
+
+    /// Depth totals settings of panes that keep an order book, when the readout is enabled
+    pub fn depth_totals_config(&self) -> Option<DepthTotalsConfig> {
+        let cfg = match &self.content {
+            Content::Heatmap { chart: Some(c), .. } => c.visual_config().depth_totals,
+            Content::Ladder(Some(panel)) => panel.config.depth_totals,
+            Content::DepthCurve(Some(panel)) => panel.config.depth_totals,
+            _ => return None,
+        };
+
+        cfg.enabled.then_some(cfg)
+    }
+
+    /// Refreshes the depth totals from the latest book, or drops them once the readout is off
+    pub fn update_depth_totals(&mut self, depth: &Depth) {
+        self.depth_totals = self
+            .depth_totals_config()
+            .map(|cfg| DepthTotals::compute(depth, cfg.band_pct));
+    }
+
+    /// Bid and ask liquidity within the configured band around mid, plus their imbalance
+    fn depth_totals_readout<'a>(&self) -> Option<Element<'a, Message>> {
+        let cfg = self.depth_totals_config()?;
+        let totals = self.depth_totals.unwrap_or_default();
+
+        Some(
+            text(format!(
+                "±{:.1}% B {} · A {} · {:+.0}%",
+                cfg.band_pct,
+                data::util::format_volume(totals.bid),
+                data::util::format_volume(totals.ask),
+                totals.imbalance() * 100.0
+            ))
+            .size(11)
+            .style(|theme: &Theme| text::Style {
+                color: Some(theme.extended_palette().background.strong.text),
+            })
+            .into(),
+        )
+    }
+
+    /// Spread history settings of panes that keep an order book, when the widget is enabled
+    pub fn spread_history_config(&self) -> Option<BookSpreadConfig> {
+        let cfg = match &self.content {
+            Content::Heatmap { chart: Some(c), .. } => c.visual_config().spread_history,
+            Content::Ladder(Some(panel)) => panel.config.spread_history,
+            Content::DepthCurve(Some(panel)) => panel.config.spread_history,
+            _ => return None,
+        };
+
+        cfg.enabled.then_some(cfg)
+    }
+
+    /// Samples the spread of the latest book, or drops the history once the widget is off
+    pub fn update_spread_history(&mut self, depth: &Depth, time: u64) {
+        match self.spread_history_config() {
+            Some(cfg) => {
+                self.spread_history
+                    .push(time, BookSpread::compute(depth), cfg.window_ms());
+            }
+            None => self.spread_history.clear(),
+        }
+    }
+
+    /// Decimals for the absolute spread: the chart's display decimals, else the tick size's
+    fn spread_decimals(&self) -> usize {
+        let chart_decimals = match &self.content {
+            Content::Heatmap { chart: Some(c), .. } => c.chart_layout().price_decimals,
+            _ => None,
+        };
+
+        chart_decimals.map(usize::from).unwrap_or_else(|| {
+            self.stream_pair().map_or(2, |info| {
+                data::util::count_decimals(f32::from(info.min_ticksize))
+            })
+        })
+    }
+
+    /// Current bid-ask spread with a sparkline of its recent history, in the pane's top-left
+    /// corner; crossed or empty books show a dash
+    fn with_spread_overlay<'a>(&self, base: Element<'a, Message>) -> Element<'a, Message> {
+        if self.spread_history_config().is_none() {
+            return base;
+        }
+
+        let readout = match self.spread_history.latest() {
+            Some(spread) => format!(
+                "{} · {:.1} bps",
+                data::util::format_price(spread.absolute, self.spread_decimals()),
+                spread.bps
+            ),
+            None => "-".to_string(),
+        };
+
+        let widget = container(
+            row![
+                text(readout).size(11),
+                widget::sparkline::sparkline(self.spread_history.values().collect(), 64.0, 16.0),
+            ]
+            .spacing(6)
+            .align_y(Alignment::Center),
+        )
+        .padding([2, 6])
+        .style(style::tooltip);
+
+        stack![
+            base,
+            container(widget)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .padding(8),
+        ]
+        .into()
+    }
+
     pub fn matches_stream(&self, stream: &StreamKind) -> bool {
         self.streams.matches_stream(stream)
     }
@@ -1493,6 +2188,15 @@ impl State {
             Content::Ladder(panel) => panel
                 .as_mut()
                 .and_then(|p| p.invalidate(Some(now)).map(Action::Panel)),
+            Content::DepthCurve(panel) => panel
+                .as_mut()
+                .and_then(|p| p.invalidate(Some(now)).map(Action::Panel)),
+            Content::FundingChart(panel) => panel
+                .as_mut()
+                .and_then(|p| p.invalidate(Some(now)).map(Action::Panel)),
+            Content::Spread(panel) => panel
+                .as_mut()
+                .and_then(|p| p.invalidate(Some(now)).map(Action::Panel)),
             Content::Starter => None,
             Content::Comparison(chart) => chart
                 .as_mut()
@@ -1502,7 +2206,10 @@ impl State {
 
     pub fn update_interval(&self) -> Option<u64> {
         match &self.content {
-            Content::Kline { .. } | Content::Comparison(_) => Some(1000),
+            Content::Kline { .. }
+            | Content::Comparison(_)
+            | Content::FundingChart(_)
+            | Content::Spread(_) => Some(1000),
             Content::Heatmap { chart, .. } => {
                 if let Some(chart) = chart {
                     chart.basis_interval()
@@ -1510,7 +2217,7 @@ impl State {
                     None
                 }
             }
-            Content::Ladder(_) | Content::TimeAndSales(_) => Some(100),
+            Content::Ladder(_) | Content::TimeAndSales(_) | Content::DepthCurve(_) => Some(100),
             Content::Starter => None,
         }
     }
@@ -1519,7 +2226,39 @@ impl State {
         self.content.last_tick()
     }
 
+    /// Marks the pane's feed as live; called whenever one of its streams delivers a message
+    pub fn mark_data_received(&mut self, at: Instant) {
+        self.last_data = Some(at);
+        self.is_stale = false;
+    }
+
+    /// Flags the pane as stale once its streams have been silent longer than the threshold
+    /// for its timeframe
+    fn update_staleness(&mut self, now: Instant) {
+        if self.streams.ready_iter().is_none() {
+            self.last_data = None;
+            self.is_stale = false;
+            return;
+        }
+
+        // the clock starts when the streams resolve, so a feed that never delivers still goes stale
+        let last_data = *self.last_data.get_or_insert(now);
+
+        let interval_ms = match self.settings.selected_basis {
+            Some(Basis::Time(tf)) => Some(tf.to_milliseconds()),
+            Some(Basis::Tick(_)) | None => None,
+        };
+
+        self.is_stale = data::stale::stale_threshold()
+            .to_millis(interval_ms)
+            .is_some_and(|ms| {
+                now.duration_since(last_data) >= std::time::Duration::from_millis(ms)
+            });
+    }
+
     pub fn tick(&mut self, now: Instant) -> Option<Action> {
+        self.update_staleness(now);
+
         let invalidate_interval: Option<u64> = self.update_interval();
         let last_tick: Option<Instant> = self.last_tick();
 
@@ -1569,11 +2308,18 @@ impl Default for State {
             notifications: vec![],
             status: Status::Ready,
             link_group: None,
+            tape_speed: TapeSpeed::default(),
+            depth_totals: None,
+            spread_history: SpreadHistory::default(),
+            last_data: None,
+            is_stale: false,
+            changed: false,
         }
     }
 }
 
 #[derive(Default)]
+#[allow(clippy::large_enum_variant)]
 pub enum Content {
     #[default]
     Starter,
@@ -1592,6 +2338,9 @@ pub enum Content {
     TimeAndSales(Option<TimeAndSales>),
     Ladder(Option<Ladder>),
     Comparison(Option<ComparisonChart>),
+    DepthCurve(Option<DepthCurve>),
+    FundingChart(Option<FundingChart>),
+    Spread(Option<SpreadChart>),
 }
 
 impl Content {
@@ -1620,10 +2369,20 @@ impl Content {
             )
         } else {
             (
-                vec![HeatmapIndicator::Volume],
+                data::chart::indicator::default_indicators().heatmap_for(ticker_info.market_type()),
                 ViewConfig {
                     splits: vec![],
                     autoscale: Some(data::chart::Autoscale::CenterLatest),
+                    price_decimals: None,
+                    price_lines: data::chart::PriceLines::default(),
+                    price_scale: data::chart::PriceScale::default(),
+                    reverse_time: false,
+                    invert_price: false,
+                    watermark: false,
+                    measured_moves: vec![],
+                    autoscale_padding: AutoscalePadding::default(),
+                    price_snap: PriceSnap::default(),
+                    quick_range: None,
                 },
                 vec![],
             )
@@ -1692,25 +2451,20 @@ impl Content {
 
         let basis = settings.selected_basis.unwrap_or(Basis::Time(default_tf));
 
-        let enabled_indicators = {
-            let available = KlineIndicator::for_market(ticker_info.market_type());
-            prev_indis.map_or_else(
-                || vec![KlineIndicator::Volume],
-                |indis| {
-                    indis
-                        .into_iter()
-                        .filter(|i| available.contains(i))
-                        .collect()
-                },
-            )
-        };
+        // keep the user's full ordering, even if some don't apply to this market (e.g. OI on spot),
+        // so switching back to a ticker that supports them restores their panels
+        let enabled_indicators =
+            prev_indis.unwrap_or_else(|| data::chart::indicator::default_indicators().kline);
+        let applicable_count =
+            data::chart::indicator::applicable(&enabled_indicators, ticker_info.market_type())
+                .len();
 
         let splits = {
             let main_chart_split: f32 = 0.8;
             let mut splits_vec = vec![main_chart_split];
 
-            if !enabled_indicators.is_empty() {
-                let num_indicators = enabled_indicators.len();
+            if applicable_count > 0 {
+                let num_indicators = applicable_count;
 
                 if num_indicators > 0 {
                     let indicator_total_height_ratio = 1.0 - main_chart_split;
@@ -1732,6 +2486,16 @@ impl Content {
             .unwrap_or(ViewConfig {
                 splits,
                 autoscale: Some(data::chart::Autoscale::FitToVisible),
+                price_decimals: None,
+                price_lines: data::chart::PriceLines::default(),
+                price_scale: data::chart::PriceScale::default(),
+                reverse_time: false,
+                invert_price: false,
+                watermark: false,
+                measured_moves: vec![],
+                autoscale_padding: AutoscalePadding::default(),
+                price_snap: PriceSnap::default(),
+                quick_range: None,
             });
 
         let chart = KlineChart::new(
@@ -1743,6 +2507,7 @@ impl Content {
             &enabled_indicators,
             ticker_info,
             &determined_chart_kind,
+            settings.visual_config.clone().and_then(|cfg| cfg.kline()),
         );
 
         Content::Kline {
@@ -1763,6 +2528,16 @@ impl Content {
                 layout: ViewConfig {
                     splits: vec![],
                     autoscale: Some(data::chart::Autoscale::FitToVisible),
+                    price_decimals: None,
+                    price_lines: data::chart::PriceLines::default(),
+                    price_scale: data::chart::PriceScale::default(),
+                    reverse_time: false,
+                    invert_price: false,
+                    watermark: false,
+                    measured_moves: vec![],
+                    autoscale_padding: AutoscalePadding::default(),
+                    price_snap: PriceSnap::default(),
+                    quick_range: None,
                 },
             },
             ContentKind::FootprintChart => Content::Kline {
@@ -1776,6 +2551,16 @@ impl Content {
                 layout: ViewConfig {
                     splits: vec![],
                     autoscale: Some(data::chart::Autoscale::FitToVisible),
+                    price_decimals: None,
+                    price_lines: data::chart::PriceLines::default(),
+                    price_scale: data::chart::PriceScale::default(),
+                    reverse_time: false,
+                    invert_price: false,
+                    watermark: false,
+                    measured_moves: vec![],
+                    autoscale_padding: AutoscalePadding::default(),
+                    price_snap: PriceSnap::default(),
+                    quick_range: None,
                 },
             },
             ContentKind::HeatmapChart => Content::Heatmap {
@@ -1785,11 +2570,24 @@ impl Content {
                 layout: ViewConfig {
                     splits: vec![],
                     autoscale: Some(data::chart::Autoscale::CenterLatest),
+                    price_decimals: None,
+                    price_lines: data::chart::PriceLines::default(),
+                    price_scale: data::chart::PriceScale::default(),
+                    reverse_time: false,
+                    invert_price: false,
+                    watermark: false,
+                    measured_moves: vec![],
+                    autoscale_padding: AutoscalePadding::default(),
+                    price_snap: PriceSnap::default(),
+                    quick_range: None,
                 },
             },
             ContentKind::ComparisonChart => Content::Comparison(None),
             ContentKind::TimeAndSales => Content::TimeAndSales(None),
             ContentKind::Ladder => Content::Ladder(None),
+            ContentKind::DepthCurve => Content::DepthCurve(None),
+            ContentKind::FundingChart => Content::FundingChart(None),
+            ContentKind::SpreadChart => Content::Spread(None),
         }
     }
 
@@ -1800,6 +2598,9 @@ impl Content {
             Content::TimeAndSales(panel) => Some(panel.as_ref()?.last_update()),
             Content::Ladder(panel) => Some(panel.as_ref()?.last_update()),
             Content::Comparison(chart) => Some(chart.as_ref()?.last_update()),
+            Content::DepthCurve(panel) => Some(panel.as_ref()?.last_update()),
+            Content::FundingChart(panel) => Some(panel.as_ref()?.last_update()),
+            Content::Spread(panel) => Some(panel.as_ref()?.last_update()),
             Content::Starter => None,
         }
     }
@@ -1811,6 +2612,14 @@ impl Content {
         }
     }
 
+    pub fn sync_crosshair(&mut self, synced: Option<(u64, LinkGroup)>) {
+        match self {
+            Content::Heatmap { chart: Some(c), .. } => chart::sync_crosshair(c, synced),
+            Content::Kline { chart: Some(c), .. } => chart::sync_crosshair(c, synced),
+            _ => {}
+        }
+    }
+
     pub fn toggle_indicator(&mut self, indicator: UiIndicator) {
         match (self, indicator) {
             (
@@ -1851,12 +2660,35 @@ impl Content {
         }
     }
 
+    /// Replaces the indicators of a chart that hasn't been built yet with the configured
+    /// defaults, leaving out those the market doesn't support (e.g. OI on spot)
+    fn inherit_default_indicators(&mut self, market: MarketKind) {
+        let defaults = data::chart::indicator::default_indicators();
+
+        match self {
+            Content::Kline {
+                chart: None,
+                indicators,
+                ..
+            } => *indicators = defaults.kline_for(market),
+            Content::Heatmap {
+                chart: None,
+                indicators,
+                ..
+            } => *indicators = defaults.heatmap_for(market),
+            _ => {}
+        }
+    }
+
     pub fn reorder_indicators(&mut self, event: &column_drag::DragEvent) {
         match self {
             Content::Heatmap { indicators, .. } => column_drag::reorder_vec(indicators, event),
             Content::Kline { indicators, .. } => column_drag::reorder_vec(indicators, event),
             Content::TimeAndSales(_)
             | Content::Ladder(_)
+            | Content::DepthCurve(_)
+            | Content::FundingChart(_)
+            | Content::Spread(_)
             | Content::Starter
             | Content::Comparison(_) => {
                 panic!("indicator reorder on {} pane", self)
@@ -1869,6 +2701,9 @@ impl Content {
             (Content::Heatmap { chart: Some(c), .. }, VisualConfig::Heatmap(cfg)) => {
                 c.set_visual_config(cfg);
             }
+            (Content::Kline { chart: Some(c), .. }, VisualConfig::Kline(cfg)) => {
+                c.set_visual_config(cfg);
+            }
             (Content::TimeAndSales(Some(panel)), VisualConfig::TimeAndSales(cfg)) => {
                 panel.config = cfg;
             }
@@ -1878,6 +2713,12 @@ impl Content {
             (Content::Comparison(Some(chart)), VisualConfig::Comparison(cfg)) => {
                 chart.config = cfg;
             }
+            (Content::Spread(Some(panel)), VisualConfig::Spread(cfg)) => {
+                panel.config = cfg;
+            }
+            (Content::DepthCurve(Some(panel)), VisualConfig::DepthCurve(cfg)) => {
+                panel.config = cfg;
+            }
             _ => {}
         }
     }
@@ -1894,6 +2735,9 @@ impl Content {
             }
             Content::TimeAndSales(_)
             | Content::Ladder(_)
+            | Content::DepthCurve(_)
+            | Content::FundingChart(_)
+            | Content::Spread(_)
             | Content::Starter
             | Content::Comparison(_) => None,
         }
@@ -1941,6 +2785,9 @@ impl Content {
             Content::TimeAndSales(_) => ContentKind::TimeAndSales,
             Content::Ladder(_) => ContentKind::Ladder,
             Content::Comparison(_) => ContentKind::ComparisonChart,
+            Content::DepthCurve(_) => ContentKind::DepthCurve,
+            Content::FundingChart(_) => ContentKind::FundingChart,
+            Content::Spread(_) => ContentKind::SpreadChart,
             Content::Starter => ContentKind::Starter,
         }
     }
@@ -1952,6 +2799,9 @@ impl Content {
             Content::TimeAndSales(panel) => panel.is_some(),
             Content::Ladder(panel) => panel.is_some(),
             Content::Comparison(chart) => chart.is_some(),
+            Content::DepthCurve(panel) => panel.is_some(),
+            Content::FundingChart(panel) => panel.is_some(),
+            Content::Spread(panel) => panel.is_some(),
             Content::Starter => true,
         }
     }
@@ -1972,6 +2822,9 @@ impl PartialEq for Content {
                 | (Content::Kline { .. }, Content::Kline { .. })
                 | (Content::TimeAndSales(_), Content::TimeAndSales(_))
                 | (Content::Ladder(_), Content::Ladder(_))
+                | (Content::DepthCurve(_), Content::DepthCurve(_))
+                | (Content::FundingChart(_), Content::FundingChart(_))
+                | (Content::Spread(_), Content::Spread(_))
         )
     }
 }
@@ -1979,6 +2832,7 @@ impl PartialEq for Content {
 fn link_group_modal<'a>(
     pane: pane_grid::Pane,
     selected_group: Option<LinkGroup>,
+    pin_ticker: bool,
 ) -> Element<'a, Message> {
     let mut grid = column![].spacing(4);
     let rows = LinkGroup::ALL.chunks(3);
@@ -1988,7 +2842,10 @@ fn link_group_modal<'a>(
 
         for &group in row_groups {
             let is_selected = selected_group == Some(group);
-            let btn_content = text(group.to_string()).font(style::AZERET_MONO);
+            let color = style::link_group_color(group);
+            let btn_content = text(group.to_string())
+                .font(style::AZERET_MONO)
+                .style(move |_theme| iced::widget::text::Style { color: Some(color) });
 
             let btn = if is_selected {
                 button_with_tooltip(
@@ -2011,6 +2868,20 @@ fn link_group_modal<'a>(
         grid = grid.push(button_row);
     }
 
+    grid = grid.push(
+        checkbox(pin_ticker)
+            .label("Keep own ticker")
+            .on_toggle(move |pinned| Message::PaneEvent(pane, Event::PinTickerToggled(pinned))),
+    );
+
+    grid = grid.push(
+        text("Linked panes share ticker changes and the crosshair time")
+            .size(11)
+            .style(|theme: &Theme| iced::widget::text::Style {
+                color: Some(theme.extended_palette().background.strong.color),
+            }),
+    );
+
     container(grid)
         .max_width(240)
         .padding(16)
@@ -2025,10 +2896,15 @@ fn ticksize_modifier<'a>(
     modifier: Option<modal::stream::Modifier>,
     kind: ModifierKind,
     exchange: Option<exchange::adapter::Exchange>,
+    round_steps: bool,
 ) -> Element<'a, Message> {
-    let modifier_modal = Modal::StreamModifier(
-        modal::stream::Modifier::new(kind).with_ticksize_view(base_ticksize, multiplier, exchange),
-    );
+    let modifier_modal =
+        Modal::StreamModifier(modal::stream::Modifier::new(kind).with_ticksize_view(
+            base_ticksize,
+            multiplier,
+            exchange,
+            round_steps,
+        ));
 
     let is_active = modifier.is_some_and(|m| {
         matches!(
@@ -2073,3 +2949,23 @@ fn by_basis_default<T>(
         Basis::Tick(_) => on_tick(),
     }
 }
+
+fn sync_higher_tf_stream(content: &Content, streams: &mut Vec<StreamKind>) -> bool {
+    let Content::Kline { chart: Some(c), .. } = content else {
+        return false;
+    };
+
+    let primary = match c.basis() {
+        Basis::Time(tf) => Some(tf),
+        Basis::Tick(_) => None,
+    };
+    let previous = streams.clone();
+
+    streams.retain(|stream| match stream {
+        StreamKind::Kline { timeframe, .. } => Some(*timeframe) == primary,
+        StreamKind::DepthAndTrades { .. } => true,
+    });
+    streams.extend(c.higher_tf_stream());
+
+    *streams != previous
+}
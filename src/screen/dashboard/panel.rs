@@ -1,4 +1,7 @@
+pub mod depth_curve;
+pub mod funding;
 pub mod ladder;
+pub mod spread;
 pub mod timeandsales;
 
 use iced::{
@@ -14,7 +17,9 @@ pub enum Message {
     Invalidate(Option<Instant>),
 }
 
-pub enum Action {}
+pub enum Action {
+    RequestFetch(exchange::fetcher::FetchRequests),
+}
 
 pub trait Panel: canvas::Program<Message> {
     fn scroll(&mut self, scroll: f32);
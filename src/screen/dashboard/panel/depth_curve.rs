@@ -0,0 +1,444 @@
+use super::Message;
+use crate::chart::ViewState;
+use crate::style;
+use data::chart::{Basis, ViewConfig};
+use data::panel::depth_curve::{
+    Config, CumulativeSide, MAX_RANGE_PCT, MIN_RANGE_PCT, cumulative_curves,
+};
+use data::util::count_decimals;
+use data::walls::{WallEvent, WallSide, WallTracker};
+use exchange::util::{Price, PriceStep};
+use exchange::{TickerInfo, Timeframe, depth::Depth};
+
+use iced::widget::canvas::{self, Path, Stroke, Text};
+use iced::{Alignment, Color, Point, Rectangle, Renderer, Size, Theme, mouse};
+
+use std::time::Instant;
+
+const TEXT_SIZE: f32 = 11.0;
+/// Height reserved under the plot for the price labels
+const PRICE_AXIS_HEIGHT: f32 = 18.0;
+/// Space kept above the largest cumulative value
+const TOP_PADDING: f32 = 24.0;
+const AREA_ALPHA: f32 = 0.2;
+
+impl super::Panel for DepthCurve {
+    fn scroll(&mut self, _delta: f32) {}
+
+    fn reset_scroll(&mut self) {}
+
+    fn invalidate(&mut self, now: Option<Instant>) -> Option<super::Action> {
+        DepthCurve::invalidate(self, now)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.curves.is_none()
+    }
+}
+
+struct Curves {
+    mid: Price,
+    bids: CumulativeSide,
+    asks: CumulativeSide,
+    spread: Option<Price>,
+}
+
+pub struct DepthCurve {
+    ticker_info: TickerInfo,
+    pub config: Config,
+    cache: canvas::Cache,
+    last_tick: Instant,
+    curves: Option<Curves>,
+    walls: WallTracker,
+    /// Price axis, fitted to the visible range so labels and snapping match the other charts
+    view: ViewState,
+}
+
+impl DepthCurve {
+    pub fn new(config: Option<Config>, ticker_info: TickerInfo) -> Self {
+        let tick_size: f32 = ticker_info.min_ticksize.into();
+
+        // the basis is unused, the curve has no time axis
+        let view = ViewState::new(
+            Basis::Time(Timeframe::M1),
+            PriceStep::from_f32(tick_size),
+            count_decimals(tick_size),
+            ticker_info,
+            ViewConfig::default(),
+            0.0,
+            1.0,
+        );
+
+        Self {
+            ticker_info,
+            config: config.unwrap_or_default(),
+            cache: canvas::Cache::default(),
+            last_tick: Instant::now(),
+            curves: None,
+            walls: WallTracker::default(),
+            view,
+        }
+    }
+
+    pub fn insert_depth(&mut self, depth: &Depth, depth_update_t: u64) -> Vec<WallEvent> {
+        let spread = match (
+            depth.bids.last_key_value().map(|(p, _)| *p),
+            depth.asks.first_key_value().map(|(p, _)| *p),
+        ) {
+            (Some(bid), Some(ask)) => Some(ask - bid),
+            _ => None,
+        };
+
+        self.curves =
+            cumulative_curves(depth, self.config.range_pct).map(|(mid, bids, asks)| Curves {
+                mid,
+                bids,
+                asks,
+                spread,
+            });
+        self.fit_price_range();
+
+        self.walls.update(depth, &self.config.walls, depth_update_t)
+    }
+
+    pub fn last_update(&self) -> Instant {
+        self.last_tick
+    }
+
+    pub fn invalidate(&mut self, now: Option<Instant>) -> Option<super::Action> {
+        self.fit_price_range();
+        self.cache.clear();
+        if let Some(now) = now {
+            self.last_tick = now;
+        }
+        None
+    }
+
+    /// Visible price range, ± the configured percent around mid
+    fn price_range(&self, curves: &Curves) -> (Price, Price) {
+        let mid = curves.mid.to_f32_lossy();
+        let range_pct = self.config.range_pct.clamp(MIN_RANGE_PCT, MAX_RANGE_PCT);
+        let offset = mid * range_pct / 100.0;
+
+        (
+            Price::from_f32_lossy(mid - offset),
+            Price::from_f32_lossy(mid + offset),
+        )
+    }
+
+    fn fit_price_range(&mut self) {
+        if let Some(curves) = &self.curves {
+            let (low, high) = self.price_range(curves);
+            self.view.fit_horizontal_price_range(low, high);
+        }
+    }
+
+    fn price_to_x(&self, price: Price, plot: Size) -> f32 {
+        self.view
+            .price_to_screen_x(price, plot.width)
+            .clamp(0.0, plot.width)
+    }
+}
+
+/// Maps a cumulative quantity to y, leaving `TOP_PADDING` above the largest one
+fn qty_to_y(qty: f32, max_qty: f32, plot: Size) -> f32 {
+    if max_qty <= 0.0 {
+        return plot.height;
+    }
+    let usable = (plot.height - TOP_PADDING).max(1.0);
+    plot.height - (qty / max_qty) * usable
+}
+
+fn qty_at(side: &CumulativeSide, price: Price, is_bid: bool) -> Option<f32> {
+    let mut qty = None;
+    for (level, cumulative) in &side.points {
+        let reached = if is_bid {
+            *level >= price
+        } else {
+            *level <= price
+        };
+        if !reached {
+            break;
+        }
+        qty = Some(*cumulative);
+    }
+    qty
+}
+
+/// Fills and strokes one side as a step curve starting at the inside of the book
+/// and running out to `edge_x`
+fn draw_side(
+    frame: &mut canvas::Frame,
+    curve: &DepthCurve,
+    side: &CumulativeSide,
+    (plot, max_qty): (Size, f32),
+    edge_x: f32,
+    color: Color,
+) {
+    let Some((first_price, _)) = side.points.first() else {
+        return;
+    };
+
+    let base_y = plot.height;
+
+    let mut steps = vec![Point::new(curve.price_to_x(*first_price, plot), base_y)];
+
+    let mut prev_y = base_y;
+    for (price, cumulative) in &side.points {
+        let x = curve.price_to_x(*price, plot);
+        let y = qty_to_y(*cumulative, max_qty, plot);
+
+        steps.push(Point::new(x, prev_y));
+        steps.push(Point::new(x, y));
+        prev_y = y;
+    }
+    steps.push(Point::new(edge_x, prev_y));
+
+    let outline = Path::new(|builder| {
+        builder.move_to(steps[0]);
+        steps[1..].iter().for_each(|p| builder.line_to(*p));
+    });
+
+    let area = Path::new(|builder| {
+        builder.move_to(steps[0]);
+        steps[1..].iter().for_each(|p| builder.line_to(*p));
+        builder.line_to(Point::new(edge_x, base_y));
+        builder.close();
+    });
+
+    frame.fill(&area, color.scale_alpha(AREA_ALPHA));
+    frame.stroke(
+        &outline,
+        Stroke::with_color(Stroke::default().with_width(1.5), color),
+    );
+}
+
+impl canvas::Program<Message> for DepthCurve {
+    type State = ();
+
+    fn update(
+        &self,
+        _state: &mut Self::State,
+        event: &iced::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Message>> {
+        cursor.position_in(bounds)?;
+
+        match event {
+            iced::Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                Some(canvas::Action::request_redraw())
+            }
+            _ => None,
+        }
+    }
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry<Renderer>> {
+        let Some(curves) = &self.curves else {
+            return vec![];
+        };
+
+        let palette = theme.extended_palette();
+
+        let text_color = palette.background.base.text;
+        let weak_text = palette.background.strong.color;
+        let bid_color = palette.success.base.color;
+        let ask_color = palette.danger.base.color;
+        let divider_color = style::split_ruler(theme).color;
+
+        let (low, high) = self.price_range(curves);
+        let max_qty = curves.bids.total().max(curves.asks.total());
+        let plot = Size::new(bounds.width, (bounds.height - PRICE_AXIS_HEIGHT).max(0.0));
+
+        let curve_visual = self.cache.draw(renderer, bounds.size(), |frame| {
+            frame.fill_rectangle(
+                Point::new(0.0, plot.height),
+                Size::new(plot.width, 1.0),
+                divider_color,
+            );
+
+            if curves.bids.is_empty() && curves.asks.is_empty() {
+                frame.fill_text(Text {
+                    content: format!("No levels within ±{}%", self.config.range_pct),
+                    position: Point::new(plot.width / 2.0, plot.height / 2.0),
+                    color: weak_text,
+                    size: TEXT_SIZE.into(),
+                    font: style::AZERET_MONO,
+                    align_x: Alignment::Center.into(),
+                    align_y: Alignment::Center.into(),
+                    ..Default::default()
+                });
+            } else {
+                draw_side(frame, self, &curves.bids, (plot, max_qty), 0.0, bid_color);
+                draw_side(
+                    frame,
+                    self,
+                    &curves.asks,
+                    (plot, max_qty),
+                    plot.width,
+                    ask_color,
+                );
+            }
+
+            if self.config.walls.enabled {
+                for (side, price, qty) in self.walls.walls() {
+                    if price < low || price > high {
+                        continue;
+                    }
+
+                    let color = match side {
+                        WallSide::Bid => bid_color,
+                        WallSide::Ask => ask_color,
+                    };
+                    let x = self.price_to_x(price, plot);
+
+                    frame.fill_rectangle(
+                        Point::new(x - 0.5, 0.0),
+                        Size::new(1.0, plot.height),
+                        color.scale_alpha(0.8),
+                    );
+                    frame.fill_text(Text {
+                        content: data::util::format_volume(qty),
+                        position: Point::new(x, TOP_PADDING),
+                        color,
+                        size: TEXT_SIZE.into(),
+                        font: style::AZERET_MONO,
+                        align_x: Alignment::Center.into(),
+                        ..Default::default()
+                    });
+                }
+            }
+
+            let mid_x = self.price_to_x(curves.mid, plot);
+            frame.fill_rectangle(
+                Point::new(mid_x, 0.0),
+                Size::new(1.0, plot.height),
+                divider_color,
+            );
+
+            // price axis: edges and mid
+            for (price, x, align) in [
+                (low, 2.0, Alignment::Start),
+                (curves.mid, mid_x, Alignment::Center),
+                (high, plot.width - 2.0, Alignment::End),
+            ] {
+                frame.fill_text(Text {
+                    content: self.view.format_price(self.view.snap_price(price)),
+                    position: Point::new(x, plot.height + PRICE_AXIS_HEIGHT / 2.0),
+                    color: weak_text,
+                    size: TEXT_SIZE.into(),
+                    font: style::AZERET_MONO,
+                    align_x: align.into(),
+                    align_y: Alignment::Center.into(),
+                    ..Default::default()
+                });
+            }
+
+            // quantity axis: peak and half
+            if max_qty > 0.0 {
+                for qty in [max_qty, max_qty / 2.0] {
+                    frame.fill_text(Text {
+                        content: data::util::format_volume(qty),
+                        position: Point::new(4.0, qty_to_y(qty, max_qty, plot)),
+                        color: weak_text,
+                        size: TEXT_SIZE.into(),
+                        font: style::AZERET_MONO,
+                        align_y: Alignment::End.into(),
+                        ..Default::default()
+                    });
+                }
+            }
+
+            if self.config.show_spread
+                && let Some(spread) = curves.spread
+            {
+                let min_ticksize = self.ticker_info.min_ticksize;
+                let spread = spread.round_to_min_tick(min_ticksize);
+
+                frame.fill_text(Text {
+                    content: format!("Spread: {}", spread.to_string(min_ticksize)),
+                    position: Point::new(plot.width / 2.0, 4.0),
+                    color: palette.secondary.strong.color,
+                    size: TEXT_SIZE.into(),
+                    font: style::AZERET_MONO,
+                    align_x: Alignment::Center.into(),
+                    ..Default::default()
+                });
+            }
+        });
+
+        let mut geometries = vec![curve_visual];
+
+        if let Some(position) = cursor.position_in(bounds)
+            && position.y <= plot.height
+        {
+            let mut frame = canvas::Frame::new(renderer, bounds.size());
+
+            let price = self
+                .view
+                .snap_price(self.view.screen_x_to_price(position.x, plot.width));
+            let snapped_x = self.price_to_x(price, plot);
+            let is_bid = price <= curves.mid;
+
+            let (side, color) = if is_bid {
+                (&curves.bids, bid_color)
+            } else {
+                (&curves.asks, ask_color)
+            };
+
+            frame.fill_rectangle(
+                Point::new(snapped_x, 0.0),
+                Size::new(1.0, plot.height),
+                color.scale_alpha(0.6),
+            );
+
+            let qty = qty_at(side, price, is_bid).unwrap_or(0.0);
+            let label = format!(
+                "{} · {}",
+                self.view.format_price(price),
+                data::util::format_volume(qty)
+            );
+
+            let (x, align) = if snapped_x > plot.width / 2.0 {
+                (snapped_x - 6.0, Alignment::End)
+            } else {
+                (snapped_x + 6.0, Alignment::Start)
+            };
+
+            frame.fill_text(Text {
+                content: label,
+                position: Point::new(x, position.y),
+                color: text_color,
+                size: TEXT_SIZE.into(),
+                font: style::AZERET_MONO,
+                align_x: align.into(),
+                align_y: Alignment::Center.into(),
+                ..Default::default()
+            });
+
+            geometries.push(frame.into_geometry());
+        }
+
+        geometries
+    }
+
+    fn mouse_interaction(
+        &self,
+        _state: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        if cursor.is_over(bounds) {
+            mouse::Interaction::Crosshair
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+}
@@ -0,0 +1,534 @@
+use super::Message;
+use crate::style;
+use data::panel::funding::FundingHistory;
+use exchange::adapter::{KlinePriceSource, MarketKind, StreamKind};
+use exchange::fetcher::{FetchRange, FetchRequests, FetchSpec, RequestHandler};
+use exchange::util::Price;
+use exchange::{FundingRate, Kline, TickerInfo, Timeframe};
+
+use iced::widget::canvas::{self, Path, Stroke, Text};
+use iced::{Alignment, Color, Point, Rectangle, Renderer, Size, Theme, mouse};
+
+use std::time::{Duration, Instant};
+
+const TEXT_SIZE: f32 = 11.0;
+/// Height reserved under the plots for the time labels
+const TIME_AXIS_HEIGHT: f32 = 18.0;
+/// Vertical padding inside each plot so extremes don't touch the edges
+const PLOT_PADDING: f32 = 14.0;
+/// Share of the height given to funding when basis is also plotted
+const FUNDING_SHARE: f32 = 0.6;
+const MARKER_RADIUS: f32 = 2.5;
+
+/// How far back funding and basis history is loaded
+const LOOKBACK_MS: u64 = 12 * 24 * 60 * 60 * 1000;
+/// Minimum wait before asking the exchange for newly settled funding rates
+const FUNDING_POLL_INTERVAL: Duration = Duration::from_secs(5 * 60);
+/// Candle interval of the perp and index prices the basis is computed from
+pub const BASIS_TIMEFRAME: Timeframe = Timeframe::H1;
+
+impl super::Panel for FundingChart {
+    fn scroll(&mut self, _delta: f32) {}
+
+    fn reset_scroll(&mut self) {}
+
+    fn invalidate(&mut self, now: Option<Instant>) -> Option<super::Action> {
+        FundingChart::invalidate(self, now)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.has_funding() && self.history.is_empty()
+    }
+}
+
+pub struct FundingChart {
+    ticker_info: TickerInfo,
+    history: FundingHistory,
+    request_handler: RequestHandler,
+    /// Window of the initial history load, fixed so repeated requests for it are deduplicated
+    history_range: (u64, u64),
+    last_funding_poll: Option<Instant>,
+    cache: canvas::Cache,
+    last_tick: Instant,
+}
+
+impl FundingChart {
+    pub fn new(ticker_info: TickerInfo) -> Self {
+        let now = now_ms();
+
+        Self {
+            ticker_info,
+            history: FundingHistory::default(),
+            request_handler: RequestHandler::new(),
+            history_range: (now.saturating_sub(LOOKBACK_MS), now),
+            last_funding_poll: None,
+            cache: canvas::Cache::default(),
+            last_tick: Instant::now(),
+        }
+    }
+
+    /// Streams backing the pane: perp candles, plus index candles when the venue provides them
+    pub fn streams(ticker_info: TickerInfo) -> Vec<StreamKind> {
+        let kline_stream = |source| StreamKind::Kline {
+            ticker_info,
+            timeframe: BASIS_TIMEFRAME,
+            source,
+        };
+
+        let mut streams = vec![kline_stream(KlinePriceSource::Trade)];
+        if has_index(ticker_info) {
+            streams.push(kline_stream(KlinePriceSource::Index));
+        }
+        streams
+    }
+
+    fn has_funding(&self) -> bool {
+        self.ticker_info.market_type() != MarketKind::Spot
+    }
+
+    pub fn insert_funding(&mut self, req_id: Option<uuid::Uuid>, rates: &[FundingRate]) {
+        self.history.insert_rates(rates);
+        if let Some(id) = req_id {
+            self.request_handler.mark_completed(id);
+        }
+        self.cache.clear();
+    }
+
+    pub fn insert_klines(
+        &mut self,
+        req_id: Option<uuid::Uuid>,
+        source: KlinePriceSource,
+        klines: &[Kline],
+    ) {
+        match source {
+            KlinePriceSource::Index => self.history.insert_index_klines(klines),
+            KlinePriceSource::Trade | KlinePriceSource::Mark => {
+                self.history.insert_perp_klines(klines);
+            }
+        }
+        if let Some(id) = req_id {
+            self.request_handler.mark_completed(id);
+        }
+        self.cache.clear();
+    }
+
+    pub fn update_latest_kline(&mut self, stream: &StreamKind, kline: &Kline) {
+        if let StreamKind::Kline { source, .. } = stream {
+            self.insert_klines(None, *source, std::slice::from_ref(kline));
+        }
+    }
+
+    pub fn last_update(&self) -> Instant {
+        self.last_tick
+    }
+
+    pub fn invalidate(&mut self, now: Option<Instant>) -> Option<super::Action> {
+        self.cache.clear();
+        if let Some(now) = now {
+            self.last_tick = now;
+        }
+
+        if !self.has_funding() {
+            return None;
+        }
+        self.missing_fetches()
+    }
+
+    /// Requests whatever history is still missing, and newly settled funding every few minutes
+    fn missing_fetches(&mut self) -> Option<super::Action> {
+        let (from, to) = self.history_range;
+
+        let mut specs = FetchRequests::new();
+        let mut queue = |fetch: FetchRange, stream: Option<StreamKind>| {
+            if let Ok(Some(req_id)) = self.request_handler.add_request(fetch) {
+                specs.push(FetchSpec {
+                    req_id,
+                    fetch,
+                    stream,
+                });
+            }
+        };
+
+        let poll_due = self
+            .last_funding_poll
+            .is_none_or(|polled| polled.elapsed() >= FUNDING_POLL_INTERVAL);
+        if poll_due {
+            let since = self
+                .history
+                .rates()
+                .last()
+                .map_or(from, |(time, _)| time + 1);
+            queue(FetchRange::Funding(since, now_ms()), None);
+            self.last_funding_poll = Some(Instant::now());
+        }
+
+        if has_index(self.ticker_info) && !self.history.has_index() {
+            for source in [KlinePriceSource::Trade, KlinePriceSource::Index] {
+                queue(
+                    FetchRange::Kline(from, to),
+                    Some(StreamKind::Kline {
+                        ticker_info: self.ticker_info,
+                        timeframe: BASIS_TIMEFRAME,
+                        source,
+                    }),
+                );
+            }
+        }
+
+        if specs.is_empty() {
+            None
+        } else {
+            Some(super::Action::RequestFetch(specs))
+        }
+    }
+
+    fn format_basis(&self, value: f32) -> String {
+        Price::from_f32_lossy(value)
+            .round_to_min_tick(self.ticker_info.min_ticksize)
+            .to_string(self.ticker_info.min_ticksize)
+    }
+}
+
+fn has_index(ticker_info: TickerInfo) -> bool {
+    ticker_info.market_type() != MarketKind::Spot
+        && KlinePriceSource::available(ticker_info.exchange()).contains(&KlinePriceSource::Index)
+}
+
+fn now_ms() -> u64 {
+    chrono::Utc::now().timestamp_millis().max(0) as u64
+}
+
+fn format_rate(rate: f32) -> String {
+    format!("{:.4}%", rate * 100.0)
+}
+
+/// Maps time to x across the whole width and values to y inside one plot band
+pub(super) struct Band {
+    pub top: f32,
+    pub height: f32,
+    pub low: f32,
+    pub high: f32,
+}
+
+impl Band {
+    /// Band whose range always includes zero, so the sign of the values stays readable
+    pub(super) fn new(top: f32, height: f32, values: impl Iterator<Item = f32>) -> Self {
+        let (low, high) = values.fold((0.0f32, 0.0f32), |(lo, hi), v| (lo.min(v), hi.max(v)));
+        let (low, high) = if high - low <= f32::EPSILON {
+            (low - 1.0, high + 1.0)
+        } else {
+            (low, high)
+        };
+
+        Self {
+            top,
+            height,
+            low,
+            high,
+        }
+    }
+
+    pub(super) fn value_to_y(&self, value: f32) -> f32 {
+        let usable = (self.height - PLOT_PADDING * 2.0).max(1.0);
+        let ratio = (value - self.low) / (self.high - self.low);
+        self.top + PLOT_PADDING + usable * (1.0 - ratio)
+    }
+}
+
+pub(super) struct TimeMapping {
+    pub start: u64,
+    pub end: u64,
+    pub width: f32,
+}
+
+impl TimeMapping {
+    pub(super) fn time_to_x(&self, time: u64) -> f32 {
+        let span = self.end.saturating_sub(self.start).max(1) as f32;
+        (time.saturating_sub(self.start) as f32 / span) * self.width
+    }
+
+    pub(super) fn x_to_time(&self, x: f32) -> u64 {
+        let span = self.end.saturating_sub(self.start) as f32;
+        self.start + ((x / self.width.max(1.0)).clamp(0.0, 1.0) * span) as u64
+    }
+}
+
+pub(super) fn draw_band_labels(
+    frame: &mut canvas::Frame,
+    band: &Band,
+    title: &str,
+    format: impl Fn(f32) -> String,
+    color: Color,
+) {
+    frame.fill_text(Text {
+        content: title.to_string(),
+        position: Point::new(4.0, band.top + 2.0),
+        color,
+        size: TEXT_SIZE.into(),
+        font: style::AZERET_MONO,
+        ..Default::default()
+    });
+
+    for (value, align) in [(band.high, Alignment::Start), (band.low, Alignment::End)] {
+        frame.fill_text(Text {
+            content: format(value),
+            position: Point::new(frame.width() - 4.0, band.value_to_y(value)),
+            color,
+            size: TEXT_SIZE.into(),
+            font: style::AZERET_MONO,
+            align_x: Alignment::End.into(),
+            align_y: align.into(),
+            ..Default::default()
+        });
+    }
+}
+
+impl canvas::Program<Message> for FundingChart {
+    type State = ();
+
+    fn update(
+        &self,
+        _state: &mut Self::State,
+        event: &iced::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Message>> {
+        cursor.position_in(bounds)?;
+
+        match event {
+            iced::Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                Some(canvas::Action::request_redraw())
+            }
+            _ => None,
+        }
+    }
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry<Renderer>> {
+        let palette = theme.extended_palette();
+
+        let text_color = palette.background.base.text;
+        let weak_text = palette.background.strong.color;
+        let positive = palette.success.base.color;
+        let negative = palette.danger.base.color;
+        let basis_color = palette.primary.base.color;
+        let divider_color = style::split_ruler(theme).color;
+
+        if !self.has_funding() {
+            let message = self.cache.draw(renderer, bounds.size(), |frame| {
+                frame.fill_text(Text {
+                    content: "Spot — no funding".to_string(),
+                    position: frame.center(),
+                    color: weak_text,
+                    size: 14.0.into(),
+                    font: style::AZERET_MONO,
+                    align_x: Alignment::Center.into(),
+                    align_y: Alignment::Center.into(),
+                    ..Default::default()
+                });
+            });
+            return vec![message];
+        }
+
+        let Some((start, end)) = self.history.time_span() else {
+            return vec![];
+        };
+
+        let rates: Vec<(u64, f32)> = self.history.rates().collect();
+        let basis = self.history.basis();
+
+        let plot_height = (bounds.height - TIME_AXIS_HEIGHT).max(0.0);
+        let funding_height = if basis.is_empty() {
+            plot_height
+        } else {
+            plot_height * FUNDING_SHARE
+        };
+
+        let time = TimeMapping {
+            start,
+            end,
+            width: bounds.width,
+        };
+        let funding_band = Band::new(0.0, funding_height, rates.iter().map(|(_, r)| *r));
+        let basis_band = Band::new(
+            funding_height,
+            plot_height - funding_height,
+            basis.iter().map(|(_, b)| *b),
+        );
+
+        let plots = self.cache.draw(renderer, bounds.size(), |frame| {
+            let width = bounds.width;
+
+            for band in [&funding_band, &basis_band] {
+                if band.height <= 0.0 {
+                    continue;
+                }
+                frame.fill_rectangle(
+                    Point::new(0.0, band.value_to_y(0.0)),
+                    Size::new(width, 1.0),
+                    divider_color.scale_alpha(0.6),
+                );
+            }
+            frame.fill_rectangle(
+                Point::new(0.0, plot_height),
+                Size::new(width, 1.0),
+                divider_color,
+            );
+
+            // funding: held from each settlement until the next one
+            if !rates.is_empty() {
+                let steps = Path::new(|builder| {
+                    let mut prev_y = None;
+                    for (t, rate) in &rates {
+                        let x = time.time_to_x(*t);
+                        let y = funding_band.value_to_y(*rate);
+                        match prev_y {
+                            None => builder.move_to(Point::new(x, y)),
+                            Some(prev_y) => {
+                                builder.line_to(Point::new(x, prev_y));
+                                builder.line_to(Point::new(x, y));
+                            }
+                        }
+                        prev_y = Some(y);
+                    }
+                    if let Some(y) = prev_y {
+                        builder.line_to(Point::new(width, y));
+                    }
+                });
+                frame.stroke(
+                    &steps,
+                    Stroke::with_color(Stroke::default().with_width(1.0), weak_text),
+                );
+
+                for (t, rate) in &rates {
+                    let color = if *rate >= 0.0 { positive } else { negative };
+                    frame.fill(
+                        &Path::circle(
+                            Point::new(time.time_to_x(*t), funding_band.value_to_y(*rate)),
+                            MARKER_RADIUS,
+                        ),
+                        color,
+                    );
+                }
+            }
+
+            draw_band_labels(frame, &funding_band, "Funding", format_rate, weak_text);
+
+            if !basis.is_empty() {
+                frame.fill_rectangle(
+                    Point::new(0.0, funding_height),
+                    Size::new(width, 1.0),
+                    divider_color,
+                );
+
+                let line = Path::new(|builder| {
+                    for (i, (t, value)) in basis.iter().enumerate() {
+                        let point = Point::new(time.time_to_x(*t), basis_band.value_to_y(*value));
+                        if i == 0 {
+                            builder.move_to(point);
+                        } else {
+                            builder.line_to(point);
+                        }
+                    }
+                });
+                frame.stroke(
+                    &line,
+                    Stroke::with_color(Stroke::default().with_width(1.5), basis_color),
+                );
+
+                draw_band_labels(
+                    frame,
+                    &basis_band,
+                    "Basis (perp − index)",
+                    |v| self.format_basis(v),
+                    weak_text,
+                );
+            }
+
+            let timezone = data::config::timezone::display_timezone();
+            let interval = BASIS_TIMEFRAME.to_milliseconds();
+            for (t, x, align) in [
+                (start, 2.0, Alignment::Start),
+                (end, width - 2.0, Alignment::End),
+            ] {
+                frame.fill_text(Text {
+                    content: timezone.format_crosshair_timestamp(t as i64, interval),
+                    position: Point::new(x, plot_height + TIME_AXIS_HEIGHT / 2.0),
+                    color: weak_text,
+                    size: TEXT_SIZE.into(),
+                    font: style::AZERET_MONO,
+                    align_x: align.into(),
+                    align_y: Alignment::Center.into(),
+                    ..Default::default()
+                });
+            }
+        });
+
+        let mut geometries = vec![plots];
+
+        if let Some(position) = cursor.position_in(bounds)
+            && position.y <= plot_height
+        {
+            let mut frame = canvas::Frame::new(renderer, bounds.size());
+
+            frame.fill_rectangle(
+                Point::new(position.x, 0.0),
+                Size::new(1.0, plot_height),
+                divider_color,
+            );
+
+            let at = time.x_to_time(position.x);
+            let rate = rates.iter().rev().find(|(t, _)| *t <= at);
+            let basis_at = basis.iter().rev().find(|(t, _)| *t <= at);
+
+            let timezone = data::config::timezone::display_timezone();
+            let mut label = timezone.format_crosshair_timestamp(at as i64, 60_000);
+            if let Some((_, rate)) = rate {
+                label.push_str(&format!(" · {}", format_rate(*rate)));
+            }
+            if let Some((_, value)) = basis_at {
+                label.push_str(&format!(" · {}", self.format_basis(*value)));
+            }
+
+            let (x, align) = if position.x > bounds.width / 2.0 {
+                (position.x - 6.0, Alignment::End)
+            } else {
+                (position.x + 6.0, Alignment::Start)
+            };
+
+            frame.fill_text(Text {
+                content: label,
+                position: Point::new(x, position.y),
+                color: text_color,
+                size: TEXT_SIZE.into(),
+                font: style::AZERET_MONO,
+                align_x: align.into(),
+                align_y: Alignment::Center.into(),
+                ..Default::default()
+            });
+
+            geometries.push(frame.into_geometry());
+        }
+
+        geometries
+    }
+
+    fn mouse_interaction(
+        &self,
+        _state: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        if cursor.is_over(bounds) && self.has_funding() {
+            mouse::Interaction::Crosshair
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+}
@@ -194,11 +194,11 @@ impl Ladder {
 
     fn format_price(&self, price: Price) -> String {
         let precision = self.ticker_info.min_ticksize;
-        price.to_string(precision)
+        data::util::number_format().separate(price.to_string(precision))
     }
 
     fn format_quantity(&self, qty: f32) -> String {
-        data::util::abbr_large_numbers(qty)
+        data::util::format_volume(qty)
     }
 }
 
@@ -0,0 +1,486 @@
+use super::Message;
+use super::funding::{Band, TimeMapping, draw_band_labels};
+use crate::style;
+use data::panel::spread::{
+    Config, Leg, SpreadAlerts, SpreadEvent, SpreadHistory, SpreadPoint, SpreadUnit,
+};
+use exchange::adapter::{KlinePriceSource, StreamKind};
+use exchange::fetcher::{FetchRange, FetchRequests, FetchSpec, RequestHandler};
+use exchange::{Kline, TickerInfo, Timeframe};
+
+use iced::widget::canvas::{self, LineDash, Path, Stroke, Text};
+use iced::{Alignment, Point, Rectangle, Renderer, Size, Theme, mouse};
+
+use std::time::Instant;
+
+const TEXT_SIZE: f32 = 11.0;
+/// Height reserved under the plot for the time labels
+const TIME_AXIS_HEIGHT: f32 = 18.0;
+/// Number of bars of history loaded for both legs
+const HISTORY_BARS: u64 = 500;
+
+impl super::Panel for SpreadChart {
+    fn scroll(&mut self, _delta: f32) {}
+
+    fn reset_scroll(&mut self) {}
+
+    fn invalidate(&mut self, now: Option<Instant>) -> Option<super::Action> {
+        SpreadChart::invalidate(self, now)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.tickers.len() == 2 && self.history.latest().is_none()
+    }
+}
+
+pub struct SpreadChart {
+    /// The pane's ticker first, then the one it is compared against
+    tickers: Vec<TickerInfo>,
+    timeframe: Timeframe,
+    history: SpreadHistory,
+    request_handlers: [RequestHandler; 2],
+    /// Window of the history load, fixed so repeated requests for it are deduplicated
+    history_range: (u64, u64),
+    alerts: SpreadAlerts,
+    pub config: Config,
+    cache: canvas::Cache,
+    last_tick: Instant,
+}
+
+impl SpreadChart {
+    pub fn new(tickers: &[TickerInfo], timeframe: Timeframe, config: Option<Config>) -> Self {
+        let mut chart = Self {
+            tickers: tickers.iter().copied().take(2).collect(),
+            timeframe,
+            history: SpreadHistory::new(timeframe.to_milliseconds()),
+            request_handlers: [RequestHandler::new(), RequestHandler::new()],
+            history_range: (0, 0),
+            alerts: SpreadAlerts::default(),
+            config: config.unwrap_or_default(),
+            cache: canvas::Cache::default(),
+            last_tick: Instant::now(),
+        };
+        chart.reset_history();
+        chart
+    }
+
+    /// Kline streams of both legs, on the pane's timeframe
+    pub fn streams(&self) -> Vec<StreamKind> {
+        self.tickers
+            .iter()
+            .map(|ticker_info| StreamKind::Kline {
+                ticker_info: *ticker_info,
+                timeframe: self.timeframe,
+                source: KlinePriceSource::Trade,
+            })
+            .collect()
+    }
+
+    pub fn selected_tickers(&self) -> &[TickerInfo] {
+        &self.tickers
+    }
+
+    pub fn timeframe(&self) -> Timeframe {
+        self.timeframe
+    }
+
+    /// Sets the leg compared against the pane's ticker, replacing the previous one
+    pub fn add_ticker(&mut self, ticker_info: &TickerInfo) -> Vec<StreamKind> {
+        if !self.tickers.contains(ticker_info) {
+            self.tickers.truncate(1);
+            self.tickers.push(*ticker_info);
+            self.reset_history();
+        }
+        self.streams()
+    }
+
+    pub fn remove_ticker(&mut self, ticker_info: &TickerInfo) -> Vec<StreamKind> {
+        if self.tickers.len() > 1 && self.tickers.contains(ticker_info) {
+            self.tickers.retain(|t| t != ticker_info);
+            self.reset_history();
+        }
+        self.streams()
+    }
+
+    pub fn set_timeframe(&mut self, timeframe: Timeframe) -> Option<super::Action> {
+        self.timeframe = timeframe;
+        self.reset_history();
+        self.missing_fetches()
+    }
+
+    fn reset_history(&mut self) {
+        let now = now_ms();
+        let lookback = self.timeframe.to_milliseconds() * HISTORY_BARS;
+
+        self.history = SpreadHistory::new(self.timeframe.to_milliseconds());
+        self.request_handlers = [RequestHandler::new(), RequestHandler::new()];
+        self.history_range = (now.saturating_sub(lookback), now);
+        self.alerts = SpreadAlerts::default();
+        self.cache.clear();
+    }
+
+    fn leg_of(&self, ticker_info: &TickerInfo) -> Option<Leg> {
+        match self.tickers.iter().position(|t| t == ticker_info)? {
+            0 => Some(Leg::First),
+            _ => Some(Leg::Second),
+        }
+    }
+
+    pub fn insert_klines(
+        &mut self,
+        req_id: Option<uuid::Uuid>,
+        ticker_info: TickerInfo,
+        timeframe: Timeframe,
+        klines: &[Kline],
+    ) {
+        let Some(leg) = self.leg_of(&ticker_info) else {
+            return;
+        };
+        if timeframe != self.timeframe {
+            log::warn!(
+                "Ignoring stale kline fetch for timeframe {timeframe:?}; spread timeframe = {:?}",
+                self.timeframe
+            );
+            return;
+        }
+
+        self.history.insert(leg, klines);
+
+        if let Some(id) = req_id {
+            let handler = &mut self.request_handlers[leg as usize];
+            if klines.is_empty() {
+                handler.mark_failed(id, "No data received".to_string());
+            } else {
+                handler.mark_completed(id);
+            }
+        }
+        self.cache.clear();
+    }
+
+    /// Applies a live kline and reports whether the spread just crossed an alert threshold
+    pub fn update_latest_kline(
+        &mut self,
+        ticker_info: &TickerInfo,
+        kline: &Kline,
+    ) -> Option<SpreadEvent> {
+        let leg = self.leg_of(ticker_info)?;
+        self.history.insert(leg, std::slice::from_ref(kline));
+
+        let latest = self.history.latest()?;
+        self.alerts.check(&self.config, latest)
+    }
+
+    pub fn alert_text(&self, event: &SpreadEvent) -> String {
+        let (point, direction, threshold) = match event {
+            SpreadEvent::Above(point) => (point, "above", self.config.upper_bps),
+            SpreadEvent::Below(point) => (point, "below", self.config.lower_bps),
+        };
+
+        format!(
+            "{} spread {direction} {threshold:+.0} bps: {} ({:+.1} bps)",
+            self.legs_label(),
+            self.format_abs(point.abs),
+            point.bps,
+        )
+    }
+
+    pub fn last_update(&self) -> Instant {
+        self.last_tick
+    }
+
+    pub fn invalidate(&mut self, now: Option<Instant>) -> Option<super::Action> {
+        self.cache.clear();
+        if let Some(now) = now {
+            self.last_tick = now;
+        }
+
+        self.missing_fetches()
+    }
+
+    fn missing_fetches(&mut self) -> Option<super::Action> {
+        if self.tickers.len() < 2 {
+            return None;
+        }
+
+        let (from, to) = self.history_range;
+        let fetch = FetchRange::Kline(from, to);
+
+        let mut specs = FetchRequests::new();
+        for (i, ticker_info) in self.tickers.iter().enumerate() {
+            if let Ok(Some(req_id)) = self.request_handlers[i].add_request(fetch) {
+                specs.push(FetchSpec {
+                    req_id,
+                    fetch,
+                    stream: Some(StreamKind::Kline {
+                        ticker_info: *ticker_info,
+                        timeframe: self.timeframe,
+                        source: KlinePriceSource::Trade,
+                    }),
+                });
+            }
+        }
+
+        if specs.is_empty() {
+            None
+        } else {
+            Some(super::Action::RequestFetch(specs))
+        }
+    }
+
+    fn legs_label(&self) -> String {
+        self.tickers
+            .iter()
+            .map(|t| format!("{} {}", t.exchange(), t.ticker.display_symbol_and_type().0))
+            .collect::<Vec<_>>()
+            .join(" − ")
+    }
+
+    /// Absolute spread, one decimal finer than the finer leg since mids can fall between ticks
+    fn format_abs(&self, value: f32) -> String {
+        let power = self
+            .tickers
+            .iter()
+            .map(|t| t.min_ticksize.power)
+            .min()
+            .unwrap_or(0);
+        let decimals = (1 - power as i32).max(0) as usize;
+
+        format!("{value:+.decimals$}")
+    }
+
+    fn format_value(&self, value: f32) -> String {
+        match self.config.unit {
+            SpreadUnit::Bps => format!("{value:+.1} bps"),
+            SpreadUnit::Absolute => self.format_abs(value),
+        }
+    }
+
+    fn point_label(&self, point: &SpreadPoint) -> String {
+        format!("{} · {:+.1} bps", self.format_abs(point.abs), point.bps)
+    }
+}
+
+fn now_ms() -> u64 {
+    chrono::Utc::now().timestamp_millis().max(0) as u64
+}
+
+impl canvas::Program<Message> for SpreadChart {
+    type State = ();
+
+    fn update(
+        &self,
+        _state: &mut Self::State,
+        event: &iced::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Message>> {
+        cursor.position_in(bounds)?;
+
+        match event {
+            iced::Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                Some(canvas::Action::request_redraw())
+            }
+            _ => None,
+        }
+    }
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry<Renderer>> {
+        let palette = theme.extended_palette();
+
+        let text_color = palette.background.base.text;
+        let weak_text = palette.background.strong.color;
+        let line_color = palette.primary.base.color;
+        let alert_color = palette.danger.base.color.scale_alpha(0.6);
+        let divider_color = style::split_ruler(theme).color;
+
+        if self.tickers.len() < 2 {
+            let message = self.cache.draw(renderer, bounds.size(), |frame| {
+                frame.fill_text(Text {
+                    content: "Add a ticker to compare from the tickers list".to_string(),
+                    position: frame.center(),
+                    color: weak_text,
+                    size: 14.0.into(),
+                    font: style::AZERET_MONO,
+                    align_x: Alignment::Center.into(),
+                    align_y: Alignment::Center.into(),
+                    ..Default::default()
+                });
+            });
+            return vec![message];
+        }
+
+        let points = self.history.points();
+        let (Some(first), Some(last)) = (points.first(), points.last()) else {
+            return vec![];
+        };
+
+        let unit = self.config.unit;
+        let show_thresholds = self.config.alert && unit == SpreadUnit::Bps;
+
+        let plot_height = (bounds.height - TIME_AXIS_HEIGHT).max(0.0);
+        let time = TimeMapping {
+            start: first.time,
+            end: last.time,
+            width: bounds.width,
+        };
+
+        let thresholds = [self.config.upper_bps, self.config.lower_bps];
+        let values = points.iter().map(|p| p.value(unit));
+        let band = if show_thresholds {
+            Band::new(0.0, plot_height, values.chain(thresholds))
+        } else {
+            Band::new(0.0, plot_height, values)
+        };
+
+        let plot = self.cache.draw(renderer, bounds.size(), |frame| {
+            let width = bounds.width;
+
+            frame.fill_rectangle(
+                Point::new(0.0, band.value_to_y(0.0)),
+                Size::new(width, 1.0),
+                divider_color.scale_alpha(0.6),
+            );
+            frame.fill_rectangle(
+                Point::new(0.0, plot_height),
+                Size::new(width, 1.0),
+                divider_color,
+            );
+
+            if show_thresholds {
+                for threshold in thresholds {
+                    let y = band.value_to_y(threshold);
+                    frame.stroke(
+                        &Path::line(Point::new(0.0, y), Point::new(width, y)),
+                        Stroke::with_color(
+                            Stroke {
+                                width: 1.0,
+                                line_dash: LineDash {
+                                    segments: &[4.0, 4.0],
+                                    offset: 0,
+                                },
+                                ..Default::default()
+                            },
+                            alert_color,
+                        ),
+                    );
+                }
+            }
+
+            let line = Path::new(|builder| {
+                for (i, point) in points.iter().enumerate() {
+                    let at = Point::new(
+                        time.time_to_x(point.time),
+                        band.value_to_y(point.value(unit)),
+                    );
+                    if i == 0 {
+                        builder.move_to(at);
+                    } else {
+                        builder.line_to(at);
+                    }
+                }
+            });
+            frame.stroke(
+                &line,
+                Stroke::with_color(Stroke::default().with_width(1.5), line_color),
+            );
+
+            draw_band_labels(
+                frame,
+                &band,
+                &self.legs_label(),
+                |v| self.format_value(v),
+                weak_text,
+            );
+
+            frame.fill_text(Text {
+                content: self.point_label(last),
+                position: Point::new(4.0, TEXT_SIZE + 6.0),
+                color: text_color,
+                size: TEXT_SIZE.into(),
+                font: style::AZERET_MONO,
+                ..Default::default()
+            });
+
+            let timezone = data::config::timezone::display_timezone();
+            let interval = self.timeframe.to_milliseconds();
+            for (t, x, align) in [
+                (first.time, 2.0, Alignment::Start),
+                (last.time, width - 2.0, Alignment::End),
+            ] {
+                frame.fill_text(Text {
+                    content: timezone.format_crosshair_timestamp(t as i64, interval),
+                    position: Point::new(x, plot_height + TIME_AXIS_HEIGHT / 2.0),
+                    color: weak_text,
+                    size: TEXT_SIZE.into(),
+                    font: style::AZERET_MONO,
+                    align_x: align.into(),
+                    align_y: Alignment::Center.into(),
+                    ..Default::default()
+                });
+            }
+        });
+
+        let mut geometries = vec![plot];
+
+        if let Some(position) = cursor.position_in(bounds)
+            && position.y <= plot_height
+        {
+            let mut frame = canvas::Frame::new(renderer, bounds.size());
+
+            frame.fill_rectangle(
+                Point::new(position.x, 0.0),
+                Size::new(1.0, plot_height),
+                divider_color,
+            );
+
+            let at = time.x_to_time(position.x);
+            let timezone = data::config::timezone::display_timezone();
+            let mut label =
+                timezone.format_crosshair_timestamp(at as i64, self.timeframe.to_milliseconds());
+            if let Some(point) = points.iter().rev().find(|p| p.time <= at) {
+                label.push_str(&format!(" · {}", self.point_label(point)));
+            }
+
+            let (x, align) = if position.x > bounds.width / 2.0 {
+                (position.x - 6.0, Alignment::End)
+            } else {
+                (position.x + 6.0, Alignment::Start)
+            };
+
+            frame.fill_text(Text {
+                content: label,
+                position: Point::new(x, position.y),
+                color: text_color,
+                size: TEXT_SIZE.into(),
+                font: style::AZERET_MONO,
+                align_x: align.into(),
+                align_y: Alignment::Center.into(),
+                ..Default::default()
+            });
+
+            geometries.push(frame.into_geometry());
+        }
+
+        geometries
+    }
+
+    fn mouse_interaction(
+        &self,
+        _state: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        if cursor.is_over(bounds) && self.tickers.len() == 2 {
+            mouse::Interaction::Crosshair
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+}
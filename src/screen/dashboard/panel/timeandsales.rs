@@ -14,6 +14,8 @@ const TEXT_SIZE: iced::Pixels = iced::Pixels(11.0);
 const METRICS_HEIGHT_COMPACT: f32 = 8.0;
 const METRICS_HEIGHT_FULL: f32 = 18.0;
 const TRADE_ROW_HEIGHT: f32 = 14.0;
+/// How long a newly printed block trade keeps flashing
+const BLOCK_FLASH_MS: u64 = 1500;
 
 impl super::Panel for TimeAndSales {
     fn scroll(&mut self, delta: f32) {
@@ -110,7 +112,10 @@ impl TimeAndSales {
         let market_type = self.ticker_info.market_type();
         let size_in_quote_ccy = volume_size_unit() == exchange::SizeUnit::Quote;
 
-        for trade in trades_buffer {
+        let prints = self.config.print_aggregation.apply(trades_buffer);
+        let timezone = data::config::timezone::display_timezone();
+
+        for trade in prints.iter() {
             let trade_time_ms = trade.time;
 
             if let Some(trade_time) = chrono::DateTime::from_timestamp(
@@ -118,7 +123,10 @@ impl TimeAndSales {
                 (trade_time_ms % 1000) as u32 * 1_000_000,
             ) {
                 let trade_display = TradeDisplay {
-                    time_str: trade_time.format("%M:%S.%3f").to_string(),
+                    time_str: timezone
+                        .to_fixed_offset(&trade_time)
+                        .format("%M:%S.%3f")
+                        .to_string(),
                     price: trade.price,
                     qty: trade.qty,
                     is_sell: trade.is_sell,
@@ -395,7 +403,7 @@ impl canvas::Program<Message> for TimeAndSales {
                         let buy_text_content = match ratio_kind {
                             StackedBarRatio::Count => format!("{}", buy_val as i64),
                             StackedBarRatio::AverageSize | StackedBarRatio::Volume => {
-                                data::util::abbr_large_numbers(buy_val as f32)
+                                data::util::format_volume(buy_val as f32)
                             }
                         };
                         let buy_text = Text {
@@ -416,7 +424,7 @@ impl canvas::Program<Message> for TimeAndSales {
                         let sell_text_content = match ratio_kind {
                             StackedBarRatio::Count => format!("{}", sell_val as i64),
                             StackedBarRatio::AverageSize | StackedBarRatio::Volume => {
-                                data::util::abbr_large_numbers(sell_val as f32)
+                                data::util::format_volume(sell_val as f32)
                             }
                         };
                         let sell_text = Text {
@@ -446,6 +454,7 @@ impl canvas::Program<Message> for TimeAndSales {
             let visible_rows = (bounds.height / row_height).ceil() as usize;
 
             let size_in_quote_ccy = volume_size_unit() == exchange::SizeUnit::Quote;
+            let now_ms = chrono::Utc::now().timestamp_millis().max(0) as u64;
 
             let trades_to_draw = self
                 .recent_trades
@@ -482,19 +491,33 @@ impl canvas::Program<Message> for TimeAndSales {
                     continue;
                 }
 
-                let bg_color = if trade.is_sell {
-                    palette.danger.weak.color
+                let trade_size =
+                    market_type.qty_in_quote_value(trade.qty, trade.price, size_in_quote_ccy);
+                let is_block_trade = self.config.is_block_trade(trade_size);
+
+                let (bg_color, block_pair) = if trade.is_sell {
+                    (palette.danger.weak.color, palette.danger.strong)
                 } else {
-                    palette.success.weak.color
+                    (palette.success.weak.color, palette.success.strong)
                 };
 
-                let bg_color_alpha = if self.max_filtered_qty > 0.0 {
+                let bar_ratio = self
+                    .config
+                    .size_scaling
+                    .bar_ratio(trade.qty, self.max_filtered_qty);
+
+                // Bars already show the size, so their text doesn't fade with it
+                let bg_color_alpha = if bar_ratio.is_some() {
+                    0.6
+                } else if self.max_filtered_qty > 0.0 {
                     (trade.qty / self.max_filtered_qty).clamp(0.02, 1.0)
                 } else {
                     0.02
                 };
 
-                let mut text_color = if palette.is_dark {
+                let mut text_color = if is_block_trade {
+                    block_pair.text
+                } else if palette.is_dark {
                     lighten(bg_color, bg_color_alpha.max(0.1))
                 } else {
                     darken(bg_color, (bg_color_alpha * 0.8).max(0.1))
@@ -507,17 +530,50 @@ impl canvas::Program<Message> for TimeAndSales {
                     text_color = text_color.scale_alpha(0.1);
                 }
 
-                frame.fill_rectangle(
-                    Point {
-                        x: 0.0,
-                        y: y_position,
-                    },
-                    Size {
-                        width: row_width,
-                        height: row_height,
-                    },
-                    bg_color.scale_alpha(bg_color_alpha.min(0.9)),
-                );
+                let row_origin = Point {
+                    x: 0.0,
+                    y: y_position,
+                };
+                let row_size = Size {
+                    width: row_width,
+                    height: row_height,
+                };
+
+                if is_block_trade {
+                    frame.fill_rectangle(row_origin, row_size, block_pair.color);
+
+                    let since_print = now_ms.saturating_sub(entry.ts_ms);
+                    if since_print < BLOCK_FLASH_MS {
+                        let fade = 1.0 - since_print as f32 / BLOCK_FLASH_MS as f32;
+                        frame.fill_rectangle(
+                            row_origin,
+                            row_size,
+                            palette.primary.base.color.scale_alpha(0.6 * fade),
+                        );
+                    }
+                } else if let Some(bar_ratio) = bar_ratio {
+                    // Volume bar growing from the right edge, over a faint row background
+                    frame.fill_rectangle(row_origin, row_size, bg_color.scale_alpha(0.08));
+
+                    let bar_width = (row_width * bar_ratio).max(1.0);
+                    frame.fill_rectangle(
+                        Point {
+                            x: row_width - bar_width,
+                            y: y_position,
+                        },
+                        Size {
+                            width: bar_width,
+                            height: row_height,
+                        },
+                        bg_color.scale_alpha(0.6),
+                    );
+                } else {
+                    frame.fill_rectangle(
+                        row_origin,
+                        row_size,
+                        bg_color.scale_alpha(bg_color_alpha.min(0.9)),
+                    );
+                }
 
                 let trade_time = create_text(
                     trade.time_str.clone(),
@@ -531,7 +587,8 @@ impl canvas::Program<Message> for TimeAndSales {
                 frame.fill_text(trade_time);
 
                 let trade_price = create_text(
-                    trade.price.to_string(self.ticker_info.min_ticksize),
+                    data::util::number_format()
+                        .separate(trade.price.to_string(self.ticker_info.min_ticksize)),
                     Point {
                         x: row_width * 0.67,
                         y: y_position,
@@ -542,7 +599,7 @@ impl canvas::Program<Message> for TimeAndSales {
                 frame.fill_text(trade_price);
 
                 let trade_qty = create_text(
-                    data::util::abbr_large_numbers(trade.qty),
+                    data::util::format_volume(trade.qty),
                     Point {
                         x: row_width * 0.9,
                         y: y_position,
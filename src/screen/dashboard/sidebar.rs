@@ -8,9 +8,9 @@ use crate::{
 use data::sidebar;
 
 use iced::{
-    Alignment, Element, Subscription, Task,
+    Alignment, Element, Length, Subscription, Task,
     widget::responsive,
-    widget::{column, row, space},
+    widget::{column, container, mouse_area, row, space},
 };
 use rustc_hash::FxHashMap;
 
@@ -18,12 +18,20 @@ use rustc_hash::FxHashMap;
 pub enum Message {
     ToggleSidebarMenu(Option<sidebar::Menu>),
     SetSidebarPosition(sidebar::Position),
+    SetPinned(bool),
+    SetCompact(bool),
+    Hovered(bool),
     TickersTable(super::tickers_table::Message),
 }
 
+/// Width of the strip left on screen while an unpinned sidebar is collapsed
+pub const COLLAPSED_WIDTH: f32 = 6.0;
+
 pub struct Sidebar {
     pub state: data::Sidebar,
     pub tickers_table: TickersTable,
+    hovered: bool,
+    changed: bool,
 }
 
 pub enum Action {
@@ -36,17 +44,20 @@ pub enum Action {
 
 impl Sidebar {
     pub fn new(state: &SavedState) -> (Self, Task<Message>) {
-        let (tickers_table, initial_fetch) =
+        let (mut tickers_table, initial_fetch) =
             if let Some(settings) = state.sidebar.tickers_table.as_ref() {
                 TickersTable::new_with_settings(settings)
             } else {
                 TickersTable::new()
             };
+        tickers_table.set_compact(state.sidebar.compact);
 
         (
             Self {
                 state: state.sidebar.clone(),
                 tickers_table,
+                hovered: false,
+                changed: false,
             },
             initial_fetch.map(Message::TickersTable),
         )
@@ -59,6 +70,19 @@ impl Sidebar {
             }
             Message::SetSidebarPosition(position) => {
                 self.state.position = position;
+                self.changed = true;
+            }
+            Message::SetPinned(pinned) => {
+                self.state.pinned = pinned;
+                self.changed = true;
+            }
+            Message::SetCompact(compact) => {
+                self.state.compact = compact;
+                self.tickers_table.set_compact(compact);
+                self.changed = true;
+            }
+            Message::Hovered(hovered) => {
+                self.hovered = hovered;
             }
             Message::TickersTable(msg) => {
                 let action = self.tickers_table.update(msg);
@@ -90,6 +114,17 @@ impl Sidebar {
     pub fn view(&self, audio_volume: Option<f32>) -> Element<'_, Message> {
         let state = &self.state;
 
+        if self.is_collapsed() {
+            return mouse_area(
+                container(space::horizontal())
+                    .width(COLLAPSED_WIDTH)
+                    .height(Length::Fill)
+                    .style(crate::style::sidebar_handle),
+            )
+            .on_enter(Message::Hovered(true))
+            .into();
+        }
+
         let tooltip_position = if state.position == sidebar::Position::Left {
             TooltipPosition::Right
         } else {
@@ -105,17 +140,31 @@ impl Sidebar {
                 .tickers_table
                 .view(size)
                 .map(Message::TickersTable))]
-            .width(200)
+            .width(self.tickers_table.table_width())
         } else {
             column![]
         };
 
-        match state.position {
+        let content = match state.position {
             sidebar::Position::Left => row![nav_buttons, tickers_table],
             sidebar::Position::Right => row![tickers_table, nav_buttons],
         }
-        .spacing(if is_table_open { 8 } else { 4 })
-        .into()
+        .spacing(if is_table_open { 8 } else { 4 });
+
+        if state.pinned {
+            content.into()
+        } else {
+            // slides over the charts, so it needs its own backdrop
+            mouse_area(
+                container(content)
+                    .padding(4)
+                    .height(Length::Fill)
+                    .style(crate::style::dashboard_modal),
+            )
+            .on_enter(Message::Hovered(true))
+            .on_exit(Message::Hovered(false))
+            .into()
+        }
     }
 
     pub fn subscription(&self) -> Subscription<Message> {
@@ -189,11 +238,95 @@ impl Sidebar {
             )
         };
 
+        let connections_btn = {
+            let is_active = self.is_menu_active(sidebar::Menu::Connections);
+
+            button_with_tooltip(
+                icon_text(Icon::Link, 14)
+                    .width(24)
+                    .align_x(Alignment::Center),
+                Message::ToggleSidebarMenu(Some(sidebar::Menu::Connections)),
+                None,
+                tooltip_position,
+                move |theme, status| crate::style::button::transparent(theme, status, is_active),
+            )
+        };
+
+        let measurements_btn = {
+            let is_active = self.is_menu_active(sidebar::Menu::Measurements);
+
+            button_with_tooltip(
+                icon_text(Icon::Edit, 14)
+                    .width(24)
+                    .align_x(Alignment::Center),
+                Message::ToggleSidebarMenu(Some(sidebar::Menu::Measurements)),
+                None,
+                tooltip_position,
+                move |theme, status| crate::style::button::transparent(theme, status, is_active),
+            )
+        };
+
+        let scanner_btn = {
+            let is_active = self.is_menu_active(sidebar::Menu::Scanner);
+
+            button_with_tooltip(
+                icon_text(Icon::Sort, 14)
+                    .width(24)
+                    .align_x(Alignment::Center),
+                Message::ToggleSidebarMenu(Some(sidebar::Menu::Scanner)),
+                None,
+                tooltip_position,
+                move |theme, status| crate::style::button::transparent(theme, status, is_active),
+            )
+        };
+
+        let replay_btn = {
+            let is_active = self.is_menu_active(sidebar::Menu::Replay);
+
+            button_with_tooltip(
+                icon_text(Icon::ChartOutline, 14)
+                    .width(24)
+                    .align_x(Alignment::Center),
+                Message::ToggleSidebarMenu(Some(sidebar::Menu::Replay)),
+                None,
+                tooltip_position,
+                move |theme, status| crate::style::button::transparent(theme, status, is_active),
+            )
+        };
+
+        let pin_btn = {
+            let is_pinned = self.state.pinned;
+            let icon = if is_pinned {
+                Icon::Locked
+            } else {
+                Icon::Unlocked
+            };
+
+            button_with_tooltip(
+                icon_text(icon, 14)
+                    .width(24)
+                    .align_x(Alignment::Center),
+                Message::SetPinned(!is_pinned),
+                Some(if is_pinned {
+                    "Unpin sidebar (auto-hide)"
+                } else {
+                    "Pin sidebar"
+                }),
+                tooltip_position,
+                move |theme, status| crate::style::button::transparent(theme, status, false),
+            )
+        };
+
         column![
             ticker_search_button,
             layout_modal_button,
             audio_btn,
+            connections_btn,
+            measurements_btn,
+            scanner_btn,
+            replay_btn,
             space::vertical(),
+            pin_btn,
             settings_modal_button,
         ]
         .width(32)
@@ -222,6 +355,14 @@ impl Sidebar {
         self.state.active_menu
     }
 
+    pub fn is_pinned(&self) -> bool {
+        self.state.pinned
+    }
+
+    pub fn is_collapsed(&self) -> bool {
+        self.state.is_collapsed(self.hovered)
+    }
+
     pub fn position(&self) -> sidebar::Position {
         self.state.position
     }
@@ -230,6 +371,11 @@ impl Sidebar {
         self.state.active_menu = menu;
     }
 
+    /// Whether the sidebar or tickers table settings changed since the last call
+    pub fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed) | self.tickers_table.take_changed()
+    }
+
     pub fn sync_tickers_table_settings(&mut self) {
         let settings = &self.tickers_table.settings();
         self.state.tickers_table = Some(settings.clone());
@@ -1,48 +1,67 @@
 use crate::{
+    TooltipPosition,
     modal::pane::mini_tickers_list::RowSelection,
     style::{self, Icon, icon_text},
+    widget::tooltip,
 };
 use data::{
     InternalError,
     layout::pane::ContentKind,
     tickers_table::{
-        PriceChangeDirection, Settings, SortOptions, TickerDisplayData, TickerRowData,
-        compute_display_data,
+        ColumnCfg, PriceChangeDirection, QuoteFilter, Settings, SortOptions, SymbolFilter,
+        TableColumn, TickerDisplayData, TickerRowData, compute_display_data, quote_groups,
+        split_decimal, staggered_period,
     },
+    util::count_decimals,
 };
 use exchange::{
     Ticker, TickerInfo, TickerStats,
-    adapter::{Exchange, ExchangeInclusive, MarketKind, fetch_ticker_info, fetch_ticker_prices},
+    adapter::{Exchange, ExchangeInclusive, MarketKind, fetch_ticker_prices},
+    fetcher::{LoadState, MetadataUpdate, fetch_market_metadata},
 };
 use iced::{
-    Alignment, Element, Length, Renderer, Size, Subscription, Task, Theme,
+    Alignment, Color, Element, Length, Renderer, Size, Subscription, Task, Theme,
     alignment::{self, Horizontal, Vertical},
     padding,
     widget::{
-        Button, Space, button, column, container, row, rule,
+        Button, Space, button, checkbox, column, container, pick_list, row, rule,
         scrollable::{self, AbsoluteOffset},
-        space, text, text_input,
+        slider, space, text, text_input,
     },
 };
 use rustc_hash::{FxHashMap, FxHashSet};
-use std::{cmp::Ordering, collections::HashMap};
+use std::{cmp::Ordering, collections::HashMap, time::Duration};
 
-const ACTIVE_UPDATE_INTERVAL: u64 = 13;
-const INACTIVE_UPDATE_INTERVAL: u64 = 300;
+/// فاصله بروزرسانی آمار هر صرافی وقتی جدول پنهان است
+const INACTIVE_UPDATE_INTERVAL: Duration = Duration::from_secs(300);
+const REFRESH_SECS_STEP: u64 = 5;
 
 /// Number of extra cards to render for visibility during scrolling
 const OVERSCAN_BUFFER: isize = 3;
 const TICKER_CARD_HEIGHT: f32 = 64.0;
+const COMPACT_CARD_HEIGHT: f32 = 32.0;
 
 const FAVORITES_SEPARATOR_HEIGHT: f32 = 12.0;
 const FAVORITES_EMPTY_HINT_HEIGHT: f32 = 32.0;
 
 const TOP_BAR_HEIGHT: f32 = 40.0;
-const SORT_AND_FILTER_HEIGHT: f32 = 200.0;
+const SORT_AND_FILTER_HEIGHT: f32 = 420.0;
+
+/// Minimum sidebar table width, it grows past this when the columns are widened
+const MIN_TABLE_WIDTH: f32 = 200.0;
+/// Horizontal space taken by the scrollbar, card paddings and the change bar
+const CARD_CHROME_WIDTH: f32 = 42.0;
+const COLUMN_SPACING: f32 = 4.0;
+/// Rough glyph width relative to the text size, for sizing the fraction part of a column
+const GLYPH_WIDTH_RATIO: f32 = 0.6;
+const CARD_TEXT_SIZE: f32 = 14.0;
+const COMPACT_TEXT_SIZE: f32 = 12.0;
+/// Symbols longer than this are truncated in the cards, with the full name in a tooltip
+const MAX_LABEL_CHARS: usize = 10;
 
 const COMPACT_ROW_HEIGHT: f32 = 28.0;
 
-const EXCHANGE_FILTERS: [(ExchangeInclusive, Exchange, &str); 4] = [
+const EXCHANGE_FILTERS: [(ExchangeInclusive, Exchange, &str); 7] = [
     (ExchangeInclusive::Bybit, Exchange::BybitLinear, "Bybit"),
     (
         ExchangeInclusive::Binance,
@@ -55,20 +74,20 @@ const EXCHANGE_FILTERS: [(ExchangeInclusive, Exchange, &str); 4] = [
         "Hyperliquid",
     ),
     (ExchangeInclusive::Okex, Exchange::OkexLinear, "OKX"),
+    (ExchangeInclusive::Gateio, Exchange::GateioLinear, "Gate"),
+    (
+        ExchangeInclusive::Coinbase,
+        Exchange::CoinbaseSpot,
+        "Coinbase",
+    ),
+    (ExchangeInclusive::Bitget, Exchange::BitgetLinear, "Bitget"),
 ];
 
 pub fn fetch_tickers_info() -> Task<Message> {
-    let fetch_tasks = Exchange::ALL
-        .iter()
-        .map(|exchange| {
-            Task::perform(fetch_ticker_info(*exchange), move |result| match result {
-                Ok(ticker_info) => Message::UpdateTickersInfo(*exchange, ticker_info),
-                Err(err) => Message::ErrorOccurred(InternalError::Fetch(err.to_string())),
-            })
-        })
-        .collect::<Vec<Task<Message>>>();
-
-    Task::batch(fetch_tasks)
+    Task::run(
+        fetch_market_metadata(Exchange::ALL),
+        Message::MetadataFetched,
+    )
 }
 
 pub enum Action {
@@ -92,8 +111,16 @@ pub enum Message {
     ToggleTable,
     ToggleFavorites,
     FetchForTickerStats(Option<Exchange>),
-    UpdateTickersInfo(Exchange, HashMap<Ticker, Option<TickerInfo>>),
+    RefreshNextExchange,
+    SetRefreshInterval(u64),
+    MoveColumn(TableColumn, bool),
+    ResizeColumn(TableColumn, f32),
+    ToggleFixedDecimals(bool),
+    SetQuoteFilter(QuoteFilter),
+    ToggleGroupByQuote(bool),
+    MetadataFetched(MetadataUpdate),
     UpdateTickerStats(Exchange, HashMap<Ticker, TickerStats>),
+    TickerStatsFailed(Exchange, String),
     ErrorOccurred(data::InternalError),
 }
 
@@ -112,7 +139,34 @@ pub struct TickersTable {
     selected_markets: FxHashSet<MarketKind>,
     show_favorites: bool,
     row_index: FxHashMap<Ticker, usize>,
-    pending_stats_batches: usize,
+    stats_in_flight: FxHashSet<Exchange>,
+    refresh_cursor: usize,
+    stats_refresh_secs: u64,
+    load_states: FxHashMap<Exchange, LoadState>,
+    compact: bool,
+    symbol_filters: Vec<SymbolFilter>,
+    columns: Vec<ColumnCfg>,
+    fixed_decimals: bool,
+    quote_filter: QuoteFilter,
+    group_by_quote: bool,
+    /// Settings returned by `settings` changed since the last check
+    changed: bool,
+}
+
+/// An entry of the main list below the favorites
+#[derive(Clone, Copy)]
+enum ListItem<'a> {
+    Ticker(&'a TickerRowData),
+    /// Subheader of a quote group with the number of tickers in it, `None` for unknown quotes
+    QuoteHeader(Option<&'static str>, usize),
+}
+
+/// A numeric column as laid out for the rows currently in view
+#[derive(Debug, Clone, Copy)]
+struct ColumnLayout {
+    column: TableColumn,
+    width: f32,
+    fraction_width: f32,
 }
 
 impl TickersTable {
@@ -126,7 +180,7 @@ impl TickersTable {
                 ticker_rows: Vec::new(),
                 display_cache: FxHashMap::default(),
                 favorited_tickers: settings.favorited_tickers.iter().cloned().collect(),
-                search_query: String::new(),
+                search_query: settings.search_query.clone(),
                 show_sort_options: false,
                 selected_sort_option: settings.selected_sort_option,
                 expand_ticker_card: None,
@@ -137,12 +191,34 @@ impl TickersTable {
                 selected_markets: settings.selected_markets.iter().cloned().collect(),
                 show_favorites: settings.show_favorites,
                 row_index: FxHashMap::default(),
-                pending_stats_batches: 0,
+                stats_in_flight: FxHashSet::default(),
+                refresh_cursor: 0,
+                stats_refresh_secs: settings.stats_refresh_secs,
+                load_states: Exchange::ALL
+                    .iter()
+                    .map(|exchange| (*exchange, LoadState::Loading))
+                    .collect(),
+                compact: false,
+                symbol_filters: settings.symbol_filters.clone(),
+                columns: settings.table_columns(),
+                fixed_decimals: settings.fixed_decimals,
+                quote_filter: settings.quote_filter.clone(),
+                group_by_quote: settings.group_by_quote,
+                changed: false,
             },
             fetch_tickers_info(),
         )
     }
 
+    /// Single-line rows in the sidebar list, to fit more tickers on small screens
+    pub fn set_compact(&mut self, compact: bool) {
+        self.compact = compact;
+    }
+
+    pub fn ticker_rows(&self) -> &[TickerRowData] {
+        &self.ticker_rows
+    }
+
     pub fn settings(&self) -> Settings {
         Settings {
             favorited_tickers: self.favorited_tickers.iter().copied().collect(),
@@ -150,16 +226,38 @@ impl TickersTable {
             selected_sort_option: self.selected_sort_option,
             selected_exchanges: self.selected_exchanges.iter().cloned().collect(),
             selected_markets: self.selected_markets.iter().cloned().collect(),
+            search_query: self.search_query.clone(),
+            stats_refresh_secs: self.stats_refresh_secs,
+            symbol_filters: self.symbol_filters.clone(),
+            columns: self.columns.clone(),
+            fixed_decimals: self.fixed_decimals,
+            quote_filter: self.quote_filter.clone(),
+            group_by_quote: self.group_by_quote,
         }
     }
 
+    /// Width of the sidebar table, wide enough for the configured columns
+    pub fn table_width(&self) -> f32 {
+        let columns: f32 = self.columns.iter().map(|c| c.width).sum();
+        let gaps = COLUMN_SPACING * self.columns.len().saturating_sub(1) as f32;
+
+        (columns + gaps + CARD_CHROME_WIDTH).max(MIN_TABLE_WIDTH)
+    }
+
+    /// Whether the persisted table settings changed since the last call
+    pub fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+
     pub fn update(&mut self, message: Message) -> Option<Action> {
         match message {
             Message::UpdateSearchQuery(query) => {
                 self.search_query = query.to_uppercase();
+                self.changed = true;
             }
             Message::ChangeSortOption(option) => {
                 self.change_sort_option(option);
+                self.changed = true;
             }
             Message::ShowSortingOptions => {
                 self.show_sort_options = !self.show_sort_options;
@@ -169,6 +267,7 @@ impl TickersTable {
             }
             Message::FavoriteTicker(ticker) => {
                 self.favorite_ticker(ticker);
+                self.changed = true;
             }
             Message::Scrolled(viewport) => {
                 self.scroll_offset = viewport.absolute_offset();
@@ -179,6 +278,7 @@ impl TickersTable {
                 } else {
                     self.selected_markets.insert(market);
                 }
+                self.changed = true;
             }
             Message::ToggleExchangeFilter(exch) => {
                 if self.selected_exchanges.contains(&exch) {
@@ -186,9 +286,11 @@ impl TickersTable {
                 } else {
                     self.selected_exchanges.insert(exch);
                 }
+                self.changed = true;
             }
             Message::ToggleFavorites => {
                 self.show_favorites = !self.show_favorites;
+                self.changed = true;
             }
             Message::TickerSelected(ticker, content) => {
                 let ticker_info = self.tickers_info.get(&ticker).cloned().flatten();
@@ -206,71 +308,126 @@ impl TickersTable {
                 self.is_shown = !self.is_shown;
 
                 if self.is_shown {
-                    self.display_cache.clear();
-                    for row in self.ticker_rows.iter_mut() {
-                        row.previous_stats = None;
-                        self.display_cache.insert(
-                            row.ticker,
-                            compute_display_data(&row.ticker, &row.stats, None),
-                        );
-                    }
+                    self.refresh_display_cache();
 
                     return Some(Action::FocusWidget("full_ticker_search_box".into()));
                 }
             }
             Message::FetchForTickerStats(exchange) => {
-                let task = if let Some(exchange) = exchange {
-                    self.pending_stats_batches = 1;
-                    Task::perform(fetch_ticker_prices(exchange), move |result| match result {
-                        Ok(ticker_rows) => Message::UpdateTickerStats(exchange, ticker_rows),
-                        Err(err) => Message::ErrorOccurred(InternalError::Fetch(err.to_string())),
-                    })
+                let exchanges: FxHashSet<Exchange> = if let Some(exchange) = exchange {
+                    FxHashSet::from_iter([exchange])
                 } else {
-                    let exchanges: FxHashSet<Exchange> =
-                        self.tickers_info.keys().map(|t| t.exchange).collect();
-
-                    self.pending_stats_batches = exchanges.len();
-
-                    let fetch_tasks = exchanges
-                        .into_iter()
-                        .map(|exchange| {
-                            Task::perform(fetch_ticker_prices(exchange), move |result| match result
-                            {
-                                Ok(ticker_rows) => {
-                                    Message::UpdateTickerStats(exchange, ticker_rows)
-                                }
-                                Err(err) => {
-                                    Message::ErrorOccurred(InternalError::Fetch(err.to_string()))
-                                }
-                            })
-                        })
-                        .collect::<Vec<Task<Message>>>();
-
-                    Task::batch(fetch_tasks)
+                    self.tickers_info.keys().map(|t| t.exchange).collect()
                 };
 
-                return Some(Action::Fetch(task));
+                let fetch_tasks = exchanges
+                    .into_iter()
+                    .map(|exchange| self.fetch_stats(exchange))
+                    .collect::<Vec<Task<Message>>>();
+
+                return Some(Action::Fetch(Task::batch(fetch_tasks)));
+            }
+            Message::RefreshNextExchange => {
+                let ready = self.ready_exchanges();
+                if ready.is_empty() {
+                    return None;
+                }
+
+                let exchange = ready[self.refresh_cursor % ready.len()];
+                self.refresh_cursor = self.refresh_cursor.wrapping_add(1);
+
+                // درخواست قبلی هنوز پشت محدودکننده نرخ منتظر است؛ نوبت این صرافی رد می‌شود
+                if self.stats_in_flight.contains(&exchange) {
+                    log::debug!("Skipping {exchange} stats refresh, previous fetch still pending");
+                    return None;
+                }
+
+                return Some(Action::Fetch(self.fetch_stats(exchange)));
+            }
+            Message::SetRefreshInterval(secs) => {
+                self.stats_refresh_secs = secs.clamp(
+                    *Settings::REFRESH_SECS_RANGE.start(),
+                    *Settings::REFRESH_SECS_RANGE.end(),
+                );
+                self.changed = true;
+            }
+            Message::MoveColumn(column, towards_start) => {
+                if let Some(idx) = self.columns.iter().position(|c| c.column == column) {
+                    let target = if towards_start {
+                        idx.checked_sub(1)
+                    } else {
+                        Some(idx + 1).filter(|i| *i < self.columns.len())
+                    };
+
+                    if let Some(target) = target {
+                        self.columns.swap(idx, target);
+                        self.changed = true;
+                    }
+                }
+            }
+            Message::ResizeColumn(column, width) => {
+                if let Some(cfg) = self.columns.iter_mut().find(|c| c.column == column) {
+                    cfg.width = width.clamp(
+                        *ColumnCfg::WIDTH_RANGE.start(),
+                        *ColumnCfg::WIDTH_RANGE.end(),
+                    );
+                    self.changed = true;
+                }
+            }
+            Message::ToggleFixedDecimals(enabled) => {
+                self.fixed_decimals = enabled;
+                self.refresh_display_cache();
+                self.changed = true;
+            }
+            Message::SetQuoteFilter(filter) => {
+                self.quote_filter = filter;
+                self.changed = true;
+            }
+            Message::ToggleGroupByQuote(enabled) => {
+                self.group_by_quote = enabled;
+                self.changed = true;
             }
             Message::UpdateTickerStats(exchange, stats) => {
                 self.update_ticker_rows(exchange, stats);
 
-                if self.pending_stats_batches > 0 {
-                    self.pending_stats_batches -= 1;
-                }
-                if self.pending_stats_batches == 0 {
+                self.stats_in_flight.remove(&exchange);
+                if self.stats_in_flight.is_empty() {
                     self.sort_ticker_rows();
                 }
             }
-            Message::UpdateTickersInfo(exchange, info) => {
-                self.update_ticker_info(exchange, info);
+            Message::TickerStatsFailed(exchange, err) => {
+                self.stats_in_flight.remove(&exchange);
 
-                let task =
-                    Task::perform(fetch_ticker_prices(exchange), move |result| match result {
-                        Ok(ticker_rows) => Message::UpdateTickerStats(exchange, ticker_rows),
-                        Err(err) => Message::ErrorOccurred(InternalError::Fetch(err.to_string())),
-                    });
+                let err = InternalError::Fetch(format!("{exchange}: {err}"));
+                log::error!("Error occurred: {err}");
+                return Some(Action::ErrorOccurred(err));
+            }
+            Message::MetadataFetched(update) => {
+                let exchange = update.exchange();
 
-                return Some(Action::Fetch(task));
+                match update {
+                    MetadataUpdate::TickersInfo(_, Ok(info)) => {
+                        self.update_ticker_info(exchange, info);
+                        self.load_states.insert(exchange, LoadState::Ready);
+                    }
+                    MetadataUpdate::TickerStats(_, Ok(stats)) => {
+                        self.update_ticker_rows(exchange, stats);
+                        self.sort_ticker_rows();
+                    }
+                    MetadataUpdate::TickersInfo(_, Err(err)) => {
+                        self.load_states
+                            .insert(exchange, LoadState::Failed(err.clone()));
+
+                        let err = InternalError::Fetch(format!("{exchange}: {err}"));
+                        log::error!("Error occurred: {err}");
+                        return Some(Action::ErrorOccurred(err));
+                    }
+                    MetadataUpdate::TickerStats(_, Err(err)) => {
+                        let err = InternalError::Fetch(format!("{exchange}: {err}"));
+                        log::error!("Error occurred: {err}");
+                        return Some(Action::ErrorOccurred(err));
+                    }
+                }
             }
             Message::ErrorOccurred(err) => {
                 log::error!("Error occurred: {err}");
@@ -292,8 +449,24 @@ impl TickersTable {
         let sep_block_height = self.sep_block_height(fav_n);
         let header_offset = self.header_offset_main();
 
+        let rest_items: Vec<ListItem> = if self.group_by_quote {
+            quote_groups(&rest_rows)
+                .into_iter()
+                .flat_map(|(quote, rows)| {
+                    std::iter::once(ListItem::QuoteHeader(quote, rows.len()))
+                        .chain(rows.into_iter().map(ListItem::Ticker))
+                })
+                .collect()
+        } else {
+            rest_rows.iter().copied().map(ListItem::Ticker).collect()
+        };
+
         let virtual_list = VirtualListConfig {
-            row_height: TICKER_CARD_HEIGHT,
+            row_height: if self.compact {
+                COMPACT_CARD_HEIGHT
+            } else {
+                TICKER_CARD_HEIGHT
+            },
             header_offset,
             overscan: OVERSCAN_BUFFER as usize,
             gap: if self.show_favorites {
@@ -302,14 +475,14 @@ impl TickersTable {
                 None
             },
         };
-        let total_rows = fav_n + rest_n;
+        let total_rows = fav_n + rest_items.len();
         let win = virtual_list.window(self.scroll_offset.y, bounds.height, total_rows);
 
         let list = self.main_list(
             &virtual_list,
             win,
             &fav_rows,
-            &rest_rows,
+            &rest_items,
             sep_block_height,
             has_any_favorites,
         );
@@ -422,13 +595,38 @@ impl TickersTable {
         .into()
     }
 
+    /// بروزرسانی دوره‌ای آمار، یک صرافی در هر تیک تا درخواست‌ها همزمان به محدودکننده‌ها نرسند
     pub fn subscription(&self) -> Subscription<Message> {
-        iced::time::every(std::time::Duration::from_secs(if self.is_shown {
-            ACTIVE_UPDATE_INTERVAL
+        let exchanges = self.ready_exchanges().len();
+        if exchanges == 0 {
+            return Subscription::none();
+        }
+
+        let interval = if self.is_shown {
+            Duration::from_secs(self.stats_refresh_secs)
         } else {
             INACTIVE_UPDATE_INTERVAL
-        }))
-        .map(|_| Message::FetchForTickerStats(None))
+        };
+
+        iced::time::every(staggered_period(interval, exchanges))
+            .map(|_| Message::RefreshNextExchange)
+    }
+
+    /// صرافی‌هایی که اطلاعات نمادهایشان دریافت شده، به ترتیب ثابت
+    fn ready_exchanges(&self) -> Vec<Exchange> {
+        Exchange::ALL
+            .into_iter()
+            .filter(|exchange| matches!(self.load_states.get(exchange), Some(LoadState::Ready)))
+            .collect()
+    }
+
+    fn fetch_stats(&mut self, exchange: Exchange) -> Task<Message> {
+        self.stats_in_flight.insert(exchange);
+
+        Task::perform(fetch_ticker_prices(exchange), move |result| match result {
+            Ok(ticker_rows) => Message::UpdateTickerStats(exchange, ticker_rows),
+            Err(err) => Message::TickerStatsFailed(exchange, err.to_string()),
+        })
     }
 
     fn sort_ticker_rows(&mut self) {
@@ -484,6 +682,61 @@ impl TickersTable {
         self.sort_ticker_rows();
     }
 
+    /// Recomputes every row's display strings, dropping the price change highlights
+    fn refresh_display_cache(&mut self) {
+        self.display_cache.clear();
+        for idx in 0..self.ticker_rows.len() {
+            self.ticker_rows[idx].previous_stats = None;
+
+            let row = &self.ticker_rows[idx];
+            let decimals = self.price_decimals(&row.ticker);
+            self.display_cache.insert(
+                row.ticker,
+                compute_display_data(&row.ticker, &row.stats, None, decimals),
+            );
+        }
+    }
+
+    /// Fixed price decimals from the ticker's tick size, when enabled
+    fn price_decimals(&self, ticker: &Ticker) -> Option<usize> {
+        if !self.fixed_decimals {
+            return None;
+        }
+
+        self.tickers_info
+            .get(ticker)
+            .copied()
+            .flatten()
+            .map(|info| count_decimals(f32::from(info.min_ticksize)))
+    }
+
+    /// Sizes each column's fraction part after the widest one among the given rows,
+    /// so the integer parts can be right-aligned on a shared decimal point
+    fn column_layouts<'a>(
+        &self,
+        rows: impl Iterator<Item = &'a TickerRowData>,
+        text_size: f32,
+    ) -> Vec<ColumnLayout> {
+        let mut fraction_chars = vec![0; self.columns.len()];
+
+        for display_data in rows.filter_map(|row| self.display_cache.get(&row.ticker)) {
+            for (chars, cfg) in fraction_chars.iter_mut().zip(&self.columns) {
+                let (_, fraction) = split_decimal(display_data.column_value(cfg.column));
+                *chars = (*chars).max(fraction.chars().count());
+            }
+        }
+
+        self.columns
+            .iter()
+            .zip(fraction_chars)
+            .map(|(cfg, chars)| ColumnLayout {
+                column: cfg.column,
+                width: cfg.width,
+                fraction_width: (chars as f32 * text_size * GLYPH_WIDTH_RATIO).min(cfg.width / 2.0),
+            })
+            .collect()
+    }
+
     fn rebuild_index(&mut self) {
         self.row_index.clear();
         for (i, row) in self.ticker_rows.iter().enumerate() {
@@ -510,6 +763,7 @@ impl TickersTable {
         ticker: &'a Ticker,
         display_data: &'a TickerDisplayData,
         is_fav: bool,
+        columns: &[ColumnLayout],
     ) -> Element<'a, Message> {
         if let Some(selected_ticker) = &self.expand_ticker_card {
             let selected_exchange = selected_ticker.exchange;
@@ -518,10 +772,10 @@ impl TickersTable {
                     .style(style::ticker_card)
                     .into()
             } else {
-                ticker_card(ticker, display_data)
+                ticker_card(ticker, display_data, self.compact, columns)
             }
         } else {
-            ticker_card(ticker, display_data)
+            ticker_card(ticker, display_data, self.compact, columns)
         }
     }
 
@@ -541,19 +795,21 @@ impl TickersTable {
     ) -> Element<'a, Message> {
         let selected = self.selected_exchanges.contains(&exch_inc);
 
-        let content = if selected {
-            row![
-                icon_text(style::exchange_icon(logo_exchange), 12).align_x(Alignment::Center),
-                text(label),
-                space::horizontal(),
-                container(icon_text(Icon::Checkmark, 12)),
-            ]
-        } else {
-            row![
-                icon_text(style::exchange_icon(logo_exchange), 12).align_x(Alignment::Center),
-                text(label)
-            ]
-        };
+        let mut content = row![
+            icon_text(style::exchange_icon(logo_exchange), 12).align_x(Alignment::Center),
+            text(label),
+            space::horizontal(),
+        ];
+
+        match self.venue_load_state(exch_inc) {
+            LoadState::Loading => content = content.push(text("Loading...").size(11)),
+            LoadState::Failed(_) => content = content.push(text("Failed").size(11)),
+            LoadState::Ready => {}
+        }
+
+        if selected {
+            content = content.push(container(icon_text(Icon::Checkmark, 12)));
+        }
 
         let btn = button(content.spacing(4).width(Length::Fill))
             .style(move |theme, status| style::button::modifier(theme, status, selected))
@@ -566,6 +822,26 @@ impl TickersTable {
             .into()
     }
 
+    /// وضعیت بارگذاری یک صرافی با در نظر گرفتن همه بازارهای آن
+    fn venue_load_state(&self, venue: ExchangeInclusive) -> &LoadState {
+        let mut states = self
+            .load_states
+            .iter()
+            .filter(|(exchange, _)| ExchangeInclusive::of(**exchange) == venue)
+            .map(|(_, state)| state);
+
+        if let Some(loading) = states
+            .clone()
+            .find(|state| matches!(state, LoadState::Loading))
+        {
+            return loading;
+        }
+
+        states
+            .find(|state| matches!(state, LoadState::Failed(_)))
+            .unwrap_or(&LoadState::Ready)
+    }
+
     fn update_ticker_info(
         &mut self,
         _exchange: Exchange,
@@ -577,20 +853,22 @@ impl TickersTable {
     }
 
     fn update_ticker_rows(&mut self, exchange: Exchange, stats: HashMap<Ticker, TickerStats>) {
-        let iter = stats
-            .into_iter()
-            .filter(|(t, _)| self.tickers_info.contains_key(t));
+        let iter = stats.into_iter().filter(|(t, _)| {
+            self.tickers_info.contains_key(t) && SymbolFilter::is_allowed(&self.symbol_filters, t)
+        });
 
         for (ticker, new_stats) in iter {
+            let decimals = self.price_decimals(&ticker);
+
             if let Some(&idx) = self.row_index.get(&ticker) {
                 let row = &mut self.ticker_rows[idx];
-                let previous_price = Some(row.stats.mark_price);
+                let previous_price = Some(row.stats.last_price);
                 row.previous_stats = Some(row.stats);
                 row.stats = new_stats;
 
                 self.display_cache.insert(
                     ticker,
-                    compute_display_data(&ticker, &row.stats, previous_price),
+                    compute_display_data(&ticker, &row.stats, previous_price, decimals),
                 );
             } else {
                 let new_row = TickerRowData {
@@ -606,7 +884,7 @@ impl TickersTable {
 
                 self.display_cache.insert(
                     ticker,
-                    compute_display_data(&ticker, &self.ticker_rows[idx].stats, None),
+                    compute_display_data(&ticker, &self.ticker_rows[idx].stats, None, decimals),
                 );
             }
         }
@@ -656,6 +934,17 @@ impl TickersTable {
     }
 
     fn top_bar_row(&self) -> Element<'_, Message> {
+        let loading = self
+            .load_states
+            .values()
+            .filter(|state| matches!(state, LoadState::Loading))
+            .count();
+
+        let load_status = (loading > 0).then(|| {
+            let done = self.load_states.len() - loading;
+            text(format!("{done}/{}", self.load_states.len())).size(11)
+        });
+
         row![
             text_input("Search for a ticker...", &self.search_query)
                 .style(|theme, status| style::validated_text_input(theme, status, true))
@@ -663,6 +952,7 @@ impl TickersTable {
                 .id("full_ticker_search_box")
                 .align_x(Horizontal::Left)
                 .padding(6),
+            load_status,
             button(
                 icon_text(Icon::Sort, 14)
                     .align_x(Horizontal::Center)
@@ -732,6 +1022,34 @@ impl TickersTable {
             col.spacing(4)
         };
 
+        let refresh_interval = {
+            let secs = self.stats_refresh_secs;
+            let (min_secs, max_secs) = (
+                *Settings::REFRESH_SECS_RANGE.start(),
+                *Settings::REFRESH_SECS_RANGE.end(),
+            );
+
+            let decrease_btn = button(text("-")).on_press_maybe(
+                (secs > min_secs).then(|| Message::SetRefreshInterval(secs - REFRESH_SECS_STEP)),
+            );
+            let increase_btn = button(text("+")).on_press_maybe(
+                (secs < max_secs).then(|| Message::SetRefreshInterval(secs + REFRESH_SECS_STEP)),
+            );
+
+            row![
+                text("Refresh every"),
+                space::horizontal(),
+                decrease_btn,
+                text(format!("{secs}s")),
+                increase_btn,
+            ]
+            .align_y(Alignment::Center)
+            .spacing(8)
+        };
+
+        let quote_settings = self.quote_settings();
+        let columns_settings = self.columns_settings();
+
         let total = rest_n + fav_n;
 
         column![
@@ -757,9 +1075,14 @@ impl TickersTable {
                 inverse_markets_btn.width(Length::Fill),
             ]
             .spacing(4),
+            quote_settings,
             rule::horizontal(1.0).style(style::split_ruler),
             exchange_filters,
             rule::horizontal(1.0).style(style::split_ruler),
+            refresh_interval,
+            rule::horizontal(1.0).style(style::split_ruler),
+            columns_settings,
+            rule::horizontal(1.0).style(style::split_ruler),
             text(if total == 0 {
                 "No tickers match filters".to_string()
             } else {
@@ -783,6 +1106,98 @@ impl TickersTable {
         .into()
     }
 
+    /// Quote picker listing the quotes of the tickers in the selected exchanges and markets,
+    /// most common first
+    fn quote_settings(&self) -> Element<'_, Message> {
+        let mut counts: Vec<(&'static str, usize)> = vec![];
+
+        for row in &self.ticker_rows {
+            if !self.selected_markets.contains(&row.ticker.market_type())
+                || !self
+                    .selected_exchanges
+                    .contains(&ExchangeInclusive::of(row.exchange))
+            {
+                continue;
+            }
+            let Some(quote) = row.ticker.quote_asset() else {
+                continue;
+            };
+            match counts.iter_mut().find(|(q, _)| *q == quote) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((quote, 1)),
+            }
+        }
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+        let mut options = vec![QuoteFilter::All];
+        options.extend(
+            counts
+                .into_iter()
+                .map(|(quote, _)| QuoteFilter::Only(quote.to_string())),
+        );
+        // keep a persisted quote selectable even before its exchange has loaded
+        if !options.contains(&self.quote_filter) {
+            options.push(self.quote_filter.clone());
+        }
+
+        row![
+            pick_list(
+                options,
+                Some(self.quote_filter.clone()),
+                Message::SetQuoteFilter
+            )
+            .width(Length::Fill),
+            checkbox(self.group_by_quote)
+                .label("Group by quote")
+                .on_toggle(Message::ToggleGroupByQuote),
+        ]
+        .align_y(Alignment::Center)
+        .spacing(8)
+        .into()
+    }
+
+    fn columns_settings(&self) -> Element<'_, Message> {
+        let last = self.columns.len().saturating_sub(1);
+        let mut col = column![text("Columns").size(11)].spacing(4);
+
+        for (idx, cfg) in self.columns.iter().enumerate() {
+            let column = cfg.column;
+
+            let move_btn = |icon: Icon, towards_start: bool, enabled: bool| {
+                button(icon_text(icon, 10).align_x(Horizontal::Center))
+                    .width(20)
+                    .on_press_maybe(enabled.then_some(Message::MoveColumn(column, towards_start)))
+                    .style(move |theme, status| style::button::transparent(theme, status, false))
+            };
+
+            col = col.push(
+                row![
+                    text(column.to_string()).width(Length::Fixed(52.0)),
+                    move_btn(Icon::SortAsc, true, idx > 0),
+                    move_btn(Icon::SortDesc, false, idx < last),
+                    slider(ColumnCfg::WIDTH_RANGE, cfg.width, move |width| {
+                        Message::ResizeColumn(column, width)
+                    })
+                    .step(4.0),
+                    text(format!("{:.0}", cfg.width))
+                        .size(11)
+                        .width(Length::Fixed(24.0)),
+                ]
+                .align_y(Alignment::Center)
+                .spacing(4),
+            );
+        }
+
+        col.push(tooltip(
+            checkbox(self.fixed_decimals)
+                .label("Fixed price decimals")
+                .on_toggle(Message::ToggleFixedDecimals),
+            Some("Show prices with the decimals of each ticker's tick size"),
+            TooltipPosition::Top,
+        ))
+        .into()
+    }
+
     fn fav_separator_block(
         &self,
         fav_n: usize,
@@ -821,7 +1236,7 @@ impl TickersTable {
         vcfg: &VirtualListConfig,
         win: VirtualWindow,
         fav_rows: &[&'a TickerRowData],
-        rest_rows: &[&'a TickerRowData],
+        rest_items: &[ListItem<'a>],
         sep_block_height: f32,
         has_any_favorites: bool,
     ) -> Element<'a, Message> {
@@ -834,6 +1249,24 @@ impl TickersTable {
             .width(Length::Shrink)
             .height(Length::Fixed(win.bottom_space));
 
+        let visible_rows =
+            (win.first..win.last).filter_map(|idx| match vcfg.virtual_to_item(idx) {
+                VirtualItemIndex::Gap => None,
+                VirtualItemIndex::Row(data_idx) if data_idx < fav_n => Some(fav_rows[data_idx]),
+                VirtualItemIndex::Row(data_idx) => match rest_items.get(data_idx - fav_n) {
+                    Some(ListItem::Ticker(row)) => Some(*row),
+                    _ => None,
+                },
+            });
+        let columns = self.column_layouts(
+            visible_rows,
+            if self.compact {
+                COMPACT_TEXT_SIZE
+            } else {
+                CARD_TEXT_SIZE
+            },
+        );
+
         let mut cards = column![top_space].spacing(4);
 
         for idx in win.first..win.last {
@@ -849,7 +1282,13 @@ impl TickersTable {
                     let row_ref = if data_idx < fav_n {
                         fav_rows[data_idx]
                     } else {
-                        rest_rows[data_idx - fav_n]
+                        match rest_items[data_idx - fav_n] {
+                            ListItem::Ticker(row) => row,
+                            ListItem::QuoteHeader(quote, count) => {
+                                cards = cards.push(self.quote_header(quote, count));
+                                continue;
+                            }
+                        }
                     };
                     if let Some(display_data) = self.display_cache.get(&row_ref.ticker) {
                         cards = cards.push(self.ticker_card_container(
@@ -857,6 +1296,7 @@ impl TickersTable {
                             &row_ref.ticker,
                             display_data,
                             row_ref.is_favorited,
+                            &columns,
                         ));
                     }
                 }
@@ -867,6 +1307,31 @@ impl TickersTable {
         cards.into()
     }
 
+    fn quote_header<'a>(&self, quote: Option<&str>, count: usize) -> Element<'a, Message> {
+        let height = if self.compact {
+            COMPACT_CARD_HEIGHT - 4.0
+        } else {
+            TICKER_CARD_HEIGHT - 8.0
+        };
+
+        container(
+            column![
+                row![
+                    text(quote.unwrap_or("Other").to_string()).size(12),
+                    space::horizontal(),
+                    text(count.to_string()).size(11),
+                ]
+                .align_y(Alignment::Center),
+                rule::horizontal(1.0).style(style::split_ruler),
+            ]
+            .spacing(2),
+        )
+        .height(Length::Fixed(height))
+        .padding(padding::left(4).right(4))
+        .align_y(Alignment::End)
+        .into()
+    }
+
     fn compact_top_bar<'a, M, FSearch>(
         &'a self,
         search_query: &'a str,
@@ -1038,6 +1503,7 @@ impl TickersTable {
             self.selected_exchanges
                 .contains(&ExchangeInclusive::of(row.exchange))
         };
+        let matches_quote = |row: &TickerRowData| self.quote_filter.matches(&row.ticker);
 
         let fav_rows = if self.show_favorites {
             self.ticker_rows
@@ -1047,6 +1513,7 @@ impl TickersTable {
                         && !excluded.is_some_and(|ex| ex.contains(&row.ticker))
                         && matches_market(row)
                         && matches_exchange(row)
+                        && matches_quote(row)
                         && matches_search(row)
                 })
                 .collect()
@@ -1062,6 +1529,7 @@ impl TickersTable {
                     && !excluded.is_some_and(|ex| ex.contains(&row.ticker))
                     && matches_market(row)
                     && matches_exchange(row)
+                    && matches_quote(row)
                     && matches_search(row)
             })
             .collect();
@@ -1082,53 +1550,37 @@ impl TickersTable {
     }
 }
 
-fn ticker_card<'a>(ticker: &Ticker, display_data: &'a TickerDisplayData) -> Element<'a, Message> {
+fn ticker_card<'a>(
+    ticker: &Ticker,
+    display_data: &'a TickerDisplayData,
+    compact: bool,
+    columns: &[ColumnLayout],
+) -> Element<'a, Message> {
+    if compact {
+        return compact_ticker_card(ticker, display_data, columns);
+    }
+
     let color_column = container(column![])
         .height(Length::Fill)
         .width(Length::Fixed(2.0))
         .style(move |theme| style::ticker_card_bar(theme, display_data.card_color_alpha));
 
-    let price_display = if display_data.price_changed_part.is_empty() {
-        row![text(&display_data.price_unchanged_part)]
-    } else {
-        row![
-            text(&display_data.price_unchanged_part),
-            text(&display_data.price_changed_part).style(move |theme: &Theme| {
-                let palette = theme.extended_palette();
-                iced::widget::text::Style {
-                    color: Some(match display_data.price_change_direction {
-                        PriceChangeDirection::Increased => palette.success.base.color,
-                        PriceChangeDirection::Decreased => palette.danger.base.color,
-                        PriceChangeDirection::Unchanged => palette.background.base.text,
-                    }),
-                }
-            })
-        ]
-    };
-
     let icon = icon_text(style::exchange_icon(ticker.exchange), 12);
-    let display_ticker = short_card_label(ticker, display_data);
+
+    let mut values = row![].spacing(COLUMN_SPACING);
+    for layout in columns {
+        values = values.push(column_cell(display_data, *layout, CARD_TEXT_SIZE));
+    }
 
     container(
         button(
             row![
                 color_column,
                 column![
-                    row![
-                        row![icon, text(display_ticker),]
-                            .spacing(2)
-                            .align_y(alignment::Vertical::Center),
-                        Space::new().width(Length::Fill).height(Length::Shrink),
-                        text(&display_data.daily_change_pct),
-                    ]
-                    .spacing(4)
-                    .align_y(alignment::Vertical::Center),
-                    row![
-                        price_display,
-                        Space::new().width(Length::Fill).height(Length::Shrink),
-                        text(&display_data.volume_display),
-                    ]
-                    .spacing(4),
+                    row![icon, card_label(ticker, display_data, CARD_TEXT_SIZE)]
+                        .spacing(2)
+                        .align_y(alignment::Vertical::Center),
+                    values,
                 ]
                 .padding(padding::left(8).right(8).bottom(4).top(4))
                 .spacing(4),
@@ -1142,6 +1594,121 @@ fn ticker_card<'a>(ticker: &Ticker, display_data: &'a TickerDisplayData) -> Elem
     .into()
 }
 
+fn compact_ticker_card<'a>(
+    ticker: &Ticker,
+    display_data: &'a TickerDisplayData,
+    columns: &[ColumnLayout],
+) -> Element<'a, Message> {
+    let color_column = container(column![])
+        .height(Length::Fill)
+        .width(Length::Fixed(2.0))
+        .style(move |theme| style::ticker_card_bar(theme, display_data.card_color_alpha));
+
+    let icon = icon_text(style::exchange_icon(ticker.exchange), 11);
+
+    // single line rows only have room for the leading column
+    let value = columns
+        .first()
+        .map(|layout| column_cell(display_data, *layout, COMPACT_TEXT_SIZE));
+
+    container(
+        button(
+            row![
+                color_column,
+                row![
+                    icon,
+                    card_label(ticker, display_data, COMPACT_TEXT_SIZE),
+                    Space::new().width(Length::Fill).height(Length::Shrink),
+                    value,
+                ]
+                .spacing(4)
+                .padding(padding::left(8).right(8))
+                .align_y(alignment::Vertical::Center),
+            ]
+            .align_y(Alignment::Center),
+        )
+        .style(style::button::ticker_card)
+        .on_press(Message::ExpandTickerCard(Some(*ticker))),
+    )
+    .height(Length::Fixed(COMPACT_CARD_HEIGHT - 4.0))
+    .into()
+}
+
+/// A numeric cell, right-aligning the integer part against the column's fraction area
+/// so the values of a column line up on the decimal point
+fn column_cell<'a>(
+    display_data: &'a TickerDisplayData,
+    layout: ColumnLayout,
+    size: f32,
+) -> Element<'a, Message> {
+    let value = display_data.column_value(layout.column);
+    let (integer, fraction) = split_decimal(value);
+
+    // the live price highlights the digits that changed since the last refresh
+    let changed = (layout.column == TableColumn::Price
+        && !display_data.price_changed_part.is_empty())
+    .then(|| {
+        let direction = display_data.price_change_direction.clone();
+        (display_data.price_unchanged_part.len(), direction)
+    });
+
+    let segment = |part: &'a str, offset: usize| {
+        let Some((at, direction)) = changed.clone() else {
+            return row![text(part).size(size)];
+        };
+        let split = at.saturating_sub(offset).min(part.len());
+
+        row![
+            text(&part[..split]).size(size),
+            text(&part[split..]).size(size).style(move |theme: &Theme| {
+                iced::widget::text::Style {
+                    color: Some(change_color(theme, &direction)),
+                }
+            }),
+        ]
+    };
+
+    row![
+        container(segment(integer, 0))
+            .width(Length::Fill)
+            .align_x(Horizontal::Right),
+        container(segment(fraction, integer.len()))
+            .width(Length::Fixed(layout.fraction_width))
+            .align_x(Horizontal::Left),
+    ]
+    .width(Length::Fixed(layout.width))
+    .into()
+}
+
+fn change_color(theme: &Theme, direction: &PriceChangeDirection) -> Color {
+    let palette = theme.extended_palette();
+
+    match direction {
+        PriceChangeDirection::Increased => palette.success.base.color,
+        PriceChangeDirection::Decreased => palette.danger.base.color,
+        PriceChangeDirection::Unchanged => palette.background.base.text,
+    }
+}
+
+/// Card symbol, truncated past `MAX_LABEL_CHARS` with the full name in a tooltip
+fn card_label<'a>(
+    ticker: &Ticker,
+    display_data: &'a TickerDisplayData,
+    size: f32,
+) -> Element<'a, Message> {
+    let label = short_card_label(ticker, display_data);
+
+    if display_data.display_ticker.chars().count() > MAX_LABEL_CHARS {
+        tooltip(
+            text(label).size(size),
+            Some(&display_data.display_ticker),
+            TooltipPosition::Top,
+        )
+    } else {
+        text(label).size(size).into()
+    }
+}
+
 fn expanded_ticker_card<'a>(
     ticker: &Ticker,
     display_data: &'a TickerDisplayData,
@@ -1150,6 +1717,24 @@ fn expanded_ticker_card<'a>(
     let (ticker_str, market) = ticker.display_symbol_and_type();
     let exchange_icon = style::exchange_icon(ticker.exchange);
 
+    let stat_row = |label: &'a str, value: &'a str| {
+        row![
+            text(label).size(11),
+            Space::new().width(Length::Fill).height(Length::Shrink),
+            text(value),
+        ]
+    };
+
+    let mut stats_column = column![stat_row("Last Price: ", &display_data.last_price_display)];
+    if market != MarketKind::Spot {
+        stats_column = stats_column
+            .push(stat_row("Mark Price: ", &display_data.mark_price_display))
+            .push(stat_row("Index Price: ", &display_data.index_price_display));
+    }
+    stats_column = stats_column
+        .push(stat_row("Daily Change: ", &display_data.daily_change_pct))
+        .push(stat_row("Daily Volume: ", &display_data.volume_display));
+
     column![
         row![
             button(icon_text(Icon::Return, 11))
@@ -1177,27 +1762,7 @@ fn expanded_ticker_card<'a>(
             ),
         ]
         .spacing(2),
-        container(
-            column![
-                row![
-                    text("Last Updated Price: ").size(11),
-                    Space::new().width(Length::Fill).height(Length::Shrink),
-                    text(&display_data.mark_price_display)
-                ],
-                row![
-                    text("Daily Change: ").size(11),
-                    Space::new().width(Length::Fill).height(Length::Shrink),
-                    text(&display_data.daily_change_pct),
-                ],
-                row![
-                    text("Daily Volume: ").size(11),
-                    Space::new().width(Length::Fill).height(Length::Shrink),
-                    text(&display_data.volume_display),
-                ],
-            ]
-            .spacing(2)
-        )
-        .style(|theme: &Theme| {
+        container(stats_column.spacing(2)).style(|theme: &Theme| {
             let palette = theme.extended_palette();
             iced::widget::container::Style {
                 text_color: Some(palette.background.base.text.scale_alpha(0.9)),
@@ -1211,6 +1776,9 @@ fn expanded_ticker_card<'a>(
             init_content_button(ContentKind::ComparisonChart, *ticker, 180.0),
             init_content_button(ContentKind::TimeAndSales, *ticker, 160.0),
             init_content_button(ContentKind::Ladder, *ticker, 160.0),
+            init_content_button(ContentKind::DepthCurve, *ticker, 160.0),
+            init_content_button(ContentKind::FundingChart, *ticker, 160.0),
+            init_content_button(ContentKind::SpreadChart, *ticker, 160.0),
         ]
         .width(Length::Fill)
         .spacing(2)
@@ -1334,8 +1902,13 @@ fn init_content_button<'a>(
 }
 
 fn short_card_label(ticker: &Ticker, display_data: &TickerDisplayData) -> String {
-    if display_data.display_ticker.len() >= 11 {
-        format!("{}...", &display_data.display_ticker[..9])
+    if display_data.display_ticker.chars().count() > MAX_LABEL_CHARS {
+        let truncated: String = display_data
+            .display_ticker
+            .chars()
+            .take(MAX_LABEL_CHARS - 1)
+            .collect();
+        format!("{truncated}...")
     } else {
         format!(
             "{}{}",
@@ -0,0 +1,497 @@
+use super::dashboard::tickers_table::TickersTable;
+use crate::style::{self, Icon, icon_text};
+use data::scanner::{Comparison, HighlightRule, LOOKBACK_BARS, Metric, ScanMetrics};
+use exchange::adapter::KlinePriceSource;
+use exchange::{Kline, Ticker, TickerInfo, Timeframe, adapter};
+
+use iced::futures::{FutureExt, StreamExt, TryFutureExt};
+use iced::widget::{
+    button, checkbox, column, container, pick_list, row, scrollable, space, text, text_input,
+};
+use iced::{Alignment, Element, Length, Subscription, Task, Theme};
+use rustc_hash::FxHashMap;
+use std::cmp::Ordering;
+use std::time::{Duration, Instant};
+
+/// How often a full pass over the candidate tickers is started
+const SCAN_INTERVAL: Duration = Duration::from_secs(60);
+
+const TOP_N_OPTIONS: [usize; 4] = [20, 40, 80, 120];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortColumn {
+    Symbol,
+    Metric(Metric),
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Tick,
+    Refresh,
+    KlinesFetched(TickerInfo, f32, Result<Vec<Kline>, String>),
+    SortBy(SortColumn),
+    SearchChanged(String),
+    ToggleHighlightedOnly(bool),
+    TopNChanged(usize),
+    RowClicked(TickerInfo),
+    RuleMetricChanged(Metric),
+    RuleComparisonChanged(Comparison),
+    RuleThresholdChanged(String),
+    AddRule,
+    RemoveRule(usize),
+}
+
+pub enum Action {
+    TickerSelected(TickerInfo),
+}
+
+struct ScanRow {
+    info: TickerInfo,
+    metrics: ScanMetrics,
+}
+
+struct RuleDraft {
+    metric: Metric,
+    comparison: Comparison,
+    threshold: String,
+}
+
+/// Ranks the most traded tickers by short-term change, volume spikes and volatility
+pub struct Scanner {
+    settings: data::Scanner,
+    rows: FxHashMap<Ticker, ScanRow>,
+    pending: usize,
+    failed: usize,
+    last_scan: Option<Instant>,
+    sort: (SortColumn, bool),
+    search: String,
+    highlighted_only: bool,
+    draft: RuleDraft,
+    changed: bool,
+}
+
+impl Scanner {
+    pub fn new(settings: data::Scanner) -> Self {
+        Self {
+            settings,
+            rows: FxHashMap::default(),
+            pending: 0,
+            failed: 0,
+            last_scan: None,
+            sort: (SortColumn::Metric(Metric::VolumeSpike), true),
+            search: String::new(),
+            highlighted_only: false,
+            draft: RuleDraft {
+                metric: Metric::VolumeSpike,
+                comparison: Comparison::Above,
+                threshold: String::new(),
+            },
+            changed: false,
+        }
+    }
+
+    pub fn settings(&self) -> data::Scanner {
+        self.settings.clone()
+    }
+
+    /// Whether the saved scanner settings changed since the last call
+    pub fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+
+    pub fn update(
+        &mut self,
+        message: Message,
+        tickers: &TickersTable,
+    ) -> (Task<Message>, Option<Action>) {
+        match message {
+            Message::Tick => {
+                let is_due = self
+                    .last_scan
+                    .is_none_or(|last| last.elapsed() >= SCAN_INTERVAL);
+
+                if is_due && self.pending == 0 {
+                    return (self.start_scan(tickers), None);
+                }
+            }
+            Message::Refresh => {
+                if self.pending == 0 {
+                    return (self.start_scan(tickers), None);
+                }
+            }
+            Message::KlinesFetched(info, daily_chg, result) => {
+                self.pending = self.pending.saturating_sub(1);
+
+                match result {
+                    Ok(klines) => {
+                        let metrics = ScanMetrics::from_klines(&klines, daily_chg);
+                        self.rows.insert(info.ticker, ScanRow { info, metrics });
+                    }
+                    Err(err) => {
+                        self.failed += 1;
+                        log::warn!("Scanner failed to fetch klines for {}: {err}", info.ticker);
+                    }
+                }
+            }
+            Message::SortBy(column) => {
+                self.sort = if self.sort.0 == column {
+                    (column, !self.sort.1)
+                } else {
+                    (column, column != SortColumn::Symbol)
+                };
+            }
+            Message::SearchChanged(query) => {
+                self.search = query.to_uppercase();
+            }
+            Message::ToggleHighlightedOnly(enabled) => {
+                self.highlighted_only = enabled;
+            }
+            Message::TopNChanged(top_n) => {
+                self.settings.top_n = top_n;
+                self.changed = true;
+                if self.pending == 0 {
+                    return (self.start_scan(tickers), None);
+                }
+            }
+            Message::RowClicked(info) => {
+                return (Task::none(), Some(Action::TickerSelected(info)));
+            }
+            Message::RuleMetricChanged(metric) => {
+                self.draft.metric = metric;
+            }
+            Message::RuleComparisonChanged(comparison) => {
+                self.draft.comparison = comparison;
+            }
+            Message::RuleThresholdChanged(threshold) => {
+                self.draft.threshold = threshold;
+            }
+            Message::AddRule => {
+                if let Ok(threshold) = self.draft.threshold.trim().parse::<f32>() {
+                    self.settings.rules.push(HighlightRule {
+                        metric: self.draft.metric,
+                        comparison: self.draft.comparison,
+                        threshold,
+                    });
+                    self.draft.threshold.clear();
+                    self.changed = true;
+                }
+            }
+            Message::RemoveRule(idx) => {
+                if idx < self.settings.rules.len() {
+                    self.settings.rules.remove(idx);
+                    self.changed = true;
+                }
+            }
+        }
+
+        (Task::none(), None)
+    }
+
+    /// Fetches hourly klines for the top tickers by daily volume, one request at a time
+    /// so the adapters' rate limiters are never flooded by a single pass
+    fn start_scan(&mut self, tickers: &TickersTable) -> Task<Message> {
+        let tickers_info = &tickers.tickers_info;
+
+        let mut candidates = tickers
+            .ticker_rows()
+            .iter()
+            .filter_map(|row| {
+                tickers_info
+                    .get(&row.ticker)
+                    .copied()
+                    .flatten()
+                    .map(|info| (info, row.stats))
+            })
+            .collect::<Vec<_>>();
+
+        candidates.sort_unstable_by(|a, b| {
+            b.1.daily_volume
+                .partial_cmp(&a.1.daily_volume)
+                .unwrap_or(Ordering::Equal)
+        });
+        candidates.truncate(self.settings.top_n);
+
+        self.rows
+            .retain(|ticker, _| candidates.iter().any(|(info, _)| info.ticker == *ticker));
+        self.last_scan = Some(Instant::now());
+        self.pending = candidates.len();
+        self.failed = 0;
+
+        if candidates.is_empty() {
+            return Task::none();
+        }
+
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+        let range = (
+            now - Timeframe::H1.to_milliseconds() * LOOKBACK_BARS as u64,
+            now,
+        );
+
+        let fetches = iced::futures::stream::iter(candidates).then(move |(info, stats)| {
+            adapter::fetch_klines(info, Timeframe::H1, KlinePriceSource::Trade, Some(range))
+                .map_err(|err| err.to_user_message().to_string())
+                .map(move |result| (info, stats.daily_price_chg, result))
+        });
+
+        Task::run(fetches, |(info, daily_chg, result)| {
+            Message::KlinesFetched(info, daily_chg, result)
+        })
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        iced::time::every(Duration::from_secs(5)).map(|_| Message::Tick)
+    }
+
+    fn is_highlighted(&self, metrics: &ScanMetrics) -> bool {
+        self.settings.rules.iter().any(|rule| rule.matches(metrics))
+    }
+
+    fn sorted_rows(&self) -> Vec<&ScanRow> {
+        let mut rows = self
+            .rows
+            .values()
+            .filter(|row| {
+                self.search.is_empty() || row.info.ticker.to_string().contains(&self.search)
+            })
+            .filter(|row| !self.highlighted_only || self.is_highlighted(&row.metrics))
+            .collect::<Vec<_>>();
+
+        let (column, descending) = self.sort;
+
+        rows.sort_by(|a, b| {
+            let ordering = match column {
+                SortColumn::Symbol => a.info.ticker.to_string().cmp(&b.info.ticker.to_string()),
+                SortColumn::Metric(metric) => {
+                    match (a.metrics.get(metric), b.metrics.get(metric)) {
+                        (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+                        // rows without enough history always sink to the bottom
+                        (Some(_), None) => return Ordering::Less,
+                        (None, Some(_)) => return Ordering::Greater,
+                        (None, None) => Ordering::Equal,
+                    }
+                }
+            };
+
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+
+        rows
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let status = if self.pending > 0 {
+            format!("Scanning, {} left", self.pending)
+        } else if self.failed > 0 {
+            format!("{} tickers, {} failed", self.rows.len(), self.failed)
+        } else {
+            format!("{} tickers", self.rows.len())
+        };
+
+        let header = row![
+            text("Market scanner").size(14),
+            space::horizontal(),
+            text(status).size(11),
+            button(text("Refresh").size(12))
+                .on_press_maybe((self.pending == 0).then_some(Message::Refresh)),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center);
+
+        let filters = row![
+            text_input("Search symbol", &self.search)
+                .on_input(Message::SearchChanged)
+                .size(12)
+                .width(Length::Fill),
+            checkbox(self.highlighted_only)
+                .label("Highlighted only")
+                .text_size(12)
+                .on_toggle(Message::ToggleHighlightedOnly),
+            text("Top").size(12),
+            pick_list(
+                TOP_N_OPTIONS,
+                Some(self.settings.top_n),
+                Message::TopNChanged
+            )
+            .text_size(12),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center);
+
+        let column_header = |label: String, column: SortColumn, width: Length| {
+            let mut content = row![text(label).size(11)]
+                .spacing(2)
+                .align_y(Alignment::Center);
+            if self.sort.0 == column {
+                let icon = if self.sort.1 {
+                    Icon::SortDesc
+                } else {
+                    Icon::SortAsc
+                };
+                content = content.push(icon_text(icon, 10));
+            }
+
+            button(content)
+                .width(width)
+                .padding([2, 4])
+                .style(move |theme, status| {
+                    style::button::transparent(theme, status, self.sort.0 == column)
+                })
+                .on_press(Message::SortBy(column))
+        };
+
+        let mut table_header = row![column_header(
+            "Symbol".to_string(),
+            SortColumn::Symbol,
+            Length::FillPortion(2)
+        )]
+        .spacing(2);
+        for metric in Metric::ALL {
+            table_header = table_header.push(column_header(
+                metric.to_string(),
+                SortColumn::Metric(metric),
+                Length::FillPortion(1),
+            ));
+        }
+
+        let mut table = column![].spacing(2);
+        let rows = self.sorted_rows();
+
+        if rows.is_empty() {
+            table = table.push(
+                text(if self.pending > 0 {
+                    "Loading..."
+                } else {
+                    "No results"
+                })
+                .size(12),
+            );
+        }
+
+        for scan_row in rows {
+            let is_highlighted = self.is_highlighted(&scan_row.metrics);
+
+            let mut cells = row![
+                text(scan_row.info.ticker.to_string())
+                    .size(12)
+                    .width(Length::FillPortion(2))
+            ]
+            .spacing(2);
+            for metric in Metric::ALL {
+                cells = cells.push(metric_cell(metric, scan_row.metrics.get(metric)));
+            }
+
+            table = table.push(
+                button(cells)
+                    .width(Length::Fill)
+                    .padding([2, 4])
+                    .style(move |theme, status| {
+                        style::button::transparent(theme, status, is_highlighted)
+                    })
+                    .on_press(Message::RowClicked(scan_row.info)),
+            );
+        }
+
+        container(
+            column![
+                header,
+                filters,
+                table_header,
+                scrollable(table).height(Length::Fill),
+                self.rules_view(),
+            ]
+            .spacing(8),
+        )
+        .width(640)
+        .height(560)
+        .padding(24)
+        .style(style::dashboard_modal)
+        .into()
+    }
+
+    fn rules_view(&self) -> Element<'_, Message> {
+        let mut rules = column![text("Highlight rules").size(12)].spacing(4);
+
+        for (idx, rule) in self.settings.rules.iter().enumerate() {
+            rules = rules.push(
+                row![
+                    text(rule.to_string()).size(12),
+                    space::horizontal(),
+                    button(text("Remove").size(11)).on_press(Message::RemoveRule(idx)),
+                ]
+                .align_y(Alignment::Center),
+            );
+        }
+
+        let threshold_valid =
+            self.draft.threshold.is_empty() || self.draft.threshold.trim().parse::<f32>().is_ok();
+
+        let draft = row![
+            pick_list(
+                Metric::ALL,
+                Some(self.draft.metric),
+                Message::RuleMetricChanged
+            )
+            .text_size(12),
+            pick_list(
+                Comparison::ALL,
+                Some(self.draft.comparison),
+                Message::RuleComparisonChanged
+            )
+            .text_size(12),
+            text_input("Threshold", &self.draft.threshold)
+                .on_input(Message::RuleThresholdChanged)
+                .on_submit(Message::AddRule)
+                .size(12)
+                .width(80)
+                .style(move |theme, status| {
+                    style::validated_text_input(theme, status, threshold_valid)
+                }),
+            button(text("Add").size(12)).on_press_maybe(
+                (threshold_valid && !self.draft.threshold.is_empty()).then_some(Message::AddRule)
+            ),
+        ]
+        .spacing(6)
+        .align_y(Alignment::Center);
+
+        container(rules.push(draft))
+            .width(Length::Fill)
+            .padding(6)
+            .style(style::modal_container)
+            .into()
+    }
+}
+
+fn metric_cell<'a>(metric: Metric, value: Option<f32>) -> Element<'a, Message> {
+    let Some(value) = value else {
+        return text("-").size(12).width(Length::FillPortion(1)).into();
+    };
+
+    let label = match metric {
+        Metric::Change1h | Metric::Change4h | Metric::Change24h => format!("{value:+.2}%"),
+        Metric::VolumeSpike => format!("{value:.2}x"),
+        Metric::Volatility => format!("{value:.2}%"),
+    };
+    let is_change = matches!(
+        metric,
+        Metric::Change1h | Metric::Change4h | Metric::Change24h
+    );
+
+    text(label)
+        .size(12)
+        .width(Length::FillPortion(1))
+        .style(move |theme: &Theme| {
+            let palette = theme.extended_palette();
+            text::Style {
+                color: is_change.then_some(if value >= 0.0 {
+                    palette.success.base.color
+                } else {
+                    palette.danger.base.color
+                }),
+            }
+        })
+        .into()
+}
@@ -112,6 +112,12 @@ pub fn exchange_icon(exchange: Exchange) -> Icon {
         }
         Exchange::HyperliquidLinear | Exchange::HyperliquidSpot => Icon::HyperliquidLogo,
         Exchange::OkexLinear | Exchange::OkexInverse | Exchange::OkexSpot => Icon::OkexLogo,
+        // فونت آیکون‌ها لوگوی گیت، کوین‌بیس و بیت‌گت را ندارد
+        Exchange::GateioLinear
+        | Exchange::GateioSpot
+        | Exchange::CoinbaseSpot
+        | Exchange::BitgetLinear
+        | Exchange::BitgetSpot => Icon::ChartOutline,
     }
 }
 
@@ -481,6 +487,31 @@ pub fn pane_background(theme: &Theme, is_focused: bool) -> Style {
     }
 }
 
+/// پوشش نیمه‌شفاف روی پنلی که داده‌هایش کهنه شده است
+pub fn stale_overlay(theme: &Theme) -> Style {
+    let palette = theme.extended_palette();
+
+    Style {
+        background: Some(palette.background.base.color.scale_alpha(0.6).into()),
+        ..Default::default()
+    }
+}
+
+/// نشان «کهنه» در نوار عنوان پنل
+pub fn stale_badge(theme: &Theme) -> Style {
+    let palette = theme.extended_palette();
+
+    Style {
+        text_color: Some(palette.danger.base.text),
+        background: Some(palette.danger.base.color.into()),
+        border: Border {
+            radius: 3.0.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
 // Modals
 // استایل‌های مربوط به مودال‌ها
 /// استایل مودال نمودار
@@ -660,6 +691,20 @@ pub fn ticker_card(theme: &Theme) -> Style {
     }
 }
 
+/// استایل نوار باریکی که هنگام جمع بودن سایدبار غیرثابت دیده می‌شود
+pub fn sidebar_handle(theme: &Theme) -> Style {
+    let palette = theme.extended_palette();
+
+    Style {
+        background: Some(palette.background.strong.color.scale_alpha(0.6).into()),
+        border: Border {
+            radius: 2.0.into(),
+            ..Border::default()
+        },
+        ..Default::default()
+    }
+}
+
 // the bar that lights up depending on the price change
 /// نواری که بر اساس تغییرات قیمت روشن می‌شود (در کارت نماد)
 pub fn ticker_card_bar(theme: &Theme, color_alpha: f32) -> Style {
@@ -760,6 +805,23 @@ pub fn split_ruler(theme: &Theme) -> iced::widget::rule::Style {
     }
 }
 
+/// رنگ ثابت هر گروه پیوند؛ برای نشان گروه روی پنل‌ها و نشانگر همگام‌شده
+pub fn link_group_color(group: data::layout::pane::LinkGroup) -> Color {
+    use data::layout::pane::LinkGroup;
+
+    match group {
+        LinkGroup::A => Color::from_rgb8(0xE5, 0x48, 0x4D), // قرمز
+        LinkGroup::B => Color::from_rgb8(0xF5, 0x9E, 0x0B), // نارنجی
+        LinkGroup::C => Color::from_rgb8(0xEA, 0xD3, 0x08), // زرد
+        LinkGroup::D => Color::from_rgb8(0x30, 0xA4, 0x6C), // سبز
+        LinkGroup::E => Color::from_rgb8(0x12, 0xA5, 0x94), // فیروزه‌ای
+        LinkGroup::F => Color::from_rgb8(0x3E, 0x8E, 0xD0), // آبی
+        LinkGroup::G => Color::from_rgb8(0x6E, 0x56, 0xCF), // بنفش
+        LinkGroup::H => Color::from_rgb8(0xD6, 0x40, 0x9F), // صورتی
+        LinkGroup::I => Color::from_rgb8(0x8D, 0x8D, 0x86), // خاکستری
+    }
+}
+
 // crosshair dashed line for charts
 /// خط‌چین برای نشانگر (Crosshair) در نمودارها
 pub fn dashed_line(theme: &'_ Theme) -> Stroke<'_> {
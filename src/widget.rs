@@ -17,6 +17,7 @@ pub mod color_picker;
 pub mod column_drag;
 pub mod decorate;
 pub mod multi_split;
+pub mod sparkline;
 pub mod toast;
 
 #[allow(dead_code)]
@@ -253,16 +254,27 @@ where
 {
     let is_active = link_group.is_some();
 
-    let icon = if let Some(group) = link_group {
-        text(group.to_string())
-            .font(style::AZERET_MONO)
-            .align_x(Alignment::Start)
-            .align_y(Alignment::Center)
+    let icon: Element<'a, Message> = if let Some(group) = link_group {
+        let color = style::link_group_color(group);
+
+        row![
+            container(space())
+                .width(6)
+                .height(6)
+                .style(move |theme| style::colored_circle_container(theme, color)),
+            text(group.to_string())
+                .font(style::AZERET_MONO)
+                .align_y(Alignment::Center),
+        ]
+        .spacing(3)
+        .align_y(Alignment::Center)
+        .into()
     } else {
         text("-")
             .font(style::AZERET_MONO)
             .align_x(Alignment::Start)
             .align_y(Alignment::Center)
+            .into()
     };
 
     button(icon)
@@ -270,7 +282,7 @@ where
             style::button::bordered_toggle(theme, status, is_active)
         })
         .on_press(on_press(id))
-        .width(28)
+        .width(32)
         .into()
 }
 
@@ -249,6 +249,24 @@ pub mod domain {
         })
     }
 
+    /// Close of the last bar at or before `x`; missing bars are skipped rather than interpolated
+    pub fn value_at_or_before(points: &[(u64, f32)], x: u64) -> Option<f32> {
+        let first = points.first()?;
+        let idx = points.partition_point(|(px, _)| *px <= x);
+
+        Some(if idx == 0 { first.1 } else { points[idx - 1].1 })
+    }
+
+    /// Reference value a series is rebased to 0% against: the anchor bar if one is picked,
+    /// otherwise the left edge of the visible window
+    pub fn base_value(points: &[(u64, f32)], min_x: u64, anchor: Option<u64>) -> Option<f32> {
+        match anchor {
+            Some(anchor) => value_at_or_before(points, anchor),
+            None => interpolate_y_at(points, min_x),
+        }
+        .filter(|&y0| y0 != 0.0)
+    }
+
     pub fn window(
         series: &[&[(u64, f32)]],
         zoom: super::Zoom,
@@ -313,7 +331,12 @@ pub mod domain {
         Some((left, right))
     }
 
-    pub fn pct_domain(series: &[&[(u64, f32)]], min_x: u64, max_x: u64) -> Option<(f32, f32)> {
+    pub fn pct_domain(
+        series: &[&[(u64, f32)]],
+        min_x: u64,
+        max_x: u64,
+        anchor: Option<u64>,
+    ) -> Option<(f32, f32)> {
         let mut min_pct = f32::INFINITY;
         let mut max_pct = f32::NEG_INFINITY;
         let mut any = false;
@@ -323,10 +346,9 @@ pub mod domain {
                 continue;
             }
 
-            let y0 = interpolate_y_at(pts, min_x).unwrap_or(0.0);
-            if y0 == 0.0 {
+            let Some(y0) = base_value(pts, min_x, anchor) else {
                 continue;
-            }
+            };
 
             let mut has_visible = false;
             for (_x, y) in pts.iter().filter(|(x, _)| *x >= min_x && *x <= max_x) {
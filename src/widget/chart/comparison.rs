@@ -38,6 +38,9 @@ const LEGEND_LINE_H: f32 = TEXT_SIZE + 6.0;
 const CHAR_W: f32 = TEXT_SIZE * 0.64;
 
 const ICON_BOX: f32 = TEXT_SIZE + 8.0;
+
+/// Horizontal tolerance for right-clicking an existing anchor to clear it
+const ANCHOR_HIT_PX: f32 = 6.0;
 const ICON_SPACING: f32 = 4.0;
 const ICON_GAP_AFTER_TEXT: f32 = 8.0;
 
@@ -48,6 +51,7 @@ pub enum LineComparisonEvent {
     SeriesCog(TickerInfo),
     SeriesRemove(TickerInfo),
     XAxisDoubleClick,
+    AnchorChanged(Option<u64>),
 }
 
 struct State {
@@ -94,6 +98,7 @@ pub struct LineComparison<'a, S> {
     timeframe: Timeframe,
     timezone: UserTimezone,
     version: u64,
+    anchor: Option<u64>,
 }
 
 impl<'a, S> LineComparison<'a, S>
@@ -109,6 +114,7 @@ where
             pan: 0.0,
             timezone: UserTimezone::Utc,
             version: 0,
+            anchor: None,
         }
     }
 
@@ -132,6 +138,12 @@ where
         self
     }
 
+    /// Rebase every series to 0% at this time instead of the left edge of the view
+    pub fn with_anchor(mut self, anchor: Option<u64>) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
     fn align_floor(ts: u64, dt: u64) -> u64 {
         if dt == 0 {
             return ts;
@@ -222,7 +234,7 @@ where
         let all_points: Vec<&[(u64, f32)]> = self.series.iter().map(|s| s.points()).collect();
 
         let (min_x, max_x) = domain::window(&all_points, self.zoom, pan_points, dt)?;
-        let (min_pct, max_pct) = domain::pct_domain(&all_points, min_x, max_x)?;
+        let (min_pct, max_pct) = domain::pct_domain(&all_points, min_x, max_x, self.anchor)?;
 
         Some(((min_x, max_x), (min_pct, max_pct)))
     }
@@ -433,8 +445,7 @@ where
             max_name_chars = max_name_chars.max(name_len);
 
             let pct_len = if include_pct_in_width {
-                domain::interpolate_y_at(s.points(), ctx.min_x)
-                    .filter(|&y0| y0 != 0.0)
+                domain::base_value(s.points(), ctx.min_x, self.anchor)
                     .and_then(|y0| {
                         cursor_x.and_then(|cx| {
                             domain::interpolate_y_at(s.points(), cx).map(|yc| {
@@ -607,11 +618,6 @@ where
             if pts.is_empty() {
                 continue;
             }
-            let global_base = pts[0].1;
-            if global_base == 0.0 {
-                continue;
-            }
-
             let last_vis = pts
                 .iter()
                 .rev()
@@ -621,26 +627,9 @@ where
                 None => continue,
             };
 
-            let idx_right = pts.iter().position(|(x, _)| *x >= ctx.min_x);
-            let y0 = match idx_right {
-                Some(0) => pts[0].1,
-                Some(i) => {
-                    let (x0, y0) = pts[i - 1];
-                    let (x2, y2) = pts[i];
-                    let dx = (x2.saturating_sub(x0)) as f32;
-                    if dx > 0.0 {
-                        let t = (ctx.min_x.saturating_sub(x0)) as f32 / dx;
-                        y0 + (y2 - y0) * t.clamp(0.0, 1.0)
-                    } else {
-                        y0
-                    }
-                }
-                None => continue,
-            };
-
-            if y0 == 0.0 {
+            let Some(y0) = domain::base_value(pts, ctx.min_x, self.anchor) else {
                 continue;
-            }
+            };
             let pct_label = ((y1 / y0) - 1.0) * 100.0;
 
             let mut py_local = ctx.map_y(pct_label);
@@ -673,40 +662,25 @@ where
     }
 
     fn format_crosshair_time(ts_ms: u64, tz: UserTimezone) -> String {
-        let ts_i64 = ts_ms as i64;
-        match tz {
-            UserTimezone::Utc => {
-                if let Some(dt) = chrono::Utc.timestamp_millis_opt(ts_i64).single() {
-                    dt.format("%a %b %-d %H:%M").to_string()
-                } else {
-                    ts_ms.to_string()
-                }
-            }
-            UserTimezone::Local => {
-                if let Some(dt) = chrono::Local.timestamp_millis_opt(ts_i64).single() {
-                    dt.format("%a %b %-d %H:%M").to_string()
-                } else {
-                    ts_ms.to_string()
-                }
-            }
+        if let Some(dt) = chrono::Utc.timestamp_millis_opt(ts_ms as i64).single() {
+            tz.to_fixed_offset(&dt)
+                .format("%a %b %-d %H:%M")
+                .to_string()
+        } else {
+            ts_ms.to_string()
         }
     }
 
     fn to_tz_ms(ts_ms: u64, tz: UserTimezone) -> u64 {
-        match tz {
-            UserTimezone::Utc => ts_ms,
-            UserTimezone::Local => {
-                if let Some(dt) = chrono::Local.timestamp_millis_opt(ts_ms as i64).single() {
-                    let off_ms = (dt.offset().local_minus_utc() as i64) * 1000;
-                    if off_ms >= 0 {
-                        ts_ms.saturating_add(off_ms as u64)
-                    } else {
-                        ts_ms.saturating_sub((-off_ms) as u64)
-                    }
-                } else {
-                    ts_ms
-                }
+        if let Some(dt) = chrono::Utc.timestamp_millis_opt(ts_ms as i64).single() {
+            let off_ms = i64::from(tz.to_fixed_offset(&dt).offset().local_minus_utc()) * 1000;
+            if off_ms >= 0 {
+                ts_ms.saturating_add(off_ms as u64)
+            } else {
+                ts_ms.saturating_sub((-off_ms) as u64)
             }
+        } else {
+            ts_ms
         }
     }
 }
@@ -877,6 +851,36 @@ where
                         state.is_panning = false;
                         state.last_cursor = None;
                     }
+                    mouse::Event::ButtonPressed(mouse::Button::Right) => {
+                        if !matches!(zone, HitZone::Plot) {
+                            return;
+                        }
+                        let Some(((min_x, max_x), _)) = self.compute_domains(self.pan) else {
+                            return;
+                        };
+
+                        let plot = regions.plot;
+                        let span_ms = max_x.saturating_sub(min_x).max(1) as f32;
+                        let px_per_ms = plot.width.max(1.0) / span_ms;
+
+                        // Right-clicking on the current anchor clears it
+                        let on_anchor = self.anchor.is_some_and(|anchor| {
+                            let anchor_px =
+                                plot.x + anchor.saturating_sub(min_x) as f32 * px_per_ms;
+                            (anchor_px - cursor_pos.x).abs() <= ANCHOR_HIT_PX
+                        });
+
+                        let anchor = if on_anchor {
+                            None
+                        } else {
+                            let ms_from_min = ((cursor_pos.x - plot.x).max(0.0) / px_per_ms) as u64;
+                            let dt = self.dt_ms_est().max(1);
+                            Some(Self::align_floor(min_x.saturating_add(ms_from_min), dt))
+                        };
+
+                        shell.publish(M::from(LineComparisonEvent::AnchorChanged(anchor)));
+                        state.clear_all_caches();
+                    }
                     mouse::Event::CursorMoved { .. } => {
                         if state.is_panning {
                             let prev = state.last_cursor.unwrap_or(cursor_pos);
@@ -986,6 +990,7 @@ where
             });
 
             let overlay_geom = state.overlay_cache.draw(r, bounds.size(), |frame| {
+                self.fill_anchor(frame, &scene.ctx, palette);
                 self.fill_overlay_y_labels(
                     frame,
                     &scene.end_labels,
@@ -1096,25 +1101,9 @@ where
             }
 
             let idx_right = pts.iter().position(|(x, _)| *x >= ctx.min_x);
-            let y0 = match idx_right {
-                Some(0) => pts[0].1,
-                Some(i) => {
-                    let (x0, y0_) = pts[i - 1];
-                    let (x1, y1_) = pts[i];
-                    let dx = (x1.saturating_sub(x0)) as f32;
-                    if dx > 0.0 {
-                        let t = (ctx.min_x.saturating_sub(x0)) as f32 / dx;
-                        y0_ + (y1_ - y0_) * t.clamp(0.0, 1.0)
-                    } else {
-                        y0_
-                    }
-                }
-                None => continue,
-            };
-
-            if y0 == 0.0 {
+            let Some(y0) = domain::base_value(pts, ctx.min_x, self.anchor) else {
                 continue;
-            }
+            };
 
             let mut builder = canvas::path::Builder::new();
 
@@ -1125,8 +1114,9 @@ where
             let mut prev_x: Option<u64> = None;
             match idx_right {
                 Some(ir) if ir > 0 => {
+                    let edge_y = domain::interpolate_y_at(pts, ctx.min_x).unwrap_or(y0);
                     let px0 = ctx.map_x(ctx.min_x);
-                    let py0 = ctx.map_y(0.0);
+                    let py0 = ctx.map_y(((edge_y / y0) - 1.0) * 100.0);
                     builder.move_to(Point::new(px0, py0));
                     prev_x = Some(ctx.min_x);
                 }
@@ -1369,16 +1359,14 @@ where
                 let pct_str = if hovering_legend {
                     None
                 } else {
-                    domain::interpolate_y_at(s.points(), ctx.min_x)
-                        .filter(|&y0| y0 != 0.0)
-                        .and_then(|y0| {
-                            cursor_x.and_then(|cx| {
-                                domain::interpolate_y_at(s.points(), cx).map(|yc| {
-                                    let pct = ((yc / y0) - 1.0) * 100.0;
-                                    super::format_pct(pct, step, true)
-                                })
+                    domain::base_value(s.points(), ctx.min_x, self.anchor).and_then(|y0| {
+                        cursor_x.and_then(|cx| {
+                            domain::interpolate_y_at(s.points(), cx).map(|yc| {
+                                let pct = ((yc / y0) - 1.0) * 100.0;
+                                super::format_pct(pct, step, true)
                             })
                         })
+                    })
                 };
 
                 let symbol_and_exchange = s.ticker_info().ticker.symbol_and_exchange_string();
@@ -1449,8 +1437,7 @@ where
             let pct_len = if hovering_legend {
                 0
             } else {
-                domain::interpolate_y_at(s.points(), ctx.min_x)
-                    .filter(|&y0| y0 != 0.0)
+                domain::base_value(s.points(), ctx.min_x, self.anchor)
                     .and_then(|y0| {
                         cursor_x.and_then(|cx| {
                             domain::interpolate_y_at(s.points(), cx).map(|yc| {
@@ -1502,16 +1489,14 @@ where
             let pct_str = if hovering_legend {
                 None
             } else {
-                domain::interpolate_y_at(s.points(), ctx.min_x)
-                    .filter(|&y0| y0 != 0.0)
-                    .and_then(|y0| {
-                        cursor_x.and_then(|cx| {
-                            domain::interpolate_y_at(s.points(), cx).map(|yc| {
-                                let pct = ((yc / y0) - 1.0) * 100.0;
-                                super::format_pct(pct, step, true)
-                            })
+                domain::base_value(s.points(), ctx.min_x, self.anchor).and_then(|y0| {
+                    cursor_x.and_then(|cx| {
+                        domain::interpolate_y_at(s.points(), cx).map(|yc| {
+                            let pct = ((yc / y0) - 1.0) * 100.0;
+                            super::format_pct(pct, step, true)
                         })
                     })
+                })
             };
 
             let symbol_and_exchange = s.ticker_info().ticker.symbol_and_exchange_string();
@@ -1536,6 +1521,28 @@ where
         }
     }
 
+    fn fill_anchor(&self, frame: &mut canvas::Frame, ctx: &PlotContext, palette: &Extended) {
+        let Some(anchor) = self.anchor else {
+            return;
+        };
+        if anchor < ctx.min_x || anchor > ctx.max_x {
+            return;
+        }
+
+        let plot_rect = ctx.plot_rect();
+        let x = plot_rect.x + ctx.map_x(anchor);
+
+        let mut b = canvas::path::Builder::new();
+        b.move_to(Point::new(x, plot_rect.y));
+        b.line_to(Point::new(x, plot_rect.y + plot_rect.height));
+        frame.stroke(
+            &b.build(),
+            canvas::Stroke::default()
+                .with_color(palette.primary.base.color.scale_alpha(0.6))
+                .with_width(1.0),
+        );
+    }
+
     fn fill_crosshair(&self, frame: &mut canvas::Frame, scene: &Scene, palette: &Extended) {
         let Some(ci) = scene.cursor else {
             return;
@@ -0,0 +1,67 @@
+use iced::widget::canvas::{self, Frame, Geometry, Path, Stroke};
+use iced::{Element, Length, Point, Rectangle, Renderer, Theme, mouse};
+
+/// A small line chart of recent values without axes, scaled to the min/max of the values
+pub struct Sparkline {
+    values: Vec<f32>,
+}
+
+pub fn sparkline<'a, Message: 'a>(
+    values: Vec<f32>,
+    width: f32,
+    height: f32,
+) -> Element<'a, Message> {
+    canvas::Canvas::new(Sparkline { values })
+        .width(Length::Fixed(width))
+        .height(Length::Fixed(height))
+        .into()
+}
+
+impl<Message> canvas::Program<Message> for Sparkline {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        if self.values.len() < 2 {
+            return vec![];
+        }
+
+        let (min, max) = self
+            .values
+            .iter()
+            .fold((f32::MAX, f32::MIN), |(lo, hi), v| (lo.min(*v), hi.max(*v)));
+        let range = (max - min).max(f32::EPSILON);
+        let step = bounds.width / (self.values.len() - 1) as f32;
+
+        let mut frame = Frame::new(renderer, bounds.size());
+
+        let line = Path::new(|builder| {
+            for (i, value) in self.values.iter().enumerate() {
+                let point = Point::new(
+                    i as f32 * step,
+                    bounds.height - 1.0 - (value - min) / range * (bounds.height - 2.0),
+                );
+                if i == 0 {
+                    builder.move_to(point);
+                } else {
+                    builder.line_to(point);
+                }
+            }
+        });
+
+        frame.stroke(
+            &line,
+            Stroke::default()
+                .with_color(theme.extended_palette().secondary.strong.color)
+                .with_width(1.0),
+        );
+
+        vec![frame.into_geometry()]
+    }
+}
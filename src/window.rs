@@ -29,6 +29,9 @@ pub fn default_size() -> Size {
 #[derive(Debug, Clone, Copy)]
 pub enum Event {
     CloseRequested(window::Id), // درخواست بستن پنجره
+    Focused,                    // یکی از پنجره‌ها فوکوس گرفت
+    Unfocused,                  // یکی از پنجره‌ها فوکوس را از دست داد
+    Reshaped,                   // جابجایی یا تغییر اندازه پنجره
 }
 
 /// گوش دادن به رویدادهای پنجره
@@ -46,6 +49,11 @@ fn filtered_events(
         iced::Event::Window(iced::window::Event::CloseRequested) => {
             Some(Event::CloseRequested(window))
         }
+        iced::Event::Window(iced::window::Event::Focused) => Some(Event::Focused),
+        iced::Event::Window(iced::window::Event::Unfocused) => Some(Event::Unfocused),
+        iced::Event::Window(iced::window::Event::Resized(_) | iced::window::Event::Moved(_)) => {
+            Some(Event::Reshaped)
+        }
         _ => None,
     }
 }